@@ -0,0 +1,11 @@
+-- Cached RetroAchievements game info + user unlock state per ROM
+CREATE TABLE IF NOT EXISTS achievements_cache (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    rom_id INTEGER NOT NULL UNIQUE REFERENCES roms(id) ON DELETE CASCADE,
+    ra_game_id TEXT NOT NULL,
+    ra_username TEXT NOT NULL,
+    data TEXT NOT NULL, -- JSON AchievementData
+    fetched_at TEXT NOT NULL DEFAULT (strftime('%Y-%m-%dT%H:%M:%fZ', 'now'))
+);
+
+CREATE INDEX IF NOT EXISTS idx_achievements_cache_rom_id ON achievements_cache(rom_id);
@@ -0,0 +1,2 @@
+-- Region of each artwork item (set for regional ScreenScraper media, NULL otherwise)
+ALTER TABLE artwork ADD COLUMN region TEXT;
@@ -0,0 +1,3 @@
+-- Archived platforms stay in the DB but are hidden from the library,
+-- enrichment and verification by default.
+ALTER TABLE platforms ADD COLUMN archived INTEGER NOT NULL DEFAULT 0;
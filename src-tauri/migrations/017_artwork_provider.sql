@@ -0,0 +1,4 @@
+-- Provider each artwork item came from (igdb, launchbox, screenscraper, libretro, romm)
+ALTER TABLE artwork ADD COLUMN provider TEXT;
+-- Perceptual hash (hex dHash) used to drop the same image served from different URLs
+ALTER TABLE artwork ADD COLUMN phash TEXT;
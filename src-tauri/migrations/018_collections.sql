@@ -0,0 +1,21 @@
+-- User collections. Rows with a source_id/remote_id are mirrored from that
+-- source (e.g. ROMM collections) and refreshed on every sync.
+CREATE TABLE IF NOT EXISTS collections (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    name TEXT NOT NULL,
+    description TEXT,
+    source_id INTEGER REFERENCES sources(id) ON DELETE CASCADE,
+    remote_id TEXT,
+    is_favorite INTEGER NOT NULL DEFAULT 0,
+    created_at TEXT NOT NULL DEFAULT (strftime('%Y-%m-%dT%H:%M:%fZ', 'now')),
+    updated_at TEXT NOT NULL DEFAULT (strftime('%Y-%m-%dT%H:%M:%fZ', 'now')),
+    UNIQUE(source_id, remote_id)
+);
+
+CREATE TABLE IF NOT EXISTS collection_roms (
+    collection_id INTEGER NOT NULL REFERENCES collections(id) ON DELETE CASCADE,
+    rom_id INTEGER NOT NULL REFERENCES roms(id) ON DELETE CASCADE,
+    PRIMARY KEY (collection_id, rom_id)
+);
+
+CREATE INDEX IF NOT EXISTS idx_collection_roms_rom ON collection_roms(rom_id);
@@ -0,0 +1,8 @@
+-- Non-secret preferences, kept in the DB so they travel with backups and
+-- settings exports. settings.json still holds paths and credentials and is
+-- refreshed from this table on startup.
+CREATE TABLE IF NOT EXISTS settings (
+    key TEXT PRIMARY KEY,
+    value TEXT NOT NULL, -- JSON
+    updated_at TEXT NOT NULL DEFAULT (strftime('%Y-%m-%dT%H:%M:%fZ', 'now'))
+);
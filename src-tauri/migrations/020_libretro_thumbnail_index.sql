@@ -0,0 +1,16 @@
+-- File listings of thumbnails.libretro.com, one per system/category, so
+-- enrichment can look thumbnails up locally instead of sending a HEAD each.
+CREATE TABLE IF NOT EXISTS libretro_thumbnail_dirs (
+    system TEXT NOT NULL,
+    category TEXT NOT NULL, -- Named_Boxarts, Named_Snaps, Named_Titles
+    entry_count INTEGER NOT NULL DEFAULT 0,
+    fetched_at TEXT NOT NULL DEFAULT (strftime('%Y-%m-%dT%H:%M:%fZ', 'now')),
+    PRIMARY KEY (system, category)
+);
+
+CREATE TABLE IF NOT EXISTS libretro_thumbnails (
+    system TEXT NOT NULL,
+    category TEXT NOT NULL,
+    name TEXT NOT NULL, -- file name without .png
+    PRIMARY KEY (system, category, name)
+) WITHOUT ROWID;
@@ -0,0 +1,4 @@
+-- Size and mtime (unix seconds) of a local ROM file when it was last hashed
+-- for verification. Unchanged files are not re-hashed by later runs.
+ALTER TABLE roms ADD COLUMN verified_file_size INTEGER;
+ALTER TABLE roms ADD COLUMN verified_file_mtime INTEGER;
@@ -0,0 +1,10 @@
+-- LaunchBox games matched to a ROM by fuzzy name similarity rather than an
+-- exact normalized name, so the user can review and reject them.
+CREATE TABLE IF NOT EXISTS launchbox_matches (
+    rom_id INTEGER PRIMARY KEY REFERENCES roms(id) ON DELETE CASCADE,
+    database_id TEXT NOT NULL,
+    matched_name TEXT NOT NULL,
+    confidence REAL NOT NULL, -- 0..1 trigram similarity
+    rejected INTEGER NOT NULL DEFAULT 0,
+    created_at TEXT NOT NULL DEFAULT (strftime('%Y-%m-%dT%H:%M:%fZ', 'now'))
+);
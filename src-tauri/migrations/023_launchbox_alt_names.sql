@@ -0,0 +1,12 @@
+-- Alternate (regional) titles from LaunchBox Metadata.xml GameAlternateName
+-- records, matched when a ROM's name isn't the game's primary title.
+CREATE TABLE IF NOT EXISTS launchbox_alt_names (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    database_id TEXT NOT NULL,
+    name TEXT NOT NULL,
+    name_normalized TEXT NOT NULL,
+    region TEXT
+);
+
+CREATE INDEX IF NOT EXISTS idx_lb_alt_names_name_norm ON launchbox_alt_names(name_normalized);
+CREATE INDEX IF NOT EXISTS idx_lb_alt_names_db_id ON launchbox_alt_names(database_id);
@@ -0,0 +1,10 @@
+-- Downloaded ROMs in the cache directory, stored as <hash_md5>/<file_name> so
+-- same-named files from different sources never collide and a cache shared
+-- between machines can be reused by content.
+CREATE TABLE IF NOT EXISTS rom_cache_index (
+    hash_md5 TEXT PRIMARY KEY,
+    file_name TEXT NOT NULL,
+    size INTEGER NOT NULL DEFAULT 0,
+    created_at TEXT NOT NULL DEFAULT (strftime('%Y-%m-%dT%H:%M:%fZ', 'now')),
+    last_used_at TEXT NOT NULL DEFAULT (strftime('%Y-%m-%dT%H:%M:%fZ', 'now'))
+);
@@ -0,0 +1,13 @@
+-- Progress of the last LaunchBox Metadata import (single row), so unchanged
+-- downloads are skipped and a cancelled import resumes where it stopped.
+CREATE TABLE IF NOT EXISTS launchbox_import_state (
+    id INTEGER PRIMARY KEY CHECK (id = 1),
+    etag TEXT,
+    last_modified TEXT,
+    content_md5 TEXT, -- of Metadata.zip
+    status TEXT NOT NULL DEFAULT 'importing', -- importing, complete
+    games_imported INTEGER NOT NULL DEFAULT 0,
+    images_imported INTEGER NOT NULL DEFAULT 0,
+    alt_names_imported INTEGER NOT NULL DEFAULT 0,
+    updated_at TEXT NOT NULL DEFAULT (strftime('%Y-%m-%dT%H:%M:%fZ', 'now'))
+);
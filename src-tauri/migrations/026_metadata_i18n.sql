@@ -0,0 +1,13 @@
+-- Descriptions in a language other than the one stored in metadata, such as a
+-- machine translation of a ScreenScraper synopsis that only exists in French.
+CREATE TABLE IF NOT EXISTS metadata_i18n (
+    rom_id INTEGER NOT NULL REFERENCES roms(id) ON DELETE CASCADE,
+    language TEXT NOT NULL,
+    description TEXT NOT NULL,
+    source_language TEXT,
+    source_md5 TEXT NOT NULL, -- of the description it was translated from
+    machine_translated INTEGER NOT NULL DEFAULT 1,
+    provider TEXT, -- deepl, libretranslate
+    created_at TEXT NOT NULL DEFAULT (strftime('%Y-%m-%dT%H:%M:%fZ', 'now')),
+    PRIMARY KEY (rom_id, language)
+);
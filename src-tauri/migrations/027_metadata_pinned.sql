@@ -0,0 +1,4 @@
+-- Pinned metadata is left alone by the stale-metadata refresh.
+ALTER TABLE metadata ADD COLUMN pinned INTEGER NOT NULL DEFAULT 0;
+
+CREATE INDEX IF NOT EXISTS idx_metadata_fetched_at ON metadata(metadata_fetched_at);
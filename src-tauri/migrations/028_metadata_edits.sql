@@ -0,0 +1,9 @@
+-- Metadata the user edited by hand. Enrichment and syncs write these values
+-- back after touching a ROM, so edits survive re-fetches.
+CREATE TABLE IF NOT EXISTS metadata_edits (
+    rom_id INTEGER NOT NULL REFERENCES roms(id) ON DELETE CASCADE,
+    field TEXT NOT NULL, -- name, description, genres, release_date, rating
+    value TEXT NOT NULL, -- JSON
+    edited_at TEXT NOT NULL DEFAULT (strftime('%Y-%m-%dT%H:%M:%fZ', 'now')),
+    PRIMARY KEY (rom_id, field)
+);
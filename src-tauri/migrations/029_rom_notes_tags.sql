@@ -0,0 +1,15 @@
+-- Personal notes and free-form tags, separate from genres and collections.
+CREATE TABLE IF NOT EXISTS rom_notes (
+    rom_id INTEGER PRIMARY KEY REFERENCES roms(id) ON DELETE CASCADE,
+    note TEXT NOT NULL,
+    updated_at TEXT NOT NULL DEFAULT (strftime('%Y-%m-%dT%H:%M:%fZ', 'now'))
+);
+
+CREATE TABLE IF NOT EXISTS rom_tags (
+    rom_id INTEGER NOT NULL REFERENCES roms(id) ON DELETE CASCADE,
+    tag TEXT NOT NULL COLLATE NOCASE,
+    created_at TEXT NOT NULL DEFAULT (strftime('%Y-%m-%dT%H:%M:%fZ', 'now')),
+    PRIMARY KEY (rom_id, tag)
+);
+
+CREATE INDEX IF NOT EXISTS idx_rom_tags_tag ON rom_tags(tag);
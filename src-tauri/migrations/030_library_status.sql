@@ -0,0 +1,3 @@
+-- Backlog status per ROM: unplayed, playing, beaten, completed, abandoned.
+-- NULL means never set. set_rom_status writes every library row of a ROM.
+ALTER TABLE library ADD COLUMN status TEXT;
@@ -0,0 +1,54 @@
+-- Profiles: favorites, play stats, completion status and local collections
+-- belong to a profile. The active one is the `current_profile` row of the
+-- settings table (profile 1 when unset).
+CREATE TABLE IF NOT EXISTS profiles (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    name TEXT NOT NULL UNIQUE COLLATE NOCASE,
+    created_at TEXT NOT NULL DEFAULT (strftime('%Y-%m-%dT%H:%M:%fZ', 'now'))
+);
+
+INSERT OR IGNORE INTO profiles (id, name) VALUES (1, 'Default');
+
+-- SQLite can't change a UNIQUE constraint, so rebuild library with the
+-- profile in it. Existing rows go to the default profile.
+CREATE TABLE library_new (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    profile_id INTEGER NOT NULL DEFAULT 1 REFERENCES profiles(id) ON DELETE CASCADE,
+    rom_id INTEGER NOT NULL REFERENCES roms(id) ON DELETE CASCADE,
+    source_id INTEGER NOT NULL REFERENCES sources(id) ON DELETE CASCADE,
+    file_path TEXT,
+    play_count INTEGER NOT NULL DEFAULT 0,
+    last_played_at TEXT,
+    favorite INTEGER NOT NULL DEFAULT 0,
+    status TEXT,
+    created_at TEXT NOT NULL DEFAULT (strftime('%Y-%m-%dT%H:%M:%fZ', 'now')),
+    updated_at TEXT NOT NULL DEFAULT (strftime('%Y-%m-%dT%H:%M:%fZ', 'now')),
+    UNIQUE(profile_id, rom_id, source_id)
+);
+
+INSERT INTO library_new (id, profile_id, rom_id, source_id, file_path, play_count,
+                         last_played_at, favorite, status, created_at, updated_at)
+SELECT id, 1, rom_id, source_id, file_path, play_count,
+       last_played_at, favorite, status, created_at, updated_at
+FROM library;
+DROP TABLE library;
+ALTER TABLE library_new RENAME TO library;
+
+CREATE INDEX IF NOT EXISTS idx_library_platform ON library(rom_id);
+CREATE INDEX IF NOT EXISTS idx_library_profile ON library(profile_id, rom_id);
+
+-- Collections mirrored from a source stay shared (NULL); local ones move to
+-- the default profile.
+ALTER TABLE collections ADD COLUMN profile_id INTEGER REFERENCES profiles(id) ON DELETE CASCADE;
+UPDATE collections SET profile_id = 1 WHERE source_id IS NULL;
+
+CREATE VIEW IF NOT EXISTS current_profile AS
+SELECT COALESCE(
+    (SELECT CAST(value AS INTEGER) FROM settings WHERE key = 'current_profile'
+       AND CAST(value AS INTEGER) IN (SELECT id FROM profiles)),
+    1
+) AS id;
+
+-- The current profile's library rows. Recreate after adding columns to library.
+CREATE VIEW IF NOT EXISTS profile_library AS
+SELECT * FROM library WHERE profile_id = (SELECT id FROM current_profile);
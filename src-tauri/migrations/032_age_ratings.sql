@@ -0,0 +1,10 @@
+-- Age ratings from IGDB and ScreenScraper. min_age is the youngest age the
+-- stricter of the two ratings allows; NULL when the game is unrated.
+ALTER TABLE metadata ADD COLUMN esrb_rating TEXT;
+ALTER TABLE metadata ADD COLUMN pegi_rating TEXT;
+ALTER TABLE metadata ADD COLUMN min_age INTEGER;
+
+-- Content filter per profile: hide games rated above max_age (NULL = no
+-- filter), and unrated games unless allow_unrated is set.
+ALTER TABLE profiles ADD COLUMN max_age INTEGER;
+ALTER TABLE profiles ADD COLUMN allow_unrated INTEGER NOT NULL DEFAULT 0;
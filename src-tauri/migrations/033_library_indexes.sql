@@ -0,0 +1,12 @@
+-- Indexes for the library joins. artwork(rom_id, art_type), library(rom_id)
+-- and source_roms(rom_id) are already covered by idx_artwork_rom_type_url,
+-- idx_library_platform and idx_source_roms_rom.
+
+-- Lets the cover join read every ROM's cover straight from the index.
+CREATE INDEX IF NOT EXISTS idx_artwork_type_rom ON artwork(art_type, rom_id, url);
+
+-- Covers the per-profile rollup of favorite, status and last played.
+CREATE INDEX IF NOT EXISTS idx_library_profile_state
+    ON library(profile_id, rom_id, favorite, status, last_played_at);
+
+ANALYZE;
@@ -0,0 +1,8 @@
+-- Hashes of a ROM with its copier/emulator header stripped (iNES, Lynx,
+-- Atari 7800, SNES/PC Engine copier headers). No-Intro DATs, Hasheous and
+-- RetroAchievements identify these consoles by the headerless data. Only
+-- filled for platforms whose dumps may carry a header; they equal the full
+-- hashes when the dump has none.
+ALTER TABLE roms ADD COLUMN headerless_crc32 TEXT;
+ALTER TABLE roms ADD COLUMN headerless_md5 TEXT;
+ALTER TABLE roms ADD COLUMN headerless_sha1 TEXT;
@@ -0,0 +1,16 @@
+-- Arcade (MAME/FBNeo) DATs list the ROMs a clone shares with its parent
+-- with a `merge` attribute; those files may or may not be in a clone's zip.
+ALTER TABLE dat_entries ADD COLUMN merge_name TEXT;
+
+CREATE INDEX IF NOT EXISTS idx_dat_entries_game ON dat_entries(game_name);
+
+-- Result of checking an arcade zip against its DAT set: the set's files not
+-- found in the zip and the zip's files that aren't part of the set.
+CREATE TABLE IF NOT EXISTS arcade_set_reports (
+    rom_id INTEGER PRIMARY KEY REFERENCES roms(id) ON DELETE CASCADE,
+    dat_file_id INTEGER NOT NULL REFERENCES dat_files(id) ON DELETE CASCADE,
+    set_name TEXT NOT NULL,
+    missing TEXT NOT NULL DEFAULT '[]', -- JSON array of ROM names
+    extra TEXT NOT NULL DEFAULT '[]', -- JSON array of file names in the zip
+    checked_at TEXT NOT NULL DEFAULT (strftime('%Y-%m-%dT%H:%M:%fZ', 'now'))
+);
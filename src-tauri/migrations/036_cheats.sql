@@ -0,0 +1,9 @@
+-- libretro cheat file downloaded for a ROM, and which of its cheats the
+-- user turned on. `enabled` is a JSON array of cheat indexes.
+CREATE TABLE IF NOT EXISTS rom_cheats (
+    rom_id INTEGER PRIMARY KEY REFERENCES roms(id) ON DELETE CASCADE,
+    source_url TEXT NOT NULL,
+    content TEXT NOT NULL,
+    enabled TEXT NOT NULL DEFAULT '[]',
+    fetched_at TEXT NOT NULL DEFAULT (strftime('%Y-%m-%dT%H:%M:%fZ', 'now'))
+);
@@ -0,0 +1,7 @@
+-- Hash of each save file as of its last cloud backup or restore, so the
+-- next sync can tell whether the local copy, the cloud copy, or both changed.
+CREATE TABLE IF NOT EXISTS cloud_save_sync (
+    remote_key TEXT PRIMARY KEY,
+    sha1 TEXT NOT NULL,
+    synced_at TEXT NOT NULL DEFAULT (strftime('%Y-%m-%dT%H:%M:%fZ', 'now'))
+);
@@ -0,0 +1,7 @@
+-- RetroArch per-game overrides set from romm-buddy, kept so they can be
+-- shown and edited again. `options` is a JSON RetroArchGameOverrides.
+CREATE TABLE IF NOT EXISTS retroarch_overrides (
+    rom_id INTEGER PRIMARY KEY REFERENCES roms(id) ON DELETE CASCADE,
+    options TEXT NOT NULL,
+    updated_at TEXT NOT NULL DEFAULT (strftime('%Y-%m-%dT%H:%M:%fZ', 'now'))
+);
@@ -0,0 +1,10 @@
+-- Game manual (PDF) cached for a ROM. `source` is 'file', 'url' or
+-- 'screenscraper'; `source_url` is where a downloaded manual came from.
+CREATE TABLE IF NOT EXISTS manuals (
+    rom_id INTEGER PRIMARY KEY REFERENCES roms(id) ON DELETE CASCADE,
+    source TEXT NOT NULL,
+    source_url TEXT,
+    path TEXT NOT NULL,
+    size_bytes INTEGER NOT NULL,
+    fetched_at TEXT NOT NULL DEFAULT (strftime('%Y-%m-%dT%H:%M:%fZ', 'now'))
+);
@@ -0,0 +1,23 @@
+-- Screenshots imported from emulators are stored as 'user_screenshot' artwork.
+-- SQLite can't change a CHECK constraint, so rebuild artwork with it allowed.
+CREATE TABLE artwork_new (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    rom_id INTEGER NOT NULL REFERENCES roms(id) ON DELETE CASCADE,
+    art_type TEXT NOT NULL CHECK (art_type IN ('cover', 'screenshot', 'fanart', 'banner', 'logo', 'user_screenshot')),
+    url TEXT,
+    local_path TEXT,
+    created_at TEXT NOT NULL DEFAULT (strftime('%Y-%m-%dT%H:%M:%fZ', 'now')),
+    region TEXT,
+    provider TEXT,
+    phash TEXT
+);
+
+INSERT INTO artwork_new (id, rom_id, art_type, url, local_path, created_at, region, provider, phash)
+SELECT id, rom_id, art_type, url, local_path, created_at, region, provider, phash
+FROM artwork;
+DROP TABLE artwork;
+ALTER TABLE artwork_new RENAME TO artwork;
+
+CREATE INDEX IF NOT EXISTS idx_artwork_rom ON artwork(rom_id);
+CREATE UNIQUE INDEX IF NOT EXISTS idx_artwork_rom_type_url ON artwork(rom_id, art_type, url);
+CREATE INDEX IF NOT EXISTS idx_artwork_type_rom ON artwork(art_type, rom_id, url);
@@ -0,0 +1,15 @@
+-- How long syncs, enrichment steps, downloads and hashing took. A batch
+-- step (e.g. 'enrich.igdb') is one row per run: `count` calls taking
+-- `duration_ms` in total.
+CREATE TABLE IF NOT EXISTS operation_log (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    operation TEXT NOT NULL,
+    detail TEXT,
+    started_at TEXT NOT NULL,
+    duration_ms INTEGER NOT NULL,
+    count INTEGER NOT NULL DEFAULT 1,
+    success INTEGER NOT NULL DEFAULT 1,
+    error TEXT
+);
+
+CREATE INDEX IF NOT EXISTS idx_operation_log_operation ON operation_log(operation, id);
@@ -0,0 +1,17 @@
+-- ROM downloads that failed verification: content not matching the ROM's
+-- known MD5, or fewer bytes than the server announced. Kept for the
+-- corrupted-files report; `attempt` counts from 1 within one download.
+CREATE TABLE IF NOT EXISTS corrupt_downloads (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    rom_id INTEGER NOT NULL REFERENCES roms(id) ON DELETE CASCADE,
+    file_name TEXT NOT NULL,
+    attempt INTEGER NOT NULL,
+    reason TEXT NOT NULL,
+    expected_md5 TEXT,
+    actual_md5 TEXT,
+    expected_size INTEGER,
+    actual_size INTEGER NOT NULL,
+    created_at TEXT NOT NULL DEFAULT (strftime('%Y-%m-%dT%H:%M:%fZ', 'now'))
+);
+
+CREATE INDEX IF NOT EXISTS idx_corrupt_downloads_rom ON corrupt_downloads(rom_id);
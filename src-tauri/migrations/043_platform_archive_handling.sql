@@ -0,0 +1,4 @@
+-- What to do with a platform's ROM files: 'keep' them as they come,
+-- 'extract' zip/7z downloads in the cache for emulators that can't open
+-- archives, or 'compress' local disc images to CHD/RVZ.
+ALTER TABLE platforms ADD COLUMN archive_handling TEXT NOT NULL DEFAULT 'keep';
@@ -0,0 +1,18 @@
+-- Feed of what's been happening for the home screen: games launched,
+-- achievements unlocked, ROMs added by a sync, collections changed. Rows of
+-- a profile's own activity carry its id; library-wide ones (syncs, shared
+-- collections) have none. Names are copied in so entries outlive the ROM.
+CREATE TABLE IF NOT EXISTS activity (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    kind TEXT NOT NULL,
+    profile_id INTEGER REFERENCES profiles(id) ON DELETE CASCADE,
+    rom_id INTEGER REFERENCES roms(id) ON DELETE SET NULL,
+    collection_id INTEGER REFERENCES collections(id) ON DELETE SET NULL,
+    source_id INTEGER REFERENCES sources(id) ON DELETE SET NULL,
+    title TEXT NOT NULL,
+    detail TEXT,
+    count INTEGER NOT NULL DEFAULT 1,
+    created_at TEXT NOT NULL DEFAULT (strftime('%Y-%m-%dT%H:%M:%fZ', 'now'))
+);
+
+CREATE INDEX IF NOT EXISTS idx_activity_profile ON activity(profile_id, id);
@@ -0,0 +1,5 @@
+-- What the file name says about the dump beyond its region and language:
+-- the revision ('1', 'A', '1.1') and GoodTools/TOSEC flags as a JSON array
+-- ('verified', 'bad', 'hack', 'translation', 'beta', ...).
+ALTER TABLE roms ADD COLUMN revision TEXT;
+ALTER TABLE roms ADD COLUMN dump_flags TEXT NOT NULL DEFAULT '[]';
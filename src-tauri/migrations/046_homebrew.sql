@@ -0,0 +1,32 @@
+-- ROMs marked by hand as homebrew or a ROM hack. Enrichment skips the
+-- commercial-game providers for them and only looks them up in the
+-- homebrew index; title, author and base game come from the matched entry.
+CREATE TABLE IF NOT EXISTS rom_homebrew (
+    rom_id INTEGER PRIMARY KEY REFERENCES roms(id) ON DELETE CASCADE,
+    kind TEXT NOT NULL,
+    title TEXT,
+    author TEXT,
+    base_game TEXT,
+    created_at TEXT NOT NULL DEFAULT (strftime('%Y-%m-%dT%H:%M:%fZ', 'now'))
+);
+
+-- Entries of the configured homebrew/hack JSON index, replaced on each update.
+CREATE TABLE IF NOT EXISTS homebrew_index (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    title TEXT NOT NULL,
+    name_normalized TEXT NOT NULL,
+    platform TEXT,
+    author TEXT,
+    base_game TEXT,
+    description TEXT,
+    release_date TEXT,
+    cover_url TEXT,
+    md5 TEXT,
+    sha1 TEXT,
+    crc32 TEXT
+);
+
+CREATE INDEX IF NOT EXISTS idx_homebrew_index_name ON homebrew_index(name_normalized);
+CREATE INDEX IF NOT EXISTS idx_homebrew_index_md5 ON homebrew_index(md5);
+CREATE INDEX IF NOT EXISTS idx_homebrew_index_sha1 ON homebrew_index(sha1);
+CREATE INDEX IF NOT EXISTS idx_homebrew_index_crc32 ON homebrew_index(crc32);
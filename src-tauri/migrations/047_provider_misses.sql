@@ -0,0 +1,11 @@
+-- Lookups a metadata provider had nothing for, so enrichment runs don't ask
+-- again until the miss is older than the provider's TTL. `lookup_key` is
+-- what was looked up: the hashes for Hasheous, the search name for IGDB,
+-- the thumbnail URL for libretro. ScreenScraper keeps its misses in
+-- screenscraper_cache.
+CREATE TABLE IF NOT EXISTS provider_misses (
+    provider TEXT NOT NULL,
+    lookup_key TEXT NOT NULL,
+    missed_at TEXT NOT NULL DEFAULT (strftime('%Y-%m-%dT%H:%M:%fZ', 'now')),
+    PRIMARY KEY (provider, lookup_key)
+);
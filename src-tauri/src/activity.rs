@@ -0,0 +1,186 @@
+use sea_orm::{ConnectionTrait, DatabaseBackend, DatabaseConnection, FromQueryResult, Statement};
+
+use crate::error::AppResult;
+use crate::models::{ActivityEntry, ActivityKind};
+
+/// Rows kept; older ones are dropped as new ones come in.
+const MAX_ROWS: i64 = 5_000;
+
+const DEFAULT_FEED_LIMIT: i64 = 50;
+
+impl ActivityKind {
+    fn as_str(self) -> &'static str {
+        match self {
+            Self::GameLaunched => "game_launched",
+            Self::AchievementUnlocked => "achievement_unlocked",
+            Self::RomsAdded => "roms_added",
+            Self::CollectionUpdated => "collection_updated",
+        }
+    }
+
+    fn parse(kind: &str) -> Option<Self> {
+        [
+            Self::GameLaunched,
+            Self::AchievementUnlocked,
+            Self::RomsAdded,
+            Self::CollectionUpdated,
+        ]
+        .into_iter()
+        .find(|k| k.as_str() == kind)
+    }
+}
+
+/// Run an `INSERT INTO activity ... SELECT` whose first value is the kind,
+/// then drop the oldest rows. Failing to record is only logged.
+async fn insert(
+    db: &DatabaseConnection,
+    kind: ActivityKind,
+    sql: &str,
+    values: Vec<sea_orm::Value>,
+) {
+    let mut all = vec![kind.as_str().into()];
+    all.extend(values);
+    let result = db
+        .execute(Statement::from_sql_and_values(
+            DatabaseBackend::Sqlite,
+            sql,
+            all,
+        ))
+        .await;
+    if let Err(e) = result {
+        log::warn!("Failed to record {} activity: {e}", kind.as_str());
+        return;
+    }
+    let _ = db
+        .execute(Statement::from_sql_and_values(
+            DatabaseBackend::Sqlite,
+            "DELETE FROM activity WHERE id <= (SELECT MAX(id) FROM activity) - ?",
+            [MAX_ROWS.into()],
+        ))
+        .await;
+}
+
+/// The current profile started a game.
+pub async fn game_launched(db: &DatabaseConnection, rom_id: i64) {
+    insert(
+        db,
+        ActivityKind::GameLaunched,
+        "INSERT INTO activity (kind, profile_id, rom_id, title, detail)
+         SELECT ?, (SELECT id FROM current_profile), r.id, r.name, p.name
+         FROM roms r JOIN platforms p ON p.id = r.platform_id
+         WHERE r.id = ?",
+        vec![rom_id.into()],
+    )
+    .await;
+}
+
+/// The current profile earned an achievement, `title`, in a game.
+pub async fn achievement_unlocked(db: &DatabaseConnection, rom_id: i64, title: &str) {
+    insert(
+        db,
+        ActivityKind::AchievementUnlocked,
+        "INSERT INTO activity (kind, profile_id, rom_id, title, detail)
+         SELECT ?, (SELECT id FROM current_profile), id, name, ?
+         FROM roms WHERE id = ?",
+        vec![title.into(), rom_id.into()],
+    )
+    .await;
+}
+
+/// A sync brought `count` new ROMs into the library.
+pub async fn roms_added(db: &DatabaseConnection, source_id: i64, count: usize) {
+    if count == 0 {
+        return;
+    }
+    insert(
+        db,
+        ActivityKind::RomsAdded,
+        "INSERT INTO activity (kind, source_id, title, count)
+         SELECT ?, id, name, ? FROM sources WHERE id = ?",
+        vec![
+            i64::try_from(count).unwrap_or(i64::MAX).into(),
+            source_id.into(),
+        ],
+    )
+    .await;
+}
+
+/// ROMs were added to or removed from a collection; `detail` says how. The
+/// entry belongs to the collection's profile, or to everyone for a shared one.
+pub async fn collection_updated(db: &DatabaseConnection, collection_id: i64, detail: &str) {
+    insert(
+        db,
+        ActivityKind::CollectionUpdated,
+        "INSERT INTO activity (kind, profile_id, collection_id, source_id, title, detail)
+         SELECT ?, profile_id, id, source_id, name, ? FROM collections WHERE id = ?",
+        vec![detail.into(), collection_id.into()],
+    )
+    .await;
+}
+
+/// "3 added, 1 removed" for a collection change.
+pub fn describe_change(added: usize, removed: usize) -> Option<String> {
+    match (added, removed) {
+        (0, 0) => None,
+        (added, 0) => Some(format!("{added} added")),
+        (0, removed) => Some(format!("{removed} removed")),
+        (added, removed) => Some(format!("{added} added, {removed} removed")),
+    }
+}
+
+/// The current profile's feed, newest first. `before` is the id of the last
+/// entry already shown, to load the page after it.
+pub async fn feed(
+    db: &DatabaseConnection,
+    limit: Option<i64>,
+    before: Option<i64>,
+) -> AppResult<Vec<ActivityEntry>> {
+    #[derive(Debug, FromQueryResult)]
+    struct Row {
+        id: i64,
+        kind: String,
+        rom_id: Option<i64>,
+        collection_id: Option<i64>,
+        source_id: Option<i64>,
+        title: String,
+        detail: Option<String>,
+        count: i64,
+        cover_url: Option<String>,
+        created_at: String,
+    }
+
+    let limit = limit.unwrap_or(DEFAULT_FEED_LIMIT).clamp(1, MAX_ROWS);
+    let rows = Row::find_by_statement(Statement::from_sql_and_values(
+        DatabaseBackend::Sqlite,
+        "SELECT a.id, a.kind, a.rom_id, a.collection_id, a.source_id, a.title, a.detail,
+                a.count, a.created_at,
+                (SELECT MIN(url) FROM artwork
+                 WHERE rom_id = a.rom_id AND art_type = 'cover') AS cover_url
+         FROM activity a
+         WHERE (a.profile_id IS NULL OR a.profile_id = (SELECT id FROM current_profile))
+           AND (? IS NULL OR a.id < ?)
+         ORDER BY a.id DESC
+         LIMIT ?",
+        [before.into(), before.into(), limit.into()],
+    ))
+    .all(db)
+    .await?;
+
+    Ok(rows
+        .into_iter()
+        .filter_map(|r| {
+            Some(ActivityEntry {
+                id: r.id,
+                kind: ActivityKind::parse(&r.kind)?,
+                rom_id: r.rom_id,
+                collection_id: r.collection_id,
+                source_id: r.source_id,
+                title: r.title,
+                detail: r.detail,
+                count: r.count,
+                cover_url: r.cover_url,
+                created_at: r.created_at,
+            })
+        })
+        .collect())
+}
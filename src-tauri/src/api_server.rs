@@ -0,0 +1,317 @@
+use std::path::Path;
+use std::sync::Arc;
+use std::time::Duration;
+
+use axum::body::Body;
+use axum::extract::{Path as UrlPath, Query, Request, State};
+use axum::http::{header, StatusCode};
+use axum::middleware::{self, Next};
+use axum::response::{IntoResponse, Redirect, Response};
+use axum::routing::get;
+use axum::{Json, Router};
+use sea_orm::{DatabaseBackend, DatabaseConnection, FromQueryResult, Statement};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use tokio::sync::oneshot;
+use tokio_util::io::ReaderStream;
+
+use crate::commands;
+use crate::error::{AppError, AppResult, ErrorCategory};
+use crate::models::{ApiServerConfig, LibraryPage, Platform};
+
+const DEFAULT_PAGE_SIZE: i64 = 100;
+const MAX_PAGE_SIZE: i64 = 500;
+
+/// The running server, if any, as Tauri state.
+#[derive(Default)]
+pub struct ApiServer(
+    tokio::sync::Mutex<Option<(oneshot::Sender<()>, tauri::async_runtime::JoinHandle<()>)>>,
+);
+
+#[derive(Clone)]
+struct ApiState {
+    db: DatabaseConnection,
+    /// SHA-256 of the token. Requests' tokens are hashed and compared with
+    /// it, so comparison time says nothing about the token.
+    token_hash: Arc<[u8; 32]>,
+}
+
+struct ApiError(StatusCode, String);
+
+impl IntoResponse for ApiError {
+    fn into_response(self) -> Response {
+        (self.0, Json(serde_json::json!({ "error": self.1 }))).into_response()
+    }
+}
+
+impl From<AppError> for ApiError {
+    fn from(e: AppError) -> Self {
+        let status = match e.category() {
+            ErrorCategory::NotFound => StatusCode::NOT_FOUND,
+            _ => {
+                log::warn!("API request failed: {e}");
+                StatusCode::INTERNAL_SERVER_ERROR
+            }
+        };
+        Self(status, e.to_string())
+    }
+}
+
+impl From<std::io::Error> for ApiError {
+    fn from(e: std::io::Error) -> Self {
+        AppError::from(e).into()
+    }
+}
+
+fn not_found(what: &str) -> ApiError {
+    ApiError(StatusCode::NOT_FOUND, format!("{what} not found"))
+}
+
+fn token_hash(token: &str) -> [u8; 32] {
+    Sha256::digest(token.as_bytes()).into()
+}
+
+/// Fail with 404 for ROMs the library doesn't list: on archived platforms or
+/// hidden by the current profile's content filter.
+async fn require_visible(db: &DatabaseConnection, rom_id: i64) -> Result<(), ApiError> {
+    if commands::rom_visible(db, rom_id).await? {
+        Ok(())
+    } else {
+        Err(not_found("ROM"))
+    }
+}
+
+/// Requests carry the token as `Authorization: Bearer <token>`, or as
+/// `?token=` where headers can't be set (`<img>` tags, download links).
+async fn require_token(State(state): State<ApiState>, req: Request, next: Next) -> Response {
+    let bearer = req
+        .headers()
+        .get(header::AUTHORIZATION)
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| v.strip_prefix("Bearer "))
+        .map(str::to_string);
+    let query = req.uri().query().and_then(|q| {
+        q.split('&')
+            .filter_map(|pair| pair.split_once('='))
+            .find(|(key, _)| *key == "token")
+            .and_then(|(_, value)| urlencoding::decode(value).ok())
+            .map(|v| v.into_owned())
+    });
+    let given = bearer.or(query);
+    if given.is_some_and(|t| token_hash(&t) == *state.token_hash) {
+        next.run(req).await
+    } else {
+        ApiError(
+            StatusCode::UNAUTHORIZED,
+            "Missing or wrong token".to_string(),
+        )
+        .into_response()
+    }
+}
+
+async fn platforms(State(state): State<ApiState>) -> Result<Json<Vec<Platform>>, ApiError> {
+    let platforms = commands::list_platforms(&state.db).await?;
+    Ok(Json(
+        platforms.into_iter().filter(|p| !p.archived).collect(),
+    ))
+}
+
+#[derive(Deserialize)]
+struct RomsQuery {
+    platform_id: Option<i64>,
+    search: Option<String>,
+    #[serde(default)]
+    favorites_only: bool,
+    language: Option<String>,
+    #[serde(default)]
+    offset: i64,
+    limit: Option<i64>,
+}
+
+async fn roms(
+    State(state): State<ApiState>,
+    Query(q): Query<RomsQuery>,
+) -> Result<Json<LibraryPage>, ApiError> {
+    let page = commands::query_library_page(
+        &state.db,
+        q.platform_id,
+        q.search.as_deref(),
+        q.favorites_only,
+        false,
+        &[],
+        None,
+        q.language.as_deref(),
+        q.offset.max(0),
+        q.limit.unwrap_or(DEFAULT_PAGE_SIZE).clamp(1, MAX_PAGE_SIZE),
+    )
+    .await?;
+    Ok(Json(page))
+}
+
+#[derive(Debug, Serialize, FromQueryResult)]
+struct ArtworkItem {
+    id: i64,
+    art_type: String,
+}
+
+async fn rom_artwork(
+    State(state): State<ApiState>,
+    UrlPath(rom_id): UrlPath<i64>,
+) -> Result<Json<Vec<ArtworkItem>>, ApiError> {
+    require_visible(&state.db, rom_id).await?;
+    let items = ArtworkItem::find_by_statement(Statement::from_sql_and_values(
+        DatabaseBackend::Sqlite,
+        "SELECT id, art_type FROM artwork WHERE rom_id = ? ORDER BY art_type, id",
+        [rom_id.into()],
+    ))
+    .all(&state.db)
+    .await
+    .map_err(AppError::from)?;
+    Ok(Json(items))
+}
+
+fn content_type(path: &Path) -> &'static str {
+    match path
+        .extension()
+        .and_then(|e| e.to_str())
+        .map(str::to_lowercase)
+        .as_deref()
+    {
+        Some("png") => "image/png",
+        Some("jpg" | "jpeg") => "image/jpeg",
+        Some("webp") => "image/webp",
+        Some("gif") => "image/gif",
+        Some("bmp") => "image/bmp",
+        _ => "application/octet-stream",
+    }
+}
+
+/// Stream a file, as an attachment when `download` is set.
+async fn serve_file(path: &Path, download: bool) -> Result<Response, ApiError> {
+    let file = tokio::fs::File::open(path).await?;
+    let len = file.metadata().await?.len();
+    let mut response = Response::builder()
+        .header(header::CONTENT_TYPE, content_type(path))
+        .header(header::CONTENT_LENGTH, len);
+    if download {
+        let name = path
+            .file_name()
+            .map(|n| n.to_string_lossy().replace('"', ""))
+            .unwrap_or_default();
+        response = response.header(
+            header::CONTENT_DISPOSITION,
+            format!("attachment; filename=\"{name}\""),
+        );
+    }
+    response
+        .body(Body::from_stream(ReaderStream::new(file)))
+        .map_err(|e| ApiError(StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))
+}
+
+/// An artwork image: the cached file when there is one, otherwise a
+/// redirect to where it was downloaded from.
+async fn artwork(
+    State(state): State<ApiState>,
+    UrlPath(id): UrlPath<i64>,
+) -> Result<Response, ApiError> {
+    #[derive(Debug, FromQueryResult)]
+    struct ArtRow {
+        rom_id: i64,
+        url: Option<String>,
+        local_path: Option<String>,
+    }
+
+    let row = ArtRow::find_by_statement(Statement::from_sql_and_values(
+        DatabaseBackend::Sqlite,
+        "SELECT rom_id, url, local_path FROM artwork WHERE id = ?",
+        [id.into()],
+    ))
+    .one(&state.db)
+    .await
+    .map_err(AppError::from)?
+    .ok_or_else(|| not_found("Artwork"))?;
+    require_visible(&state.db, row.rom_id).await?;
+
+    if let Some(path) = row
+        .local_path
+        .as_deref()
+        .map(Path::new)
+        .filter(|p| p.is_file())
+    {
+        return serve_file(path, false).await;
+    }
+    match row.url {
+        Some(url) if url.starts_with("http://") || url.starts_with("https://") => {
+            Ok(Redirect::temporary(&url).into_response())
+        }
+        _ => Err(not_found("Artwork file")),
+    }
+}
+
+/// Download a ROM that's on this machine: a local source file or a
+/// cached download.
+async fn rom_file(
+    State(state): State<ApiState>,
+    UrlPath(rom_id): UrlPath<i64>,
+) -> Result<Response, ApiError> {
+    require_visible(&state.db, rom_id).await?;
+    let path = commands::local_rom_path(&state.db, rom_id)
+        .await?
+        .ok_or_else(|| not_found("ROM file"))?;
+    serve_file(&path, true).await
+}
+
+/// Start serving the read-only library API on every interface at
+/// `config.port`, replacing a server already running.
+pub async fn start(
+    server: &ApiServer,
+    db: DatabaseConnection,
+    config: &ApiServerConfig,
+) -> AppResult<()> {
+    stop(server).await;
+    if config.token.is_empty() {
+        return Err(AppError::Other("The API server needs a token".to_string()));
+    }
+
+    let state = ApiState {
+        db,
+        token_hash: Arc::new(token_hash(&config.token)),
+    };
+    let app = Router::new()
+        .route("/platforms", get(platforms))
+        .route("/roms", get(roms))
+        .route("/roms/{id}/artwork", get(rom_artwork))
+        .route("/roms/{id}/file", get(rom_file))
+        .route("/artwork/{id}", get(artwork))
+        .layer(middleware::from_fn_with_state(state.clone(), require_token))
+        .with_state(state);
+
+    let listener = tokio::net::TcpListener::bind(("0.0.0.0", config.port))
+        .await
+        .map_err(|e| AppError::Other(format!("Can't listen on port {}: {e}", config.port)))?;
+    let (tx, rx) = oneshot::channel::<()>();
+    let port = config.port;
+    let handle = tauri::async_runtime::spawn(async move {
+        let shutdown = async {
+            let _ = rx.await;
+        };
+        if let Err(e) = axum::serve(listener, app)
+            .with_graceful_shutdown(shutdown)
+            .await
+        {
+            log::warn!("API server stopped: {e}");
+        }
+    });
+    *server.0.lock().await = Some((tx, handle));
+    log::info!("API server listening on port {port}");
+    Ok(())
+}
+
+/// Stop the server, waiting briefly so its port is free to bind again.
+/// Downloads still in progress are left to finish.
+pub async fn stop(server: &ApiServer) {
+    if let Some((tx, handle)) = server.0.lock().await.take() {
+        let _ = tx.send(());
+        let _ = tokio::time::timeout(Duration::from_secs(2), handle).await;
+    }
+}
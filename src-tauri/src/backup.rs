@@ -0,0 +1,134 @@
+use std::path::{Path, PathBuf};
+
+use sea_orm::{ConnectionTrait, DatabaseBackend, DatabaseConnection, Statement};
+use tauri::{Emitter, Manager};
+use tauri_plugin_store::StoreExt;
+
+use crate::error::AppResult;
+use crate::models::{DatabaseBackup, IntegrityReport};
+
+/// Emitted at startup when the integrity check finds problems.
+pub const DB_INTEGRITY_EVENT: &str = "database://integrity-failed";
+
+/// Backups kept when the setting is unset.
+pub const DEFAULT_BACKUP_COUNT: u32 = 5;
+
+const BACKUP_PREFIX: &str = "romm-buddy-";
+const BACKUP_EXTENSION: &str = "db";
+
+/// Settings key: how many backups to keep; 0 turns off the startup backup.
+pub const BACKUP_COUNT_KEY: &str = "database_backup_count";
+
+pub fn read_backup_count(app: &tauri::AppHandle) -> u32 {
+    app.store("settings.json")
+        .ok()
+        .and_then(|store| store.get(BACKUP_COUNT_KEY))
+        .and_then(|v| serde_json::from_value(v).ok())
+        .unwrap_or(DEFAULT_BACKUP_COUNT)
+}
+
+/// Where database backups are written.
+pub fn backup_dir() -> PathBuf {
+    directories::ProjectDirs::from("com", "romm-buddy", "romm-buddy").map_or_else(
+        || PathBuf::from("backups"),
+        |p| p.data_dir().join("backups"),
+    )
+}
+
+fn is_backup(path: &Path) -> bool {
+    path.extension().is_some_and(|e| e == BACKUP_EXTENSION)
+        && path
+            .file_name()
+            .and_then(|n| n.to_str())
+            .is_some_and(|n| n.starts_with(BACKUP_PREFIX))
+}
+
+/// Backups in `dir`, newest first. Names sort by their timestamp.
+fn list_backups(dir: &Path) -> Vec<PathBuf> {
+    let mut backups: Vec<PathBuf> = std::fs::read_dir(dir)
+        .into_iter()
+        .flatten()
+        .filter_map(Result::ok)
+        .map(|e| e.path())
+        .filter(|p| is_backup(p))
+        .collect();
+    backups.sort_unstable_by(|a, b| b.cmp(a));
+    backups
+}
+
+/// Copy the live database into `dir` with `VACUUM INTO`, which writes a
+/// consistent snapshot while other connections keep working, then delete
+/// all but the newest `keep` backups.
+pub async fn backup(db: &DatabaseConnection, dir: &Path, keep: u32) -> AppResult<DatabaseBackup> {
+    tokio::fs::create_dir_all(dir).await?;
+    let now = chrono::Utc::now();
+    let path = dir.join(format!(
+        "{BACKUP_PREFIX}{}.{BACKUP_EXTENSION}",
+        now.format("%Y%m%d-%H%M%S")
+    ));
+    if path.exists() {
+        tokio::fs::remove_file(&path).await?;
+    }
+
+    db.execute(Statement::from_sql_and_values(
+        DatabaseBackend::Sqlite,
+        "VACUUM INTO ?",
+        [path.to_string_lossy().to_string().into()],
+    ))
+    .await?;
+
+    for stale in list_backups(dir)
+        .into_iter()
+        .skip(usize::try_from(keep.max(1)).unwrap_or(usize::MAX))
+    {
+        if let Err(e) = tokio::fs::remove_file(&stale).await {
+            log::warn!("Failed to remove old backup {}: {e}", stale.display());
+        }
+    }
+
+    let size_bytes = tokio::fs::metadata(&path).await?.len();
+    Ok(DatabaseBackup {
+        path: path.to_string_lossy().to_string(),
+        size_bytes,
+        created_at: now.format("%Y-%m-%dT%H:%M:%S%.3fZ").to_string(),
+    })
+}
+
+/// Run `PRAGMA integrity_check`. A healthy database reports a single "ok".
+pub async fn integrity_check(db: &DatabaseConnection) -> AppResult<IntegrityReport> {
+    let rows = db
+        .query_all(Statement::from_string(
+            DatabaseBackend::Sqlite,
+            "PRAGMA integrity_check",
+        ))
+        .await?;
+    let problems: Vec<String> = rows
+        .iter()
+        .map(|row| row.try_get_by_index::<String>(0))
+        .collect::<Result<_, _>>()?;
+    let problems: Vec<String> = problems.into_iter().filter(|p| p != "ok").collect();
+    Ok(IntegrityReport {
+        ok: problems.is_empty(),
+        problems,
+    })
+}
+
+/// Startup: check the database and take the rotating backup. A database that
+/// fails the check isn't backed up, so corruption can't push out good copies.
+pub async fn on_startup(app: &tauri::AppHandle) -> AppResult<()> {
+    let db = app.state::<DatabaseConnection>();
+    let report = integrity_check(db.inner()).await?;
+    if !report.ok {
+        log::error!("Database integrity check failed: {:?}", report.problems);
+        let _ = app.emit(DB_INTEGRITY_EVENT, &report);
+        return Ok(());
+    }
+
+    let keep = read_backup_count(app);
+    if keep == 0 {
+        return Ok(());
+    }
+    let copy = backup(db.inner(), &backup_dir(), keep).await?;
+    log::info!("Backed up the database to {}", copy.path);
+    Ok(())
+}
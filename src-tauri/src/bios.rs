@@ -0,0 +1,524 @@
+use std::collections::{BTreeMap, HashMap};
+use std::io::Read;
+use std::path::{Path, PathBuf};
+
+use md5::{Digest, Md5};
+use sha1::Sha1;
+
+use crate::models::{BiosFileStatus, BiosHint, BiosReadiness, BiosStatus};
+
+/// A firmware file a platform's emulators expect in the system directory.
+pub struct BiosDef {
+    pub platform: &'static str,
+    /// Path relative to the system directory (some cores use subfolders).
+    pub file_name: &'static str,
+    pub description: &'static str,
+    pub md5: Option<&'static str>,
+    pub sha1: Option<&'static str>,
+    pub required: bool,
+    /// Files sharing a group are alternatives (e.g. regional BIOS); any one of
+    /// them satisfies the requirement.
+    pub group: Option<&'static str>,
+}
+
+/// Known BIOS files, keyed by canonical platform slug. Hashes follow the
+/// libretro documentation for the recommended cores.
+pub const BIOS_FILES: &[BiosDef] = &[
+    // ── Nintendo ──
+    BiosDef {
+        platform: "fds",
+        file_name: "disksys.rom",
+        description: "Famicom Disk System BIOS",
+        md5: Some("ca30b50f880eb660a320674ed365ef7a"),
+        sha1: Some("57fe1bdee955bb48d357e463ccbf129496930b62"),
+        required: true,
+        group: None,
+    },
+    BiosDef {
+        platform: "gb",
+        file_name: "gb_bios.bin",
+        description: "Game Boy boot ROM",
+        md5: Some("32fbbd84168d3482956eb3c5051637f5"),
+        sha1: None,
+        required: false,
+        group: None,
+    },
+    BiosDef {
+        platform: "gbc",
+        file_name: "gbc_bios.bin",
+        description: "Game Boy Color boot ROM",
+        md5: Some("dbfce9db9deaa2567f6a84fde55f9680"),
+        sha1: None,
+        required: false,
+        group: None,
+    },
+    BiosDef {
+        platform: "gba",
+        file_name: "gba_bios.bin",
+        description: "Game Boy Advance BIOS",
+        md5: Some("a860e8c0b6d573d191e4ec7db1b1e4f6"),
+        sha1: Some("300c20df6731a33952ded8c436f7f186d25d3492"),
+        required: false,
+        group: None,
+    },
+    BiosDef {
+        platform: "nds",
+        file_name: "bios7.bin",
+        description: "Nintendo DS ARM7 BIOS",
+        md5: Some("df692a80a5b1bc90728bc3dfc76cd948"),
+        sha1: None,
+        required: false,
+        group: None,
+    },
+    BiosDef {
+        platform: "nds",
+        file_name: "bios9.bin",
+        description: "Nintendo DS ARM9 BIOS",
+        md5: Some("a392174eb3e572fed6447e956bde4b25"),
+        sha1: None,
+        required: false,
+        group: None,
+    },
+    BiosDef {
+        platform: "nds",
+        // Firmware dumps embed user settings, so there is no canonical hash
+        file_name: "firmware.bin",
+        description: "Nintendo DS firmware",
+        md5: None,
+        sha1: None,
+        required: false,
+        group: None,
+    },
+    BiosDef {
+        platform: "pokemini",
+        file_name: "bios.min",
+        description: "Pokémon Mini BIOS",
+        md5: Some("1e4fb124a3a886865acb574f388c803d"),
+        sha1: None,
+        required: false,
+        group: None,
+    },
+    // ── Sony ──
+    BiosDef {
+        platform: "psx",
+        file_name: "scph5500.bin",
+        description: "PlayStation BIOS (JP)",
+        md5: Some("8dd7d5296a650fac7319bce665a6a53c"),
+        sha1: Some("b05def971d8ec59f346f2d9ac21fb742e3eb6917"),
+        required: true,
+        group: Some("psx"),
+    },
+    BiosDef {
+        platform: "psx",
+        file_name: "scph5501.bin",
+        description: "PlayStation BIOS (US)",
+        md5: Some("490f666e1afb15b7362b406ed1cea246"),
+        sha1: Some("0555c6fae8906f3f09baf5988f00e55f88e9f30b"),
+        required: true,
+        group: Some("psx"),
+    },
+    BiosDef {
+        platform: "psx",
+        file_name: "scph5502.bin",
+        description: "PlayStation BIOS (EU)",
+        md5: Some("32736f17079d0b2b7024407c39bd3050"),
+        sha1: Some("f6bc2d1f5eb6593de7d089c425ac681d6fffd3f0"),
+        required: true,
+        group: Some("psx"),
+    },
+    // ── Sega ──
+    BiosDef {
+        platform: "segacd",
+        file_name: "bios_CD_U.bin",
+        description: "Sega CD BIOS (US)",
+        md5: Some("2efd74e3232ff260e371b99f84024f7f"),
+        sha1: None,
+        required: true,
+        group: Some("segacd"),
+    },
+    BiosDef {
+        platform: "segacd",
+        file_name: "bios_CD_E.bin",
+        description: "Mega-CD BIOS (EU)",
+        md5: Some("e66fa1dc5820d254611fdcdba0662372"),
+        sha1: None,
+        required: true,
+        group: Some("segacd"),
+    },
+    BiosDef {
+        platform: "segacd",
+        file_name: "bios_CD_J.bin",
+        description: "Mega-CD BIOS (JP)",
+        md5: Some("278a9397d192149e84e820ac621a8edd"),
+        sha1: None,
+        required: true,
+        group: Some("segacd"),
+    },
+    BiosDef {
+        platform: "saturn",
+        file_name: "sega_101.bin",
+        description: "Saturn BIOS (JP)",
+        md5: Some("85ec9ca47d8f6807718151cbcca8b964"),
+        sha1: None,
+        required: true,
+        group: Some("saturn"),
+    },
+    BiosDef {
+        platform: "saturn",
+        file_name: "mpr-17933.bin",
+        description: "Saturn BIOS (US/EU)",
+        md5: Some("3240872c70984b6cbfda1586cab68dbe"),
+        sha1: None,
+        required: true,
+        group: Some("saturn"),
+    },
+    BiosDef {
+        platform: "dreamcast",
+        file_name: "dc/dc_boot.bin",
+        description: "Dreamcast BIOS",
+        md5: Some("e10c53c2f8b90bab96ead2d368858623"),
+        sha1: None,
+        required: true,
+        group: None,
+    },
+    BiosDef {
+        platform: "dreamcast",
+        file_name: "dc/dc_flash.bin",
+        description: "Dreamcast flash memory",
+        md5: Some("0a93f7940c455905bea6e392dfde92a4"),
+        sha1: None,
+        required: false,
+        group: None,
+    },
+    // ── SNK ──
+    BiosDef {
+        platform: "neogeo",
+        // Romset versions differ, so the archive can't be checked by hash
+        file_name: "neogeo.zip",
+        description: "Neo Geo BIOS set",
+        md5: None,
+        sha1: None,
+        required: true,
+        group: None,
+    },
+    // ── NEC ──
+    BiosDef {
+        platform: "pcecd",
+        file_name: "syscard3.pce",
+        description: "PC Engine CD System Card 3",
+        md5: Some("38179df8f4ac870017db21ebcbf53114"),
+        sha1: Some("79f5ff55dd10187c7fd7b8daab0b3ffbd1f56a2c"),
+        required: true,
+        group: None,
+    },
+    BiosDef {
+        platform: "pcfx",
+        file_name: "pcfx.rom",
+        description: "PC-FX BIOS",
+        md5: Some("08e36edbea28a017f79f8d4f7ff9b6d7"),
+        sha1: None,
+        required: true,
+        group: None,
+    },
+    // ── Atari ──
+    BiosDef {
+        platform: "atari5200",
+        file_name: "5200.rom",
+        description: "Atari 5200 BIOS",
+        md5: Some("281f20ea4320404ec820fb7ec0693b38"),
+        sha1: None,
+        required: true,
+        group: None,
+    },
+    BiosDef {
+        platform: "atari7800",
+        file_name: "7800 BIOS (U).rom",
+        description: "Atari 7800 BIOS",
+        md5: Some("0763f1ffb006ddbe32e52d497ee848ae"),
+        sha1: None,
+        required: false,
+        group: None,
+    },
+    BiosDef {
+        platform: "lynx",
+        file_name: "lynxboot.img",
+        description: "Atari Lynx boot ROM",
+        md5: Some("fcd403db69f54290b51035d82f835e7b"),
+        sha1: None,
+        required: true,
+        group: None,
+    },
+    // ── Other consoles ──
+    BiosDef {
+        platform: "3do",
+        file_name: "panafz10.bin",
+        description: "Panasonic FZ-10 BIOS",
+        md5: Some("51f2f43ae2f3508a14d9f56597e2d3ce"),
+        sha1: None,
+        required: true,
+        group: None,
+    },
+    BiosDef {
+        platform: "colecovision",
+        file_name: "colecovision.rom",
+        description: "ColecoVision BIOS",
+        md5: Some("2c66f5911e5b42b8ebe113403548eee7"),
+        sha1: None,
+        required: true,
+        group: None,
+    },
+    BiosDef {
+        platform: "intellivision",
+        file_name: "exec.bin",
+        description: "Intellivision Executive ROM",
+        md5: Some("62e761035cb657903761800f4437b8af"),
+        sha1: None,
+        required: true,
+        group: None,
+    },
+    BiosDef {
+        platform: "intellivision",
+        file_name: "grom.bin",
+        description: "Intellivision Graphics ROM",
+        md5: Some("0cd5946c6473e42e8e4c2137785e427f"),
+        sha1: None,
+        required: true,
+        group: None,
+    },
+    BiosDef {
+        platform: "channelf",
+        file_name: "sl31253.bin",
+        description: "Channel F BIOS (PSU 1)",
+        md5: Some("ac9804d4c0e9d07e33472e3726ed15c3"),
+        sha1: None,
+        required: true,
+        group: None,
+    },
+    BiosDef {
+        platform: "channelf",
+        file_name: "sl31254.bin",
+        description: "Channel F BIOS (PSU 2)",
+        md5: Some("da98f4bb3242ab80d76629021bb27585"),
+        sha1: None,
+        required: true,
+        group: None,
+    },
+    BiosDef {
+        platform: "odyssey2",
+        file_name: "o2rom.bin",
+        description: "Odyssey² BIOS",
+        md5: Some("562d5ebf9e030a40d6fabfc2f33139fd"),
+        sha1: None,
+        required: true,
+        group: None,
+    },
+    // ── Computers ──
+    BiosDef {
+        platform: "amiga",
+        file_name: "kick34005.A500",
+        description: "Kickstart 1.3 (A500)",
+        md5: Some("82a21c1890cae844b3df741f2762d48d"),
+        sha1: None,
+        required: false,
+        group: Some("amiga"),
+    },
+    BiosDef {
+        platform: "amiga",
+        file_name: "kick40068.A1200",
+        description: "Kickstart 3.1 (A1200)",
+        md5: Some("646773759326fbac3b2311fd8c8793ee"),
+        sha1: None,
+        required: false,
+        group: Some("amiga"),
+    },
+];
+
+/// BIOS files known for a platform.
+pub fn for_platform(slug: &str) -> impl Iterator<Item = &'static BiosDef> + '_ {
+    BIOS_FILES.iter().filter(move |b| b.platform == slug)
+}
+
+/// RetroArch's system directory: `system_directory` from retroarch.cfg, or
+/// the default under Application Support.
+pub fn default_bios_dir() -> Option<PathBuf> {
+    let configured = crate::saves::retroarch_config_path()
+        .and_then(|cfg| crate::saves::read_retroarch_cfg_value(&cfg, "system_directory"))
+        .filter(|v| v != "default")
+        .map(|v| PathBuf::from(crate::saves::expand_tilde(&v)));
+    configured.or_else(|| {
+        dirs::home_dir().map(|h| h.join("Library/Application Support/RetroArch/system"))
+    })
+}
+
+/// Check every registry file against `dir`. File names are matched
+/// case-insensitively since users often copy BIOS dumps with other casing.
+///
+/// Must be called from a blocking context (not async).
+pub fn scan_bios(dir: &Path) -> Vec<BiosFileStatus> {
+    let index = index_dir(dir);
+    BIOS_FILES.iter().map(|def| check_file(def, &index)).collect()
+}
+
+/// Readiness of one platform: every required file (or one of each group of
+/// alternatives) must be present with a matching hash.
+///
+/// Must be called from a blocking context (not async).
+pub fn platform_readiness(dir: &Path, slug: &str) -> BiosReadiness {
+    let index = index_dir(dir);
+    let files: Vec<BiosFileStatus> =
+        for_platform(slug).map(|def| check_file(def, &index)).collect();
+
+    // Requirement name -> (alternatives, satisfied)
+    let mut requirements: BTreeMap<&str, (Vec<&str>, bool)> = BTreeMap::new();
+    for (def, status) in for_platform(slug).zip(&files) {
+        if !def.required {
+            continue;
+        }
+        let entry = requirements
+            .entry(def.group.unwrap_or(def.file_name))
+            .or_default();
+        entry.0.push(def.file_name);
+        entry.1 |= matches!(status.status, BiosStatus::Ok | BiosStatus::Unverified);
+    }
+    let missing: Vec<String> = requirements
+        .into_values()
+        .filter(|(_, satisfied)| !satisfied)
+        .map(|(names, _)| names.join(" / "))
+        .collect();
+
+    BiosReadiness {
+        platform_slug: slug.to_string(),
+        ready: missing.is_empty(),
+        missing,
+        files,
+    }
+}
+
+/// Lowercased relative path (with `/` separators) -> actual path, two levels
+/// deep to cover per-core subfolders like `dc/`.
+fn index_dir(dir: &Path) -> HashMap<String, PathBuf> {
+    fn walk(dir: &Path, prefix: &str, depth: u8, index: &mut HashMap<String, PathBuf>) {
+        let Ok(entries) = std::fs::read_dir(dir) else {
+            return;
+        };
+        for entry in entries.flatten() {
+            let path = entry.path();
+            let name = entry.file_name().to_string_lossy().to_lowercase();
+            let rel = format!("{prefix}{name}");
+            if path.is_dir() {
+                if depth > 0 {
+                    walk(&path, &format!("{rel}/"), depth - 1, index);
+                }
+            } else {
+                index.insert(rel, path);
+            }
+        }
+    }
+
+    let mut index = HashMap::new();
+    walk(dir, "", 1, &mut index);
+    index
+}
+
+fn check_file(def: &BiosDef, index: &HashMap<String, PathBuf>) -> BiosFileStatus {
+    let path = index.get(&def.file_name.to_lowercase());
+    let status = match path {
+        None => BiosStatus::Missing,
+        Some(_) if def.md5.is_none() && def.sha1.is_none() => BiosStatus::Unverified,
+        Some(p) => match hash_file(p) {
+            Ok((md5, sha1)) => {
+                let md5_ok = def.md5.is_some_and(|h| h.eq_ignore_ascii_case(&md5));
+                let sha1_ok = def.sha1.is_some_and(|h| h.eq_ignore_ascii_case(&sha1));
+                if md5_ok || sha1_ok {
+                    BiosStatus::Ok
+                } else {
+                    BiosStatus::Mismatch
+                }
+            }
+            Err(e) => {
+                log::warn!("Failed to hash BIOS file {}: {e}", p.display());
+                BiosStatus::Mismatch
+            }
+        },
+    };
+
+    BiosFileStatus {
+        platform_slug: def.platform.to_string(),
+        file_name: def.file_name.to_string(),
+        description: def.description.to_string(),
+        required: def.required,
+        group: def.group.map(str::to_string),
+        status,
+        path: path.map(|p| p.to_string_lossy().into_owned()),
+    }
+}
+
+/// MD5 and SHA1 of a file as-is (BIOS archives like `neogeo.zip` are not unpacked).
+fn hash_file(path: &Path) -> std::io::Result<(String, String)> {
+    let mut file = std::fs::File::open(path)?;
+    let mut md5 = Md5::new();
+    let mut sha1 = Sha1::new();
+    let mut buf = vec![0u8; 64 * 1024];
+    loop {
+        let n = file.read(&mut buf)?;
+        if n == 0 {
+            break;
+        }
+        md5.update(&buf[..n]);
+        sha1.update(&buf[..n]);
+    }
+    Ok((format!("{:x}", md5.finalize()), format!("{:x}", sha1.finalize())))
+}
+
+/// Parse the firmware entries of a libretro core `.info` file:
+///
+/// ```text
+/// firmware0_desc = "bios_CD_E.bin (Mega-CD (Model 1 1.00 Europe) BIOS)"
+/// firmware0_path = "bios_CD_E.bin"
+/// firmware0_opt = "false"
+/// notes = "(!) bios_CD_E.bin (md5): e66fa1dc5820d254611fdcdba0662372|..."
+/// ```
+pub fn parse_core_firmware(info: &str) -> Vec<BiosHint> {
+    let values: HashMap<&str, &str> = info
+        .lines()
+        .filter_map(|line| {
+            let (key, value) = line.split_once('=')?;
+            Some((key.trim(), value.trim().trim_matches('"')))
+        })
+        .collect();
+
+    // Notes list expected hashes as "(!) <file> (md5): <hash>"
+    let md5s: HashMap<String, String> = values
+        .get("notes")
+        .map(|notes| {
+            notes
+                .split('|')
+                .filter_map(|note| {
+                    let (file, hash) = note.trim().strip_prefix("(!)")?.split_once("(md5):")?;
+                    Some((file.trim().to_lowercase(), hash.trim().to_lowercase()))
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+
+    let count: usize = values
+        .get("firmware_count")
+        .and_then(|c| c.parse().ok())
+        .unwrap_or(0);
+
+    (0..count)
+        .filter_map(|i| {
+            let path = values.get(format!("firmware{i}_path").as_str())?.to_string();
+            let description = values
+                .get(format!("firmware{i}_desc").as_str())
+                .map_or_else(|| path.clone(), |d| (*d).to_string());
+            let optional = values
+                .get(format!("firmware{i}_opt").as_str())
+                .is_some_and(|o| *o == "true");
+            let lower = path.to_lowercase();
+            let file_name = lower.rsplit('/').next().unwrap_or(&lower);
+            let md5 = md5s.get(&lower).or_else(|| md5s.get(file_name)).cloned();
+            Some(BiosHint { path, description, optional, md5 })
+        })
+        .collect()
+}
@@ -0,0 +1,177 @@
+use std::collections::HashMap;
+use std::io::{Read, Seek};
+use std::path::{Path, PathBuf};
+
+use futures_util::StreamExt;
+
+use crate::error::{AppError, AppResult};
+
+const BUILDBOT_URL: &str = "https://buildbot.libretro.com/nightly";
+
+/// Cloudflare in front of the buildbot turns away requests that don't look
+/// like they come from a client it knows, so identify as a core updater.
+const USER_AGENT: &str = "RetroArch/1.19 romm-buddy/0.1 (core updater)";
+
+/// Shared-library extension of cores on this OS.
+pub fn core_extension() -> &'static str {
+    if cfg!(target_os = "macos") {
+        "dylib"
+    } else if cfg!(target_os = "windows") {
+        "dll"
+    } else {
+        "so"
+    }
+}
+
+/// The buildbot folder with nightly cores for this OS and architecture.
+fn platform_url() -> AppResult<String> {
+    let dir = match (std::env::consts::OS, std::env::consts::ARCH) {
+        ("macos", "aarch64") => "apple/osx/arm64",
+        ("macos", "x86_64") => "apple/osx/x86_64",
+        ("windows", "x86_64") => "windows/x86_64",
+        ("windows", "x86") => "windows/x86",
+        ("linux", "x86_64") => "linux/x86_64",
+        ("linux", "arm") => "linux/armhf",
+        (os, arch) => {
+            return Err(AppError::Other(format!(
+                "The libretro buildbot has no cores for {os} ({arch}); install them from a file instead"
+            )))
+        }
+    };
+    Ok(format!("{BUILDBOT_URL}/{dir}/latest"))
+}
+
+fn client() -> AppResult<reqwest::Client> {
+    Ok(crate::http::client_builder()
+        .user_agent(USER_AGENT)
+        .timeout(std::time::Duration::from_secs(300))
+        .build()?)
+}
+
+async fn get(client: &reqwest::Client, url: &str) -> AppResult<reqwest::Response> {
+    crate::http::ensure_online("the libretro buildbot")?;
+    let resp = client
+        .get(url)
+        .header(reqwest::header::ACCEPT, "*/*")
+        .send()
+        .await?;
+    if !resp.status().is_success() {
+        return Err(crate::http::status_error("libretro buildbot", &resp));
+    }
+    Ok(resp)
+}
+
+/// Core names on the buildbot with the CRC32 of each one's zip, from the
+/// `.index-extended` listing RetroArch's own updater reads. Each line is
+/// `<date> <crc32> <core>_libretro.<ext>.zip`.
+pub async fn list_cores() -> AppResult<HashMap<String, u32>> {
+    let client = client()?;
+    let index = get(&client, &format!("{}/.index-extended", platform_url()?))
+        .await?
+        .text()
+        .await?;
+    let suffix = format!(".{}.zip", core_extension());
+    Ok(index
+        .lines()
+        .filter_map(|line| {
+            let mut fields = line.split_whitespace();
+            let _date = fields.next()?;
+            let crc = u32::from_str_radix(fields.next()?, 16).ok()?;
+            let name = fields.next()?.strip_suffix(&suffix)?;
+            Some((name.to_string(), crc))
+        })
+        .collect())
+}
+
+/// Download a core's zip, checking it against the CRC32 the buildbot lists
+/// for it. `on_progress` gets bytes downloaded and the total when known.
+pub async fn download_core(core_name: &str, on_progress: impl Fn(u64, u64)) -> AppResult<Vec<u8>> {
+    let expected =
+        list_cores().await?.get(core_name).copied().ok_or_else(|| {
+            AppError::NotFound(format!("Core {core_name} on the libretro buildbot"))
+        })?;
+
+    let client = client()?;
+    let url = format!("{}/{core_name}.{}.zip", platform_url()?, core_extension());
+    let resp = get(&client, &url).await?;
+    let total = resp.content_length().unwrap_or(0);
+    let mut bytes = Vec::with_capacity(usize::try_from(total).unwrap_or(0));
+    let mut stream = resp.bytes_stream();
+    while let Some(chunk) = stream.next().await {
+        bytes.extend_from_slice(&chunk?);
+        on_progress(bytes.len() as u64, total);
+    }
+
+    let actual = crc32fast::hash(&bytes);
+    if actual != expected {
+        return Err(AppError::Other(format!(
+            "Downloaded {core_name} is corrupt (CRC32 {actual:08x}, expected {expected:08x})"
+        )));
+    }
+    Ok(bytes)
+}
+
+/// Whether `file_name` is a libretro core for this OS, e.g.
+/// `snes9x_libretro.dylib`. Returns the core name.
+pub fn core_name_of(file_name: &str) -> Option<&str> {
+    let stem = file_name
+        .strip_suffix(core_extension())?
+        .strip_suffix('.')?;
+    stem.ends_with("_libretro").then_some(stem)
+}
+
+/// Write the core inside a buildbot download to `cores_dir`, returning its
+/// path.
+pub fn extract_core(zip_bytes: Vec<u8>, cores_dir: &Path) -> AppResult<PathBuf> {
+    extract_cores(std::io::Cursor::new(zip_bytes), cores_dir, |_| true)?
+        .into_iter()
+        .next()
+        .ok_or_else(|| AppError::Other(format!("No .{} core found in the zip", core_extension())))
+}
+
+/// Write the cores in a zip (a buildbot download or an offline bundle of
+/// many) that `accept` allows to `cores_dir`, returning their paths. Only the
+/// file name of each entry is used, so a zip can't write outside the folder.
+pub fn extract_cores(
+    reader: impl Read + Seek,
+    cores_dir: &Path,
+    accept: impl Fn(&str) -> bool,
+) -> AppResult<Vec<PathBuf>> {
+    let mut archive = zip::ZipArchive::new(reader)
+        .map_err(|e| AppError::Other(format!("Failed to open zip: {e}")))?;
+
+    let mut installed = Vec::new();
+    for i in 0..archive.len() {
+        let mut file = archive
+            .by_index(i)
+            .map_err(|e| AppError::Other(format!("Failed to read zip entry: {e}")))?;
+        let Some(file_name) = Path::new(file.name())
+            .file_name()
+            .and_then(|n| n.to_str())
+            .map(str::to_string)
+        else {
+            continue;
+        };
+        let Some(core_name) = core_name_of(&file_name) else {
+            continue;
+        };
+        if !accept(core_name) {
+            log::info!("Skipping unknown core {file_name} in zip");
+            continue;
+        }
+        let mut bytes = Vec::new();
+        file.read_to_end(&mut bytes)?;
+        installed.push(install_library(&bytes, &file_name, cores_dir)?);
+    }
+    Ok(installed)
+}
+
+/// Write a core library to `cores_dir` under `file_name`, through a
+/// temporary file so a running RetroArch never sees half a core.
+pub fn install_library(bytes: &[u8], file_name: &str, cores_dir: &Path) -> AppResult<PathBuf> {
+    let out_path = cores_dir.join(file_name);
+    let tmp_path = cores_dir.join(format!("{file_name}.part"));
+    std::fs::write(&tmp_path, bytes)?;
+    std::fs::rename(&tmp_path, &out_path)?;
+    Ok(out_path)
+}
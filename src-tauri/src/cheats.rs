@@ -0,0 +1,290 @@
+use std::collections::{BTreeSet, HashSet};
+use std::path::{Path, PathBuf};
+
+use sea_orm::{ConnectionTrait, DatabaseBackend, DatabaseConnection, FromQueryResult, Statement};
+
+use crate::error::{AppError, AppResult};
+use crate::http::{self, Provider};
+use crate::models::Cheat;
+use crate::platform_registry;
+use crate::saves;
+
+const CHEATS_BASE_URL: &str =
+    "https://raw.githubusercontent.com/libretro/libretro-database/master/cht";
+
+#[derive(Debug, FromQueryResult)]
+struct CheatRow {
+    content: String,
+    enabled: String,
+}
+
+/// `cheat3_enable` -> `(3, "enable")`.
+fn cheat_key(key: &str) -> Option<(u32, &str)> {
+    let rest = key.strip_prefix("cheat")?;
+    let (index, field) = rest.split_once('_')?;
+    Some((index.parse().ok()?, field))
+}
+
+fn parse_line(line: &str) -> Option<(&str, &str)> {
+    let (key, value) = line.split_once('=')?;
+    Some((key.trim(), value.trim().trim_matches('"')))
+}
+
+/// Parse the cheats of a `.cht` file. `enabled` overrides the file's own
+/// `cheatN_enable` flags.
+pub fn parse_cht(content: &str, enabled: &HashSet<u32>) -> Vec<Cheat> {
+    let count: u32 = content
+        .lines()
+        .filter_map(parse_line)
+        .find(|(key, _)| *key == "cheats")
+        .and_then(|(_, value)| value.parse().ok())
+        .unwrap_or(0);
+    let mut cheats: Vec<Cheat> = (0..count)
+        .map(|index| Cheat {
+            index,
+            description: format!("Cheat {}", index + 1),
+            code: None,
+            enabled: enabled.contains(&index),
+        })
+        .collect();
+
+    for (key, value) in content.lines().filter_map(parse_line) {
+        let Some((index, field)) = cheat_key(key) else {
+            continue;
+        };
+        let Some(cheat) = cheats.get_mut(index as usize) else {
+            continue;
+        };
+        match field {
+            "desc" if !value.is_empty() => cheat.description = value.to_string(),
+            "code" if !value.is_empty() => cheat.code = Some(value.to_string()),
+            _ => {}
+        }
+    }
+    cheats
+}
+
+/// The `.cht` file with `cheatN_enable` set from `enabled`, keeping every
+/// other line as downloaded.
+fn render_cht(content: &str, enabled: &HashSet<u32>) -> String {
+    let mut out = String::with_capacity(content.len());
+    let mut seen = HashSet::new();
+    for line in content.lines() {
+        let flag = parse_line(line)
+            .and_then(|(key, _)| cheat_key(key))
+            .filter(|(_, field)| *field == "enable");
+        match flag {
+            Some((index, _)) => {
+                seen.insert(index);
+                out.push_str(&format!(
+                    "cheat{index}_enable = \"{}\"\n",
+                    enabled.contains(&index)
+                ));
+            }
+            None => {
+                out.push_str(line);
+                out.push('\n');
+            }
+        }
+    }
+    for index in enabled.iter().filter(|i| !seen.contains(i)) {
+        out.push_str(&format!("cheat{index}_enable = \"true\"\n"));
+    }
+    out
+}
+
+/// RetroArch's replacement of characters that can't be in file names.
+fn sanitize_name(name: &str) -> String {
+    name.chars()
+        .map(|c| match c {
+            '&' | '*' | '/' | ':' | '`' | '"' | '<' | '>' | '?' | '\\' | '|' => '_',
+            _ => c,
+        })
+        .collect()
+}
+
+async fn stored(db: &DatabaseConnection, rom_id: i64) -> AppResult<Option<CheatRow>> {
+    Ok(CheatRow::find_by_statement(Statement::from_sql_and_values(
+        DatabaseBackend::Sqlite,
+        "SELECT content, enabled FROM rom_cheats WHERE rom_id = ?",
+        [rom_id.into()],
+    ))
+    .one(db)
+    .await?)
+}
+
+fn enabled_set(row: &CheatRow) -> HashSet<u32> {
+    serde_json::from_str::<Vec<u32>>(&row.enabled)
+        .unwrap_or_default()
+        .into_iter()
+        .collect()
+}
+
+/// The downloaded cheats of a ROM; empty until [`fetch`] found a file.
+pub async fn get(db: &DatabaseConnection, rom_id: i64) -> AppResult<Vec<Cheat>> {
+    Ok(stored(db, rom_id)
+        .await?
+        .map(|row| parse_cht(&row.content, &enabled_set(&row)))
+        .unwrap_or_default())
+}
+
+/// Download the libretro cheat file for a ROM, looked up by its DAT game
+/// name, then its file name, then its title. Cheats that were enabled stay
+/// enabled when they're still in the file. None when libretro has no file.
+pub async fn fetch(
+    db: &DatabaseConnection,
+    client: &reqwest::Client,
+    rom_id: i64,
+) -> AppResult<Option<Vec<Cheat>>> {
+    #[derive(Debug, FromQueryResult)]
+    struct RomRow {
+        name: String,
+        file_name: String,
+        dat_game_name: Option<String>,
+        platform_slug: String,
+    }
+
+    let rom = RomRow::find_by_statement(Statement::from_sql_and_values(
+        DatabaseBackend::Sqlite,
+        "SELECT r.name, r.file_name, r.dat_game_name, p.slug AS platform_slug
+         FROM roms r JOIN platforms p ON p.id = r.platform_id
+         WHERE r.id = ?",
+        [rom_id.into()],
+    ))
+    .one(db)
+    .await?
+    .ok_or_else(|| AppError::NotFound(format!("ROM {rom_id}")))?;
+    let system = platform_registry::libretro_dir(&rom.platform_slug)
+        .ok_or_else(|| AppError::Other(format!("No libretro cheats for {}", rom.platform_slug)))?;
+
+    let file_stem = Path::new(&rom.file_name)
+        .file_stem()
+        .map(|s| s.to_string_lossy().into_owned());
+    let mut candidates: Vec<String> = Vec::new();
+    for name in [rom.dat_game_name, file_stem, Some(rom.name)]
+        .into_iter()
+        .flatten()
+    {
+        for candidate in [sanitize_name(&name), name] {
+            if !candidates.contains(&candidate) {
+                candidates.push(candidate);
+            }
+        }
+    }
+
+    for name in candidates {
+        let url = format!(
+            "{CHEATS_BASE_URL}/{}/{}.cht",
+            urlencoding::encode(system),
+            urlencoding::encode(&name)
+        );
+        let fetched = http::fetch_text(Provider::Libretro, client.get(&url))
+            .await
+            .map_err(AppError::Other)?;
+        if fetched.status == reqwest::StatusCode::NOT_FOUND {
+            continue;
+        }
+        if !fetched.status.is_success() {
+            return Err(AppError::Other(format!(
+                "Cheat download failed: {}",
+                fetched.status
+            )));
+        }
+
+        let previous = stored(db, rom_id).await?.map(|row| enabled_set(&row));
+        let cheats = parse_cht(&fetched.body, &previous.unwrap_or_default());
+        let enabled: Vec<u32> = cheats
+            .iter()
+            .filter(|c| c.enabled)
+            .map(|c| c.index)
+            .collect();
+        db.execute(Statement::from_sql_and_values(
+            DatabaseBackend::Sqlite,
+            "INSERT INTO rom_cheats (rom_id, source_url, content, enabled) VALUES (?, ?, ?, ?)
+             ON CONFLICT(rom_id) DO UPDATE SET
+               source_url = excluded.source_url,
+               content = excluded.content,
+               enabled = excluded.enabled,
+               fetched_at = strftime('%Y-%m-%dT%H:%M:%fZ', 'now')",
+            [
+                rom_id.into(),
+                url.into(),
+                fetched.body.into(),
+                serde_json::json!(enabled).to_string().into(),
+            ],
+        ))
+        .await?;
+        return Ok(Some(cheats));
+    }
+    Ok(None)
+}
+
+/// Turn on exactly the cheats in `indexes`.
+pub async fn set_enabled(
+    db: &DatabaseConnection,
+    rom_id: i64,
+    indexes: &[u32],
+) -> AppResult<Vec<Cheat>> {
+    let row = stored(db, rom_id)
+        .await?
+        .ok_or_else(|| AppError::Other("No cheats downloaded for this ROM".to_string()))?;
+    let count = parse_cht(&row.content, &HashSet::new()).len();
+    let indexes: BTreeSet<u32> = indexes
+        .iter()
+        .copied()
+        .filter(|&i| (i as usize) < count)
+        .collect();
+    db.execute(Statement::from_sql_and_values(
+        DatabaseBackend::Sqlite,
+        "UPDATE rom_cheats SET enabled = ? WHERE rom_id = ?",
+        [serde_json::json!(indexes).to_string().into(), rom_id.into()],
+    ))
+    .await?;
+    get(db, rom_id).await
+}
+
+/// RetroArch's cheat folder: `cheat_database_path` from retroarch.cfg, else
+/// the default under Application Support.
+fn cheats_dir() -> Option<PathBuf> {
+    saves::retroarch_config_path()
+        .and_then(|cfg| saves::read_retroarch_cfg_value(&cfg, "cheat_database_path"))
+        .filter(|dir| dir != "default")
+        .map(|dir| PathBuf::from(saves::expand_tilde(&dir)))
+        .or_else(|| {
+            dirs::home_dir().map(|h| h.join("Library/Application Support/RetroArch/cheats"))
+        })
+}
+
+/// Write a ROM's cheat file where RetroArch looks for the game's cheats,
+/// `<cheats>/<core name>/<content name>.cht`, with the enabled cheats on.
+/// Without enabled cheats, a file staged for an earlier launch is removed.
+/// Returns whether cheats were staged.
+pub async fn stage(
+    db: &DatabaseConnection,
+    rom_id: i64,
+    core_name: &str,
+    rom_path: &Path,
+) -> AppResult<bool> {
+    let dir = cheats_dir()
+        .ok_or_else(|| AppError::NotFound("RetroArch cheats folder".to_string()))?
+        .join(core_name);
+    let content_name = rom_path
+        .file_stem()
+        .map(|s| s.to_string_lossy().into_owned())
+        .ok_or_else(|| AppError::Other("ROM has no file name".to_string()))?;
+    let path = dir.join(format!("{content_name}.cht"));
+
+    let row = stored(db, rom_id).await?;
+    let enabled = row.as_ref().map(enabled_set).unwrap_or_default();
+    let Some(row) = row.filter(|_| !enabled.is_empty()) else {
+        if path.exists() {
+            tokio::fs::remove_file(&path).await?;
+        }
+        return Ok(false);
+    };
+
+    tokio::fs::create_dir_all(&dir).await?;
+    tokio::fs::write(&path, render_cht(&row.content, &enabled)).await?;
+    log::info!("Staged {} cheats at {}", enabled.len(), path.display());
+    Ok(true)
+}
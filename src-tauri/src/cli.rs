@@ -0,0 +1,240 @@
+use std::sync::atomic::{AtomicU64, Ordering};
+
+use sea_orm::{ColumnTrait, DatabaseConnection, EntityTrait, QueryFilter};
+use tokio_util::sync::CancellationToken;
+
+use crate::commands;
+use crate::entity::sources;
+use crate::error::AppResult;
+use crate::events::LibraryEvents;
+use crate::models::ScanProgress;
+use crate::settings::{SettingsFile, SettingsRead};
+
+const USAGE: &str = "\
+Usage: romm-buddy [--sync-all | --sync <source id>...] [--enrich] [--verify] [--evict-cache]
+
+Runs library maintenance without opening the app, in this order:
+  --sync-all      Sync every enabled source
+  --sync <id>     Sync one source; can be repeated
+  --enrich        Fetch metadata and artwork for ROMs that don't have it yet
+  --verify        Check local ROMs against the imported DAT files
+  --evict-cache   Remove cached ROMs that weren't played recently
+  --help          Show this help";
+
+/// What a headless run was asked to do.
+#[derive(Debug, Default)]
+struct Tasks {
+    sync_all: bool,
+    sync: Vec<i64>,
+    enrich: bool,
+    verify: bool,
+    evict_cache: bool,
+}
+
+/// `None` when none of the arguments are CLI flags, so the app starts as
+/// usual (macOS and Tauri pass arguments of their own).
+fn parse(args: &[String]) -> Result<Option<Tasks>, String> {
+    const FLAGS: [&str; 5] = [
+        "--sync-all",
+        "--sync",
+        "--enrich",
+        "--verify",
+        "--evict-cache",
+    ];
+    if !args.iter().any(|a| FLAGS.contains(&a.as_str())) {
+        return Ok(None);
+    }
+
+    let mut tasks = Tasks::default();
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--sync-all" => tasks.sync_all = true,
+            "--sync" => {
+                let id = args
+                    .next()
+                    .and_then(|id| id.parse().ok())
+                    .ok_or_else(|| "--sync needs a source id".to_string())?;
+                tasks.sync.push(id);
+            }
+            "--enrich" => tasks.enrich = true,
+            "--verify" => tasks.verify = true,
+            "--evict-cache" => tasks.evict_cache = true,
+            other => return Err(format!("Unknown argument: {other}")),
+        }
+    }
+    Ok(Some(tasks))
+}
+
+/// Prints a line each time a task gets another 10% further, so scheduled
+/// runs leave a readable log.
+fn print_progress(label: &'static str) -> impl Fn(ScanProgress) + Send {
+    let last = AtomicU64::new(u64::MAX);
+    move |p| {
+        if p.total == 0 {
+            return;
+        }
+        let step = p.current * 10 / p.total;
+        if last.swap(step, Ordering::Relaxed) != step {
+            eprintln!("{label}: {}/{} {}", p.current, p.total, p.current_item);
+        }
+    }
+}
+
+/// Run the tasks one after another. A failed task is reported and the
+/// rest still run; returns whether all of them succeeded.
+async fn run_tasks(tasks: &Tasks) -> AppResult<bool> {
+    let db: DatabaseConnection = crate::db::create_pool(&crate::db_url()?).await?;
+    let settings = SettingsFile::load(&db).await?;
+    crate::http::set_proxy(crate::commands::read_proxy_from_store(&settings));
+    crate::http::set_offline(crate::commands::read_offline_from_store(&settings));
+    let events = LibraryEvents::default();
+
+    // Ctrl-C (or the scheduler stopping the job) ends the current task cleanly
+    let cancel = CancellationToken::new();
+    let on_signal = cancel.clone();
+    tauri::async_runtime::spawn(async move {
+        if tokio::signal::ctrl_c().await.is_ok() {
+            eprintln!("Cancelling...");
+            on_signal.cancel();
+        }
+    });
+
+    let mut ok = true;
+    let mut report = |task: &str, result: AppResult<String>| match result {
+        Ok(summary) => println!("{task}: {summary}"),
+        Err(e) => {
+            eprintln!("{task} failed: {e}");
+            ok = false;
+        }
+    };
+
+    let mut source_ids = tasks.sync.clone();
+    if tasks.sync_all {
+        source_ids.extend(
+            sources::Entity::find()
+                .filter(sources::Column::Enabled.eq(true))
+                .all(&db)
+                .await?
+                .into_iter()
+                .map(|s| s.id),
+        );
+        source_ids.sort_unstable();
+        source_ids.dedup();
+    }
+    let mut synced = false;
+    for source_id in source_ids {
+        if cancel.is_cancelled() {
+            break;
+        }
+        let result =
+            commands::sync_source_to_db(&db, source_id, print_progress("Sync"), cancel.clone())
+                .await;
+        synced |= result.is_ok();
+        report(
+            &format!("Sync of source {source_id}"),
+            result.map(|()| "done".to_string()),
+        );
+    }
+    if synced && !cancel.is_cancelled() && commands::read_auto_dedup_from_store(&settings) {
+        let result = crate::dedup::reconcile_duplicates(&db, &cancel, &events).await;
+        report("Dedup", result.map(|n| format!("{n} duplicates merged")));
+    }
+
+    if tasks.enrich && !cancel.is_cancelled() {
+        let result = crate::metadata::enrich_roms(
+            crate::metadata::RomSelection::Unenriched {
+                platform_id: None,
+                search: None,
+            },
+            &db,
+            print_progress("Enrich"),
+            cancel.clone(),
+            commands::read_igdb_client_from_store(&settings).as_ref(),
+            commands::read_ss_creds_from_store(&settings).as_ref(),
+            &commands::read_ss_prefs_from_store(&settings),
+            &commands::read_artwork_chains_from_store(&settings),
+            commands::read_artwork_dedup_from_store(&settings),
+            &crate::metadata::PriorityLane::default(),
+            &events,
+            commands::read_translator_from_store(&settings).as_ref(),
+            &commands::read_enrichment_options_from_store(&settings),
+        )
+        .await;
+        report("Enrich", result.map(|()| "done".to_string()));
+    }
+
+    if tasks.verify && !cancel.is_cancelled() {
+        let result =
+            crate::metadata::dat::verify_roms(&db, None, print_progress("Verify"), cancel.clone())
+                .await;
+        report(
+            "Verify",
+            result.map(|s| {
+                format!(
+                    "{} verified, {} unverified, {} bad dumps, {} not checked",
+                    s.verified, s.unverified, s.bad_dump, s.not_checked
+                )
+            }),
+        );
+    }
+
+    if tasks.evict_cache && !cancel.is_cancelled() {
+        let days = settings
+            .setting("cache_eviction_days")
+            .and_then(|v| v.as_u64())
+            .unwrap_or(crate::DEFAULT_CACHE_EVICTION_DAYS);
+        let result = crate::evict_stale_cache(&db, days)
+            .await
+            .map_err(|e| crate::error::AppError::Other(e.to_string()));
+        report("Cache eviction", result.map(|()| "done".to_string()));
+    }
+
+    Ok(ok && !cancel.is_cancelled())
+}
+
+/// Release builds on Windows have no console of their own, so print to the
+/// one of the terminal that started the CLI, if any. Output redirected to a
+/// file (e.g. from Task Scheduler) goes there either way.
+#[cfg(windows)]
+fn attach_console() {
+    use windows_sys::Win32::System::Console::{AttachConsole, ATTACH_PARENT_PROCESS};
+
+    // SAFETY: AttachConsole has no preconditions; without a parent console it fails harmlessly
+    unsafe {
+        AttachConsole(ATTACH_PARENT_PROCESS);
+    }
+}
+
+#[cfg(not(windows))]
+fn attach_console() {}
+
+/// Run the maintenance tasks given on the command line without the webview.
+/// Returns the process exit code, or `None` when there are no CLI flags.
+pub fn run(args: &[String]) -> Option<i32> {
+    if args.iter().any(|a| a == "--help") {
+        attach_console();
+        println!("{USAGE}");
+        return Some(0);
+    }
+    let parsed = parse(args);
+    if !matches!(parsed, Ok(None)) {
+        attach_console();
+    }
+    let tasks = match parsed {
+        Ok(Some(tasks)) => tasks,
+        Ok(None) => return None,
+        Err(msg) => {
+            eprintln!("{msg}\n\n{USAGE}");
+            return Some(2);
+        }
+    };
+    match tauri::async_runtime::block_on(run_tasks(&tasks)) {
+        Ok(true) => Some(0),
+        Ok(false) => Some(1),
+        Err(e) => {
+            eprintln!("{e}");
+            Some(1)
+        }
+    }
+}
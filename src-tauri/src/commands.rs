@@ -9,12 +9,25 @@ use tokio::io::AsyncWriteExt;
 use tokio_util::sync::CancellationToken;
 
 use crate::error::{AppError, AppResult};
+use crate::events::LibraryEvents;
+use crate::jobs::JobManager;
 use crate::platform_registry;
+use crate::settings::SettingsRead;
 use crate::models::{
-    AchievementData, CacheInfo, CachedFile, ConnectionTestResult, CoreInfo, CoreMapping,
-    DownloadProgress, EmulatorDef, IgdbTestResult, LibraryPage, Platform, PlatformWithCount,
-    RaTestResult, RomWithMeta, SaveFileInfo, SavePathOverride, ScanProgress, SsTestResult,
-    SourceConfig,
+    AchievementData, ActivityEntry, ApiServerConfig, ArchiveHandling, ArtworkChains, BiosFileStatus,
+    BiosHint, BiosReadiness, CacheInfo, CacheMigrationReport, CachedFile, Cheat, CleanupReport,
+    CloudBackupConfig, CloudSyncReport, Collection, CompletionStatus, CompressedFormat,
+    CompressionReport, ConnectionTestResult, CoreInfo, CoreMapping, CoreRecommendation,
+    CoreSetupReport, CorruptDownload, CustomEmulator, DiscardedCacheFile, DownloadProgress,
+    EmulatorArgTemplate, EmulatorDef, EnrichmentOptions, FolderBrowse, HistoryImportReport,
+    HomebrewInfo, IgdbTestResult, IntegrityReport, Job, JobKind, LaunchBoxFuzzyMatch,
+    LaunchGridPage, LaunchGridRow, LaunchHooks, LaunchWarning, LaunchWarningKind, LibraryBatch,
+    LibraryChanged, LibraryPage, LibraryPageLimits, ListImportReport, LocalizedDescription,
+    MetadataEdit, MetadataProvider, MisterConfig, OperationLogEntry, Platform, PlatformCopy,
+    PlatformCoreSetup, PlatformSetupStatus, PlatformWithCount, Profile, ProxySettings, RaTestResult,
+    RetroArchGameOverrides, RomDownloadUrl, RomWithMeta, SaveFileInfo, SavePathOverride,
+    ScanProgress, SearchSuggestion, SourceConfig, SourceSettings, SsMediaPreferences, SsTestResult,
+    StatusCount, SuggestionKind, SyncSummary, TagCount, TranslationSettings, YearlyWrapped,
 };
 use crate::saves;
 use crate::sources::local_sync;
@@ -33,6 +46,11 @@ struct EmulatorEntry {
     name: &'static str,
     default_macos_app: &'static str,
     platforms: &'static [&'static str],
+    /// Default argument template, see [`render_emulator_args`].
+    args_template: &'static str,
+    fullscreen_flag: &'static str,
+    /// Archive formats the emulator opens without extracting them first.
+    archives: &'static [&'static str],
 }
 
 const EMULATOR_REGISTRY: &[EmulatorEntry] = &[
@@ -41,84 +59,262 @@ const EMULATOR_REGISTRY: &[EmulatorEntry] = &[
         name: "Dolphin",
         default_macos_app: "/Applications/Dolphin.app",
         platforms: &["gc", "wii"],
+        args_template: "{fullscreen} --exec={rom} [-s {savestate}]",
+        fullscreen_flag: "-C Dolphin.Display.Fullscreen=True",
+        archives: &[],
     },
     EmulatorEntry {
         id: "duckstation",
         name: "DuckStation",
         default_macos_app: "/Applications/DuckStation.app",
         platforms: &["psx"],
+        args_template: "{fullscreen} {rom} [-statefile {savestate}]",
+        fullscreen_flag: "-fullscreen",
+        archives: &[],
     },
     EmulatorEntry {
         id: "pcsx2",
         name: "PCSX2",
         default_macos_app: "/Applications/PCSX2.app",
         platforms: &["ps2"],
+        args_template: "{fullscreen} {rom} [-statefile {savestate}]",
+        fullscreen_flag: "-fullscreen",
+        archives: &[],
     },
     EmulatorEntry {
         id: "mgba",
         name: "mGBA",
         default_macos_app: "/Applications/mGBA.app",
         platforms: &["gba", "gb", "gbc"],
+        args_template: "[--savestate {savestate}] {fullscreen} {rom}",
+        fullscreen_flag: "-f",
+        archives: &["zip", "7z"],
     },
     EmulatorEntry {
         id: "cemu",
         name: "Cemu",
         default_macos_app: "/Applications/Cemu.app",
         platforms: &["wiiu"],
+        args_template: "{fullscreen} -g {rom}",
+        fullscreen_flag: "-f",
+        archives: &[],
     },
     EmulatorEntry {
         id: "xemu",
         name: "xemu",
         default_macos_app: "/Applications/xemu.app",
         platforms: &["xbox"],
+        args_template: "{fullscreen} -dvd_path {rom}",
+        fullscreen_flag: "-full-screen",
+        archives: &[],
     },
     EmulatorEntry {
         id: "rpcs3",
         name: "RPCS3",
         default_macos_app: "/Applications/RPCS3.app",
         platforms: &["ps3"],
+        args_template: "--no-gui {fullscreen} {rom}",
+        fullscreen_flag: "--fullscreen",
+        archives: &[],
     },
     EmulatorEntry {
         id: "melonds",
         name: "melonDS",
         default_macos_app: "/Applications/melonDS.app",
         platforms: &["nds"],
+        args_template: "{fullscreen} {rom}",
+        fullscreen_flag: "-f",
+        archives: &["zip", "7z", "rar"],
     },
 ];
 
-fn build_emulator_args(emulator_type: &str, rom_path: &str) -> Vec<String> {
-    match emulator_type {
-        "dolphin" => vec![format!("--exec={rom_path}")],
-        "duckstation" | "pcsx2" => vec![rom_path.into()],
-        "cemu" => vec!["-g".into(), rom_path.into()],
-        "xemu" => vec!["-dvd_path".into(), rom_path.into()],
-        "rpcs3" => vec!["--no-gui".into(), rom_path.into()],
-        _ => vec![rom_path.into()],
+/// Settings key holding per-emulator overrides of the argument templates.
+const EMULATOR_TEMPLATES_KEY: &str = "emulator_arg_templates";
+/// Settings key holding user-defined emulators not in [`EMULATOR_REGISTRY`].
+const CUSTOM_EMULATORS_KEY: &str = "custom_emulators";
+
+fn default_arg_template(emulator_type: &str) -> EmulatorArgTemplate {
+    EMULATOR_REGISTRY
+        .iter()
+        .find(|e| e.id == emulator_type)
+        .map_or_else(
+            || EmulatorArgTemplate {
+                args: "{rom}".into(),
+                fullscreen_flag: None,
+                fullscreen: false,
+            },
+            |e| EmulatorArgTemplate {
+                args: e.args_template.into(),
+                fullscreen_flag: Some(e.fullscreen_flag.into()).filter(|f: &String| !f.is_empty()),
+                fullscreen: false,
+            },
+        )
+}
+
+fn read_custom_emulators<R: tauri::Runtime>(
+    store: &tauri_plugin_store::Store<R>,
+) -> Vec<CustomEmulator> {
+    store
+        .get(CUSTOM_EMULATORS_KEY)
+        .and_then(|v| serde_json::from_value(v).ok())
+        .unwrap_or_default()
+}
+
+/// Resolve the argument template for an emulator: user override, then custom
+/// emulator definition, then the built-in registry default.
+fn read_arg_template<R: tauri::Runtime>(
+    store: &tauri_plugin_store::Store<R>,
+    emulator_type: &str,
+) -> EmulatorArgTemplate {
+    let overrides: HashMap<String, EmulatorArgTemplate> = store
+        .get(EMULATOR_TEMPLATES_KEY)
+        .and_then(|v| serde_json::from_value(v).ok())
+        .unwrap_or_default();
+    if let Some(t) = overrides.get(emulator_type) {
+        return t.clone();
+    }
+    read_custom_emulators(store)
+        .into_iter()
+        .find(|c| c.id == emulator_type)
+        .map_or_else(|| default_arg_template(emulator_type), |c| c.template)
+}
+
+/// Expand an emulator argument template into process arguments.
+///
+/// Placeholders: `{rom}`, `{savestate}` and `{fullscreen}`. Arguments are split
+/// on whitespace unless double-quoted. A `[...]` group is dropped entirely when
+/// a placeholder inside it has no value (e.g. `[-s {savestate}]` without a save
+/// state); outside a group only the affected argument is dropped.
+fn render_emulator_args(
+    template: &str,
+    rom_path: &str,
+    save_state: Option<&str>,
+    fullscreen_flag: Option<&str>,
+) -> Vec<String> {
+    fn push_token(groups: &mut [(Vec<String>, bool)], token: &mut String) {
+        if !token.is_empty() {
+            if let Some(group) = groups.last_mut() {
+                group.0.push(std::mem::take(token));
+            }
+        }
+    }
+
+    // (arguments, optional)
+    let mut groups: Vec<(Vec<String>, bool)> = vec![(Vec::new(), false)];
+    let mut token = String::new();
+    let mut in_quotes = false;
+    for c in template.chars() {
+        match c {
+            '"' => in_quotes = !in_quotes,
+            '[' if !in_quotes => {
+                push_token(&mut groups, &mut token);
+                groups.push((Vec::new(), true));
+            }
+            ']' if !in_quotes => {
+                push_token(&mut groups, &mut token);
+                groups.push((Vec::new(), false));
+            }
+            c if c.is_whitespace() && !in_quotes => push_token(&mut groups, &mut token),
+            c => token.push(c),
+        }
+    }
+    push_token(&mut groups, &mut token);
+
+    let fullscreen_flag = fullscreen_flag.filter(|f| !f.trim().is_empty());
+    let expand = |arg: &str| -> Option<Vec<String>> {
+        if arg == "{fullscreen}" {
+            // A bare flag may expand to several arguments (e.g. `-C Key=Value`)
+            return fullscreen_flag.map(|f| f.split_whitespace().map(String::from).collect());
+        }
+        if (arg.contains("{savestate}") && save_state.is_none())
+            || (arg.contains("{fullscreen}") && fullscreen_flag.is_none())
+        {
+            return None;
+        }
+        Some(vec![arg
+            .replace("{rom}", rom_path)
+            .replace("{savestate}", save_state.unwrap_or_default())
+            .replace("{fullscreen}", fullscreen_flag.unwrap_or_default())])
+    };
+
+    let mut args = Vec::new();
+    for (tokens, optional) in groups {
+        let expanded: Vec<Option<Vec<String>>> = tokens.iter().map(|t| expand(t)).collect();
+        if optional && expanded.iter().any(Option::is_none) {
+            continue;
+        }
+        args.extend(expanded.into_iter().flatten().flatten());
     }
+    args
 }
 
 #[tauri::command]
 pub async fn get_platforms(db: State<'_, DatabaseConnection>) -> AppResult<Vec<Platform>> {
+    list_platforms(db.inner()).await
+}
+
+pub(crate) async fn list_platforms(db: &DatabaseConnection) -> AppResult<Vec<Platform>> {
     use crate::entity::platforms;
     use sea_orm::{EntityTrait, QueryOrder};
 
     let models = platforms::Entity::find()
         .order_by_asc(platforms::Column::Name)
-        .all(db.inner())
+        .all(db)
         .await?;
 
     Ok(models
         .into_iter()
         .map(|m| Platform {
             id: m.id,
-            slug: m.slug,
             name: m.name,
             igdb_id: m.igdb_id,
             file_extensions: m.file_extensions.into_inner(),
+            default_extensions: crate::platform_registry::extensions(&m.slug)
+                .iter()
+                .map(|e| (*e).to_string())
+                .collect(),
+            slug: m.slug,
+            archived: m.archived,
+            archive_handling: m.archive_handling,
         })
         .collect())
 }
 
+/// Set the file extensions a local sync picks up for a platform on top of the
+/// registry defaults. Returns the cleaned list.
+#[tauri::command]
+pub async fn set_platform_extensions(
+    db: State<'_, DatabaseConnection>,
+    platform_id: i64,
+    extensions: Vec<String>,
+) -> AppResult<Vec<String>> {
+    use sea_orm::{ConnectionTrait, DatabaseBackend, Statement};
+
+    let mut extensions: Vec<String> = extensions
+        .iter()
+        .map(|e| e.trim().trim_start_matches('.').to_lowercase())
+        .filter(|e| !e.is_empty())
+        .collect();
+    extensions.sort();
+    extensions.dedup();
+    if let Some(bad) = extensions.iter().find(|e| !e.chars().all(char::is_alphanumeric)) {
+        return Err(AppError::Other(format!("Invalid file extension: {bad}")));
+    }
+
+    let result = db
+        .inner()
+        .execute(Statement::from_sql_and_values(
+            DatabaseBackend::Sqlite,
+            "UPDATE platforms SET file_extensions = ?, updated_at = strftime('%Y-%m-%dT%H:%M:%fZ', 'now') WHERE id = ?",
+            [crate::entity::json_vec::JsonVec(extensions.clone()).into(), platform_id.into()],
+        ))
+        .await?;
+    if result.rows_affected() == 0 {
+        return Err(AppError::NotFound(format!("Platform {platform_id}")));
+    }
+    Ok(extensions)
+}
+
 #[tauri::command]
 pub async fn get_sources(db: State<'_, DatabaseConnection>) -> AppResult<Vec<SourceConfig>> {
     use crate::entity::sources;
@@ -144,20 +340,43 @@ pub async fn get_sources(db: State<'_, DatabaseConnection>) -> AppResult<Vec<Sou
         .collect())
 }
 
+/// Test a ROMM server with either an API token or a username and password,
+/// and the TLS options the source would be saved with.
 #[tauri::command]
 pub async fn test_romm_connection(
     url: String,
     username: String,
     password: String,
+    api_token: Option<String>,
+    ca_cert_path: Option<String>,
+    cert_fingerprint: Option<String>,
 ) -> AppResult<ConnectionTestResult> {
-    let client = RommClient::new(url, username, password);
-    client.test_connection().await
+    let client = match api_token.filter(|t| !t.is_empty()) {
+        Some(token) => RommClient::with_api_token(url, token),
+        None => RommClient::new(url, username, password),
+    };
+    let tls = crate::tls::TlsOptions {
+        ca_cert_path: ca_cert_path.filter(|p| !p.is_empty()).map(std::path::PathBuf::from),
+        cert_fingerprint: cert_fingerprint.filter(|f| !f.is_empty()),
+    };
+    client.with_tls(&tls)?.test_connection().await
+}
+
+/// The SHA-256 fingerprint of the certificate a server presents, so a
+/// self-signed one can be checked and pinned.
+#[tauri::command]
+pub async fn get_server_certificate_fingerprint(url: String) -> AppResult<String> {
+    crate::tls::fetch_fingerprint(&url).await
 }
 
 #[tauri::command]
-pub async fn test_local_path(path: String) -> AppResult<ConnectionTestResult> {
+pub async fn test_local_path(
+    db: State<'_, DatabaseConnection>,
+    path: String,
+) -> AppResult<ConnectionTestResult> {
     let root = std::path::Path::new(&path);
-    let (_layout, platform_count, rom_count) = local_sync::test_local_path(root)?;
+    let custom = local_sync::custom_extensions(db.inner()).await?;
+    let (_layout, platform_count, rom_count) = local_sync::test_local_path(root, &custom)?;
     #[allow(clippy::cast_possible_truncation)]
     Ok(ConnectionTestResult {
         platform_count,
@@ -165,6 +384,44 @@ pub async fn test_local_path(path: String) -> AppResult<ConnectionTestResult> {
     })
 }
 
+/// List what importing `path` as a local source would add, without creating
+/// the source. Entries already in the library are flagged.
+#[tauri::command]
+pub async fn browse_folder(
+    db: State<'_, DatabaseConnection>,
+    path: String,
+) -> AppResult<FolderBrowse> {
+    use sea_orm::{DatabaseBackend, FromQueryResult, Statement};
+
+    let root = std::path::PathBuf::from(&path);
+    let custom = local_sync::custom_extensions(db.inner()).await?;
+    let mut browse = tokio::task::spawn_blocking(move || local_sync::browse_folder(&root, &custom))
+        .await
+        .map_err(|e| AppError::Other(format!("Task join error: {e}")))??;
+
+    #[derive(Debug, FromQueryResult)]
+    struct KnownRomRow {
+        slug: String,
+        file_name: String,
+    }
+
+    let known: std::collections::HashSet<(String, String)> =
+        KnownRomRow::find_by_statement(Statement::from_string(
+            DatabaseBackend::Sqlite,
+            "SELECT p.slug, r.file_name FROM roms r JOIN platforms p ON p.id = r.platform_id",
+        ))
+        .all(db.inner())
+        .await?
+        .into_iter()
+        .map(|r| (r.slug, r.file_name))
+        .collect();
+
+    for rom in &mut browse.roms {
+        rom.in_library = known.contains(&(rom.platform_slug.clone(), rom.file_name.clone()));
+    }
+    Ok(browse)
+}
+
 #[tauri::command]
 pub async fn add_source(
     db: State<'_, DatabaseConnection>,
@@ -174,14 +431,14 @@ pub async fn add_source(
     credentials_json: String,
 ) -> AppResult<i64> {
     use crate::entity::sources;
-    use sea_orm::{ActiveModelTrait, ActiveValue::Set};
+    use sea_orm::{ActiveModelTrait, ActiveValue::Set, ConnectionTrait, DatabaseBackend, Statement};
 
     let model = sources::ActiveModel {
         id: sea_orm::ActiveValue::NotSet,
         name: Set(name),
         source_type: Set(source_type),
         url: Set(url),
-        credentials: Set(credentials_json),
+        credentials: Set("{}".to_string()),
         settings: Set("{}".to_string()),
         enabled: Set(true),
         last_synced_at: Set(None),
@@ -191,6 +448,18 @@ pub async fn add_source(
     .insert(db.inner())
     .await?;
 
+    // Saved once the ID is known, since the keychain entries are named after it
+    db.inner()
+        .execute(Statement::from_sql_and_values(
+            DatabaseBackend::Sqlite,
+            "UPDATE sources SET credentials = ? WHERE id = ?",
+            [
+                crate::secrets::seal_source_credentials(model.id, &credentials_json).into(),
+                model.id.into(),
+            ],
+        ))
+        .await?;
+
     Ok(model.id)
 }
 
@@ -208,7 +477,12 @@ pub async fn update_source(
         .execute(Statement::from_sql_and_values(
             DatabaseBackend::Sqlite,
             "UPDATE sources SET name = ?, url = ?, credentials = ?, updated_at = strftime('%Y-%m-%dT%H:%M:%fZ', 'now') WHERE id = ?",
-            [name.into(), url.into(), credentials_json.into(), source_id.into()],
+            [
+                name.into(),
+                url.into(),
+                crate::secrets::seal_source_credentials(source_id, &credentials_json).into(),
+                source_id.into(),
+            ],
         ))
         .await?;
     Ok(())
@@ -226,7 +500,80 @@ pub async fn get_source_credentials(
         .one(db.inner())
         .await?
         .ok_or_else(|| AppError::SourceNotFound(source_id.to_string()))?;
-    Ok(model.credentials)
+    Ok(crate::secrets::open_credentials(&model.credentials))
+}
+
+/// A source's sync filters; unreadable settings count as no filters.
+fn parse_source_settings(json: &str) -> SourceSettings {
+    serde_json::from_str(json).unwrap_or_else(|e| {
+        log::warn!("Ignoring unreadable source settings: {e}");
+        SourceSettings::default()
+    })
+}
+
+#[tauri::command]
+pub async fn get_source_settings(
+    db: State<'_, DatabaseConnection>,
+    source_id: i64,
+) -> AppResult<SourceSettings> {
+    use crate::entity::sources;
+    use sea_orm::EntityTrait;
+
+    let model = sources::Entity::find_by_id(source_id)
+        .one(db.inner())
+        .await?
+        .ok_or_else(|| AppError::SourceNotFound(source_id.to_string()))?;
+    Ok(parse_source_settings(&model.settings))
+}
+
+/// Save a source's sync filters. They apply from the next sync on.
+#[tauri::command]
+pub async fn set_source_settings(
+    db: State<'_, DatabaseConnection>,
+    source_id: i64,
+    settings: SourceSettings,
+) -> AppResult<SourceSettings> {
+    use sea_orm::{ConnectionTrait, DatabaseBackend, Statement};
+
+    if let (Some(min), Some(max)) = (settings.min_file_size, settings.max_file_size) {
+        if min > max {
+            return Err(AppError::Other(
+                "Minimum file size is larger than the maximum".to_string(),
+            ));
+        }
+    }
+    let clean = |list: Vec<String>| -> Vec<String> {
+        let mut list: Vec<String> = list
+            .iter()
+            .map(|s| s.trim().trim_start_matches('.').to_lowercase())
+            .filter(|s| !s.is_empty())
+            .collect();
+        list.sort();
+        list.dedup();
+        list
+    };
+    let settings = SourceSettings {
+        include_platforms: clean(settings.include_platforms),
+        exclude_platforms: clean(settings.exclude_platforms),
+        min_file_size: settings.min_file_size,
+        max_file_size: settings.max_file_size,
+        include_extensions: clean(settings.include_extensions),
+        exclude_extensions: clean(settings.exclude_extensions),
+    };
+
+    let json = serde_json::to_string(&settings).map_err(|e| AppError::Other(e.to_string()))?;
+    let result = db
+        .inner()
+        .execute(Statement::from_sql_and_values(
+            DatabaseBackend::Sqlite,
+            "UPDATE sources SET settings = ?, updated_at = strftime('%Y-%m-%dT%H:%M:%fZ', 'now') WHERE id = ?",
+            [json.into(), source_id.into()],
+        ))
+        .await?;
+    if result.rows_affected() == 0 {
+        return Err(AppError::SourceNotFound(source_id.to_string()));
+    }
+    Ok(settings)
 }
 
 #[tauri::command]
@@ -265,176 +612,513 @@ pub async fn remove_source(
     .await?;
 
     txn.commit().await?;
+    crate::secrets::forget_source(source_id);
     Ok(())
 }
 
-#[tauri::command]
-pub async fn sync_source(
-    db: State<'_, DatabaseConnection>,
-    cancel_tokens: State<'_, CancelTokenMap>,
+/// Scan a local source or pull a ROMM source into the library.
+pub(crate) async fn sync_source_to_db(
+    db: &DatabaseConnection,
     source_id: i64,
-    channel: Channel<ScanProgress>,
+    on_progress: impl Fn(ScanProgress) + Send,
+    cancel: CancellationToken,
 ) -> AppResult<()> {
-    // Get source info
     use crate::entity::sources;
     use sea_orm::EntityTrait;
 
     let source = sources::Entity::find_by_id(source_id)
-        .one(db.inner())
+        .one(db)
         .await?
         .ok_or_else(|| AppError::SourceNotFound(source_id.to_string()))?;
 
-    let (url_opt, credentials, source_type) = (source.url, source.credentials, source.source_type);
+    let settings = parse_source_settings(&source.settings);
+    let (url_opt, credentials, source_type) = (
+        source.url,
+        crate::secrets::open_credentials(&source.credentials),
+        source.source_type,
+    );
+    let detail = format!("{} (source {source_id})", source.name);
+
+    let sync = async move {
+        match source_type {
+            crate::entity::sources::SourceType::Local => {
+                let creds: HashMap<String, String> =
+                    serde_json::from_str(&credentials).map_err(|e| AppError::Other(e.to_string()))?;
+                let path = creds
+                    .get("path")
+                    .ok_or_else(|| AppError::Other("Missing path in credentials".to_string()))?
+                    .clone();
+                let root = std::path::PathBuf::from(path);
+                local_sync::sync_local_to_db(source_id, &root, &settings, db, on_progress, cancel)
+                    .await
+            }
+            crate::entity::sources::SourceType::Romm => {
+                let url = url_opt.ok_or_else(|| {
+                    AppError::Other("Source has no URL configured".to_string())
+                })?;
+                let creds: HashMap<String, String> =
+                    serde_json::from_str(&credentials).map_err(|e| AppError::Other(e.to_string()))?;
+                let has_login = ["username", "password"]
+                    .iter()
+                    .all(|k| creds.get(*k).is_some_and(|v| !v.is_empty()));
+                if !has_login && creds.get("api_token").is_none_or(String::is_empty) {
+                    return Err(AppError::Auth(
+                        "Missing API token or username and password in credentials".to_string(),
+                    ));
+                }
+                let client = RommClient::from_source(url, &credentials);
+                client
+                    .sync_to_db(source_id, &settings, db, on_progress, cancel)
+                    .await
+            }
+        }
+    };
+    crate::operation_log::timed(db, "sync", Some(&detail), sync).await
+}
 
-    let cancel = CancellationToken::new();
-    cancel_tokens
-        .0
-        .lock()
-        .await
-        .insert(CancelKey::Source(source_id), cancel.clone());
+#[tauri::command]
+pub async fn sync_source(
+    app: tauri::AppHandle,
+    db: State<'_, DatabaseConnection>,
+    jobs: State<'_, JobManager>,
+    source_id: i64,
+    channel: Channel<ScanProgress>,
+) -> AppResult<SyncSummary> {
+    let job = jobs.start(JobKind::Sync, Some(source_id), "Syncing source");
+    let cancel = job.token();
 
     let db_ref = db.inner();
+    let rom_ids_before = source_rom_ids(db_ref, source_id).await?;
 
-    let result = match source_type {
-        crate::entity::sources::SourceType::Local => {
-            let creds: HashMap<String, String> =
-                serde_json::from_str(&credentials).map_err(|e| AppError::Other(e.to_string()))?;
-            let path = creds
-                .get("path")
-                .ok_or_else(|| AppError::Other("Missing path in credentials".to_string()))?
-                .clone();
-            let root = std::path::PathBuf::from(path);
-            local_sync::sync_local_to_db(source_id, &root, db_ref, move |progress| {
-                let _ = channel.send(progress);
-            }, cancel)
-            .await
-        }
-        crate::entity::sources::SourceType::Romm => {
-            let url = url_opt.ok_or_else(|| {
-                AppError::Other("Source has no URL configured".to_string())
-            })?;
-            let creds: HashMap<String, String> =
-                serde_json::from_str(&credentials).map_err(|e| AppError::Other(e.to_string()))?;
-            let username = creds
-                .get("username")
-                .ok_or_else(|| AppError::Other("Missing username in credentials".to_string()))?
-                .clone();
-            let password = creds
-                .get("password")
-                .ok_or_else(|| AppError::Other("Missing password in credentials".to_string()))?
-                .clone();
-            let client = RommClient::new(url, username, password);
-            client.sync_to_db(source_id, db_ref, move |progress| {
-                let _ = channel.send(progress);
-            }, cancel)
+    let result = sync_source_to_db(
+        db_ref,
+        source_id,
+        job.reporter(move |progress| {
+            let _ = channel.send(progress);
+        }),
+        cancel.clone(),
+    )
+    .await;
+
+    // Syncs can link the same game from several sources; merge those right away.
+    // This is still the sync job, so `cancel_sync` also stops the dedup pass.
+    let run_dedup = result.is_ok() && !cancel.is_cancelled() && read_auto_dedup_from_store(&app);
+    let merged_duplicates = if run_dedup {
+        crate::dedup::reconcile_duplicates(db_ref, &cancel, &LibraryEvents::new(&app))
             .await
-        }
+            .unwrap_or_else(|e| {
+                log::warn!("Post-sync dedup failed: {e}");
+                0
+            })
+    } else {
+        0
     };
 
-    cancel_tokens.0.lock().await.remove(&CancelKey::Source(source_id));
-    result
-}
+    job.finish(&result);
+    result?;
 
-#[tauri::command]
-pub async fn cancel_sync(
-    cancel_tokens: State<'_, CancelTokenMap>,
-    source_id: i64,
-) -> AppResult<()> {
-    if let Some(token) = cancel_tokens.0.lock().await.get(&CancelKey::Source(source_id)) {
-        token.cancel();
+    let rom_count = count_query(
+        db_ref,
+        "SELECT COUNT(*) FROM source_roms WHERE source_id = ?",
+        vec![source_id.into()],
+    )
+    .await?;
+
+    let added: Vec<i64> = source_rom_ids(db_ref, source_id)
+        .await?
+        .difference(&rom_ids_before)
+        .copied()
+        .collect();
+    crate::activity::roms_added(db_ref, source_id, added.len()).await;
+    LibraryEvents::new(&app).roms_added(source_id, added);
+
+    {
+        use tauri::Emitter;
+        let _ = app.emit(
+            LIBRARY_CHANGED_EVENT,
+            LibraryChanged { source_id: Some(source_id), merged_duplicates },
+        );
     }
-    Ok(())
+
+    Ok(SyncSummary {
+        source_id,
+        rom_count,
+        merged_duplicates,
+        cancelled: cancel.is_cancelled(),
+    })
 }
 
-#[derive(Debug, sea_orm::FromQueryResult)]
-struct RomWithMetaRow {
-    id: i64,
-    platform_id: i64,
-    platform_slug: String,
-    platform_name: String,
-    name: String,
-    file_name: String,
-    file_size: Option<i64>,
-    regions: crate::entity::json_vec::JsonVec,
-    description: Option<String>,
-    rating: Option<f64>,
-    release_date: Option<String>,
-    developer: Option<String>,
-    publisher: Option<String>,
-    genres: crate::entity::json_vec::JsonVec,
-    themes: crate::entity::json_vec::JsonVec,
-    languages: crate::entity::json_vec::JsonVec,
-    cover_url: Option<String>,
-    retroachievements_game_id: Option<String>,
-    wikipedia_url: Option<String>,
-    igdb_id: Option<i64>,
-    thegamesdb_game_id: Option<String>,
+/// ROMs currently linked to a source.
+async fn source_rom_ids(
+    db: &DatabaseConnection,
     source_id: i64,
-    source_rom_id: Option<String>,
-    source_type: Option<crate::entity::sources::SourceType>,
-    favorite: i64,
-    verification_status: Option<crate::entity::roms::VerificationStatus>,
-    dat_game_name: Option<String>,
+) -> AppResult<std::collections::HashSet<i64>> {
+    use sea_orm::{ConnectionTrait, DatabaseBackend, Statement};
+
+    Ok(db
+        .query_all(Statement::from_sql_and_values(
+            DatabaseBackend::Sqlite,
+            "SELECT rom_id FROM source_roms WHERE source_id = ?",
+            [source_id.into()],
+        ))
+        .await?
+        .into_iter()
+        .filter_map(|r| r.try_get::<i64>("", "rom_id").ok())
+        .collect())
 }
 
-impl RomWithMetaRow {
-    fn into_rom_with_meta(self) -> RomWithMeta {
-        RomWithMeta {
-            id: self.id,
-            platform_id: self.platform_id,
-            platform_slug: self.platform_slug,
-            platform_name: self.platform_name,
-            name: self.name,
-            file_name: self.file_name,
-            file_size: self.file_size,
-            regions: self.regions.into_inner(),
-            description: self.description,
-            rating: self.rating,
-            release_date: self.release_date,
-            developer: self.developer,
-            publisher: self.publisher,
-            genres: self.genres.into_inner(),
-            themes: self.themes.into_inner(),
-            languages: self.languages.into_inner(),
-            cover_url: self.cover_url,
-            screenshot_urls: vec![],
-            source_id: self.source_id,
-            source_rom_id: self.source_rom_id,
-            source_type: self.source_type,
-            retroachievements_game_id: self.retroachievements_game_id,
-            wikipedia_url: self.wikipedia_url,
-            igdb_id: self.igdb_id,
-            thegamesdb_game_id: self.thegamesdb_game_id,
-            favorite: self.favorite != 0,
-            verification_status: self.verification_status,
-            dat_game_name: self.dat_game_name,
-        }
+/// Backfill play history from RetroArch's content history playlist. `path`
+/// defaults to the playlist RetroArch itself uses.
+#[tauri::command]
+pub async fn import_retroarch_history(
+    app: tauri::AppHandle,
+    db: State<'_, DatabaseConnection>,
+    path: Option<String>,
+) -> AppResult<HistoryImportReport> {
+    let lpl_path = path
+        .map(std::path::PathBuf::from)
+        .or_else(crate::play_history::find_content_history)
+        .ok_or_else(|| AppError::NotFound("RetroArch content history".to_string()))?;
+
+    let report = crate::play_history::import_content_history(db.inner(), &lpl_path).await?;
+    if report.updated > 0 {
+        use tauri::Emitter;
+        let _ = app.emit(
+            LIBRARY_CHANGED_EVENT,
+            LibraryChanged { source_id: None, merged_duplicates: 0 },
+        );
     }
+    Ok(report)
 }
 
-const ROM_WITH_META_SELECT: &str =
-    "SELECT r.id, r.platform_id, p.slug as platform_slug, p.name as platform_name,
-            r.name, r.file_name, r.file_size, r.regions,
-            m.description, m.rating, m.release_date, m.developer, m.publisher,
-            COALESCE(m.genres, '[]') as genres,
-            COALESCE(m.themes, '[]') as themes,
-            COALESCE(r.languages, '[]') as languages,
-            (SELECT url FROM artwork WHERE rom_id = r.id AND art_type = 'cover' LIMIT 1) as cover_url,
-            hc.retroachievements_game_id,
-            hc.wikipedia_url,
-            m.igdb_id,
+/// Event emitted whenever a sync (or anything else) changes which ROMs exist.
+pub const LIBRARY_CHANGED_EVENT: &str = "library-changed";
+
+pub(crate) fn read_auto_dedup_from_store(app: &impl SettingsRead) -> bool {
+    app.setting("auto_dedup_after_sync")
+        .and_then(|v| v.as_bool())
+        .unwrap_or(true)
+}
+
+#[tauri::command]
+pub async fn get_auto_dedup(app: tauri::AppHandle) -> AppResult<bool> {
+    Ok(read_auto_dedup_from_store(&app))
+}
+
+/// Toggle merging duplicate ROMs at the end of every sync. On by default.
+#[tauri::command]
+pub async fn set_auto_dedup(app: tauri::AppHandle, enabled: bool) -> AppResult<()> {
+    let store = app
+        .store("settings.json")
+        .map_err(|e| AppError::Other(e.to_string()))?;
+    store.set("auto_dedup_after_sync", serde_json::json!(enabled));
+    store
+        .save()
+        .map_err(|e| AppError::Other(e.to_string()))?;
+    crate::settings::save_to_db(&app).await?;
+    Ok(())
+}
+
+/// Reorganize a local source's ROM files into `layout` (e.g. `esde`, `batocera`).
+/// Returns a dry-run plan unless `dry_run` is explicitly `false`.
+#[tauri::command]
+pub async fn organize_local_source(
+    db: State<'_, DatabaseConnection>,
+    source_id: i64,
+    layout: local_sync::FolderLayout,
+    dry_run: Option<bool>,
+) -> AppResult<crate::models::OrganizePlan> {
+    use crate::entity::sources;
+    use sea_orm::EntityTrait;
+
+    let source = sources::Entity::find_by_id(source_id)
+        .one(db.inner())
+        .await?
+        .ok_or_else(|| AppError::SourceNotFound(source_id.to_string()))?;
+    if source.source_type != crate::entity::sources::SourceType::Local {
+        return Err(AppError::Other("Only local sources can be organized".to_string()));
+    }
+
+    let creds: HashMap<String, String> =
+        serde_json::from_str(&source.credentials).map_err(|e| AppError::Other(e.to_string()))?;
+    let root = creds
+        .get("path")
+        .map(std::path::PathBuf::from)
+        .ok_or_else(|| AppError::Other("Missing path in credentials".to_string()))?;
+
+    local_sync::organize_local_source(
+        db.inner(),
+        source_id,
+        &root,
+        &layout,
+        dry_run.unwrap_or(true),
+    )
+    .await
+}
+
+/// Copy ROMs, covers, gamelists and collections for the chosen platforms
+/// onto a device folder (SD card) in `layout`. An empty `platform_ids`
+/// exports every non-archived platform. For a Steam Deck, `emudeck` with its
+/// `Emulation` folder as `target` provisions ROMs, ES-DE media and collections.
+#[tauri::command]
+#[allow(clippy::too_many_arguments)]
+pub async fn export_to_device(
+    db: State<'_, DatabaseConnection>,
+    jobs: State<'_, JobManager>,
+    target: String,
+    layout: local_sync::FolderLayout,
+    platform_ids: Vec<i64>,
+    favorites_only: Option<bool>,
+    include_artwork: Option<bool>,
+    include_gamelists: Option<bool>,
+    include_collections: Option<bool>,
+    channel: Channel<ScanProgress>,
+) -> AppResult<crate::models::DeviceExportReport> {
+    let opts = crate::export::ExportOptions {
+        target: std::path::PathBuf::from(target),
+        layout,
+        platform_ids,
+        favorites_only: favorites_only.unwrap_or(false),
+        include_artwork: include_artwork.unwrap_or(true),
+        include_gamelists: include_gamelists.unwrap_or(true),
+        include_collections: include_collections.unwrap_or(true),
+    };
+    let job = jobs.start(JobKind::DeviceExport, None, "Exporting to a device");
+    let result = crate::export::export_to_device(
+        db.inner(),
+        &opts,
+        job.reporter(move |p| { let _ = channel.send(p); }),
+        &job.token(),
+    )
+    .await;
+    job.finish(&result);
+    result
+}
+
+#[tauri::command]
+pub async fn cancel_device_export(jobs: State<'_, JobManager>) -> AppResult<()> {
+    jobs.cancel_kind(JobKind::DeviceExport, None);
+    Ok(())
+}
+
+fn read_mister_from_store(app: &tauri::AppHandle) -> AppResult<MisterConfig> {
+    app.setting("mister")
+        .and_then(|v| serde_json::from_value(v).ok())
+        .ok_or_else(|| AppError::Other("No MiSTer is set up".to_string()))
+}
+
+#[tauri::command]
+pub async fn get_mister_config(app: tauri::AppHandle) -> AppResult<Option<MisterConfig>> {
+    Ok(read_mister_from_store(&app).ok())
+}
+
+/// Set the MiSTer to export to, after checking it can be reached.
+/// `None` forgets it.
+#[tauri::command]
+pub async fn configure_mister(
+    app: tauri::AppHandle,
+    config: Option<MisterConfig>,
+) -> AppResult<()> {
+    if let Some(config) = &config {
+        crate::mister::test(config).await?;
+    }
+    let store = app
+        .store("settings.json")
+        .map_err(|e| AppError::Other(e.to_string()))?;
+    match config {
+        Some(config) => store.set(
+            "mister",
+            crate::secrets::seal_setting_object("mister", serde_json::json!(config)),
+        ),
+        None => {
+            store.delete("mister");
+            crate::secrets::forget_setting_object("mister");
+        }
+    }
+    store
+        .save()
+        .map_err(|e| AppError::Other(e.to_string()))?;
+    Ok(())
+}
+
+/// Upload the selected ROMs into the MiSTer's games folders. Cancelled
+/// with `cancel_device_export`.
+#[tauri::command]
+pub async fn export_to_mister(
+    app: tauri::AppHandle,
+    db: State<'_, DatabaseConnection>,
+    jobs: State<'_, JobManager>,
+    rom_ids: Vec<i64>,
+    channel: Channel<ScanProgress>,
+) -> AppResult<crate::models::DeviceExportReport> {
+    let config = read_mister_from_store(&app)?;
+    let job = jobs.start(JobKind::DeviceExport, None, "Exporting to MiSTer");
+    let result = crate::mister::export(
+        db.inner(),
+        &config,
+        &rom_ids,
+        job.reporter(move |p| { let _ = channel.send(p); }),
+        &job.token(),
+    )
+    .await;
+    job.finish(&result);
+    result
+}
+
+/// Start an exported ROM on the MiSTer through MiSTer Remote.
+#[tauri::command]
+pub async fn launch_on_mister(
+    app: tauri::AppHandle,
+    db: State<'_, DatabaseConnection>,
+    rom_id: i64,
+) -> AppResult<()> {
+    let config = read_mister_from_store(&app)?;
+    let client = crate::http::client_builder()
+        .user_agent("romm-buddy/0.1")
+        .timeout(std::time::Duration::from_secs(15))
+        .build()
+        .unwrap_or_default();
+    crate::mister::launch(db.inner(), &client, &config, rom_id).await
+}
+
+#[tauri::command]
+pub async fn cancel_sync(
+    jobs: State<'_, JobManager>,
+    source_id: i64,
+) -> AppResult<()> {
+    jobs.cancel_kind(JobKind::Sync, Some(source_id));
+    Ok(())
+}
+
+#[derive(Debug, sea_orm::FromQueryResult)]
+struct RomWithMetaRow {
+    id: i64,
+    platform_id: i64,
+    platform_slug: String,
+    platform_name: String,
+    name: String,
+    file_name: String,
+    file_size: Option<i64>,
+    regions: crate::entity::json_vec::JsonVec,
+    description: Option<String>,
+    rating: Option<f64>,
+    release_date: Option<String>,
+    developer: Option<String>,
+    publisher: Option<String>,
+    genres: crate::entity::json_vec::JsonVec,
+    themes: crate::entity::json_vec::JsonVec,
+    languages: crate::entity::json_vec::JsonVec,
+    revision: Option<String>,
+    dump_flags: crate::entity::json_vec::JsonVec,
+    cover_url: Option<String>,
+    retroachievements_game_id: Option<String>,
+    wikipedia_url: Option<String>,
+    igdb_id: Option<i64>,
+    thegamesdb_game_id: Option<String>,
+    source_id: i64,
+    source_rom_id: Option<String>,
+    source_type: Option<crate::entity::sources::SourceType>,
+    favorite: i64,
+    verification_status: Option<crate::entity::roms::VerificationStatus>,
+    dat_game_name: Option<String>,
+    metadata_pinned: i64,
+    user_edited: crate::entity::json_vec::JsonVec,
+    esrb_rating: Option<String>,
+    pegi_rating: Option<String>,
+    note: Option<String>,
+    tags: crate::entity::json_vec::JsonVec,
+    completion_status: Option<CompletionStatus>,
+    homebrew_kind: Option<crate::entity::rom_homebrew::HomebrewKind>,
+}
+
+impl RomWithMetaRow {
+    fn into_rom_with_meta(self) -> RomWithMeta {
+        RomWithMeta {
+            id: self.id,
+            platform_id: self.platform_id,
+            platform_slug: self.platform_slug,
+            platform_name: self.platform_name,
+            name: self.name,
+            file_name: self.file_name,
+            file_size: self.file_size,
+            regions: self.regions.into_inner(),
+            description: self.description,
+            rating: self.rating,
+            release_date: self.release_date,
+            developer: self.developer,
+            publisher: self.publisher,
+            genres: self.genres.into_inner(),
+            themes: self.themes.into_inner(),
+            languages: self.languages.into_inner(),
+            revision: self.revision,
+            dump_flags: self.dump_flags.into_inner(),
+            cover_url: self.cover_url,
+            screenshot_urls: vec![],
+            source_id: self.source_id,
+            source_rom_id: self.source_rom_id,
+            source_type: self.source_type,
+            retroachievements_game_id: self.retroachievements_game_id,
+            wikipedia_url: self.wikipedia_url,
+            igdb_id: self.igdb_id,
+            thegamesdb_game_id: self.thegamesdb_game_id,
+            favorite: self.favorite != 0,
+            verification_status: self.verification_status,
+            dat_game_name: self.dat_game_name,
+            metadata_pinned: self.metadata_pinned != 0,
+            user_edited: self.user_edited.into_inner(),
+            esrb_rating: self.esrb_rating,
+            pegi_rating: self.pegi_rating,
+            note: self.note,
+            tags: self.tags.into_inner(),
+            completion_status: self.completion_status.unwrap_or(CompletionStatus::Unplayed),
+            homebrew_kind: self.homebrew_kind,
+            other_platforms: vec![],
+        }
+    }
+}
+
+const ROM_WITH_META_SELECT: &str =
+    "SELECT r.id, r.platform_id, p.slug as platform_slug, p.name as platform_name,
+            r.name, r.file_name, r.file_size, r.regions,
+            m.description, m.rating, m.release_date, m.developer, m.publisher,
+            COALESCE(m.genres, '[]') as genres,
+            COALESCE(m.themes, '[]') as themes,
+            COALESCE(r.languages, '[]') as languages,
+            r.revision, r.dump_flags,
+            cover.url as cover_url,
+            hc.retroachievements_game_id,
+            hc.wikipedia_url,
+            m.igdb_id,
             hc.thegamesdb_game_id,
             sr.source_id, sr.source_rom_id, s.source_type,
-            COALESCE((SELECT MAX(favorite) FROM library l WHERE l.rom_id = r.id), 0) as favorite,
-            r.verification_status, r.dat_game_name
+            COALESCE(lib.favorite, 0) as favorite,
+            r.verification_status, r.dat_game_name,
+            COALESCE(m.pinned, 0) as metadata_pinned,
+            (SELECT json_group_array(field) FROM metadata_edits WHERE rom_id = r.id) as user_edited,
+            m.esrb_rating, m.pegi_rating,
+            (SELECT note FROM rom_notes WHERE rom_id = r.id) as note,
+            (SELECT json_group_array(tag)
+             FROM (SELECT tag FROM rom_tags WHERE rom_id = r.id ORDER BY tag)) as tags,
+            lib.status as completion_status,
+            (SELECT kind FROM rom_homebrew WHERE rom_id = r.id) as homebrew_kind
      FROM roms r
      JOIN platforms p ON p.id = r.platform_id";
 
+/// Joins `ROM_WITH_META_SELECT` and `LIBRARY_ORDER` read from: the first cover
+/// (`cover`) and the current profile's library state (`lib`). Each is
+/// aggregated once per query instead of once per row.
+const ROM_STATE_JOINS: &str =
+    " LEFT JOIN (SELECT rom_id, MIN(url) AS url FROM artwork
+                WHERE art_type = 'cover' GROUP BY rom_id) cover ON cover.rom_id = r.id
+      LEFT JOIN (SELECT rom_id, MAX(favorite) AS favorite, MAX(status) AS status,
+                        MAX(last_played_at) AS last_played_at
+                 FROM profile_library GROUP BY rom_id) lib ON lib.rom_id = r.id";
+
 /// Default library sort: last-played first (most recent on top), then a
 /// deterministic pseudo-random shuffle for everything else (stable across pages).
 const LIBRARY_ORDER: &str =
-    "(SELECT MAX(l.last_played_at) FROM library l WHERE l.rom_id = r.id) IS NULL,
-     (SELECT MAX(l.last_played_at) FROM library l WHERE l.rom_id = r.id) DESC,
+    "lib.last_played_at IS NULL,
+     lib.last_played_at DESC,
      (r.id * 2654435761) % 4294967296";
 
 /// Helper: execute a raw count query with dynamic values via SeaORM.
@@ -454,82 +1138,252 @@ async fn query_rom_rows(db: &DatabaseConnection, sql: &str, values: Vec<sea_orm:
     Ok(RomWithMetaRow::find_by_statement(stmt).all(db).await?)
 }
 
+const DEFAULT_LIBRARY_PAGE_SIZE: i64 = 50;
+const MAX_LIBRARY_PAGE_SIZE: i64 = 500;
+
+/// Read the library page size limits from settings, falling back to the built-in defaults.
+fn read_library_page_limits(app: &tauri::AppHandle) -> LibraryPageLimits {
+    let store = app.store("settings.json").ok();
+    let read = |key: &str| {
+        store
+            .as_ref()
+            .and_then(|s| s.get(key))
+            .and_then(|v| v.as_i64())
+            .filter(|v| *v > 0)
+    };
+    let max_limit = read("library_max_page_size").unwrap_or(MAX_LIBRARY_PAGE_SIZE);
+    let default_limit = read("library_default_page_size")
+        .unwrap_or(DEFAULT_LIBRARY_PAGE_SIZE)
+        .min(max_limit);
+    LibraryPageLimits {
+        default_limit,
+        max_limit,
+    }
+}
+
+#[tauri::command]
+pub async fn get_library_page_limits(app: tauri::AppHandle) -> AppResult<LibraryPageLimits> {
+    Ok(read_library_page_limits(&app))
+}
+
+#[tauri::command]
+pub async fn set_library_page_limits(
+    app: tauri::AppHandle,
+    default_limit: i64,
+    max_limit: i64,
+) -> AppResult<LibraryPageLimits> {
+    if default_limit < 1 || max_limit < 1 {
+        return Err(AppError::Other("Page sizes must be at least 1".to_string()));
+    }
+    let store = app.store("settings.json")
+        .map_err(|e| AppError::Other(e.to_string()))?;
+    store.set("library_default_page_size", serde_json::json!(default_limit.min(max_limit)));
+    store.set("library_max_page_size", serde_json::json!(max_limit));
+    store.save().map_err(|e| AppError::Other(e.to_string()))?;
+    crate::settings::save_to_db(&app).await?;
+    Ok(read_library_page_limits(&app))
+}
+
+/// Get a page of library ROMs. `limit` defaults to and is capped by the
+/// configured page size limits. With `prefetch_next_page`, the following
+/// page's query is run in the background so SQLite's page cache is warm
+/// when the frontend asks for it.
 #[tauri::command]
+#[allow(clippy::too_many_arguments)]
 pub async fn get_library_roms(
+    app: tauri::AppHandle,
     db: State<'_, DatabaseConnection>,
     platform_id: Option<i64>,
     search: Option<String>,
     favorites_only: Option<bool>,
+    archived: Option<bool>,
+    tags: Option<Vec<String>>,
+    status: Option<CompletionStatus>,
+    language: Option<String>,
     offset: i64,
-    limit: i64,
+    limit: Option<i64>,
+    prefetch_next_page: Option<bool>,
 ) -> AppResult<LibraryPage> {
+    let limits = read_library_page_limits(&app);
+    let limit = limit.unwrap_or(limits.default_limit).clamp(1, limits.max_limit);
+    let offset = offset.max(0);
     let favorites_only = favorites_only.unwrap_or(false);
+    let archived = archived.unwrap_or(false);
+    let tags = tags.unwrap_or_default();
 
-    // Build query based on filters
-    let (rows, total) = if let Some(ref query) = search {
-        if query.trim().is_empty() {
-            return get_library_roms_filtered(db, platform_id, favorites_only, offset, limit)
-                .await;
-        }
-        // FTS search
-        let search_query = format!("{}*", query.replace('"', ""));
+    let page = query_library_page(
+        db.inner(),
+        platform_id,
+        search.as_deref(),
+        favorites_only,
+        archived,
+        &tags,
+        status,
+        language.as_deref(),
+        offset,
+        limit,
+    )
+    .await?;
 
-        let fav_clause = if favorites_only {
-            " AND EXISTS (SELECT 1 FROM library l WHERE l.rom_id = r.id AND l.favorite = 1)"
-        } else {
-            ""
-        };
+    if prefetch_next_page.unwrap_or(false) && offset + limit < page.total {
+        let db = db.inner().clone();
+        tauri::async_runtime::spawn(async move {
+            if let Err(e) = query_library_page(
+                &db,
+                platform_id,
+                search.as_deref(),
+                favorites_only,
+                archived,
+                &tags,
+                status,
+                language.as_deref(),
+                offset + limit,
+                limit,
+            )
+            .await
+            {
+                log::debug!("Library prefetch failed: {e}");
+            }
+        });
+    }
 
-        let count = if let Some(pid) = platform_id {
-            let q = format!(
-                "SELECT COUNT(*) FROM roms r
-                 JOIN roms_fts ON roms_fts.rowid = r.id
-                 WHERE roms_fts MATCH ? AND r.platform_id = ?{fav_clause}"
-            );
-            count_query(db.inner(), &q, vec![search_query.clone().into(), pid.into()]).await?
-        } else {
-            let q = format!(
-                "SELECT COUNT(*) FROM roms r
-                 JOIN roms_fts ON roms_fts.rowid = r.id
-                 WHERE roms_fts MATCH ?{fav_clause}"
-            );
-            count_query(db.inner(), &q, vec![search_query.clone().into()]).await?
-        };
+    Ok(page)
+}
 
-        let rows = if let Some(pid) = platform_id {
-            let q = format!(
-                "{ROM_WITH_META_SELECT} JOIN roms_fts ON roms_fts.rowid = r.id
-                 LEFT JOIN metadata m ON m.rom_id = r.id
+/// SQL condition selecting ROMs on non-archived platforms.
+const NOT_ARCHIVED_CLAUSE: &str = "r.platform_id IN (SELECT id FROM platforms WHERE archived = 0)";
+
+/// SQL condition selecting ROMs on archived platforms.
+const ARCHIVED_ONLY_CLAUSE: &str = "r.platform_id IN (SELECT id FROM platforms WHERE archived = 1)";
+
+/// SQL condition hiding ROMs the current profile's content filter excludes:
+/// rated above its `max_age`, or unrated unless it allows that.
+const CONTENT_RATING_CLAUSE: &str =
+    "NOT EXISTS (SELECT 1 FROM profiles pf
+                 WHERE pf.id = (SELECT id FROM current_profile) AND pf.max_age IS NOT NULL
+                   AND NOT COALESCE((SELECT mr.min_age FROM metadata mr WHERE mr.rom_id = r.id) <= pf.max_age,
+                                    pf.allow_unrated))";
+
+/// FTS join, `WHERE` clause and bound values for a library filter. Archived
+/// platforms are excluded unless `archived` is set (then only archived content
+/// is returned) or a specific platform is requested. ROMs must carry every tag
+/// in `tags`; ROMs without a status count as unplayed. `language` is an
+/// ISO 639-1 code the ROM must list. The profile's content filter always
+/// applies.
+fn library_filter(
+    platform_id: Option<i64>,
+    search: Option<&str>,
+    favorites_only: bool,
+    archived: bool,
+    tags: &[String],
+    status: Option<CompletionStatus>,
+    language: Option<&str>,
+) -> (&'static str, String, Vec<sea_orm::Value>) {
+    use sea_orm::ActiveEnum;
 
-                 LEFT JOIN hasheous_cache hc ON hc.rom_id = r.id
-                 LEFT JOIN source_roms sr ON sr.rom_id = r.id
-                 LEFT JOIN sources s ON s.id = sr.source_id
-                 WHERE roms_fts MATCH ? AND r.platform_id = ?{fav_clause}
-                 GROUP BY r.id
-                 ORDER BY {LIBRARY_ORDER}
-                 LIMIT ? OFFSET ?",
-            );
-            query_rom_rows(db.inner(), &q, vec![search_query.clone().into(), pid.into(), limit.into(), offset.into()]).await?
-        } else {
-            let q = format!(
-                "{ROM_WITH_META_SELECT} JOIN roms_fts ON roms_fts.rowid = r.id
-                 LEFT JOIN metadata m ON m.rom_id = r.id
+    let search_query = search.and_then(crate::search::fts_query);
 
-                 LEFT JOIN hasheous_cache hc ON hc.rom_id = r.id
-                 LEFT JOIN source_roms sr ON sr.rom_id = r.id
-                 LEFT JOIN sources s ON s.id = sr.source_id
-                 WHERE roms_fts MATCH ?{fav_clause}
-                 GROUP BY r.id
-                 ORDER BY {LIBRARY_ORDER}
-                 LIMIT ? OFFSET ?",
-            );
-            query_rom_rows(db.inner(), &q, vec![search_query.clone().into(), limit.into(), offset.into()]).await?
-        };
+    let mut conditions: Vec<&str> = vec![CONTENT_RATING_CLAUSE];
+    let mut values: Vec<sea_orm::Value> = Vec::new();
 
-        (rows, count)
+    if let Some(ref fts) = search_query {
+        conditions.push("roms_fts MATCH ?");
+        values.push(fts.clone().into());
+    }
+    if let Some(pid) = platform_id {
+        conditions.push("r.platform_id = ?");
+        values.push(pid.into());
+    } else if archived {
+        conditions.push(ARCHIVED_ONLY_CLAUSE);
+    } else {
+        conditions.push(NOT_ARCHIVED_CLAUSE);
+    }
+    if favorites_only {
+        conditions.push("EXISTS (SELECT 1 FROM profile_library l WHERE l.rom_id = r.id AND l.favorite = 1)");
+    }
+    for tag in tags.iter().map(|t| t.trim()).filter(|t| !t.is_empty()) {
+        conditions.push("EXISTS (SELECT 1 FROM rom_tags t WHERE t.rom_id = r.id AND t.tag = ?)");
+        values.push(tag.into());
+    }
+    if let Some(status) = status {
+        conditions.push(
+            "COALESCE((SELECT MAX(l.status) FROM profile_library l WHERE l.rom_id = r.id), 'unplayed') = ?",
+        );
+        values.push(status.to_value().into());
+    }
+    if let Some(language) = language.map(str::trim).filter(|l| !l.is_empty()) {
+        conditions.push("EXISTS (SELECT 1 FROM json_each(r.languages) WHERE json_each.value = ?)");
+        values.push(language.to_ascii_lowercase().into());
+    }
+
+    let fts_join = if search_query.is_some() {
+        " JOIN roms_fts ON roms_fts.rowid = r.id"
     } else {
-        return get_library_roms_filtered(db, platform_id, favorites_only, offset, limit).await;
+        ""
     };
+    let where_clause = format!("WHERE {}", conditions.join(" AND "));
+    (fts_join, where_clause, values)
+}
+
+/// Whether a ROM shows up in the library at all: its platform isn't
+/// archived and the current profile's content filter lets it through.
+pub(crate) async fn rom_visible(db: &DatabaseConnection, rom_id: i64) -> AppResult<bool> {
+    let (_, where_clause, mut values) = library_filter(None, None, false, false, &[], None, None);
+    values.push(rom_id.into());
+    let count = count_query(
+        db,
+        &format!("SELECT COUNT(*) FROM roms r {where_clause} AND r.id = ?"),
+        values,
+    )
+    .await?;
+    Ok(count > 0)
+}
+
+/// Full library query for a filter, without `LIMIT`/`OFFSET`.
+fn library_query(fts_join: &str, where_clause: &str) -> String {
+    format!(
+        "{ROM_WITH_META_SELECT}{ROM_STATE_JOINS}{fts_join}
+         LEFT JOIN metadata m ON m.rom_id = r.id
+         LEFT JOIN hasheous_cache hc ON hc.rom_id = r.id
+         LEFT JOIN source_roms sr ON sr.rom_id = r.id
+         LEFT JOIN sources s ON s.id = sr.source_id
+         {where_clause}
+         GROUP BY r.id
+         ORDER BY {LIBRARY_ORDER}",
+    )
+}
+
+/// Query one page of the library. See `library_filter` for archived handling.
+#[allow(clippy::too_many_arguments)]
+pub(crate) async fn query_library_page(
+    db: &DatabaseConnection,
+    platform_id: Option<i64>,
+    search: Option<&str>,
+    favorites_only: bool,
+    archived: bool,
+    tags: &[String],
+    status: Option<CompletionStatus>,
+    language: Option<&str>,
+    offset: i64,
+    limit: i64,
+) -> AppResult<LibraryPage> {
+    let (fts_join, where_clause, mut values) = library_filter(
+        platform_id,
+        search,
+        favorites_only,
+        archived,
+        tags,
+        status,
+        language,
+    );
+
+    let count_q = format!("SELECT COUNT(*) FROM roms r{fts_join} {where_clause}");
+    let total = count_query(db, &count_q, values.clone()).await?;
+
+    let q = format!("{} LIMIT ? OFFSET ?", library_query(fts_join, &where_clause));
+    values.push(limit.into());
+    values.push(offset.into());
+    let rows = query_rom_rows(db, &q, values).await?;
 
     Ok(LibraryPage {
         roms: rows
@@ -540,88 +1394,338 @@ pub async fn get_library_roms(
     })
 }
 
-async fn get_library_roms_filtered(
-    db: State<'_, DatabaseConnection>,
+/// Where launch grid cover thumbnails are cached.
+fn grid_cover_dir() -> std::path::PathBuf {
+    directories::ProjectDirs::from("com", "romm-buddy", "romm-buddy").map_or_else(
+        || std::path::PathBuf::from("grid_covers"),
+        |p| p.cache_dir().join("grid_covers"),
+    )
+}
+
+/// Cached thumbnail for a cover, named by the MD5 of its source URL so ROMs
+/// sharing artwork share the file and ids from another machine never collide.
+fn grid_cover_path(url: &str) -> std::path::PathBuf {
+    use md5::{Digest, Md5};
+    grid_cover_dir().join(format!("{:x}.png", Md5::digest(url.as_bytes())))
+}
+
+/// Lightweight library page for big-picture mode: id, name and a local cover
+/// thumbnail per ROM, in library order. Covers missing from the cache are
+/// fetched in the background, along with the next page's, so scrolling with a
+/// controller mostly hits local files.
+#[tauri::command]
+pub async fn get_launch_grid(
+    app: tauri::AppHandle,
+    db: State<'_, DatabaseConnection>,
+    platform_id: Option<i64>,
+    offset: Option<i64>,
+    limit: Option<i64>,
+) -> AppResult<LaunchGridPage> {
+    let limits = read_library_page_limits(&app);
+    let limit = limit.unwrap_or(limits.default_limit).clamp(1, limits.max_limit);
+    let offset = offset.unwrap_or(0).max(0);
+
+    let (rows, total) = query_launch_grid(db.inner(), platform_id, offset, limit).await?;
+
+    let db = db.inner().clone();
+    let mut to_warm: Vec<(i64, String)> = rows
+        .iter()
+        .filter(|r| r.cover_path.is_none())
+        .filter_map(|r| Some((r.id, r.cover_url.clone()?)))
+        .collect();
+    let prefetch = offset + limit < total;
+    tauri::async_runtime::spawn(async move {
+        if prefetch {
+            match query_launch_grid(&db, platform_id, offset + limit, limit).await {
+                Ok((next, _)) => to_warm.extend(
+                    next.into_iter()
+                        .filter(|r| r.cover_path.is_none())
+                        .filter_map(|r| Some((r.id, r.cover_url?))),
+                ),
+                Err(e) => log::debug!("Launch grid prefetch failed: {e}"),
+            }
+        }
+        warm_grid_covers(&db, to_warm).await;
+    });
+
+    Ok(LaunchGridPage { rows, total })
+}
+
+async fn query_launch_grid(
+    db: &DatabaseConnection,
     platform_id: Option<i64>,
-    favorites_only: bool,
     offset: i64,
     limit: i64,
-) -> AppResult<LibraryPage> {
-    let fav_clause = if favorites_only {
-        " EXISTS (SELECT 1 FROM library l WHERE l.rom_id = r.id AND l.favorite = 1)"
-    } else {
-        ""
-    };
+) -> AppResult<(Vec<LaunchGridRow>, i64)> {
+    use sea_orm::{DatabaseBackend, FromQueryResult, Statement};
 
-    let (rows, total) = if let Some(pid) = platform_id {
-        let where_clause = if favorites_only {
-            format!("WHERE r.platform_id = ? AND{fav_clause}")
-        } else {
-            "WHERE r.platform_id = ?".to_string()
-        };
+    #[derive(Debug, FromQueryResult)]
+    struct GridRow {
+        id: i64,
+        name: String,
+        cover_url: Option<String>,
+    }
 
-        let count_q = format!("SELECT COUNT(*) FROM roms r {where_clause}");
-        let count = count_query(db.inner(), &count_q, vec![pid.into()]).await?;
+    let (_, where_clause, mut values) =
+        library_filter(platform_id, None, false, false, &[], None, None);
+    let total = count_query(
+        db,
+        &format!("SELECT COUNT(*) FROM roms r {where_clause}"),
+        values.clone(),
+    )
+    .await?;
 
-        let q = format!(
-            "{ROM_WITH_META_SELECT} LEFT JOIN metadata m ON m.rom_id = r.id
-             LEFT JOIN artwork a ON a.rom_id = r.id AND a.art_type = 'cover'
-             LEFT JOIN hasheous_cache hc ON hc.rom_id = r.id
-             LEFT JOIN source_roms sr ON sr.rom_id = r.id
-             LEFT JOIN sources s ON s.id = sr.source_id
+    values.push(limit.into());
+    values.push(offset.into());
+    let rows = GridRow::find_by_statement(Statement::from_sql_and_values(
+        DatabaseBackend::Sqlite,
+        format!(
+            "SELECT r.id, r.name, cover.url as cover_url
+             FROM roms r{ROM_STATE_JOINS}
              {where_clause}
-             GROUP BY r.id
              ORDER BY {LIBRARY_ORDER}
-             LIMIT ? OFFSET ?",
-        );
-        let rows = query_rom_rows(db.inner(), &q, vec![pid.into(), limit.into(), offset.into()]).await?;
+             LIMIT ? OFFSET ?"
+        ),
+        values,
+    ))
+    .all(db)
+    .await?;
 
-        (rows, count)
-    } else if favorites_only {
-        let where_clause = format!("WHERE{fav_clause}");
+    let rows = rows
+        .into_iter()
+        .map(|r| LaunchGridRow {
+            id: r.id,
+            name: r.name,
+            cover_path: r
+                .cover_url
+                .as_deref()
+                .map(grid_cover_path)
+                .filter(|p| p.exists())
+                .map(|p| p.to_string_lossy().into_owned()),
+            cover_url: r.cover_url,
+        })
+        .collect();
+    Ok((rows, total))
+}
 
-        let count_q = format!("SELECT COUNT(*) FROM roms r {where_clause}");
-        let count = count_query(db.inner(), &count_q, vec![]).await?;
+/// Download and shrink covers into the grid cache. ROMM-hosted covers are
+/// fetched with the credentials of the source whose URL they live under.
+async fn warm_grid_covers(db: &DatabaseConnection, covers: Vec<(i64, String)>) {
+    use crate::entity::sources;
+    use sea_orm::{ColumnTrait, EntityTrait, QueryFilter};
 
-        let q = format!(
-            "{ROM_WITH_META_SELECT} LEFT JOIN metadata m ON m.rom_id = r.id
-             LEFT JOIN artwork a ON a.rom_id = r.id AND a.art_type = 'cover'
-             LEFT JOIN hasheous_cache hc ON hc.rom_id = r.id
-             LEFT JOIN source_roms sr ON sr.rom_id = r.id
-             LEFT JOIN sources s ON s.id = sr.source_id
-             {where_clause}
-             GROUP BY r.id
-             ORDER BY {LIBRARY_ORDER}
-             LIMIT ? OFFSET ?",
-        );
-        let rows = query_rom_rows(db.inner(), &q, vec![limit.into(), offset.into()]).await?;
+    if covers.is_empty() || crate::http::is_offline() {
+        return;
+    }
+    let romm_clients: Vec<(String, RommClient)> = sources::Entity::find()
+        .filter(sources::Column::SourceType.eq("romm"))
+        .all(db)
+        .await
+        .unwrap_or_default()
+        .into_iter()
+        .filter_map(|s| {
+            let url = s.url?;
+            Some((url.clone(), RommClient::from_source(url, &s.credentials)))
+        })
+        .collect();
+    let http = crate::http::client_builder()
+        .timeout(std::time::Duration::from_secs(30))
+        .build()
+        .unwrap_or_default();
+    futures_util::stream::iter(covers)
+        .for_each_concurrent(4, |(rom_id, url)| {
+            let client = romm_clients
+                .iter()
+                .find(|(base, _)| url.starts_with(base.as_str()))
+                .map(|(_, c)| c);
+            let path = grid_cover_path(&url);
+            let http = &http;
+            async move {
+                if let Err(e) = crate::export::write_cover(client, http, &url, &path).await {
+                    log::debug!("Failed to cache grid cover for ROM {rom_id}: {e}");
+                }
+            }
+        })
+        .await;
+}
 
-        (rows, count)
-    } else {
-        let count = count_query(db.inner(), "SELECT COUNT(*) FROM roms", vec![]).await?;
+/// Default and maximum ROMs per batch for `stream_library_roms`.
+const DEFAULT_LIBRARY_BATCH_SIZE: usize = 200;
+const MAX_LIBRARY_BATCH_SIZE: usize = 2000;
 
-        let q = format!(
-            "{ROM_WITH_META_SELECT} LEFT JOIN metadata m ON m.rom_id = r.id
-             LEFT JOIN artwork a ON a.rom_id = r.id AND a.art_type = 'cover'
-             LEFT JOIN hasheous_cache hc ON hc.rom_id = r.id
-             LEFT JOIN source_roms sr ON sr.rom_id = r.id
-             LEFT JOIN sources s ON s.id = sr.source_id
-             GROUP BY r.id
-             ORDER BY {LIBRARY_ORDER}
-             LIMIT ? OFFSET ?",
-        );
-        let rows = query_rom_rows(db.inner(), &q, vec![limit.into(), offset.into()]).await?;
+/// Streaming variant of `get_library_roms` for large filters: rows are sent
+/// over `channel` in batches as SQLite yields them, so the UI can render before
+/// the whole result is materialized. Returns the total number of matching ROMs.
+#[tauri::command]
+#[allow(clippy::too_many_arguments)]
+pub async fn stream_library_roms(
+    db: State<'_, DatabaseConnection>,
+    platform_id: Option<i64>,
+    search: Option<String>,
+    favorites_only: Option<bool>,
+    archived: Option<bool>,
+    tags: Option<Vec<String>>,
+    status: Option<CompletionStatus>,
+    language: Option<String>,
+    batch_size: Option<usize>,
+    channel: Channel<LibraryBatch>,
+) -> AppResult<i64> {
+    use sea_orm::{DatabaseBackend, FromQueryResult, Statement};
 
-        (rows, count)
-    };
+    let batch_size = batch_size
+        .unwrap_or(DEFAULT_LIBRARY_BATCH_SIZE)
+        .clamp(1, MAX_LIBRARY_BATCH_SIZE);
+    let (fts_join, where_clause, values) = library_filter(
+        platform_id,
+        search.as_deref(),
+        favorites_only.unwrap_or(false),
+        archived.unwrap_or(false),
+        &tags.unwrap_or_default(),
+        status,
+        language.as_deref(),
+    );
 
-    Ok(LibraryPage {
-        roms: rows
-            .into_iter()
-            .map(RomWithMetaRow::into_rom_with_meta)
-            .collect(),
-        total,
-    })
+    let count_q = format!("SELECT COUNT(*) FROM roms r{fts_join} {where_clause}");
+    let total = count_query(db.inner(), &count_q, values.clone()).await?;
+
+    let q = library_query(fts_join, &where_clause);
+    let mut stream = RomWithMetaRow::find_by_statement(Statement::from_sql_and_values(
+        DatabaseBackend::Sqlite,
+        &q,
+        values,
+    ))
+    .stream(db.inner())
+    .await?;
+
+    let mut offset: i64 = 0;
+    let mut batch = Vec::with_capacity(batch_size);
+    while let Some(row) = stream.next().await {
+        batch.push(row?.into_rom_with_meta());
+        if batch.len() == batch_size {
+            let roms = std::mem::replace(&mut batch, Vec::with_capacity(batch_size));
+            #[allow(clippy::cast_possible_wrap)]
+            let sent = roms.len() as i64;
+            if channel.send(LibraryBatch { roms, offset, total, done: false }).is_err() {
+                // Webview is gone; nothing left to render into
+                return Ok(total);
+            }
+            offset += sent;
+        }
+    }
+    let _ = channel.send(LibraryBatch { roms: batch, offset, total, done: true });
+
+    Ok(total)
+}
+
+const DEFAULT_SUGGESTION_LIMIT: i64 = 8;
+const MAX_SUGGESTION_LIMIT: i64 = 50;
+
+/// Typeahead for the search box: up to `limit` ROM names (FTS, best match
+/// first) plus franchises, genres and platforms whose name has a word
+/// starting with `prefix`. Archived platforms and games the profile's
+/// content filter hides are left out.
+#[tauri::command]
+pub async fn search_suggest(
+    db: State<'_, DatabaseConnection>,
+    prefix: String,
+    limit: Option<i64>,
+) -> AppResult<Vec<SearchSuggestion>> {
+    use sea_orm::{DatabaseBackend, FromQueryResult, Statement};
+
+    #[derive(Debug, FromQueryResult)]
+    struct SuggestionRow {
+        id: Option<i64>,
+        label: String,
+        detail: Option<String>,
+        rom_count: Option<i64>,
+    }
+
+    let prefix = prefix.trim();
+    if crate::search::fts_query(prefix).is_none() {
+        return Ok(Vec::new());
+    }
+    let limit = limit
+        .unwrap_or(DEFAULT_SUGGESTION_LIMIT)
+        .clamp(1, MAX_SUGGESTION_LIMIT);
+
+    let (fts_join, where_clause, mut values) =
+        library_filter(None, Some(prefix), false, false, &[], None, None);
+    values.push(limit.into());
+    let roms = format!(
+        "SELECT r.id, r.name AS label, p.name AS detail, NULL AS rom_count
+         FROM roms r{fts_join}
+         JOIN platforms p ON p.id = r.platform_id
+         {where_clause}
+         ORDER BY roms_fts.rank
+         LIMIT ?"
+    );
+
+    let escaped = crate::search::like_escape(prefix);
+    let starts = format!("{escaped}%");
+    let word_starts = format!("% {escaped}%");
+    let franchises = format!(
+        "SELECT NULL AS id, MIN(ic.franchise_name) AS label, NULL AS detail, COUNT(*) AS rom_count
+         FROM igdb_cache ic JOIN roms r ON r.id = ic.rom_id
+         WHERE {NOT_ARCHIVED_CLAUSE} AND {CONTENT_RATING_CLAUSE}
+           AND (ic.franchise_name LIKE ? ESCAPE '\\' OR ic.franchise_name LIKE ? ESCAPE '\\')
+         GROUP BY ic.franchise_name COLLATE NOCASE
+         ORDER BY rom_count DESC LIMIT ?"
+    );
+    let genres = format!(
+        "SELECT NULL AS id, MIN(g.value) AS label, NULL AS detail, COUNT(*) AS rom_count
+         FROM metadata m JOIN roms r ON r.id = m.rom_id, json_each(m.genres) g
+         WHERE {NOT_ARCHIVED_CLAUSE} AND {CONTENT_RATING_CLAUSE}
+           AND (g.value LIKE ? ESCAPE '\\' OR g.value LIKE ? ESCAPE '\\')
+         GROUP BY g.value COLLATE NOCASE
+         ORDER BY rom_count DESC LIMIT ?"
+    );
+    let platforms = format!(
+        "SELECT p.id, p.name AS label, NULL AS detail, COUNT(*) AS rom_count
+         FROM platforms p JOIN roms r ON r.platform_id = p.id
+         WHERE p.archived = 0 AND {CONTENT_RATING_CLAUSE}
+           AND (p.name LIKE ? ESCAPE '\\' OR p.name LIKE ? ESCAPE '\\')
+         GROUP BY p.id
+         ORDER BY rom_count DESC LIMIT ?"
+    );
+
+    let queries = [
+        (SuggestionKind::Rom, roms, values),
+        (
+            SuggestionKind::Franchise,
+            franchises,
+            vec![starts.clone().into(), word_starts.clone().into(), limit.into()],
+        ),
+        (
+            SuggestionKind::Genre,
+            genres,
+            vec![starts.clone().into(), word_starts.clone().into(), limit.into()],
+        ),
+        (
+            SuggestionKind::Platform,
+            platforms,
+            vec![starts.into(), word_starts.into(), limit.into()],
+        ),
+    ];
+
+    let mut suggestions = Vec::new();
+    for (kind, sql, values) in queries {
+        let rows = SuggestionRow::find_by_statement(Statement::from_sql_and_values(
+            DatabaseBackend::Sqlite,
+            sql,
+            values,
+        ))
+        .all(db.inner())
+        .await?;
+        suggestions.extend(rows.into_iter().map(|row| SearchSuggestion {
+            kind,
+            highlights: crate::search::highlight_ranges(&row.label, prefix),
+            label: row.label,
+            id: row.id,
+            detail: row.detail,
+            rom_count: row.rom_count,
+        }));
+    }
+    Ok(suggestions)
 }
 
 #[tauri::command]
@@ -652,7 +1756,9 @@ pub async fn toggle_favorite(
     db.inner()
         .execute(Statement::from_sql_and_values(
             DatabaseBackend::Sqlite,
-            "INSERT INTO library (rom_id, source_id, favorite) VALUES (?, ?, ?) ON CONFLICT(rom_id, source_id) DO UPDATE SET favorite = excluded.favorite",
+            "INSERT INTO library (profile_id, rom_id, source_id, favorite)
+             VALUES ((SELECT id FROM current_profile), ?, ?, ?)
+             ON CONFLICT(profile_id, rom_id, source_id) DO UPDATE SET favorite = excluded.favorite",
             [rom_id.into(), source_id.into(), fav_val.into()],
         ))
         .await?;
@@ -668,7 +1774,7 @@ pub async fn get_favorites_count(db: State<'_, DatabaseConnection>) -> AppResult
         .inner()
         .query_one(Statement::from_string(
             DatabaseBackend::Sqlite,
-            "SELECT COUNT(DISTINCT rom_id) as cnt FROM library WHERE favorite = 1",
+            "SELECT COUNT(DISTINCT rom_id) as cnt FROM profile_library WHERE favorite = 1",
         ))
         .await?
         .ok_or_else(|| AppError::Other("Count query failed".to_string()))?;
@@ -676,51 +1782,604 @@ pub async fn get_favorites_count(db: State<'_, DatabaseConnection>) -> AppResult
     Ok(count)
 }
 
+/// Set where a ROM stands in the backlog.
+#[tauri::command]
+pub async fn set_rom_status(
+    db: State<'_, DatabaseConnection>,
+    rom_id: i64,
+    status: CompletionStatus,
+) -> AppResult<CompletionStatus> {
+    use sea_orm::{ActiveEnum, ConnectionTrait, DatabaseBackend, Statement};
+
+    // Same upsert as toggle_favorite, then bring the ROM's other library rows in line
+    db.inner()
+        .execute(Statement::from_sql_and_values(
+            DatabaseBackend::Sqlite,
+            "INSERT INTO library (profile_id, rom_id, source_id, status)
+             VALUES ((SELECT id FROM current_profile), ?,
+                     COALESCE((SELECT source_id FROM source_roms WHERE rom_id = ? LIMIT 1), 0), ?)
+             ON CONFLICT(profile_id, rom_id, source_id) DO UPDATE SET
+               status = excluded.status,
+               updated_at = strftime('%Y-%m-%dT%H:%M:%fZ', 'now')",
+            [rom_id.into(), rom_id.into(), status.to_value().into()],
+        ))
+        .await?;
+    db.inner()
+        .execute(Statement::from_sql_and_values(
+            DatabaseBackend::Sqlite,
+            "UPDATE library SET status = ?, updated_at = strftime('%Y-%m-%dT%H:%M:%fZ', 'now')
+             WHERE rom_id = ? AND profile_id = (SELECT id FROM current_profile)
+               AND status IS NOT ?",
+            [status.to_value().into(), rom_id.into(), status.to_value().into()],
+        ))
+        .await?;
+
+    Ok(status)
+}
+
+/// Number of ROMs in each completion status, across non-archived platforms.
+#[tauri::command]
+pub async fn get_status_counts(db: State<'_, DatabaseConnection>) -> AppResult<Vec<StatusCount>> {
+    use sea_orm::{DatabaseBackend, FromQueryResult, Iterable, Statement};
+
+    #[derive(Debug, FromQueryResult)]
+    struct StatusCountRow {
+        status: CompletionStatus,
+        rom_count: i64,
+    }
+    let rows = StatusCountRow::find_by_statement(Statement::from_string(
+        DatabaseBackend::Sqlite,
+        format!(
+            "SELECT COALESCE((SELECT MAX(l.status) FROM profile_library l WHERE l.rom_id = r.id), 'unplayed') AS status,
+                    COUNT(*) AS rom_count
+             FROM roms r
+             WHERE {NOT_ARCHIVED_CLAUSE}
+             GROUP BY 1"
+        ),
+    ))
+    .all(db.inner())
+    .await?;
+
+    Ok(CompletionStatus::iter()
+        .map(|status| StatusCount {
+            status,
+            rom_count: rows
+                .iter()
+                .find(|r| r.status == status)
+                .map_or(0, |r| r.rom_count),
+        })
+        .collect())
+}
+
+/// Set a ROM's personal note; an empty note removes it.
+#[tauri::command]
+pub async fn set_rom_note(
+    db: State<'_, DatabaseConnection>,
+    rom_id: i64,
+    note: Option<String>,
+) -> AppResult<()> {
+    use sea_orm::{ConnectionTrait, DatabaseBackend, Statement};
+
+    let note = note.filter(|n| !n.trim().is_empty());
+    let stmt = match note {
+        Some(note) => Statement::from_sql_and_values(
+            DatabaseBackend::Sqlite,
+            "INSERT INTO rom_notes (rom_id, note) VALUES (?, ?)
+             ON CONFLICT(rom_id) DO UPDATE SET
+               note = excluded.note,
+               updated_at = strftime('%Y-%m-%dT%H:%M:%fZ', 'now')",
+            [rom_id.into(), note.into()],
+        ),
+        None => Statement::from_sql_and_values(
+            DatabaseBackend::Sqlite,
+            "DELETE FROM rom_notes WHERE rom_id = ?",
+            [rom_id.into()],
+        ),
+    };
+    db.inner().execute(stmt).await?;
+    Ok(())
+}
+
+async fn rom_tags(db: &DatabaseConnection, rom_id: i64) -> AppResult<Vec<String>> {
+    use sea_orm::{DatabaseBackend, FromQueryResult, Statement};
+
+    #[derive(Debug, FromQueryResult)]
+    struct TagRow {
+        tag: String,
+    }
+    Ok(TagRow::find_by_statement(Statement::from_sql_and_values(
+        DatabaseBackend::Sqlite,
+        "SELECT tag FROM rom_tags WHERE rom_id = ? ORDER BY tag",
+        [rom_id.into()],
+    ))
+    .all(db)
+    .await?
+    .into_iter()
+    .map(|r| r.tag)
+    .collect())
+}
+
+/// Tag a ROM. Tags are case-insensitive; returns the ROM's tags.
+#[tauri::command]
+pub async fn add_rom_tag(
+    db: State<'_, DatabaseConnection>,
+    rom_id: i64,
+    tag: String,
+) -> AppResult<Vec<String>> {
+    use sea_orm::{ConnectionTrait, DatabaseBackend, Statement};
+
+    let tag = tag.trim();
+    if tag.is_empty() {
+        return Err(AppError::Other("Tag can't be empty".to_string()));
+    }
+    db.inner()
+        .execute(Statement::from_sql_and_values(
+            DatabaseBackend::Sqlite,
+            "INSERT OR IGNORE INTO rom_tags (rom_id, tag) VALUES (?, ?)",
+            [rom_id.into(), tag.into()],
+        ))
+        .await?;
+    rom_tags(db.inner(), rom_id).await
+}
+
+/// Remove a tag from a ROM; returns the ROM's remaining tags.
+#[tauri::command]
+pub async fn remove_rom_tag(
+    db: State<'_, DatabaseConnection>,
+    rom_id: i64,
+    tag: String,
+) -> AppResult<Vec<String>> {
+    use sea_orm::{ConnectionTrait, DatabaseBackend, Statement};
+
+    db.inner()
+        .execute(Statement::from_sql_and_values(
+            DatabaseBackend::Sqlite,
+            "DELETE FROM rom_tags WHERE rom_id = ? AND tag = ?",
+            [rom_id.into(), tag.trim().into()],
+        ))
+        .await?;
+    rom_tags(db.inner(), rom_id).await
+}
+
+/// Mark a ROM as homebrew or a ROM hack, or clear the mark with `None`.
+/// Marked ROMs are only looked up in the homebrew index during enrichment.
+#[tauri::command]
+pub async fn set_rom_homebrew(
+    db: State<'_, DatabaseConnection>,
+    rom_id: i64,
+    kind: Option<crate::entity::rom_homebrew::HomebrewKind>,
+) -> AppResult<Option<HomebrewInfo>> {
+    crate::metadata::homebrew::set_kind(db.inner(), rom_id, kind).await?;
+    crate::metadata::homebrew::info(db.inner(), rom_id).await
+}
+
+/// A ROM's homebrew mark and the title, author and base game the homebrew
+/// index matched; None when the ROM isn't marked.
+#[tauri::command]
+pub async fn get_rom_homebrew(
+    db: State<'_, DatabaseConnection>,
+    rom_id: i64,
+) -> AppResult<Option<HomebrewInfo>> {
+    crate::metadata::homebrew::info(db.inner(), rom_id).await
+}
+
+/// Every tag in use, for the tag filter.
+#[tauri::command]
+pub async fn get_all_tags(db: State<'_, DatabaseConnection>) -> AppResult<Vec<TagCount>> {
+    use sea_orm::{DatabaseBackend, FromQueryResult, Statement};
+
+    #[derive(Debug, FromQueryResult)]
+    struct TagCountRow {
+        tag: String,
+        rom_count: i64,
+    }
+    Ok(TagCountRow::find_by_statement(Statement::from_string(
+        DatabaseBackend::Sqlite,
+        "SELECT MIN(tag) AS tag, COUNT(*) AS rom_count FROM rom_tags
+         GROUP BY tag ORDER BY tag",
+    ))
+    .all(db.inner())
+    .await?
+    .into_iter()
+    .map(|r| TagCount {
+        tag: r.tag,
+        rom_count: r.rom_count,
+    })
+    .collect())
+}
+
+/// Mark the local favorites of a ROMM source as that server's favourites,
+/// replacing whatever the collection held. Returns how many ROMs were pushed.
+#[tauri::command]
+pub async fn push_favorites_to_romm(
+    db: State<'_, DatabaseConnection>,
+    source_id: i64,
+) -> AppResult<usize> {
+    use crate::entity::sources;
+    use sea_orm::{DatabaseBackend, EntityTrait, FromQueryResult, Statement};
+
+    let source = sources::Entity::find_by_id(source_id)
+        .one(db.inner())
+        .await?
+        .ok_or_else(|| AppError::SourceNotFound(source_id.to_string()))?;
+    if source.source_type != sources::SourceType::Romm {
+        return Err(AppError::Other("Favorites can only be pushed to a ROMM source".to_string()));
+    }
+
+    #[derive(Debug, FromQueryResult)]
+    struct RemoteIdRow {
+        source_rom_id: String,
+    }
+
+    let romm_rom_ids: Vec<i64> = RemoteIdRow::find_by_statement(Statement::from_sql_and_values(
+        DatabaseBackend::Sqlite,
+        "SELECT DISTINCT sr.source_rom_id
+         FROM source_roms sr
+         WHERE sr.source_id = ? AND sr.source_rom_id IS NOT NULL
+           AND EXISTS (SELECT 1 FROM profile_library l WHERE l.rom_id = sr.rom_id AND l.favorite = 1)",
+        [source_id.into()],
+    ))
+    .all(db.inner())
+    .await?
+    .into_iter()
+    .filter_map(|r| r.source_rom_id.parse().ok())
+    .collect();
+
+    let client = RommClient::from_source(source.url.unwrap_or_default(), &source.credentials)
+        .with_collections_write_access();
+    client.push_favorites(&romm_rom_ids).await?;
+    Ok(romm_rom_ids.len())
+}
+
+#[tauri::command]
+pub async fn get_collections(db: State<'_, DatabaseConnection>) -> AppResult<Vec<Collection>> {
+    use sea_orm::{DatabaseBackend, FromQueryResult, Statement};
+
+    #[derive(Debug, FromQueryResult)]
+    struct CollectionRow {
+        id: i64,
+        name: String,
+        description: Option<String>,
+        source_id: Option<i64>,
+        is_favorite: bool,
+        rom_count: i64,
+    }
+
+    let rows = CollectionRow::find_by_statement(Statement::from_string(
+        DatabaseBackend::Sqlite,
+        "SELECT c.id, c.name, c.description, c.source_id, c.is_favorite,
+                COUNT(cr.rom_id) AS rom_count
+         FROM collections c
+         LEFT JOIN collection_roms cr ON cr.collection_id = c.id
+         WHERE c.profile_id IS NULL OR c.profile_id = (SELECT id FROM current_profile)
+         GROUP BY c.id
+         ORDER BY c.is_favorite DESC, c.name COLLATE NOCASE",
+    ))
+    .all(db.inner())
+    .await?;
+
+    Ok(rows
+        .into_iter()
+        .map(|r| Collection {
+            id: r.id,
+            name: r.name,
+            description: r.description,
+            source_id: r.source_id,
+            is_favorite: r.is_favorite,
+            rom_count: r.rom_count,
+        })
+        .collect())
+}
+
+#[tauri::command]
+pub async fn get_collection_roms(
+    db: State<'_, DatabaseConnection>,
+    collection_id: i64,
+) -> AppResult<Vec<RomWithMeta>> {
+    let q = library_query(
+        "",
+        &format!(
+            "WHERE r.id IN (SELECT rom_id FROM collection_roms WHERE collection_id = ?)
+               AND {CONTENT_RATING_CLAUSE}"
+        ),
+    );
+    let rows = query_rom_rows(db.inner(), &q, vec![collection_id.into()]).await?;
+    Ok(rows.into_iter().map(RomWithMetaRow::into_rom_with_meta).collect())
+}
+
+#[tauri::command]
+pub async fn get_platforms_with_counts(
+    app: tauri::AppHandle,
+    db: State<'_, DatabaseConnection>,
+    include_archived: Option<bool>,
+) -> AppResult<Vec<PlatformWithCount>> {
+    use sea_orm::{DatabaseBackend, FromQueryResult, Statement};
+
+    #[derive(Debug, FromQueryResult)]
+    struct PlatformCountRow {
+        id: i64,
+        slug: String,
+        name: String,
+        rom_count: i64,
+        archived: bool,
+    }
+
+    let archived_filter = if include_archived.unwrap_or(false) {
+        ""
+    } else {
+        " WHERE p.archived = 0"
+    };
+    let rows = PlatformCountRow::find_by_statement(Statement::from_string(
+        DatabaseBackend::Sqlite,
+        format!("SELECT p.id, p.slug, p.name, COUNT(r.id) as rom_count, p.archived FROM platforms p INNER JOIN roms r ON r.platform_id = p.id AND {CONTENT_RATING_CLAUSE}{archived_filter} GROUP BY p.id ORDER BY p.name"),
+    ))
+    .all(db.inner())
+    .await?;
+
+    let names = PlatformNames::from_store(&app);
+    Ok(rows
+        .into_iter()
+        .map(|r| PlatformWithCount {
+            id: r.id,
+            name: names.name(&r.slug, r.name),
+            slug: r.slug,
+            rom_count: r.rom_count,
+            archived: r.archived,
+        })
+        .collect())
+}
+
+/// Archive or unarchive a platform. Archived platforms' ROMs stay in the DB
+/// but are hidden from the library, enrichment and verification by default.
+#[tauri::command]
+pub async fn set_platform_archived(
+    db: State<'_, DatabaseConnection>,
+    platform_id: i64,
+    archived: bool,
+) -> AppResult<()> {
+    use sea_orm::{ConnectionTrait, DatabaseBackend, Statement};
+
+    let result = db
+        .inner()
+        .execute(Statement::from_sql_and_values(
+            DatabaseBackend::Sqlite,
+            "UPDATE platforms SET archived = ?, updated_at = strftime('%Y-%m-%dT%H:%M:%fZ', 'now') WHERE id = ?",
+            [archived.into(), platform_id.into()],
+        ))
+        .await?;
+    if result.rows_affected() == 0 {
+        return Err(AppError::NotFound(format!("Platform {platform_id}")));
+    }
+    Ok(())
+}
+
+/// Set what happens to a platform's ROM files: kept as they are, extracted
+/// from zip/7z after download, or compressed to CHD/RVZ when local.
+#[tauri::command]
+pub async fn set_platform_archive_handling(
+    db: State<'_, DatabaseConnection>,
+    platform_id: i64,
+    handling: ArchiveHandling,
+) -> AppResult<()> {
+    use sea_orm::{ConnectionTrait, DatabaseBackend, Statement};
+
+    let result = db
+        .inner()
+        .execute(Statement::from_sql_and_values(
+            DatabaseBackend::Sqlite,
+            "UPDATE platforms SET archive_handling = ?, updated_at = strftime('%Y-%m-%dT%H:%M:%fZ', 'now') WHERE id = ?",
+            [handling.into(), platform_id.into()],
+        ))
+        .await?;
+    if result.rows_affected() == 0 {
+        return Err(AppError::NotFound(format!("Platform {platform_id}")));
+    }
+    Ok(())
+}
+
+/// Compress the local disc images of platforms set to compress (or only
+/// `platform_id`'s) to CHD/RVZ with chdman/dolphin-tool, as a background
+/// job. Reports the space saved.
+#[tauri::command]
+pub async fn compress_local_roms(
+    db: State<'_, DatabaseConnection>,
+    jobs: State<'_, JobManager>,
+    platform_id: Option<i64>,
+    remove_originals: bool,
+    channel: Channel<ScanProgress>,
+) -> AppResult<CompressionReport> {
+    let job = jobs.start(JobKind::Conversion, None, "Compressing disc images");
+    let result = crate::convert::compress_local_roms(
+        db.inner(),
+        platform_id,
+        remove_originals,
+        job.reporter(move |p| { let _ = channel.send(p); }),
+        job.token(),
+    )
+    .await;
+    job.finish(&result);
+    result
+}
+
+/// Convert one ROM's local disc image to CHD, RVZ or CSO with chdman,
+/// dolphin-tool or maxcso. With `remove_originals`, the original files are
+/// deleted once the converted image checks out.
+#[tauri::command]
+pub async fn convert_rom(
+    db: State<'_, DatabaseConnection>,
+    jobs: State<'_, JobManager>,
+    rom_id: i64,
+    target_format: CompressedFormat,
+    remove_originals: bool,
+) -> AppResult<CompressionReport> {
+    let job = jobs.start(
+        JobKind::Conversion,
+        Some(rom_id),
+        format!("Converting to {}", target_format.extension().to_uppercase()),
+    );
+    let result = crate::convert::convert_rom(
+        db.inner(),
+        rom_id,
+        target_format,
+        remove_originals,
+        &job.token(),
+    )
+    .await;
+    job.finish(&result);
+    result
+}
+
+/// Cancel the conversion of `rom_id`, or every running conversion.
 #[tauri::command]
-pub async fn get_platforms_with_counts(
-    db: State<'_, DatabaseConnection>,
-) -> AppResult<Vec<PlatformWithCount>> {
-    use sea_orm::{DatabaseBackend, FromQueryResult, Statement};
+pub async fn cancel_conversion(jobs: State<'_, JobManager>, rom_id: Option<i64>) -> AppResult<()> {
+    jobs.cancel_kind(JobKind::Conversion, rom_id);
+    Ok(())
+}
 
-    #[derive(Debug, FromQueryResult)]
-    struct PlatformCountRow {
-        id: i64,
-        slug: String,
-        name: String,
-        rom_count: i64,
-    }
+/// Platform display names for the configured UI language: user overrides
+/// first, then the bundled translations, then the English name.
+struct PlatformNames {
+    locale: Option<String>,
+    overrides: HashMap<String, String>,
+}
 
-    let rows = PlatformCountRow::find_by_statement(Statement::from_string(
-        DatabaseBackend::Sqlite,
-        "SELECT p.id, p.slug, p.name, COUNT(r.id) as rom_count FROM platforms p INNER JOIN roms r ON r.platform_id = p.id GROUP BY p.id ORDER BY p.name",
-    ))
-    .all(db.inner())
-    .await?;
+impl PlatformNames {
+    fn from_store(app: &tauri::AppHandle) -> Self {
+        let Ok(store) = app.store("settings.json") else {
+            return Self { locale: None, overrides: HashMap::new() };
+        };
+        let locale = store
+            .get("language")
+            .and_then(|v| v.as_str().map(|s| s.to_string()))
+            .filter(|l| !l.is_empty());
+        let mut all: HashMap<String, HashMap<String, String>> = store
+            .get("platform_name_overrides")
+            .and_then(|v| serde_json::from_value(v).ok())
+            .unwrap_or_default();
+        let overrides = locale
+            .as_deref()
+            .and_then(|l| all.remove(l))
+            .unwrap_or_default();
+        Self { locale, overrides }
+    }
 
-    Ok(rows
-        .into_iter()
-        .map(|r| PlatformWithCount {
-            id: r.id,
-            slug: r.slug,
-            name: r.name,
-            rom_count: r.rom_count,
-        })
-        .collect())
+    fn name(&self, slug: &str, fallback: String) -> String {
+        let Some(ref locale) = self.locale else {
+            return fallback;
+        };
+        self.overrides
+            .get(slug)
+            .cloned()
+            .or_else(|| {
+                platform_registry::localized_display_name(slug, locale).map(str::to_string)
+            })
+            .unwrap_or(fallback)
+    }
 }
 
 #[tauri::command]
-pub fn get_all_registry_platforms() -> Vec<(String, String)> {
+pub fn get_all_registry_platforms(app: tauri::AppHandle) -> Vec<(String, String)> {
+    let names = PlatformNames::from_store(&app);
     platform_registry::PLATFORMS
         .iter()
-        .map(|p| (p.slug.to_string(), p.display_name.to_string()))
+        .map(|p| (p.slug.to_string(), names.name(p.slug, p.display_name.to_string())))
         .collect()
 }
 
+#[tauri::command]
+pub async fn get_language(app: tauri::AppHandle) -> AppResult<Option<String>> {
+    let store = app
+        .store("settings.json")
+        .map_err(|e| AppError::Other(e.to_string()))?;
+    Ok(store
+        .get("language")
+        .and_then(|v| v.as_str().map(|s| s.to_string())))
+}
+
+/// Set the UI language (e.g. `en`, `ja`, `pt-BR`) used for platform display names.
+#[tauri::command]
+pub async fn set_language(app: tauri::AppHandle, language: String) -> AppResult<()> {
+    let store = app
+        .store("settings.json")
+        .map_err(|e| AppError::Other(e.to_string()))?;
+    store.set("language", serde_json::json!(language));
+    store.save().map_err(|e| AppError::Other(e.to_string()))?;
+    crate::settings::save_to_db(&app).await?;
+    Ok(())
+}
+
+/// Override a platform's display name for a locale; `None` restores the bundled name.
+#[tauri::command]
+pub async fn set_platform_name_override(
+    app: tauri::AppHandle,
+    locale: String,
+    slug: String,
+    name: Option<String>,
+) -> AppResult<()> {
+    let store = app
+        .store("settings.json")
+        .map_err(|e| AppError::Other(e.to_string()))?;
+    let mut all: HashMap<String, HashMap<String, String>> = store
+        .get("platform_name_overrides")
+        .and_then(|v| serde_json::from_value(v).ok())
+        .unwrap_or_default();
+    let names = all.entry(locale.clone()).or_default();
+    match name.filter(|n| !n.trim().is_empty()) {
+        Some(n) => {
+            names.insert(slug, n);
+        }
+        None => {
+            names.remove(&slug);
+            if names.is_empty() {
+                all.remove(&locale);
+            }
+        }
+    }
+    store.set("platform_name_overrides", serde_json::json!(all));
+    store.save().map_err(|e| AppError::Other(e.to_string()))?;
+    crate::settings::save_to_db(&app).await?;
+    Ok(())
+}
+
+/// A copy of an image already on disk: the file an artwork row points to,
+/// or the launch grid's thumbnail of it.
+async fn cached_image_path(db: &DatabaseConnection, url: &str) -> Option<std::path::PathBuf> {
+    use crate::entity::artwork;
+    use sea_orm::{ColumnTrait, EntityTrait, QueryFilter};
+
+    let stored = artwork::Entity::find()
+        .filter(artwork::Column::Url.eq(url))
+        .filter(artwork::Column::LocalPath.is_not_null())
+        .one(db)
+        .await
+        .ok()
+        .flatten()
+        .and_then(|a| a.local_path)
+        .map(std::path::PathBuf::from);
+    stored
+        .into_iter()
+        .chain([grid_cover_path(url)])
+        .find(|p| p.is_file())
+}
+
 #[tauri::command]
 pub async fn proxy_image(
     db: State<'_, DatabaseConnection>,
     url: String,
 ) -> AppResult<String> {
+    // Screenshots taken in an emulator are local files
+    if let Some(path) = url.strip_prefix("file://") {
+        return read_file_base64(path.to_string()).await;
+    }
+
+    // Offline, only images already on disk can be shown
+    if crate::http::is_offline() {
+        return match cached_image_path(db.inner(), &url).await {
+            Some(path) => read_file_base64(path.to_string_lossy().into_owned()).await,
+            None => Err(AppError::Offline(format!("{url} isn't cached"))),
+        };
+    }
+
     // Get any ROMM source credentials to authenticate if needed
     use crate::entity::sources;
     use sea_orm::{ColumnTrait, EntityTrait, QueryFilter};
@@ -732,19 +2391,12 @@ pub async fn proxy_image(
     let row = romm_source.map(|s| (s.url.unwrap_or_default(), s.credentials));
 
     if let Some((base_url, credentials)) = row {
-        let creds: HashMap<String, String> =
-            serde_json::from_str(&credentials).unwrap_or_else(|e| {
-                log::warn!("Failed to parse credentials JSON: {e}");
-                HashMap::new()
-            });
-        let username = creds.get("username").cloned().unwrap_or_default();
-        let password = creds.get("password").cloned().unwrap_or_default();
-        let client = RommClient::new(base_url, username, password);
+        let client = RommClient::from_source(base_url, &credentials);
         client.proxy_image(&url).await
     } else {
         // No source, try direct fetch and return as base64 data URL
         use base64::Engine;
-        let resp = reqwest::get(&url).await?;
+        let resp = crate::http::client_builder().build()?.get(&url).send().await?;
         let content_type = resp
             .headers()
             .get("content-type")
@@ -817,18 +2469,318 @@ pub async fn set_retroarch_path(app: tauri::AppHandle, path: String) -> AppResul
     Ok(())
 }
 
+/// BIOS directory: the user's override, else RetroArch's system directory.
+fn read_bios_dir_from_store(app: &tauri::AppHandle) -> Option<std::path::PathBuf> {
+    app.store("settings.json")
+        .ok()
+        .and_then(|store| store.get("bios_path"))
+        .and_then(|v| v.as_str().map(std::path::PathBuf::from))
+        .or_else(crate::bios::default_bios_dir)
+}
+
+#[tauri::command]
+pub async fn get_bios_path(app: tauri::AppHandle) -> AppResult<Option<String>> {
+    Ok(read_bios_dir_from_store(&app).map(|p| p.to_string_lossy().into_owned()))
+}
+
+#[tauri::command]
+pub async fn set_bios_path(app: tauri::AppHandle, path: String) -> AppResult<()> {
+    if !std::path::Path::new(&path).is_dir() {
+        return Err(AppError::Other(format!("Not a directory: {path}")));
+    }
+    let store = app
+        .store("settings.json")
+        .map_err(|e| AppError::Other(e.to_string()))?;
+    store.set("bios_path", serde_json::json!(path));
+    store.save().map_err(|e| AppError::Other(e.to_string()))?;
+    Ok(())
+}
+
+/// Validate every known BIOS file against the contents of `path`.
+#[tauri::command]
+pub async fn scan_bios(path: String) -> AppResult<Vec<BiosFileStatus>> {
+    let dir = std::path::PathBuf::from(&path);
+    if !dir.is_dir() {
+        return Err(AppError::Other(format!("Not a directory: {path}")));
+    }
+    tokio::task::spawn_blocking(move || crate::bios::scan_bios(&dir))
+        .await
+        .map_err(|e| AppError::Other(format!("Task join error: {e}")))
+}
+
+/// BIOS readiness of a platform, or `None` if no BIOS directory is known.
+async fn bios_readiness_for_platform(
+    app: &tauri::AppHandle,
+    db: &DatabaseConnection,
+    platform_id: i64,
+) -> AppResult<Option<BiosReadiness>> {
+    use crate::entity::platforms;
+    use sea_orm::EntityTrait;
+
+    let Some(dir) = read_bios_dir_from_store(app) else {
+        return Ok(None);
+    };
+    let slug = platforms::Entity::find_by_id(platform_id)
+        .one(db)
+        .await?
+        .map(|p| p.slug)
+        .ok_or_else(|| AppError::NotFound(format!("Platform {platform_id}")))?;
+    if crate::bios::for_platform(&slug).next().is_none() {
+        return Ok(None);
+    }
+    let readiness = tokio::task::spawn_blocking(move || crate::bios::platform_readiness(&dir, &slug))
+        .await
+        .map_err(|e| AppError::Other(format!("Task join error: {e}")))?;
+    Ok(Some(readiness))
+}
+
+#[tauri::command]
+pub async fn get_bios_readiness(
+    app: tauri::AppHandle,
+    db: State<'_, DatabaseConnection>,
+    platform_id: i64,
+) -> AppResult<Option<BiosReadiness>> {
+    bios_readiness_for_platform(&app, db.inner(), platform_id).await
+}
+
+/// Firmware a libretro core expects, read from RetroArch's installed `.info`
+/// file. With `download` (or when it isn't installed) the file is fetched
+/// from libretro's core-info repository instead.
+#[tauri::command]
+pub async fn get_core_bios_hints(
+    core_name: String,
+    download: Option<bool>,
+) -> AppResult<Vec<BiosHint>> {
+    let core_name = core_name.trim_end_matches("_libretro").to_string();
+    let file_name = format!("{core_name}_libretro.info");
+
+    let local = if download.unwrap_or(false) {
+        None
+    } else {
+        find_info_dir().and_then(|dir| std::fs::read_to_string(dir.join(&file_name)).ok())
+    };
+    let info = if let Some(info) = local {
+        info
+    } else {
+        let url = format!(
+            "https://raw.githubusercontent.com/libretro/libretro-core-info/master/{file_name}"
+        );
+        crate::http::ensure_online("GitHub")?;
+        let resp = crate::http::client_builder().build()?.get(&url).send().await?;
+        if !resp.status().is_success() {
+            return Err(AppError::Other(format!(
+                "No core info for {core_name}: {}",
+                resp.status()
+            )));
+        }
+        resp.text().await?
+    };
+
+    Ok(crate::bios::parse_core_firmware(&info))
+}
+
 #[tauri::command]
-pub async fn get_emulators() -> AppResult<Vec<EmulatorDef>> {
-    Ok(EMULATOR_REGISTRY
+pub async fn get_emulators(app: tauri::AppHandle) -> AppResult<Vec<EmulatorDef>> {
+    let store = app
+        .store("settings.json")
+        .map_err(|e| AppError::Other(e.to_string()))?;
+    let mut emulators: Vec<EmulatorDef> = EMULATOR_REGISTRY
         .iter()
         .map(|e| EmulatorDef {
             id: e.id.to_string(),
             name: e.name.to_string(),
             platforms: e.platforms.iter().map(|s| (*s).to_string()).collect(),
+            custom: false,
+        })
+        .collect();
+    emulators.extend(read_custom_emulators(&store).into_iter().map(|c| EmulatorDef {
+        id: c.id,
+        name: c.name,
+        platforms: c.platforms,
+        custom: true,
+    }));
+    Ok(emulators)
+}
+
+/// Effective argument template for every known emulator (built-in and custom).
+#[tauri::command]
+pub async fn get_emulator_arg_templates(
+    app: tauri::AppHandle,
+) -> AppResult<HashMap<String, EmulatorArgTemplate>> {
+    let store = app
+        .store("settings.json")
+        .map_err(|e| AppError::Other(e.to_string()))?;
+    let ids = EMULATOR_REGISTRY
+        .iter()
+        .map(|e| e.id.to_string())
+        .chain(read_custom_emulators(&store).into_iter().map(|c| c.id));
+    Ok(ids
+        .map(|id| {
+            let template = read_arg_template(&store, &id);
+            (id, template)
         })
         .collect())
 }
 
+/// Override the argument template for an emulator; `None` restores the default.
+#[tauri::command]
+pub async fn set_emulator_arg_template(
+    app: tauri::AppHandle,
+    emulator_id: String,
+    template: Option<EmulatorArgTemplate>,
+) -> AppResult<()> {
+    let store = app
+        .store("settings.json")
+        .map_err(|e| AppError::Other(e.to_string()))?;
+    let mut overrides: HashMap<String, EmulatorArgTemplate> = store
+        .get(EMULATOR_TEMPLATES_KEY)
+        .and_then(|v| serde_json::from_value(v).ok())
+        .unwrap_or_default();
+    match template {
+        Some(t) => {
+            if !t.args.contains("{rom}") {
+                return Err(AppError::Other(
+                    "Argument template must contain {rom}".to_string(),
+                ));
+            }
+            overrides.insert(emulator_id, t);
+        }
+        None => {
+            overrides.remove(&emulator_id);
+        }
+    }
+    store.set(EMULATOR_TEMPLATES_KEY, serde_json::json!(overrides));
+    store.save().map_err(|e| AppError::Other(e.to_string()))?;
+    crate::settings::save_to_db(&app).await?;
+    Ok(())
+}
+
+/// Hook commands live in settings.json only; they name local scripts and paths.
+const LAUNCH_HOOKS_KEY: &str = "launch_hooks";
+
+fn read_launch_hooks_from_store(app: &tauri::AppHandle) -> LaunchHooks {
+    app.store("settings.json")
+        .ok()
+        .and_then(|store| store.get(LAUNCH_HOOKS_KEY))
+        .and_then(|v| serde_json::from_value(v).ok())
+        .unwrap_or_default()
+}
+
+#[tauri::command]
+pub async fn get_launch_hooks(app: tauri::AppHandle) -> AppResult<LaunchHooks> {
+    Ok(read_launch_hooks_from_store(&app))
+}
+
+/// Save the global and per-platform commands run before launch and after the
+/// emulator exits.
+#[tauri::command]
+pub async fn set_launch_hooks(app: tauri::AppHandle, hooks: LaunchHooks) -> AppResult<()> {
+    if std::iter::once(&hooks.global)
+        .chain(hooks.platforms.values())
+        .any(|h| h.timeout_secs == Some(0))
+    {
+        return Err(AppError::Other(
+            "Hook timeout must be at least 1 second".to_string(),
+        ));
+    }
+    let store = app
+        .store("settings.json")
+        .map_err(|e| AppError::Other(e.to_string()))?;
+    store.set(LAUNCH_HOOKS_KEY, serde_json::json!(hooks));
+    store.save().map_err(|e| AppError::Other(e.to_string()))?;
+    Ok(())
+}
+
+/// Register an emulator that isn't in the built-in registry. It can then be
+/// mapped to platforms like any other standalone emulator.
+#[tauri::command]
+pub async fn add_custom_emulator(
+    app: tauri::AppHandle,
+    name: String,
+    path: String,
+    platforms: Vec<String>,
+    args: String,
+    fullscreen_flag: Option<String>,
+) -> AppResult<EmulatorDef> {
+    let name = name.trim().to_string();
+    if name.is_empty() {
+        return Err(AppError::Other("Emulator name is required".to_string()));
+    }
+    if !std::path::Path::new(&path).exists() {
+        return Err(AppError::Other(format!("Path does not exist: {path}")));
+    }
+    if !args.contains("{rom}") {
+        return Err(AppError::Other(
+            "Argument template must contain {rom}".to_string(),
+        ));
+    }
+
+    let slug: String = name
+        .to_lowercase()
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '-' })
+        .collect();
+    let id = format!("custom-{}", slug.trim_matches('-'));
+    if EMULATOR_REGISTRY.iter().any(|e| e.id == id) {
+        return Err(AppError::Other(format!("Emulator {id} already exists")));
+    }
+
+    let store = app
+        .store("settings.json")
+        .map_err(|e| AppError::Other(e.to_string()))?;
+    let mut customs = read_custom_emulators(&store);
+    if customs.iter().any(|c| c.id == id) {
+        return Err(AppError::Other(format!("Emulator {id} already exists")));
+    }
+    customs.push(CustomEmulator {
+        id: id.clone(),
+        name: name.clone(),
+        platforms: platforms.clone(),
+        template: EmulatorArgTemplate {
+            args,
+            fullscreen_flag: fullscreen_flag.filter(|f| !f.trim().is_empty()),
+            fullscreen: false,
+        },
+    });
+    store.set(CUSTOM_EMULATORS_KEY, serde_json::json!(customs));
+
+    let mut paths: serde_json::Map<String, serde_json::Value> =
+        store.get("emulator_paths")
+            .and_then(|v| v.as_object().cloned())
+            .unwrap_or_default();
+    paths.insert(id.clone(), serde_json::json!(path));
+    store.set("emulator_paths", serde_json::json!(paths));
+    store.save().map_err(|e| AppError::Other(e.to_string()))?;
+    crate::settings::save_to_db(&app).await?;
+
+    Ok(EmulatorDef {
+        id,
+        name,
+        platforms,
+        custom: true,
+    })
+}
+
+#[tauri::command]
+pub async fn remove_custom_emulator(app: tauri::AppHandle, emulator_id: String) -> AppResult<()> {
+    let store = app
+        .store("settings.json")
+        .map_err(|e| AppError::Other(e.to_string()))?;
+    let mut customs = read_custom_emulators(&store);
+    customs.retain(|c| c.id != emulator_id);
+    store.set(CUSTOM_EMULATORS_KEY, serde_json::json!(customs));
+
+    let mut paths: serde_json::Map<String, serde_json::Value> =
+        store.get("emulator_paths")
+            .and_then(|v| v.as_object().cloned())
+            .unwrap_or_default();
+    paths.remove(&emulator_id);
+    store.set("emulator_paths", serde_json::json!(paths));
+    store.save().map_err(|e| AppError::Other(e.to_string()))?;
+    crate::settings::save_to_db(&app).await?;
+    Ok(())
+}
+
 #[tauri::command]
 pub async fn get_emulator_paths(app: tauri::AppHandle) -> AppResult<HashMap<String, String>> {
     let store = app
@@ -884,12 +2836,12 @@ pub async fn detect_emulators() -> AppResult<Vec<(String, String)>> {
     Ok(found)
 }
 
-/// Parse `display_name` from a `RetroArch` `.info` file (simple key = "value" format).
-fn parse_display_name(info_path: &std::path::Path) -> Option<String> {
+/// Read `key` (e.g. `display_name`) from a `RetroArch` `.info` file (simple key = "value" format).
+fn parse_info_value(info_path: &std::path::Path, key: &str) -> Option<String> {
     let content = std::fs::read_to_string(info_path).ok()?;
     for line in content.lines() {
         let line = line.trim();
-        if let Some(rest) = line.strip_prefix("display_name") {
+        if let Some(rest) = line.strip_prefix(key) {
             let rest = rest.trim();
             if let Some(rest) = rest.strip_prefix('=') {
                 let rest = rest.trim();
@@ -903,6 +2855,18 @@ fn parse_display_name(info_path: &std::path::Path) -> Option<String> {
     None
 }
 
+/// The name `RetroArch` files per-core folders (cheats, overrides, remaps)
+/// under: the core's `corename`, or its file name when there's no .info file.
+fn retroarch_core_name(core_path: &str) -> Option<String> {
+    let stem = std::path::Path::new(core_path)
+        .file_stem()?
+        .to_string_lossy()
+        .into_owned();
+    find_info_dir()
+        .and_then(|dir| parse_info_value(&dir.join(format!("{stem}.info")), "corename"))
+        .or(Some(stem))
+}
+
 /// Locate the `RetroArch` info directory (sibling to cores dir).
 fn find_info_dir() -> Option<std::path::PathBuf> {
     if cfg!(target_os = "macos") {
@@ -950,13 +2914,7 @@ pub async fn detect_cores(retroarch_path: String) -> AppResult<Vec<CoreInfo>> {
 
     let info_dir = find_info_dir();
 
-    let ext = if cfg!(target_os = "macos") {
-        "dylib"
-    } else if cfg!(target_os = "windows") {
-        "dll"
-    } else {
-        "so"
-    };
+    let ext = crate::buildbot::core_extension();
 
     let mut cores = Vec::new();
     if let Ok(entries) = std::fs::read_dir(&cores_dir) {
@@ -969,7 +2927,7 @@ pub async fn detect_cores(retroarch_path: String) -> AppResult<Vec<CoreInfo>> {
                     .unwrap_or("")
                     .to_string();
                 let display_name = info_dir.as_ref().and_then(|dir| {
-                    parse_display_name(&dir.join(format!("{name}.info")))
+                    parse_info_value(&dir.join(format!("{name}.info")), "display_name")
                 });
                 cores.push(CoreInfo {
                     core_name: name,
@@ -1017,6 +2975,89 @@ pub async fn has_core_mapping(
     Ok(row.is_some())
 }
 
+/// Everything a platform needs before its games launch well: core, emulator,
+/// BIOS, DAT, RetroAchievements and artwork coverage.
+#[tauri::command]
+pub async fn get_platform_setup_status(
+    app: tauri::AppHandle,
+    db: State<'_, DatabaseConnection>,
+    platform_id: i64,
+) -> AppResult<PlatformSetupStatus> {
+    use crate::entity::platforms;
+    use sea_orm::{DatabaseBackend, EntityTrait, FromQueryResult, Statement};
+
+    #[derive(Debug, FromQueryResult)]
+    struct MappingRow {
+        core_name: String,
+        emulator_type: String,
+    }
+
+    let slug = platforms::Entity::find_by_id(platform_id)
+        .one(db.inner())
+        .await?
+        .map(|p| p.slug)
+        .ok_or_else(|| AppError::NotFound(format!("Platform {platform_id}")))?;
+
+    let mapping = MappingRow::find_by_statement(Statement::from_sql_and_values(
+        DatabaseBackend::Sqlite,
+        "SELECT core_name, emulator_type FROM core_mappings
+         WHERE platform_id = ? ORDER BY is_default DESC LIMIT 1",
+        [platform_id.into()],
+    ))
+    .one(db.inner())
+    .await?;
+
+    let emulator_path_set = match &mapping {
+        Some(m) if m.emulator_type == "retroarch" => get_retroarch_path(app.clone()).await?.is_some(),
+        Some(m) => get_emulator_paths(app.clone()).await?.contains_key(&m.emulator_type),
+        None => false,
+    };
+
+    let rom_count = count_query(
+        db.inner(),
+        "SELECT COUNT(*) FROM roms WHERE platform_id = ?",
+        vec![platform_id.into()],
+    )
+    .await?;
+    let roms_with_cover = count_query(
+        db.inner(),
+        "SELECT COUNT(*) FROM roms r WHERE r.platform_id = ?
+           AND EXISTS (SELECT 1 FROM artwork a WHERE a.rom_id = r.id AND a.art_type = 'cover')",
+        vec![platform_id.into()],
+    )
+    .await?;
+    let dat_count = count_query(
+        db.inner(),
+        "SELECT COUNT(*) FROM dat_files WHERE platform_slug = ?",
+        vec![slug.clone().into()],
+    )
+    .await?;
+
+    #[allow(clippy::cast_precision_loss)]
+    let cover_coverage = if rom_count > 0 {
+        roms_with_cover as f64 * 100.0 / rom_count as f64
+    } else {
+        0.0
+    };
+
+    Ok(PlatformSetupStatus {
+        platform_id,
+        core_mapped: mapping.is_some(),
+        core_name: mapping.as_ref().map(|m| m.core_name.clone()),
+        emulator_type: mapping.map(|m| m.emulator_type),
+        emulator_path_set,
+        bios_required: crate::bios::for_platform(&slug).next().is_some(),
+        bios: bios_readiness_for_platform(&app, db.inner(), platform_id).await?,
+        dat_imported: dat_count > 0,
+        ra_supported: platform_registry::ra_console_id(&slug).is_some(),
+        ra_configured: get_ra_credentials(app.clone()).await?.is_some(),
+        rom_count,
+        roms_with_cover,
+        cover_coverage,
+        platform_slug: slug,
+    })
+}
+
 #[tauri::command]
 pub async fn set_core_mapping(
     db: State<'_, DatabaseConnection>,
@@ -1043,28 +3084,35 @@ pub async fn set_core_mapping(
 pub async fn download_and_launch(
     app: tauri::AppHandle,
     db: State<'_, DatabaseConnection>,
+    jobs: State<'_, JobManager>,
     rom_id: i64,
     source_id: i64,
     channel: Channel<DownloadProgress>,
     save_state_slot: Option<u32>,
     save_state_path: Option<String>,
+    achievements: Option<bool>,
 ) -> AppResult<()> {
     use sea_orm::{DatabaseBackend, FromQueryResult, Statement};
 
     #[derive(Debug, FromQueryResult)]
     struct RomDownloadInfo {
+        name: String,
         file_name: String,
         file_size: Option<i64>,
         platform_id: i64,
+        platform_slug: String,
         source_rom_id: String,
         source_type: crate::entity::sources::SourceType,
+        archive_handling: ArchiveHandling,
     }
 
     // 1. Get ROM info + source type (try exact source_id first, fall back to any source)
     let rom = RomDownloadInfo::find_by_statement(Statement::from_sql_and_values(
         DatabaseBackend::Sqlite,
-        "SELECT r.file_name, r.file_size, r.platform_id, sr.source_rom_id, s.source_type
+        "SELECT r.name, r.file_name, r.file_size, r.platform_id, p.slug AS platform_slug,
+                sr.source_rom_id, s.source_type, p.archive_handling
          FROM roms r
+         JOIN platforms p ON p.id = r.platform_id
          JOIN source_roms sr ON sr.rom_id = r.id AND sr.source_id = ?
          JOIN sources s ON s.id = sr.source_id
          WHERE r.id = ?",
@@ -1079,8 +3127,10 @@ pub async fn download_and_launch(
         // Fallback: use any available source for this ROM
         RomDownloadInfo::find_by_statement(Statement::from_sql_and_values(
             DatabaseBackend::Sqlite,
-            "SELECT r.file_name, r.file_size, r.platform_id, sr.source_rom_id, s.source_type
+            "SELECT r.name, r.file_name, r.file_size, r.platform_id, p.slug AS platform_slug,
+                    sr.source_rom_id, s.source_type, p.archive_handling
              FROM roms r
+             JOIN platforms p ON p.id = r.platform_id
              JOIN source_roms sr ON sr.rom_id = r.id
              JOIN sources s ON s.id = sr.source_id
              WHERE r.id = ?
@@ -1092,7 +3142,16 @@ pub async fn download_and_launch(
         .ok_or_else(|| AppError::Other("ROM not found in any source".to_string()))?
     };
 
-    let RomDownloadInfo { file_name, file_size, platform_id, source_rom_id, source_type } = rom;
+    let RomDownloadInfo {
+        name,
+        file_name,
+        file_size,
+        platform_id,
+        platform_slug,
+        source_rom_id,
+        source_type,
+        archive_handling,
+    } = rom;
 
     // 2. Check core mapping exists
     #[derive(Debug, FromQueryResult)]
@@ -1142,22 +3201,34 @@ pub async fn download_and_launch(
         })?
     };
 
+    // Warn (but still launch) when the platform's BIOS files aren't in place
+    if is_retroarch {
+        if let Some(readiness) = bios_readiness_for_platform(&app, db.inner(), platform_id).await? {
+            if !readiness.ready {
+                let _ = channel.send(DownloadProgress::warning(
+                    rom_id,
+                    "bios_missing",
+                    format!("Missing BIOS: {}", readiness.missing.join(", ")),
+                ));
+            }
+        }
+    }
+
     // 4. Determine ROM path -- local sources use the file directly, remote sources download
     let rom_path = if source_type == crate::entity::sources::SourceType::Local {
         let path = std::path::PathBuf::from(&source_rom_id);
         if !path.exists() {
-            return Err(AppError::Other(format!(
-                "ROM file not found: {source_rom_id}"
-            )));
+            return Err(AppError::NotFound(format!("ROM file {source_rom_id}")));
         }
         path
     } else {
-        let cache_dir = directories::ProjectDirs::from("com", "romm-buddy", "romm-buddy")
-            .map_or_else(|| std::path::PathBuf::from("rom_cache"), |p| p.cache_dir().join("rom_cache"));
-        std::fs::create_dir_all(&cache_dir)?;
+        std::fs::create_dir_all(rom_cache_dir())?;
 
-        let cached = cache_dir.join(&file_name);
-        if !cached.exists() {
+        if let Some(cached) = crate::rom_cache::cached_rom_path(db.inner(), rom_id).await? {
+            cached
+        } else if crate::http::is_offline() {
+            return Err(AppError::Offline(format!("{name} hasn't been downloaded")));
+        } else {
             let _ = channel.send(DownloadProgress::status(rom_id, "downloading"));
 
             // ROMM: authenticated download
@@ -1173,50 +3244,100 @@ pub async fn download_and_launch(
             ))
             .one(db.inner())
             .await?
-            .ok_or_else(|| AppError::Other("Source not found".to_string()))?;
-            let (base_url, credentials) = (cred_row.url, cred_row.credentials);
-
-            let creds: std::collections::HashMap<String, String> =
-                serde_json::from_str(&credentials).unwrap_or_else(|e| {
-                log::warn!("Failed to parse credentials JSON: {e}");
-                HashMap::new()
-            });
-            let username = creds.get("username").cloned().unwrap_or_default();
-            let password = creds.get("password").cloned().unwrap_or_default();
-
-            let client = RommClient::new(base_url, username, password);
+            .ok_or_else(|| AppError::NotFound("Source".to_string()))?;
+            let client = RommClient::from_source(cred_row.url, &cred_row.credentials);
             #[allow(clippy::similar_names)]
             let romm_id: i64 = source_rom_id.parse().map_err(|_| {
                 AppError::Other("Invalid source ROM ID".to_string())
             })?;
 
-            let resp = client.download_rom(romm_id, &file_name).await?;
-
-            let total_bytes = resp.content_length()
-                .or_else(|| file_size.and_then(|s| u64::try_from(s).ok()))
-                .unwrap_or(0);
-            let mut downloaded: u64 = 0;
-
-            // Download to a temp file, then rename atomically to avoid partial cached files
-            let tmp_path = cache_dir.join(format!(".{file_name}.part"));
-            let mut file = tokio::fs::File::create(&tmp_path).await?;
-            let mut stream = resp.bytes_stream();
-
-            while let Some(chunk) = stream.next().await {
-                let chunk = chunk?;
-                #[allow(clippy::cast_possible_truncation)]
-                {
-                    downloaded += chunk.len() as u64;
+            let segments = read_download_segments_from_store(&app);
+            let fallback_total = file_size.and_then(|s| u64::try_from(s).ok()).unwrap_or(0);
+            let job = jobs.start(JobKind::Download, Some(rom_id), format!("Downloading {name}"));
+            let cancel = job.token();
+            let download = async {
+                let mut previous_md5 = None;
+                let mut attempt = 0;
+                loop {
+                    attempt += 1;
+                    // Download to a temp file, then move it under its content hash
+                    let tmp_path = crate::rom_cache::part_path(rom_id, &file_name);
+                    let fetched = crate::downloader::download_rom(
+                        &client,
+                        romm_id,
+                        &file_name,
+                        &tmp_path,
+                        segments,
+                        &cancel,
+                        |downloaded, total| {
+                            let total = if total > 0 { total } else { fallback_total };
+                            let _ = channel
+                                .send(DownloadProgress::downloading(rom_id, downloaded, total));
+                            job.progress(downloaded, total, file_name.as_str());
+                        },
+                    )
+                    .await;
+                    let announced_size = match fetched {
+                        Ok(size) => size,
+                        Err(e) => {
+                            let _ = tokio::fs::remove_file(&tmp_path).await;
+                            return Err(e);
+                        }
+                    };
+
+                    let _ = channel.send(DownloadProgress::status(rom_id, "verifying"));
+                    let stored = crate::rom_cache::store(
+                        db.inner(),
+                        rom_id,
+                        &tmp_path,
+                        &file_name,
+                        announced_size,
+                        attempt,
+                    )
+                    .await?;
+                    let (reason, actual_md5) = match stored {
+                        crate::rom_cache::Stored::Cached(path) => return Ok(path),
+                        crate::rom_cache::Stored::Corrupt { reason, actual_md5 } => {
+                            (reason, actual_md5)
+                        }
+                    };
+                    // The same wrong content twice in a row is what the server
+                    // has, so downloading it again won't help
+                    if actual_md5.is_some() && actual_md5 == previous_md5 {
+                        return Err(AppError::Other(format!(
+                            "{file_name} on the server doesn't match the library's hash; rescan the source"
+                        )));
+                    }
+                    if attempt == crate::rom_cache::DOWNLOAD_ATTEMPTS {
+                        return Err(AppError::Other(format!(
+                            "{file_name} failed verification after {attempt} downloads: {reason}"
+                        )));
+                    }
+                    let _ = channel.send(DownloadProgress::warning(
+                        rom_id,
+                        "retrying",
+                        format!("Download was corrupt ({reason}), retrying"),
+                    ));
+                    previous_md5 = actual_md5;
                 }
-                tokio::io::AsyncWriteExt::write_all(&mut file, &chunk).await?;
-                let _ = channel.send(DownloadProgress::downloading(rom_id, downloaded, total_bytes));
-            }
-            file.flush().await?;
-            file.sync_all().await?;
-            drop(file);
-            tokio::fs::rename(&tmp_path, &cached).await?;
+            };
+            let result =
+                crate::operation_log::timed(db.inner(), "download", Some(&file_name), download)
+                    .await;
+            job.finish(&result);
+            result?
         }
-        cached
+    };
+
+    // 5. Unpack archives for platforms whose emulator can't open them
+    let rom_path = if archive_handling == ArchiveHandling::Extract
+        && source_type != crate::entity::sources::SourceType::Local
+        && crate::rom_cache::is_extractable(&rom_path)
+    {
+        let _ = channel.send(DownloadProgress::status(rom_id, "extracting"));
+        crate::rom_cache::extract_cached(db.inner(), &rom_path, &platform_slug).await?
+    } else {
+        rom_path
     };
 
     // 6. Update play stats (upsert — library row may not exist yet)
@@ -1224,168 +3345,333 @@ pub async fn download_and_launch(
         use sea_orm::{ConnectionTrait, DatabaseBackend, Statement};
         let _ = db.inner().execute(Statement::from_sql_and_values(
             DatabaseBackend::Sqlite,
-            "INSERT INTO library (rom_id, source_id, play_count, last_played_at)
-             VALUES (?, ?, 1, strftime('%Y-%m-%dT%H:%M:%fZ', 'now'))
-             ON CONFLICT(rom_id, source_id) DO UPDATE SET
+            "INSERT INTO library (profile_id, rom_id, source_id, play_count, last_played_at)
+             VALUES ((SELECT id FROM current_profile), ?, ?, 1, strftime('%Y-%m-%dT%H:%M:%fZ', 'now'))
+             ON CONFLICT(profile_id, rom_id, source_id) DO UPDATE SET
                 play_count = play_count + 1,
                 last_played_at = strftime('%Y-%m-%dT%H:%M:%fZ', 'now'),
                 updated_at = strftime('%Y-%m-%dT%H:%M:%fZ', 'now')",
             [rom_id.into(), source_id.into()],
         )).await;
     }
-
-    // 7. Launch RetroArch
-    let _ = channel.send(DownloadProgress::status(rom_id, "launching"));
+    crate::activity::game_launched(db.inner(), rom_id).await;
 
     let rom_path_str = rom_path.to_string_lossy().to_string();
 
+    // 7. Pre-launch hook. A failing hook is reported but doesn't stop the launch.
+    let hooks = crate::launch_hooks::resolve(
+        &read_launch_hooks_from_store(&app),
+        &crate::launch_hooks::HookVars {
+            rom: &rom_path_str,
+            rom_name: &name,
+            platform: &platform_slug,
+            emulator: &emulator_type,
+        },
+    );
+    if let Some(hook) = &hooks.pre_launch {
+        let _ = channel.send(DownloadProgress::status(rom_id, "running_hook"));
+        if let Err(e) = crate::launch_hooks::run(hook).await {
+            let _ = channel.send(DownloadProgress::warning(rom_id, "hook_failed", e.to_string()));
+        }
+    }
+    let post_exit = hooks.post_exit;
+    // `open` returns as soon as the app is up; -W makes it wait for the app to quit
+    let wait_for_exit = post_exit.is_some();
+
+    // 8. Launch RetroArch
+    let _ = channel.send(DownloadProgress::status(rom_id, "launching"));
+
     if is_retroarch {
+        if let Some(enabled) = achievements {
+            configure_retroarch_achievements(&store, enabled)?;
+        }
+
+        if let Some(cheat_core) = retroarch_core_name(&core_path) {
+            match crate::cheats::stage(db.inner(), rom_id, &cheat_core, &rom_path).await {
+                Ok(true) => {
+                    if let Some(cfg) = crate::saves::retroarch_config_path() {
+                        if let Err(e) = crate::saves::patch_retroarch_cfg(
+                            &cfg,
+                            &[("apply_cheats_after_load", "true")],
+                        ) {
+                            log::warn!("Failed to turn on apply_cheats_after_load: {e}");
+                        }
+                    }
+                }
+                Ok(false) => {}
+                Err(e) => {
+                    let _ = channel.send(DownloadProgress::warning(
+                        rom_id,
+                        "cheats_failed",
+                        e.to_string(),
+                    ));
+                }
+            }
+        }
+
+        // Soft patch from the game's overrides; a patch that's gone only warns
+        let soft_patch = crate::retroarch_overrides::get(db.inner(), rom_id)
+            .await?
+            .soft_patch;
+        let patch_args = match soft_patch
+            .as_deref()
+            .map(crate::retroarch_overrides::soft_patch_args)
+        {
+            Some(Ok(args)) => args.to_vec(),
+            Some(Err(e)) => {
+                let _ = channel.send(DownloadProgress::warning(
+                    rom_id,
+                    "patch_failed",
+                    e.to_string(),
+                ));
+                Vec::new()
+            }
+            None => Vec::new(),
+        };
+
         log::info!(
             "Launching RetroArch: ra_path={ra_path}, core_path={core_path}, rom_path={rom_path_str}, source_type={source_type:?}",
         );
 
         // On macOS, .app binaries must be launched via `open` to work properly with LaunchServices.
-        let status = if ra_path.contains(".app/") {
+        let mut cmd = if ra_path.contains(".app/") {
             let app_path = ra_path.split(".app/").next().unwrap_or(&ra_path).to_string() + ".app";
             log::info!("Launching via: open {app_path} --args -L {core_path} {rom_path_str}");
             let mut cmd = std::process::Command::new("open");
+            if wait_for_exit {
+                cmd.arg("-W");
+            }
             cmd.arg(&app_path)
                 .arg("--args")
                 .arg("-L")
                 .arg(&core_path)
-                .arg(&rom_path_str);
+                .arg(&rom_path_str)
+                .args(&patch_args);
             if let Some(slot) = save_state_slot {
                 cmd.arg("-e").arg(slot.to_string());
             }
-            cmd.status()
+            cmd
         } else {
             log::info!("Launching binary directly: {ra_path} -L {core_path} {rom_path_str}");
             let mut cmd = std::process::Command::new(&ra_path);
             cmd.arg("-L")
                 .arg(&core_path)
-                .arg(&rom_path_str);
+                .arg(&rom_path_str)
+                .args(&patch_args);
             if let Some(slot) = save_state_slot {
                 cmd.arg("-e").arg(slot.to_string());
             }
-            cmd.spawn()
-                .map(|_| ())
-                .map_err(|e| AppError::Other(format!("Failed to launch RetroArch: {e}")))?;
-            let _ = channel.send(DownloadProgress::status(rom_id, "done"));
-            return Ok(());
+            cmd
         };
 
-        match status {
-            Ok(s) => {
-                log::info!("open command exited with: {s}");
-                let _ = channel.send(DownloadProgress::status(rom_id, "done"));
-                Ok(())
-            }
-            Err(e) => Err(AppError::Other(format!("Failed to launch RetroArch: {e}"))),
-        }
+        crate::launch_hooks::start(&mut cmd, post_exit)
+            .map_err(|e| AppError::Other(format!("Failed to launch RetroArch: {e}")))?;
+        let _ = channel.send(DownloadProgress::status(rom_id, "done"));
+        Ok(())
     } else {
         // Standalone emulator launch
-        let mut args = build_emulator_args(&emulator_type, &rom_path_str);
-
-        // Append save state args for standalone emulators that support it
-        if let Some(ref ss_path) = save_state_path {
-            match emulator_type.as_str() {
-                "mgba" => {
-                    args.insert(0, ss_path.clone());
-                    args.insert(0, "--savestate".into());
-                }
-                "dolphin" => {
-                    args.push("-s".into());
-                    args.push(ss_path.clone());
-                }
-                "duckstation" => {
-                    args.push("-statefile".into());
-                    args.push(ss_path.clone());
-                }
-                "pcsx2" => {
-                    args.push("-statefile".into());
-                    args.push(ss_path.clone());
-                }
-                _ => {}
-            }
-        }
+        let template = read_arg_template(&store, &emulator_type);
+        let fullscreen_flag = if template.fullscreen {
+            template.fullscreen_flag.as_deref()
+        } else {
+            None
+        };
+        let args = render_emulator_args(
+            &template.args,
+            &rom_path_str,
+            save_state_path.as_deref(),
+            fullscreen_flag,
+        );
 
         log::info!(
             "Launching standalone emulator: type={emulator_type}, path={ra_path}, args={args:?}",
         );
 
         // On macOS, use `open` for .app bundles
-        let status = if std::path::Path::new(&ra_path)
+        let mut cmd = if std::path::Path::new(&ra_path)
             .extension()
             .is_some_and(|ext| ext.eq_ignore_ascii_case("app"))
         {
             let mut cmd = std::process::Command::new("open");
+            if wait_for_exit {
+                cmd.arg("-W");
+            }
             cmd.arg(&ra_path).arg("--args");
             for arg in &args {
                 cmd.arg(arg);
             }
-            cmd.status()
+            cmd
         } else {
-            std::process::Command::new(&ra_path)
-                .args(&args)
-                .spawn()
-                .map(|_| ())
-                .map_err(|e| AppError::Other(format!("Failed to launch emulator: {e}")))?;
-            let _ = channel.send(DownloadProgress::status(rom_id, "done"));
-            return Ok(());
+            let mut cmd = std::process::Command::new(&ra_path);
+            cmd.args(&args);
+            cmd
         };
 
-        match status {
-            Ok(s) => {
-                log::info!("open command exited with: {s}");
-                let _ = channel.send(DownloadProgress::status(rom_id, "done"));
-                Ok(())
-            }
-            Err(e) => Err(AppError::Other(format!("Failed to launch emulator: {e}"))),
-        }
+        crate::launch_hooks::start(&mut cmd, post_exit)
+            .map_err(|e| AppError::Other(format!("Failed to launch emulator: {e}")))?;
+        let _ = channel.send(DownloadProgress::status(rom_id, "done"));
+        Ok(())
     }
 }
 
+/// Check a launch for known pitfalls (archive the emulator can't read,
+/// missing BIOS, file type the core doesn't list, save state older than the
+/// emulator) so the frontend can ask before `download_and_launch`.
 #[tauri::command]
-pub async fn get_available_cores(retroarch_path: String) -> AppResult<Vec<CoreInfo>> {
-    let arch = if cfg!(target_arch = "aarch64") { "arm64" } else { "x86_64" };
-    let url = format!(
-        "https://buildbot.libretro.com/nightly/apple/osx/{arch}/latest/",
-    );
+pub async fn preflight_launch(
+    app: tauri::AppHandle,
+    db: State<'_, DatabaseConnection>,
+    rom_id: i64,
+    save_state_path: Option<String>,
+) -> AppResult<Vec<LaunchWarning>> {
+    use crate::preflight::{CoreSupport, LaunchCheck, Launcher};
+    use sea_orm::{DatabaseBackend, FromQueryResult, Statement};
 
-    let tmp_file = std::env::temp_dir().join(format!("romm-buddy-cores-{}.html", std::process::id()));
-    let tmp_path = tmp_file.to_string_lossy().to_string();
-    let status = tokio::process::Command::new("/usr/bin/curl")
-        .args(["-s", "-L", "-o", &tmp_path, &url])
-        .status()
-        .await
-        .map_err(|e| AppError::Other(format!("Failed to run curl: {e}")))?;
-    if !status.success() {
-        return Err(AppError::Other(format!("curl failed with status {status}")));
+    #[derive(Debug, FromQueryResult)]
+    struct PreflightRow {
+        file_name: String,
+        platform_id: i64,
+        local_path: Option<String>,
+        core_path: Option<String>,
+        emulator_type: Option<String>,
     }
-    let html = tokio::fs::read_to_string(&tmp_file)
+
+    let row = PreflightRow::find_by_statement(Statement::from_sql_and_values(
+        DatabaseBackend::Sqlite,
+        "SELECT r.file_name, r.platform_id,
+                (SELECT sr.source_rom_id FROM source_roms sr JOIN sources s ON s.id = sr.source_id
+                 WHERE sr.rom_id = r.id AND s.source_type = 'local' LIMIT 1) as local_path,
+                cm.core_path, cm.emulator_type
+         FROM roms r
+         LEFT JOIN core_mappings cm ON cm.platform_id = r.platform_id
+         WHERE r.id = ?
+         ORDER BY cm.is_default DESC
+         LIMIT 1",
+        [rom_id.into()],
+    ))
+    .one(db.inner())
+    .await?
+    .ok_or_else(|| AppError::NotFound(format!("ROM {rom_id}")))?;
+
+    // Without a mapping the launch itself explains what to set up
+    let (Some(core_path), Some(emulator_type)) = (row.core_path, row.emulator_type) else {
+        return Ok(Vec::new());
+    };
+
+    let rom_path = match row.local_path {
+        Some(path) => Some(std::path::PathBuf::from(path)),
+        None => crate::rom_cache::cached_rom_path(db.inner(), rom_id).await?,
+    };
+
+    let store = app
+        .store("settings.json")
+        .map_err(|e| AppError::Other(e.to_string()))?;
+    let is_retroarch = emulator_type == "retroarch";
+    let (emulator_path, launcher) = if is_retroarch {
+        let core_path = std::path::PathBuf::from(core_path);
+        let core = core_path
+            .file_stem()
+            .and_then(|stem| find_info_dir().map(|dir| dir.join(stem).with_extension("info")))
+            .and_then(|info| std::fs::read_to_string(info).ok())
+            .map(|info| CoreSupport::parse(&info));
+        (core_path, Launcher::RetroArch { core })
+    } else {
+        let emu_paths: HashMap<String, String> = store
+            .get("emulator_paths")
+            .and_then(|v| serde_json::from_value(v).ok())
+            .unwrap_or_default();
+        let Some(path) = emu_paths.get(&emulator_type) else {
+            return Ok(Vec::new());
+        };
+        let builtin = EMULATOR_REGISTRY.iter().find(|e| e.id == emulator_type);
+        let name = builtin.map_or_else(|| emulator_type.clone(), |e| e.name.to_string());
+        (
+            std::path::PathBuf::from(path),
+            Launcher::Standalone {
+                name,
+                archives: builtin.map(|e| e.archives),
+            },
+        )
+    };
+
+    let check = LaunchCheck {
+        file_name: row.file_name,
+        rom_path: rom_path.filter(|p| p.exists()),
+        emulator_path,
+        launcher,
+        save_state: save_state_path.map(std::path::PathBuf::from),
+    };
+    let mut warnings = tokio::task::spawn_blocking(move || crate::preflight::check(&check))
         .await
-        .map_err(|e| AppError::Other(format!("Failed to read curl output: {e}")))?;
-    let _ = tokio::fs::remove_file(&tmp_file).await;
-    log::info!("Buildbot fetch: html_len={}", html.len());
-
-    // Parse .dylib.zip links from the HTML listing (scoped to drop non-Send types before await)
-    let available_names = {
-        let document = scraper::Html::parse_document(&html);
-        let selector = scraper::Selector::parse("a[href]").expect("hardcoded CSS selector");
-        let mut names: Vec<String> = Vec::new();
-        for element in document.select(&selector) {
-            if let Some(href) = element.value().attr("href") {
-                if let Some(stripped) = href.strip_suffix(".dylib.zip") {
-                    // href may be an absolute path; extract just the filename
-                    let name = stripped.rsplit('/').next().unwrap_or(stripped);
-                    if !name.is_empty() {
-                        names.push(name.to_string());
-                    }
-                }
+        .map_err(|e| AppError::Other(format!("Task join error: {e}")))?;
+
+    if is_retroarch {
+        if let Some(readiness) = bios_readiness_for_platform(&app, db.inner(), row.platform_id).await? {
+            if !readiness.ready {
+                warnings.push(LaunchWarning {
+                    kind: LaunchWarningKind::BiosMissing,
+                    message: format!("Missing BIOS: {}", readiness.missing.join(", ")),
+                });
             }
         }
-        names
-    };
+    }
+
+    Ok(warnings)
+}
+
+/// Patch retroarch.cfg so the upcoming launch has RetroAchievements enabled or
+/// disabled, using the stored RA username, Connect token and hardcore preference.
+fn configure_retroarch_achievements<R: tauri::Runtime>(
+    store: &tauri_plugin_store::Store<R>,
+    enabled: bool,
+) -> AppResult<()> {
+    let cfg = saves::retroarch_config_path().ok_or_else(|| {
+        AppError::NotFound("RetroArch config (retroarch.cfg)".to_string())
+    })?;
+
+    if !enabled {
+        saves::patch_retroarch_cfg(&cfg, &[("cheevos_enable", "false")])?;
+        return Ok(());
+    }
+
+    let username = store
+        .get("retroachievements_username")
+        .and_then(|v| v.as_str().map(|s| s.to_string()))
+        .filter(|s| !s.is_empty())
+        .ok_or_else(|| AppError::Other("RA username not configured".into()))?;
+    let token = store
+        .get("retroachievements_token")
+        .map(crate::secrets::open_setting)
+        .and_then(|v| v.as_str().map(|s| s.to_string()))
+        .filter(|s| !s.is_empty());
+    let hardcore = store
+        .get("retroachievements_hardcore")
+        .and_then(|v| v.as_bool())
+        .unwrap_or(false);
+
+    let mut updates = vec![
+        ("cheevos_enable", "true"),
+        ("cheevos_username", username.as_str()),
+        ("cheevos_hardcore_mode_enable", if hardcore { "true" } else { "false" }),
+    ];
+    if let Some(ref t) = token {
+        updates.push(("cheevos_token", t.as_str()));
+    } else if saves::read_retroarch_cfg_value(&cfg, "cheevos_username").as_deref()
+        != Some(username.as_str())
+    {
+        // A token issued to another account would fail to log in; let RetroArch prompt instead
+        updates.push(("cheevos_token", ""));
+    }
+
+    log::info!("[RA] Enabling achievements in {} (hardcore={hardcore})", cfg.display());
+    saves::patch_retroarch_cfg(&cfg, &updates)?;
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn get_available_cores(retroarch_path: String) -> AppResult<Vec<CoreInfo>> {
+    let available = crate::buildbot::list_cores().await?;
+    log::info!("Buildbot lists {} cores", available.len());
 
     // Get installed core names to filter them out
     let installed: Vec<CoreInfo> = detect_cores(retroarch_path).await.unwrap_or_default();
@@ -1396,12 +3682,12 @@ pub async fn get_available_cores(retroarch_path: String) -> AppResult<Vec<CoreIn
 
     let info_dir = find_info_dir();
 
-    let mut cores: Vec<CoreInfo> = available_names
-        .into_iter()
+    let mut cores: Vec<CoreInfo> = available
+        .into_keys()
         .filter(|name| !installed_names.contains(name.as_str()))
         .map(|name| {
             let display_name = info_dir.as_ref().and_then(|dir| {
-                parse_display_name(&dir.join(format!("{name}.info")))
+                parse_info_value(&dir.join(format!("{name}.info")), "display_name")
             });
             CoreInfo {
                 core_name: name,
@@ -1415,71 +3701,45 @@ pub async fn get_available_cores(retroarch_path: String) -> AppResult<Vec<CoreIn
     Ok(cores)
 }
 
+/// Download a core from the libretro buildbot into RetroArch's cores folder,
+/// reporting download progress in bytes on `channel`.
 #[tauri::command]
-pub async fn install_core(retroarch_path: String, core_name: String) -> AppResult<CoreInfo> {
-    let arch = if cfg!(target_arch = "aarch64") { "arm64" } else { "x86_64" };
-    let url = format!(
-        "https://buildbot.libretro.com/nightly/apple/osx/{arch}/latest/{core_name}.dylib.zip",
-    );
+pub async fn install_core(
+    retroarch_path: String,
+    core_name: String,
+    channel: Channel<ScanProgress>,
+) -> AppResult<CoreInfo> {
+    let item = core_name.clone();
+    install_buildbot_core(retroarch_path, core_name, move |current, total| {
+        let _ = channel.send(ScanProgress {
+            source_id: -1,
+            total,
+            current,
+            current_item: item.clone(),
+        });
+    })
+    .await
+}
 
+async fn install_buildbot_core(
+    retroarch_path: String,
+    core_name: String,
+    on_progress: impl Fn(u64, u64),
+) -> AppResult<CoreInfo> {
     let cores_dir = find_cores_dir(&retroarch_path).ok_or_else(|| {
         AppError::Other("Could not find RetroArch cores directory".to_string())
     })?;
 
-    // Download the zip via curl (Cloudflare blocks reqwest)
-    let tmp_file = std::env::temp_dir().join(format!("romm-buddy-{core_name}.dylib.zip"));
-    let tmp_path = tmp_file.to_string_lossy().to_string();
-    let status = tokio::process::Command::new("curl")
-        .args(["-s", "-L", "-f", "-o", &tmp_path, &url])
-        .status()
-        .await
-        .map_err(|e| AppError::Other(format!("Failed to run curl: {e}")))?;
-    if !status.success() {
-        return Err(AppError::Other(format!(
-            "Failed to download core: curl exit {status}"
-        )));
-    }
-    let bytes = tokio::fs::read(&tmp_file)
-        .await
-        .map_err(|e| AppError::Other(format!("Failed to read downloaded zip: {e}")))?;
-    let _ = tokio::fs::remove_file(&tmp_file).await;
-
-    // Extract .dylib from zip
-    let cursor = std::io::Cursor::new(bytes);
-    let mut archive = zip::ZipArchive::new(cursor)
-        .map_err(|e| AppError::Other(format!("Failed to open zip: {e}")))?;
-
-    let mut extracted = false;
-    for i in 0..archive.len() {
-        let mut file = archive
-            .by_index(i)
-            .map_err(|e| AppError::Other(format!("Failed to read zip entry: {e}")))?;
-        let name = file.name().to_string();
-        if std::path::Path::new(&name)
-            .extension()
-            .is_some_and(|ext| ext.eq_ignore_ascii_case("dylib"))
-        {
-            let out_path = cores_dir.join(&name);
-            let mut out_file = std::fs::File::create(&out_path)?;
-            std::io::copy(&mut file, &mut out_file)?;
-            extracted = true;
-            break;
-        }
-    }
-
-    if !extracted {
-        return Err(AppError::Other(
-            "No .dylib found in downloaded zip".to_string(),
-        ));
-    }
-
-    let core_path = cores_dir
-        .join(format!("{core_name}.dylib"))
-        .to_string_lossy()
-        .to_string();
+    let bytes = crate::buildbot::download_core(&core_name, on_progress).await?;
+    let core_path =
+        tokio::task::spawn_blocking(move || crate::buildbot::extract_core(bytes, &cores_dir))
+            .await
+            .map_err(|e| AppError::Other(format!("Task join error: {e}")))??
+            .to_string_lossy()
+            .to_string();
 
     let display_name = find_info_dir().and_then(|dir| {
-        parse_display_name(&dir.join(format!("{core_name}.info")))
+        parse_info_value(&dir.join(format!("{core_name}.info")), "display_name")
     });
 
     Ok(CoreInfo {
@@ -1489,89 +3749,499 @@ pub async fn install_core(retroarch_path: String, core_name: String) -> AppResul
     })
 }
 
-/// Key for the cancellation token map — avoids magic sentinel i64 values.
-#[derive(Debug, Clone, PartialEq, Eq, Hash)]
-pub enum CancelKey {
-    Source(i64),
-    Metadata,
-    Verification,
+/// Install cores from a file downloaded by hand, for machines that can't
+/// reach the buildbot: a single core library (`.dylib`/`.so`/`.dll`) or a
+/// zip holding one or many. Only files named after a known core are
+/// installed.
+#[tauri::command]
+pub async fn install_core_from_file(
+    retroarch_path: String,
+    path: String,
+) -> AppResult<Vec<CoreInfo>> {
+    let cores_dir = find_cores_dir(&retroarch_path)
+        .ok_or_else(|| AppError::Other("Could not find RetroArch cores directory".to_string()))?;
+    let info_dir = find_info_dir();
+    let is_known = move |core_name: &str| {
+        platform_registry::is_recommended_core(core_name)
+            || info_dir
+                .as_ref()
+                .is_some_and(|dir| dir.join(format!("{core_name}.info")).is_file())
+    };
+
+    let file = std::path::PathBuf::from(&path);
+    let file_name = file
+        .file_name()
+        .and_then(|n| n.to_str())
+        .map(str::to_string)
+        .ok_or_else(|| AppError::Other(format!("Not a file: {path}")))?;
+    let is_zip = file
+        .extension()
+        .is_some_and(|ext| ext.eq_ignore_ascii_case("zip"));
+
+    let installed = tokio::task::spawn_blocking(move || -> AppResult<Vec<std::path::PathBuf>> {
+        if is_zip {
+            let cores =
+                crate::buildbot::extract_cores(std::fs::File::open(&file)?, &cores_dir, is_known)?;
+            if cores.is_empty() {
+                return Err(AppError::Other(format!(
+                    "{file_name} has no known .{} cores in it",
+                    crate::buildbot::core_extension()
+                )));
+            }
+            return Ok(cores);
+        }
+
+        let core_name = crate::buildbot::core_name_of(&file_name).ok_or_else(|| {
+            AppError::Other(format!(
+                "{file_name} isn't a libretro core for this system (expected name_libretro.{})",
+                crate::buildbot::core_extension()
+            ))
+        })?;
+        if !is_known(core_name) {
+            return Err(AppError::Other(format!(
+                "{core_name} is not a known libretro core"
+            )));
+        }
+        let bytes = std::fs::read(&file)?;
+        let core_path = crate::buildbot::install_library(&bytes, &file_name, &cores_dir)?;
+        Ok(vec![core_path])
+    })
+    .await
+    .map_err(|e| AppError::Other(format!("Task join error: {e}")))??;
+
+    let info_dir = find_info_dir();
+    Ok(installed
+        .into_iter()
+        .filter_map(|core_path| {
+            let core_name = core_path.file_stem()?.to_str()?.to_string();
+            let display_name = info_dir.as_ref().and_then(|dir| {
+                parse_info_value(&dir.join(format!("{core_name}.info")), "display_name")
+            });
+            Some(CoreInfo {
+                core_name,
+                core_path: core_path.to_string_lossy().to_string(),
+                display_name,
+            })
+        })
+        .collect())
+}
+
+/// Curated cores for a platform, best first, flagged with whether each is installed.
+#[tauri::command]
+pub async fn recommend_cores(
+    app: tauri::AppHandle,
+    platform_slug: String,
+) -> AppResult<Vec<CoreRecommendation>> {
+    let installed = match get_retroarch_path(app).await? {
+        Some(ra_path) => detect_cores(ra_path).await?,
+        None => Vec::new(),
+    };
+    let info_dir = find_info_dir();
+
+    Ok(platform_registry::recommended_cores(&platform_slug)
+        .iter()
+        .map(|&name| {
+            let core = installed.iter().find(|c| c.core_name == name);
+            CoreRecommendation {
+                core_name: name.to_string(),
+                display_name: core.and_then(|c| c.display_name.clone()).or_else(|| {
+                    info_dir.as_ref().and_then(|dir| {
+                        parse_info_value(&dir.join(format!("{name}.info")), "display_name")
+                    })
+                }),
+                installed: core.is_some(),
+                core_path: core.map(|c| c.core_path.clone()),
+            }
+        })
+        .collect())
+}
+
+/// First-run setup: map every platform that has ROMs to its recommended core,
+/// preferring one that's already installed and installing the top pick
+/// otherwise. Existing mappings are kept unless `overwrite` is set.
+#[tauri::command]
+pub async fn setup_recommended_cores(
+    app: tauri::AppHandle,
+    db: State<'_, DatabaseConnection>,
+    platform_ids: Option<Vec<i64>>,
+    overwrite: Option<bool>,
+) -> AppResult<CoreSetupReport> {
+    use sea_orm::{DatabaseBackend, FromQueryResult, Statement};
+
+    let ra_path = get_retroarch_path(app).await?.ok_or_else(|| {
+        AppError::Other("RetroArch path not configured. Set it in Settings.".to_string())
+    })?;
+    let mut installed: HashMap<String, String> = detect_cores(ra_path.clone())
+        .await?
+        .into_iter()
+        .map(|c| (c.core_name, c.core_path))
+        .collect();
+
+    #[derive(Debug, FromQueryResult)]
+    struct PlatformRow {
+        id: i64,
+        slug: String,
+    }
+
+    let mapping_filter = if overwrite.unwrap_or(false) {
+        ""
+    } else {
+        " AND NOT EXISTS (SELECT 1 FROM core_mappings cm WHERE cm.platform_id = p.id)"
+    };
+    let platforms = PlatformRow::find_by_statement(Statement::from_string(
+        DatabaseBackend::Sqlite,
+        format!(
+            "SELECT p.id, p.slug FROM platforms p
+             WHERE EXISTS (SELECT 1 FROM roms r WHERE r.platform_id = p.id){mapping_filter}
+             ORDER BY p.slug"
+        ),
+    ))
+    .all(db.inner())
+    .await?
+    .into_iter()
+    .filter(|p| platform_ids.as_ref().is_none_or(|ids| ids.contains(&p.id)));
+
+    let mut report = CoreSetupReport::default();
+    for platform in platforms {
+        let recommended = platform_registry::recommended_cores(&platform.slug);
+        let Some(&top_pick) = recommended.first() else {
+            report.skipped.push(platform.slug);
+            continue;
+        };
+
+        let core_name = recommended
+            .iter()
+            .find(|name| installed.contains_key(**name))
+            .copied()
+            .unwrap_or(top_pick);
+        if !installed.contains_key(core_name) {
+            match install_buildbot_core(ra_path.clone(), core_name.to_string(), |_, _| {}).await {
+                Ok(core) => {
+                    report.installed.push(core.core_name.clone());
+                    installed.insert(core.core_name, core.core_path);
+                }
+                Err(e) => {
+                    report.failed.push(format!("{}: {e}", platform.slug));
+                    continue;
+                }
+            }
+        }
+
+        set_core_mapping(
+            db.clone(),
+            platform.id,
+            core_name.to_string(),
+            installed[core_name].clone(),
+            None,
+        )
+        .await?;
+        report.mapped.push(PlatformCoreSetup {
+            platform_id: platform.id,
+            platform_slug: platform.slug,
+            core_name: core_name.to_string(),
+        });
+    }
+
+    Ok(report)
+}
+
+// -- Metadata enrichment commands --
+
+#[tauri::command]
+pub async fn update_launchbox_db(
+    db: State<'_, DatabaseConnection>,
+    jobs: State<'_, JobManager>,
+    channel: Channel<ScanProgress>,
+) -> AppResult<()> {
+    let job = jobs.start(JobKind::LaunchBoxImport, None, "Updating the LaunchBox database");
+    let cancel = job.token();
+
+    let result = async {
+        // Pick up a cancelled import, or download and extract Metadata.xml
+        if !crate::metadata::launchbox::has_resumable_import(db.inner()).await? {
+            let channel_clone = channel.clone();
+            let outcome = crate::metadata::launchbox::download_and_extract(db.inner(), job.reporter(move |progress| {
+                let _ = channel_clone.send(progress);
+            }), cancel.clone())
+            .await?;
+            if matches!(outcome, crate::metadata::launchbox::DownloadOutcome::UpToDate) {
+                return Ok(());
+            }
+        }
+
+        // Import into SQLite tables
+        crate::metadata::launchbox::import_to_db(db.inner(), job.reporter(move |progress| {
+            let _ = channel.send(progress);
+        }), cancel.clone())
+        .await
+    }
+    .await;
+    job.finish(&result);
+    result
+}
+
+fn read_homebrew_index_url_from_store(app: &impl SettingsRead) -> Option<String> {
+    app.setting("homebrew_index_url")
+        .and_then(|v| v.as_str().map(str::to_string))
+        .filter(|url| !url.is_empty())
 }
 
-/// Managed state for sync cancellation tokens.
-pub struct CancelTokenMap(pub tokio::sync::Mutex<HashMap<CancelKey, CancellationToken>>);
+#[tauri::command]
+pub async fn get_homebrew_index_url(app: tauri::AppHandle) -> AppResult<Option<String>> {
+    Ok(read_homebrew_index_url_from_store(&app))
+}
 
-// -- Metadata enrichment commands --
+/// Set the URL of the JSON homebrew/hack index, or clear it with `None`.
+#[tauri::command]
+pub async fn set_homebrew_index_url(app: tauri::AppHandle, url: Option<String>) -> AppResult<()> {
+    let url = url.map(|u| u.trim().to_string()).filter(|u| !u.is_empty());
+    if let Some(url) = &url {
+        reqwest::Url::parse(url).map_err(|e| AppError::Other(format!("Invalid URL: {e}")))?;
+    }
+    let store = app
+        .store("settings.json")
+        .map_err(|e| AppError::Other(e.to_string()))?;
+    match url {
+        Some(url) => store.set("homebrew_index_url", serde_json::json!(url)),
+        None => {
+            store.delete("homebrew_index_url");
+        }
+    }
+    store
+        .save()
+        .map_err(|e| AppError::Other(e.to_string()))?;
+    crate::settings::save_to_db(&app).await?;
+    Ok(())
+}
 
+/// Download the configured homebrew index, replacing the stored one.
+/// Returns the number of entries.
 #[tauri::command]
-pub async fn update_launchbox_db(
+pub async fn update_homebrew_index(
+    app: tauri::AppHandle,
     db: State<'_, DatabaseConnection>,
-    cancel_tokens: State<'_, CancelTokenMap>,
-    channel: Channel<ScanProgress>,
-) -> AppResult<()> {
-    let cancel = CancellationToken::new();
-    cancel_tokens.0.lock().await.insert(CancelKey::Metadata, cancel.clone());
-
-    // Download and extract Metadata.xml
-    let channel_clone = channel.clone();
-    crate::metadata::launchbox::download_and_extract(move |progress| {
-        let _ = channel_clone.send(progress);
-    }, cancel.clone())
-    .await?;
+) -> AppResult<u64> {
+    let url = read_homebrew_index_url_from_store(&app)
+        .ok_or_else(|| AppError::Other("No homebrew index URL is set".to_string()))?;
+    crate::metadata::homebrew::update_index(db.inner(), &url).await
+}
 
-    // Import into SQLite tables
-    let result = crate::metadata::launchbox::import_to_db(db.inner(), move |progress| {
-        let _ = channel.send(progress);
-    })
-    .await;
-    cancel_tokens.0.lock().await.remove(&CancelKey::Metadata);
-    result
+/// Forget the lookups `provider` (or, with `None`, every provider) had
+/// nothing for, so the next enrichment run asks again. Returns the number
+/// of cached misses dropped.
+#[tauri::command]
+pub async fn clear_provider_cache(
+    db: State<'_, DatabaseConnection>,
+    provider: Option<MetadataProvider>,
+) -> AppResult<u64> {
+    crate::metadata::misses::clear(db.inner(), provider).await
 }
 
 #[tauri::command]
 pub async fn fetch_metadata(
     app: tauri::AppHandle,
     db: State<'_, DatabaseConnection>,
-    cancel_tokens: State<'_, CancelTokenMap>,
+    jobs: State<'_, JobManager>,
+    priority: State<'_, crate::metadata::PriorityLane>,
     platform_id: Option<i64>,
     search: Option<String>,
+    options: Option<EnrichmentOptions>,
     channel: Channel<ScanProgress>,
 ) -> AppResult<()> {
-    let cancel = CancellationToken::new();
-    cancel_tokens.0.lock().await.insert(CancelKey::Metadata, cancel.clone());
+    let label = match platform_id {
+        Some(id) => format!("Fetching metadata for platform {id}"),
+        None => "Fetching metadata".to_string(),
+    };
+    let job = jobs.start(JobKind::Enrichment, platform_id, label);
+
+    let steps = options.unwrap_or_else(|| read_enrichment_options_from_store(&app));
 
     // Read IGDB credentials and construct client if available
     let igdb_client = read_igdb_client_from_store(&app);
 
     // Read ScreenScraper credentials if available
     let ss_creds = read_ss_creds_from_store(&app);
+    let ss_prefs = read_ss_prefs_from_store(&app);
 
     let result = crate::metadata::enrich_roms(
-        platform_id,
-        search.as_deref(),
+        crate::metadata::RomSelection::Unenriched {
+            platform_id,
+            search: search.as_deref(),
+        },
         db.inner(),
-        move |progress| {
+        job.reporter(move |progress| {
             let _ = channel.send(progress);
-        },
-        cancel,
+        }),
+        job.token(),
+        igdb_client.as_ref(),
+        ss_creds.as_ref(),
+        &ss_prefs,
+        &read_artwork_chains_from_store(&app),
+        read_artwork_dedup_from_store(&app),
+        priority.inner(),
+        &LibraryEvents::new(&app),
+        read_translator_from_store(&app).as_ref(),
+        &steps,
+    )
+    .await;
+
+    job.finish(&result);
+    if !jobs.is_running(JobKind::Enrichment) {
+        priority.set(Vec::new());
+    }
+    result
+}
+
+const DEFAULT_METADATA_STALE_DAYS: u32 = 90;
+
+fn read_metadata_stale_days_from_store(app: &tauri::AppHandle) -> u32 {
+    app.store("settings.json")
+        .ok()
+        .and_then(|store| store.get("metadata_stale_days"))
+        .and_then(|v| v.as_u64())
+        .and_then(|d| u32::try_from(d).ok())
+        .unwrap_or(DEFAULT_METADATA_STALE_DAYS)
+}
+
+#[tauri::command]
+pub async fn get_metadata_stale_days(app: tauri::AppHandle) -> AppResult<u32> {
+    Ok(read_metadata_stale_days_from_store(&app))
+}
+
+/// Set how old metadata must be before `refresh_stale_metadata` re-fetches it.
+#[tauri::command]
+pub async fn set_metadata_stale_days(app: tauri::AppHandle, days: u32) -> AppResult<()> {
+    if days == 0 {
+        return Err(AppError::Other("Days must be at least 1".to_string()));
+    }
+    let store = app
+        .store("settings.json")
+        .map_err(|e| AppError::Other(e.to_string()))?;
+    store.set("metadata_stale_days", serde_json::json!(days));
+    store
+        .save()
+        .map_err(|e| AppError::Other(e.to_string()))?;
+    crate::settings::save_to_db(&app).await?;
+    Ok(())
+}
+
+/// Re-enrich ROMs whose metadata is older than `days` (default: the saved
+/// setting), bypassing provider caches. Pinned metadata is left alone.
+/// Cancelled with `cancel_metadata`.
+#[tauri::command]
+pub async fn refresh_stale_metadata(
+    app: tauri::AppHandle,
+    db: State<'_, DatabaseConnection>,
+    jobs: State<'_, JobManager>,
+    priority: State<'_, crate::metadata::PriorityLane>,
+    days: Option<u32>,
+    channel: Channel<ScanProgress>,
+) -> AppResult<()> {
+    let job = jobs.start(JobKind::Enrichment, None, "Refreshing stale metadata");
+
+    let days = days.unwrap_or_else(|| read_metadata_stale_days_from_store(&app));
+    let steps = EnrichmentOptions {
+        covers_only: false,
+        force_refresh: true,
+        ..read_enrichment_options_from_store(&app)
+    };
+    let igdb_client = read_igdb_client_from_store(&app);
+    let ss_creds = read_ss_creds_from_store(&app);
+    let ss_prefs = read_ss_prefs_from_store(&app);
+
+    let result = crate::metadata::enrich_roms(
+        crate::metadata::RomSelection::Stale { days },
+        db.inner(),
+        job.reporter(move |progress| {
+            let _ = channel.send(progress);
+        }),
+        job.token(),
         igdb_client.as_ref(),
         ss_creds.as_ref(),
+        &ss_prefs,
+        &read_artwork_chains_from_store(&app),
+        read_artwork_dedup_from_store(&app),
+        priority.inner(),
+        &LibraryEvents::new(&app),
+        read_translator_from_store(&app).as_ref(),
+        &steps,
     )
     .await;
 
-    cancel_tokens.0.lock().await.remove(&CancelKey::Metadata);
+    job.finish(&result);
+    if !jobs.is_running(JobKind::Enrichment) {
+        priority.set(Vec::new());
+    }
     result
 }
 
+/// Pin a ROM's metadata so stale-metadata refreshes skip it, or unpin it.
+#[tauri::command]
+pub async fn set_metadata_pinned(
+    db: State<'_, DatabaseConnection>,
+    rom_id: i64,
+    pinned: bool,
+) -> AppResult<()> {
+    use sea_orm::{ConnectionTrait, DatabaseBackend, Statement};
+    db.inner()
+        .execute(Statement::from_sql_and_values(
+            DatabaseBackend::Sqlite,
+            "INSERT INTO metadata (rom_id, pinned) VALUES (?, ?)
+             ON CONFLICT(rom_id) DO UPDATE SET pinned = excluded.pinned",
+            [rom_id.into(), pinned.into()],
+        ))
+        .await?;
+    Ok(())
+}
+
+/// Move the given ROMs (typically those visible in the library grid) to the
+/// front of the running enrichment job.
+#[tauri::command]
+pub async fn prioritize_roms(
+    priority: State<'_, crate::metadata::PriorityLane>,
+    rom_ids: Vec<i64>,
+) -> AppResult<()> {
+    priority.set(rom_ids);
+    Ok(())
+}
+
+/// Cancel the enrichment running for `platform_id`, or every enrichment
+/// run and LaunchBox import when it's omitted.
 #[tauri::command]
 pub async fn cancel_metadata(
-    cancel_tokens: State<'_, CancelTokenMap>,
+    jobs: State<'_, JobManager>,
+    platform_id: Option<i64>,
 ) -> AppResult<()> {
-    if let Some(token) = cancel_tokens.0.lock().await.get(&CancelKey::Metadata) {
-        token.cancel();
+    jobs.cancel_kind(JobKind::Enrichment, platform_id);
+    if platform_id.is_none() {
+        jobs.cancel_kind(JobKind::LaunchBoxImport, None);
     }
     Ok(())
 }
 
+/// LaunchBox matches made by fuzzy name similarity, least confident first.
+#[tauri::command]
+pub async fn get_launchbox_fuzzy_matches(
+    db: State<'_, DatabaseConnection>,
+    include_rejected: bool,
+) -> AppResult<Vec<LaunchBoxFuzzyMatch>> {
+    crate::metadata::launchbox::fuzzy_matches(db.inner(), include_rejected).await
+}
+
+/// Reject a ROM's fuzzy LaunchBox match and drop what it contributed.
+#[tauri::command]
+pub async fn reject_launchbox_match(
+    app: tauri::AppHandle,
+    db: State<'_, DatabaseConnection>,
+    rom_id: i64,
+) -> AppResult<()> {
+    crate::metadata::launchbox::reject_match(db.inner(), rom_id).await?;
+    let events = LibraryEvents::new(&app);
+    events.metadata_updated(vec![rom_id]);
+    events.artwork_updated(vec![rom_id]);
+    Ok(())
+}
+
 #[tauri::command]
 pub async fn has_launchbox_db(
     db: State<'_, DatabaseConnection>,
@@ -1579,12 +4249,53 @@ pub async fn has_launchbox_db(
     Ok(crate::metadata::launchbox::has_imported_db(db.inner()).await)
 }
 
+/// Path to a ROM file readable on this machine: the local source file, or the
+/// download cache copy for remote ROMs. `None` if neither exists.
+pub(crate) async fn local_rom_path(
+    db: &DatabaseConnection,
+    rom_id: i64,
+) -> AppResult<Option<std::path::PathBuf>> {
+    use sea_orm::{DatabaseBackend, FromQueryResult, Statement};
+
+    #[derive(Debug, FromQueryResult)]
+    struct RomPathRow {
+        file_name: String,
+        source_rom_id: Option<String>,
+        source_type: Option<crate::entity::sources::SourceType>,
+    }
+    let rows = RomPathRow::find_by_statement(Statement::from_sql_and_values(
+        DatabaseBackend::Sqlite,
+        "SELECT r.file_name, sr.source_rom_id, s.source_type
+         FROM roms r
+         LEFT JOIN source_roms sr ON sr.rom_id = r.id
+         LEFT JOIN sources s ON s.id = sr.source_id
+         WHERE r.id = ?",
+        [rom_id.into()],
+    ))
+    .all(db)
+    .await?;
+
+    for row in &rows {
+        if row.source_type == Some(crate::entity::sources::SourceType::Local) {
+            if let Some(path) = row.source_rom_id.as_ref().map(std::path::PathBuf::from) {
+                if path.exists() {
+                    return Ok(Some(path));
+                }
+            }
+        }
+    }
+    if rows.is_empty() {
+        return Ok(None);
+    }
+    crate::rom_cache::cached_rom_path(db, rom_id).await
+}
+
 async fn compute_rom_hash_inner(
     db: &DatabaseConnection,
     rom_id: i64,
 ) -> AppResult<Option<String>> {
     use crate::entity::roms;
-    use sea_orm::{ConnectionTrait, DatabaseBackend, EntityTrait, FromQueryResult, Statement};
+    use sea_orm::EntityTrait;
 
     // Check if already computed
     let rom_model = roms::Entity::find_by_id(rom_id).one(db).await?;
@@ -1596,6 +4307,15 @@ async fn compute_rom_hash_inner(
         }
     }
 
+    let detail = format!("rom {rom_id}");
+    crate::operation_log::timed(db, "hash", Some(&detail), hash_rom_file(db, rom_id)).await
+}
+
+/// Hash a ROM's file and store the MD5, downloading it to a temp file first
+/// when it's on a ROMM server.
+async fn hash_rom_file(db: &DatabaseConnection, rom_id: i64) -> AppResult<Option<String>> {
+    use sea_orm::{ConnectionTrait, DatabaseBackend, FromQueryResult, Statement};
+
     // Get ROM info to determine how to access the file
     #[derive(Debug, FromQueryResult)]
     struct RomInfoRow {
@@ -1611,7 +4331,7 @@ async fn compute_rom_hash_inner(
     ))
     .one(db)
     .await?
-    .ok_or_else(|| AppError::Other(format!("ROM {rom_id} not found")))?;
+    .ok_or_else(|| AppError::NotFound(format!("ROM {rom_id}")))?;
 
     let (file_name, source_rom_id, source_type, source_id) =
         (info.file_name, info.source_rom_id, info.source_type, info.source_id);
@@ -1654,18 +4374,8 @@ async fn compute_rom_hash_inner(
     ))
     .one(db)
     .await?
-    .ok_or_else(|| AppError::Other(format!("Source {source_id} not found")))?;
-    let (base_url, credentials) = (creds_row.url, creds_row.credentials);
-
-    let creds: std::collections::HashMap<String, String> =
-        serde_json::from_str(&credentials).unwrap_or_else(|e| {
-            log::warn!("Failed to parse credentials JSON: {e}");
-            std::collections::HashMap::new()
-        });
-    let username = creds.get("username").cloned().unwrap_or_default();
-    let password = creds.get("password").cloned().unwrap_or_default();
-
-    let client = RommClient::new(base_url, username, password);
+    .ok_or_else(|| AppError::NotFound(format!("Source {source_id}")))?;
+    let client = RommClient::from_source(creds_row.url, &creds_row.credentials);
     let romm_id: i64 = source_rom_id
         .parse()
         .map_err(|_| AppError::Other("Invalid source ROM ID".into()))?;
@@ -1673,10 +4383,7 @@ async fn compute_rom_hash_inner(
     let resp = client.download_rom(romm_id, &file_name).await?;
 
     if !resp.status().is_success() {
-        return Err(AppError::Other(format!(
-            "Download failed: {}",
-            resp.status()
-        )));
+        return Err(crate::http::status_error("ROMM", &resp));
     }
 
     // Stream to temp file
@@ -1711,66 +4418,309 @@ async fn compute_rom_hash_inner(
 }
 
 #[tauri::command]
-pub async fn compute_rom_hash(
+pub async fn compute_rom_hash(
+    db: State<'_, DatabaseConnection>,
+    rom_id: i64,
+) -> AppResult<Option<String>> {
+    compute_rom_hash_inner(db.inner(), rom_id).await
+}
+
+#[tauri::command]
+pub async fn enrich_single_rom(
+    app: tauri::AppHandle,
+    db: State<'_, DatabaseConnection>,
+    rom_id: i64,
+) -> AppResult<RomWithMeta> {
+    let igdb_client = read_igdb_client_from_store(&app);
+    let ss_creds = read_ss_creds_from_store(&app);
+    let ss_prefs = read_ss_prefs_from_store(&app);
+    crate::metadata::enrich_single_rom(
+        rom_id,
+        db.inner(),
+        igdb_client.as_ref(),
+        ss_creds.as_ref(),
+        &ss_prefs,
+        &read_artwork_chains_from_store(&app),
+        read_artwork_dedup_from_store(&app),
+        &LibraryEvents::new(&app),
+        read_translator_from_store(&app).as_ref(),
+        &read_enrichment_options_from_store(&app),
+    )
+    .await?;
+
+    // Return the updated ROM data
+    fetch_rom_with_meta(db.inner(), rom_id).await
+}
+
+/// Hand-edit a ROM's metadata. Edited fields are kept through later
+/// enrichment and syncs until they are reset.
+#[tauri::command]
+pub async fn update_rom_metadata(
+    app: tauri::AppHandle,
+    db: State<'_, DatabaseConnection>,
+    rom_id: i64,
+    fields: MetadataEdit,
+) -> AppResult<RomWithMeta> {
+    crate::metadata::edits::apply(db.inner(), rom_id, &fields).await?;
+    LibraryEvents::new(&app).metadata_updated(vec![rom_id]);
+    fetch_rom_with_meta(db.inner(), rom_id).await
+}
+
+/// Apply the same metadata edit to several ROMs at once. Returns how many were edited.
+#[tauri::command]
+pub async fn batch_update_rom_metadata(
+    app: tauri::AppHandle,
+    db: State<'_, DatabaseConnection>,
+    rom_ids: Vec<i64>,
+    fields: MetadataEdit,
+) -> AppResult<usize> {
+    use sea_orm::TransactionTrait;
+
+    let txn = db.inner().begin().await?;
+    for &rom_id in &rom_ids {
+        crate::metadata::edits::apply(&txn, rom_id, &fields).await?;
+    }
+    txn.commit().await?;
+
+    LibraryEvents::new(&app).metadata_updated(rom_ids.clone());
+    Ok(rom_ids.len())
+}
+
+/// Fetch a single ROM with all metadata, cover, and screenshots.
+async fn fetch_rom_with_meta(db: &DatabaseConnection, rom_id: i64) -> AppResult<RomWithMeta> {
+    use crate::entity::artwork;
+    use sea_orm::{ColumnTrait, EntityTrait, QueryFilter, QueryOrder};
+
+    let q = format!(
+        "{ROM_WITH_META_SELECT}{ROM_STATE_JOINS}
+         LEFT JOIN metadata m ON m.rom_id = r.id
+         LEFT JOIN hasheous_cache hc ON hc.rom_id = r.id
+         LEFT JOIN source_roms sr ON sr.rom_id = r.id
+                 LEFT JOIN sources s ON s.id = sr.source_id
+         WHERE r.id = ?
+         GROUP BY r.id",
+    );
+    let rows = query_rom_rows(db, &q, vec![rom_id.into()]).await?;
+    let row = rows.into_iter().next()
+        .ok_or_else(|| crate::error::AppError::NotFound(format!("ROM {rom_id}")))?;
+    let mut rom = row.into_rom_with_meta();
+
+    // Fetch screenshot URLs separately (multiple per ROM)
+    rom.screenshot_urls = artwork::Entity::find()
+        .filter(artwork::Column::RomId.eq(rom_id))
+        .filter(artwork::Column::ArtType.is_in([
+            "screenshot",
+            crate::user_screenshots::USER_SCREENSHOT,
+        ]))
+        .order_by_asc(artwork::Column::Id)
+        .all(db)
+        .await?
+        .into_iter()
+        .filter_map(|m| m.url)
+        .collect();
+
+    Ok(rom)
+}
+
+#[tauri::command]
+pub async fn get_rom(
+    db: State<'_, DatabaseConnection>,
+    rom_id: i64,
+) -> AppResult<RomWithMeta> {
+    use sea_orm::{ConnectionTrait, DatabaseBackend, Statement};
+
+    let allowed = db
+        .inner()
+        .query_one(Statement::from_sql_and_values(
+            DatabaseBackend::Sqlite,
+            format!("SELECT 1 FROM roms r WHERE r.id = ? AND {CONTENT_RATING_CLAUSE}"),
+            [rom_id.into()],
+        ))
+        .await?
+        .is_some();
+    if !allowed {
+        return Err(AppError::NotFound(format!("ROM {rom_id}")));
+    }
+    let mut rom = fetch_rom_with_meta(db.inner(), rom_id).await?;
+    rom.other_platforms = other_platform_copies(db.inner(), &rom).await?;
+    Ok(rom)
+}
+
+/// The same game on other platforms: matched by IGDB or Hasheous id, or by
+/// normalized name. One copy per platform, sorted by platform name.
+async fn other_platform_copies(
+    db: &DatabaseConnection,
+    rom: &RomWithMeta,
+) -> AppResult<Vec<PlatformCopy>> {
+    use crate::metadata::launchbox::normalize_for_match;
+    use sea_orm::{DatabaseBackend, FromQueryResult, Statement};
+
+    #[derive(Debug, FromQueryResult)]
+    struct CopyRow {
+        rom_id: i64,
+        name: String,
+        platform_id: i64,
+        platform_slug: String,
+        platform_name: String,
+    }
+
+    const COPY_SELECT: &str =
+        "SELECT r.id AS rom_id, r.name, p.id AS platform_id, p.slug AS platform_slug,
+                p.name AS platform_name
+         FROM roms r JOIN platforms p ON p.id = r.platform_id";
+
+    let by_id = CopyRow::find_by_statement(Statement::from_sql_and_values(
+        DatabaseBackend::Sqlite,
+        format!(
+            "WITH target AS (
+                 SELECT m.igdb_id, hc.hasheous_id, CAST(hc.igdb_game_id AS INTEGER) AS hc_igdb_id
+                 FROM roms r
+                 LEFT JOIN metadata m ON m.rom_id = r.id
+                 LEFT JOIN hasheous_cache hc ON hc.rom_id = r.id
+                 WHERE r.id = ?
+             )
+             {COPY_SELECT}
+             LEFT JOIN metadata m ON m.rom_id = r.id
+             LEFT JOIN hasheous_cache hc ON hc.rom_id = r.id
+             CROSS JOIN target t
+             WHERE r.platform_id != ? AND p.archived = 0 AND {CONTENT_RATING_CLAUSE}
+               AND (m.igdb_id IN (t.igdb_id, t.hc_igdb_id)
+                    OR CAST(hc.igdb_game_id AS INTEGER) IN (t.igdb_id, t.hc_igdb_id)
+                    OR hc.hasheous_id = t.hasheous_id)
+             ORDER BY r.id"
+        ),
+        [rom.id.into(), rom.platform_id.into()],
+    ))
+    .all(db)
+    .await?;
+
+    // Name matches: FTS narrows the candidates, the normalized names decide.
+    let normalized = normalize_for_match(&rom.name);
+    let by_name = match crate::search::fts_query(&normalized) {
+        Some(fts) => CopyRow::find_by_statement(Statement::from_sql_and_values(
+            DatabaseBackend::Sqlite,
+            format!(
+                "{COPY_SELECT}
+                 JOIN roms_fts ON roms_fts.rowid = r.id
+                 WHERE roms_fts MATCH ? AND r.platform_id != ? AND p.archived = 0
+                   AND {CONTENT_RATING_CLAUSE}
+                 ORDER BY r.id"
+            ),
+            [fts.into(), rom.platform_id.into()],
+        ))
+        .all(db)
+        .await?
+        .into_iter()
+        .filter(|row| normalize_for_match(&row.name) == normalized)
+        .collect(),
+        None => Vec::new(),
+    };
+
+    let mut copies: Vec<PlatformCopy> = Vec::new();
+    for row in by_id.into_iter().chain(by_name) {
+        if copies.iter().any(|c| c.platform_id == row.platform_id) {
+            continue;
+        }
+        copies.push(PlatformCopy {
+            rom_id: row.rom_id,
+            name: row.name,
+            platform_id: row.platform_id,
+            platform_slug: row.platform_slug,
+            platform_name: row.platform_name,
+        });
+    }
+    copies.sort_by(|a, b| a.platform_name.cmp(&b.platform_name));
+    Ok(copies)
+}
+
+const DEFAULT_SIMILAR_LIMIT: i64 = 12;
+const MAX_SIMILAR_LIMIT: i64 = 50;
+
+/// Library entries like `rom_id` for "More like this": a shared franchise
+/// scores 5, the same developer 3, each shared genre 2 and each shared
+/// theme 1. Copies of the game itself (same name) are left out.
+#[tauri::command]
+pub async fn get_similar_roms(
     db: State<'_, DatabaseConnection>,
     rom_id: i64,
-) -> AppResult<Option<String>> {
-    compute_rom_hash_inner(db.inner(), rom_id).await
+    limit: Option<i64>,
+) -> AppResult<Vec<RomWithMeta>> {
+    let limit = limit
+        .unwrap_or(DEFAULT_SIMILAR_LIMIT)
+        .clamp(1, MAX_SIMILAR_LIMIT);
+    let q = format!(
+        "WITH target AS (
+             SELECT r.name, m.developer, ic.franchise_name,
+                    COALESCE(m.genres, '[]') AS genres, COALESCE(m.themes, '[]') AS themes
+             FROM roms r
+             LEFT JOIN metadata m ON m.rom_id = r.id
+             LEFT JOIN igdb_cache ic ON ic.rom_id = r.id
+             WHERE r.id = ?
+         ),
+         similar AS (
+             SELECT rom_id, score FROM (
+                 SELECT r.id AS rom_id,
+                        CASE WHEN ic.franchise_name = t.franchise_name COLLATE NOCASE THEN 5 ELSE 0 END
+                        + CASE WHEN m.developer = t.developer COLLATE NOCASE THEN 3 ELSE 0 END
+                        + 2 * (SELECT COUNT(*) FROM json_each(m.genres) g
+                               WHERE g.value IN (SELECT value FROM json_each(t.genres)))
+                        + (SELECT COUNT(*) FROM json_each(m.themes) th
+                           WHERE th.value IN (SELECT value FROM json_each(t.themes))) AS score
+                 FROM roms r
+                 JOIN metadata m ON m.rom_id = r.id
+                 LEFT JOIN igdb_cache ic ON ic.rom_id = r.id
+                 CROSS JOIN target t
+                 WHERE r.id != ? AND r.name != t.name COLLATE NOCASE
+                   AND {NOT_ARCHIVED_CLAUSE} AND {CONTENT_RATING_CLAUSE}
+             )
+             WHERE score > 0
+             ORDER BY score DESC
+             LIMIT ?
+         )
+         {ROM_WITH_META_SELECT}{ROM_STATE_JOINS}
+         JOIN similar sim ON sim.rom_id = r.id
+         LEFT JOIN metadata m ON m.rom_id = r.id
+         LEFT JOIN hasheous_cache hc ON hc.rom_id = r.id
+         LEFT JOIN source_roms sr ON sr.rom_id = r.id
+         LEFT JOIN sources s ON s.id = sr.source_id
+         GROUP BY r.id
+         ORDER BY MAX(sim.score) DESC, r.name"
+    );
+    let rows = query_rom_rows(
+        db.inner(),
+        &q,
+        vec![rom_id.into(), rom_id.into(), limit.into()],
+    )
+    .await?;
+    Ok(rows.into_iter().map(RomWithMetaRow::into_rom_with_meta).collect())
 }
 
+/// All artwork stored for a ROM, with the provider and region each item came from.
 #[tauri::command]
-pub async fn enrich_single_rom(
-    app: tauri::AppHandle,
+pub async fn get_rom_artwork(
     db: State<'_, DatabaseConnection>,
     rom_id: i64,
-) -> AppResult<RomWithMeta> {
-    let igdb_client = read_igdb_client_from_store(&app);
-    let ss_creds = read_ss_creds_from_store(&app);
-    crate::metadata::enrich_single_rom(rom_id, db.inner(), igdb_client.as_ref(), ss_creds.as_ref()).await?;
-
-    // Return the updated ROM data
-    fetch_rom_with_meta(db.inner(), rom_id).await
-}
-
-/// Fetch a single ROM with all metadata, cover, and screenshots.
-async fn fetch_rom_with_meta(db: &DatabaseConnection, rom_id: i64) -> AppResult<RomWithMeta> {
+) -> AppResult<Vec<crate::models::RomArtwork>> {
     use crate::entity::artwork;
     use sea_orm::{ColumnTrait, EntityTrait, QueryFilter, QueryOrder};
 
-    let q = format!(
-        "{ROM_WITH_META_SELECT} LEFT JOIN metadata m ON m.rom_id = r.id
-         LEFT JOIN artwork a ON a.rom_id = r.id AND a.art_type = 'cover'
-         LEFT JOIN hasheous_cache hc ON hc.rom_id = r.id
-         LEFT JOIN source_roms sr ON sr.rom_id = r.id
-                 LEFT JOIN sources s ON s.id = sr.source_id
-         WHERE r.id = ?
-         GROUP BY r.id",
-    );
-    let rows = query_rom_rows(db, &q, vec![rom_id.into()]).await?;
-    let row = rows.into_iter().next()
-        .ok_or_else(|| crate::error::AppError::Other(format!("ROM {rom_id} not found")))?;
-    let mut rom = row.into_rom_with_meta();
-
-    // Fetch screenshot URLs separately (multiple per ROM)
-    rom.screenshot_urls = artwork::Entity::find()
+    let models = artwork::Entity::find()
         .filter(artwork::Column::RomId.eq(rom_id))
-        .filter(artwork::Column::ArtType.eq("screenshot"))
+        .order_by_asc(artwork::Column::ArtType)
         .order_by_asc(artwork::Column::Id)
-        .all(db)
-        .await?
+        .all(db.inner())
+        .await?;
+    Ok(models
         .into_iter()
-        .filter_map(|m| m.url)
-        .collect();
-
-    Ok(rom)
-}
-
-#[tauri::command]
-pub async fn get_rom(
-    db: State<'_, DatabaseConnection>,
-    rom_id: i64,
-) -> AppResult<RomWithMeta> {
-    fetch_rom_with_meta(db.inner(), rom_id).await
+        .map(|m| crate::models::RomArtwork {
+            id: m.id,
+            art_type: m.art_type,
+            url: m.url,
+            provider: m.provider,
+            region: m.region,
+            created_at: m.created_at,
+        })
+        .collect())
 }
 
 #[tauri::command]
@@ -1783,13 +4733,27 @@ pub async fn get_rom_screenshots(
 
     let models = artwork::Entity::find()
         .filter(artwork::Column::RomId.eq(rom_id))
-        .filter(artwork::Column::ArtType.eq("screenshot"))
+        .filter(artwork::Column::ArtType.is_in([
+            "screenshot",
+            crate::user_screenshots::USER_SCREENSHOT,
+        ]))
         .order_by_asc(artwork::Column::Id)
         .all(db.inner())
         .await?;
     Ok(models.into_iter().filter_map(|m| m.url).collect())
 }
 
+/// Import screenshots the user took of this ROM in RetroArch or a
+/// standalone emulator into its gallery. Returns how many are new.
+#[tauri::command]
+pub async fn scan_user_screenshots(
+    db: State<'_, DatabaseConnection>,
+    rom_id: i64,
+) -> AppResult<usize> {
+    let rom_path = local_rom_path(db.inner(), rom_id).await?;
+    crate::user_screenshots::scan(db.inner(), rom_id, rom_path.as_deref()).await
+}
+
 #[tauri::command]
 pub async fn get_ra_credentials(
     app: tauri::AppHandle,
@@ -1802,6 +4766,7 @@ pub async fn get_ra_credentials(
         .and_then(|v| v.as_str().map(|s| s.to_string()));
     let api_key = store
         .get("retroachievements_api_key")
+        .map(crate::secrets::open_setting)
         .and_then(|v| v.as_str().map(|s| s.to_string()));
 
     match (username, api_key) {
@@ -1820,32 +4785,63 @@ pub async fn set_ra_credentials(
     app: tauri::AppHandle,
     username: String,
     api_key: String,
+    token: Option<String>,
 ) -> AppResult<()> {
     let store = app
         .store("settings.json")
         .map_err(|e| AppError::Other(e.to_string()))?;
     store.set("retroachievements_username", serde_json::json!(username));
-    store.set("retroachievements_api_key", serde_json::json!(api_key));
+    store.set("retroachievements_api_key", crate::secrets::seal_setting("retroachievements_api_key", &api_key));
+    if let Some(token) = token {
+        store.set("retroachievements_token", crate::secrets::seal_setting("retroachievements_token", &token));
+    }
+    store
+        .save()
+        .map_err(|e| AppError::Other(e.to_string()))?;
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn get_ra_hardcore_mode(app: tauri::AppHandle) -> AppResult<bool> {
+    let store = app
+        .store("settings.json")
+        .map_err(|e| AppError::Other(e.to_string()))?;
+    Ok(store
+        .get("retroachievements_hardcore")
+        .and_then(|v| v.as_bool())
+        .unwrap_or(false))
+}
+
+#[tauri::command]
+pub async fn set_ra_hardcore_mode(app: tauri::AppHandle, enabled: bool) -> AppResult<()> {
+    let store = app
+        .store("settings.json")
+        .map_err(|e| AppError::Other(e.to_string()))?;
+    store.set("retroachievements_hardcore", serde_json::json!(enabled));
     store
         .save()
         .map_err(|e| AppError::Other(e.to_string()))?;
+    crate::settings::save_to_db(&app).await?;
     Ok(())
 }
 
 #[tauri::command]
 pub async fn test_ra_connection(username: String, api_key: String) -> AppResult<RaTestResult> {
-    let client = reqwest::Client::builder()
+    let client = crate::http::client_builder()
         .timeout(std::time::Duration::from_secs(30))
         .build()
         .unwrap_or_default();
     Ok(crate::retroachievements::test_connection(&client, &username, &api_key).await)
 }
 
+/// Get achievements for a ROM, served from the local cache while it is fresh.
+/// Falls back to stale cached data when the RA API can't be reached.
 #[tauri::command]
 pub async fn get_achievements(
     app: tauri::AppHandle,
     db: State<'_, DatabaseConnection>,
     rom_id: i64,
+    force_refresh: Option<bool>,
 ) -> AppResult<AchievementData> {
     let store = app
         .store("settings.json")
@@ -1856,10 +4852,62 @@ pub async fn get_achievements(
         .ok_or_else(|| AppError::Other("RA username not configured".into()))?;
     let api_key = store
         .get("retroachievements_api_key")
+        .map(crate::secrets::open_setting)
         .and_then(|v| v.as_str().map(|s| s.to_string()))
         .ok_or_else(|| AppError::Other("RA API key not configured".into()))?;
 
-    let client = reqwest::Client::builder()
+    let cached = crate::retroachievements::get_cached(db.inner(), rom_id, &username).await;
+    if !force_refresh.unwrap_or(false) {
+        if let Some(ref c) = cached {
+            if c.fresh {
+                return Ok(c.data.clone());
+            }
+        }
+    }
+
+    match fetch_achievements_live(db.inner(), rom_id, &username, &api_key).await {
+        Ok((ra_game_id, data)) => {
+            // Unlocks since the last fetch. Without one there's nothing to
+            // compare against, and the whole history would flood the feed.
+            if let Some(previous) = &cached {
+                for achievement in data.achievements.iter().filter(|a| {
+                    a.earned
+                        && !previous
+                            .data
+                            .achievements
+                            .iter()
+                            .any(|p| p.id == a.id && p.earned)
+                }) {
+                    crate::activity::achievement_unlocked(db.inner(), rom_id, &achievement.title)
+                        .await;
+                }
+            }
+            crate::retroachievements::save_to_cache(db.inner(), rom_id, &ra_game_id, &username, &data)
+                .await;
+            Ok(data)
+        }
+        Err(e) => match cached {
+            Some(c) => {
+                log::warn!(
+                    "[RA] ROM {rom_id}: live fetch failed ({e}), serving cached data for game {}",
+                    c.ra_game_id
+                );
+                Ok(c.data)
+            }
+            None => Err(e),
+        },
+    }
+}
+
+/// Resolve the RA game ID for a ROM and fetch its achievements from the RA API.
+async fn fetch_achievements_live(
+    db: &DatabaseConnection,
+    rom_id: i64,
+    username: &str,
+    api_key: &str,
+) -> AppResult<(String, AchievementData)> {
+    crate::http::ensure_online("RetroAchievements")?;
+    let client = crate::http::client_builder()
         .timeout(std::time::Duration::from_secs(30))
         .build()
         .unwrap_or_default();
@@ -1868,7 +4916,7 @@ pub async fn get_achievements(
 
     // Try to get RA game ID from hasheous cache first
     let cached_id = {
-        let result = db.inner()
+        let result = db
             .query_one(Statement::from_sql_and_values(
                 DatabaseBackend::Sqlite,
                 "SELECT retroachievements_game_id FROM hasheous_cache WHERE rom_id = ?",
@@ -1887,79 +4935,134 @@ pub async fn get_achievements(
         struct RomHashInfo {
             slug: String,
             hash_md5: Option<String>,
+            headerless_md5: Option<String>,
         }
         let rom_info = RomHashInfo::find_by_statement(Statement::from_sql_and_values(
             DatabaseBackend::Sqlite,
-            "SELECT p.slug, r.hash_md5 FROM roms r JOIN platforms p ON p.id = r.platform_id WHERE r.id = ?",
+            "SELECT p.slug, r.hash_md5, r.headerless_md5 FROM roms r JOIN platforms p ON p.id = r.platform_id WHERE r.id = ?",
             [rom_id.into()],
         ))
-        .one(db.inner())
+        .one(db)
         .await?
-        .ok_or_else(|| AppError::Other(format!("ROM {rom_id} not found")))?;
+        .ok_or_else(|| AppError::NotFound(format!("ROM {rom_id}")))?;
 
         let (platform_slug, md5) = (rom_info.slug, rom_info.hash_md5);
         log::info!("[RA] ROM {rom_id}: platform_slug={platform_slug}, has_md5={}", md5.is_some());
 
-        // If ROM has no hash, compute it on-demand (downloads remote ROMs temporarily)
-        let md5 = match md5 {
-            Some(h) if !h.is_empty() => h,
-            _ => {
-                log::info!("[RA] ROM {rom_id}: computing hash on-demand...");
-                compute_rom_hash_inner(db.inner(), rom_id)
-                    .await?
-                    .ok_or_else(|| {
-                        AppError::Other(
-                            "No RetroAchievements game found for this ROM".into(),
-                        )
-                    })?
+        // RA hashes headered cartridges without their header, which DAT
+        // verification or enrichment may already have stored
+        let headerless_md5 = rom_info
+            .headerless_md5
+            .filter(|_| crate::hash::may_have_header(&platform_slug));
+
+        // Consoles with RA-specific hashing (headers, byte order, disc executables)
+        let ra_hash_id = if let Some(h) = headerless_md5 {
+            log::info!("[RA] ROM {rom_id}: headerless md5={h}, looking up...");
+            crate::retroachievements::find_game_id_by_hash(
+                &client,
+                username,
+                api_key,
+                &platform_slug,
+                &h,
+            )
+            .await
+        } else if crate::hash::has_custom_ra_hash(&platform_slug) {
+            match local_rom_path(db, rom_id).await? {
+                Some(path) => {
+                    let slug = platform_slug.clone();
+                    let ra_hash = tokio::task::spawn_blocking(move || {
+                        crate::hash::compute_ra_hash(&path, &slug)
+                    })
+                    .await
+                    .map_err(|e| AppError::Other(e.to_string()))?;
+                    match ra_hash {
+                        Ok(h) => {
+                            log::info!("[RA] ROM {rom_id}: RA hash={h}, looking up...");
+                            crate::retroachievements::find_game_id_by_hash(
+                                &client,
+                                username,
+                                api_key,
+                                &platform_slug,
+                                &h,
+                            )
+                            .await
+                        }
+                        Err(e) => {
+                            log::warn!("[RA] ROM {rom_id}: RA hash failed: {e}");
+                            None
+                        }
+                    }
+                }
+                None => None,
             }
+        } else {
+            None
         };
 
-        log::info!("[RA] ROM {rom_id}: md5={md5}, looking up RA game by hash for platform {platform_slug}...");
+        let found_id = if let Some(id) = ra_hash_id {
+            id
+        } else {
+            // If ROM has no hash, compute it on-demand (downloads remote ROMs temporarily)
+            let md5 = match md5 {
+                Some(h) if !h.is_empty() => h,
+                _ => {
+                    log::info!("[RA] ROM {rom_id}: computing hash on-demand...");
+                    compute_rom_hash_inner(db, rom_id)
+                        .await?
+                        .ok_or_else(|| {
+                            AppError::Other(
+                                "No RetroAchievements game found for this ROM".into(),
+                            )
+                        })?
+                }
+            };
 
-        let mut found_id = crate::retroachievements::find_game_id_by_hash(
-            &client,
-            &username,
-            &api_key,
-            &platform_slug,
-            &md5,
-        )
-        .await;
+            log::info!("[RA] ROM {rom_id}: md5={md5}, looking up RA game by hash for platform {platform_slug}...");
 
-        // If lookup failed, the stored hash might be from a zip file (pre-fix).
-        // Clear it and recompute with zip-aware logic.
-        if found_id.is_none() {
-            log::info!("[RA] ROM {rom_id}: hash {md5} not found in RA, clearing and recomputing...");
-            let _ = db.inner()
-                .execute(Statement::from_sql_and_values(
-                    DatabaseBackend::Sqlite,
-                    "UPDATE roms SET hash_md5 = NULL WHERE id = ?",
-                    [rom_id.into()],
-                ))
-                .await;
-            if let Ok(Some(new_md5)) = compute_rom_hash_inner(db.inner(), rom_id).await {
-                if new_md5 != md5 {
-                    log::info!("[RA] ROM {rom_id}: recomputed hash={new_md5} (was {md5}), retrying RA lookup...");
-                    found_id = crate::retroachievements::find_game_id_by_hash(
-                        &client,
-                        &username,
-                        &api_key,
-                        &platform_slug,
-                        &new_md5,
-                    )
+            let mut found_id = crate::retroachievements::find_game_id_by_hash(
+                &client,
+                username,
+                api_key,
+                &platform_slug,
+                &md5,
+            )
+            .await;
+
+            // If lookup failed, the stored hash might be from a zip file (pre-fix).
+            // Clear it and recompute with zip-aware logic.
+            if found_id.is_none() {
+                log::info!("[RA] ROM {rom_id}: hash {md5} not found in RA, clearing and recomputing...");
+                let _ = db
+                    .execute(Statement::from_sql_and_values(
+                        DatabaseBackend::Sqlite,
+                        "UPDATE roms SET hash_md5 = NULL WHERE id = ?",
+                        [rom_id.into()],
+                    ))
                     .await;
+                if let Ok(Some(new_md5)) = compute_rom_hash_inner(db, rom_id).await {
+                    if new_md5 != md5 {
+                        log::info!("[RA] ROM {rom_id}: recomputed hash={new_md5} (was {md5}), retrying RA lookup...");
+                        found_id = crate::retroachievements::find_game_id_by_hash(
+                            &client,
+                            username,
+                            api_key,
+                            &platform_slug,
+                            &new_md5,
+                        )
+                        .await;
+                    }
                 }
             }
-        }
 
-        let found_id = found_id.ok_or_else(|| {
-            AppError::Other("No RetroAchievements game found for this ROM".into())
-        })?;
+            found_id.ok_or_else(|| {
+                AppError::Other("No RetroAchievements game found for this ROM".into())
+            })?
+        };
 
         log::info!("[RA] ROM {rom_id}: found RA game ID: {found_id}");
 
         // Cache the discovered RA game ID in hasheous_cache for next time
-        let _ = db.inner()
+        let _ = db
             .execute(Statement::from_sql_and_values(
                 DatabaseBackend::Sqlite,
                 "INSERT INTO hasheous_cache (rom_id, retroachievements_game_id)
@@ -1972,8 +5075,10 @@ pub async fn get_achievements(
         found_id
     };
 
-    crate::retroachievements::fetch_game_achievements(&client, &username, &api_key, &ra_game_id)
-        .await
+    let data =
+        crate::retroachievements::fetch_game_achievements(&client, username, api_key, &ra_game_id)
+            .await?;
+    Ok((ra_game_id, data))
 }
 
 /// A source link for a ROM (returned by get_rom_sources).
@@ -2005,9 +5110,309 @@ pub async fn get_rom_sources(
     Ok(rows)
 }
 
+/// Push a ROM that only exists in a local source to a ROMM server. Multi-file
+/// sets (cue/gdi/m3u) are zipped so ROMM sees them as one game. Returns the
+/// uploaded file name; the ROM shows up in this source after ROMM's next scan
+/// and a sync.
+#[tauri::command]
+pub async fn upload_rom_to_romm(
+    db: State<'_, DatabaseConnection>,
+    rom_id: i64,
+    source_id: i64,
+    channel: Channel<DownloadProgress>,
+) -> AppResult<String> {
+    use crate::entity::sources;
+    use sea_orm::{DatabaseBackend, EntityTrait, FromQueryResult, Statement};
+
+    let source = sources::Entity::find_by_id(source_id)
+        .one(db.inner())
+        .await?
+        .ok_or_else(|| AppError::SourceNotFound(source_id.to_string()))?;
+    if source.source_type != sources::SourceType::Romm {
+        return Err(AppError::Other("Uploads need a ROMM source".to_string()));
+    }
+
+    let already_there = count_query(
+        db.inner(),
+        "SELECT COUNT(*) FROM source_roms WHERE rom_id = ? AND source_id = ?",
+        vec![rom_id.into(), source_id.into()],
+    )
+    .await?;
+    if already_there > 0 {
+        return Err(AppError::Other("ROM is already on this ROMM server".to_string()));
+    }
+
+    #[derive(Debug, FromQueryResult)]
+    struct LocalRomRow {
+        path: String,
+        slug: String,
+    }
+
+    let local = LocalRomRow::find_by_statement(Statement::from_sql_and_values(
+        DatabaseBackend::Sqlite,
+        "SELECT sr.source_rom_id AS path, p.slug
+         FROM source_roms sr
+         JOIN sources s ON s.id = sr.source_id
+         JOIN roms r ON r.id = sr.rom_id
+         JOIN platforms p ON p.id = r.platform_id
+         WHERE sr.rom_id = ? AND s.source_type = 'local' AND sr.source_rom_id IS NOT NULL
+         LIMIT 1",
+        [rom_id.into()],
+    ))
+    .one(db.inner())
+    .await?
+    .ok_or_else(|| AppError::Other("ROM has no local copy to upload".to_string()))?;
+
+    let path = std::path::PathBuf::from(&local.path);
+    if !path.is_file() {
+        return Err(AppError::Other(format!("ROM file not found: {}", local.path)));
+    }
+
+    let client = RommClient::from_source(source.url.unwrap_or_default(), &source.credentials)
+        .with_write_access();
+    let romm_platform_id = client.find_platform_id(&local.slug).await?.ok_or_else(|| {
+        AppError::Other(format!(
+            "Platform '{}' does not exist on the ROMM server",
+            local.slug
+        ))
+    })?;
+
+    let companions = local_sync::companion_files(&path);
+    let (upload_path, upload_name, temp_zip) = if companions.is_empty() {
+        let name = path.file_name().unwrap_or_default().to_string_lossy().into_owned();
+        (path.clone(), name, false)
+    } else {
+        let _ = channel.send(DownloadProgress::status(rom_id, "packing"));
+        let stem = path.file_stem().unwrap_or_default().to_string_lossy().into_owned();
+        let zip_path = std::env::temp_dir()
+            .join("romm-buddy-upload")
+            .join(format!("{stem}.zip"));
+        let mut files = vec![path.clone()];
+        files.extend(companions);
+        let dest = zip_path.clone();
+        tokio::task::spawn_blocking(move || zip_files(&files, &dest))
+            .await
+            .map_err(|e| AppError::Other(format!("Task join error: {e}")))??;
+        (zip_path, format!("{stem}.zip"), true)
+    };
+
+    let total = std::fs::metadata(&upload_path)?.len();
+    let _ = channel.send(DownloadProgress::uploading(rom_id, 0, total));
+    let progress = channel.clone();
+    let result = client
+        .upload_rom(romm_platform_id, &upload_path, &upload_name, move |sent| {
+            let _ = progress.send(DownloadProgress::uploading(rom_id, sent, total));
+        })
+        .await;
+
+    if temp_zip {
+        let _ = std::fs::remove_file(&upload_path);
+    }
+    result?;
+
+    let _ = channel.send(DownloadProgress::status(rom_id, "done"));
+    Ok(upload_name)
+}
+
+#[derive(Debug, sea_orm::FromQueryResult)]
+struct RomFileSource {
+    file_name: String,
+    file_size: Option<i64>,
+    source_rom_id: String,
+    source_type: crate::entity::sources::SourceType,
+    url: Option<String>,
+    credentials: String,
+}
+
+/// Where a ROM's file can be fetched from: `source_id` if given, otherwise
+/// local sources before remote ones.
+async fn rom_file_source(
+    db: &DatabaseConnection,
+    rom_id: i64,
+    source_id: Option<i64>,
+) -> AppResult<RomFileSource> {
+    use sea_orm::{DatabaseBackend, FromQueryResult, Statement};
+
+    RomFileSource::find_by_statement(Statement::from_sql_and_values(
+        DatabaseBackend::Sqlite,
+        "SELECT r.file_name, r.file_size, sr.source_rom_id, s.source_type, s.url, s.credentials
+         FROM roms r
+         JOIN source_roms sr ON sr.rom_id = r.id
+         JOIN sources s ON s.id = sr.source_id
+         WHERE r.id = ? AND sr.source_rom_id IS NOT NULL AND (? IS NULL OR s.id = ?)
+         ORDER BY s.source_type = 'local' DESC
+         LIMIT 1",
+        [rom_id.into(), source_id.into(), source_id.into()],
+    ))
+    .one(db)
+    .await?
+    .ok_or_else(|| AppError::Other("ROM not found in any source".to_string()))
+}
+
+/// URL for pulling a ROM with another tool. ROMM URLs need the returned
+/// `Authorization` header, which is only valid until the access token expires.
+#[tauri::command]
+pub async fn get_rom_download_url(
+    db: State<'_, DatabaseConnection>,
+    rom_id: i64,
+    source_id: Option<i64>,
+) -> AppResult<RomDownloadUrl> {
+    let rom = rom_file_source(db.inner(), rom_id, source_id).await?;
+
+    if rom.source_type == crate::entity::sources::SourceType::Local {
+        let url = reqwest::Url::from_file_path(&rom.source_rom_id)
+            .map_err(|()| AppError::Other(format!("Not an absolute path: {}", rom.source_rom_id)))?
+            .to_string();
+        return Ok(RomDownloadUrl {
+            curl_command: format!("curl -o '{}' '{url}'", rom.file_name.replace('\'', "'\\''")),
+            url,
+            file_name: rom.file_name,
+            authorization: None,
+        });
+    }
+
+    let romm_id: i64 = rom
+        .source_rom_id
+        .parse()
+        .map_err(|_| AppError::Other("Invalid source ROM ID".to_string()))?;
+    let client = RommClient::from_source(rom.url.unwrap_or_default(), &rom.credentials);
+    let (url, token) = client.content_url(romm_id, &rom.file_name).await?;
+    let authorization = format!("Bearer {token}");
+    Ok(RomDownloadUrl {
+        curl_command: format!(
+            "curl -L -H 'Authorization: {authorization}' -o '{}' '{url}'",
+            rom.file_name.replace('\'', "'\\''"),
+        ),
+        url,
+        file_name: rom.file_name,
+        authorization: Some(authorization),
+    })
+}
+
+/// Copy or download a ROM to `dest_path` (a folder, or a full file path)
+/// without launching it. Returns the path written.
+#[tauri::command]
+pub async fn download_rom_to(
+    app: tauri::AppHandle,
+    db: State<'_, DatabaseConnection>,
+    jobs: State<'_, JobManager>,
+    rom_id: i64,
+    source_id: Option<i64>,
+    dest_path: String,
+    channel: Channel<DownloadProgress>,
+) -> AppResult<String> {
+    let rom = rom_file_source(db.inner(), rom_id, source_id).await?;
+
+    let dest = std::path::PathBuf::from(&dest_path);
+    let dest = if dest.is_dir() { dest.join(&rom.file_name) } else { dest };
+
+    let cached = crate::rom_cache::cached_rom_path(db.inner(), rom_id).await?;
+    if rom.source_type == crate::entity::sources::SourceType::Local || cached.is_some() {
+        let from = match cached {
+            Some(path) if rom.source_type != crate::entity::sources::SourceType::Local => path,
+            _ => std::path::PathBuf::from(&rom.source_rom_id),
+        };
+        if !from.is_file() {
+            return Err(AppError::Other(format!("ROM file not found: {}", from.display())));
+        }
+        let _ = channel.send(DownloadProgress::status(rom_id, "downloading"));
+        crate::export::copy_local(&from, &dest).await?;
+    } else {
+        let romm_id: i64 = rom
+            .source_rom_id
+            .parse()
+            .map_err(|_| AppError::Other("Invalid source ROM ID".to_string()))?;
+        let client = RommClient::from_source(rom.url.unwrap_or_default(), &rom.credentials);
+        let fallback_total = rom
+            .file_size
+            .and_then(|s| u64::try_from(s).ok())
+            .unwrap_or(0);
+
+        if let Some(parent) = dest.parent() {
+            tokio::fs::create_dir_all(parent).await?;
+        }
+        let part = dest.with_file_name(format!(
+            ".{}.part",
+            dest.file_name().unwrap_or_default().to_string_lossy()
+        ));
+        let job = jobs.start(
+            JobKind::Download,
+            Some(rom_id),
+            format!("Downloading {}", rom.file_name),
+        );
+        let cancel = job.token();
+        let result = crate::downloader::download_rom(
+            &client,
+            romm_id,
+            &rom.file_name,
+            &part,
+            read_download_segments_from_store(&app),
+            &cancel,
+            |downloaded, total| {
+                let total = if total > 0 { total } else { fallback_total };
+                let _ = channel.send(DownloadProgress::downloading(rom_id, downloaded, total));
+                job.progress(downloaded, total, rom.file_name.as_str());
+            },
+        )
+        .await;
+        job.finish(&result);
+        if let Err(e) = result {
+            let _ = tokio::fs::remove_file(&part).await;
+            return Err(e);
+        }
+        tokio::fs::rename(&part, &dest).await?;
+    }
+
+    let _ = channel.send(DownloadProgress::status(rom_id, "done"));
+    Ok(dest.to_string_lossy().into_owned())
+}
+
+/// Store `files` flat (by file name, uncompressed) in a zip at `dest`.
+fn zip_files(files: &[std::path::PathBuf], dest: &std::path::Path) -> AppResult<()> {
+    if let Some(parent) = dest.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    let zip_err = |e: zip::result::ZipError| AppError::Other(format!("Failed to zip ROM set: {e}"));
+    let mut writer = zip::ZipWriter::new(std::fs::File::create(dest)?);
+    let options = zip::write::SimpleFileOptions::default()
+        .compression_method(zip::CompressionMethod::Stored)
+        .large_file(true);
+    for file in files {
+        let name = file.file_name().unwrap_or_default().to_string_lossy();
+        writer.start_file(name, options).map_err(zip_err)?;
+        std::io::copy(&mut std::fs::File::open(file)?, &mut writer)?;
+    }
+    writer.finish().map_err(zip_err)?;
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn deduplicate_roms(
+    app: tauri::AppHandle,
+    db: State<'_, DatabaseConnection>,
+    jobs: State<'_, JobManager>,
+) -> AppResult<u64> {
+    let job = jobs.start(JobKind::Dedup, None, "Merging duplicate ROMs");
+    let result =
+        crate::dedup::reconcile_duplicates(db.inner(), &job.token(), &LibraryEvents::new(&app))
+            .await;
+    job.finish(&result);
+    if let Ok(merged_duplicates) = result {
+        if merged_duplicates > 0 {
+            use tauri::Emitter;
+            let _ = app.emit(
+                LIBRARY_CHANGED_EVENT,
+                LibraryChanged { source_id: None, merged_duplicates },
+            );
+        }
+    }
+    result
+}
+
 #[tauri::command]
-pub async fn deduplicate_roms(db: State<'_, DatabaseConnection>) -> AppResult<u64> {
-    crate::dedup::reconcile_duplicates(db.inner()).await
+pub async fn cancel_dedup(jobs: State<'_, JobManager>) -> AppResult<()> {
+    jobs.cancel_kind(JobKind::Dedup, None);
+    Ok(())
 }
 
 // ---------- DAT verification commands ----------
@@ -2015,20 +5420,68 @@ pub async fn deduplicate_roms(db: State<'_, DatabaseConnection>) -> AppResult<u6
 #[tauri::command]
 pub async fn import_dat_file(
     db: State<'_, DatabaseConnection>,
+    jobs: State<'_, JobManager>,
     file_path: String,
     dat_type: String,
     platform_slug: String,
     channel: Channel<ScanProgress>,
 ) -> AppResult<i64> {
+    let job = jobs.start(JobKind::DatImport, None, "Importing a DAT file");
     let path = std::path::PathBuf::from(file_path);
-    crate::metadata::dat::import_dat_file(
+    let result = crate::metadata::dat::import_dat_file(
         db.inner(),
         &path,
         &dat_type,
         &platform_slug,
-        move |p| { let _ = channel.send(p); },
+        job.reporter(move |p| { let _ = channel.send(p); }),
+        job.token(),
+    )
+    .await;
+    job.finish(&result);
+    result
+}
+
+/// Download and import the latest DATs for `platform_slugs` from the
+/// configured mirror (libretro-database by default). Cancelled like an import.
+#[tauri::command]
+pub async fn fetch_latest_dats(
+    app: tauri::AppHandle,
+    db: State<'_, DatabaseConnection>,
+    jobs: State<'_, JobManager>,
+    platform_slugs: Vec<String>,
+    channel: Channel<ScanProgress>,
+) -> AppResult<Vec<crate::metadata::dat::DatFetchResult>> {
+    let mirror = app
+        .store("settings.json")
+        .ok()
+        .and_then(|store| store.get("dat_mirror_url"))
+        .and_then(|v| v.as_str().map(str::to_string))
+        .filter(|url| !url.trim().is_empty())
+        .unwrap_or_else(|| crate::metadata::dat::DEFAULT_DAT_MIRROR.to_string());
+    let client = crate::http::client_builder()
+        .user_agent("romm-buddy/0.1")
+        .timeout(std::time::Duration::from_secs(120))
+        .build()
+        .unwrap_or_default();
+
+    let job = jobs.start(JobKind::DatImport, None, "Downloading the latest DAT files");
+    let result = crate::metadata::dat::fetch_latest_dats(
+        db.inner(),
+        &client,
+        &mirror,
+        &platform_slugs,
+        job.reporter(move |p| { let _ = channel.send(p); }),
+        job.token(),
     )
-    .await
+    .await;
+    job.finish(&result);
+    result
+}
+
+#[tauri::command]
+pub async fn cancel_dat_import(jobs: State<'_, JobManager>) -> AppResult<()> {
+    jobs.cancel_kind(JobKind::DatImport, None);
+    Ok(())
 }
 
 #[tauri::command]
@@ -2098,37 +5551,34 @@ pub async fn detect_dat_platform(file_path: String) -> AppResult<DatDetectResult
 #[tauri::command]
 pub async fn verify_library(
     db: State<'_, DatabaseConnection>,
-    cancel_map: State<'_, CancelTokenMap>,
+    jobs: State<'_, JobManager>,
     platform_id: Option<i64>,
     channel: Channel<ScanProgress>,
 ) -> AppResult<crate::metadata::dat::VerificationStats> {
-    let cancel = CancellationToken::new();
-    {
-        let mut map = cancel_map.0.lock().await;
-        map.insert(CancelKey::Verification, cancel.clone());
-    }
+    let job = jobs.start(JobKind::Verification, None, "Verifying ROMs");
     let result = crate::metadata::dat::verify_roms(
         db.inner(),
         platform_id,
-        move |p| { let _ = channel.send(p); },
-        cancel,
+        job.reporter(move |p| { let _ = channel.send(p); }),
+        job.token(),
     )
     .await;
-    {
-        let mut map = cancel_map.0.lock().await;
-        map.remove(&CancelKey::Verification);
-    }
+    job.finish(&result);
     result
 }
 
+/// Clear verification results so the next run starts from scratch.
 #[tauri::command]
-pub async fn cancel_verification(
-    cancel_map: State<'_, CancelTokenMap>,
-) -> AppResult<()> {
-    let map = cancel_map.0.lock().await;
-    if let Some(token) = map.get(&CancelKey::Verification) {
-        token.cancel();
-    }
+pub async fn reset_verification(
+    db: State<'_, DatabaseConnection>,
+    platform_id: Option<i64>,
+) -> AppResult<u64> {
+    crate::metadata::dat::reset_verification(db.inner(), platform_id).await
+}
+
+#[tauri::command]
+pub async fn cancel_verification(jobs: State<'_, JobManager>) -> AppResult<()> {
+    jobs.cancel_kind(JobKind::Verification, None);
     Ok(())
 }
 
@@ -2140,144 +5590,515 @@ pub async fn get_verification_stats(
     crate::metadata::dat::get_verification_stats(db.inner(), platform_id).await
 }
 
+/// Write the verification results of a platform (or every platform) to
+/// `path` as CSV or a standalone HTML page. Returns the number of ROMs written.
+#[tauri::command]
+pub async fn export_verification_report(
+    db: State<'_, DatabaseConnection>,
+    platform_id: Option<i64>,
+    format: crate::metadata::dat_report::ReportFormat,
+    path: String,
+) -> AppResult<usize> {
+    let (report, count) =
+        crate::metadata::dat_report::verification_report(db.inner(), platform_id, format).await?;
+    tokio::fs::write(&path, report).await?;
+    Ok(count)
+}
+
+/// Write `have.txt` and `miss.txt` for a DAT file into the folder `dir`.
+#[tauri::command]
+pub async fn export_have_miss(
+    db: State<'_, DatabaseConnection>,
+    dat_file_id: i64,
+    dir: String,
+) -> AppResult<crate::metadata::dat_report::HaveMissReport> {
+    crate::metadata::dat_report::export_have_miss(db.inner(), dat_file_id, std::path::Path::new(&dir))
+        .await
+}
+
+/// Missing and extra files found when an arcade ROM was last checked against its DAT set.
+#[tauri::command]
+pub async fn get_arcade_set_report(
+    db: State<'_, DatabaseConnection>,
+    rom_id: i64,
+) -> AppResult<Option<crate::metadata::dat::ArcadeSetReport>> {
+    crate::metadata::dat::get_arcade_set_report(db.inner(), rom_id).await
+}
+
+// ---------- Cheat commands ----------
+
+/// Download the libretro cheat file for a ROM. Returns None when libretro
+/// has no cheats for the game.
+#[tauri::command]
+pub async fn fetch_cheats(
+    db: State<'_, DatabaseConnection>,
+    rom_id: i64,
+) -> AppResult<Option<Vec<Cheat>>> {
+    let client = crate::http::client_builder()
+        .user_agent("romm-buddy/0.1")
+        .timeout(std::time::Duration::from_secs(30))
+        .build()
+        .unwrap_or_default();
+    crate::cheats::fetch(db.inner(), &client, rom_id).await
+}
+
+/// The downloaded cheats of a ROM and which are turned on.
+#[tauri::command]
+pub async fn get_cheats(db: State<'_, DatabaseConnection>, rom_id: i64) -> AppResult<Vec<Cheat>> {
+    crate::cheats::get(db.inner(), rom_id).await
+}
+
+/// Turn on exactly the given cheats; they're applied on the next RetroArch launch.
+#[tauri::command]
+pub async fn set_enabled_cheats(
+    db: State<'_, DatabaseConnection>,
+    rom_id: i64,
+    indexes: Vec<u32>,
+) -> AppResult<Vec<Cheat>> {
+    crate::cheats::set_enabled(db.inner(), rom_id, &indexes).await
+}
+
+// ---------- Manual commands ----------
+
+/// Attach a PDF manual to a ROM from a local file or a URL. Returns the
+/// path of the cached copy.
+#[tauri::command]
+pub async fn attach_manual(
+    db: State<'_, DatabaseConnection>,
+    rom_id: i64,
+    path_or_url: String,
+) -> AppResult<String> {
+    let client = crate::http::client_builder()
+        .user_agent("romm-buddy/0.1")
+        .timeout(std::time::Duration::from_secs(120))
+        .build()
+        .unwrap_or_default();
+    crate::manuals::attach(db.inner(), &client, rom_id, &path_or_url).await
+}
+
+/// Path of the ROM's cached manual, for the viewer.
+#[tauri::command]
+pub async fn get_manual(
+    db: State<'_, DatabaseConnection>,
+    rom_id: i64,
+) -> AppResult<Option<String>> {
+    crate::manuals::get(db.inner(), rom_id).await
+}
+
+// ---------- RetroArch game overrides ----------
+
+/// The overrides last set for a ROM with `set_retroarch_game_overrides`.
+#[tauri::command]
+pub async fn get_retroarch_game_overrides(
+    db: State<'_, DatabaseConnection>,
+    rom_id: i64,
+) -> AppResult<RetroArchGameOverrides> {
+    crate::retroarch_overrides::get(db.inner(), rom_id).await
+}
+
+/// Write per-game RetroArch overrides (shader preset, aspect ratio,
+/// run-ahead, core options, remaps) for the core the ROM's platform uses.
+#[tauri::command]
+pub async fn set_retroarch_game_overrides(
+    db: State<'_, DatabaseConnection>,
+    rom_id: i64,
+    options: RetroArchGameOverrides,
+) -> AppResult<()> {
+    use sea_orm::{DatabaseBackend, FromQueryResult, Statement};
+
+    #[derive(Debug, FromQueryResult)]
+    struct OverrideTarget {
+        file_name: String,
+        core_path: Option<String>,
+        emulator_type: Option<String>,
+    }
+
+    let target = OverrideTarget::find_by_statement(Statement::from_sql_and_values(
+        DatabaseBackend::Sqlite,
+        "SELECT r.file_name, cm.core_path, cm.emulator_type
+         FROM roms r
+         LEFT JOIN core_mappings cm ON cm.platform_id = r.platform_id
+         WHERE r.id = ?
+         ORDER BY cm.is_default DESC
+         LIMIT 1",
+        [rom_id.into()],
+    ))
+    .one(db.inner())
+    .await?
+    .ok_or_else(|| AppError::NotFound(format!("ROM {rom_id}")))?;
+
+    let core_name = target
+        .core_path
+        .filter(|_| target.emulator_type.as_deref().unwrap_or("retroarch") == "retroarch")
+        .and_then(|core_path| retroarch_core_name(&core_path))
+        .ok_or_else(|| {
+            AppError::Other("No RetroArch core is set up for this platform".to_string())
+        })?;
+    // RetroArch names per-game files after the loaded file, without extension
+    let content_name = std::path::Path::new(&target.file_name)
+        .file_stem()
+        .map(|s| s.to_string_lossy().into_owned())
+        .ok_or_else(|| AppError::Other("ROM has no file name".to_string()))?;
+
+    crate::retroarch_overrides::apply(db.inner(), rom_id, &core_name, &content_name, &options)
+        .await
+}
+
 // ---------- IGDB credential commands ----------
 
 /// Helper to read IGDB credentials from the store and construct an IgdbClient if available.
-fn read_igdb_client_from_store(
-    app: &tauri::AppHandle,
+pub(crate) fn read_igdb_client_from_store(
+    app: &impl SettingsRead,
 ) -> Option<crate::metadata::igdb::IgdbClient> {
-    let store = app.store("settings.json").ok()?;
+    let client_id = app
+        .setting("igdb_client_id")
+        .and_then(|v| v.as_str().map(|s| s.to_string()))?;
+    let client_secret = app
+        .setting("igdb_client_secret")
+        .and_then(|v| v.as_str().map(|s| s.to_string()))?;
+
+    if client_id.is_empty() || client_secret.is_empty() {
+        return None;
+    }
+
+    Some(crate::metadata::igdb::IgdbClient::new(
+        client_id,
+        client_secret,
+    ))
+}
+
+#[tauri::command]
+pub async fn get_igdb_credentials(
+    app: tauri::AppHandle,
+) -> AppResult<Option<crate::models::IgdbCredentials>> {
+    let store = app
+        .store("settings.json")
+        .map_err(|e| AppError::Other(e.to_string()))?;
     let client_id = store
         .get("igdb_client_id")
-        .and_then(|v| v.as_str().map(|s| s.to_string()))?;
+        .and_then(|v| v.as_str().map(|s| s.to_string()));
     let client_secret = store
         .get("igdb_client_secret")
+        .map(crate::secrets::open_setting)
+        .and_then(|v| v.as_str().map(|s| s.to_string()));
+
+    match (client_id, client_secret) {
+        (Some(id), Some(secret)) if !id.is_empty() && !secret.is_empty() => {
+            Ok(Some(crate::models::IgdbCredentials {
+                client_id: id,
+                client_secret: secret,
+            }))
+        }
+        _ => Ok(None),
+    }
+}
+
+#[tauri::command]
+pub async fn set_igdb_credentials(
+    app: tauri::AppHandle,
+    client_id: String,
+    client_secret: String,
+) -> AppResult<()> {
+    let store = app
+        .store("settings.json")
+        .map_err(|e| AppError::Other(e.to_string()))?;
+    store.set("igdb_client_id", serde_json::json!(client_id));
+    store.set("igdb_client_secret", crate::secrets::seal_setting("igdb_client_secret", &client_secret));
+    store
+        .save()
+        .map_err(|e| AppError::Other(e.to_string()))?;
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn test_igdb_connection(
+    client_id: String,
+    client_secret: String,
+) -> AppResult<IgdbTestResult> {
+    let client = crate::metadata::igdb::IgdbClient::new(client_id, client_secret);
+    client.test_connection().await
+}
+
+// ---------- ScreenScraper credential commands ----------
+
+/// Helper to read ScreenScraper user credentials from the store.
+pub(crate) fn read_ss_creds_from_store(
+    app: &impl SettingsRead,
+) -> Option<crate::metadata::screenscraper::SsUserCredentials> {
+    let username = app
+        .setting("screenscraper_username")
+        .and_then(|v| v.as_str().map(|s| s.to_string()))?;
+    let password = app
+        .setting("screenscraper_password")
         .and_then(|v| v.as_str().map(|s| s.to_string()))?;
 
-    if client_id.is_empty() || client_secret.is_empty() {
+    if username.is_empty() || password.is_empty() {
         return None;
     }
 
-    Some(crate::metadata::igdb::IgdbClient::new(
-        client_id,
-        client_secret,
-    ))
+    Some(crate::metadata::screenscraper::SsUserCredentials {
+        username,
+        password,
+    })
+}
+
+#[tauri::command]
+pub async fn get_ss_credentials(
+    app: tauri::AppHandle,
+) -> AppResult<Option<crate::models::SsCredentials>> {
+    let store = app
+        .store("settings.json")
+        .map_err(|e| AppError::Other(e.to_string()))?;
+    let username = store
+        .get("screenscraper_username")
+        .and_then(|v| v.as_str().map(|s| s.to_string()));
+    let password = store
+        .get("screenscraper_password")
+        .map(crate::secrets::open_setting)
+        .and_then(|v| v.as_str().map(|s| s.to_string()));
+
+    match (username, password) {
+        (Some(u), Some(p)) if !u.is_empty() && !p.is_empty() => {
+            Ok(Some(crate::models::SsCredentials {
+                username: u,
+                password: p,
+            }))
+        }
+        _ => Ok(None),
+    }
+}
+
+#[tauri::command]
+pub async fn set_ss_credentials(
+    app: tauri::AppHandle,
+    username: String,
+    password: String,
+) -> AppResult<()> {
+    let store = app
+        .store("settings.json")
+        .map_err(|e| AppError::Other(e.to_string()))?;
+    store.set("screenscraper_username", serde_json::json!(username));
+    store.set("screenscraper_password", crate::secrets::seal_setting("screenscraper_password", &password));
+    store
+        .save()
+        .map_err(|e| AppError::Other(e.to_string()))?;
+    Ok(())
+}
+
+/// Read ScreenScraper region/language preferences, falling back to defaults.
+pub(crate) fn read_ss_prefs_from_store(app: &impl SettingsRead) -> SsMediaPreferences {
+    let defaults = SsMediaPreferences::default();
+    let read_list = |key: &str| {
+        app.setting(key)
+            .and_then(|v| serde_json::from_value::<Vec<String>>(v).ok())
+            .filter(|v| !v.is_empty())
+    };
+    SsMediaPreferences {
+        regions: read_list("screenscraper_regions").unwrap_or(defaults.regions),
+        languages: read_list("screenscraper_languages").unwrap_or(defaults.languages),
+    }
+}
+
+#[tauri::command]
+pub async fn get_ss_media_preferences(app: tauri::AppHandle) -> AppResult<SsMediaPreferences> {
+    Ok(read_ss_prefs_from_store(&app))
+}
+
+/// Save region/language preferences and re-select cached ScreenScraper artwork.
+/// Returns the number of ROMs whose artwork was re-selected.
+#[tauri::command]
+pub async fn set_ss_media_preferences(
+    app: tauri::AppHandle,
+    db: State<'_, DatabaseConnection>,
+    jobs: State<'_, JobManager>,
+    preferences: SsMediaPreferences,
+) -> AppResult<u64> {
+    let store = app
+        .store("settings.json")
+        .map_err(|e| AppError::Other(e.to_string()))?;
+    store.set("screenscraper_regions", serde_json::json!(preferences.regions));
+    store.set("screenscraper_languages", serde_json::json!(preferences.languages));
+    store
+        .save()
+        .map_err(|e| AppError::Other(e.to_string()))?;
+    crate::settings::save_to_db(&app).await?;
+
+    let prefs = read_ss_prefs_from_store(&app);
+    let job = jobs.start(JobKind::ArtworkReselect, None, "Re-selecting ScreenScraper artwork");
+    let result = crate::metadata::reselect_screenscraper_artwork(
+        db.inner(),
+        &prefs,
+        &job.token(),
+        &LibraryEvents::new(&app),
+    )
+    .await;
+    job.finish(&result);
+    result
+}
+
+#[tauri::command]
+pub async fn cancel_artwork_reselect(jobs: State<'_, JobManager>) -> AppResult<()> {
+    jobs.cancel_kind(JobKind::ArtworkReselect, None);
+    Ok(())
+}
+
+pub(crate) fn read_artwork_chains_from_store(app: &impl SettingsRead) -> ArtworkChains {
+    app.setting("artwork_provider_order")
+        .and_then(|v| serde_json::from_value(v).ok())
+        .unwrap_or_default()
+}
+
+#[tauri::command]
+pub async fn get_artwork_chains(app: tauri::AppHandle) -> AppResult<ArtworkChains> {
+    Ok(read_artwork_chains_from_store(&app))
+}
+
+/// Set the provider order enrichment uses for covers and screenshots.
+#[tauri::command]
+pub async fn set_artwork_chains(app: tauri::AppHandle, chains: ArtworkChains) -> AppResult<()> {
+    for provider in chains.cover.iter().chain(&chains.screenshot) {
+        if !crate::models::ARTWORK_PROVIDERS.contains(&provider.as_str()) {
+            return Err(AppError::Other(format!("Unknown artwork provider: {provider}")));
+        }
+    }
+    let store = app
+        .store("settings.json")
+        .map_err(|e| AppError::Other(e.to_string()))?;
+    store.set("artwork_provider_order", serde_json::json!(chains));
+    store
+        .save()
+        .map_err(|e| AppError::Other(e.to_string()))?;
+    crate::settings::save_to_db(&app).await?;
+    Ok(())
+}
+
+pub(crate) fn read_enrichment_options_from_store(app: &impl SettingsRead) -> EnrichmentOptions {
+    app.setting("enrichment_defaults")
+        .and_then(|v| serde_json::from_value(v).ok())
+        .unwrap_or_default()
 }
 
 #[tauri::command]
-pub async fn get_igdb_credentials(
+pub async fn get_enrichment_options(app: tauri::AppHandle) -> AppResult<EnrichmentOptions> {
+    Ok(read_enrichment_options_from_store(&app))
+}
+
+/// Set the enrichment steps `fetch_metadata` runs when the caller passes none.
+#[tauri::command]
+pub async fn set_enrichment_options(
     app: tauri::AppHandle,
-) -> AppResult<Option<crate::models::IgdbCredentials>> {
+    options: EnrichmentOptions,
+) -> AppResult<()> {
     let store = app
         .store("settings.json")
         .map_err(|e| AppError::Other(e.to_string()))?;
-    let client_id = store
-        .get("igdb_client_id")
-        .and_then(|v| v.as_str().map(|s| s.to_string()));
-    let client_secret = store
-        .get("igdb_client_secret")
+    store.set("enrichment_defaults", serde_json::json!(options));
+    store
+        .save()
+        .map_err(|e| AppError::Other(e.to_string()))?;
+    crate::settings::save_to_db(&app).await?;
+    Ok(())
+}
+
+pub(crate) fn read_translation_settings_from_store(app: &impl SettingsRead) -> TranslationSettings {
+    app.setting("translation_service")
+        .and_then(|v| serde_json::from_value(v).ok())
+        .unwrap_or_default()
+}
+
+/// The configured translation service, or None when translation is off.
+pub(crate) fn read_translator_from_store(
+    app: &impl SettingsRead,
+) -> Option<crate::metadata::translate::Translator> {
+    let ui_language = app
+        .setting("language")
         .and_then(|v| v.as_str().map(|s| s.to_string()));
+    crate::metadata::translate::Translator::new(
+        read_translation_settings_from_store(app),
+        ui_language.as_deref(),
+    )
+}
 
-    match (client_id, client_secret) {
-        (Some(id), Some(secret)) if !id.is_empty() && !secret.is_empty() => {
-            Ok(Some(crate::models::IgdbCredentials {
-                client_id: id,
-                client_secret: secret,
-            }))
-        }
-        _ => Ok(None),
-    }
+#[tauri::command]
+pub async fn get_translation_settings(app: tauri::AppHandle) -> AppResult<TranslationSettings> {
+    Ok(read_translation_settings_from_store(&app))
 }
 
+/// Configure the DeepL/LibreTranslate service used to translate descriptions.
 #[tauri::command]
-pub async fn set_igdb_credentials(
+pub async fn set_translation_settings(
     app: tauri::AppHandle,
-    client_id: String,
-    client_secret: String,
+    settings: TranslationSettings,
 ) -> AppResult<()> {
     let store = app
         .store("settings.json")
         .map_err(|e| AppError::Other(e.to_string()))?;
-    store.set("igdb_client_id", serde_json::json!(client_id));
-    store.set("igdb_client_secret", serde_json::json!(client_secret));
+    store.set(
+        "translation_service",
+        crate::secrets::seal_setting_object("translation_service", serde_json::json!(settings)),
+    );
     store
         .save()
         .map_err(|e| AppError::Other(e.to_string()))?;
     Ok(())
 }
 
+/// The cached machine translation of a ROM's description into the target language.
 #[tauri::command]
-pub async fn test_igdb_connection(
-    client_id: String,
-    client_secret: String,
-) -> AppResult<IgdbTestResult> {
-    let client = crate::metadata::igdb::IgdbClient::new(client_id, client_secret);
-    client.test_connection().await
+pub async fn get_rom_translation(
+    app: tauri::AppHandle,
+    db: State<'_, DatabaseConnection>,
+    rom_id: i64,
+) -> AppResult<Option<LocalizedDescription>> {
+    let Some(translator) = read_translator_from_store(&app) else {
+        return Ok(None);
+    };
+    crate::metadata::translate::cached(db.inner(), rom_id, translator.target()).await
 }
 
-// ---------- ScreenScraper credential commands ----------
-
-/// Helper to read ScreenScraper user credentials from the store.
-fn read_ss_creds_from_store(
-    app: &tauri::AppHandle,
-) -> Option<crate::metadata::screenscraper::SsUserCredentials> {
-    let store = app.store("settings.json").ok()?;
-    let username = store
-        .get("screenscraper_username")
-        .and_then(|v| v.as_str().map(|s| s.to_string()))?;
-    let password = store
-        .get("screenscraper_password")
-        .and_then(|v| v.as_str().map(|s| s.to_string()))?;
-
-    if username.is_empty() || password.is_empty() {
-        return None;
-    }
+/// Translate a ROM's description now, letting the service detect its language.
+/// Returns None when it is already in the target language.
+#[tauri::command]
+pub async fn translate_rom_description(
+    app: tauri::AppHandle,
+    db: State<'_, DatabaseConnection>,
+    rom_id: i64,
+) -> AppResult<Option<LocalizedDescription>> {
+    let translator = read_translator_from_store(&app)
+        .ok_or_else(|| AppError::Other("No translation service is configured".to_string()))?;
+    let http_client = crate::http::client_builder()
+        .user_agent("romm-buddy/0.1")
+        .timeout(std::time::Duration::from_secs(30))
+        .build()
+        .unwrap_or_default();
+    translator
+        .translate_description(db.inner(), &http_client, rom_id, None)
+        .await
+}
 
-    Some(crate::metadata::screenscraper::SsUserCredentials {
-        username,
-        password,
-    })
+pub(crate) fn read_artwork_dedup_from_store(app: &impl SettingsRead) -> bool {
+    app.setting("artwork_phash_dedup")
+        .and_then(|v| v.as_bool())
+        .unwrap_or(false)
 }
 
 #[tauri::command]
-pub async fn get_ss_credentials(
-    app: tauri::AppHandle,
-) -> AppResult<Option<crate::models::SsCredentials>> {
-    let store = app
-        .store("settings.json")
-        .map_err(|e| AppError::Other(e.to_string()))?;
-    let username = store
-        .get("screenscraper_username")
-        .and_then(|v| v.as_str().map(|s| s.to_string()));
-    let password = store
-        .get("screenscraper_password")
-        .and_then(|v| v.as_str().map(|s| s.to_string()));
-
-    match (username, password) {
-        (Some(u), Some(p)) if !u.is_empty() && !p.is_empty() => {
-            Ok(Some(crate::models::SsCredentials {
-                username: u,
-                password: p,
-            }))
-        }
-        _ => Ok(None),
-    }
+pub async fn get_artwork_dedup(app: tauri::AppHandle) -> AppResult<bool> {
+    Ok(read_artwork_dedup_from_store(&app))
 }
 
+/// Toggle perceptual-hash dedup of artwork during enrichment. Off by default
+/// since it downloads every image to hash it.
 #[tauri::command]
-pub async fn set_ss_credentials(
-    app: tauri::AppHandle,
-    username: String,
-    password: String,
-) -> AppResult<()> {
+pub async fn set_artwork_dedup(app: tauri::AppHandle, enabled: bool) -> AppResult<()> {
     let store = app
         .store("settings.json")
         .map_err(|e| AppError::Other(e.to_string()))?;
-    store.set("screenscraper_username", serde_json::json!(username));
-    store.set("screenscraper_password", serde_json::json!(password));
+    store.set("artwork_phash_dedup", serde_json::json!(enabled));
     store
         .save()
         .map_err(|e| AppError::Other(e.to_string()))?;
+    crate::settings::save_to_db(&app).await?;
     Ok(())
 }
 
@@ -2286,7 +6107,7 @@ pub async fn test_ss_connection(
     username: String,
     password: String,
 ) -> AppResult<SsTestResult> {
-    let client = reqwest::Client::builder()
+    let client = crate::http::client_builder()
         .user_agent("romm-buddy/0.1")
         .timeout(std::time::Duration::from_secs(30))
         .build()
@@ -2298,6 +6119,17 @@ pub async fn test_ss_connection(
     crate::metadata::screenscraper::test_connection(&client, &creds).await
 }
 
+/// Reachability, credential checks and remaining quota of every service
+/// (each ROMM source, IGDB, ScreenScraper, RetroAchievements, Hasheous and
+/// LaunchBox images), for the indicators on the settings page.
+#[tauri::command]
+pub async fn get_provider_status(
+    app: tauri::AppHandle,
+    db: State<'_, DatabaseConnection>,
+) -> AppResult<Vec<crate::models::ProviderStatus>> {
+    crate::provider_status::check_all(&app, db.inner()).await
+}
+
 #[tauri::command]
 pub async fn get_rom_saves(
     app: tauri::AppHandle,
@@ -2319,7 +6151,7 @@ pub async fn get_rom_saves(
     ))
     .one(db.inner())
     .await?
-    .ok_or_else(|| AppError::Other(format!("ROM {rom_id} not found")))?;
+    .ok_or_else(|| AppError::NotFound(format!("ROM {rom_id}")))?;
     let (file_name, platform_id) = (rom_info.file_name, rom_info.platform_id);
 
     // Get the ROM's local file path (for "same directory as ROM" scanning)
@@ -2386,28 +6218,96 @@ pub async fn get_rom_saves(
     if let Some(ref rom_path) = rom_local_path {
         if let Some(parent) = std::path::Path::new(rom_path).parent() {
             let parent_str = parent.to_string_lossy().into_owned();
-            if !save_dirs.contains(&parent_str) {
-                save_dirs.push(parent_str.clone());
-            }
-            if !state_dirs.contains(&parent_str) {
-                state_dirs.push(parent_str);
-            }
+            saves::push_dir(&mut save_dirs, parent_str.clone());
+            saves::push_dir(&mut state_dirs, parent_str);
         }
     }
 
     // Also scan the ROM cache directory (for ROMM downloaded ROMs)
     if let Some(proj) = directories::ProjectDirs::from("com", "romm-buddy", "romm-buddy") {
         let cache_dir = proj.cache_dir().join("rom_cache").to_string_lossy().into_owned();
-        if !save_dirs.contains(&cache_dir) {
-            save_dirs.push(cache_dir.clone());
-        }
-        if !state_dirs.contains(&cache_dir) {
-            state_dirs.push(cache_dir);
-        }
+        saves::push_dir(&mut save_dirs, cache_dir.clone());
+        saves::push_dir(&mut state_dirs, cache_dir);
+    }
+
+    // Downloaded ROMs live in their own hash-keyed folder under the cache
+    let rom_path = local_rom_path(db.inner(), rom_id).await?;
+    if let Some(parent) = rom_path.as_deref().and_then(std::path::Path::parent) {
+        let parent_str = parent.to_string_lossy().into_owned();
+        saves::push_dir(&mut save_dirs, parent_str.clone());
+        saves::push_dir(&mut state_dirs, parent_str);
+    }
+
+    // 7. Scan for saves, plus states standalone emulators key by disc ID
+    let mut results = saves::scan_for_saves(&file_name, &save_dirs, &state_dirs);
+    let emulator_states =
+        saves::scan_emulator_states(&emulator_type, rom_path.as_deref(), &state_dirs);
+    if !emulator_states.is_empty() {
+        results.extend(emulator_states);
+        results.sort_by_cached_key(|r| saves::path_key(&r.file_path));
+        results.dedup_by(|a, b| saves::path_key(&a.file_path) == saves::path_key(&b.file_path));
+        results.sort_by(|a, b| b.modified_at.cmp(&a.modified_at));
+    }
+    Ok(results)
+}
+
+/// Launch a ROM from a save state picked in the save browser. RetroArch
+/// loads states by slot (`-e`); standalone emulators get the file itself
+/// through their argument template.
+#[tauri::command]
+pub async fn launch_from_save_state(
+    app: tauri::AppHandle,
+    db: State<'_, DatabaseConnection>,
+    rom_id: i64,
+    state_path: String,
+    channel: Channel<DownloadProgress>,
+) -> AppResult<()> {
+    use sea_orm::{ConnectionTrait, DatabaseBackend, Statement};
+
+    let path = std::path::Path::new(&state_path);
+    if !path.is_file() {
+        return Err(AppError::NotFound(format!("Save state {state_path}")));
+    }
+
+    let row = db
+        .inner()
+        .query_one(Statement::from_sql_and_values(
+            DatabaseBackend::Sqlite,
+            "SELECT (SELECT source_id FROM source_roms WHERE rom_id = r.id LIMIT 1) AS source_id,
+                    (SELECT emulator_type FROM core_mappings WHERE platform_id = r.platform_id
+                     ORDER BY is_default DESC LIMIT 1) AS emulator_type
+             FROM roms r WHERE r.id = ?",
+            [rom_id.into()],
+        ))
+        .await?
+        .ok_or_else(|| AppError::NotFound(format!("ROM {rom_id}")))?;
+    let source_id: Option<i64> = row.try_get("", "source_id")?;
+    let source_id =
+        source_id.ok_or_else(|| AppError::Other("ROM not found in any source".to_string()))?;
+    let emulator_type: Option<String> = row.try_get("", "emulator_type")?;
+
+    let slot = path
+        .extension()
+        .and_then(|e| e.to_str())
+        .and_then(saves::extract_slot);
+    let is_retroarch = emulator_type.as_deref().unwrap_or("retroarch") == "retroarch";
+    if is_retroarch && slot.is_none() {
+        return Err(AppError::Other(
+            "RetroArch can only load numbered save state slots".to_string(),
+        ));
     }
 
-    // 7. Scan for saves
-    Ok(saves::scan_for_saves(&file_name, &save_dirs, &state_dirs))
+    download_and_launch(
+        app,
+        db,
+        rom_id,
+        source_id,
+        channel,
+        slot,
+        Some(state_path),
+        None,
+    )
+    .await
 }
 
 #[tauri::command]
@@ -2476,11 +6376,218 @@ pub async fn set_save_path(
     Ok(())
 }
 
+// ---------- Library API server ----------
+
+pub(crate) fn read_api_server_from_store(app: &tauri::AppHandle) -> Option<ApiServerConfig> {
+    app.setting("api_server")
+        .and_then(|v| serde_json::from_value(v).ok())
+}
+
+#[tauri::command]
+pub async fn get_api_server(app: tauri::AppHandle) -> AppResult<Option<ApiServerConfig>> {
+    Ok(read_api_server_from_store(&app))
+}
+
+/// Save the API server settings and start or stop it to match. An empty
+/// token is replaced with a random one. Returns the saved settings.
+#[tauri::command]
+pub async fn configure_api_server(
+    app: tauri::AppHandle,
+    db: State<'_, DatabaseConnection>,
+    server: State<'_, crate::api_server::ApiServer>,
+    mut config: ApiServerConfig,
+) -> AppResult<ApiServerConfig> {
+    if config.token.trim().is_empty() {
+        config.token = uuid::Uuid::new_v4().simple().to_string();
+    }
+    if config.enabled {
+        crate::api_server::start(server.inner(), db.inner().clone(), &config).await?;
+    } else {
+        crate::api_server::stop(server.inner()).await;
+    }
+    let store = app
+        .store("settings.json")
+        .map_err(|e| AppError::Other(e.to_string()))?;
+    store.set(
+        "api_server",
+        crate::secrets::seal_setting_object("api_server", serde_json::json!(config)),
+    );
+    store
+        .save()
+        .map_err(|e| AppError::Other(e.to_string()))?;
+    Ok(config)
+}
+
+// ---------- Proxy ----------
+
+pub(crate) fn read_proxy_from_store(app: &impl SettingsRead) -> ProxySettings {
+    app.setting("proxy")
+        .and_then(|v| serde_json::from_value(v).ok())
+        .unwrap_or_default()
+}
+
+#[tauri::command]
+pub async fn get_proxy_settings(app: tauri::AppHandle) -> AppResult<ProxySettings> {
+    Ok(read_proxy_from_store(&app))
+}
+
+/// Save the proxy settings. Requests started after this use them.
+#[tauri::command]
+pub async fn set_proxy_settings(
+    app: tauri::AppHandle,
+    mut settings: ProxySettings,
+) -> AppResult<()> {
+    settings.url = settings.url.map(|u| u.trim().to_string()).filter(|u| !u.is_empty());
+    if let Some(url) = &settings.url {
+        crate::http::validate_proxy_url(url)?;
+    }
+    for url in settings.overrides.values_mut() {
+        *url = url.trim().to_string();
+        if !url.is_empty() {
+            crate::http::validate_proxy_url(url)?;
+        }
+    }
+    let store = app
+        .store("settings.json")
+        .map_err(|e| AppError::Other(e.to_string()))?;
+    let sealed = crate::secrets::seal_setting_object("proxy", serde_json::json!(settings));
+    crate::secrets::forget_replaced(store.get("proxy").as_ref(), &sealed);
+    store.set("proxy", sealed);
+    store
+        .save()
+        .map_err(|e| AppError::Other(e.to_string()))?;
+    crate::http::set_proxy(settings);
+    Ok(())
+}
+
+// ---------- Offline mode ----------
+
+pub(crate) fn read_offline_from_store(app: &impl SettingsRead) -> bool {
+    app.setting("offline_mode")
+        .and_then(|v| v.as_bool())
+        .unwrap_or(false)
+}
+
+#[tauri::command]
+pub async fn get_offline_mode(app: tauri::AppHandle) -> AppResult<bool> {
+    Ok(read_offline_from_store(&app))
+}
+
+/// Turn offline mode on or off. While it's on, anything that needs the
+/// network fails right away and enrichment only uses local data.
+#[tauri::command]
+pub async fn set_offline_mode(app: tauri::AppHandle, enabled: bool) -> AppResult<()> {
+    let store = app
+        .store("settings.json")
+        .map_err(|e| AppError::Other(e.to_string()))?;
+    store.set("offline_mode", serde_json::json!(enabled));
+    store
+        .save()
+        .map_err(|e| AppError::Other(e.to_string()))?;
+    crate::http::set_offline(enabled);
+    Ok(())
+}
+
+// ---------- Cloud save backup ----------
+
+fn read_cloud_backup_from_store(app: &tauri::AppHandle) -> Option<CloudBackupConfig> {
+    app.setting("cloud_backup")
+        .and_then(|v| serde_json::from_value(v).ok())
+}
+
+fn read_save_path_overrides(app: &tauri::AppHandle) -> HashMap<String, SavePathOverride> {
+    app.store("settings.json")
+        .ok()
+        .and_then(|store| store.get("save_paths"))
+        .and_then(|v| serde_json::from_value(v).ok())
+        .unwrap_or_default()
+}
+
+fn cloud_provider_from_store(
+    app: &tauri::AppHandle,
+) -> AppResult<Box<dyn saves::cloud::CloudProvider>> {
+    let config = read_cloud_backup_from_store(app)
+        .ok_or_else(|| AppError::Other("Cloud backup is not configured".to_string()))?;
+    saves::cloud::provider(&config)
+}
+
+#[tauri::command]
+pub async fn get_cloud_backup(app: tauri::AppHandle) -> AppResult<Option<CloudBackupConfig>> {
+    Ok(read_cloud_backup_from_store(&app))
+}
+
+/// Set where save backups go, after checking the credentials work.
+/// `None` turns cloud backup off.
+#[tauri::command]
+pub async fn configure_cloud_backup(
+    app: tauri::AppHandle,
+    config: Option<CloudBackupConfig>,
+) -> AppResult<()> {
+    if let Some(config) = &config {
+        saves::cloud::test(saves::cloud::provider(config)?.as_ref()).await?;
+    }
+    let store = app
+        .store("settings.json")
+        .map_err(|e| AppError::Other(e.to_string()))?;
+    match config {
+        Some(config) => store.set(
+            "cloud_backup",
+            crate::secrets::seal_setting_object("cloud_backup", serde_json::json!(config)),
+        ),
+        None => {
+            store.delete("cloud_backup");
+            crate::secrets::forget_setting_object("cloud_backup");
+        }
+    }
+    store
+        .save()
+        .map_err(|e| AppError::Other(e.to_string()))?;
+    Ok(())
+}
+
+/// Upload save files and states that changed since the last backup.
+/// Files also changed in the cloud are reported as conflicts unless
+/// `overwrite` is set.
+#[tauri::command]
+pub async fn backup_saves_to_cloud(
+    app: tauri::AppHandle,
+    db: State<'_, DatabaseConnection>,
+    overwrite: Option<bool>,
+) -> AppResult<CloudSyncReport> {
+    let provider = cloud_provider_from_store(&app)?;
+    saves::cloud::backup(
+        db.inner(),
+        provider.as_ref(),
+        &read_save_path_overrides(&app),
+        overwrite.unwrap_or(false),
+    )
+    .await
+}
+
+/// Download the cloud copies of save files and states into this device's
+/// save folders. Files also changed here are reported as conflicts unless
+/// `overwrite` is set.
+#[tauri::command]
+pub async fn restore_saves_from_cloud(
+    app: tauri::AppHandle,
+    db: State<'_, DatabaseConnection>,
+    overwrite: Option<bool>,
+) -> AppResult<CloudSyncReport> {
+    let provider = cloud_provider_from_store(&app)?;
+    saves::cloud::restore(
+        db.inner(),
+        provider.as_ref(),
+        &read_save_path_overrides(&app),
+        overwrite.unwrap_or(false),
+    )
+    .await
+}
+
 #[tauri::command]
 pub async fn delete_save_file(file_path: String) -> AppResult<()> {
     let path = std::path::PathBuf::from(&file_path);
     if !path.is_file() {
-        return Err(AppError::Other(format!("File not found: {file_path}")));
+        return Err(AppError::NotFound(format!("File {file_path}")));
     }
     tokio::fs::remove_file(&path)
         .await
@@ -2492,9 +6599,7 @@ pub async fn delete_save_file(file_path: String) -> AppResult<()> {
 pub async fn export_save_file(source_path: String, dest_path: String) -> AppResult<()> {
     let src = std::path::PathBuf::from(&source_path);
     if !src.is_file() {
-        return Err(AppError::Other(format!(
-            "Source file not found: {source_path}"
-        )));
+        return Err(AppError::NotFound(format!("Source file {source_path}")));
     }
     tokio::fs::copy(&src, &dest_path)
         .await
@@ -2510,9 +6615,7 @@ pub async fn import_save_file(
 ) -> AppResult<()> {
     let src = std::path::PathBuf::from(&source_path);
     if !src.is_file() {
-        return Err(AppError::Other(format!(
-            "Source file not found: {source_path}"
-        )));
+        return Err(AppError::NotFound(format!("Source file {source_path}")));
     }
     let dest = std::path::Path::new(&dest_dir).join(&file_name);
     tokio::fs::copy(&src, &dest)
@@ -2550,54 +6653,75 @@ pub async fn read_file_base64(file_path: String) -> AppResult<String> {
 // ── Cache Management ──
 
 #[tauri::command]
-pub async fn get_cache_info(db: State<'_, DatabaseConnection>) -> AppResult<CacheInfo> {
+pub async fn get_cache_info(
+    db: State<'_, DatabaseConnection>,
+    jobs: State<'_, JobManager>,
+) -> AppResult<CacheInfo> {
+    let job = jobs.start(JobKind::CacheScan, None, "Scanning the ROM cache");
+    let result = scan_cache_info(db.inner(), job.token()).await;
+    job.finish(&result);
+    result
+}
+
+#[tauri::command]
+pub async fn cancel_cache_scan(jobs: State<'_, JobManager>) -> AppResult<()> {
+    jobs.cancel_kind(JobKind::CacheScan, None);
+    Ok(())
+}
+
+async fn scan_cache_info(
+    db: &DatabaseConnection,
+    cancel: CancellationToken,
+) -> AppResult<CacheInfo> {
     use sea_orm::{ConnectionTrait, DatabaseBackend, Statement};
 
     let cache_dir = rom_cache_dir();
 
     // Collect file info in a blocking task to avoid stalling the async runtime
-    let file_entries: Vec<(String, u64)> = tokio::task::spawn_blocking(move || {
-        let mut entries = Vec::new();
-        if let Ok(dir_entries) = std::fs::read_dir(&cache_dir) {
-            for entry in dir_entries.flatten() {
-                let path = entry.path();
-                if !path.is_file() {
-                    continue;
-                }
-                let file_name = path.file_name().unwrap_or_default().to_string_lossy().to_string();
-                if file_name.starts_with('.') && file_name.ends_with(".part") {
-                    continue;
-                }
-                let size = entry.metadata().map(|m| m.len()).unwrap_or(0);
-                entries.push((file_name, size));
-            }
-        }
-        entries
+    let file_entries = tokio::task::spawn_blocking(move || {
+        crate::rom_cache::list_entries(&cache_dir)
     })
     .await
     .map_err(|e| AppError::Other(format!("Task join error: {e}")))?;
 
-    // Batch query: get last_played_at for all cached file names in one query
-    let mut last_played_map: std::collections::HashMap<String, String> = std::collections::HashMap::new();
-    if !file_entries.is_empty() {
-        let placeholders: String = file_entries.iter().map(|_| "?").collect::<Vec<_>>().join(",");
-        let values: Vec<sea_orm::Value> = file_entries.iter().map(|(name, _)| name.clone().into()).collect();
+    if cancel.is_cancelled() {
+        return Err(AppError::Cancelled("Cache scan".to_string()));
+    }
+
+    // Batch queries: last_played_at for keyed entries by hash, older ones by file name
+    let mut played_by_hash: HashMap<String, String> = HashMap::new();
+    let mut played_by_name: HashMap<String, String> = HashMap::new();
+    let hashes: Vec<sea_orm::Value> =
+        file_entries.iter().filter_map(|e| e.hash_md5.clone()).map(Into::into).collect();
+    let names: Vec<sea_orm::Value> = file_entries
+        .iter()
+        .filter(|e| e.hash_md5.is_none())
+        .map(|e| e.file_name.clone().into())
+        .collect();
+    for (column, values, map) in [
+        ("LOWER(r.hash_md5)", hashes, &mut played_by_hash),
+        ("r.file_name", names, &mut played_by_name),
+    ] {
+        if values.is_empty() {
+            continue;
+        }
+        let placeholders: String = values.iter().map(|_| "?").collect::<Vec<_>>().join(",");
         let sql = format!(
-            "SELECT r.file_name, MAX(l.last_played_at) as last_played_at \
+            "SELECT {column} AS cache_key, MAX(l.last_played_at) as last_played_at \
              FROM roms r JOIN library l ON l.rom_id = r.id \
-             WHERE r.file_name IN ({placeholders}) \
-             GROUP BY r.file_name"
+             WHERE {column} IN ({placeholders}) \
+             GROUP BY {column}"
         );
-        if let Ok(rows) = db.inner()
+        if let Ok(rows) = db
             .query_all(Statement::from_sql_and_values(DatabaseBackend::Sqlite, &sql, values))
             .await
         {
             for row in rows {
-                if let (Ok(name), Ok(Some(played))) = (
-                    row.try_get::<String>("", "file_name"),
+                if let (Ok(key), Ok(Some(played))) = (
+                    row.try_get::<String>("", "cache_key"),
                     row.try_get::<Option<String>>("", "last_played_at"),
                 ) {
-                    last_played_map.insert(name, played);
+                    map.insert(key, played);
                 }
             }
         }
@@ -2606,10 +6730,18 @@ pub async fn get_cache_info(db: State<'_, DatabaseConnection>) -> AppResult<Cach
     let mut files = Vec::new();
     let mut total_size: u64 = 0;
 
-    for (file_name, size) in file_entries {
-        total_size += size;
-        let last_played = last_played_map.remove(&file_name);
-        files.push(CachedFile { file_name, size, last_played_at: last_played });
+    for entry in file_entries {
+        total_size += entry.size;
+        let last_played = match &entry.hash_md5 {
+            Some(hash) => played_by_hash.get(hash).cloned(),
+            None => played_by_name.get(&entry.file_name).cloned(),
+        };
+        files.push(CachedFile {
+            path: entry.relative_path,
+            file_name: entry.file_name,
+            size: entry.size,
+            last_played_at: last_played,
+        });
     }
 
     files.sort_by(|a, b| {
@@ -2621,43 +6753,318 @@ pub async fn get_cache_info(db: State<'_, DatabaseConnection>) -> AppResult<Cach
 }
 
 #[tauri::command]
-pub async fn clear_all_cache() -> AppResult<()> {
+pub async fn clear_all_cache(db: State<'_, DatabaseConnection>) -> AppResult<()> {
     let cache_dir = rom_cache_dir();
     tokio::task::spawn_blocking(move || {
-        if let Ok(entries) = std::fs::read_dir(&cache_dir) {
-            for entry in entries.flatten() {
-                let path = entry.path();
-                if path.is_file() {
-                    let name = path.file_name().unwrap_or_default().to_string_lossy().to_string();
-                    if name.starts_with('.') && name.ends_with(".part") {
-                        continue;
-                    }
-                    let _ = std::fs::remove_file(&path);
-                }
-            }
+        for entry in crate::rom_cache::list_entries(&cache_dir) {
+            let _ = crate::rom_cache::remove_entry(&entry.path);
         }
     })
     .await
     .map_err(|e| AppError::Other(format!("Task join error: {e}")))?;
+    crate::rom_cache::prune_index(db.inner()).await?;
     Ok(())
 }
 
+/// Remove cached ROMs by their `CachedFile::path`.
 #[tauri::command]
-pub async fn clear_cache_files(file_names: Vec<String>) -> AppResult<()> {
+pub async fn clear_cache_files(
+    db: State<'_, DatabaseConnection>,
+    file_names: Vec<String>,
+) -> AppResult<()> {
     let cache_dir = rom_cache_dir();
     tokio::task::spawn_blocking(move || {
         for file_name in &file_names {
-            if file_name.contains('/') || file_name.contains('\\') || file_name.contains("..") {
+            let Some(path) = crate::rom_cache::resolve(&cache_dir, file_name) else {
                 continue;
-            }
-            let path = cache_dir.join(file_name);
+            };
             if path.is_file() {
-                let _ = std::fs::remove_file(&path);
+                let _ = crate::rom_cache::remove_entry(&path);
+            }
+        }
+    })
+    .await
+    .map_err(|e| AppError::Other(format!("Task join error: {e}")))?;
+    crate::rom_cache::prune_index(db.inner()).await?;
+    Ok(())
+}
+
+/// Partial downloads and temp hash files younger than this may still be in use.
+const CLEANUP_MIN_AGE: std::time::Duration = std::time::Duration::from_secs(60 * 60);
+
+fn is_older_than(meta: &std::fs::Metadata, age: std::time::Duration) -> bool {
+    meta.modified()
+        .ok()
+        .and_then(|t| t.elapsed().ok())
+        .is_some_and(|elapsed| elapsed > age)
+}
+
+/// Remove cache files no ROM refers to, stale `.part` downloads, leftover temp
+/// hash files, and artwork/provider cache rows whose ROM no longer exists.
+#[tauri::command]
+pub async fn cleanup_storage(
+    db: State<'_, DatabaseConnection>,
+    jobs: State<'_, JobManager>,
+) -> AppResult<CleanupReport> {
+    let job = jobs.start(JobKind::Cleanup, None, "Cleaning up storage");
+    let result = run_storage_cleanup(db.inner(), job.token()).await;
+    job.finish(&result);
+    result
+}
+
+#[tauri::command]
+pub async fn cancel_cleanup(jobs: State<'_, JobManager>) -> AppResult<()> {
+    jobs.cancel_kind(JobKind::Cleanup, None);
+    Ok(())
+}
+
+/// Body of `cleanup_storage`; stops between files when `cancel` fires and
+/// reports what was removed so far.
+async fn run_storage_cleanup(
+    db: &DatabaseConnection,
+    cancel: CancellationToken,
+) -> AppResult<CleanupReport> {
+    use sea_orm::{ConnectionTrait, DatabaseBackend, Statement};
+
+    let known_files: std::collections::HashSet<String> = db
+        .query_all(Statement::from_string(
+            DatabaseBackend::Sqlite,
+            "SELECT DISTINCT file_name FROM roms",
+        ))
+        .await?
+        .into_iter()
+        .filter_map(|row| row.try_get::<String>("", "file_name").ok())
+        .collect();
+    let known_hashes: std::collections::HashSet<String> = db
+        .query_all(Statement::from_string(
+            DatabaseBackend::Sqlite,
+            "SELECT DISTINCT LOWER(hash_md5) AS hash_md5 FROM roms WHERE hash_md5 IS NOT NULL",
+        ))
+        .await?
+        .into_iter()
+        .filter_map(|row| row.try_get::<String>("", "hash_md5").ok())
+        .collect();
+
+    let cache_dir = rom_cache_dir();
+    let scan_cancel = cancel.clone();
+    let mut report = tokio::task::spawn_blocking(move || {
+        let mut report = CleanupReport {
+            orphaned_cache_files: 0,
+            partial_downloads: 0,
+            temp_hash_files: 0,
+            orphaned_db_rows: 0,
+            reclaimed_bytes: 0,
+        };
+
+        if let Ok(entries) = std::fs::read_dir(&cache_dir) {
+            for entry in entries.flatten() {
+                if scan_cancel.is_cancelled() {
+                    return report;
+                }
+                let path = entry.path();
+                let Ok(meta) = entry.metadata() else { continue };
+                if !meta.is_file() {
+                    continue;
+                }
+                let name = path.file_name().unwrap_or_default().to_string_lossy().to_string();
+                let is_part = name.starts_with('.') && name.ends_with(".part");
+                if is_part {
+                    if !is_older_than(&meta, CLEANUP_MIN_AGE) {
+                        continue;
+                    }
+                } else if known_files.contains(&name) {
+                    continue;
+                }
+                if std::fs::remove_file(&path).is_ok() {
+                    report.reclaimed_bytes += meta.len();
+                    if is_part {
+                        report.partial_downloads += 1;
+                    } else {
+                        report.orphaned_cache_files += 1;
+                    }
+                }
+            }
+        }
+
+        // Hash-keyed downloads whose content no ROM in the library has
+        for entry in crate::rom_cache::list_entries(&cache_dir) {
+            if scan_cancel.is_cancelled() {
+                return report;
+            }
+            let Some(hash) = entry.hash_md5 else { continue };
+            if known_hashes.contains(&hash) {
+                continue;
+            }
+            if crate::rom_cache::remove_entry(&entry.path).is_ok() {
+                report.reclaimed_bytes += entry.size;
+                report.orphaned_cache_files += 1;
+            }
+        }
+
+        let tmp_dir = std::env::temp_dir().join("romm-buddy-hash");
+        if let Ok(entries) = std::fs::read_dir(&tmp_dir) {
+            for entry in entries.flatten() {
+                if scan_cancel.is_cancelled() {
+                    return report;
+                }
+                let Ok(meta) = entry.metadata() else { continue };
+                if !meta.is_file() || !is_older_than(&meta, CLEANUP_MIN_AGE) {
+                    continue;
+                }
+                if std::fs::remove_file(entry.path()).is_ok() {
+                    report.reclaimed_bytes += meta.len();
+                    report.temp_hash_files += 1;
+                }
             }
         }
+
+        report
     })
     .await
     .map_err(|e| AppError::Other(format!("Task join error: {e}")))?;
+    crate::rom_cache::prune_index(db).await?;
+
+    // Rows left behind by ROMs deleted before foreign keys were enforced
+    for table in [
+        "artwork",
+        "metadata",
+        "hasheous_cache",
+        "igdb_cache",
+        "screenscraper_cache",
+    ] {
+        if cancel.is_cancelled() {
+            break;
+        }
+        let result = db
+            .execute(Statement::from_string(
+                DatabaseBackend::Sqlite,
+                format!("DELETE FROM {table} WHERE rom_id NOT IN (SELECT id FROM roms)"),
+            ))
+            .await?;
+        report.orphaned_db_rows += result.rows_affected();
+    }
+
+    log::info!(
+        "Storage cleanup: {} cache files, {} partial downloads, {} temp hash files, {} db rows, {} bytes reclaimed",
+        report.orphaned_cache_files,
+        report.partial_downloads,
+        report.temp_hash_files,
+        report.orphaned_db_rows,
+        report.reclaimed_bytes,
+    );
+
+    Ok(report)
+}
+
+/// Serializes the startup run of the cache migration with `migrate_cache_layout`.
+static CACHE_MIGRATION: tokio::sync::Mutex<()> = tokio::sync::Mutex::const_new(());
+
+/// Move ROM downloads cached by file name and grid covers cached by ROM id to
+/// hash-keyed names, discarding anything that fails verification. Runs in the
+/// background at startup; calling it again is harmless.
+#[tauri::command]
+pub async fn migrate_cache_layout(
+    db: State<'_, DatabaseConnection>,
+) -> AppResult<CacheMigrationReport> {
+    run_cache_migration(db.inner()).await
+}
+
+pub(crate) async fn run_cache_migration(
+    db: &DatabaseConnection,
+) -> AppResult<CacheMigrationReport> {
+    let _guard = CACHE_MIGRATION.lock().await;
+    let mut report = CacheMigrationReport::default();
+    crate::rom_cache::migrate_legacy(db, &mut report).await?;
+    migrate_grid_covers(db, &mut report).await?;
+    if report.roms_rekeyed + report.covers_rekeyed > 0 || !report.discarded.is_empty() {
+        log::info!(
+            "Cache migration: {} ROMs and {} covers re-keyed, {} files discarded, {} bytes reclaimed",
+            report.roms_rekeyed,
+            report.covers_rekeyed,
+            report.discarded.len(),
+            report.reclaimed_bytes,
+        );
+    }
+    Ok(report)
+}
+
+/// Downloads thrown away for failing verification (truncated, or not
+/// matching the ROM's known hash), newest first.
+#[tauri::command]
+pub async fn get_corrupt_downloads(
+    db: State<'_, DatabaseConnection>,
+    limit: Option<i64>,
+) -> AppResult<Vec<CorruptDownload>> {
+    crate::rom_cache::list_corrupt(db.inner(), limit).await
+}
+
+#[tauri::command]
+pub async fn clear_corrupt_downloads(db: State<'_, DatabaseConnection>) -> AppResult<u64> {
+    crate::rom_cache::clear_corrupt(db.inner()).await
+}
+
+/// Rename `<rom_id>.png` grid covers to [`grid_cover_path`] names, dropping
+/// ones whose ROM or cover is gone or that don't decode.
+async fn migrate_grid_covers(
+    db: &DatabaseConnection,
+    report: &mut CacheMigrationReport,
+) -> AppResult<()> {
+    use sea_orm::{ConnectionTrait, DatabaseBackend, Statement};
+
+    let Ok(entries) = std::fs::read_dir(grid_cover_dir()) else {
+        return Ok(());
+    };
+    let legacy: Vec<(i64, std::path::PathBuf, u64)> = entries
+        .flatten()
+        .filter_map(|e| {
+            let path = e.path();
+            let rom_id = path.file_stem()?.to_str()?.parse().ok()?;
+            let size = e.metadata().ok()?.len();
+            Some((rom_id, path, size))
+        })
+        .collect();
+
+    for (rom_id, path, size) in legacy {
+        let cover_url: Option<String> = db
+            .query_one(Statement::from_sql_and_values(
+                DatabaseBackend::Sqlite,
+                "SELECT url FROM artwork WHERE rom_id = ? AND art_type = 'cover' LIMIT 1",
+                [rom_id.into()],
+            ))
+            .await?
+            .and_then(|row| row.try_get::<String>("", "url").ok());
+
+        let check = path.clone();
+        let readable = tokio::task::spawn_blocking(move || image::open(&check).is_ok())
+            .await
+            .map_err(|e| AppError::Other(format!("Task join error: {e}")))?;
+
+        let reason = match cover_url {
+            None => "ROM or its cover no longer exists",
+            Some(_) if !readable => "Unreadable image",
+            Some(url) => {
+                let dest = grid_cover_path(&url);
+                if dest.exists() {
+                    let _ = tokio::fs::remove_file(&path).await;
+                } else {
+                    tokio::fs::rename(&path, &dest).await?;
+                }
+                report.covers_rekeyed += 1;
+                continue;
+            }
+        };
+        let name = path.file_name().unwrap_or_default().to_string_lossy().into_owned();
+        if let Err(e) = tokio::fs::remove_file(&path).await {
+            log::warn!("Failed to remove grid cover {name}: {e}");
+            continue;
+        }
+        report.reclaimed_bytes += size;
+        report.discarded.push(DiscardedCacheFile {
+            path: format!("grid_covers/{name}"),
+            reason: reason.to_string(),
+            size,
+        });
+    }
     Ok(())
 }
 
@@ -2677,5 +7084,275 @@ pub async fn set_cache_eviction_days(app: tauri::AppHandle, days: u32) -> AppRes
         .map_err(|e| AppError::Other(e.to_string()))?;
     store.set("cache_eviction_days", serde_json::json!(days));
     store.save().map_err(|e| AppError::Other(e.to_string()))?;
+    crate::settings::save_to_db(&app).await?;
+    Ok(())
+}
+
+/// How many parallel byte ranges large ROM downloads are split into; 1
+/// downloads on a single connection.
+pub(crate) fn read_download_segments_from_store(app: &impl SettingsRead) -> u32 {
+    app.setting("download_segments")
+        .and_then(|v| v.as_u64())
+        .and_then(|v| u32::try_from(v).ok())
+        .unwrap_or(crate::downloader::DEFAULT_SEGMENTS)
+        .clamp(1, crate::downloader::MAX_SEGMENTS)
+}
+
+#[tauri::command]
+pub async fn get_download_segments(app: tauri::AppHandle) -> AppResult<u32> {
+    Ok(read_download_segments_from_store(&app))
+}
+
+#[tauri::command]
+pub async fn set_download_segments(app: tauri::AppHandle, segments: u32) -> AppResult<()> {
+    let store = app
+        .store("settings.json")
+        .map_err(|e| AppError::Other(e.to_string()))?;
+    store.set(
+        "download_segments",
+        serde_json::json!(segments.clamp(1, crate::downloader::MAX_SEGMENTS)),
+    );
+    store.save().map_err(|e| AppError::Other(e.to_string()))?;
+    crate::settings::save_to_db(&app).await?;
+    Ok(())
+}
+
+/// Back up the database now. Counts toward the kept backups.
+#[tauri::command]
+pub async fn backup_database(
+    app: tauri::AppHandle,
+    db: State<'_, DatabaseConnection>,
+) -> AppResult<DatabaseBackup> {
+    let keep = crate::backup::read_backup_count(&app);
+    crate::backup::backup(db.inner(), &crate::backup::backup_dir(), keep).await
+}
+
+#[tauri::command]
+pub async fn check_database_integrity(
+    db: State<'_, DatabaseConnection>,
+) -> AppResult<IntegrityReport> {
+    crate::backup::integrity_check(db.inner()).await
+}
+
+#[tauri::command]
+pub async fn get_database_backup_count(app: tauri::AppHandle) -> AppResult<u32> {
+    Ok(crate::backup::read_backup_count(&app))
+}
+
+/// How many backups to keep; 0 turns off the backup at startup.
+#[tauri::command]
+pub async fn set_database_backup_count(app: tauri::AppHandle, count: u32) -> AppResult<()> {
+    let store = app
+        .store("settings.json")
+        .map_err(|e| AppError::Other(e.to_string()))?;
+    store.set(crate::backup::BACKUP_COUNT_KEY, serde_json::json!(count));
+    store.save().map_err(|e| AppError::Other(e.to_string()))?;
+    crate::settings::save_to_db(&app).await?;
+    Ok(())
+}
+
+/// Write preferences (not paths or credentials) to a JSON file that
+/// `import_settings` can apply on another install. Returns settings written.
+#[tauri::command]
+pub async fn export_settings(app: tauri::AppHandle, path: String) -> AppResult<usize> {
+    crate::settings::save_to_db(&app).await?;
+    crate::settings::export_to_file(&app, std::path::Path::new(&path)).await
+}
+
+/// Apply a file written by `export_settings`. Returns settings applied.
+#[tauri::command]
+pub async fn import_settings(app: tauri::AppHandle, path: String) -> AppResult<usize> {
+    crate::settings::import_from_file(&app, std::path::Path::new(&path)).await
+}
+
+/// Write a collection (or the favorites when `collection_id` is None) as a
+/// shareable list. `format` is "json" (importable) or "markdown". Returns the
+/// number of games written.
+#[tauri::command]
+pub async fn export_list(
+    db: State<'_, DatabaseConnection>,
+    collection_id: Option<i64>,
+    path: String,
+    format: crate::lists::ListFormat,
+) -> AppResult<usize> {
+    let source = match collection_id {
+        Some(id) => crate::lists::ListSource::Collection(id),
+        None => crate::lists::ListSource::Favorites,
+    };
+    let list = crate::lists::build_list(db.inner(), source).await?;
+    let contents = crate::lists::render_list(&list, format)?;
+    tokio::fs::write(&path, contents).await?;
+    Ok(list.entries.len())
+}
+
+/// Import a JSON list from `export_list` as a new local collection, matching
+/// its games against this library by hash, then by name.
+#[tauri::command]
+pub async fn import_list(
+    app: tauri::AppHandle,
+    db: State<'_, DatabaseConnection>,
+    path: String,
+) -> AppResult<ListImportReport> {
+    use tauri::Emitter;
+
+    let report = crate::lists::import_list(db.inner(), std::path::Path::new(&path)).await?;
+    let _ = app.emit(
+        LIBRARY_CHANGED_EVENT,
+        LibraryChanged { source_id: None, merged_duplicates: 0 },
+    );
+    Ok(report)
+}
+
+#[tauri::command]
+pub async fn get_profiles(db: State<'_, DatabaseConnection>) -> AppResult<Vec<Profile>> {
+    crate::profiles::list(db.inner()).await
+}
+
+#[tauri::command]
+pub async fn create_profile(db: State<'_, DatabaseConnection>, name: String) -> AppResult<Profile> {
+    crate::profiles::create(db.inner(), &name).await
+}
+
+/// Switch the active profile. The library is re-read since favorites, play
+/// stats and statuses change with it. Leaving a profile with a content
+/// filter takes the parent `pin`.
+#[tauri::command]
+pub async fn switch_profile(
+    app: tauri::AppHandle,
+    db: State<'_, DatabaseConnection>,
+    profile_id: i64,
+    pin: Option<String>,
+) -> AppResult<Profile> {
+    use tauri::Emitter;
+
+    let profile = crate::profiles::switch(db.inner(), profile_id, pin.as_deref()).await?;
+    let _ = app.emit(
+        LIBRARY_CHANGED_EVENT,
+        LibraryChanged { source_id: None, merged_duplicates: 0 },
+    );
+    Ok(profile)
+}
+
+/// Set the age limit of a profile's content filter; `max_age` of None shows
+/// every game. Takes the parent `pin`.
+#[tauri::command]
+pub async fn set_profile_content_filter(
+    app: tauri::AppHandle,
+    db: State<'_, DatabaseConnection>,
+    profile_id: i64,
+    max_age: Option<i64>,
+    allow_unrated: bool,
+    pin: Option<String>,
+) -> AppResult<Profile> {
+    use tauri::Emitter;
+
+    let profile = crate::profiles::set_content_filter(
+        db.inner(),
+        profile_id,
+        max_age,
+        allow_unrated,
+        pin.as_deref(),
+    )
+    .await?;
+    let _ = app.emit(
+        LIBRARY_CHANGED_EVENT,
+        LibraryChanged { source_id: None, merged_duplicates: 0 },
+    );
+    Ok(profile)
+}
+
+/// Delete a profile with its favorites, play stats, statuses and local
+/// collections. Takes the parent `pin` while the active profile is filtered.
+#[tauri::command]
+pub async fn delete_profile(
+    app: tauri::AppHandle,
+    db: State<'_, DatabaseConnection>,
+    profile_id: i64,
+    pin: Option<String>,
+) -> AppResult<()> {
+    use tauri::Emitter;
+
+    crate::profiles::delete(db.inner(), profile_id, pin.as_deref()).await?;
+    let _ = app.emit(
+        LIBRARY_CHANGED_EVENT,
+        LibraryChanged { source_id: None, merged_duplicates: 0 },
+    );
     Ok(())
 }
+
+#[tauri::command]
+pub async fn has_parent_pin(db: State<'_, DatabaseConnection>) -> AppResult<bool> {
+    crate::profiles::has_parent_pin(db.inner()).await
+}
+
+/// Set, change or remove (`new_pin` of None) the parent PIN guarding
+/// content filters. Changing or removing it takes the `current_pin`.
+#[tauri::command]
+pub async fn set_parent_pin(
+    db: State<'_, DatabaseConnection>,
+    current_pin: Option<String>,
+    new_pin: Option<String>,
+) -> AppResult<()> {
+    crate::profiles::set_parent_pin(db.inner(), current_pin.as_deref(), new_pin.as_deref()).await
+}
+
+// ---------- Operation timing ----------
+
+/// Recent syncs, downloads, hashes and enrichment runs with how long they
+/// took, newest first. `operation` narrows it to e.g. `sync` or `enrich`
+/// (which includes the per-step rows such as `enrich.screenscraper`).
+#[tauri::command]
+pub async fn get_operation_history(
+    db: State<'_, DatabaseConnection>,
+    operation: Option<String>,
+    limit: Option<i64>,
+) -> AppResult<Vec<OperationLogEntry>> {
+    crate::operation_log::history(db.inner(), operation.as_deref(), limit).await
+}
+
+// ---------- Activity ----------
+
+/// What's been happening: launches, achievements, ROMs added by syncs and
+/// collection changes, newest first. Pass the id of the last entry shown as
+/// `before` to load older ones.
+#[tauri::command]
+pub async fn get_activity_feed(
+    db: State<'_, DatabaseConnection>,
+    limit: Option<i64>,
+    before: Option<i64>,
+) -> AppResult<Vec<ActivityEntry>> {
+    crate::activity::feed(db.inner(), limit, before).await
+}
+
+/// A year's summary of play: launches, most-played games and platforms,
+/// additions, achievements and finished games, all from local data.
+#[tauri::command]
+pub async fn get_yearly_wrapped(
+    app: tauri::AppHandle,
+    db: State<'_, DatabaseConnection>,
+    year: i32,
+) -> AppResult<YearlyWrapped> {
+    let ra_username = app
+        .setting("retroachievements_username")
+        .and_then(|v| v.as_str().map(str::to_string))
+        .filter(|s| !s.is_empty());
+    crate::wrapped::yearly_wrapped(db.inner(), year, ra_username.as_deref()).await
+}
+
+// ---------- Jobs ----------
+
+/// Running jobs and the most recent finished ones. `jobs://updated` carries
+/// each change after this first read.
+#[tauri::command]
+pub async fn list_jobs(jobs: State<'_, JobManager>) -> AppResult<Vec<Job>> {
+    Ok(jobs.list())
+}
+
+#[tauri::command]
+pub async fn cancel_job(jobs: State<'_, JobManager>, job_id: u64) -> AppResult<()> {
+    if jobs.cancel(job_id) {
+        Ok(())
+    } else {
+        Err(AppError::NotFound(format!("Running job {job_id}")))
+    }
+}
@@ -0,0 +1,507 @@
+use std::path::{Path, PathBuf};
+use std::process::Stdio;
+
+use sea_orm::{ConnectionTrait, DatabaseBackend, DatabaseConnection, FromQueryResult, Statement};
+use tokio_util::sync::CancellationToken;
+
+use crate::error::{AppError, AppResult};
+use crate::models::{CompressedFormat, CompressionReport, ScanProgress};
+
+/// Folders conversion tools are often installed to without being on PATH
+/// (a GUI app launched from the Finder doesn't get the shell's PATH).
+#[cfg(target_os = "macos")]
+const TOOL_DIRS: &[&str] = &[
+    "/opt/homebrew/bin",
+    "/usr/local/bin",
+    "/Applications/Dolphin.app/Contents/MacOS",
+];
+#[cfg(target_os = "windows")]
+const TOOL_DIRS: &[&str] = &[
+    r"C:\Program Files\Dolphin",
+    r"C:\Program Files\MAME",
+    r"C:\Program Files\maxcso",
+];
+#[cfg(not(any(target_os = "macos", target_os = "windows")))]
+const TOOL_DIRS: &[&str] = &["/usr/local/bin", "/usr/bin"];
+
+impl CompressedFormat {
+    pub fn extension(self) -> &'static str {
+        match self {
+            Self::Chd => "chd",
+            Self::Rvz => "rvz",
+            Self::Cso => "cso",
+        }
+    }
+
+    /// Executable names of the tool that writes this format.
+    fn tool_names(self) -> &'static [&'static str] {
+        match self {
+            Self::Chd => &["chdman"],
+            Self::Rvz => &["dolphin-tool", "DolphinTool"],
+            Self::Cso => &["maxcso"],
+        }
+    }
+
+    /// Disc image extensions the tool reads.
+    fn source_extensions(self) -> &'static [&'static str] {
+        match self {
+            Self::Chd => &["cue", "gdi", "toc", "iso"],
+            Self::Rvz => &["iso", "gcm", "wbfs", "ciso", "gcz", "wia"],
+            Self::Cso => &["iso"],
+        }
+    }
+}
+
+/// The format a platform's disc images compress to: RVZ where its emulator
+/// reads that, CHD where it reads that, nothing for cartridge platforms.
+pub fn format_for_platform(platform_slug: &str) -> Option<CompressedFormat> {
+    let extensions = crate::platform_registry::extensions(platform_slug);
+    if extensions.contains(&"rvz") {
+        Some(CompressedFormat::Rvz)
+    } else if extensions.contains(&"chd") {
+        Some(CompressedFormat::Chd)
+    } else {
+        None
+    }
+}
+
+fn lower_extension(path: &Path) -> String {
+    path.extension()
+        .and_then(|e| e.to_str())
+        .map(str::to_ascii_lowercase)
+        .unwrap_or_default()
+}
+
+/// Whether `path` is a disc image that can be converted to `format`.
+pub fn can_convert(path: &Path, format: CompressedFormat) -> bool {
+    format
+        .source_extensions()
+        .contains(&lower_extension(path).as_str())
+}
+
+/// Where the tool for `format` is installed: on PATH or in one of the usual
+/// install folders.
+pub fn find_tool(format: CompressedFormat) -> Option<PathBuf> {
+    let mut dirs: Vec<PathBuf> = std::env::var_os("PATH")
+        .map(|path| std::env::split_paths(&path).collect())
+        .unwrap_or_default();
+    dirs.extend(TOOL_DIRS.iter().map(PathBuf::from));
+
+    format.tool_names().iter().find_map(|name| {
+        let exe = if cfg!(windows) {
+            format!("{name}.exe")
+        } else {
+            (*name).to_string()
+        };
+        dirs.iter().map(|dir| dir.join(&exe)).find(|p| p.is_file())
+    })
+}
+
+fn tool(format: CompressedFormat) -> AppResult<PathBuf> {
+    find_tool(format).ok_or_else(|| {
+        AppError::NotFound(format!(
+            "{} (install it or add it to PATH)",
+            format.tool_names()[0]
+        ))
+    })
+}
+
+/// Run a conversion tool to completion, killing it if `cancel` fires.
+/// Returns what it printed to stdout.
+async fn run(
+    mut cmd: tokio::process::Command,
+    format: CompressedFormat,
+    cancel: &CancellationToken,
+) -> AppResult<String> {
+    cmd.stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .kill_on_drop(true);
+    let child = cmd
+        .spawn()
+        .map_err(|e| AppError::Other(format!("Failed to start {}: {e}", format.tool_names()[0])))?;
+    let output = tokio::select! {
+        output = child.wait_with_output() => output?,
+        () = cancel.cancelled() => return Err(AppError::Cancelled("Conversion".to_string())),
+    };
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        let reason = stderr
+            .lines()
+            .rev()
+            .find(|l| !l.trim().is_empty())
+            .unwrap_or("no output");
+        return Err(AppError::Other(format!(
+            "{} exited with {}: {}",
+            format.tool_names()[0],
+            output.status,
+            reason.trim()
+        )));
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+/// Convert a disc image to `format` next to it, returning the new file. The
+/// tool writes to a temporary name that is only renamed into place once it
+/// succeeds, so a failed or cancelled conversion leaves nothing behind. The
+/// original is left alone.
+pub async fn convert_file(
+    src: &Path,
+    format: CompressedFormat,
+    cancel: &CancellationToken,
+) -> AppResult<PathBuf> {
+    let tool = tool(format)?;
+    let out = src.with_extension(format.extension());
+    if out.exists() {
+        return Err(AppError::Other(format!("{} already exists", out.display())));
+    }
+    let tmp = src.with_extension(format!("{}.part", format.extension()));
+    let _ = tokio::fs::remove_file(&tmp).await;
+
+    let mut cmd = tokio::process::Command::new(&tool);
+    match format {
+        CompressedFormat::Chd => {
+            let create = if lower_extension(src) == "iso" {
+                "createdvd"
+            } else {
+                "createcd"
+            };
+            cmd.arg(create).arg("-i").arg(src).arg("-o").arg(&tmp);
+        }
+        // The block size, codec and level Dolphin's own "Convert File" uses
+        CompressedFormat::Rvz => {
+            cmd.args([
+                "convert", "-f", "rvz", "-b", "131072", "-c", "zstd", "-l", "5",
+            ])
+            .arg("-i")
+            .arg(src)
+            .arg("-o")
+            .arg(&tmp);
+        }
+        CompressedFormat::Cso => {
+            cmd.arg("--format=cso1").arg(src).arg("-o").arg(&tmp);
+        }
+    }
+
+    log::info!("Converting {} to {}", src.display(), format.extension());
+    if let Err(e) = run(cmd, format, cancel).await {
+        let _ = tokio::fs::remove_file(&tmp).await;
+        return Err(e);
+    }
+    tokio::fs::rename(&tmp, &out).await?;
+    Ok(out)
+}
+
+/// Check a converted image holds the same disc as `original`. chdman checks
+/// a CHD against the checksum stored inside it; for RVZ and CSO the tool
+/// computes the CRC32 of the disc data in both files, since the original may
+/// itself be compressed (WBFS, GCZ).
+pub async fn verify_converted(
+    converted: &Path,
+    original: &Path,
+    format: CompressedFormat,
+    cancel: &CancellationToken,
+) -> AppResult<()> {
+    let tool = tool(format)?;
+    if format == CompressedFormat::Chd {
+        let mut cmd = tokio::process::Command::new(&tool);
+        cmd.arg("verify").arg("-i").arg(converted);
+        run(cmd, format, cancel).await?;
+        return Ok(());
+    }
+
+    let disc_crc32 = |path: &Path| {
+        let mut cmd = tokio::process::Command::new(&tool);
+        match format {
+            CompressedFormat::Rvz => {
+                cmd.arg("verify").arg("-i").arg(path).args(["-a", "crc32"]);
+            }
+            _ => {
+                cmd.arg("--crc").arg(path);
+            }
+        }
+        async move { Ok::<_, AppError>(last_crc32(&run(cmd, format, cancel).await?)) }
+    };
+    let expected = disc_crc32(original).await?;
+    let actual = disc_crc32(converted).await?;
+    match (expected, actual) {
+        (Some(expected), Some(actual)) if expected == actual => Ok(()),
+        (Some(expected), Some(actual)) => Err(AppError::Other(format!(
+            "{} doesn't match the original (CRC32 {actual}, expected {expected})",
+            converted.display()
+        ))),
+        _ => Err(AppError::Other(format!(
+            "{} printed no CRC32 to verify {} with",
+            format.tool_names()[0],
+            converted.display()
+        ))),
+    }
+}
+
+/// The last 8-digit hex word in a tool's output, lowercased.
+fn last_crc32(output: &str) -> Option<String> {
+    output
+        .split(|c: char| c.is_whitespace() || c == ':')
+        .rev()
+        .find(|word| word.len() == 8 && word.chars().all(|c| c.is_ascii_hexdigit()))
+        .map(str::to_ascii_lowercase)
+}
+
+/// The files a ROM is made of on disk: the file itself plus the tracks of a
+/// cue/gdi sheet.
+pub fn rom_files(path: &Path) -> Vec<PathBuf> {
+    let mut files = vec![path.to_path_buf()];
+    files.extend(crate::sources::local_sync::companion_files(path));
+    files
+}
+
+fn total_size(files: &[PathBuf]) -> u64 {
+    files
+        .iter()
+        .filter_map(|f| std::fs::metadata(f).ok())
+        .map(|m| m.len())
+        .sum()
+}
+
+/// Point a local source's ROM at its converted file. The ROM row itself is
+/// only renamed when no other source has it, since remote sources download
+/// by that file name.
+pub async fn record_converted(
+    db: &DatabaseConnection,
+    source_rom_row: i64,
+    rom_id: i64,
+    converted: &Path,
+) -> AppResult<()> {
+    let path = converted.to_string_lossy().into_owned();
+    let file_name = converted
+        .file_name()
+        .map(|n| n.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    let size = std::fs::metadata(converted)
+        .ok()
+        .and_then(|m| i64::try_from(m.len()).ok());
+
+    db.execute(Statement::from_sql_and_values(
+        DatabaseBackend::Sqlite,
+        "UPDATE source_roms SET source_rom_id = ?, file_name = ?, hash_md5 = NULL WHERE id = ?",
+        [path.into(), file_name.clone().into(), source_rom_row.into()],
+    ))
+    .await?;
+    db.execute(Statement::from_sql_and_values(
+        DatabaseBackend::Sqlite,
+        "UPDATE roms SET file_name = ?, file_size = ?,
+                updated_at = strftime('%Y-%m-%dT%H:%M:%fZ', 'now')
+         WHERE id = ?
+           AND NOT EXISTS (SELECT 1 FROM source_roms WHERE rom_id = roms.id AND id != ?)",
+        [
+            file_name.into(),
+            size.into(),
+            rom_id.into(),
+            source_rom_row.into(),
+        ],
+    ))
+    .await?;
+    // Same game data in a new container: keep its verification result
+    // instead of re-hashing the compressed file on the next run
+    if let Some((size, mtime)) = crate::metadata::dat::file_stamp(converted) {
+        db.execute(Statement::from_sql_and_values(
+            DatabaseBackend::Sqlite,
+            "UPDATE roms SET verified_file_size = ?, verified_file_mtime = ?
+             WHERE id = ? AND verified_file_size IS NOT NULL",
+            [size.into(), mtime.into(), rom_id.into()],
+        ))
+        .await?;
+    }
+    Ok(())
+}
+
+/// Convert one local ROM file and point the library at the result. With
+/// `remove_originals`, the new file is verified first and the original (with
+/// its track files) deleted afterwards. Returns the size before and after.
+async fn convert_rom_file(
+    db: &DatabaseConnection,
+    source_rom_row: i64,
+    rom_id: i64,
+    src: &Path,
+    format: CompressedFormat,
+    remove_originals: bool,
+    cancel: &CancellationToken,
+) -> AppResult<(u64, u64)> {
+    let originals = rom_files(src);
+    let converted = convert_file(src, format, cancel).await?;
+    if remove_originals {
+        if let Err(e) = verify_converted(&converted, src, format, cancel).await {
+            let _ = tokio::fs::remove_file(&converted).await;
+            return Err(e);
+        }
+    }
+    record_converted(db, source_rom_row, rom_id, &converted).await?;
+
+    let sizes = (
+        total_size(&originals),
+        total_size(std::slice::from_ref(&converted)),
+    );
+    if remove_originals {
+        for file in &originals {
+            if let Err(e) = tokio::fs::remove_file(file).await {
+                log::warn!("Failed to remove {} after converting: {e}", file.display());
+            }
+        }
+    }
+    Ok(sizes)
+}
+
+/// Convert one ROM's local file to `format`. Errors when the ROM has no
+/// local file or it isn't a disc image that format can be made from.
+pub async fn convert_rom(
+    db: &DatabaseConnection,
+    rom_id: i64,
+    format: CompressedFormat,
+    remove_originals: bool,
+    cancel: &CancellationToken,
+) -> AppResult<CompressionReport> {
+    #[derive(Debug, FromQueryResult)]
+    struct LocalFileRow {
+        id: i64,
+        path: String,
+    }
+
+    let row = LocalFileRow::find_by_statement(Statement::from_sql_and_values(
+        DatabaseBackend::Sqlite,
+        "SELECT sr.id, sr.source_rom_id AS path
+         FROM source_roms sr
+         JOIN sources s ON s.id = sr.source_id AND s.source_type = 'local'
+         WHERE sr.rom_id = ? AND sr.source_rom_id IS NOT NULL
+         LIMIT 1",
+        [rom_id.into()],
+    ))
+    .one(db)
+    .await?
+    .ok_or_else(|| AppError::NotFound(format!("Local file for ROM {rom_id}")))?;
+
+    let src = PathBuf::from(&row.path);
+    if !src.is_file() {
+        return Err(AppError::NotFound(format!("ROM file {}", row.path)));
+    }
+    if !can_convert(&src, format) {
+        return Err(AppError::Other(format!(
+            "{} can't be converted to {}",
+            row.path,
+            format.extension().to_uppercase()
+        )));
+    }
+
+    let (original_bytes, compressed_bytes) =
+        convert_rom_file(db, row.id, rom_id, &src, format, remove_originals, cancel).await?;
+    Ok(CompressionReport {
+        converted: 1,
+        skipped: 0,
+        failed: Vec::new(),
+        original_bytes,
+        compressed_bytes,
+    })
+}
+
+/// Compress the local disc images of every platform set to compress (or
+/// only `platform_id`'s) to CHD/RVZ, updating the library to point at the
+/// new files. Originals and their track files are deleted (once the new file
+/// is verified) when `remove_originals` is set; otherwise they stay next to
+/// the new files.
+pub async fn compress_local_roms(
+    db: &DatabaseConnection,
+    platform_id: Option<i64>,
+    remove_originals: bool,
+    on_progress: impl Fn(ScanProgress) + Send,
+    cancel: CancellationToken,
+) -> AppResult<CompressionReport> {
+    #[derive(Debug, FromQueryResult)]
+    struct LocalRomRow {
+        id: i64,
+        rom_id: i64,
+        path: String,
+        platform_slug: String,
+    }
+
+    let rows = LocalRomRow::find_by_statement(Statement::from_sql_and_values(
+        DatabaseBackend::Sqlite,
+        "SELECT sr.id, sr.rom_id, sr.source_rom_id AS path, p.slug AS platform_slug
+         FROM source_roms sr
+         JOIN sources s ON s.id = sr.source_id AND s.source_type = 'local'
+         JOIN roms r ON r.id = sr.rom_id
+         JOIN platforms p ON p.id = r.platform_id
+         WHERE sr.source_rom_id IS NOT NULL
+           AND p.archive_handling = 'compress'
+           AND (? IS NULL OR p.id = ?)
+         ORDER BY p.slug, r.name",
+        [platform_id.into(), platform_id.into()],
+    ))
+    .all(db)
+    .await?;
+
+    #[allow(clippy::cast_possible_truncation)]
+    let total = rows.len() as u64;
+    let mut report = CompressionReport {
+        converted: 0,
+        skipped: 0,
+        failed: Vec::new(),
+        original_bytes: 0,
+        compressed_bytes: 0,
+    };
+
+    for (current, row) in (0u64..).zip(rows) {
+        if cancel.is_cancelled() {
+            break;
+        }
+        let src = PathBuf::from(&row.path);
+        let file_name = src
+            .file_name()
+            .map(|n| n.to_string_lossy().into_owned())
+            .unwrap_or_else(|| row.path.clone());
+        on_progress(ScanProgress {
+            source_id: -1,
+            total,
+            current,
+            current_item: format!("Compressing: {file_name}"),
+        });
+
+        let Some(format) = format_for_platform(&row.platform_slug)
+            .filter(|&format| can_convert(&src, format) && src.is_file())
+        else {
+            report.skipped += 1;
+            continue;
+        };
+
+        match convert_rom_file(
+            db,
+            row.id,
+            row.rom_id,
+            &src,
+            format,
+            remove_originals,
+            &cancel,
+        )
+        .await
+        {
+            Ok((original, compressed)) => {
+                report.converted += 1;
+                report.original_bytes += original;
+                report.compressed_bytes += compressed;
+            }
+            Err(AppError::Cancelled(_)) => break,
+            Err(e) => {
+                log::warn!("Failed to compress {}: {e}", src.display());
+                report.failed.push(format!("{file_name}: {e}"));
+            }
+        }
+    }
+
+    log::info!(
+        "Compressed {} ROMs ({} -> {} bytes), {} skipped, {} failed",
+        report.converted,
+        report.original_bytes,
+        report.compressed_bytes,
+        report.skipped,
+        report.failed.len()
+    );
+    Ok(report)
+}
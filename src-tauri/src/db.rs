@@ -1,13 +1,18 @@
 use sea_orm::DatabaseConnection;
-use sqlx::sqlite::{SqliteConnectOptions, SqlitePoolOptions};
+use sqlx::sqlite::{SqliteConnectOptions, SqliteJournalMode, SqlitePoolOptions, SqliteSynchronous};
 use std::str::FromStr;
+use std::time::Duration;
 
 use crate::error::AppResult;
 
 pub async fn create_pool(db_path: &str) -> AppResult<DatabaseConnection> {
     let options = SqliteConnectOptions::from_str(db_path)?
         .create_if_missing(true)
-        .journal_mode(sqlx::sqlite::SqliteJournalMode::Wal)
+        .journal_mode(SqliteJournalMode::Wal)
+        // WAL only needs a sync at checkpoints to stay consistent.
+        .synchronous(SqliteSynchronous::Normal)
+        // Wait for a sync or enrichment writer instead of failing with SQLITE_BUSY.
+        .busy_timeout(Duration::from_secs(10))
         .foreign_keys(true);
 
     let pool = SqlitePoolOptions::new()
@@ -2,9 +2,11 @@ use sea_orm::{
     ActiveModelTrait, ActiveValue::Set, ColumnTrait, ConnectionTrait, DatabaseBackend,
     DatabaseConnection, EntityTrait, FromQueryResult, QueryFilter, Statement,
 };
+use tokio_util::sync::CancellationToken;
 
 use crate::entity::roms;
 use crate::error::AppResult;
+use crate::events::LibraryEvents;
 
 /// Check if a ROM with this hash already exists on this platform.
 pub async fn find_existing_rom_by_hash(
@@ -97,6 +99,7 @@ pub async fn upsert_rom_deduped(
             ],
         ))
         .await?;
+        crate::metadata::edits::reapply(db, rom_id).await?;
 
         link_source(
             db,
@@ -124,11 +127,18 @@ pub async fn upsert_rom_deduped(
         hash_md5: Set(hash_md5.map(str::to_string)),
         hash_crc32: Set(None),
         hash_sha1: Set(None),
+        headerless_crc32: Set(None),
+        headerless_md5: Set(None),
+        headerless_sha1: Set(None),
         regions: Set(serde_json::from_str::<Vec<String>>(regions).unwrap_or_default().into()),
         languages: Set(crate::entity::json_vec::JsonVec::default()),
+        revision: Set(None),
+        dump_flags: Set(crate::entity::json_vec::JsonVec::default()),
         verification_status: Set(None),
         dat_entry_id: Set(None),
         dat_game_name: Set(None),
+        verified_file_size: Set(None),
+        verified_file_mtime: Set(None),
         created_at: Set(now.clone()),
         updated_at: Set(now),
     }
@@ -183,7 +193,12 @@ async fn link_source(
 
 /// Post-enrichment reconciliation: find ROMs sharing (platform_id, hash_md5)
 /// and merge them (keep oldest, move all related rows, delete dupes).
-pub async fn reconcile_duplicates(db: &DatabaseConnection) -> AppResult<u64> {
+/// Stops between groups when `cancel` fires; groups already merged stay merged.
+pub async fn reconcile_duplicates(
+    db: &DatabaseConnection,
+    cancel: &CancellationToken,
+    events: &LibraryEvents,
+) -> AppResult<u64> {
     // Find duplicate groups
     #[derive(Debug, FromQueryResult)]
     struct DupeGroup {
@@ -205,6 +220,10 @@ pub async fn reconcile_duplicates(db: &DatabaseConnection) -> AppResult<u64> {
     let mut merged_count: u64 = 0;
 
     for group in &groups {
+        if cancel.is_cancelled() {
+            break;
+        }
+
         // Get all ROM IDs in this group, ordered by id (keep oldest)
         #[derive(Debug, FromQueryResult)]
         struct RomId {
@@ -262,6 +281,24 @@ pub async fn reconcile_duplicates(db: &DatabaseConnection) -> AppResult<u64> {
             ))
             .await?;
 
+            // Move collection memberships (ignore conflicts)
+            db.execute(Statement::from_sql_and_values(
+                DatabaseBackend::Sqlite,
+                "UPDATE OR IGNORE collection_roms SET rom_id = ? WHERE rom_id = ?",
+                [keeper_id.into(), dupe_id.into()],
+            ))
+            .await?;
+
+            // Move notes, tags and the homebrew mark (ignore conflicts)
+            for table in ["rom_notes", "rom_tags", "rom_homebrew"] {
+                db.execute(Statement::from_sql_and_values(
+                    DatabaseBackend::Sqlite,
+                    format!("UPDATE OR IGNORE {table} SET rom_id = ? WHERE rom_id = ?"),
+                    [keeper_id.into(), dupe_id.into()],
+                ))
+                .await?;
+            }
+
             // Move hasheous_cache (ignore conflicts)
             db.execute(Statement::from_sql_and_values(
                 DatabaseBackend::Sqlite,
@@ -280,6 +317,8 @@ pub async fn reconcile_duplicates(db: &DatabaseConnection) -> AppResult<u64> {
 
             merged_count += 1;
         }
+
+        events.roms_merged(keeper_id, dupes.to_vec());
     }
 
     Ok(merged_count)
@@ -0,0 +1,153 @@
+use std::io::SeekFrom;
+use std::path::Path;
+use std::sync::atomic::{AtomicU64, Ordering};
+
+use futures_util::StreamExt;
+use tokio::io::{AsyncSeekExt, AsyncWriteExt};
+use tokio_util::sync::CancellationToken;
+
+use crate::error::{AppError, AppResult};
+use crate::sources::romm::RommClient;
+
+/// Segments a download is split into unless configured otherwise.
+pub const DEFAULT_SEGMENTS: u32 = 4;
+pub const MAX_SEGMENTS: u32 = 8;
+
+/// Smaller files aren't worth extra connections.
+const PARALLEL_THRESHOLD: u64 = 64 * 1024 * 1024;
+
+/// Download a ROM from ROMM into `part`. Large files are fetched as
+/// `segments` byte ranges in parallel when the server supports ranges, and
+/// on one connection otherwise. `on_progress` gets bytes downloaded and the
+/// total when known. Returns the size the server announced, if it did.
+pub async fn download_rom(
+    client: &RommClient,
+    romm_id: i64,
+    file_name: &str,
+    part: &Path,
+    segments: u32,
+    cancel: &CancellationToken,
+    on_progress: impl Fn(u64, u64),
+) -> AppResult<Option<u64>> {
+    // An open-ended range tells us whether ranges work and the full size,
+    // and is an ordinary download if they don't
+    let resp = client
+        .download_rom_range(romm_id, file_name, 0, None)
+        .await?;
+    let total = match resp.status() {
+        reqwest::StatusCode::PARTIAL_CONTENT => content_range_total(&resp),
+        _ => None,
+    };
+
+    match total {
+        Some(total) if segments > 1 && total >= PARALLEL_THRESHOLD => {
+            drop(resp);
+            log::info!("Downloading {file_name} ({total} bytes) in {segments} segments");
+            download_segments(
+                client,
+                romm_id,
+                file_name,
+                part,
+                total,
+                segments.min(MAX_SEGMENTS),
+                cancel,
+                &on_progress,
+            )
+            .await?;
+            Ok(Some(total))
+        }
+        _ => {
+            let announced = total.or_else(|| resp.content_length());
+            let mut file = tokio::fs::File::create(part).await?;
+            let mut stream = resp.bytes_stream();
+            let mut downloaded: u64 = 0;
+            while let Some(chunk) = stream.next().await {
+                if cancel.is_cancelled() {
+                    return Err(AppError::Cancelled("Download".to_string()));
+                }
+                let chunk = chunk?;
+                file.write_all(&chunk).await?;
+                downloaded += chunk.len() as u64;
+                on_progress(downloaded, announced.unwrap_or(0));
+            }
+            file.flush().await?;
+            file.sync_all().await?;
+            Ok(announced)
+        }
+    }
+}
+
+/// The full size from a `Content-Range: bytes 0-99/1234` header.
+fn content_range_total(resp: &reqwest::Response) -> Option<u64> {
+    resp.headers()
+        .get(reqwest::header::CONTENT_RANGE)?
+        .to_str()
+        .ok()?
+        .rsplit_once('/')?
+        .1
+        .parse()
+        .ok()
+}
+
+#[allow(clippy::too_many_arguments)]
+async fn download_segments(
+    client: &RommClient,
+    romm_id: i64,
+    file_name: &str,
+    part: &Path,
+    total: u64,
+    segments: u32,
+    cancel: &CancellationToken,
+    on_progress: &impl Fn(u64, u64),
+) -> AppResult<()> {
+    let file = tokio::fs::File::create(part).await?;
+    file.set_len(total).await?;
+    drop(file);
+
+    let downloaded = AtomicU64::new(0);
+    let segment_len = total.div_ceil(u64::from(segments));
+    let ranges = (0..u64::from(segments))
+        .map(|i| (i * segment_len, ((i + 1) * segment_len).min(total) - 1))
+        .filter(|(start, end)| start <= end);
+
+    futures_util::future::try_join_all(ranges.map(|(start, end)| {
+        let downloaded = &downloaded;
+        async move {
+            let resp = client
+                .download_rom_range(romm_id, file_name, start, Some(end))
+                .await?;
+            if resp.status() != reqwest::StatusCode::PARTIAL_CONTENT {
+                return Err(AppError::Other(format!(
+                    "Server stopped honouring ranges while downloading {file_name}"
+                )));
+            }
+
+            let mut file = tokio::fs::OpenOptions::new().write(true).open(part).await?;
+            file.seek(SeekFrom::Start(start)).await?;
+            let mut written: u64 = 0;
+            let mut stream = resp.bytes_stream();
+            while let Some(chunk) = stream.next().await {
+                if cancel.is_cancelled() {
+                    return Err(AppError::Cancelled("Download".to_string()));
+                }
+                let chunk = chunk?;
+                file.write_all(&chunk).await?;
+                written += chunk.len() as u64;
+                let so_far = downloaded.fetch_add(chunk.len() as u64, Ordering::Relaxed)
+                    + chunk.len() as u64;
+                on_progress(so_far, total);
+            }
+            file.flush().await?;
+            file.sync_all().await?;
+
+            if written != end - start + 1 {
+                return Err(AppError::Other(format!(
+                    "Segment {start}-{end} of {file_name} ended after {written} bytes"
+                )));
+            }
+            Ok(())
+        }
+    }))
+    .await?;
+    Ok(())
+}
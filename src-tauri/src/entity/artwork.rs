@@ -9,6 +9,9 @@ pub struct Model {
     pub art_type: String,
     pub url: Option<String>,
     pub local_path: Option<String>,
+    pub region: Option<String>,
+    pub provider: Option<String>,
+    pub phash: Option<String>,
     pub created_at: String,
 }
 
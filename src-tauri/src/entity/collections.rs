@@ -0,0 +1,31 @@
+use sea_orm::entity::prelude::*;
+
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel)]
+#[sea_orm(table_name = "collections")]
+pub struct Model {
+    #[sea_orm(primary_key)]
+    pub id: i64,
+    pub name: String,
+    pub description: Option<String>,
+    pub source_id: Option<i64>,
+    pub remote_id: Option<String>,
+    pub is_favorite: bool,
+    /// None for collections mirrored from a source, which every profile sees.
+    pub profile_id: Option<i64>,
+    pub created_at: String,
+    pub updated_at: String,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+    #[sea_orm(has_many = "super::collection_roms::Entity")]
+    CollectionRoms,
+}
+
+impl Related<super::collection_roms::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::CollectionRoms.def()
+    }
+}
+
+impl ActiveModelBehavior for ActiveModel {}
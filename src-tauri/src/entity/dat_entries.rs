@@ -13,6 +13,7 @@ pub struct Model {
     pub md5: Option<String>,
     pub sha1: Option<String>,
     pub status: Option<String>,
+    pub merge_name: Option<String>,
 }
 
 #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
@@ -0,0 +1,17 @@
+use sea_orm::entity::prelude::*;
+
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel)]
+#[sea_orm(table_name = "launchbox_alt_names")]
+pub struct Model {
+    #[sea_orm(primary_key)]
+    pub id: i64,
+    pub database_id: String,
+    pub name: String,
+    pub name_normalized: String,
+    pub region: Option<String>,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {}
+
+impl ActiveModelBehavior for ActiveModel {}
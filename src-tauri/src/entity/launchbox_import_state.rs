@@ -0,0 +1,21 @@
+use sea_orm::entity::prelude::*;
+
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel)]
+#[sea_orm(table_name = "launchbox_import_state")]
+pub struct Model {
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub id: i64,
+    pub etag: Option<String>,
+    pub last_modified: Option<String>,
+    pub content_md5: Option<String>,
+    pub status: String,
+    pub games_imported: i64,
+    pub images_imported: i64,
+    pub alt_names_imported: i64,
+    pub updated_at: String,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {}
+
+impl ActiveModelBehavior for ActiveModel {}
@@ -0,0 +1,31 @@
+use sea_orm::entity::prelude::*;
+
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel)]
+#[sea_orm(table_name = "launchbox_matches")]
+pub struct Model {
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub rom_id: i64,
+    pub database_id: String,
+    pub matched_name: String,
+    pub confidence: f64,
+    pub rejected: bool,
+    pub created_at: String,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+    #[sea_orm(
+        belongs_to = "super::roms::Entity",
+        from = "Column::RomId",
+        to = "super::roms::Column::Id"
+    )]
+    Rom,
+}
+
+impl Related<super::roms::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Rom.def()
+    }
+}
+
+impl ActiveModelBehavior for ActiveModel {}
@@ -1,16 +1,35 @@
 use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, EnumIter, DeriveActiveEnum)]
+#[sea_orm(rs_type = "String", db_type = "String(StringLen::None)")]
+#[serde(rename_all = "snake_case")]
+pub enum CompletionStatus {
+    #[sea_orm(string_value = "unplayed")]
+    Unplayed,
+    #[sea_orm(string_value = "playing")]
+    Playing,
+    #[sea_orm(string_value = "beaten")]
+    Beaten,
+    #[sea_orm(string_value = "completed")]
+    Completed,
+    #[sea_orm(string_value = "abandoned")]
+    Abandoned,
+}
 
 #[derive(Clone, Debug, PartialEq, DeriveEntityModel)]
 #[sea_orm(table_name = "library")]
 pub struct Model {
     #[sea_orm(primary_key)]
     pub id: i64,
+    pub profile_id: i64,
     pub rom_id: i64,
     pub source_id: i64,
     pub file_path: Option<String>,
     pub play_count: i32,
     pub last_played_at: Option<String>,
     pub favorite: bool,
+    pub status: Option<CompletionStatus>,
     pub created_at: String,
     pub updated_at: String,
 }
@@ -29,6 +48,12 @@ pub enum Relation {
         to = "super::sources::Column::Id"
     )]
     Source,
+    #[sea_orm(
+        belongs_to = "super::profiles::Entity",
+        from = "Column::ProfileId",
+        to = "super::profiles::Column::Id"
+    )]
+    Profile,
 }
 
 impl Related<super::roms::Entity> for Entity {
@@ -43,4 +68,10 @@ impl Related<super::sources::Entity> for Entity {
     }
 }
 
+impl Related<super::profiles::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Profile.def()
+    }
+}
+
 impl ActiveModelBehavior for ActiveModel {}
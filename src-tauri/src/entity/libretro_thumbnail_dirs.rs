@@ -0,0 +1,17 @@
+use sea_orm::entity::prelude::*;
+
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel)]
+#[sea_orm(table_name = "libretro_thumbnail_dirs")]
+pub struct Model {
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub system: String,
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub category: String,
+    pub entry_count: i64,
+    pub fetched_at: String,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {}
+
+impl ActiveModelBehavior for ActiveModel {}
@@ -22,6 +22,10 @@ pub struct Model {
     #[sea_orm(column_type = "Text")]
     pub themes: JsonVec,
     pub metadata_fetched_at: Option<String>,
+    pub pinned: bool,
+    pub esrb_rating: Option<String>,
+    pub pegi_rating: Option<String>,
+    pub min_age: Option<i64>,
     pub created_at: String,
     pub updated_at: String,
 }
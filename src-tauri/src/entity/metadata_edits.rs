@@ -0,0 +1,30 @@
+use sea_orm::entity::prelude::*;
+
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel)]
+#[sea_orm(table_name = "metadata_edits")]
+pub struct Model {
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub rom_id: i64,
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub field: String,
+    pub value: String,
+    pub edited_at: String,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+    #[sea_orm(
+        belongs_to = "super::roms::Entity",
+        from = "Column::RomId",
+        to = "super::roms::Column::Id"
+    )]
+    Rom,
+}
+
+impl Related<super::roms::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Rom.def()
+    }
+}
+
+impl ActiveModelBehavior for ActiveModel {}
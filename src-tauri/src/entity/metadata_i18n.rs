@@ -0,0 +1,34 @@
+use sea_orm::entity::prelude::*;
+
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel)]
+#[sea_orm(table_name = "metadata_i18n")]
+pub struct Model {
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub rom_id: i64,
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub language: String,
+    pub description: String,
+    pub source_language: Option<String>,
+    pub source_md5: String,
+    pub machine_translated: bool,
+    pub provider: Option<String>,
+    pub created_at: String,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+    #[sea_orm(
+        belongs_to = "super::roms::Entity",
+        from = "Column::RomId",
+        to = "super::roms::Column::Id"
+    )]
+    Rom,
+}
+
+impl Related<super::roms::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Rom.def()
+    }
+}
+
+impl ActiveModelBehavior for ActiveModel {}
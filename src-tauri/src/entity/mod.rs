@@ -1,4 +1,7 @@
+pub mod achievements_cache;
 pub mod artwork;
+pub mod collection_roms;
+pub mod collections;
 pub mod core_mappings;
 pub mod dat_entries;
 pub mod dat_files;
@@ -6,12 +9,25 @@ pub mod downloads;
 pub mod hasheous_cache;
 pub mod igdb_cache;
 pub mod json_vec;
+pub mod launchbox_alt_names;
 pub mod launchbox_games;
 pub mod launchbox_images;
+pub mod launchbox_import_state;
+pub mod launchbox_matches;
 pub mod library;
+pub mod libretro_thumbnail_dirs;
+pub mod libretro_thumbnails;
 pub mod metadata;
+pub mod metadata_edits;
+pub mod metadata_i18n;
 pub mod platforms;
+pub mod profiles;
+pub mod rom_cache_index;
+pub mod rom_homebrew;
+pub mod rom_notes;
+pub mod rom_tags;
 pub mod roms;
 pub mod screenscraper_cache;
 pub mod source_roms;
+pub mod settings;
 pub mod sources;
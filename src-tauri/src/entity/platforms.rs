@@ -1,7 +1,25 @@
 use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
 
 use super::json_vec::JsonVec;
 
+/// What happens to a platform's ROM files around download and launch.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, EnumIter, DeriveActiveEnum)]
+#[sea_orm(rs_type = "String", db_type = "String(StringLen::None)")]
+#[serde(rename_all = "snake_case")]
+pub enum ArchiveHandling {
+    /// Use files as they are.
+    #[sea_orm(string_value = "keep")]
+    Keep,
+    /// Extract zip/7z downloads in the cache, for emulators that can't open
+    /// archives.
+    #[sea_orm(string_value = "extract")]
+    Extract,
+    /// Compress local disc images to CHD (or RVZ for GameCube/Wii).
+    #[sea_orm(string_value = "compress")]
+    Compress,
+}
+
 #[derive(Clone, Debug, PartialEq, DeriveEntityModel)]
 #[sea_orm(table_name = "platforms")]
 pub struct Model {
@@ -16,6 +34,8 @@ pub struct Model {
     pub file_extensions: JsonVec,
     #[sea_orm(column_type = "Text")]
     pub folder_aliases: JsonVec,
+    pub archived: bool,
+    pub archive_handling: ArchiveHandling,
     pub created_at: String,
     pub updated_at: String,
 }
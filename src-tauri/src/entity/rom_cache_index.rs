@@ -0,0 +1,17 @@
+use sea_orm::entity::prelude::*;
+
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel)]
+#[sea_orm(table_name = "rom_cache_index")]
+pub struct Model {
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub hash_md5: String,
+    pub file_name: String,
+    pub size: i64,
+    pub created_at: String,
+    pub last_used_at: String,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {}
+
+impl ActiveModelBehavior for ActiveModel {}
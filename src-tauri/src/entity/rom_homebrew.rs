@@ -0,0 +1,42 @@
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, EnumIter, DeriveActiveEnum)]
+#[sea_orm(rs_type = "String", db_type = "String(StringLen::None)")]
+#[serde(rename_all = "snake_case")]
+pub enum HomebrewKind {
+    #[sea_orm(string_value = "homebrew")]
+    Homebrew,
+    #[sea_orm(string_value = "hack")]
+    Hack,
+}
+
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel)]
+#[sea_orm(table_name = "rom_homebrew")]
+pub struct Model {
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub rom_id: i64,
+    pub kind: HomebrewKind,
+    pub title: Option<String>,
+    pub author: Option<String>,
+    pub base_game: Option<String>,
+    pub created_at: String,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+    #[sea_orm(
+        belongs_to = "super::roms::Entity",
+        from = "Column::RomId",
+        to = "super::roms::Column::Id"
+    )]
+    Rom,
+}
+
+impl Related<super::roms::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Rom.def()
+    }
+}
+
+impl ActiveModelBehavior for ActiveModel {}
@@ -27,13 +27,21 @@ pub struct Model {
     pub hash_crc32: Option<String>,
     pub hash_md5: Option<String>,
     pub hash_sha1: Option<String>,
+    pub headerless_crc32: Option<String>,
+    pub headerless_md5: Option<String>,
+    pub headerless_sha1: Option<String>,
     #[sea_orm(column_type = "Text")]
     pub regions: JsonVec,
     #[sea_orm(column_type = "Text")]
     pub languages: JsonVec,
+    pub revision: Option<String>,
+    #[sea_orm(column_type = "Text")]
+    pub dump_flags: JsonVec,
     pub verification_status: Option<VerificationStatus>,
     pub dat_entry_id: Option<i64>,
     pub dat_game_name: Option<String>,
+    pub verified_file_size: Option<i64>,
+    pub verified_file_mtime: Option<i64>,
     pub created_at: String,
     pub updated_at: String,
 }
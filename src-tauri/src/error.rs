@@ -1,3 +1,6 @@
+use std::time::Duration;
+
+use reqwest::StatusCode;
 use serde::Serialize;
 
 #[derive(Debug, thiserror::Error)]
@@ -23,16 +26,141 @@ pub enum AppError {
     #[error("Source not found: {0}")]
     SourceNotFound(String),
 
+    /// What's missing, e.g. `ROM 12`.
+    #[error("{0} not found")]
+    NotFound(String),
+
+    /// An HTTP error response that doesn't fit a more specific variant.
+    #[error("{service} request failed: {status}")]
+    Http { service: String, status: StatusCode },
+
+    #[error("{provider} is rate limiting requests, try again later")]
+    RateLimited {
+        provider: String,
+        retry_after: Option<Duration>,
+    },
+
+    /// A request refused because offline mode is on, e.g. `Can't reach ROMM`.
+    #[error("{0}; offline mode is on")]
+    Offline(String),
+
+    /// What was cancelled, e.g. `DAT import`.
+    #[error("{0} cancelled")]
+    Cancelled(String),
+
     #[error("{0}")]
     Other(String),
 }
 
+/// What kind of problem an error is, so the UI can offer the right way out:
+/// a retry, a link to the credentials in Settings, or nothing for a cancel.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ErrorCategory {
+    Network,
+    Auth,
+    NotFound,
+    Filesystem,
+    RateLimited,
+    Offline,
+    Cancelled,
+    Database,
+    Other,
+}
+
+fn status_category(status: StatusCode) -> ErrorCategory {
+    match status {
+        StatusCode::UNAUTHORIZED | StatusCode::FORBIDDEN => ErrorCategory::Auth,
+        StatusCode::NOT_FOUND => ErrorCategory::NotFound,
+        StatusCode::TOO_MANY_REQUESTS => ErrorCategory::RateLimited,
+        _ => ErrorCategory::Network,
+    }
+}
+
+impl AppError {
+    /// The error for an unsuccessful response from `service`.
+    pub fn from_status(service: &str, status: StatusCode) -> Self {
+        match status {
+            StatusCode::TOO_MANY_REQUESTS => Self::RateLimited {
+                provider: service.to_string(),
+                retry_after: None,
+            },
+            StatusCode::UNAUTHORIZED | StatusCode::FORBIDDEN => {
+                Self::Auth(format!("{service} refused the credentials ({status})"))
+            }
+            _ => Self::Http {
+                service: service.to_string(),
+                status,
+            },
+        }
+    }
+
+    pub fn category(&self) -> ErrorCategory {
+        match self {
+            Self::Network(e) => e.status().map_or(ErrorCategory::Network, status_category),
+            Self::Http { status, .. } => status_category(*status),
+            Self::Auth(_) => ErrorCategory::Auth,
+            Self::SourceNotFound(_) | Self::NotFound(_) => ErrorCategory::NotFound,
+            Self::Io(_) => ErrorCategory::Filesystem,
+            Self::RateLimited { .. } => ErrorCategory::RateLimited,
+            Self::Offline(_) => ErrorCategory::Offline,
+            Self::Cancelled(_) => ErrorCategory::Cancelled,
+            Self::Database(_) | Self::Migration(_) | Self::Orm(_) => ErrorCategory::Database,
+            Self::Other(_) => ErrorCategory::Other,
+        }
+    }
+
+    /// Whether trying the same thing again later may work.
+    pub fn is_retryable(&self) -> bool {
+        match self {
+            Self::Network(e) => {
+                e.is_timeout() || e.is_connect() || e.status().is_some_and(|s| s.is_server_error())
+            }
+            Self::Http { status, .. } => status.is_server_error(),
+            Self::RateLimited { .. } => true,
+            _ => false,
+        }
+    }
+}
+
+/// How errors reach the frontend: a category and message, plus context for
+/// the kinds that have some.
+#[derive(Serialize)]
+struct ErrorPayload<'a> {
+    category: ErrorCategory,
+    message: String,
+    retryable: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    status: Option<u16>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    provider: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    retry_after_secs: Option<u64>,
+}
+
 impl Serialize for AppError {
     fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
     where
         S: serde::Serializer,
     {
-        serializer.serialize_str(&self.to_string())
+        let (status, provider, retry_after) = match self {
+            Self::Network(e) => (e.status(), None, None),
+            Self::Http { service, status } => (Some(*status), Some(service.as_str()), None),
+            Self::RateLimited {
+                provider,
+                retry_after,
+            } => (None, Some(provider.as_str()), *retry_after),
+            _ => (None, None, None),
+        };
+        ErrorPayload {
+            category: self.category(),
+            message: self.to_string(),
+            retryable: self.is_retryable(),
+            status: status.map(|s| s.as_u16()),
+            provider,
+            retry_after_secs: retry_after.map(|d| d.as_secs()),
+        }
+        .serialize(serializer)
     }
 }
 
@@ -0,0 +1,66 @@
+use tauri::{AppHandle, Emitter};
+
+use crate::models::LibraryRomsEvent;
+
+/// New ROMs were linked into the library (sync, import).
+pub const ROM_ADDED_EVENT: &str = "library://rom-added";
+/// A ROM's metadata changed (enrichment, or a dedup merge into it).
+pub const METADATA_UPDATED_EVENT: &str = "library://metadata-updated";
+/// A ROM's artwork rows changed.
+pub const ARTWORK_UPDATED_EVENT: &str = "library://artwork-updated";
+
+/// Emits per-ROM library events so open views can refresh just the rows
+/// that changed. `LibraryEvents::default()` emits nothing, for callers that
+/// run without a window.
+#[derive(Clone, Default)]
+pub struct LibraryEvents {
+    app: Option<AppHandle>,
+}
+
+impl LibraryEvents {
+    pub fn new(app: &AppHandle) -> Self {
+        Self {
+            app: Some(app.clone()),
+        }
+    }
+
+    pub fn roms_added(&self, source_id: i64, rom_ids: Vec<i64>) {
+        self.emit(
+            ROM_ADDED_EVENT,
+            LibraryRomsEvent {
+                rom_ids,
+                source_id: Some(source_id),
+                merged_rom_ids: Vec::new(),
+            },
+        );
+    }
+
+    pub fn metadata_updated(&self, rom_ids: Vec<i64>) {
+        self.emit(METADATA_UPDATED_EVENT, LibraryRomsEvent::for_roms(rom_ids));
+    }
+
+    pub fn artwork_updated(&self, rom_ids: Vec<i64>) {
+        self.emit(ARTWORK_UPDATED_EVENT, LibraryRomsEvent::for_roms(rom_ids));
+    }
+
+    /// `merged_rom_ids` were deleted after being merged into `keeper_id`,
+    /// which may have picked up their metadata and artwork.
+    pub fn roms_merged(&self, keeper_id: i64, merged_rom_ids: Vec<i64>) {
+        let event = LibraryRomsEvent {
+            rom_ids: vec![keeper_id],
+            source_id: None,
+            merged_rom_ids,
+        };
+        self.emit(METADATA_UPDATED_EVENT, event.clone());
+        self.emit(ARTWORK_UPDATED_EVENT, event);
+    }
+
+    fn emit(&self, event: &str, payload: LibraryRomsEvent) {
+        if payload.rom_ids.is_empty() {
+            return;
+        }
+        if let Some(app) = &self.app {
+            let _ = app.emit(event, payload);
+        }
+    }
+}
@@ -0,0 +1,758 @@
+use std::collections::{BTreeMap, HashMap};
+use std::fmt::Write as _;
+use std::path::{Path, PathBuf};
+
+use futures_util::StreamExt;
+use quick_xml::events::Event;
+use quick_xml::Reader;
+use sea_orm::{
+    ColumnTrait, DatabaseBackend, DatabaseConnection, EntityTrait, FromQueryResult, QueryFilter,
+    Statement,
+};
+use tokio::io::AsyncWriteExt;
+use tokio_util::sync::CancellationToken;
+
+use crate::entity::sources::{self, SourceType};
+use crate::error::{AppError, AppResult};
+use crate::models::{DeviceExportReport, ScanProgress};
+use crate::sources::local_sync::{self, FolderLayout};
+use crate::sources::romm::RommClient;
+
+/// Covers larger than this on either side are downscaled; handheld screens never need more.
+const MAX_COVER_SIZE: u32 = 500;
+
+/// What to copy to the device and how to lay it out.
+pub struct ExportOptions {
+    pub target: PathBuf,
+    pub layout: FolderLayout,
+    /// Platforms to export; empty means every non-archived platform.
+    pub platform_ids: Vec<i64>,
+    pub favorites_only: bool,
+    pub include_artwork: bool,
+    pub include_gamelists: bool,
+    /// ES-DE custom collections of the exported ROMs, for layouts that read them.
+    pub include_collections: bool,
+}
+
+#[derive(Debug, FromQueryResult)]
+struct ExportRow {
+    id: i64,
+    name: String,
+    file_name: String,
+    file_size: Option<i64>,
+    slug: String,
+    description: Option<String>,
+    release_date: Option<String>,
+    developer: Option<String>,
+    publisher: Option<String>,
+    genres: String,
+    cover_url: Option<String>,
+    source_id: i64,
+    source_rom_id: String,
+    source_type: SourceType,
+}
+
+/// One `<game>` in a generated gamelist.
+struct GamelistEntry {
+    file_name: String,
+    name: String,
+    description: Option<String>,
+    release_date: Option<String>,
+    developer: Option<String>,
+    publisher: Option<String>,
+    genres: Vec<String>,
+    /// Cover path relative to the system folder, for OSes that read it from the gamelist.
+    image: Option<String>,
+}
+
+/// Copy (local sources) or download (ROMM sources) the selected ROMs onto a
+/// device folder in `layout`, with covers and gamelists where the OS expects them.
+/// ROMs already on the device with the same size are skipped.
+pub async fn export_to_device(
+    db: &DatabaseConnection,
+    opts: &ExportOptions,
+    on_progress: impl Fn(ScanProgress),
+    cancel: &CancellationToken,
+) -> AppResult<DeviceExportReport> {
+    if !opts.target.is_dir() {
+        return Err(AppError::Other(format!(
+            "Export target not found: {}",
+            opts.target.display()
+        )));
+    }
+
+    let rows = export_rows(db, opts).await?;
+    let roms_root = local_sync::target_roms_root(&opts.target, &opts.layout);
+
+    // Plan destinations first so the free-space check only counts files not yet on the device
+    let mut planned = Vec::with_capacity(rows.len());
+    let mut needed: u64 = 0;
+    for row in rows {
+        let Some(folder) = local_sync::layout_folder_name(&row.slug, &opts.layout) else {
+            continue;
+        };
+        let dest = roms_root.join(&folder).join(&row.file_name);
+        let size = row.file_size.and_then(|s| u64::try_from(s).ok());
+        let present = size.is_some() && dest.metadata().ok().map(|m| m.len()) == size;
+        if !present {
+            needed += size.unwrap_or(0);
+        }
+        let companions = match row.source_type {
+            SourceType::Local => missing_companions(Path::new(&row.source_rom_id), &dest),
+            SourceType::Romm => Vec::new(),
+        };
+        needed += companions.iter().map(|(_, _, size)| size).sum::<u64>();
+        planned.push((row, folder, dest, present, companions));
+    }
+
+    let available = fs4::available_space(&opts.target)?;
+    if needed > available {
+        return Err(AppError::Other(format!(
+            "Not enough free space on device: {} MB needed, {} MB available",
+            needed / 1_000_000,
+            available / 1_000_000,
+        )));
+    }
+
+    let clients: HashMap<i64, RommClient> = sources::Entity::find()
+        .filter(sources::Column::SourceType.eq(SourceType::Romm))
+        .all(db)
+        .await?
+        .into_iter()
+        .map(|s| (s.id, RommClient::from_source(s.url.unwrap_or_default(), &s.credentials)))
+        .collect();
+    let http = crate::http::client_builder()
+        .timeout(std::time::Duration::from_secs(30))
+        .build()
+        .unwrap_or_default();
+
+    let total = planned.len() as u64;
+    let mut report = DeviceExportReport::default();
+    let mut gamelists: BTreeMap<String, (PathBuf, Vec<GamelistEntry>)> = BTreeMap::new();
+    // ROM id -> path on the device, for collections
+    let mut on_device: HashMap<i64, String> = HashMap::new();
+
+    for (i, (row, folder, dest, present, companions)) in planned.into_iter().enumerate() {
+        if cancel.is_cancelled() {
+            report.cancelled = true;
+            break;
+        }
+        on_progress(ScanProgress {
+            source_id: -1,
+            total,
+            current: i as u64,
+            current_item: row.name.clone(),
+        });
+
+        if present && companions.is_empty() {
+            report.skipped += 1;
+        } else {
+            let result = match row.source_type {
+                SourceType::Local => {
+                    copy_local_rom(Path::new(&row.source_rom_id), &dest, present, &companions).await
+                }
+                SourceType::Romm => match clients.get(&row.source_id) {
+                    Some(client) => download_romm(client, &row, &dest, cancel).await,
+                    None => Err(AppError::SourceNotFound(row.source_id.to_string())),
+                },
+            };
+            match result {
+                Ok(bytes) => {
+                    report.copied += 1;
+                    report.bytes_written += bytes;
+                }
+                Err(_) if cancel.is_cancelled() => {
+                    report.cancelled = true;
+                    break;
+                }
+                Err(e) => {
+                    log::warn!("Export of {} failed: {e}", row.file_name);
+                    report.failed.push(row.file_name);
+                    continue;
+                }
+            }
+        }
+        on_device.insert(row.id, format!("{folder}/{}", row.file_name));
+
+        let system_dir = roms_root.join(&folder);
+        let mut image = None;
+        if opts.include_artwork {
+            let target = cover_path(&opts.target, &opts.layout, &system_dir, &folder, &row.file_name);
+            if let (Some(url), Some(path)) = (row.cover_url.as_deref(), target) {
+                let has_cover = path.exists()
+                    || match write_cover(clients.get(&row.source_id), &http, url, &path).await {
+                        Ok(()) => {
+                            report.covers += 1;
+                            true
+                        }
+                        Err(e) => {
+                            log::warn!("Cover for {} not exported: {e}", row.file_name);
+                            false
+                        }
+                    };
+                if has_cover {
+                    image = path
+                        .strip_prefix(&system_dir)
+                        .ok()
+                        .map(|rel| format!("./{}", rel.to_string_lossy().replace('\\', "/")));
+                }
+            }
+        }
+
+        if opts.include_gamelists {
+            gamelists
+                .entry(folder)
+                .or_insert_with(|| (system_dir, Vec::new()))
+                .1
+                .push(GamelistEntry {
+                    name: row.name,
+                    file_name: row.file_name,
+                    description: row.description,
+                    release_date: row.release_date,
+                    developer: row.developer,
+                    publisher: row.publisher,
+                    genres: serde_json::from_str(&row.genres).unwrap_or_default(),
+                    image,
+                });
+        }
+    }
+
+    // Merged into what's on the device, so a cancelled export's games are listed too
+    for (folder, (system_dir, entries)) in &gamelists {
+        if let Some((path, contents)) =
+            render_gamelist(&opts.target, &opts.layout, system_dir, folder, entries)
+        {
+            if let Some(parent) = path.parent() {
+                tokio::fs::create_dir_all(parent).await?;
+            }
+            tokio::fs::write(&path, contents).await?;
+            report.gamelists += 1;
+        }
+    }
+    if !report.cancelled && opts.include_collections {
+        if let Some(dir) = collections_dir(&opts.target, &opts.layout) {
+            report.collections = write_collections(db, &dir, &on_device).await?;
+        }
+    }
+
+    Ok(report)
+}
+
+async fn export_rows(db: &DatabaseConnection, opts: &ExportOptions) -> AppResult<Vec<ExportRow>> {
+    // One source per ROM, preferring a local copy over a download
+    let mut sql = String::from(
+        "SELECT r.id, r.name, r.file_name, r.file_size, p.slug,
+                m.description, m.release_date, m.developer, m.publisher,
+                COALESCE(m.genres, '[]') as genres,
+                (SELECT url FROM artwork WHERE rom_id = r.id AND art_type = 'cover' LIMIT 1) as cover_url,
+                sr.source_id, sr.source_rom_id, s.source_type
+         FROM roms r
+         JOIN platforms p ON p.id = r.platform_id
+         JOIN source_roms sr ON sr.id = (
+             SELECT sr2.id FROM source_roms sr2
+             JOIN sources s2 ON s2.id = sr2.source_id
+             WHERE sr2.rom_id = r.id AND s2.enabled = 1 AND sr2.source_rom_id IS NOT NULL
+             ORDER BY s2.source_type = 'local' DESC, sr2.id
+             LIMIT 1
+         )
+         JOIN sources s ON s.id = sr.source_id
+         LEFT JOIN metadata m ON m.rom_id = r.id",
+    );
+    let mut values: Vec<sea_orm::Value> = Vec::new();
+
+    if opts.platform_ids.is_empty() {
+        sql.push_str(" WHERE p.archived = 0");
+    } else {
+        let placeholders = vec!["?"; opts.platform_ids.len()].join(",");
+        let _ = write!(sql, " WHERE p.id IN ({placeholders})");
+        values.extend(opts.platform_ids.iter().map(|&id| id.into()));
+    }
+    if opts.favorites_only {
+        sql.push_str(" AND EXISTS (SELECT 1 FROM profile_library l WHERE l.rom_id = r.id AND l.favorite = 1)");
+    }
+    sql.push_str(" ORDER BY p.slug, r.name");
+
+    Ok(ExportRow::find_by_statement(Statement::from_sql_and_values(
+        DatabaseBackend::Sqlite,
+        &sql,
+        values,
+    ))
+    .all(db)
+    .await?)
+}
+
+/// Sibling `.part` file written first so an interrupted export never leaves a truncated ROM.
+fn part_path(to: &Path) -> PathBuf {
+    let name = to.file_name().unwrap_or_default().to_string_lossy();
+    to.with_file_name(format!(".{name}.part"))
+}
+
+pub(crate) async fn copy_local(from: &Path, to: &Path) -> AppResult<u64> {
+    if let Some(parent) = to.parent() {
+        tokio::fs::create_dir_all(parent).await?;
+    }
+    let part = part_path(to);
+    let bytes = match tokio::fs::copy(from, &part).await {
+        Ok(bytes) => bytes,
+        Err(e) => {
+            let _ = tokio::fs::remove_file(&part).await;
+            return Err(e.into());
+        }
+    };
+    tokio::fs::rename(&part, to).await?;
+    Ok(bytes)
+}
+
+/// The tracks or discs of a local multi-file ROM at `from` that aren't next
+/// to `to` on the device yet: source, destination and size of each.
+fn missing_companions(from: &Path, to: &Path) -> Vec<(PathBuf, PathBuf, u64)> {
+    let dest_dir = to.parent().unwrap_or(to);
+    local_sync::companion_files(from)
+        .into_iter()
+        .filter_map(|file| {
+            let size = file.metadata().ok()?.len();
+            let dest = dest_dir.join(local_sync::companion_relative_path(from, &file));
+            let present = dest.metadata().is_ok_and(|m| m.len() == size);
+            (!present).then_some((file, dest, size))
+        })
+        .collect()
+}
+
+/// Copy a local ROM's missing companion files, then the ROM itself unless
+/// it's already `present`. The sheet goes last so it's never on the device
+/// without its tracks.
+async fn copy_local_rom(
+    from: &Path,
+    to: &Path,
+    present: bool,
+    companions: &[(PathBuf, PathBuf, u64)],
+) -> AppResult<u64> {
+    let mut bytes = 0;
+    for (file, dest, _) in companions {
+        bytes += copy_local(file, dest).await?;
+    }
+    if !present {
+        bytes += copy_local(from, to).await?;
+    }
+    Ok(bytes)
+}
+
+async fn download_romm(
+    client: &RommClient,
+    row: &ExportRow,
+    to: &Path,
+    cancel: &CancellationToken,
+) -> AppResult<u64> {
+    let romm_id: i64 = row
+        .source_rom_id
+        .parse()
+        .map_err(|_| AppError::Other("Invalid source ROM ID".to_string()))?;
+    let resp = client.download_rom(romm_id, &row.file_name).await?;
+
+    if let Some(parent) = to.parent() {
+        tokio::fs::create_dir_all(parent).await?;
+    }
+    let part = part_path(to);
+    let result = async {
+        let mut file = tokio::fs::File::create(&part).await?;
+        let mut stream = resp.bytes_stream();
+        let mut written: u64 = 0;
+        while let Some(chunk) = stream.next().await {
+            if cancel.is_cancelled() {
+                return Err(AppError::Cancelled("Export".to_string()));
+            }
+            let chunk = chunk?;
+            file.write_all(&chunk).await?;
+            written += chunk.len() as u64;
+        }
+        file.flush().await?;
+        Ok::<u64, AppError>(written)
+    }
+    .await;
+
+    match result {
+        Ok(written) => {
+            tokio::fs::rename(&part, to).await?;
+            Ok(written)
+        }
+        Err(e) => {
+            let _ = tokio::fs::remove_file(&part).await;
+            Err(e)
+        }
+    }
+}
+
+/// ES-DE keeps media and gamelists in an `ES-DE/` folder next to the ROM folder.
+/// For EmuDeck that's next to `Emulation/`, i.e. `~/ES-DE` on a Deck.
+fn es_de_dir(target: &Path) -> PathBuf {
+    target.parent().unwrap_or(target).join("ES-DE")
+}
+
+/// Where ES-DE reads custom collections from, or `None` if `layout` has none.
+fn collections_dir(target: &Path, layout: &FolderLayout) -> Option<PathBuf> {
+    match layout {
+        FolderLayout::EsDe | FolderLayout::EmuDeck => Some(es_de_dir(target).join("collections")),
+        _ => None,
+    }
+}
+
+/// Write an ES-DE custom collection (`custom-<name>.cfg`, one `%ROMPATH%`
+/// path per line) for every collection with ROMs on the device.
+async fn write_collections(
+    db: &DatabaseConnection,
+    dir: &Path,
+    on_device: &HashMap<i64, String>,
+) -> AppResult<u32> {
+    #[derive(Debug, FromQueryResult)]
+    struct MemberRow {
+        name: String,
+        rom_id: i64,
+    }
+
+    let members = MemberRow::find_by_statement(Statement::from_string(
+        DatabaseBackend::Sqlite,
+        "SELECT c.name, cr.rom_id FROM collections c
+         JOIN collection_roms cr ON cr.collection_id = c.id",
+    ))
+    .all(db)
+    .await?;
+
+    let mut collections: BTreeMap<String, Vec<&str>> = BTreeMap::new();
+    for member in &members {
+        if let Some(path) = on_device.get(&member.rom_id) {
+            let name: String = member
+                .name
+                .chars()
+                .map(|c| match c {
+                    '/' | '\\' | ':' | '*' | '?' | '"' | '<' | '>' | '|' => '_',
+                    _ => c,
+                })
+                .collect();
+            collections.entry(name).or_default().push(path);
+        }
+    }
+
+    if collections.is_empty() {
+        return Ok(0);
+    }
+    tokio::fs::create_dir_all(dir).await?;
+    for (name, paths) in &mut collections {
+        paths.sort_unstable();
+        paths.dedup();
+        let mut contents = String::new();
+        for path in paths.iter() {
+            let _ = writeln!(contents, "%ROMPATH%/{path}");
+        }
+        tokio::fs::write(dir.join(format!("custom-{name}.cfg")), contents).await?;
+    }
+    Ok(collections.len() as u32)
+}
+
+/// Where `layout` expects a ROM's cover, or `None` if the OS has no art convention.
+fn cover_path(
+    target: &Path,
+    layout: &FolderLayout,
+    system_dir: &Path,
+    folder: &str,
+    file_name: &str,
+) -> Option<PathBuf> {
+    let stem = Path::new(file_name).file_stem()?.to_string_lossy();
+    match layout {
+        FolderLayout::OnionOs => Some(system_dir.join("Imgs").join(format!("{stem}.png"))),
+        FolderLayout::MinUi => Some(system_dir.join(".res").join(format!("{file_name}.png"))),
+        FolderLayout::Batocera => Some(system_dir.join("images").join(format!("{stem}.png"))),
+        FolderLayout::EsDe => Some(
+            es_de_dir(target)
+                .join("downloaded_media")
+                .join(folder)
+                .join("covers")
+                .join(format!("{stem}.png")),
+        ),
+        // EmuDeck points ES-DE's media folder into its own tools folder
+        FolderLayout::EmuDeck => Some(
+            target
+                .join("tools/downloaded_media")
+                .join(folder)
+                .join("covers")
+                .join(format!("{stem}.png")),
+        ),
+        FolderLayout::MuOs | FolderLayout::Unknown => None,
+    }
+}
+
+/// Download a cover and store it as a PNG, the one format every handheld OS reads.
+pub(crate) async fn write_cover(
+    client: Option<&RommClient>,
+    http: &reqwest::Client,
+    url: &str,
+    path: &Path,
+) -> AppResult<()> {
+    let bytes = match client {
+        Some(client) => client.fetch_image(url).await?,
+        None => {
+            crate::http::ensure_online("the cover's website")?;
+            http.get(url)
+                .send()
+                .await?
+                .error_for_status()?
+                .bytes()
+                .await?
+                .to_vec()
+        }
+    };
+    let path = path.to_path_buf();
+    tokio::task::spawn_blocking(move || -> AppResult<()> {
+        let img = image::load_from_memory(&bytes)
+            .map_err(|e| AppError::Other(format!("Unreadable cover: {e}")))?;
+        let img = if img.width() > MAX_COVER_SIZE || img.height() > MAX_COVER_SIZE {
+            img.thumbnail(MAX_COVER_SIZE, MAX_COVER_SIZE)
+        } else {
+            img
+        };
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        img.save_with_format(&path, image::ImageFormat::Png)
+            .map_err(|e| AppError::Other(format!("Failed to write cover: {e}")))
+    })
+    .await
+    .map_err(|e| AppError::Other(format!("Task join error: {e}")))?
+}
+
+/// Gamelist file and contents for one system folder, or `None` if `layout` has none.
+/// The exported games are merged into the file already on the device, so the
+/// games exported on earlier runs keep their entries.
+fn render_gamelist(
+    target: &Path,
+    layout: &FolderLayout,
+    system_dir: &Path,
+    folder: &str,
+    entries: &[GamelistEntry],
+) -> Option<(PathBuf, String)> {
+    let (path, full) = match layout {
+        FolderLayout::EsDe | FolderLayout::EmuDeck => (
+            es_de_dir(target)
+                .join("gamelists")
+                .join(folder)
+                .join("gamelist.xml"),
+            true,
+        ),
+        FolderLayout::Batocera => (system_dir.join("gamelist.xml"), true),
+        FolderLayout::OnionOs => (system_dir.join("miyoogamelist.xml"), false),
+        // MinUI only reads display names, from a tab-separated map.txt
+        FolderLayout::MinUi => {
+            let path = system_dir.join("map.txt");
+            let existing = std::fs::read_to_string(&path).unwrap_or_default();
+            return Some((path, merge_map_txt(&existing, entries)));
+        }
+        FolderLayout::MuOs | FolderLayout::Unknown => return None,
+    };
+    let existing = std::fs::read_to_string(&path).unwrap_or_default();
+    let games = parse_gamelist(&existing).unwrap_or_else(|| {
+        if !existing.is_empty() {
+            log::warn!("Replacing unreadable gamelist {}", path.display());
+        }
+        Vec::new()
+    });
+    Some((path, gamelist_xml(&games, entries, full)))
+}
+
+/// MinUI's `map.txt` with the exported games' names set, keeping the lines
+/// of every other file.
+fn merge_map_txt(existing: &str, entries: &[GamelistEntry]) -> String {
+    let exported: HashMap<&str, &str> = entries
+        .iter()
+        .map(|e| (e.file_name.as_str(), e.name.as_str()))
+        .collect();
+    let mut map = String::new();
+    for line in existing.lines().filter(|l| !l.trim().is_empty()) {
+        let file_name = line.split('\t').next().unwrap_or(line);
+        if !exported.contains_key(file_name) {
+            let _ = writeln!(map, "{line}");
+        }
+    }
+    for entry in entries {
+        let _ = writeln!(map, "{}\t{}", entry.file_name, entry.name);
+    }
+    map
+}
+
+/// A `<game>` in a gamelist already on the device.
+#[derive(Default)]
+struct ExistingGame {
+    path: Option<String>,
+    /// Tag name and raw XML of each child element.
+    children: Vec<(String, String)>,
+}
+
+/// A top-level element of a gamelist already on the device.
+enum GamelistItem {
+    Game(ExistingGame),
+    /// Anything else (e.g. a `<folder>`), written back as is.
+    Other(String),
+}
+
+/// The elements of an EmulationStation-style gamelist, or `None` if it isn't one.
+fn parse_gamelist(xml: &str) -> Option<Vec<GamelistItem>> {
+    let mut reader = Reader::from_str(xml);
+    let mut items = Vec::new();
+    let mut depth = 0;
+    let mut item_start = 0;
+    let mut game: Option<ExistingGame> = None;
+    let mut child: Option<(String, usize)> = None;
+
+    loop {
+        let start = reader.buffer_position() as usize;
+        let event = reader.read_event().ok()?;
+        let end = reader.buffer_position() as usize;
+        match event {
+            Event::Start(e) => {
+                depth += 1;
+                let name = String::from_utf8_lossy(e.name().as_ref()).into_owned();
+                match depth {
+                    1 if name != "gameList" => return None,
+                    2 => {
+                        item_start = start;
+                        game = (name == "game").then(ExistingGame::default);
+                    }
+                    3 => child = Some((name, start)),
+                    _ => {}
+                }
+            }
+            Event::Empty(e) => match depth {
+                1 => items.push(GamelistItem::Other(xml[start..end].to_string())),
+                2 => {
+                    if let Some(game) = &mut game {
+                        let name = String::from_utf8_lossy(e.name().as_ref()).into_owned();
+                        game.children.push((name, xml[start..end].to_string()));
+                    }
+                }
+                _ => {}
+            },
+            Event::Text(e) if depth == 3 && child.as_ref().is_some_and(|(n, _)| n == "path") => {
+                if let Some(game) = &mut game {
+                    game.path = Some(e.unescape().ok()?.trim().to_string());
+                }
+            }
+            Event::End(_) => {
+                match depth {
+                    2 => items.push(match game.take() {
+                        Some(game) => GamelistItem::Game(game),
+                        None => GamelistItem::Other(xml[item_start..end].to_string()),
+                    }),
+                    3 => {
+                        if let (Some(game), Some((name, child_start))) = (&mut game, child.take()) {
+                            game.children
+                                .push((name, xml[child_start..end].to_string()));
+                        }
+                    }
+                    _ => {}
+                }
+                depth -= 1;
+            }
+            Event::Eof => break,
+            _ => {}
+        }
+    }
+    Some(items)
+}
+
+/// A gamelist path without the leading `./`, for matching entries.
+fn gamelist_path_key(path: &str) -> &str {
+    path.strip_prefix("./").unwrap_or(path)
+}
+
+/// The `<game>` fields written for an exported ROM. `full` adds the metadata
+/// fields that OnionOS's trimmed-down `miyoogamelist.xml` doesn't support.
+fn game_fields(entry: &GamelistEntry, full: bool) -> Vec<(&'static str, String)> {
+    let mut fields = vec![
+        ("path", format!("./{}", entry.file_name)),
+        ("name", entry.name.clone()),
+    ];
+    if let Some(image) = &entry.image {
+        fields.push(("image", image.clone()));
+    }
+    if full {
+        if let Some(desc) = &entry.description {
+            fields.push(("desc", desc.clone()));
+        }
+        if let Some(date) = entry.release_date.as_deref().and_then(es_release_date) {
+            fields.push(("releasedate", date));
+        }
+        if let Some(developer) = &entry.developer {
+            fields.push(("developer", developer.clone()));
+        }
+        if let Some(publisher) = &entry.publisher {
+            fields.push(("publisher", publisher.clone()));
+        }
+        if !entry.genres.is_empty() {
+            fields.push(("genre", entry.genres.join(", ")));
+        }
+    }
+    fields
+}
+
+/// EmulationStation-style `gamelist.xml`: the games already in it, with the
+/// exported fields replaced on those that were exported again (their play
+/// count, favorite flag and other fields stay), then the newly exported games.
+fn gamelist_xml(existing: &[GamelistItem], entries: &[GamelistEntry], full: bool) -> String {
+    fn game(xml: &mut String, fields: &[(&str, String)], kept: &[(String, String)]) {
+        xml.push_str("  <game>\n");
+        for (name, value) in fields {
+            let _ = writeln!(
+                xml,
+                "    <{name}>{}</{name}>",
+                quick_xml::escape::escape(value)
+            );
+        }
+        for (_, raw) in kept {
+            let _ = writeln!(xml, "    {raw}");
+        }
+        xml.push_str("  </game>\n");
+    }
+
+    let mut exported: HashMap<&str, &GamelistEntry> =
+        entries.iter().map(|e| (e.file_name.as_str(), e)).collect();
+    let mut xml = String::from("<?xml version=\"1.0\"?>\n<gameList>\n");
+    for item in existing {
+        match item {
+            GamelistItem::Game(ExistingGame { path, children }) => {
+                let entry = path
+                    .as_deref()
+                    .and_then(|p| exported.remove(gamelist_path_key(p)));
+                match entry {
+                    Some(entry) => {
+                        let fields = game_fields(entry, full);
+                        let kept: Vec<_> = children
+                            .iter()
+                            .filter(|(name, _)| !fields.iter().any(|(f, _)| f == name))
+                            .cloned()
+                            .collect();
+                        game(&mut xml, &fields, &kept);
+                    }
+                    None => game(&mut xml, &[], children),
+                }
+            }
+            GamelistItem::Other(raw) => {
+                let _ = writeln!(xml, "  {raw}");
+            }
+        }
+    }
+    for entry in entries {
+        if exported.contains_key(entry.file_name.as_str()) {
+            game(&mut xml, &game_fields(entry, full), &[]);
+        }
+    }
+    xml.push_str("</gameList>\n");
+    xml
+}
+
+/// `YYYY-MM-DD` (or a bare year) to EmulationStation's `YYYYMMDDT000000`.
+fn es_release_date(date: &str) -> Option<String> {
+    let digits: String = date.chars().filter(char::is_ascii_digit).take(8).collect();
+    match digits.len() {
+        8 => Some(format!("{digits}T000000")),
+        4 => Some(format!("{digits}0101T000000")),
+        _ => None,
+    }
+}
@@ -3,6 +3,12 @@ use std::path::Path;
 
 use md5::{Digest, Md5};
 
+/// Read buffer size for hashing (reused across the whole file).
+const HASH_BUFFER_SIZE: usize = 1024 * 1024;
+
+/// Report hashing progress at most once per this many bytes.
+const PROGRESS_INTERVAL: u64 = 16 * 1024 * 1024;
+
 /// Result of triple-hash computation (for DAT verification).
 pub struct RomHashes {
     pub crc32: String,
@@ -10,50 +16,144 @@ pub struct RomHashes {
     pub sha1: String,
 }
 
-/// Hash a reader into CRC32 + MD5 + SHA1 in a single pass.
-fn hash_reader(reader: &mut impl Read) -> Result<RomHashes, String> {
-    use crc32fast::Hasher as Crc32Hasher;
-    use sha1::Sha1;
+/// Hashes of a dump as stored and with its header stripped.
+pub struct DumpHashes {
+    pub full: RomHashes,
+    /// Set for platforms whose dumps may carry a header; equal to `full`
+    /// when this dump has none.
+    pub headerless: Option<RomHashes>,
+}
+
+/// Bytes read up front to detect a header (the largest is a 512-byte copier header).
+const HEADER_PEEK: u64 = 512;
+
+/// Platforms whose dumps may start with a copier or emulator header that
+/// No-Intro, Hasheous and RetroAchievements leave out of their hashes.
+pub fn may_have_header(platform_slug: &str) -> bool {
+    matches!(
+        platform_slug,
+        "nes" | "fds" | "lynx" | "atari7800" | "snes" | "pce" | "sgfx"
+    )
+}
+
+/// Size of the header at the start of a dump, given its first bytes and total size.
+fn header_size(platform_slug: &str, head: &[u8], size: u64) -> usize {
+    match platform_slug {
+        "nes" if head.starts_with(b"NES\x1a") => 16,
+        "fds" if head.starts_with(b"FDS\x1a") => 16,
+        "lynx" if head.starts_with(b"LYNX\0") => 64,
+        "atari7800" if head.get(1..10) == Some(b"ATARI7800".as_slice()) => 128,
+        "snes" if size % 8192 == 512 => 512,
+        "pce" | "sgfx" if size % 131_072 == 512 => 512,
+        _ => 0,
+    }
+}
 
-    let mut crc_hasher = Crc32Hasher::new();
-    let mut md5_hasher = Md5::new();
-    let mut sha1_hasher = Sha1::new();
+#[derive(Default)]
+struct TripleHasher {
+    crc32: crc32fast::Hasher,
+    md5: Md5,
+    sha1: sha1::Sha1,
+}
 
-    let mut buf = [0u8; 8192];
+impl TripleHasher {
+    fn update(&mut self, data: &[u8]) {
+        self.crc32.update(data);
+        self.md5.update(data);
+        self.sha1.update(data);
+    }
+
+    fn finish(self) -> RomHashes {
+        RomHashes {
+            crc32: format!("{:08X}", self.crc32.finalize()),
+            md5: format!("{:x}", self.md5.finalize()),
+            sha1: format!("{:x}", self.sha1.finalize()),
+        }
+    }
+}
+
+/// Hash a reader into CRC32 + MD5 + SHA1 in a single pass. With a
+/// `platform_slug`, also hashes the data after that platform's header.
+/// `on_progress` receives `(bytes_read, total_bytes)`.
+fn hash_reader(
+    reader: &mut dyn Read,
+    total: u64,
+    platform_slug: Option<&str>,
+    on_progress: &mut dyn FnMut(u64, u64),
+) -> Result<DumpHashes, String> {
+    let mut full = TripleHasher::default();
+    let mut headerless = None;
+    let mut read_total: u64 = 0;
+
+    if let Some(slug) = platform_slug {
+        let mut head = Vec::new();
+        (&mut *reader)
+            .take(HEADER_PEEK)
+            .read_to_end(&mut head)
+            .map_err(|e| e.to_string())?;
+        let skip = header_size(slug, &head, total).min(head.len());
+        full.update(&head);
+        let mut stripped = TripleHasher::default();
+        stripped.update(&head[skip..]);
+        headerless = Some(stripped);
+        read_total = head.len() as u64;
+    }
+
+    let mut buf = vec![0u8; HASH_BUFFER_SIZE];
+    let mut last_report: u64 = 0;
     loop {
         let n = reader.read(&mut buf).map_err(|e| e.to_string())?;
         if n == 0 {
             break;
         }
-        crc_hasher.update(&buf[..n]);
-        md5_hasher.update(&buf[..n]);
-        sha1_hasher.update(&buf[..n]);
+        full.update(&buf[..n]);
+        if let Some(stripped) = headerless.as_mut() {
+            stripped.update(&buf[..n]);
+        }
+
+        read_total += n as u64;
+        if read_total - last_report >= PROGRESS_INTERVAL {
+            on_progress(read_total, total);
+            last_report = read_total;
+        }
     }
+    on_progress(read_total, total);
 
-    Ok(RomHashes {
-        crc32: format!("{:08X}", crc_hasher.finalize()),
-        md5: format!("{:x}", md5_hasher.finalize()),
-        sha1: format!("{:x}", sha1_hasher.finalize()),
+    Ok(DumpHashes {
+        full: full.finish(),
+        headerless: headerless.map(TripleHasher::finish),
     })
 }
 
-/// Open a file (or the first entry inside a zip) and return a boxed reader.
-fn open_rom_reader(path: &Path) -> Result<Box<dyn Read>, String> {
-    let lower = path.to_string_lossy().to_lowercase();
-    if lower.ends_with(".zip") {
-        let file = std::fs::File::open(path).map_err(|e| e.to_string())?;
+/// Open a file (or the first entry inside a zip) and hand a streaming reader
+/// plus its uncompressed size to `f`.
+fn with_rom_reader<T>(
+    path: &Path,
+    f: impl FnOnce(&mut dyn Read, u64) -> Result<T, String>,
+) -> Result<T, String> {
+    with_rom_reader_named(path, &path.to_string_lossy(), f)
+}
+
+/// [`with_rom_reader`] for a file whose real name is `file_name`, such as a
+/// download still under its temporary name.
+fn with_rom_reader_named<T>(
+    path: &Path,
+    file_name: &str,
+    f: impl FnOnce(&mut dyn Read, u64) -> Result<T, String>,
+) -> Result<T, String> {
+    let file = std::fs::File::open(path).map_err(|e| e.to_string())?;
+    if file_name.to_lowercase().ends_with(".zip") {
         let mut archive = zip::ZipArchive::new(file).map_err(|e| e.to_string())?;
         if archive.is_empty() {
             return Err("Empty zip archive".into());
         }
-        // ZipFile borrows the archive, so we read into a buffer
         let mut inner = archive.by_index(0).map_err(|e| e.to_string())?;
-        let mut data = Vec::new();
-        inner.read_to_end(&mut data).map_err(|e| e.to_string())?;
-        Ok(Box::new(std::io::Cursor::new(data)))
+        let size = inner.size();
+        f(&mut inner, size)
     } else {
-        let file = std::fs::File::open(path).map_err(|e| e.to_string())?;
-        Ok(Box::new(file))
+        let size = file.metadata().map(|m| m.len()).unwrap_or(0);
+        let mut file = file;
+        f(&mut file, size)
     }
 }
 
@@ -62,16 +162,397 @@ fn open_rom_reader(path: &Path) -> Result<Box<dyn Read>, String> {
 ///
 /// Must be called from a blocking context (not async).
 pub fn compute_triple_hash(path: &Path) -> Result<RomHashes, String> {
-    let mut reader = open_rom_reader(path)?;
-    hash_reader(&mut reader)
+    with_rom_reader(path, |reader, size| {
+        hash_reader(reader, size, None, &mut |_, _| {}).map(|h| h.full)
+    })
+}
+
+/// Like [`compute_triple_hash`], also hashing the dump without its header on
+/// platforms where [`may_have_header`] holds, and reporting
+/// `(bytes_read, total_bytes)` as the file is read so large disc images can
+/// show per-file progress.
+///
+/// Must be called from a blocking context (not async).
+pub fn compute_dump_hashes_with_progress(
+    path: &Path,
+    platform_slug: &str,
+    mut on_progress: impl FnMut(u64, u64),
+) -> Result<DumpHashes, String> {
+    let slug = may_have_header(platform_slug).then_some(platform_slug);
+    with_rom_reader(path, |reader, size| hash_reader(reader, size, slug, &mut on_progress))
 }
 
 /// Compute only the MD5 hash of a file (extracting from zip if needed).
 ///
 /// Must be called from a blocking context (not async).
 pub fn compute_md5(path: &Path) -> Result<String, String> {
-    let mut reader = open_rom_reader(path)?;
+    compute_md5_as(path, &path.to_string_lossy())
+}
+
+/// [`compute_md5`] for a file saved under another name, hashing it as if it
+/// were called `file_name`.
+///
+/// Must be called from a blocking context (not async).
+pub fn compute_md5_as(path: &Path, file_name: &str) -> Result<String, String> {
+    with_rom_reader_named(path, file_name, |reader, _| {
+        let mut hasher = Md5::new();
+        std::io::copy(reader, &mut hasher).map_err(|e| e.to_string())?;
+        Ok(format!("{:x}", hasher.finalize()))
+    })
+}
+
+// ---------- RetroAchievements hashing ----------
+
+/// Cartridge images above this size are hashed as plain MD5 without header checks.
+const RA_MAX_CART_SIZE: u64 = 64 * 1024 * 1024;
+
+/// Whether RetroAchievements hashes this platform differently from a plain MD5.
+pub fn has_custom_ra_hash(platform_slug: &str) -> bool {
+    matches!(
+        platform_slug,
+        "nes" | "fds" | "snes" | "lynx" | "atari7800" | "pce" | "sgfx" | "n64" | "psx"
+            | "arcade" | "cps1" | "cps2" | "cps3" | "neogeo"
+    )
+}
+
+/// Compute the RetroAchievements hash for a ROM following RAHasher's
+/// per-console rules (header skipping, byte order, disc executables).
+/// Falls back to a plain MD5 of the ROM data for other platforms.
+///
+/// Must be called from a blocking context (not async).
+pub fn compute_ra_hash(path: &Path, platform_slug: &str) -> Result<String, String> {
+    match platform_slug {
+        // Arcade sets are identified by their file name
+        "arcade" | "cps1" | "cps2" | "cps3" | "neogeo" => {
+            let stem = path
+                .file_stem()
+                .map(|s| s.to_string_lossy().into_owned())
+                .ok_or("ROM has no file name")?;
+            Ok(format!("{:x}", Md5::digest(stem.as_bytes())))
+        }
+        "psx" => compute_psx_ra_hash(path),
+        _ => with_rom_reader(path, |reader, size| {
+            if size > RA_MAX_CART_SIZE {
+                let mut hasher = Md5::new();
+                std::io::copy(reader, &mut hasher).map_err(|e| e.to_string())?;
+                return Ok(format!("{:x}", hasher.finalize()));
+            }
+            let mut data = Vec::with_capacity(usize::try_from(size).unwrap_or(0));
+            reader.read_to_end(&mut data).map_err(|e| e.to_string())?;
+            let data = ra_cart_data(platform_slug, &mut data);
+            Ok(format!("{:x}", Md5::digest(data)))
+        }),
+    }
+}
+
+/// Strip copier headers / normalize byte order as RAHasher does for cartridges.
+fn ra_cart_data<'a>(platform_slug: &str, data: &'a mut [u8]) -> &'a [u8] {
+    if platform_slug == "n64" {
+        let magic = data.get(..4).map(|m| [m[0], m[1], m[2], m[3]]);
+        match magic {
+            // .v64: 16-bit byte-swapped
+            Some([0x37, 0x80, 0x40, 0x12]) => {
+                for pair in data.chunks_exact_mut(2) {
+                    pair.swap(0, 1);
+                }
+            }
+            // .n64: 32-bit little-endian
+            Some([0x40, 0x12, 0x37, 0x80]) => {
+                for word in data.chunks_exact_mut(4) {
+                    word.reverse();
+                }
+            }
+            _ => {}
+        }
+        return data;
+    }
+    let skip = header_size(platform_slug, data, data.len() as u64);
+    &data[skip.min(data.len())..]
+}
+
+/// A CD image opened for ISO 9660 reads (raw 2352-byte or cooked 2048-byte sectors).
+struct DiscImage {
+    file: std::fs::File,
+    sector_size: u64,
+    data_offset: u64,
+}
+
+impl DiscImage {
+    fn open(path: &Path) -> Result<Self, String> {
+        use std::io::{Seek, SeekFrom};
+
+        let lower = path.to_string_lossy().to_lowercase();
+        let image_path = if lower.ends_with(".cue") {
+            first_cue_track(path)?
+        } else {
+            path.to_path_buf()
+        };
+
+        let mut file = std::fs::File::open(&image_path).map_err(|e| e.to_string())?;
+        let len = file.metadata().map_err(|e| e.to_string())?.len();
+        let mut header = [0u8; 16];
+        file.read_exact(&mut header).map_err(|e| e.to_string())?;
+        file.seek(SeekFrom::Start(0)).map_err(|e| e.to_string())?;
+
+        const SYNC: [u8; 12] = [0x00, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0x00];
+        let (sector_size, data_offset) = if header[..12] == SYNC && len % 2352 == 0 {
+            (2352, if header[15] == 2 { 24 } else { 16 })
+        } else {
+            (2048, 0)
+        };
+        Ok(Self { file, sector_size, data_offset })
+    }
+
+    /// Read `len` bytes of user data starting at sector `lba`.
+    fn read(&mut self, lba: u64, len: usize) -> Result<Vec<u8>, String> {
+        use std::io::{Seek, SeekFrom};
+
+        let mut out = Vec::with_capacity(len);
+        let mut sector = lba;
+        let mut buf = [0u8; 2048];
+        while out.len() < len {
+            self.file
+                .seek(SeekFrom::Start(sector * self.sector_size + self.data_offset))
+                .map_err(|e| e.to_string())?;
+            let want = (len - out.len()).min(2048);
+            self.file.read_exact(&mut buf[..want]).map_err(|e| e.to_string())?;
+            out.extend_from_slice(&buf[..want]);
+            sector += 1;
+        }
+        Ok(out)
+    }
+
+    /// Locate a file by backslash-separated path, returning `(lba, size)`.
+    fn find_file(&mut self, path: &str) -> Result<Option<(u64, u64)>, String> {
+        let pvd = self.read(16, 2048)?;
+        if &pvd[1..6] != b"CD001" {
+            return Err("Not an ISO 9660 image".into());
+        }
+        let root = &pvd[156..190];
+        let mut dir = (u64::from(le_u32(&root[2..6])), u64::from(le_u32(&root[10..14])));
+
+        let parts: Vec<&str> = path.split('\\').filter(|p| !p.is_empty()).collect();
+        for (i, part) in parts.iter().enumerate() {
+            let data = self.read(dir.0, usize::try_from(dir.1).map_err(|e| e.to_string())?)?;
+            let Some(found) = find_dir_entry(&data, part) else {
+                return Ok(None);
+            };
+            if i + 1 == parts.len() {
+                return Ok(Some(found));
+            }
+            dir = found;
+        }
+        Ok(None)
+    }
+}
+
+fn le_u32(b: &[u8]) -> u32 {
+    u32::from_le_bytes([b[0], b[1], b[2], b[3]])
+}
+
+/// Find a named entry in an ISO 9660 directory extent.
+fn find_dir_entry(data: &[u8], name: &str) -> Option<(u64, u64)> {
+    let mut pos = 0usize;
+    while pos < data.len() {
+        let rec_len = data[pos] as usize;
+        if rec_len == 0 {
+            // Records don't span sectors; skip to the next one
+            pos = (pos / 2048 + 1) * 2048;
+            continue;
+        }
+        let rec = data.get(pos..pos + rec_len)?;
+        let name_len = *rec.get(32)? as usize;
+        let raw_name = String::from_utf8_lossy(rec.get(33..33 + name_len)?);
+        let entry_name = raw_name.split(';').next().unwrap_or_default();
+        if entry_name.eq_ignore_ascii_case(name) {
+            return Some((u64::from(le_u32(&rec[2..6])), u64::from(le_u32(&rec[10..14]))));
+        }
+        pos += rec_len;
+    }
+    None
+}
+
+/// Resolve the first `FILE` entry of a cue sheet relative to the cue's folder.
+fn first_cue_track(cue_path: &Path) -> Result<std::path::PathBuf, String> {
+    let cue = std::fs::read_to_string(cue_path).map_err(|e| e.to_string())?;
+    let file_name = cue
+        .lines()
+        .map(str::trim)
+        .find(|l| l.to_uppercase().starts_with("FILE"))
+        .and_then(|l| l.split('"').nth(1))
+        .ok_or("No FILE entry in cue sheet")?;
+    Ok(cue_path.parent().unwrap_or(Path::new(".")).join(file_name))
+}
+
+/// Boot executable path from SYSTEM.CNF (`PSX.EXE` when the disc has none).
+fn psx_boot_executable(disc: &mut DiscImage) -> Result<String, String> {
+    match disc.find_file("SYSTEM.CNF")? {
+        Some((lba, size)) => {
+            let cnf = disc.read(lba, usize::try_from(size.min(2048)).unwrap_or(2048))?;
+            let cnf = String::from_utf8_lossy(&cnf);
+            cnf.lines()
+                .map(str::trim)
+                .find(|l| l.to_uppercase().starts_with("BOOT"))
+                .and_then(|l| l.split_once("cdrom:").map(|(_, p)| p))
+                .map(|p| p.trim_start_matches('\\').split(';').next().unwrap_or_default().trim().to_string())
+                .filter(|p| !p.is_empty())
+                .ok_or_else(|| "No BOOT entry in SYSTEM.CNF".to_string())
+        }
+        None => Ok("PSX.EXE".to_string()),
+    }
+}
+
+/// PlayStation game serial (e.g. `SLUS-00594`), derived from the boot
+/// executable name `SLUS_005.94`. Emulators like DuckStation key states by it.
+pub fn psx_serial(path: &Path) -> Option<String> {
+    let mut disc = DiscImage::open(path).ok()?;
+    let exe = psx_boot_executable(&mut disc).ok()?;
+    let name = exe.rsplit('\\').next().unwrap_or(&exe);
+    let (prefix, number) = name.split_once('_')?;
+    let number: String = number.chars().filter(char::is_ascii_digit).collect();
+    if prefix.len() != 4 || number.is_empty() {
+        return None;
+    }
+    Some(format!("{}-{number}", prefix.to_uppercase()))
+}
+
+/// GameCube/Wii six-character game ID (e.g. `GALE01`) from the disc header.
+/// Supports plain ISO/GCM, CISO, WBFS and WIA/RVZ containers.
+pub fn gamecube_game_id(path: &Path) -> Option<String> {
+    use std::io::{Seek, SeekFrom};
+
+    let mut file = std::fs::File::open(path).ok()?;
+    let mut magic = [0u8; 16];
+    file.read_exact(&mut magic).ok()?;
+
+    let offset = match &magic[..4] {
+        b"CISO" => 0x8000,
+        // The disc header copy follows the WBFS header sector (size = 1 << byte 8)
+        b"WBFS" => 1u64 << magic[8],
+        // wia_file_head_t is 0x48 bytes; wia_disc_t keeps the disc header at +0x10
+        b"WIA\x01" | b"RVZ\x01" => 0x58,
+        _ => 0,
+    };
+
+    let mut id = [0u8; 6];
+    file.seek(SeekFrom::Start(offset)).ok()?;
+    file.read_exact(&mut id).ok()?;
+    if !id.iter().all(u8::is_ascii_alphanumeric) {
+        return None;
+    }
+    Some(String::from_utf8_lossy(&id).into_owned())
+}
+
+/// PlayStation: MD5 of the boot executable name (from SYSTEM.CNF) followed by
+/// the executable's contents, sized from its PS-X EXE header.
+fn compute_psx_ra_hash(path: &Path) -> Result<String, String> {
+    let mut disc = DiscImage::open(path)?;
+    let exe_name = psx_boot_executable(&mut disc)?;
+
+    let (lba, size) = disc
+        .find_file(&exe_name)?
+        .ok_or_else(|| format!("Boot executable {exe_name} not found on disc"))?;
+
+    let header = disc.read(lba, 32)?;
+    let exe_size = if header.starts_with(b"PS-X EXE") {
+        (u64::from(le_u32(&header[28..32])) + 2048).min(size)
+    } else {
+        size
+    };
+    let exe = disc.read(lba, usize::try_from(exe_size).map_err(|e| e.to_string())?)?;
+
     let mut hasher = Md5::new();
-    std::io::copy(&mut reader, &mut hasher).map_err(|e| e.to_string())?;
+    hasher.update(exe_name.as_bytes());
+    hasher.update(&exe);
     Ok(format!("{:x}", hasher.finalize()))
 }
+
+/// Title and serial read straight from a ROM's internal header.
+pub struct HeaderIdentity {
+    pub title: Option<String>,
+    pub serial: Option<String>,
+}
+
+/// Identify a ROM from its internal header without hashing the whole file.
+/// Zipped ROMs and platforms without a usable header return `None`.
+pub fn header_identity(path: &Path, platform_slug: &str) -> Option<HeaderIdentity> {
+    use std::io::{Seek, SeekFrom};
+
+    let ext = path.extension().and_then(|e| e.to_str()).unwrap_or_default();
+    if ext.eq_ignore_ascii_case("zip") || ext.eq_ignore_ascii_case("7z") {
+        return None;
+    }
+
+    let read_at = |offset: u64, len: usize| -> Option<Vec<u8>> {
+        let mut file = std::fs::File::open(path).ok()?;
+        file.seek(SeekFrom::Start(offset)).ok()?;
+        let mut buf = vec![0u8; len];
+        file.read_exact(&mut buf).ok()?;
+        Some(buf)
+    };
+    // Header strings are space/NUL padded ASCII (Shift-JIS on some carts)
+    let text = |bytes: &[u8]| -> Option<String> {
+        let s: String = bytes
+            .iter()
+            .take_while(|&&b| b != 0)
+            .map(|&b| if b.is_ascii_graphic() || b == b' ' { b as char } else { ' ' })
+            .collect();
+        let s = s.split_whitespace().collect::<Vec<_>>().join(" ");
+        (!s.is_empty()).then_some(s)
+    };
+
+    let identity = match platform_slug {
+        "gb" | "gbc" => {
+            let h = read_at(0x134, 16)?;
+            // CGB flag in the last title byte shortens the title to 15 chars
+            let len = if h[15] & 0x80 != 0 { 15 } else { 16 };
+            HeaderIdentity { title: text(&h[..len]), serial: None }
+        }
+        "gba" => {
+            let h = read_at(0xA0, 16)?;
+            HeaderIdentity { title: text(&h[..12]), serial: text(&h[12..16]) }
+        }
+        "nds" => {
+            let h = read_at(0, 16)?;
+            HeaderIdentity { title: text(&h[..12]), serial: text(&h[12..16]) }
+        }
+        "n64" => {
+            let h = read_at(0, 0x40)?;
+            // Only big-endian (.z64) dumps have a readable header without swapping
+            if h[..4] != [0x80, 0x37, 0x12, 0x40] {
+                return None;
+            }
+            HeaderIdentity { title: text(&h[0x20..0x34]), serial: text(&h[0x3B..0x3F]) }
+        }
+        "genesis" => {
+            let h = read_at(0x100, 0x90)?;
+            if !h.starts_with(b"SEGA") {
+                return None;
+            }
+            HeaderIdentity {
+                title: text(&h[0x50..0x80]).or_else(|| text(&h[0x20..0x50])),
+                serial: text(&h[0x80..0x8E]),
+            }
+        }
+        "snes" => {
+            let size = std::fs::metadata(path).ok()?.len();
+            // Skip a 512-byte copier header, then try LoROM then HiROM
+            let base = if size % 1024 == 512 { 512 } else { 0 };
+            [0x7FC0u64, 0xFFC0]
+                .into_iter()
+                .filter_map(|offset| read_at(base + offset, 32))
+                .find(|h| {
+                    // Checksum and its complement must add up to 0xFFFF
+                    let complement = u16::from_le_bytes([h[28], h[29]]);
+                    let checksum = u16::from_le_bytes([h[30], h[31]]);
+                    checksum.wrapping_add(complement) == 0xFFFF
+                })
+                .map(|h| HeaderIdentity { title: text(&h[..21]), serial: None })?
+        }
+        "psx" => HeaderIdentity { title: None, serial: Some(psx_serial(path)?) },
+        "gamecube" | "wii" => HeaderIdentity { title: None, serial: Some(gamecube_game_id(path)?) },
+        _ => return None,
+    };
+
+    (identity.title.is_some() || identity.serial.is_some()).then_some(identity)
+}
@@ -0,0 +1,373 @@
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, LazyLock, Mutex, PoisonError, RwLock};
+use std::time::{Duration, Instant};
+
+use reqwest::{ClientBuilder, Proxy, RequestBuilder, Response, StatusCode, Url};
+
+use crate::error::{AppError, AppResult};
+use crate::models::{ProxyService, ProxySettings};
+
+/// Retries after the first attempt for 429/5xx responses and dropped connections.
+const MAX_RETRIES: u32 = 3;
+const BASE_BACKOFF: Duration = Duration::from_millis(500);
+/// Upper bound for a server-provided `Retry-After`.
+const MAX_RETRY_AFTER: Duration = Duration::from_secs(60);
+
+/// Metadata services with their own rate limit.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Provider {
+    Igdb,
+    ScreenScraper,
+    Hasheous,
+    Libretro,
+    Translation,
+}
+
+impl Provider {
+    /// Token bucket size and refill rate (requests per second).
+    fn rate(self) -> (f64, f64) {
+        match self {
+            // IGDB allows 4 requests per second
+            Self::Igdb => (4.0, 4.0),
+            // Free ScreenScraper accounts get a single thread
+            Self::ScreenScraper => (1.0, 1.0),
+            Self::Hasheous => (5.0, 5.0),
+            // HEAD checks against raw.githubusercontent.com
+            Self::Libretro => (10.0, 10.0),
+            // DeepL or a (often self-hosted) LibreTranslate instance
+            Self::Translation => (2.0, 2.0),
+        }
+    }
+
+    fn name(self) -> &'static str {
+        match self {
+            Self::Igdb => "IGDB",
+            Self::ScreenScraper => "ScreenScraper",
+            Self::Hasheous => "Hasheous",
+            Self::Libretro => "libretro",
+            Self::Translation => "the translation service",
+        }
+    }
+}
+
+struct TokenBucket {
+    tokens: f64,
+    capacity: f64,
+    per_sec: f64,
+    updated: Instant,
+}
+
+impl TokenBucket {
+    fn new(provider: Provider) -> Self {
+        let (capacity, per_sec) = provider.rate();
+        Self {
+            tokens: capacity,
+            capacity,
+            per_sec,
+            updated: Instant::now(),
+        }
+    }
+
+    /// Take a token, or return how long until one is available.
+    fn try_take(&mut self) -> Option<Duration> {
+        let now = Instant::now();
+        let elapsed = now.duration_since(self.updated).as_secs_f64();
+        self.tokens = (self.tokens + elapsed * self.per_sec).min(self.capacity);
+        self.updated = now;
+        if self.tokens >= 1.0 {
+            self.tokens -= 1.0;
+            None
+        } else {
+            Some(Duration::from_secs_f64((1.0 - self.tokens) / self.per_sec))
+        }
+    }
+}
+
+static BUCKETS: LazyLock<Mutex<HashMap<Provider, TokenBucket>>> =
+    LazyLock::new(|| Mutex::new(HashMap::new()));
+
+/// Wait for the provider's rate limit to allow another request.
+async fn acquire(provider: Provider) {
+    loop {
+        let wait = BUCKETS
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .entry(provider)
+            .or_insert_with(|| TokenBucket::new(provider))
+            .try_take();
+        match wait {
+            Some(wait) => tokio::time::sleep(wait).await,
+            None => return,
+        }
+    }
+}
+
+static OFFLINE: AtomicBool = AtomicBool::new(false);
+
+/// Turn offline mode on or off. Set at startup and when the setting changes.
+pub fn set_offline(offline: bool) {
+    OFFLINE.store(offline, Ordering::Relaxed);
+}
+
+pub fn is_offline() -> bool {
+    OFFLINE.load(Ordering::Relaxed)
+}
+
+/// Fail right away while offline mode is on, instead of waiting for a
+/// request to `service` to time out.
+pub fn ensure_online(service: &str) -> AppResult<()> {
+    if is_offline() {
+        return Err(AppError::Offline(format!("Can't reach {service}")));
+    }
+    Ok(())
+}
+
+static PROXY: LazyLock<RwLock<ProxySettings>> = LazyLock::new(RwLock::default);
+
+/// Use `settings` for clients built from now on. Set at startup and when
+/// the proxy settings are saved.
+pub fn set_proxy(settings: ProxySettings) {
+    *PROXY.write().unwrap_or_else(PoisonError::into_inner) = settings;
+}
+
+/// Check a proxy URL before it's saved.
+pub fn validate_proxy_url(url: &str) -> AppResult<()> {
+    let parsed =
+        Url::parse(url).map_err(|e| AppError::Other(format!("Invalid proxy URL {url}: {e}")))?;
+    match parsed.scheme() {
+        "http" | "https" | "socks5" | "socks5h" => Ok(()),
+        scheme => Err(AppError::Other(format!(
+            "Unsupported proxy type {scheme}; use http, https, socks5 or socks5h"
+        ))),
+    }
+}
+
+/// Which service a request is for, by the host it goes to.
+fn service_for_host(host: &str) -> Option<ProxyService> {
+    let is = |domain: &str| host == domain || host.ends_with(&format!(".{domain}"));
+    if is("igdb.com") || is("twitch.tv") {
+        Some(ProxyService::Igdb)
+    } else if is("screenscraper.fr") {
+        Some(ProxyService::ScreenScraper)
+    } else if is("hasheous.org") {
+        Some(ProxyService::Hasheous)
+    } else if is("launchbox-app.com") {
+        Some(ProxyService::LaunchBox)
+    } else {
+        None
+    }
+}
+
+/// The system proxy for `url` from `HTTPS_PROXY`, `HTTP_PROXY` or
+/// `ALL_PROXY` (either case), unless `NO_PROXY` lists its host. What
+/// reqwest uses on its own once a client has no proxy of ours.
+fn env_proxy(url: &Url) -> Option<Url> {
+    let var = |name: &str| {
+        std::env::var(name)
+            .or_else(|_| std::env::var(name.to_lowercase()))
+            .ok()
+            .filter(|v| !v.trim().is_empty())
+    };
+    let host = url.host_str()?;
+    let bypassed = var("NO_PROXY").is_some_and(|list| {
+        list.split(',').map(str::trim).any(|entry| {
+            let domain = entry.trim_start_matches('*').trim_start_matches('.');
+            entry == "*"
+                || (!domain.is_empty() && (host == domain || host.ends_with(&format!(".{domain}"))))
+        })
+    });
+    if bypassed {
+        return None;
+    }
+    let proxy = match url.scheme() {
+        "https" => var("HTTPS_PROXY"),
+        _ => var("HTTP_PROXY"),
+    }
+    .or_else(|| var("ALL_PROXY"))?;
+    let proxy = proxy.trim();
+    if proxy.contains("://") {
+        Url::parse(proxy).ok()
+    } else {
+        Url::parse(&format!("http://{proxy}")).ok()
+    }
+}
+
+/// The proxy for a request to `url` for `service`: its override, else the
+/// global one, else the system's.
+fn proxy_for(service: Option<ProxyService>, url: &Url) -> Option<Url> {
+    let settings = PROXY.read().unwrap_or_else(PoisonError::into_inner);
+    let proxy = match service.and_then(|s| settings.overrides.get(&s)) {
+        Some(proxy) => proxy.as_str(),
+        None => match settings.url.as_deref() {
+            Some(proxy) => proxy,
+            None => return env_proxy(url),
+        },
+    };
+    if proxy.is_empty() {
+        return None;
+    }
+    Url::parse(proxy).ok()
+}
+
+fn builder(service: Option<ProxyService>) -> ClientBuilder {
+    let builder = reqwest::Client::builder();
+    {
+        let settings = PROXY.read().unwrap_or_else(PoisonError::into_inner);
+        let has_own = settings.url.is_some()
+            || match service {
+                Some(service) => settings.overrides.contains_key(&service),
+                None => !settings.overrides.is_empty(),
+            };
+        // Without a proxy of our own, reqwest uses the system's
+        if !has_own {
+            return builder;
+        }
+    }
+    builder.proxy(Proxy::custom(move |url: &Url| {
+        let service = service.or_else(|| url.host_str().and_then(service_for_host));
+        proxy_for(service, url)
+    }))
+}
+
+/// A client builder for outbound requests, routed through the configured
+/// proxy (or a service's override, picked by the request's host).
+pub fn client_builder() -> ClientBuilder {
+    builder(None)
+}
+
+/// A client builder for requests that all go to `service`, such as a ROMM
+/// server, whose host can't be recognised.
+pub fn client_builder_for(service: ProxyService) -> ClientBuilder {
+    builder(Some(service))
+}
+
+fn is_retryable(status: StatusCode) -> bool {
+    status == StatusCode::TOO_MANY_REQUESTS || status.is_server_error()
+}
+
+/// `Retry-After` in seconds (the HTTP-date form isn't used by these services).
+fn retry_after(resp: &Response) -> Option<Duration> {
+    let secs: u64 = resp
+        .headers()
+        .get(reqwest::header::RETRY_AFTER)?
+        .to_str()
+        .ok()?
+        .trim()
+        .parse()
+        .ok()?;
+    Some(Duration::from_secs(secs).min(MAX_RETRY_AFTER))
+}
+
+/// The error for an unsuccessful response from `service`, with how long to
+/// wait when it's rate limiting.
+pub fn status_error(service: &str, resp: &Response) -> AppError {
+    match AppError::from_status(service, resp.status()) {
+        AppError::RateLimited { provider, .. } => AppError::RateLimited {
+            provider,
+            retry_after: retry_after(resp),
+        },
+        e => e,
+    }
+}
+
+/// Send a request under the provider's rate limit, retrying 429/5xx responses
+/// and connection failures with exponential backoff. Requests with streaming
+/// bodies can't be replayed and get a single attempt.
+pub async fn send(provider: Provider, request: RequestBuilder) -> AppResult<Response> {
+    ensure_online(provider.name())?;
+    let mut attempt = 0;
+    loop {
+        acquire(provider).await;
+        let Some(this_attempt) = request.try_clone() else {
+            return Ok(request.send().await?);
+        };
+        let result = this_attempt.send().await;
+        let delay = match &result {
+            Ok(resp) if is_retryable(resp.status()) => {
+                retry_after(resp).unwrap_or(BASE_BACKOFF * 2u32.pow(attempt))
+            }
+            Err(e) if e.is_timeout() || e.is_connect() => BASE_BACKOFF * 2u32.pow(attempt),
+            _ => return Ok(result?),
+        };
+        if attempt >= MAX_RETRIES {
+            return Ok(result?);
+        }
+        log::debug!("{provider:?} request failed, retrying in {delay:?}");
+        tokio::time::sleep(delay).await;
+        attempt += 1;
+    }
+}
+
+/// Status and body of a finished request.
+#[derive(Debug, Clone)]
+pub struct Fetched {
+    pub status: StatusCode,
+    pub body: String,
+}
+
+type InFlight = Arc<tokio::sync::OnceCell<Result<Fetched, String>>>;
+
+/// Requests currently being sent, keyed by method, URL and body.
+static IN_FLIGHT: LazyLock<Mutex<HashMap<String, InFlight>>> =
+    LazyLock::new(|| Mutex::new(HashMap::new()));
+
+/// Removes an in-flight entry once its owner finishes or is dropped.
+struct InFlightGuard {
+    key: String,
+    cell: InFlight,
+}
+
+impl Drop for InFlightGuard {
+    fn drop(&mut self) {
+        let mut in_flight = IN_FLIGHT.lock().unwrap_or_else(PoisonError::into_inner);
+        if in_flight.get(&self.key).is_some_and(|c| Arc::ptr_eq(c, &self.cell)) {
+            in_flight.remove(&self.key);
+        }
+    }
+}
+
+fn request_key(request: &RequestBuilder) -> Option<String> {
+    let built = request.try_clone()?.build().ok()?;
+    let body = built
+        .body()
+        .and_then(reqwest::Body::as_bytes)
+        .map(String::from_utf8_lossy)
+        .unwrap_or_default();
+    Some(format!("{} {} {body}", built.method(), built.url()))
+}
+
+/// Like [`send`], reading the body as text. Identical requests made while one
+/// is already in flight share its result instead of hitting the API again.
+pub async fn fetch_text(provider: Provider, request: RequestBuilder) -> Result<Fetched, String> {
+    let Some(key) = request_key(&request) else {
+        return fetch_uncoalesced(provider, request).await;
+    };
+
+    let (cell, _guard) = {
+        let mut in_flight = IN_FLIGHT.lock().unwrap_or_else(PoisonError::into_inner);
+        match in_flight.get(&key) {
+            Some(cell) => (cell.clone(), None),
+            None => {
+                let cell = InFlight::default();
+                in_flight.insert(key.clone(), cell.clone());
+                let guard = InFlightGuard {
+                    key,
+                    cell: cell.clone(),
+                };
+                (cell, Some(guard))
+            }
+        }
+    };
+
+    cell.get_or_init(|| fetch_uncoalesced(provider, request))
+        .await
+        .clone()
+}
+
+async fn fetch_uncoalesced(provider: Provider, request: RequestBuilder) -> Result<Fetched, String> {
+    let resp = send(provider, request).await.map_err(|e| e.to_string())?;
+    let status = resp.status();
+    let body = resp.text().await.map_err(|e| e.to_string())?;
+    Ok(Fetched { status, body })
+}
@@ -0,0 +1,263 @@
+use std::collections::BTreeMap;
+use std::sync::{Arc, Mutex, PoisonError};
+use std::time::{Duration, Instant};
+
+use tauri::{AppHandle, Emitter};
+use tokio_util::sync::CancellationToken;
+
+use crate::error::{AppResult, ErrorCategory};
+use crate::models::{Job, JobKind, JobProgress, JobState, ScanProgress};
+
+/// Sent with the job whenever one starts, moves forward or ends.
+pub const JOB_UPDATED_EVENT: &str = "jobs://updated";
+
+/// Finished jobs kept for the activity list.
+const KEEP_FINISHED: usize = 50;
+/// Progress events for a job are sent at most this often.
+const PROGRESS_INTERVAL: Duration = Duration::from_millis(250);
+
+fn now() -> String {
+    chrono::Utc::now()
+        .format("%Y-%m-%dT%H:%M:%S%.3fZ")
+        .to_string()
+}
+
+struct Entry {
+    job: Job,
+    cancel: CancellationToken,
+    last_emit: Instant,
+}
+
+#[derive(Default)]
+struct Registry {
+    next_id: u64,
+    jobs: BTreeMap<u64, Entry>,
+}
+
+/// Every sync, enrichment, import, export and download running in the
+/// background, each with its own cancellation token. Managed Tauri state.
+#[derive(Clone)]
+pub struct JobManager {
+    registry: Arc<Mutex<Registry>>,
+    app: AppHandle,
+}
+
+impl JobManager {
+    pub fn new(app: &AppHandle) -> Self {
+        Self {
+            registry: Arc::default(),
+            app: app.clone(),
+        }
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, Registry> {
+        self.registry.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    fn emit(&self, job: Job) {
+        let _ = self.app.emit(JOB_UPDATED_EVENT, job);
+    }
+
+    /// Register a running job. `target_id` is the source being synced or the
+    /// ROM being downloaded, so those can be cancelled one at a time.
+    pub fn start(
+        &self,
+        kind: JobKind,
+        target_id: Option<i64>,
+        label: impl Into<String>,
+    ) -> JobHandle {
+        let cancel = CancellationToken::new();
+        let job = {
+            let mut registry = self.lock();
+            registry.next_id += 1;
+            let job = Job {
+                id: registry.next_id,
+                kind,
+                target_id,
+                label: label.into(),
+                state: JobState::Running,
+                progress: None,
+                error: None,
+                started_at: now(),
+                finished_at: None,
+            };
+            registry.jobs.insert(
+                job.id,
+                Entry {
+                    job: job.clone(),
+                    cancel: cancel.clone(),
+                    last_emit: Instant::now(),
+                },
+            );
+            job
+        };
+        let id = job.id;
+        self.emit(job);
+        JobHandle {
+            id,
+            cancel,
+            manager: self.clone(),
+            finished: false,
+        }
+    }
+
+    /// Running jobs first, then finished ones, newest first.
+    pub fn list(&self) -> Vec<Job> {
+        let mut jobs: Vec<Job> = self.lock().jobs.values().map(|e| e.job.clone()).collect();
+        jobs.sort_by_key(|j| (j.state != JobState::Running, std::cmp::Reverse(j.id)));
+        jobs
+    }
+
+    /// Cancel a running job. Returns whether there was one with that id.
+    pub fn cancel(&self, id: u64) -> bool {
+        let registry = self.lock();
+        match registry.jobs.get(&id) {
+            Some(entry) if entry.job.state == JobState::Running => {
+                entry.cancel.cancel();
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// Cancel every running job of `kind`, or only the one for `target_id`.
+    pub fn cancel_kind(&self, kind: JobKind, target_id: Option<i64>) {
+        for entry in self.lock().jobs.values() {
+            if entry.job.state == JobState::Running
+                && entry.job.kind == kind
+                && (target_id.is_none() || entry.job.target_id == target_id)
+            {
+                entry.cancel.cancel();
+            }
+        }
+    }
+
+    /// Whether any job of `kind` is still running.
+    pub fn is_running(&self, kind: JobKind) -> bool {
+        self.lock()
+            .jobs
+            .values()
+            .any(|e| e.job.kind == kind && e.job.state == JobState::Running)
+    }
+
+    fn set_progress(&self, id: u64, progress: JobProgress) {
+        let job = {
+            let mut registry = self.lock();
+            let Some(entry) = registry.jobs.get_mut(&id) else {
+                return;
+            };
+            let done = progress.total > 0 && progress.current >= progress.total;
+            entry.job.progress = Some(progress);
+            if !done && entry.last_emit.elapsed() < PROGRESS_INTERVAL {
+                return;
+            }
+            entry.last_emit = Instant::now();
+            entry.job.clone()
+        };
+        self.emit(job);
+    }
+
+    fn end(&self, id: u64, state: JobState, error: Option<String>) {
+        let job = {
+            let mut registry = self.lock();
+            let Some(entry) = registry.jobs.get_mut(&id) else {
+                return;
+            };
+            entry.job.state = state;
+            entry.job.error = error;
+            entry.job.finished_at = Some(now());
+            let job = entry.job.clone();
+
+            let finished: Vec<u64> = registry
+                .jobs
+                .values()
+                .filter(|e| e.job.state != JobState::Running)
+                .map(|e| e.job.id)
+                .collect();
+            for old in finished
+                .iter()
+                .take(finished.len().saturating_sub(KEEP_FINISHED))
+            {
+                registry.jobs.remove(old);
+            }
+            job
+        };
+        self.emit(job);
+    }
+}
+
+/// A running job. Call [`JobHandle::finish`] with the outcome; a handle
+/// dropped without it (an early `?` return) marks the job as failed.
+pub struct JobHandle {
+    id: u64,
+    cancel: CancellationToken,
+    manager: JobManager,
+    finished: bool,
+}
+
+impl JobHandle {
+    /// Cancelled by `cancel_job` or the operation's own cancel command.
+    pub fn token(&self) -> CancellationToken {
+        self.cancel.clone()
+    }
+
+    pub fn progress(&self, current: u64, total: u64, item: impl Into<String>) {
+        self.manager.set_progress(
+            self.id,
+            JobProgress {
+                current,
+                total,
+                item: item.into(),
+            },
+        );
+    }
+
+    /// A progress callback that updates the job and passes each update on
+    /// to `forward` (usually the command's channel).
+    pub fn reporter(
+        &self,
+        forward: impl Fn(ScanProgress) + Send + Sync + 'static,
+    ) -> impl Fn(ScanProgress) + Send + Sync + 'static {
+        let manager = self.manager.clone();
+        let id = self.id;
+        move |p: ScanProgress| {
+            manager.set_progress(
+                id,
+                JobProgress {
+                    current: p.current,
+                    total: p.total,
+                    item: p.current_item.clone(),
+                },
+            );
+            forward(p);
+        }
+    }
+
+    /// Record how the job ended. Errors from a cancelled job count as the
+    /// cancellation, not a failure.
+    pub fn finish<T>(mut self, result: &AppResult<T>) {
+        self.finished = true;
+        let (state, error) = match result {
+            Err(e) if e.category() == ErrorCategory::Cancelled => (JobState::Cancelled, None),
+            Err(_) if self.cancel.is_cancelled() => (JobState::Cancelled, None),
+            Err(e) => (JobState::Failed, Some(e.to_string())),
+            Ok(_) if self.cancel.is_cancelled() => (JobState::Cancelled, None),
+            Ok(_) => (JobState::Completed, None),
+        };
+        self.manager.end(self.id, state, error);
+    }
+}
+
+impl Drop for JobHandle {
+    fn drop(&mut self) {
+        if self.finished {
+            return;
+        }
+        if self.cancel.is_cancelled() {
+            self.manager.end(self.id, JobState::Cancelled, None);
+        } else {
+            let error = "Stopped before finishing".to_string();
+            self.manager.end(self.id, JobState::Failed, Some(error));
+        }
+    }
+}
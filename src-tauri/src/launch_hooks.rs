@@ -0,0 +1,135 @@
+use std::process::Command;
+use std::time::Duration;
+
+use crate::error::{AppError, AppResult};
+use crate::models::LaunchHooks;
+
+const DEFAULT_TIMEOUT_SECS: u64 = 30;
+
+/// Values substituted into hook commands.
+pub struct HookVars<'a> {
+    pub rom: &'a str,
+    pub rom_name: &'a str,
+    pub platform: &'a str,
+    pub emulator: &'a str,
+}
+
+/// A hook command with its placeholders filled in.
+pub struct PreparedHook {
+    command: String,
+    env: [(&'static str, String); 4],
+    timeout: Duration,
+}
+
+/// The pre-launch and post-exit hooks that apply to one launch.
+pub struct ResolvedHooks {
+    pub pre_launch: Option<PreparedHook>,
+    pub post_exit: Option<PreparedHook>,
+}
+
+#[cfg(windows)]
+fn reference(name: &str) -> String {
+    format!("\"%{name}%\"")
+}
+
+#[cfg(not(windows))]
+fn reference(name: &str) -> String {
+    format!("\"${name}\"")
+}
+
+/// Point each placeholder at a quoted environment variable. The values
+/// themselves never reach the command line, so names containing `%`, `$`
+/// or quotes can't be expanded or break out of their argument.
+fn render(template: &str) -> String {
+    template
+        .replace("{rom}", &reference("ROM"))
+        .replace("{rom_name}", &reference("ROM_NAME"))
+        .replace("{platform}", &reference("PLATFORM"))
+        .replace("{emulator}", &reference("EMULATOR"))
+}
+
+/// Pick the hooks for `vars.platform`, falling back to the global ones.
+pub fn resolve(hooks: &LaunchHooks, vars: &HookVars) -> ResolvedHooks {
+    let platform = hooks.platforms.get(vars.platform);
+    let pick = |field: fn(&crate::models::LaunchHook) -> &Option<String>| {
+        platform
+            .and_then(|p| field(p).as_deref())
+            .or_else(|| field(&hooks.global).as_deref())
+            .filter(|c| !c.trim().is_empty())
+    };
+    let timeout = Duration::from_secs(
+        platform
+            .and_then(|p| p.timeout_secs)
+            .or(hooks.global.timeout_secs)
+            .unwrap_or(DEFAULT_TIMEOUT_SECS),
+    );
+    let prepare = |template: &str| PreparedHook {
+        command: render(template),
+        env: [
+            ("ROM", vars.rom.to_string()),
+            ("ROM_NAME", vars.rom_name.to_string()),
+            ("PLATFORM", vars.platform.to_string()),
+            ("EMULATOR", vars.emulator.to_string()),
+        ],
+        timeout,
+    };
+    ResolvedHooks {
+        pre_launch: pick(|h| &h.pre_launch).map(prepare),
+        post_exit: pick(|h| &h.post_exit).map(prepare),
+    }
+}
+
+/// Run a hook through the system shell, killing it once the timeout passes.
+pub async fn run(hook: &PreparedHook) -> AppResult<()> {
+    #[cfg(windows)]
+    let mut cmd = {
+        let mut cmd = tokio::process::Command::new("cmd");
+        cmd.arg("/C").arg(&hook.command);
+        cmd
+    };
+    #[cfg(not(windows))]
+    let mut cmd = {
+        let mut cmd = tokio::process::Command::new("sh");
+        cmd.arg("-c").arg(&hook.command);
+        cmd
+    };
+    cmd.envs(hook.env.iter().map(|(k, v)| (*k, v)));
+    cmd.kill_on_drop(true);
+
+    log::info!("Running launch hook: {}", hook.command);
+    let status = tokio::time::timeout(hook.timeout, cmd.status())
+        .await
+        .map_err(|_| {
+            AppError::Other(format!(
+                "Hook timed out after {}s: {}",
+                hook.timeout.as_secs(),
+                hook.command
+            ))
+        })??;
+    if !status.success() {
+        return Err(AppError::Other(format!(
+            "Hook exited with {status}: {}",
+            hook.command
+        )));
+    }
+    Ok(())
+}
+
+/// Start the emulator and watch it in the background, running the
+/// post-exit hook once the process ends.
+pub fn start(cmd: &mut Command, post_exit: Option<PreparedHook>) -> std::io::Result<()> {
+    let mut child = cmd.spawn()?;
+    tauri::async_runtime::spawn(async move {
+        match tokio::task::spawn_blocking(move || child.wait()).await {
+            Ok(Ok(status)) => log::info!("Emulator exited with: {status}"),
+            Ok(Err(e)) => log::warn!("Failed to wait for emulator: {e}"),
+            Err(e) => log::warn!("Emulator watcher failed: {e}"),
+        }
+        if let Some(hook) = post_exit {
+            if let Err(e) = run(&hook).await {
+                log::warn!("Post-exit hook failed: {e}");
+            }
+        }
+    });
+    Ok(())
+}
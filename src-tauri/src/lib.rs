@@ -1,19 +1,59 @@
+mod activity;
+mod api_server;
+mod backup;
+mod bios;
+mod buildbot;
+mod cheats;
+mod cli;
 mod commands;
+mod convert;
 mod db;
 mod dedup;
+mod downloader;
 pub mod entity;
 mod error;
+mod events;
+mod export;
 mod hash;
+mod http;
+mod jobs;
+mod launch_hooks;
+mod lists;
+mod manuals;
 mod metadata;
+mod mister;
+mod naming;
 mod models;
+mod operation_log;
+mod play_history;
+mod preflight;
+mod profiles;
+mod provider_status;
 pub mod platform_registry;
 mod retroachievements;
+mod retroarch_overrides;
+mod rom_cache;
 mod saves;
+mod search;
+mod secrets;
+mod settings;
 mod sources;
+mod tls;
+mod user_screenshots;
+mod wrapped;
 
 use directories::ProjectDirs;
 use sea_orm::DatabaseConnection;
 use tauri::Manager;
+use tauri_plugin_store::StoreExt;
+
+/// Run a headless maintenance task when the command line asks for one
+/// (`romm-buddy --sync-all --enrich`). Returns the exit code, or `None` to
+/// start the app normally.
+pub fn run_cli() -> Option<i32> {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    cli::run(&args)
+}
 
 /// Run the Tauri application.
 ///
@@ -41,28 +81,50 @@ pub fn run() {
 
     builder
         .setup(|app| {
-            let db_path = if let Some(proj_dirs) =
-                ProjectDirs::from("com", "romm-buddy", "romm-buddy")
-            {
-                let data_dir = proj_dirs.data_dir();
-                std::fs::create_dir_all(data_dir)?;
-                format!("sqlite:{}/romm-buddy.db", data_dir.display())
-            } else {
-                "sqlite:romm-buddy.db".to_string()
-            };
-
-            let db = tauri::async_runtime::block_on(db::create_pool(&db_path))?;
+            let db = tauri::async_runtime::block_on(db::create_pool(&db_url()?))?;
             app.manage(db);
-            app.manage(commands::CancelTokenMap(
-                tokio::sync::Mutex::new(std::collections::HashMap::new()),
-            ));
+            app.manage(jobs::JobManager::new(app.handle()));
+            app.manage(metadata::PriorityLane::default());
+            app.manage(api_server::ApiServer::default());
 
-            // Spawn background cache eviction
+            if let Err(e) = tauri::async_runtime::block_on(settings::load_from_db(app.handle())) {
+                log::warn!("Failed to load settings from the database: {e}");
+            }
+            http::set_proxy(commands::read_proxy_from_store(app.handle()));
+            http::set_offline(commands::read_offline_from_store(app.handle()));
+            if let Err(e) = tauri::async_runtime::block_on(secrets::migrate(app.handle())) {
+                log::warn!("Failed to move credentials to the keychain: {e}");
+            }
+
+            // Spawn background integrity check + backup, cache migration and eviction
             let app_handle = app.handle().clone();
             tauri::async_runtime::spawn(async move {
-                if let Err(e) = evict_stale_cache(&app_handle).await {
+                if let Err(e) = backup::on_startup(&app_handle).await {
+                    log::warn!("Database backup failed: {e}");
+                }
+                let db = app_handle.state::<DatabaseConnection>();
+                if let Err(e) = commands::run_cache_migration(db.inner()).await {
+                    log::warn!("Cache migration failed: {e}");
+                }
+                let days = app_handle
+                    .store("settings.json")
+                    .ok()
+                    .and_then(|store| store.get("cache_eviction_days"))
+                    .and_then(|v| v.as_u64())
+                    .unwrap_or(DEFAULT_CACHE_EVICTION_DAYS);
+                if let Err(e) = evict_stale_cache(db.inner(), days).await {
                     log::warn!("Cache eviction failed: {e}");
                 }
+                if let Some(config) =
+                    commands::read_api_server_from_store(&app_handle).filter(|c| c.enabled)
+                {
+                    let server = app_handle.state::<api_server::ApiServer>();
+                    if let Err(e) =
+                        api_server::start(server.inner(), db.inner().clone(), &config).await
+                    {
+                        log::warn!("API server failed to start: {e}");
+                    }
+                }
             });
 
             Ok(())
@@ -71,114 +133,264 @@ pub fn run() {
             commands::get_platforms,
             commands::get_sources,
             commands::test_romm_connection,
+            commands::get_server_certificate_fingerprint,
             commands::test_local_path,
+            commands::browse_folder,
             commands::add_source,
             commands::update_source,
             commands::get_source_credentials,
+            commands::get_source_settings,
+            commands::set_source_settings,
             commands::remove_source,
             commands::sync_source,
             commands::cancel_sync,
+            commands::get_auto_dedup,
+            commands::set_auto_dedup,
+            commands::organize_local_source,
+            commands::export_to_device,
+            commands::cancel_device_export,
+            commands::get_mister_config,
+            commands::configure_mister,
+            commands::export_to_mister,
+            commands::launch_on_mister,
             commands::get_library_roms,
+            commands::stream_library_roms,
+            commands::search_suggest,
+            commands::get_launch_grid,
+            commands::get_library_page_limits,
+            commands::set_library_page_limits,
             commands::get_platforms_with_counts,
+            commands::set_platform_archived,
+            commands::set_platform_archive_handling,
+            commands::compress_local_roms,
+            commands::convert_rom,
+            commands::cancel_conversion,
+            commands::set_platform_extensions,
             commands::proxy_image,
             commands::get_retroarch_path,
             commands::set_retroarch_path,
+            commands::get_bios_path,
+            commands::set_bios_path,
+            commands::scan_bios,
+            commands::get_bios_readiness,
+            commands::get_core_bios_hints,
             commands::detect_cores,
             commands::get_core_mappings,
             commands::has_core_mapping,
             commands::set_core_mapping,
+            commands::get_platform_setup_status,
+            commands::preflight_launch,
             commands::download_and_launch,
             commands::get_available_cores,
             commands::install_core,
+            commands::install_core_from_file,
+            commands::recommend_cores,
+            commands::setup_recommended_cores,
             commands::get_emulators,
             commands::get_emulator_paths,
             commands::set_emulator_path,
             commands::detect_emulators,
+            commands::get_emulator_arg_templates,
+            commands::set_emulator_arg_template,
+            commands::get_launch_hooks,
+            commands::set_launch_hooks,
+            commands::add_custom_emulator,
+            commands::remove_custom_emulator,
             commands::update_launchbox_db,
+            commands::get_homebrew_index_url,
+            commands::set_homebrew_index_url,
+            commands::update_homebrew_index,
+            commands::clear_provider_cache,
             commands::fetch_metadata,
+            commands::refresh_stale_metadata,
+            commands::get_metadata_stale_days,
+            commands::set_metadata_stale_days,
+            commands::set_metadata_pinned,
             commands::cancel_metadata,
+            commands::prioritize_roms,
             commands::has_launchbox_db,
+            commands::get_launchbox_fuzzy_matches,
+            commands::reject_launchbox_match,
             commands::compute_rom_hash,
             commands::enrich_single_rom,
+            commands::update_rom_metadata,
+            commands::batch_update_rom_metadata,
             commands::get_rom,
+            commands::get_similar_roms,
             commands::get_rom_screenshots,
+            commands::scan_user_screenshots,
+            commands::get_rom_artwork,
             commands::get_ra_credentials,
             commands::set_ra_credentials,
+            commands::get_ra_hardcore_mode,
+            commands::set_ra_hardcore_mode,
             commands::test_ra_connection,
             commands::get_achievements,
             commands::toggle_favorite,
             commands::get_favorites_count,
+            commands::set_rom_status,
+            commands::get_status_counts,
+            commands::set_rom_note,
+            commands::add_rom_tag,
+            commands::remove_rom_tag,
+            commands::set_rom_homebrew,
+            commands::get_rom_homebrew,
+            commands::get_all_tags,
+            commands::push_favorites_to_romm,
+            commands::get_collections,
+            commands::get_collection_roms,
             commands::get_rom_sources,
+            commands::import_retroarch_history,
+            commands::upload_rom_to_romm,
+            commands::get_rom_download_url,
+            commands::download_rom_to,
             commands::deduplicate_roms,
+            commands::cancel_dedup,
             commands::import_dat_file,
+            commands::fetch_latest_dats,
+            commands::cancel_dat_import,
             commands::get_dat_files,
             commands::remove_dat_file,
             commands::detect_dat_platform,
             commands::verify_library,
             commands::cancel_verification,
+            commands::reset_verification,
             commands::get_verification_stats,
+            commands::get_arcade_set_report,
+            commands::fetch_cheats,
+            commands::get_cheats,
+            commands::set_enabled_cheats,
+            commands::attach_manual,
+            commands::get_manual,
+            commands::get_retroarch_game_overrides,
+            commands::set_retroarch_game_overrides,
+            commands::export_verification_report,
+            commands::export_have_miss,
             commands::get_igdb_credentials,
             commands::set_igdb_credentials,
             commands::test_igdb_connection,
             commands::get_ss_credentials,
             commands::set_ss_credentials,
             commands::test_ss_connection,
+            commands::get_provider_status,
+            commands::get_ss_media_preferences,
+            commands::set_ss_media_preferences,
+            commands::cancel_artwork_reselect,
+            commands::get_artwork_chains,
+            commands::set_artwork_chains,
+            commands::get_artwork_dedup,
+            commands::set_artwork_dedup,
+            commands::get_enrichment_options,
+            commands::set_enrichment_options,
+            commands::get_translation_settings,
+            commands::set_translation_settings,
+            commands::get_rom_translation,
+            commands::translate_rom_description,
             commands::get_rom_saves,
+            commands::launch_from_save_state,
             commands::get_save_paths,
             commands::set_save_path,
+            commands::get_cloud_backup,
+            commands::configure_cloud_backup,
+            commands::backup_saves_to_cloud,
+            commands::restore_saves_from_cloud,
+            commands::get_api_server,
+            commands::configure_api_server,
             commands::delete_save_file,
             commands::export_save_file,
             commands::import_save_file,
             commands::read_file_base64,
             commands::get_all_registry_platforms,
+            commands::get_language,
+            commands::set_language,
+            commands::set_platform_name_override,
             commands::get_cache_info,
+            commands::get_operation_history,
+            commands::get_activity_feed,
+            commands::get_yearly_wrapped,
+            commands::list_jobs,
+            commands::cancel_job,
+            commands::get_proxy_settings,
+            commands::set_proxy_settings,
+            commands::get_offline_mode,
+            commands::set_offline_mode,
+            commands::cancel_cache_scan,
             commands::clear_all_cache,
             commands::clear_cache_files,
+            commands::cleanup_storage,
+            commands::migrate_cache_layout,
+            commands::get_corrupt_downloads,
+            commands::clear_corrupt_downloads,
+            commands::cancel_cleanup,
             commands::get_cache_eviction_days,
             commands::set_cache_eviction_days,
+            commands::get_download_segments,
+            commands::set_download_segments,
+            commands::backup_database,
+            commands::check_database_integrity,
+            commands::get_database_backup_count,
+            commands::set_database_backup_count,
+            commands::export_settings,
+            commands::import_settings,
+            commands::export_list,
+            commands::import_list,
+            commands::get_profiles,
+            commands::create_profile,
+            commands::switch_profile,
+            commands::set_profile_content_filter,
+            commands::delete_profile,
+            commands::has_parent_pin,
+            commands::set_parent_pin,
         ])
         .run(tauri::generate_context!())
         .expect("error while running tauri application");
 }
 
-async fn evict_stale_cache(app: &tauri::AppHandle) -> Result<(), Box<dyn std::error::Error>> {
+/// `sqlite:` URL of the library database, creating its folder if needed.
+fn db_url() -> std::io::Result<String> {
+    if let Some(proj_dirs) = ProjectDirs::from("com", "romm-buddy", "romm-buddy") {
+        let data_dir = proj_dirs.data_dir();
+        std::fs::create_dir_all(data_dir)?;
+        Ok(format!("sqlite:{}/romm-buddy.db", data_dir.display()))
+    } else {
+        Ok("sqlite:romm-buddy.db".to_string())
+    }
+}
+
+const DEFAULT_CACHE_EVICTION_DAYS: u64 = 7;
+
+/// Remove cached ROMs last played (or, if never played, downloaded) more
+/// than `days` ago.
+async fn evict_stale_cache(
+    db: &DatabaseConnection,
+    days: u64,
+) -> Result<(), Box<dyn std::error::Error>> {
     use sea_orm::{ConnectionTrait, DatabaseBackend, Statement};
-    use tauri_plugin_store::StoreExt;
 
     let cache_dir = commands::rom_cache_dir();
     if !cache_dir.exists() {
         return Ok(());
     }
 
-    let store = app.store("settings.json")?;
-    let days = store.get("cache_eviction_days")
-        .and_then(|v: serde_json::Value| v.as_u64())
-        .unwrap_or(7);
-
-    let db = app.state::<DatabaseConnection>();
     let cutoff = chrono::Utc::now() - chrono::Duration::days(days as i64);
     let cutoff_str = cutoff.format("%Y-%m-%dT%H:%M:%S").to_string();
 
-    let entries: Vec<_> = std::fs::read_dir(&cache_dir)?
-        .flatten()
-        .filter(|e| e.path().is_file())
-        .collect();
+    let entries = rom_cache::list_entries(&cache_dir);
 
     for entry in entries {
-        let path = entry.path();
-        let file_name = path.file_name().unwrap_or_default().to_string_lossy().to_string();
-
-        if file_name.starts_with('.') && file_name.ends_with(".part") {
-            continue;
-        }
-
-        let last_played: Option<String> = db.inner()
+        let (column, key) = match &entry.hash_md5 {
+            Some(hash) => ("LOWER(r.hash_md5)", hash.clone()),
+            None => ("r.file_name", entry.file_name.clone()),
+        };
+        let last_played: Option<String> = db
             .query_one(Statement::from_sql_and_values(
                 DatabaseBackend::Sqlite,
-                "SELECT MAX(l.last_played_at) as last_played_at
-                 FROM roms r JOIN library l ON l.rom_id = r.id
-                 WHERE r.file_name = ?",
-                [file_name.clone().into()],
+                format!(
+                    "SELECT MAX(l.last_played_at) as last_played_at
+                     FROM roms r JOIN library l ON l.rom_id = r.id
+                     WHERE {column} = ?"
+                ),
+                [key.into()],
             ))
             .await
             .ok()
@@ -189,7 +401,7 @@ async fn evict_stale_cache(app: &tauri::AppHandle) -> Result<(), Box<dyn std::er
         let should_evict = if let Some(ref played) = last_played {
             played < &cutoff_str
         } else {
-            entry.metadata()
+            std::fs::metadata(&entry.path)
                 .and_then(|m| m.modified())
                 .map(|t| {
                     let modified: chrono::DateTime<chrono::Utc> = t.into();
@@ -199,10 +411,11 @@ async fn evict_stale_cache(app: &tauri::AppHandle) -> Result<(), Box<dyn std::er
         };
 
         if should_evict {
-            log::info!("Evicting stale cached ROM: {file_name}");
-            let _ = std::fs::remove_file(&path);
+            log::info!("Evicting stale cached ROM: {}", entry.relative_path);
+            let _ = rom_cache::remove_entry(&entry.path);
         }
     }
+    rom_cache::prune_index(db).await?;
 
     Ok(())
 }
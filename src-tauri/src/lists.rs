@@ -0,0 +1,250 @@
+use std::collections::HashMap;
+use std::fmt::Write;
+use std::path::Path;
+
+use sea_orm::{ConnectionTrait, DatabaseBackend, DatabaseConnection, FromQueryResult, Statement};
+
+use crate::error::{AppError, AppResult};
+use crate::metadata::launchbox::normalize_for_match;
+use crate::models::{ListImportReport, SharedList, SharedListEntry};
+
+const LIST_VERSION: u32 = 1;
+
+/// Which games to put in a shared list.
+pub enum ListSource {
+    Collection(i64),
+    Favorites,
+}
+
+/// Output format of a shared list. Only JSON can be imported again.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ListFormat {
+    Json,
+    Markdown,
+}
+
+#[derive(Debug, FromQueryResult)]
+struct EntryRow {
+    name: String,
+    platform_slug: String,
+    platform_name: String,
+    regions: String,
+    file_name: String,
+    hash_crc32: Option<String>,
+    hash_md5: Option<String>,
+    hash_sha1: Option<String>,
+}
+
+/// Build a shareable list from a collection or the favorites.
+pub async fn build_list(db: &DatabaseConnection, source: ListSource) -> AppResult<SharedList> {
+    let (name, description, filter, values): (String, Option<String>, &str, Vec<sea_orm::Value>) =
+        match source {
+            ListSource::Collection(id) => {
+                let row = db
+                    .query_one(Statement::from_sql_and_values(
+                        DatabaseBackend::Sqlite,
+                        "SELECT name, description FROM collections WHERE id = ?",
+                        [id.into()],
+                    ))
+                    .await?
+                    .ok_or_else(|| AppError::NotFound(format!("Collection {id}")))?;
+                (
+                    row.try_get("", "name")?,
+                    row.try_get("", "description")?,
+                    "r.id IN (SELECT rom_id FROM collection_roms WHERE collection_id = ?)",
+                    vec![id.into()],
+                )
+            }
+            ListSource::Favorites => (
+                "Favorites".to_string(),
+                None,
+                "EXISTS (SELECT 1 FROM profile_library l WHERE l.rom_id = r.id AND l.favorite = 1)",
+                Vec::new(),
+            ),
+        };
+
+    let rows = EntryRow::find_by_statement(Statement::from_sql_and_values(
+        DatabaseBackend::Sqlite,
+        format!(
+            "SELECT r.name, p.slug AS platform_slug, p.name AS platform_name, r.regions,
+                    r.file_name, r.hash_crc32, r.hash_md5, r.hash_sha1
+             FROM roms r
+             JOIN platforms p ON p.id = r.platform_id
+             WHERE {filter}
+             ORDER BY p.name, r.name"
+        ),
+        values,
+    ))
+    .all(db)
+    .await?;
+
+    Ok(SharedList {
+        version: LIST_VERSION,
+        name,
+        description,
+        exported_at: chrono::Utc::now()
+            .format("%Y-%m-%dT%H:%M:%S%.3fZ")
+            .to_string(),
+        entries: rows
+            .into_iter()
+            .map(|r| SharedListEntry {
+                name: r.name,
+                platform_slug: r.platform_slug,
+                platform_name: r.platform_name,
+                regions: serde_json::from_str(&r.regions).unwrap_or_default(),
+                file_name: r.file_name,
+                crc32: r.hash_crc32,
+                md5: r.hash_md5,
+                sha1: r.hash_sha1,
+            })
+            .collect(),
+    })
+}
+
+/// Render a list in `format`.
+pub fn render_list(list: &SharedList, format: ListFormat) -> AppResult<String> {
+    match format {
+        ListFormat::Json => {
+            serde_json::to_string_pretty(list).map_err(|e| AppError::Other(e.to_string()))
+        }
+        ListFormat::Markdown => Ok(render_markdown(list)),
+    }
+}
+
+fn render_markdown(list: &SharedList) -> String {
+    let cell = |s: &str| s.replace('|', "\\|");
+    let mut out = format!("# {}\n\n", list.name);
+    if let Some(description) = list.description.as_deref().filter(|d| !d.is_empty()) {
+        let _ = write!(out, "{description}\n\n");
+    }
+    out.push_str("| Game | Platform | Region | MD5 |\n|---|---|---|---|\n");
+    for entry in &list.entries {
+        let _ = writeln!(
+            out,
+            "| {} | {} | {} | {} |",
+            cell(&entry.name),
+            cell(&entry.platform_name),
+            entry.regions.join(", "),
+            entry
+                .md5
+                .as_deref()
+                .map(|h| format!("`{h}`"))
+                .unwrap_or_default(),
+        );
+    }
+    out
+}
+
+/// Import a JSON list as a new local collection, matching each entry against
+/// this library by SHA1, MD5, CRC32, then normalized name on the same platform.
+pub async fn import_list(db: &DatabaseConnection, path: &Path) -> AppResult<ListImportReport> {
+    let contents = tokio::fs::read_to_string(path).await?;
+    let list: SharedList = serde_json::from_str(&contents)
+        .map_err(|e| AppError::Other(format!("Not a shared list: {e}")))?;
+    if list.version > LIST_VERSION {
+        return Err(AppError::Other(format!(
+            "List version {} is newer than this app supports",
+            list.version
+        )));
+    }
+
+    let collection_id = db
+        .execute(Statement::from_sql_and_values(
+            DatabaseBackend::Sqlite,
+            "INSERT INTO collections (name, description, profile_id)
+             VALUES (?, ?, (SELECT id FROM current_profile))",
+            [list.name.clone().into(), list.description.clone().into()],
+        ))
+        .await?
+        .last_insert_id();
+    let collection_id = i64::try_from(collection_id)
+        .map_err(|_| AppError::Other("Invalid collection id".to_string()))?;
+
+    let mut names_by_platform: HashMap<String, HashMap<String, i64>> = HashMap::new();
+    let mut matched = 0;
+    let mut missing = Vec::new();
+    let total = list.entries.len();
+
+    for entry in list.entries {
+        let rom_id = match match_by_hash(db, &entry).await? {
+            Some(id) => Some(id),
+            None => {
+                if !names_by_platform.contains_key(&entry.platform_slug) {
+                    let names = platform_names(db, &entry.platform_slug).await?;
+                    names_by_platform.insert(entry.platform_slug.clone(), names);
+                }
+                names_by_platform[&entry.platform_slug]
+                    .get(&normalize_for_match(&entry.name))
+                    .copied()
+            }
+        };
+        let Some(rom_id) = rom_id else {
+            missing.push(entry);
+            continue;
+        };
+        db.execute(Statement::from_sql_and_values(
+            DatabaseBackend::Sqlite,
+            "INSERT OR IGNORE INTO collection_roms (collection_id, rom_id) VALUES (?, ?)",
+            [collection_id.into(), rom_id.into()],
+        ))
+        .await?;
+        matched += 1;
+    }
+    if let Some(change) = crate::activity::describe_change(matched, 0) {
+        crate::activity::collection_updated(db, collection_id, &change).await;
+    }
+
+    Ok(ListImportReport {
+        collection_id,
+        name: list.name,
+        total,
+        matched,
+        missing,
+    })
+}
+
+async fn match_by_hash(db: &DatabaseConnection, entry: &SharedListEntry) -> AppResult<Option<i64>> {
+    let candidates = [
+        ("hash_sha1", entry.sha1.as_deref().map(str::to_lowercase)),
+        ("hash_md5", entry.md5.as_deref().map(str::to_lowercase)),
+        ("hash_crc32", entry.crc32.as_deref().map(str::to_uppercase)),
+    ];
+    for (column, value) in candidates {
+        let Some(value) = value.filter(|v| !v.is_empty()) else {
+            continue;
+        };
+        if let Some(row) = db
+            .query_one(Statement::from_sql_and_values(
+                DatabaseBackend::Sqlite,
+                format!("SELECT id FROM roms WHERE {column} = ? LIMIT 1"),
+                [value.into()],
+            ))
+            .await?
+        {
+            return Ok(Some(row.try_get("", "id")?));
+        }
+    }
+    Ok(None)
+}
+
+/// Normalized name -> ROM id for every ROM on a platform.
+async fn platform_names(db: &DatabaseConnection, slug: &str) -> AppResult<HashMap<String, i64>> {
+    let rows = db
+        .query_all(Statement::from_sql_and_values(
+            DatabaseBackend::Sqlite,
+            "SELECT r.id, r.name FROM roms r
+             JOIN platforms p ON p.id = r.platform_id
+             WHERE p.slug = ?
+             ORDER BY r.id",
+            [slug.into()],
+        ))
+        .await?;
+    let mut names = HashMap::new();
+    for row in rows {
+        let id: i64 = row.try_get("", "id")?;
+        let name: String = row.try_get("", "name")?;
+        names.entry(normalize_for_match(&name)).or_insert(id);
+    }
+    Ok(names)
+}
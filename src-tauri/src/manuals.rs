@@ -0,0 +1,130 @@
+use std::path::{Path, PathBuf};
+
+use sea_orm::{ConnectionTrait, DatabaseBackend, DatabaseConnection, Statement};
+
+use crate::error::{AppError, AppResult};
+use crate::http::{self, Provider};
+
+const PDF_MAGIC: &[u8] = b"%PDF-";
+
+/// Where manuals are cached, one `<rom id>.pdf` per ROM.
+pub fn manuals_dir() -> PathBuf {
+    directories::ProjectDirs::from("com", "romm-buddy", "romm-buddy").map_or_else(
+        || PathBuf::from("manuals"),
+        |p| p.cache_dir().join("manuals"),
+    )
+}
+
+/// Write a manual into the cache and record it. Anything that isn't a PDF
+/// is refused, so an error page never ends up as the manual.
+async fn store(
+    db: &DatabaseConnection,
+    rom_id: i64,
+    source: &str,
+    source_url: Option<&str>,
+    bytes: &[u8],
+) -> AppResult<String> {
+    if !bytes.starts_with(PDF_MAGIC) {
+        return Err(AppError::Other("The manual is not a PDF".to_string()));
+    }
+
+    let dir = manuals_dir();
+    tokio::fs::create_dir_all(&dir).await?;
+    let path = dir.join(format!("{rom_id}.pdf"));
+    let part = dir.join(format!("{rom_id}.pdf.part"));
+    tokio::fs::write(&part, bytes).await?;
+    tokio::fs::rename(&part, &path).await?;
+
+    let path = path.to_string_lossy().into_owned();
+    db.execute(Statement::from_sql_and_values(
+        DatabaseBackend::Sqlite,
+        "INSERT INTO manuals (rom_id, source, source_url, path, size_bytes) VALUES (?, ?, ?, ?, ?)
+         ON CONFLICT(rom_id) DO UPDATE SET
+           source = excluded.source,
+           source_url = excluded.source_url,
+           path = excluded.path,
+           size_bytes = excluded.size_bytes,
+           fetched_at = strftime('%Y-%m-%dT%H:%M:%fZ', 'now')",
+        [
+            rom_id.into(),
+            source.into(),
+            source_url.map(str::to_string).into(),
+            path.clone().into(),
+            (bytes.len() as i64).into(),
+        ],
+    ))
+    .await?;
+    Ok(path)
+}
+
+async fn download(
+    provider: Option<Provider>,
+    request: reqwest::RequestBuilder,
+) -> AppResult<Vec<u8>> {
+    let resp = match provider {
+        Some(provider) => http::send(provider, request).await?,
+        None => {
+            http::ensure_online("the manual's website")?;
+            request.send().await?
+        }
+    };
+    let status = resp.status();
+    if !status.is_success() {
+        return Err(AppError::Other(format!("Manual download failed: {status}")));
+    }
+    Ok(resp.bytes().await?.to_vec())
+}
+
+/// Attach a manual to a ROM from a local PDF or an http(s) URL, replacing
+/// any manual it had. Returns the cached file's path.
+pub async fn attach(
+    db: &DatabaseConnection,
+    client: &reqwest::Client,
+    rom_id: i64,
+    path_or_url: &str,
+) -> AppResult<String> {
+    let path_or_url = path_or_url.trim();
+    if path_or_url.starts_with("http://") || path_or_url.starts_with("https://") {
+        let bytes = download(None, client.get(path_or_url)).await?;
+        store(db, rom_id, "url", Some(path_or_url), &bytes).await
+    } else {
+        let path = Path::new(path_or_url);
+        if !path.is_file() {
+            return Err(AppError::NotFound(format!("File {path_or_url}")));
+        }
+        let bytes = tokio::fs::read(path).await?;
+        store(db, rom_id, "file", None, &bytes).await
+    }
+}
+
+/// Download a ScreenScraper manual for a ROM that doesn't have one yet.
+/// Returns whether a manual was stored.
+pub async fn fetch_from_screenscraper(
+    db: &DatabaseConnection,
+    client: &reqwest::Client,
+    rom_id: i64,
+    url: &str,
+) -> AppResult<bool> {
+    if get(db, rom_id).await?.is_some() {
+        return Ok(false);
+    }
+    let bytes = download(Some(Provider::ScreenScraper), client.get(url)).await?;
+    store(db, rom_id, "screenscraper", Some(url), &bytes).await?;
+    Ok(true)
+}
+
+/// Path of a ROM's cached manual, if it has one and the file is still there.
+pub async fn get(db: &DatabaseConnection, rom_id: i64) -> AppResult<Option<String>> {
+    let row = db
+        .query_one(Statement::from_sql_and_values(
+            DatabaseBackend::Sqlite,
+            "SELECT path FROM manuals WHERE rom_id = ?",
+            [rom_id.into()],
+        ))
+        .await?;
+    let Some(row) = row else {
+        return Ok(None);
+    };
+    let path: String = row.try_get("", "path")?;
+    Ok(Path::new(&path).is_file().then_some(path))
+}
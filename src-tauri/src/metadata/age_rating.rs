@@ -0,0 +1,146 @@
+use sea_orm::{ConnectionTrait, DatabaseBackend, DatabaseConnection, Statement};
+
+use crate::error::AppResult;
+
+/// ESRB and PEGI ratings of a game, by label (`E10+`, `12`).
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct AgeRatings {
+    pub esrb: Option<String>,
+    pub pegi: Option<String>,
+}
+
+fn esrb_age(label: &str) -> Option<i64> {
+    match label.trim().to_uppercase().as_str() {
+        "EC" => Some(3),
+        "E" => Some(6),
+        "E10" | "E10+" => Some(10),
+        "T" => Some(13),
+        "M" => Some(17),
+        "AO" => Some(18),
+        _ => None,
+    }
+}
+
+fn pegi_age(label: &str) -> Option<i64> {
+    let age: i64 = label
+        .trim()
+        .trim_start_matches("PEGI")
+        .trim()
+        .parse()
+        .ok()?;
+    [3, 7, 12, 16, 18].contains(&age).then_some(age)
+}
+
+impl AgeRatings {
+    /// From IGDB's `age_ratings` (category 1 = ESRB, 2 = PEGI).
+    pub fn from_igdb(ratings: &[super::igdb::IgdbAgeRating]) -> Self {
+        let mut out = Self::default();
+        for r in ratings {
+            let label = match r.rating {
+                Some(1) => "3",
+                Some(2) => "7",
+                Some(3) => "12",
+                Some(4) => "16",
+                Some(5) => "18",
+                Some(7) => "EC",
+                Some(8) => "E",
+                Some(9) => "E10+",
+                Some(10) => "T",
+                Some(11) => "M",
+                Some(12) => "AO",
+                _ => continue,
+            };
+            match r.category {
+                Some(1) if esrb_age(label).is_some() => out.esrb = Some(label.to_string()),
+                Some(2) if pegi_age(label).is_some() => out.pegi = Some(label.to_string()),
+                _ => {}
+            }
+        }
+        out
+    }
+
+    /// From ScreenScraper's `classifications` (`[{"type": "PEGI", "text": "12"}]`).
+    pub fn from_screenscraper(classifications: &serde_json::Value) -> Self {
+        let mut out = Self::default();
+        for c in classifications.as_array().into_iter().flatten() {
+            let Some(text) = c.get("text").and_then(|t| t.as_str()) else {
+                continue;
+            };
+            match c.get("type").and_then(|t| t.as_str()) {
+                Some("ESRB") if esrb_age(text).is_some() => {
+                    out.esrb = Some(text.trim().to_uppercase());
+                }
+                Some("PEGI") if pegi_age(text).is_some() => {
+                    out.pegi = pegi_age(text).map(|a| a.to_string());
+                }
+                _ => {}
+            }
+        }
+        out
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.esrb.is_none() && self.pegi.is_none()
+    }
+
+    /// Youngest age the stricter rating allows; None when unrated.
+    pub fn min_age(&self) -> Option<i64> {
+        let esrb = self.esrb.as_deref().and_then(esrb_age);
+        let pegi = self.pegi.as_deref().and_then(pegi_age);
+        esrb.max(pegi)
+    }
+}
+
+/// Merge `ratings` into a ROM's metadata row and recompute `min_age`. With
+/// `overwrite` the new ratings replace stored ones, else they only fill gaps.
+pub async fn store(
+    db: &DatabaseConnection,
+    rom_id: i64,
+    ratings: &AgeRatings,
+    overwrite: bool,
+) -> AppResult<()> {
+    if ratings.is_empty() {
+        return Ok(());
+    }
+    let Some(row) = db
+        .query_one(Statement::from_sql_and_values(
+            DatabaseBackend::Sqlite,
+            "SELECT esrb_rating, pegi_rating FROM metadata WHERE rom_id = ?",
+            [rom_id.into()],
+        ))
+        .await?
+    else {
+        return Ok(());
+    };
+    let stored = AgeRatings {
+        esrb: row.try_get("", "esrb_rating")?,
+        pegi: row.try_get("", "pegi_rating")?,
+    };
+    let (first, second) = if overwrite {
+        (ratings, &stored)
+    } else {
+        (&stored, ratings)
+    };
+    let merged = AgeRatings {
+        esrb: first.esrb.clone().or_else(|| second.esrb.clone()),
+        pegi: first.pegi.clone().or_else(|| second.pegi.clone()),
+    };
+    if merged == stored {
+        return Ok(());
+    }
+
+    db.execute(Statement::from_sql_and_values(
+        DatabaseBackend::Sqlite,
+        "UPDATE metadata SET esrb_rating = ?, pegi_rating = ?, min_age = ?,
+           updated_at = strftime('%Y-%m-%dT%H:%M:%fZ', 'now')
+         WHERE rom_id = ?",
+        [
+            merged.esrb.clone().into(),
+            merged.pegi.clone().into(),
+            merged.min_age().into(),
+            rom_id.into(),
+        ],
+    ))
+    .await?;
+    Ok(())
+}
@@ -1,19 +1,33 @@
-use std::path::Path;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
 
 use quick_xml::events::Event;
 use quick_xml::reader::Reader;
+use std::collections::{HashMap, HashSet};
+
+use futures_util::stream::{self, StreamExt};
 use sea_orm::{
     ActiveModelTrait, ActiveValue::Set, ColumnTrait, ConnectionTrait, DatabaseBackend,
-    DatabaseConnection, EntityTrait, FromQueryResult, QueryFilter, Statement,
+    DatabaseConnection, EntityTrait, FromQueryResult, QueryFilter, Statement, TransactionTrait,
 };
 use tokio_util::sync::CancellationToken;
 
 use crate::entity::{dat_entries, dat_files};
 use crate::error::{AppError, AppResult};
 use crate::hash;
+use crate::http::{self, Provider};
 use crate::models::ScanProgress;
 use crate::platform_registry;
 
+/// ROMs per verification batch; each batch is hashed concurrently and written in one transaction.
+const VERIFY_CHUNK_SIZE: usize = 64;
+
+/// Files at least this large report per-file hashing progress.
+const LARGE_FILE_PROGRESS_THRESHOLD: u64 = 256 * 1024 * 1024;
+
+/// Upper bound on concurrent hashing workers (disk throughput saturates well before CPU).
+const MAX_HASH_WORKERS: usize = 8;
+
 /// Parsed DAT header info.
 pub struct DatHeader {
     pub name: String,
@@ -30,6 +44,8 @@ pub struct DatEntry {
     pub md5: Option<String>,
     pub sha1: Option<String>,
     pub status: Option<String>,
+    /// Arcade DATs: the parent's ROM this one is shared with.
+    pub merge_name: Option<String>,
 }
 
 /// Result of parsing a DAT file.
@@ -60,14 +76,73 @@ pub struct VerificationStats {
     pub not_checked: i64,
 }
 
+/// How an arcade zip compares with its DAT set (returned to frontend).
+#[derive(Debug, serde::Serialize)]
+pub struct ArcadeSetReport {
+    pub rom_id: i64,
+    pub set_name: String,
+    pub dat_name: String,
+    /// ROMs of the set not found in the zip.
+    pub missing: Vec<String>,
+    /// Files in the zip that aren't part of the set.
+    pub extra: Vec<String>,
+    pub checked_at: String,
+}
+
+/// Platforms whose games are multi-file MAME/FBNeo sets, verified by set name.
+const ARCADE_PLATFORMS: &[&str] = &["arcade", "cps1", "cps2", "cps3", "neogeo"];
+
+/// libretro-database, which mirrors the No-Intro and Redump DATs daily.
+pub const DEFAULT_DAT_MIRROR: &str =
+    "https://raw.githubusercontent.com/libretro/libretro-database/master";
+
+/// Folders under a mirror's `metadat/` tried for each platform, by DAT type.
+const DAT_GROUPS: &[&str] = &["no-intro", "redump"];
+
+/// Outcome of fetching one platform's DATs.
+#[derive(Debug, Clone, Copy, serde::Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DatFetchStatus {
+    Imported,
+    Unchanged,
+    NotFound,
+    Failed,
+}
+
+/// Result of fetching one DAT (returned to frontend).
+#[derive(Debug, serde::Serialize)]
+pub struct DatFetchResult {
+    pub platform_slug: String,
+    pub status: DatFetchStatus,
+    pub dat_type: Option<String>,
+    pub version: Option<String>,
+    pub message: Option<String>,
+}
+
 /// Auto-detect platform slug from DAT header name.
 pub fn detect_platform_slug(dat_name: &str) -> Option<String> {
     platform_registry::resolve_dat_name(dat_name).map(|s| s.to_string())
 }
 
-/// Parse a Logiqx XML DAT file, returning header + entries.
+/// Whether a DAT file is Logiqx XML rather than clrmamepro text.
+fn is_xml_dat(file: &mut std::fs::File) -> std::io::Result<bool> {
+    use std::io::{Read, Seek, SeekFrom};
+
+    let mut head = [0u8; 512];
+    let n = file.read(&mut head)?;
+    file.seek(SeekFrom::Start(0))?;
+    Ok(String::from_utf8_lossy(&head[..n])
+        .trim_start_matches('\u{feff}')
+        .trim_start()
+        .starts_with('<'))
+}
+
+/// Parse a Logiqx XML or clrmamepro DAT file, returning header + entries.
 pub fn parse_dat_file(path: &Path) -> AppResult<ParsedDat> {
-    let file = std::fs::File::open(path)?;
+    let mut file = std::fs::File::open(path)?;
+    if !is_xml_dat(&mut file)? {
+        return Ok(parse_clrmamepro(&String::from_utf8_lossy(&std::fs::read(path)?)));
+    }
     let reader_buf = std::io::BufReader::with_capacity(64 * 1024, file);
     let mut reader = Reader::from_reader(reader_buf);
     reader.config_mut().trim_text(true);
@@ -120,6 +195,7 @@ pub fn parse_dat_file(path: &Path) -> AppResult<ParsedDat> {
                         md5: None,
                         sha1: None,
                         status: None,
+                        merge_name: None,
                     };
                     for attr in e.attributes().flatten() {
                         match attr.key.as_ref() {
@@ -161,6 +237,13 @@ pub fn parse_dat_file(path: &Path) -> AppResult<ParsedDat> {
                                         .to_string(),
                                 );
                             }
+                            b"merge" => {
+                                entry.merge_name = Some(
+                                    attr.unescape_value()
+                                        .unwrap_or_default()
+                                        .to_string(),
+                                );
+                            }
                             _ => {}
                         }
                     }
@@ -196,6 +279,134 @@ pub fn parse_dat_file(path: &Path) -> AppResult<ParsedDat> {
     Ok(ParsedDat { header, entries })
 }
 
+enum CmpToken<'a> {
+    Open,
+    Close,
+    Word(&'a str),
+}
+
+enum CmpValue<'a> {
+    Text(&'a str),
+    Block(Vec<(&'a str, &'a str)>),
+}
+
+/// Split clrmamepro text into parentheses, quoted strings and bare words.
+fn clrmamepro_tokens(text: &str) -> Vec<CmpToken<'_>> {
+    let mut tokens = Vec::new();
+    let mut rest = text;
+    loop {
+        rest = rest.trim_start();
+        let Some(c) = rest.chars().next() else {
+            break;
+        };
+        match c {
+            '(' => {
+                tokens.push(CmpToken::Open);
+                rest = &rest[1..];
+            }
+            ')' => {
+                tokens.push(CmpToken::Close);
+                rest = &rest[1..];
+            }
+            '"' => {
+                let body = &rest[1..];
+                let end = body.find('"').unwrap_or(body.len());
+                tokens.push(CmpToken::Word(&body[..end]));
+                rest = body.get(end + 1..).unwrap_or_default();
+            }
+            _ => {
+                let end = rest
+                    .find(|c: char| c.is_whitespace() || c == '(' || c == ')')
+                    .unwrap_or(rest.len());
+                tokens.push(CmpToken::Word(&rest[..end]));
+                rest = &rest[end..];
+            }
+        }
+    }
+    tokens
+}
+
+/// Read `key value` pairs up to the closing parenthesis. Values are text or
+/// a nested block of pairs, like a game's `rom ( name "..." crc ... )`.
+fn clrmamepro_block<'a>(
+    tokens: &mut impl Iterator<Item = CmpToken<'a>>,
+) -> Vec<(&'a str, CmpValue<'a>)> {
+    let mut fields = Vec::new();
+    while let Some(CmpToken::Word(key)) = tokens.next() {
+        match tokens.next() {
+            Some(CmpToken::Word(value)) => fields.push((key, CmpValue::Text(value))),
+            Some(CmpToken::Open) => {
+                let mut pairs = Vec::new();
+                while let Some(CmpToken::Word(k)) = tokens.next() {
+                    let Some(CmpToken::Word(v)) = tokens.next() else {
+                        break;
+                    };
+                    pairs.push((k, v));
+                }
+                fields.push((key, CmpValue::Block(pairs)));
+            }
+            _ => break,
+        }
+    }
+    fields
+}
+
+/// Parse a clrmamepro DAT, the format libretro-database ships.
+fn parse_clrmamepro(text: &str) -> ParsedDat {
+    let mut header = DatHeader {
+        name: String::new(),
+        description: None,
+        version: None,
+    };
+    let mut entries = Vec::new();
+
+    let mut tokens = clrmamepro_tokens(text).into_iter();
+    while let Some(token) = tokens.next() {
+        let CmpToken::Word(section) = token else {
+            continue;
+        };
+        if !matches!(tokens.next(), Some(CmpToken::Open)) {
+            continue;
+        }
+        let fields = clrmamepro_block(&mut tokens);
+        let text = |key: &str| {
+            fields.iter().find_map(|(k, v)| match v {
+                CmpValue::Text(t) if *k == key => Some((*t).to_string()),
+                _ => None,
+            })
+        };
+        match section {
+            "clrmamepro" => {
+                header.name = text("name").unwrap_or_default();
+                header.description = text("description");
+                header.version = text("version");
+            }
+            "game" | "machine" | "resource" => {
+                let game_name = text("name").unwrap_or_default();
+                for (key, value) in &fields {
+                    let ("rom", CmpValue::Block(pairs)) = (*key, value) else {
+                        continue;
+                    };
+                    let get = |k: &str| pairs.iter().find(|(pk, _)| *pk == k).map(|(_, v)| *v);
+                    entries.push(DatEntry {
+                        game_name: game_name.clone(),
+                        rom_name: get("name").unwrap_or_default().to_string(),
+                        size: get("size").and_then(|v| v.parse().ok()),
+                        crc32: get("crc").map(str::to_uppercase),
+                        md5: get("md5").map(str::to_lowercase),
+                        sha1: get("sha1").map(str::to_lowercase),
+                        status: get("status").or_else(|| get("flags")).map(str::to_string),
+                        merge_name: get("merge").map(str::to_string),
+                    });
+                }
+            }
+            _ => {}
+        }
+    }
+
+    ParsedDat { header, entries }
+}
+
 /// Import a DAT file into the database. Returns the dat_file id.
 pub async fn import_dat_file(
     db: &DatabaseConnection,
@@ -203,6 +414,7 @@ pub async fn import_dat_file(
     dat_type: &str,
     platform_slug: &str,
     on_progress: impl Fn(ScanProgress) + Send + 'static,
+    cancel: CancellationToken,
 ) -> AppResult<i64> {
     let path = path.to_path_buf();
     let dat_type = dat_type.to_string();
@@ -221,11 +433,18 @@ pub async fn import_dat_file(
         current_item: format!("Importing {} entries...", parsed.entries.len()),
     });
 
+    if cancel.is_cancelled() {
+        return Err(AppError::Cancelled("DAT import".to_string()));
+    }
+
+    // Replace in one transaction so a cancelled import leaves the previous DAT intact
+    let txn = db.begin().await?;
+
     // Remove any existing DAT for this platform + type
     dat_files::Entity::delete_many()
         .filter(dat_files::Column::PlatformSlug.eq(&platform_slug))
         .filter(dat_files::Column::DatType.eq(&dat_type))
-        .exec(db)
+        .exec(&txn)
         .await?;
 
     // Insert dat_file record
@@ -241,20 +460,25 @@ pub async fn import_dat_file(
         entry_count: Set(entry_count),
         imported_at: Set(chrono::Utc::now().format("%Y-%m-%dT%H:%M:%S%.3fZ").to_string()),
     }
-    .insert(db)
+    .insert(&txn)
     .await?;
     let dat_file_id = result.id;
 
     // Batch insert entries
     let batch_size = 500;
     for (i, chunk) in parsed.entries.chunks(batch_size).enumerate() {
+        if cancel.is_cancelled() {
+            txn.rollback().await?;
+            return Err(AppError::Cancelled("DAT import".to_string()));
+        }
+
         let mut query = String::from(
-            "INSERT INTO dat_entries (dat_file_id, game_name, rom_name, size, crc32, md5, sha1, status) VALUES ",
+            "INSERT INTO dat_entries (dat_file_id, game_name, rom_name, size, crc32, md5, sha1, status, merge_name) VALUES ",
         );
         let mut first = true;
         for _ in chunk {
             if !first { query.push(','); }
-            query.push_str("(?, ?, ?, ?, ?, ?, ?, ?)");
+            query.push_str("(?, ?, ?, ?, ?, ?, ?, ?, ?)");
             first = false;
         }
 
@@ -268,8 +492,9 @@ pub async fn import_dat_file(
             values.push(entry.md5.clone().into());
             values.push(entry.sha1.clone().into());
             values.push(entry.status.clone().into());
+            values.push(entry.merge_name.clone().into());
         }
-        db.execute(Statement::from_sql_and_values(
+        txn.execute(Statement::from_sql_and_values(
             DatabaseBackend::Sqlite,
             &query,
             values,
@@ -287,9 +512,169 @@ pub async fn import_dat_file(
         });
     }
 
+    txn.commit().await?;
     Ok(dat_file_id)
 }
 
+/// Where downloaded DATs are kept, one folder per DAT type.
+pub fn dat_dir() -> PathBuf {
+    directories::ProjectDirs::from("com", "romm-buddy", "romm-buddy")
+        .map_or_else(|| PathBuf::from("dats"), |p| p.data_dir().join("dats"))
+}
+
+/// Download the current DATs of each platform from `mirror` (laid out like
+/// libretro-database) and import them. The platform is taken from the DAT
+/// header when it names a known one. A DAT is skipped when its version, or
+/// its content when it has no version, matches what was imported last time.
+pub async fn fetch_latest_dats(
+    db: &DatabaseConnection,
+    client: &reqwest::Client,
+    mirror: &str,
+    platform_slugs: &[String],
+    on_progress: impl Fn(ScanProgress) + Send + Sync + 'static,
+    cancel: CancellationToken,
+) -> AppResult<Vec<DatFetchResult>> {
+    let on_progress = Arc::new(on_progress);
+    let mirror = mirror.trim().trim_end_matches('/');
+    let mut results = Vec::new();
+
+    for (i, slug) in platform_slugs.iter().enumerate() {
+        if cancel.is_cancelled() {
+            break;
+        }
+        #[allow(clippy::cast_possible_truncation)]
+        on_progress(ScanProgress {
+            source_id: -1,
+            total: platform_slugs.len() as u64,
+            current: i as u64,
+            current_item: format!("Fetching DATs for {slug}"),
+        });
+        let result = |status, dat_type: Option<&str>, version, message: Option<String>| DatFetchResult {
+            platform_slug: slug.clone(),
+            status,
+            dat_type: dat_type.map(str::to_string),
+            version,
+            message,
+        };
+
+        let Some(dat_name) = platform_registry::libretro_dir(slug) else {
+            results.push(result(
+                DatFetchStatus::NotFound,
+                None,
+                None,
+                Some("No DAT name is known for this platform".to_string()),
+            ));
+            continue;
+        };
+
+        let mut found = false;
+        for &dat_type in DAT_GROUPS {
+            let url = format!(
+                "{mirror}/metadat/{dat_type}/{}.dat",
+                urlencoding::encode(dat_name)
+            );
+            let body = match http::send(Provider::Libretro, client.get(&url)).await {
+                Ok(resp) if resp.status() == reqwest::StatusCode::NOT_FOUND => continue,
+                Ok(resp) if !resp.status().is_success() => Err(format!("{url} returned {}", resp.status())),
+                Ok(resp) => resp.bytes().await.map_err(|e| e.to_string()),
+                Err(e) => Err(e.to_string()),
+            };
+            found = true;
+            let body = match body {
+                Ok(body) => body,
+                Err(e) => {
+                    log::warn!("Failed to download {dat_type} DAT for {slug}: {e}");
+                    results.push(result(DatFetchStatus::Failed, Some(dat_type), None, Some(e)));
+                    continue;
+                }
+            };
+
+            match import_fetched_dat(db, slug, dat_type, dat_name, &body, &on_progress, &cancel).await {
+                Ok((status, version)) => results.push(result(status, Some(dat_type), version, None)),
+                Err(e) => {
+                    log::warn!("Failed to import {dat_type} DAT for {slug}: {e}");
+                    results.push(result(DatFetchStatus::Failed, Some(dat_type), None, Some(e.to_string())));
+                }
+            }
+        }
+        if !found {
+            results.push(result(
+                DatFetchStatus::NotFound,
+                None,
+                None,
+                Some(format!("No DAT named \"{dat_name}\" on the mirror")),
+            ));
+        }
+    }
+
+    Ok(results)
+}
+
+/// Import a downloaded DAT unless it matches the one already imported.
+async fn import_fetched_dat(
+    db: &DatabaseConnection,
+    slug: &str,
+    dat_type: &str,
+    dat_name: &str,
+    body: &[u8],
+    on_progress: &Arc<impl Fn(ScanProgress) + Send + Sync + 'static>,
+    cancel: &CancellationToken,
+) -> AppResult<(DatFetchStatus, Option<String>)> {
+    let dir = dat_dir().join(dat_type);
+    tokio::fs::create_dir_all(&dir).await?;
+    let path = dir.join(format!("{dat_name}.dat"));
+    let previous = tokio::fs::read(&path).await.ok();
+
+    // Parse a staged copy for the header; the import parses it again
+    let staged = path.with_extension("dat.part");
+    tokio::fs::write(&staged, body).await?;
+    let read_path = staged.clone();
+    let header = tokio::task::spawn_blocking(move || parse_dat_file(&read_path))
+        .await
+        .map_err(|e| AppError::Other(format!("Task join error: {e}")))?
+        .map(|parsed| parsed.header);
+    let header = match header {
+        Ok(header) => header,
+        Err(e) => {
+            let _ = tokio::fs::remove_file(&staged).await;
+            return Err(e);
+        }
+    };
+    let version = header.version.clone().filter(|v| !v.is_empty());
+    let platform_slug = detect_platform_slug(&header.name).unwrap_or_else(|| slug.to_string());
+
+    let existing = dat_files::Entity::find()
+        .filter(dat_files::Column::PlatformSlug.eq(&platform_slug))
+        .filter(dat_files::Column::DatType.eq(dat_type))
+        .one(db)
+        .await?;
+    if let Some(existing) = existing {
+        let same_version = version.is_some() && existing.version == version;
+        if same_version || previous.as_deref() == Some(body) {
+            let _ = tokio::fs::remove_file(&staged).await;
+            return Ok((DatFetchStatus::Unchanged, existing.version));
+        }
+    }
+
+    let progress = Arc::clone(on_progress);
+    let imported = import_dat_file(
+        db,
+        &staged,
+        dat_type,
+        &platform_slug,
+        move |p| progress(p),
+        cancel.clone(),
+    )
+    .await;
+    if let Err(e) = imported {
+        let _ = tokio::fs::remove_file(&staged).await;
+        return Err(e);
+    }
+    // Keep the copy only once imported, so a failed import is retried next time
+    tokio::fs::rename(&staged, &path).await?;
+    Ok((DatFetchStatus::Imported, version))
+}
+
 /// Row returned by the verification ROM query.
 #[derive(Debug, FromQueryResult)]
 struct VerifyRomRow {
@@ -298,11 +683,35 @@ struct VerifyRomRow {
     hash_crc32: Option<String>,
     hash_md5: Option<String>,
     hash_sha1: Option<String>,
+    headerless_crc32: Option<String>,
+    headerless_md5: Option<String>,
+    headerless_sha1: Option<String>,
+    platform_slug: String,
     source_rom_id: Option<String>,
+    verified_file_size: Option<i64>,
+    verified_file_mtime: Option<i64>,
+}
+
+/// Size and mtime (unix seconds) of a file, to notice when it changes.
+pub(crate) fn file_stamp(path: &Path) -> Option<(i64, i64)> {
+    let meta = std::fs::metadata(path).ok()?;
+    let mtime = meta
+        .modified()
+        .ok()?
+        .duration_since(std::time::UNIX_EPOCH)
+        .ok()?
+        .as_secs();
+    Some((
+        i64::try_from(meta.len()).ok()?,
+        i64::try_from(mtime).ok()?,
+    ))
 }
 
 /// Verify ROMs against imported DAT files.
 /// Computes triple hashes for ROMs, looks up in dat_entries, sets verification_status.
+/// Local files are only re-hashed when their size or mtime changed since the
+/// last run, so repeat runs over an unchanged library skip hashing entirely.
+/// Arcade ROMs are checked as whole sets instead (see [`verify_arcade_sets`]).
 pub async fn verify_roms(
     db: &DatabaseConnection,
     platform_id: Option<i64>,
@@ -313,8 +722,12 @@ pub async fn verify_roms(
     let query = if let Some(pid) = platform_id {
         Statement::from_sql_and_values(
             DatabaseBackend::Sqlite,
-            "SELECT r.id, r.name, r.hash_crc32, r.hash_md5, r.hash_sha1, sr.source_rom_id
+            "SELECT r.id, r.name, r.hash_crc32, r.hash_md5, r.hash_sha1,
+                    r.headerless_crc32, r.headerless_md5, r.headerless_sha1,
+                    p.slug AS platform_slug, sr.source_rom_id,
+                    r.verified_file_size, r.verified_file_mtime
              FROM roms r
+             JOIN platforms p ON p.id = r.platform_id
              LEFT JOIN source_roms sr ON sr.rom_id = r.id
              LEFT JOIN sources s ON s.id = sr.source_id AND s.source_type = 'local'
              WHERE r.platform_id = ?
@@ -324,17 +737,25 @@ pub async fn verify_roms(
     } else {
         Statement::from_string(
             DatabaseBackend::Sqlite,
-            "SELECT r.id, r.name, r.hash_crc32, r.hash_md5, r.hash_sha1, sr.source_rom_id
+            "SELECT r.id, r.name, r.hash_crc32, r.hash_md5, r.hash_sha1,
+                    r.headerless_crc32, r.headerless_md5, r.headerless_sha1,
+                    p.slug AS platform_slug, sr.source_rom_id,
+                    r.verified_file_size, r.verified_file_mtime
              FROM roms r
+             JOIN platforms p ON p.id = r.platform_id
              LEFT JOIN source_roms sr ON sr.rom_id = r.id
              LEFT JOIN sources s ON s.id = sr.source_id AND s.source_type = 'local'
+             WHERE p.archived = 0
              GROUP BY r.id",
         )
     };
     let rom_rows = VerifyRomRow::find_by_statement(query).all(db).await?;
+    let (arcade_rows, rom_rows): (Vec<_>, Vec<_>) = rom_rows
+        .into_iter()
+        .partition(|row| ARCADE_PLATFORMS.contains(&row.platform_slug.as_str()));
 
     #[allow(clippy::cast_possible_truncation)]
-    let total = rom_rows.len() as u64;
+    let total = (arcade_rows.len() + rom_rows.len()) as u64;
     let mut stats = VerificationStats {
         verified: 0,
         unverified: 0,
@@ -342,102 +763,485 @@ pub async fn verify_roms(
         not_checked: 0,
     };
 
-    for (i, row) in rom_rows.iter().enumerate() {
+    let workers = std::thread::available_parallelism()
+        .map_or(2, std::num::NonZeroUsize::get)
+        .clamp(2, MAX_HASH_WORKERS);
+    let mut processed: u64 = 0;
+
+    for chunk in arcade_rows.chunks(VERIFY_CHUNK_SIZE) {
         if cancel.is_cancelled() {
             return Ok(stats);
         }
+        on_progress(ScanProgress {
+            source_id: -1,
+            total,
+            current: processed,
+            current_item: format!("Verifying: {}", chunk[0].name),
+        });
+        verify_arcade_sets(db, chunk, &mut stats).await?;
 
         #[allow(clippy::cast_possible_truncation)]
-        let current = i as u64 + 1;
-        if i % 10 == 0 {
-            on_progress(ScanProgress {
-                source_id: -1,
-                total,
-                current,
-                current_item: format!("Verifying: {}", row.name),
-            });
+        {
+            processed += chunk.len() as u64;
         }
+    }
 
-        // Compute hashes if missing and file is accessible
-        let (crc, md5, sha1) = if row.hash_crc32.is_some() && row.hash_md5.is_some() && row.hash_sha1.is_some() {
-            (row.hash_crc32.clone(), row.hash_md5.clone(), row.hash_sha1.clone())
-        } else if let Some(ref path_str) = row.source_rom_id {
-            let path = std::path::PathBuf::from(path_str);
-            if path.exists() {
-                let path_clone = path.clone();
-                let hashes = tokio::task::spawn_blocking(move || {
-                    hash::compute_triple_hash(&path_clone)
+    for chunk in rom_rows.chunks(VERIFY_CHUNK_SIZE) {
+        if cancel.is_cancelled() {
+            return Ok(stats);
+        }
+
+        on_progress(ScanProgress {
+            source_id: -1,
+            total,
+            current: processed,
+            current_item: format!("Verifying: {}", chunk[0].name),
+        });
+
+        // Current size/mtime of every local file in the chunk
+        let stamps: HashMap<i64, (i64, i64)> = chunk
+            .iter()
+            .filter_map(|row| {
+                let path = Path::new(row.source_rom_id.as_ref()?);
+                Some((row.id, file_stamp(path)?))
+            })
+            .collect();
+        let changed: HashSet<i64> = chunk
+            .iter()
+            .filter(|row| {
+                row.verified_file_size.is_some()
+                    && stamps.get(&row.id).is_some_and(|&(size, mtime)| {
+                        (row.verified_file_size, row.verified_file_mtime) != (Some(size), Some(mtime))
+                    })
+            })
+            .map(|row| row.id)
+            .collect();
+
+        // Hash every file in the chunk that is missing hashes or changed since
+        // it was last hashed, N at a time. Files hashed before stamps were
+        // recorded keep their hashes. Workers report per-file byte progress
+        // over a channel for large images.
+        let (progress_tx, mut progress_rx) =
+            tokio::sync::mpsc::unbounded_channel::<(String, u64, u64)>();
+        let hash_all = stream::iter(chunk.iter().filter_map(|row| {
+            if !stamps.contains_key(&row.id) {
+                return None;
+            }
+            let has_all = row.hash_crc32.is_some() && row.hash_md5.is_some() && row.hash_sha1.is_some()
+                && (row.headerless_md5.is_some() || !hash::may_have_header(&row.platform_slug));
+            let path = std::path::PathBuf::from(row.source_rom_id.as_ref()?);
+            (!has_all || changed.contains(&row.id))
+                .then(|| (row.id, row.name.clone(), row.platform_slug.clone(), path))
+        }))
+        .map(|(id, name, slug, path)| {
+            let cancel = cancel.clone();
+            let progress_tx = progress_tx.clone();
+            async move {
+                if cancel.is_cancelled() {
+                    return None;
+                }
+                tokio::task::spawn_blocking(move || {
+                    hash::compute_dump_hashes_with_progress(&path, &slug, |read, size| {
+                        if size >= LARGE_FILE_PROGRESS_THRESHOLD {
+                            let _ = progress_tx.send((name.clone(), read, size));
+                        }
+                    })
                 })
                 .await
                 .ok()
-                .and_then(|r| r.ok());
-
-                if let Some(h) = hashes {
-                    // Store computed hashes
-                    let _ = db.execute(Statement::from_sql_and_values(
-                        DatabaseBackend::Sqlite,
-                        "UPDATE roms SET hash_crc32 = ?, hash_md5 = ?, hash_sha1 = ? WHERE id = ?",
-                        [h.crc32.clone().into(), h.md5.clone().into(), h.sha1.clone().into(), row.id.into()],
-                    )).await;
+                .and_then(Result::ok)
+                .map(|h| (id, h))
+            }
+        })
+        .buffer_unordered(workers)
+        .filter_map(|r| async move { r })
+        .collect::<HashMap<i64, hash::DumpHashes>>();
+        tokio::pin!(hash_all);
 
-                    (Some(h.crc32), Some(h.md5), Some(h.sha1))
-                } else {
-                    stats.not_checked += 1;
-                    continue;
+        let hashed = loop {
+            tokio::select! {
+                hashed = &mut hash_all => break hashed,
+                Some((name, read, size)) = progress_rx.recv() => {
+                    on_progress(ScanProgress {
+                        source_id: -1,
+                        total,
+                        current: processed,
+                        current_item: format!("Hashing: {name} ({}%)", read * 100 / size.max(1)),
+                    });
                 }
-            } else {
+            }
+        };
+
+        if cancel.is_cancelled() {
+            return Ok(stats);
+        }
+
+        // Write hashes and verification results for the whole chunk in one transaction
+        let txn = db.begin().await?;
+        for row in chunk {
+            let mut headerless = row.headerless_md5.as_ref().map(|md5| {
+                (row.headerless_crc32.clone(), Some(md5.clone()), row.headerless_sha1.clone())
+            });
+            let (crc, md5, sha1) = if let Some(dump) = hashed.get(&row.id) {
+                let (h, stripped) = (&dump.full, dump.headerless.as_ref());
+                txn.execute(Statement::from_sql_and_values(
+                    DatabaseBackend::Sqlite,
+                    "UPDATE roms SET hash_crc32 = ?, hash_md5 = ?, hash_sha1 = ?,
+                            headerless_crc32 = ?, headerless_md5 = ?, headerless_sha1 = ?
+                     WHERE id = ?",
+                    [
+                        h.crc32.clone().into(),
+                        h.md5.clone().into(),
+                        h.sha1.clone().into(),
+                        stripped.map(|s| s.crc32.clone()).into(),
+                        stripped.map(|s| s.md5.clone()).into(),
+                        stripped.map(|s| s.sha1.clone()).into(),
+                        row.id.into(),
+                    ],
+                )).await?;
+                headerless = stripped.map(|s| (Some(s.crc32.clone()), Some(s.md5.clone()), Some(s.sha1.clone())));
+                (Some(h.crc32.clone()), Some(h.md5.clone()), Some(h.sha1.clone()))
+            } else if changed.contains(&row.id) {
+                // Changed on disk but couldn't be re-hashed; the stored hashes are stale
                 stats.not_checked += 1;
                 continue;
-            }
-        } else {
-            // No file accessible, try with whatever hashes we have
-            if row.hash_md5.is_none() && row.hash_crc32.is_none() && row.hash_sha1.is_none() {
+            } else if row.hash_crc32.is_some() && row.hash_md5.is_some() && row.hash_sha1.is_some() {
+                (row.hash_crc32.clone(), row.hash_md5.clone(), row.hash_sha1.clone())
+            } else if row.source_rom_id.is_some() {
+                // File missing or hashing failed
                 stats.not_checked += 1;
                 continue;
-            }
-            (row.hash_crc32.clone(), row.hash_md5.clone(), row.hash_sha1.clone())
-        };
+            } else {
+                // No file accessible, try with whatever hashes we have
+                if row.hash_md5.is_none() && row.hash_crc32.is_none() && row.hash_sha1.is_none() {
+                    stats.not_checked += 1;
+                    continue;
+                }
+                (row.hash_crc32.clone(), row.hash_md5.clone(), row.hash_sha1.clone())
+            };
 
-        // Look up in dat_entries by any available hash
-        let dat_match = find_dat_match(db, crc.as_deref(), md5.as_deref(), sha1.as_deref()).await?;
-
-        match dat_match {
-            Some((entry_id, game_name, status)) => {
-                let verification = if status.as_deref() == Some("baddump") {
-                    stats.bad_dump += 1;
-                    "bad_dump"
-                } else {
-                    stats.verified += 1;
-                    "verified"
-                };
-                db.execute(Statement::from_sql_and_values(
+            let stamp = stamps.get(&row.id).filter(|_| {
+                hashed.contains_key(&row.id) || row.verified_file_size.is_none()
+            });
+            if let Some(&(size, mtime)) = stamp {
+                txn.execute(Statement::from_sql_and_values(
                     DatabaseBackend::Sqlite,
-                    "UPDATE roms SET verification_status = ?, dat_entry_id = ?, dat_game_name = ? WHERE id = ?",
-                    [verification.into(), entry_id.into(), game_name.into(), row.id.into()],
+                    "UPDATE roms SET verified_file_size = ?, verified_file_mtime = ? WHERE id = ?",
+                    [size.into(), mtime.into(), row.id.into()],
                 )).await?;
             }
-            None => {
-                // Hashes computed but no DAT match
-                if crc.is_some() || md5.is_some() || sha1.is_some() {
-                    db.execute(Statement::from_sql_and_values(
+
+            // Look up in dat_entries by any available hash. No-Intro catalogues
+            // headered consoles by their headerless data, so try that first.
+            let mut dat_match = None;
+            if let Some((h_crc, h_md5, h_sha1)) = headerless.filter(|(_, stripped, _)| *stripped != md5) {
+                dat_match = find_dat_match(&txn, h_crc.as_deref(), h_md5.as_deref(), h_sha1.as_deref()).await?;
+            }
+            if dat_match.is_none() {
+                dat_match = find_dat_match(&txn, crc.as_deref(), md5.as_deref(), sha1.as_deref()).await?;
+            }
+
+            match dat_match {
+                Some((entry_id, game_name, status)) => {
+                    let verification = if status.as_deref() == Some("baddump") {
+                        stats.bad_dump += 1;
+                        "bad_dump"
+                    } else {
+                        stats.verified += 1;
+                        "verified"
+                    };
+                    txn.execute(Statement::from_sql_and_values(
+                        DatabaseBackend::Sqlite,
+                        "UPDATE roms SET verification_status = ?, dat_entry_id = ?, dat_game_name = ? WHERE id = ?",
+                        [verification.into(), entry_id.into(), game_name.into(), row.id.into()],
+                    )).await?;
+                }
+                None => {
+                    txn.execute(Statement::from_sql_and_values(
                         DatabaseBackend::Sqlite,
                         "UPDATE roms SET verification_status = 'unverified' WHERE id = ?",
                         [row.id.into()],
                     )).await?;
                     stats.unverified += 1;
-                } else {
-                    stats.not_checked += 1;
                 }
             }
         }
+        txn.commit().await?;
+
+        #[allow(clippy::cast_possible_truncation)]
+        {
+            processed += chunk.len() as u64;
+        }
     }
 
+    on_progress(ScanProgress {
+        source_id: -1,
+        total,
+        current: processed,
+        current_item: "Verification complete".to_string(),
+    });
+
     Ok(stats)
 }
 
+/// A file inside an arcade zip.
+struct ZipMember {
+    name: String,
+    crc32: String,
+}
+
+/// The set name and file listing of an arcade zip, read from its central
+/// directory without decompressing anything. None for other archives.
+fn arcade_zip_listing(path: &Path) -> Option<(String, Vec<ZipMember>)> {
+    if !path.extension().is_some_and(|e| e.eq_ignore_ascii_case("zip")) {
+        return None;
+    }
+    let set_name = path.file_stem()?.to_str()?.to_lowercase();
+    let file = std::fs::File::open(path).ok()?;
+    let mut archive = zip::ZipArchive::new(file).ok()?;
+    let mut members = Vec::with_capacity(archive.len());
+    for i in 0..archive.len() {
+        let entry = archive.by_index_raw(i).ok()?;
+        if entry.is_dir() {
+            continue;
+        }
+        members.push(ZipMember {
+            name: entry.name().rsplit('/').next().unwrap_or_default().to_string(),
+            crc32: format!("{:08X}", entry.crc32()),
+        });
+    }
+    Some((set_name, members))
+}
+
+#[derive(Debug, FromQueryResult)]
+struct ArcadeSetEntry {
+    id: i64,
+    dat_file_id: i64,
+    rom_name: String,
+    crc32: Option<String>,
+    status: Option<String>,
+    merge_name: Option<String>,
+}
+
+/// One DAT's set compared with a zip.
+struct SetCheck {
+    dat_file_id: i64,
+    entry_id: i64,
+    missing: Vec<String>,
+    extra: Vec<String>,
+    bad_dump: bool,
+}
+
+/// Files are matched by CRC, as MAME does, so renamed ROMs still count.
+/// ROMs merged from the parent may be in a clone's zip or not, and
+/// `nodump` ROMs have no known data to look for.
+fn compare_set(entries: &[ArcadeSetEntry], members: &[ZipMember]) -> SetCheck {
+    let zip_crcs: HashSet<&str> = members.iter().map(|m| m.crc32.as_str()).collect();
+    let set_crcs: HashSet<&str> = entries.iter().filter_map(|e| e.crc32.as_deref()).collect();
+    let missing = entries
+        .iter()
+        .filter(|e| e.merge_name.is_none() && e.status.as_deref() != Some("nodump"))
+        .filter(|e| e.crc32.as_deref().is_some_and(|crc| !zip_crcs.contains(crc)))
+        .map(|e| e.rom_name.clone())
+        .collect();
+    let extra = members
+        .iter()
+        .filter(|m| !set_crcs.contains(m.crc32.as_str()))
+        .map(|m| m.name.clone())
+        .collect();
+    SetCheck {
+        dat_file_id: entries[0].dat_file_id,
+        entry_id: entries[0].id,
+        missing,
+        extra,
+        bad_dump: entries.iter().any(|e| e.status.as_deref() == Some("baddump")),
+    }
+}
+
+/// Verify arcade ROMs set by set: the zip's name picks the DAT machine and
+/// the CRCs in the zip's directory are checked against the machine's ROMs.
+/// When several imported DATs have the set (MAME and FBNeo), the closest
+/// match wins, preferring the ROM's own platform. A set missing files counts
+/// as a bad dump; extra files are only reported.
+async fn verify_arcade_sets(
+    db: &DatabaseConnection,
+    chunk: &[VerifyRomRow],
+    stats: &mut VerificationStats,
+) -> AppResult<()> {
+    let paths: Vec<(i64, std::path::PathBuf)> = chunk
+        .iter()
+        .filter_map(|row| Some((row.id, std::path::PathBuf::from(row.source_rom_id.as_ref()?))))
+        .collect();
+    let listings: HashMap<i64, (String, Vec<ZipMember>)> = tokio::task::spawn_blocking(move || {
+        paths
+            .into_iter()
+            .filter_map(|(id, path)| Some((id, arcade_zip_listing(&path)?)))
+            .collect()
+    })
+    .await
+    .map_err(|e| AppError::Other(format!("Task join error: {e}")))?;
+
+    let placeholders = vec!["?"; ARCADE_PLATFORMS.len()].join(", ");
+    let txn = db.begin().await?;
+    for row in chunk {
+        let Some((set_name, members)) = listings.get(&row.id) else {
+            stats.not_checked += 1;
+            continue;
+        };
+
+        let mut values: Vec<sea_orm::Value> = vec![set_name.clone().into()];
+        values.extend(ARCADE_PLATFORMS.iter().map(|&slug| slug.into()));
+        values.push(row.platform_slug.clone().into());
+        let entries = ArcadeSetEntry::find_by_statement(Statement::from_sql_and_values(
+            DatabaseBackend::Sqlite,
+            format!(
+                "SELECT e.id, e.dat_file_id, e.rom_name, e.crc32, e.status, e.merge_name
+                 FROM dat_entries e
+                 JOIN dat_files f ON f.id = e.dat_file_id
+                 WHERE e.game_name = ? AND f.platform_slug IN ({placeholders})
+                 ORDER BY f.platform_slug = ? DESC, e.dat_file_id, e.id"
+            ),
+            values,
+        ))
+        .all(&txn)
+        .await?;
+
+        let mut checks: Vec<SetCheck> = Vec::new();
+        let mut start = 0;
+        while start < entries.len() {
+            let dat_file_id = entries[start].dat_file_id;
+            let end = entries[start..]
+                .iter()
+                .position(|e| e.dat_file_id != dat_file_id)
+                .map_or(entries.len(), |n| start + n);
+            checks.push(compare_set(&entries[start..end], members));
+            start = end;
+        }
+        let best = checks
+            .into_iter()
+            .min_by_key(|c| (c.missing.len(), c.extra.len()));
+
+        let Some(check) = best else {
+            txn.execute(Statement::from_sql_and_values(
+                DatabaseBackend::Sqlite,
+                "UPDATE roms SET verification_status = 'unverified' WHERE id = ?",
+                [row.id.into()],
+            ))
+            .await?;
+            txn.execute(Statement::from_sql_and_values(
+                DatabaseBackend::Sqlite,
+                "DELETE FROM arcade_set_reports WHERE rom_id = ?",
+                [row.id.into()],
+            ))
+            .await?;
+            stats.unverified += 1;
+            continue;
+        };
+
+        let verification = if check.bad_dump || !check.missing.is_empty() {
+            stats.bad_dump += 1;
+            "bad_dump"
+        } else {
+            stats.verified += 1;
+            "verified"
+        };
+        txn.execute(Statement::from_sql_and_values(
+            DatabaseBackend::Sqlite,
+            "UPDATE roms SET verification_status = ?, dat_entry_id = ?, dat_game_name = ? WHERE id = ?",
+            [verification.into(), check.entry_id.into(), set_name.clone().into(), row.id.into()],
+        ))
+        .await?;
+        txn.execute(Statement::from_sql_and_values(
+            DatabaseBackend::Sqlite,
+            "INSERT INTO arcade_set_reports (rom_id, dat_file_id, set_name, missing, extra)
+             VALUES (?, ?, ?, ?, ?)
+             ON CONFLICT(rom_id) DO UPDATE SET
+               dat_file_id = excluded.dat_file_id,
+               set_name = excluded.set_name,
+               missing = excluded.missing,
+               extra = excluded.extra,
+               checked_at = strftime('%Y-%m-%dT%H:%M:%fZ', 'now')",
+            [
+                row.id.into(),
+                check.dat_file_id.into(),
+                set_name.clone().into(),
+                serde_json::json!(check.missing).to_string().into(),
+                serde_json::json!(check.extra).to_string().into(),
+            ],
+        ))
+        .await?;
+    }
+    txn.commit().await?;
+    Ok(())
+}
+
+/// The last arcade set check for a ROM, if it has been verified as a set.
+pub async fn get_arcade_set_report(
+    db: &DatabaseConnection,
+    rom_id: i64,
+) -> AppResult<Option<ArcadeSetReport>> {
+    #[derive(Debug, FromQueryResult)]
+    struct ReportRow {
+        set_name: String,
+        dat_name: String,
+        missing: String,
+        extra: String,
+        checked_at: String,
+    }
+
+    let row = ReportRow::find_by_statement(Statement::from_sql_and_values(
+        DatabaseBackend::Sqlite,
+        "SELECT a.set_name, f.name AS dat_name, a.missing, a.extra, a.checked_at
+         FROM arcade_set_reports a
+         JOIN dat_files f ON f.id = a.dat_file_id
+         WHERE a.rom_id = ?",
+        [rom_id.into()],
+    ))
+    .one(db)
+    .await?;
+
+    Ok(row.map(|row| ArcadeSetReport {
+        rom_id,
+        set_name: row.set_name,
+        dat_name: row.dat_name,
+        missing: serde_json::from_str(&row.missing).unwrap_or_default(),
+        extra: serde_json::from_str(&row.extra).unwrap_or_default(),
+        checked_at: row.checked_at,
+    }))
+}
+
+/// Clear verification results for a platform (or every platform) and forget
+/// the file stamps, so the next run re-hashes every file. Returns ROMs reset.
+pub async fn reset_verification(
+    db: &DatabaseConnection,
+    platform_id: Option<i64>,
+) -> AppResult<u64> {
+    let result = db
+        .execute(Statement::from_sql_and_values(
+            DatabaseBackend::Sqlite,
+            "UPDATE roms SET
+                verification_status = NULL,
+                dat_entry_id = NULL,
+                dat_game_name = NULL,
+                verified_file_size = NULL,
+                verified_file_mtime = NULL
+             WHERE (? IS NULL OR platform_id = ?)",
+            [platform_id.into(), platform_id.into()],
+        ))
+        .await?;
+    db.execute(Statement::from_sql_and_values(
+        DatabaseBackend::Sqlite,
+        "DELETE FROM arcade_set_reports
+         WHERE ? IS NULL OR rom_id IN (SELECT id FROM roms WHERE platform_id = ?)",
+        [platform_id.into(), platform_id.into()],
+    ))
+    .await?;
+    Ok(result.rows_affected())
+}
+
 /// Find a matching DAT entry by hash (try SHA1 first, then MD5, then CRC32).
 async fn find_dat_match(
-    db: &DatabaseConnection,
+    db: &impl ConnectionTrait,
     crc: Option<&str>,
     md5: Option<&str>,
     sha1: Option<&str>,
@@ -500,7 +1304,7 @@ pub async fn get_verification_stats(
                 SUM(CASE WHEN verification_status = 'unverified' THEN 1 ELSE 0 END) as unverified, \
                 SUM(CASE WHEN verification_status = 'bad_dump' THEN 1 ELSE 0 END) as bad_dump, \
                 SUM(CASE WHEN verification_status IS NULL THEN 1 ELSE 0 END) as not_checked \
-            FROM roms",
+            FROM roms WHERE platform_id IN (SELECT id FROM platforms WHERE archived = 0)",
             vec![],
         )
     };
@@ -0,0 +1,228 @@
+use std::fmt::Write;
+use std::path::Path;
+
+use quick_xml::escape::escape;
+use sea_orm::{ConnectionTrait, DatabaseBackend, DatabaseConnection, FromQueryResult, Statement};
+
+use crate::error::{AppError, AppResult};
+
+/// Output format of a verification report.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ReportFormat {
+    Csv,
+    Html,
+}
+
+#[derive(Debug, FromQueryResult)]
+struct ReportRow {
+    platform_name: String,
+    name: String,
+    file_name: String,
+    verification_status: Option<String>,
+    dat_game_name: Option<String>,
+    hash_crc32: Option<String>,
+    hash_md5: Option<String>,
+    hash_sha1: Option<String>,
+}
+
+impl ReportRow {
+    fn status(&self) -> &str {
+        self.verification_status.as_deref().unwrap_or("not_checked")
+    }
+}
+
+const COLUMNS: [&str; 8] = [
+    "Platform", "Name", "File", "Status", "DAT game", "CRC32", "MD5", "SHA1",
+];
+
+/// Render the verification results of a platform (or every non-archived
+/// platform) in `format`. Returns the report and the number of ROMs in it.
+pub async fn verification_report(
+    db: &DatabaseConnection,
+    platform_id: Option<i64>,
+    format: ReportFormat,
+) -> AppResult<(String, usize)> {
+    let rows = ReportRow::find_by_statement(Statement::from_sql_and_values(
+        DatabaseBackend::Sqlite,
+        "SELECT p.name AS platform_name, r.name, r.file_name, r.verification_status,
+                r.dat_game_name, r.hash_crc32, r.hash_md5, r.hash_sha1
+         FROM roms r
+         JOIN platforms p ON p.id = r.platform_id
+         WHERE (? IS NULL AND p.archived = 0) OR r.platform_id = ?
+         ORDER BY p.name, r.name",
+        [platform_id.into(), platform_id.into()],
+    ))
+    .all(db)
+    .await?;
+
+    let report = match format {
+        ReportFormat::Csv => render_csv(&rows),
+        ReportFormat::Html => render_html(&rows),
+    };
+    Ok((report, rows.len()))
+}
+
+fn cells(row: &ReportRow) -> [&str; 8] {
+    [
+        &row.platform_name,
+        &row.name,
+        &row.file_name,
+        row.status(),
+        row.dat_game_name.as_deref().unwrap_or_default(),
+        row.hash_crc32.as_deref().unwrap_or_default(),
+        row.hash_md5.as_deref().unwrap_or_default(),
+        row.hash_sha1.as_deref().unwrap_or_default(),
+    ]
+}
+
+/// RFC 4180 quoting: fields with commas, quotes or line breaks are quoted.
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+fn render_csv(rows: &[ReportRow]) -> String {
+    let line = |cells: [&str; 8]| {
+        cells
+            .iter()
+            .map(|c| csv_field(c))
+            .collect::<Vec<_>>()
+            .join(",")
+    };
+    let mut out = line(COLUMNS);
+    out.push_str("\r\n");
+    for row in rows {
+        out.push_str(&line(cells(row)));
+        out.push_str("\r\n");
+    }
+    out
+}
+
+/// A standalone page: inline styles, no scripts or external assets.
+fn render_html(rows: &[ReportRow]) -> String {
+    let count = |status: &str| rows.iter().filter(|r| r.status() == status).count();
+    let generated = chrono::Utc::now().format("%Y-%m-%d %H:%M UTC");
+
+    let mut out = String::from(
+        "<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n<meta charset=\"utf-8\">\n\
+         <title>ROM verification report</title>\n<style>\n\
+         body { font-family: system-ui, sans-serif; margin: 2rem; color: #222; }\n\
+         table { border-collapse: collapse; width: 100%; font-size: 0.85rem; }\n\
+         th, td { border: 1px solid #ddd; padding: 0.3rem 0.5rem; text-align: left; }\n\
+         th { background: #f4f4f4; }\n\
+         td.hash { font-family: ui-monospace, monospace; font-size: 0.75rem; }\n\
+         .verified { color: #1a7f37; } .bad_dump { color: #cf222e; }\n\
+         .unverified, .not_checked { color: #9a6700; }\n\
+         </style>\n</head>\n<body>\n<h1>ROM verification report</h1>\n",
+    );
+    let _ = writeln!(
+        out,
+        "<p>Generated {generated}. {} ROMs: {} verified, {} unverified, {} bad dumps, {} not checked.</p>",
+        rows.len(),
+        count("verified"),
+        count("unverified"),
+        count("bad_dump"),
+        count("not_checked"),
+    );
+
+    out.push_str("<table>\n<thead><tr>");
+    for column in COLUMNS {
+        let _ = write!(out, "<th>{column}</th>");
+    }
+    out.push_str("</tr></thead>\n<tbody>\n");
+    for row in rows {
+        let [platform, name, file, status, dat_game, crc32, md5, sha1] = cells(row);
+        let _ = write!(
+            out,
+            "<tr><td>{}</td><td>{}</td><td>{}</td><td class=\"{status}\">{}</td><td>{}</td>",
+            escape(platform),
+            escape(name),
+            escape(file),
+            status.replace('_', " "),
+            escape(dat_game),
+        );
+        for hash in [crc32, md5, sha1] {
+            let _ = write!(out, "<td class=\"hash\">{}</td>", escape(hash));
+        }
+        out.push_str("</tr>\n");
+    }
+    out.push_str("</tbody>\n</table>\n</body>\n</html>\n");
+    out
+}
+
+/// Game counts of a have/miss export (returned to frontend).
+#[derive(Debug, serde::Serialize)]
+pub struct HaveMissReport {
+    pub dat_name: String,
+    pub have: usize,
+    pub miss: usize,
+    pub have_path: String,
+    pub miss_path: String,
+}
+
+/// Write ClrMamePro-style `have.txt` and `miss.txt` into `dir`: the games of
+/// a DAT file with a verified ROM in the library, and the rest. One game
+/// name per line, sorted.
+pub async fn export_have_miss(
+    db: &DatabaseConnection,
+    dat_file_id: i64,
+    dir: &Path,
+) -> AppResult<HaveMissReport> {
+    #[derive(Debug, FromQueryResult)]
+    struct GameRow {
+        game_name: String,
+        have: bool,
+    }
+
+    let dat_name: String = db
+        .query_one(Statement::from_sql_and_values(
+            DatabaseBackend::Sqlite,
+            "SELECT name FROM dat_files WHERE id = ?",
+            [dat_file_id.into()],
+        ))
+        .await?
+        .ok_or_else(|| AppError::NotFound(format!("DAT file {dat_file_id}")))?
+        .try_get("", "name")?;
+
+    let games = GameRow::find_by_statement(Statement::from_sql_and_values(
+        DatabaseBackend::Sqlite,
+        "SELECT e.game_name, MAX(v.id IS NOT NULL) AS have
+         FROM dat_entries e
+         LEFT JOIN (
+             SELECT DISTINCT dat_entry_id AS id FROM roms
+             WHERE verification_status = 'verified' AND dat_entry_id IS NOT NULL
+         ) v ON v.id = e.id
+         WHERE e.dat_file_id = ?
+         GROUP BY e.game_name
+         ORDER BY e.game_name",
+        [dat_file_id.into()],
+    ))
+    .all(db)
+    .await?;
+
+    let (have, miss): (Vec<_>, Vec<_>) = games.into_iter().partition(|g| g.have);
+    let list = |games: &[GameRow]| {
+        games.iter().fold(String::new(), |mut out, g| {
+            let _ = writeln!(out, "{}", g.game_name);
+            out
+        })
+    };
+
+    tokio::fs::create_dir_all(dir).await?;
+    let have_path = dir.join("have.txt");
+    let miss_path = dir.join("miss.txt");
+    tokio::fs::write(&have_path, list(&have)).await?;
+    tokio::fs::write(&miss_path, list(&miss)).await?;
+
+    Ok(HaveMissReport {
+        dat_name,
+        have: have.len(),
+        miss: miss.len(),
+        have_path: have_path.to_string_lossy().into_owned(),
+        miss_path: miss_path.to_string_lossy().into_owned(),
+    })
+}
@@ -0,0 +1,143 @@
+use sea_orm::{ConnectionTrait, DatabaseBackend, FromQueryResult, Statement};
+
+use crate::error::{AppError, AppResult};
+use crate::models::MetadataEdit;
+
+/// Fields `update_rom_metadata` can edit. `name` lives on `roms`, the rest on `metadata`.
+pub const EDITABLE_FIELDS: &[&str] = &["name", "description", "genres", "release_date", "rating"];
+
+#[derive(Debug, FromQueryResult)]
+struct EditRow {
+    field: String,
+    value: String,
+}
+
+fn edited_values(edit: &MetadataEdit) -> AppResult<Vec<(&'static str, serde_json::Value)>> {
+    if let Some(rating) = edit.rating {
+        if !(0.0..=10.0).contains(&rating) {
+            return Err(AppError::Other(
+                "Rating must be between 0 and 10".to_string(),
+            ));
+        }
+    }
+    if edit.name.as_deref().is_some_and(|n| n.trim().is_empty()) {
+        return Err(AppError::Other("Name can't be empty".to_string()));
+    }
+    let values = [
+        (
+            "name",
+            edit.name.as_ref().map(|n| serde_json::json!(n.trim())),
+        ),
+        (
+            "description",
+            edit.description.as_ref().map(|d| serde_json::json!(d)),
+        ),
+        ("genres", edit.genres.as_ref().map(|g| serde_json::json!(g))),
+        (
+            "release_date",
+            edit.release_date.as_ref().map(|d| serde_json::json!(d)),
+        ),
+        ("rating", edit.rating.map(|r| serde_json::json!(r))),
+    ];
+    Ok(values
+        .into_iter()
+        .filter_map(|(field, value)| Some((field, value?)))
+        .collect())
+}
+
+/// Apply a hand edit to a ROM and record the edited fields so enrichment
+/// keeps them. Fields in `edit.reset` go back to enrichment control; they
+/// keep their current value until the next refresh replaces it.
+pub async fn apply(db: &impl ConnectionTrait, rom_id: i64, edit: &MetadataEdit) -> AppResult<()> {
+    if let Some(field) = edit
+        .reset
+        .iter()
+        .find(|f| !EDITABLE_FIELDS.contains(&f.as_str()))
+    {
+        return Err(AppError::Other(format!("Unknown metadata field: {field}")));
+    }
+    for (field, value) in edited_values(edit)? {
+        db.execute(Statement::from_sql_and_values(
+            DatabaseBackend::Sqlite,
+            "INSERT INTO metadata_edits (rom_id, field, value) VALUES (?, ?, ?)
+             ON CONFLICT(rom_id, field) DO UPDATE SET
+               value = excluded.value,
+               edited_at = strftime('%Y-%m-%dT%H:%M:%fZ', 'now')",
+            [rom_id.into(), field.into(), value.to_string().into()],
+        ))
+        .await?;
+    }
+    for field in &edit.reset {
+        db.execute(Statement::from_sql_and_values(
+            DatabaseBackend::Sqlite,
+            "DELETE FROM metadata_edits WHERE rom_id = ? AND field = ?",
+            [rom_id.into(), field.clone().into()],
+        ))
+        .await?;
+    }
+    reapply(db, rom_id).await
+}
+
+/// Write a ROM's hand-edited values back over whatever enrichment or a sync stored.
+pub async fn reapply(db: &impl ConnectionTrait, rom_id: i64) -> AppResult<()> {
+    let edits = EditRow::find_by_statement(Statement::from_sql_and_values(
+        DatabaseBackend::Sqlite,
+        "SELECT field, value FROM metadata_edits WHERE rom_id = ?",
+        [rom_id.into()],
+    ))
+    .all(db)
+    .await?;
+    if edits.is_empty() {
+        return Ok(());
+    }
+
+    if edits.iter().any(|e| e.field != "name") {
+        db.execute(Statement::from_sql_and_values(
+            DatabaseBackend::Sqlite,
+            "INSERT INTO metadata (rom_id) VALUES (?) ON CONFLICT(rom_id) DO NOTHING",
+            [rom_id.into()],
+        ))
+        .await?;
+    }
+
+    for edit in edits {
+        let Ok(value) = serde_json::from_str::<serde_json::Value>(&edit.value) else {
+            log::warn!("Ignoring unreadable {} edit for rom {rom_id}", edit.field);
+            continue;
+        };
+        let text = value.as_str().map(str::to_string);
+        let (column, value): (&str, sea_orm::Value) = match edit.field.as_str() {
+            "name" => {
+                let Some(name) = text else {
+                    continue;
+                };
+                db.execute(Statement::from_sql_and_values(
+                    DatabaseBackend::Sqlite,
+                    "UPDATE roms SET name = ?, updated_at = strftime('%Y-%m-%dT%H:%M:%fZ', 'now')
+                     WHERE id = ?",
+                    [name.into(), rom_id.into()],
+                ))
+                .await?;
+                continue;
+            }
+            "description" => ("description", text.into()),
+            "genres" => ("genres", value.to_string().into()),
+            "release_date" => ("release_date", text.into()),
+            "rating" => ("rating", value.as_f64().into()),
+            other => {
+                log::warn!("Ignoring edit of unknown field {other} for rom {rom_id}");
+                continue;
+            }
+        };
+        db.execute(Statement::from_sql_and_values(
+            DatabaseBackend::Sqlite,
+            format!(
+                "UPDATE metadata SET {column} = ?, updated_at = strftime('%Y-%m-%dT%H:%M:%fZ', 'now')
+                 WHERE rom_id = ?"
+            ),
+            [value, rom_id.into()],
+        ))
+        .await?;
+    }
+    Ok(())
+}
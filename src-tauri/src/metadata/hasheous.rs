@@ -1,5 +1,7 @@
 use sea_orm::DatabaseConnection;
 
+use crate::http::{self, Provider};
+
 /// Extract a numeric ID from a `serde_json::Value` (handles both JSON numbers and strings).
 fn extract_id_i64(value: &serde_json::Value) -> Option<i64> {
     value
@@ -30,39 +32,84 @@ pub struct HasheousResult {
     pub raw_response: String,
 }
 
-/// Look up a ROM by its MD5 hash via the Hasheous public API.
-/// Returns `None` on 404 or network error.
-pub async fn lookup_by_md5(client: &reqwest::Client, md5: &str) -> Option<HasheousResult> {
-    let url = format!(
-        "https://hasheous.org/api/v1/Lookup/ByHash/md5/{md5}",
-    );
+/// Hashes a ROM can be looked up by. Any may be missing; Hasheous matches on
+/// whichever are sent.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct HashQuery<'a> {
+    pub md5: Option<&'a str>,
+    pub sha1: Option<&'a str>,
+    pub crc32: Option<&'a str>,
+}
 
-    let resp = match client.get(&url).send().await {
-        Ok(r) => r,
-        Err(e) => {
-            log::warn!("Hasheous HTTP request failed for md5 {md5}: {e}");
-            return None;
-        }
-    };
-    if !resp.status().is_success() {
-        return None;
+impl HashQuery<'_> {
+    /// The hashes as one string, to remember a lookup by. None when no hash
+    /// is known.
+    pub fn cache_key(&self) -> Option<String> {
+        let key: Vec<String> = [("md5", self.md5), ("sha1", self.sha1), ("crc", self.crc32)]
+            .into_iter()
+            .filter_map(|(name, hash)| {
+                let hash = hash.filter(|h| !h.is_empty())?;
+                Some(format!("{name}:{}", hash.to_lowercase()))
+            })
+            .collect();
+        (!key.is_empty()).then(|| key.join(" "))
     }
+}
+
+/// Outcome of a Hasheous lookup.
+pub enum Lookup {
+    Found(HasheousResult),
+    /// Hasheous doesn't know the hashes.
+    NotFound,
+    /// The request failed or the response couldn't be read; worth retrying.
+    Failed,
+}
 
-    let raw_response = match resp.text().await {
-        Ok(t) => t,
+/// Look up a ROM via the Hasheous public API, sending every known hash in one
+/// request. No hash known counts as not found.
+pub async fn lookup_by_hashes(client: &reqwest::Client, hashes: HashQuery<'_>) -> Lookup {
+    let body: serde_json::Map<String, serde_json::Value> =
+        [("md5", hashes.md5), ("sha1", hashes.sha1), ("crc", hashes.crc32)]
+            .into_iter()
+            .filter_map(|(key, value)| {
+                let value = value.filter(|v| !v.is_empty())?;
+                Some((key.to_string(), serde_json::Value::String(value.to_lowercase())))
+            })
+            .collect();
+    if body.is_empty() {
+        return Lookup::NotFound;
+    }
+    let label = serde_json::Value::Object(body.clone()).to_string();
+
+    let request = client
+        .post("https://hasheous.org/api/v1/Lookup/ByHash")
+        .json(&body);
+    let fetched = match http::fetch_text(Provider::Hasheous, request).await {
+        Ok(f) => f,
         Err(e) => {
-            log::warn!("Failed to read Hasheous response body for md5 {md5}: {e}");
-            return None;
+            log::warn!("Hasheous HTTP request failed for {label}: {e}");
+            return Lookup::Failed;
         }
     };
+    if fetched.status == reqwest::StatusCode::NOT_FOUND {
+        return Lookup::NotFound;
+    }
+    if !fetched.status.is_success() {
+        return Lookup::Failed;
+    }
+
+    let raw_response = fetched.body;
     let v: serde_json::Value = match serde_json::from_str(&raw_response) {
         Ok(v) => v,
         Err(e) => {
-            log::warn!("Failed to parse Hasheous JSON for md5 {md5}: {e}");
-            return None;
+            log::warn!("Failed to parse Hasheous JSON for {label}: {e}");
+            return Lookup::Failed;
         }
     };
+    parse_result(&v, raw_response).map_or(Lookup::NotFound, Lookup::Found)
+}
 
+fn parse_result(v: &serde_json::Value, raw_response: String) -> Option<HasheousResult> {
     let hasheous_id = v.get("id").and_then(serde_json::Value::as_i64);
     let name = v.get("name").and_then(serde_json::Value::as_str)?.to_string();
     let publisher = v
@@ -0,0 +1,254 @@
+use sea_orm::{
+    ConnectionTrait, DatabaseBackend, DatabaseConnection, FromQueryResult, Statement,
+    TransactionTrait,
+};
+use serde::Deserialize;
+
+use super::launchbox::normalize_for_match;
+use crate::entity::rom_homebrew::HomebrewKind;
+use crate::error::{AppError, AppResult};
+use crate::models::HomebrewInfo;
+
+/// One game in a homebrew/hack index. The index is a JSON array of these,
+/// or an object with them under `entries`.
+#[derive(Debug, Deserialize)]
+struct IndexEntry {
+    title: String,
+    /// Platform slug, e.g. `snes`. Entries without one match any platform.
+    platform: Option<String>,
+    author: Option<String>,
+    /// The commercial game a hack is made from.
+    base_game: Option<String>,
+    description: Option<String>,
+    release_date: Option<String>,
+    cover_url: Option<String>,
+    md5: Option<String>,
+    sha1: Option<String>,
+    crc32: Option<String>,
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum IndexFile {
+    List(Vec<IndexEntry>),
+    Wrapped { entries: Vec<IndexEntry> },
+}
+
+/// Row of `homebrew_index` matched to a ROM.
+#[derive(Debug, FromQueryResult)]
+pub struct IndexRow {
+    pub title: String,
+    pub author: Option<String>,
+    pub base_game: Option<String>,
+    pub description: Option<String>,
+    pub release_date: Option<String>,
+    pub cover_url: Option<String>,
+}
+
+/// Download the index at `url` and replace the stored one with it. ROMs
+/// marked as homebrew that the old index didn't match are queued for the
+/// next enrichment run. Returns the number of entries.
+pub async fn update_index(db: &DatabaseConnection, url: &str) -> AppResult<u64> {
+    crate::http::ensure_online("the homebrew index")?;
+    let client = crate::http::client_builder()
+        .user_agent("romm-buddy/0.1")
+        .timeout(std::time::Duration::from_secs(60))
+        .build()?;
+    let resp = client.get(url).send().await?;
+    if !resp.status().is_success() {
+        return Err(crate::http::status_error("homebrew index", &resp));
+    }
+    let entries = match resp.json::<IndexFile>().await {
+        Ok(IndexFile::List(entries) | IndexFile::Wrapped { entries }) => entries,
+        Err(e) => return Err(AppError::Other(format!("Invalid homebrew index: {e}"))),
+    };
+
+    let lower = |hash: Option<String>| {
+        hash.map(|h| h.trim().to_lowercase())
+            .filter(|h| !h.is_empty())
+    };
+    let txn = db.begin().await?;
+    txn.execute(Statement::from_string(
+        DatabaseBackend::Sqlite,
+        "DELETE FROM homebrew_index",
+    ))
+    .await?;
+    let mut count = 0;
+    for entry in entries {
+        let normalized = normalize_for_match(&entry.title);
+        if normalized.is_empty() {
+            continue;
+        }
+        txn.execute(Statement::from_sql_and_values(
+            DatabaseBackend::Sqlite,
+            "INSERT INTO homebrew_index
+                (title, name_normalized, platform, author, base_game, description,
+                 release_date, cover_url, md5, sha1, crc32)
+             VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)",
+            [
+                entry.title.into(),
+                normalized.into(),
+                entry.platform.into(),
+                entry.author.into(),
+                entry.base_game.into(),
+                entry.description.into(),
+                entry.release_date.into(),
+                entry.cover_url.into(),
+                lower(entry.md5).into(),
+                lower(entry.sha1).into(),
+                lower(entry.crc32).into(),
+            ],
+        ))
+        .await?;
+        count += 1;
+    }
+    txn.execute(Statement::from_string(
+        DatabaseBackend::Sqlite,
+        "UPDATE metadata SET metadata_fetched_at = NULL
+         WHERE rom_id IN (SELECT rom_id FROM rom_homebrew WHERE title IS NULL)",
+    ))
+    .await?;
+    txn.commit().await?;
+    Ok(count)
+}
+
+/// Mark a ROM as homebrew or a hack, or clear the mark with `None`. A newly
+/// marked ROM is looked up in the index on the next enrichment run.
+pub async fn set_kind(
+    db: &DatabaseConnection,
+    rom_id: i64,
+    kind: Option<HomebrewKind>,
+) -> AppResult<()> {
+    let Some(kind) = kind else {
+        db.execute(Statement::from_sql_and_values(
+            DatabaseBackend::Sqlite,
+            "DELETE FROM rom_homebrew WHERE rom_id = ?",
+            [rom_id.into()],
+        ))
+        .await?;
+        return Ok(());
+    };
+    let result = db
+        .execute(Statement::from_sql_and_values(
+            DatabaseBackend::Sqlite,
+            "INSERT INTO rom_homebrew (rom_id, kind) SELECT id, ? FROM roms WHERE id = ?
+             ON CONFLICT(rom_id) DO UPDATE SET kind = excluded.kind",
+            [kind.into(), rom_id.into()],
+        ))
+        .await?;
+    if result.rows_affected() == 0 {
+        return Err(AppError::NotFound(format!("ROM {rom_id}")));
+    }
+    db.execute(Statement::from_sql_and_values(
+        DatabaseBackend::Sqlite,
+        "UPDATE metadata SET metadata_fetched_at = NULL WHERE rom_id = ?",
+        [rom_id.into()],
+    ))
+    .await?;
+    Ok(())
+}
+
+/// The homebrew mark of a ROM and what the index said about it.
+pub async fn info(db: &DatabaseConnection, rom_id: i64) -> AppResult<Option<HomebrewInfo>> {
+    use crate::entity::rom_homebrew;
+    use sea_orm::EntityTrait;
+
+    Ok(rom_homebrew::Entity::find_by_id(rom_id)
+        .one(db)
+        .await?
+        .map(|m| HomebrewInfo {
+            kind: m.kind,
+            title: m.title,
+            author: m.author,
+            base_game: m.base_game,
+        }))
+}
+
+/// Find a ROM in the index: by hash first, then by normalized name on its
+/// platform (or entries for any platform).
+pub async fn find(
+    db: &DatabaseConnection,
+    hashes: super::hasheous::HashQuery<'_>,
+    name: &str,
+    platform_slug: &str,
+) -> Option<IndexRow> {
+    const COLUMNS: &str =
+        "SELECT title, author, base_game, description, release_date, cover_url FROM homebrew_index";
+
+    for (column, hash) in [
+        ("md5", hashes.md5),
+        ("sha1", hashes.sha1),
+        ("crc32", hashes.crc32),
+    ] {
+        let Some(hash) = hash.filter(|h| !h.is_empty()) else {
+            continue;
+        };
+        let row = IndexRow::find_by_statement(Statement::from_sql_and_values(
+            DatabaseBackend::Sqlite,
+            format!("{COLUMNS} WHERE {column} = ? LIMIT 1"),
+            [hash.to_lowercase().into()],
+        ))
+        .one(db)
+        .await
+        .ok()
+        .flatten();
+        if row.is_some() {
+            return row;
+        }
+    }
+
+    IndexRow::find_by_statement(Statement::from_sql_and_values(
+        DatabaseBackend::Sqlite,
+        format!(
+            "{COLUMNS} WHERE name_normalized = ? AND (platform IS NULL OR platform = ?)
+             ORDER BY platform IS NULL LIMIT 1"
+        ),
+        [normalize_for_match(name).into(), platform_slug.into()],
+    ))
+    .one(db)
+    .await
+    .ok()
+    .flatten()
+}
+
+/// Store what the index says about a ROM: title, author and base game on
+/// its homebrew mark, and the rest as metadata where none is set yet.
+pub async fn apply(db: &DatabaseConnection, rom_id: i64, entry: &IndexRow) {
+    if let Err(e) = db
+        .execute(Statement::from_sql_and_values(
+            DatabaseBackend::Sqlite,
+            "UPDATE rom_homebrew SET title = ?, author = ?, base_game = ? WHERE rom_id = ?",
+            [
+                entry.title.clone().into(),
+                entry.author.clone().into(),
+                entry.base_game.clone().into(),
+                rom_id.into(),
+            ],
+        ))
+        .await
+    {
+        log::warn!("Failed to store homebrew match for rom {rom_id}: {e}");
+    }
+
+    if let Err(e) = db
+        .execute(Statement::from_sql_and_values(
+            DatabaseBackend::Sqlite,
+            "INSERT INTO metadata (rom_id, description, developer, release_date, metadata_fetched_at)
+             VALUES (?, ?, ?, ?, strftime('%Y-%m-%dT%H:%M:%fZ', 'now'))
+             ON CONFLICT(rom_id) DO UPDATE SET
+               description = COALESCE(metadata.description, excluded.description),
+               developer = COALESCE(metadata.developer, excluded.developer),
+               release_date = COALESCE(metadata.release_date, excluded.release_date),
+               metadata_fetched_at = strftime('%Y-%m-%dT%H:%M:%fZ', 'now')",
+            [
+                rom_id.into(),
+                entry.description.clone().into(),
+                entry.author.clone().into(),
+                entry.release_date.clone().into(),
+            ],
+        ))
+        .await
+    {
+        log::warn!("Failed to upsert homebrew metadata for rom {rom_id}: {e}");
+    }
+}
@@ -5,6 +5,7 @@ use serde::{Deserialize, Serialize};
 use tokio::sync::{RwLock, Semaphore};
 
 use crate::error::{AppError, AppResult};
+use crate::http::{self, Provider};
 
 // ---------------------------------------------------------------------------
 // IGDB response types
@@ -26,6 +27,7 @@ pub struct IgdbGameData {
     pub screenshots: Option<Vec<IgdbImage>>,
     pub involved_companies: Option<Vec<IgdbInvolvedCompany>>,
     pub franchises: Option<Vec<IgdbNamedItem>>,
+    pub age_ratings: Option<Vec<IgdbAgeRating>>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -47,6 +49,14 @@ pub struct IgdbInvolvedCompany {
     pub publisher: Option<bool>,
 }
 
+/// `category`: 1 = ESRB, 2 = PEGI. `rating`: 1-5 = PEGI 3/7/12/16/18,
+/// 7-12 = ESRB EC/E/E10+/T/M/AO.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct IgdbAgeRating {
+    pub category: Option<i64>,
+    pub rating: Option<i64>,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct IgdbCompany {
     pub id: Option<i64>,
@@ -72,7 +82,6 @@ pub struct IgdbClient {
     http: reqwest::Client,
     token: Arc<RwLock<Option<TokenState>>>,
     semaphore: Arc<Semaphore>,
-    last_request: Arc<RwLock<Instant>>,
 }
 
 impl IgdbClient {
@@ -80,14 +89,13 @@ impl IgdbClient {
         Self {
             client_id,
             client_secret,
-            http: reqwest::Client::builder()
+            http: http::client_builder()
                 .user_agent("romm-buddy/0.1")
                 .timeout(std::time::Duration::from_secs(30))
                 .build()
                 .unwrap_or_default(),
             token: Arc::new(RwLock::new(None)),
             semaphore: Arc::new(Semaphore::new(4)),
-            last_request: Arc::new(RwLock::new(Instant::now() - std::time::Duration::from_secs(1))),
         }
     }
 
@@ -118,21 +126,22 @@ impl IgdbClient {
             expires_in: u64,
         }
 
-        let resp = self
-            .http
-            .post("https://id.twitch.tv/oauth2/token")
-            .form(&[
-                ("client_id", self.client_id.as_str()),
-                ("client_secret", self.client_secret.as_str()),
-                ("grant_type", "client_credentials"),
-            ])
-            .send()
-            .await
-            .map_err(|e| AppError::Other(format!("IGDB token request failed: {e}")))?;
+        let request = self.http.post("https://id.twitch.tv/oauth2/token").form(&[
+            ("client_id", self.client_id.as_str()),
+            ("client_secret", self.client_secret.as_str()),
+            ("grant_type", "client_credentials"),
+        ]);
+        let resp = http::send(Provider::Igdb, request).await?;
 
         if !resp.status().is_success() {
             let status = resp.status();
             let body = resp.text().await.unwrap_or_default();
+            // Twitch answers a wrong client ID with 403 and a wrong secret with 400
+            if matches!(status.as_u16(), 400 | 401 | 403) {
+                return Err(AppError::Auth(format!(
+                    "IGDB rejected the client ID or secret ({status}): {body}"
+                )));
+            }
             return Err(AppError::Other(format!(
                 "IGDB token request returned {status}: {body}"
             )));
@@ -155,7 +164,7 @@ impl IgdbClient {
         Ok(access_token)
     }
 
-    /// Rate-limited query to IGDB API.
+    /// Rate-limited query to IGDB API (at most 4 open requests, 4 per second).
     async fn query(&self, endpoint: &str, body: &str) -> AppResult<String> {
         let _permit = self
             .semaphore
@@ -163,42 +172,28 @@ impl IgdbClient {
             .await
             .map_err(|e| AppError::Other(format!("Semaphore error: {e}")))?;
 
-        // Enforce minimum 250ms between requests (single write lock to prevent races)
-        {
-            let mut last = self.last_request.write().await;
-            let elapsed = last.elapsed();
-            let min_interval = std::time::Duration::from_millis(250);
-            if elapsed < min_interval {
-                tokio::time::sleep(min_interval - elapsed).await;
-            }
-            *last = Instant::now();
-        }
-
         let token = self.ensure_token().await?;
         let url = format!("https://api.igdb.com/v4/{endpoint}");
 
-        let resp = self
+        let request = self
             .http
             .post(&url)
             .header("Client-ID", &self.client_id)
             .header("Authorization", format!("Bearer {token}"))
             .header("Content-Type", "text/plain")
-            .body(body.to_string())
-            .send()
+            .body(body.to_string());
+        let fetched = http::fetch_text(Provider::Igdb, request)
             .await
             .map_err(|e| AppError::Other(format!("IGDB API request failed: {e}")))?;
 
-        if !resp.status().is_success() {
-            let status = resp.status();
-            let body = resp.text().await.unwrap_or_default();
+        if !fetched.status.is_success() {
             return Err(AppError::Other(format!(
-                "IGDB API returned {status}: {body}"
+                "IGDB API returned {}: {}",
+                fetched.status, fetched.body
             )));
         }
 
-        resp.text()
-            .await
-            .map_err(|e| AppError::Other(format!("Failed to read IGDB response: {e}")))
+        Ok(fetched.body)
     }
 
     /// Fetch multiple games by their IGDB IDs (batch query).
@@ -213,7 +208,7 @@ impl IgdbClient {
              genres.name, themes.name, game_modes.name, player_perspectives.name, \
              cover.image_id, screenshots.image_id, \
              involved_companies.company.name, involved_companies.developer, involved_companies.publisher, \
-             franchises.name; \
+             franchises.name, age_ratings.category, age_ratings.rating; \
              where id = ({}); \
              limit {};",
             id_list.join(","),
@@ -235,7 +230,7 @@ impl IgdbClient {
              genres.name, themes.name, game_modes.name, player_perspectives.name, \
              cover.image_id, screenshots.image_id, \
              involved_companies.company.name, involved_companies.developer, involved_companies.publisher, \
-             franchises.name; \
+             franchises.name, age_ratings.category, age_ratings.rating; \
              search \"{escaped}\"; \
              limit 1;"
         );
@@ -247,6 +242,12 @@ impl IgdbClient {
         Ok(games.into_iter().next())
     }
 
+    /// Fetch an access token, failing with [`AppError::Auth`] when Twitch
+    /// rejects the client ID or secret.
+    pub async fn check_auth(&self) -> AppResult<()> {
+        self.ensure_token().await.map(drop)
+    }
+
     /// Test connection by attempting token acquisition.
     pub async fn test_connection(&self) -> AppResult<crate::models::IgdbTestResult> {
         match self.ensure_token().await {
@@ -1,20 +1,31 @@
+use std::collections::HashSet;
 use std::path::PathBuf;
 
 use quick_xml::events::Event;
 use quick_xml::Reader;
 use sea_orm::{
-    ColumnTrait, ConnectionTrait, DatabaseBackend,
-    DatabaseConnection, EntityTrait, PaginatorTrait, QueryFilter, Statement,
+    ColumnTrait, Condition, ConnectionTrait, DatabaseBackend, DatabaseConnection, EntityTrait,
+    FromQueryResult, PaginatorTrait, QueryFilter, QuerySelect, Statement, TransactionTrait,
 };
 
-use crate::entity::{launchbox_games, launchbox_images};
+use crate::entity::{launchbox_alt_names, launchbox_games, launchbox_images, launchbox_import_state};
 use crate::error::{AppError, AppResult};
-use crate::models::ScanProgress;
+use crate::models::{LaunchBoxFuzzyMatch, ScanProgress};
 use crate::platform_registry;
 
+/// Fuzzy candidates less similar than this (trigram similarity, 0..1) are
+/// never used.
+pub const FUZZY_MATCH_THRESHOLD: f64 = 0.6;
+
+/// Most candidates scored by one fuzzy lookup.
+const FUZZY_CANDIDATE_LIMIT: u64 = 500;
+
 /// Row returned from `launchbox_games` queries.
 pub struct LaunchBoxRow {
     pub database_id: String,
+    pub name: String,
+    /// 1.0 for an exact normalized-name match, else the fuzzy similarity.
+    pub confidence: f64,
     pub overview: Option<String>,
     pub developer: Option<String>,
     pub publisher: Option<String>,
@@ -90,32 +101,90 @@ pub fn metadata_xml_path() -> PathBuf {
     launchbox_cache_dir().join("Metadata.xml")
 }
 
-/// Download `Metadata.zip` and extract `Metadata.xml` to cache.
+const STATUS_IMPORTING: &str = "importing";
+const STATUS_COMPLETE: &str = "complete";
+
+/// What [`download_and_extract`] did.
+pub enum DownloadOutcome {
+    /// `Metadata.xml` is ready for [`import_to_db`].
+    Extracted,
+    /// The remote database hasn't changed since the last complete import.
+    UpToDate,
+}
+
+async fn import_state(db: &DatabaseConnection) -> AppResult<Option<launchbox_import_state::Model>> {
+    Ok(launchbox_import_state::Entity::find_by_id(1).one(db).await?)
+}
+
+/// Whether a cancelled import left `Metadata.xml` behind to pick up again.
+pub async fn has_resumable_import(db: &DatabaseConnection) -> AppResult<bool> {
+    Ok(metadata_xml_path().exists()
+        && import_state(db)
+            .await?
+            .is_some_and(|s| s.status == STATUS_IMPORTING))
+}
+
+/// Download `Metadata.zip` and extract `Metadata.xml` to cache. Skipped when
+/// the server reports the file unchanged (ETag / Last-Modified) or the
+/// download hashes the same as the last complete import.
 pub async fn download_and_extract(
+    db: &DatabaseConnection,
     on_progress: impl Fn(ScanProgress) + Send,
     cancel: tokio_util::sync::CancellationToken,
-) -> AppResult<()> {
+) -> AppResult<DownloadOutcome> {
     use futures_util::StreamExt;
+    use md5::{Digest, Md5};
     use tokio::io::AsyncWriteExt;
 
+    crate::http::ensure_online("the LaunchBox database")?;
     let cache_dir = launchbox_cache_dir();
     tokio::fs::create_dir_all(&cache_dir).await?;
 
     let url = "https://gamesdb.launchbox-app.com/Metadata.zip";
-    let client = reqwest::Client::builder()
+    let client = crate::http::client_builder()
         .user_agent("romm-buddy/0.1")
         .timeout(std::time::Duration::from_secs(120))
         .build()
         .map_err(|e| AppError::Other(e.to_string()))?;
 
-    let resp = client.get(url).send().await?;
+    let previous = import_state(db)
+        .await?
+        .filter(|s| s.status == STATUS_COMPLETE);
+    let previous = if has_imported_db(db).await { previous } else { None };
+
+    let mut request = client.get(url);
+    if let Some(state) = &previous {
+        if let Some(etag) = &state.etag {
+            request = request.header(reqwest::header::IF_NONE_MATCH, etag);
+        }
+        if let Some(last_modified) = &state.last_modified {
+            request = request.header(reqwest::header::IF_MODIFIED_SINCE, last_modified);
+        }
+    }
+
+    let resp = request.send().await?;
+    if resp.status() == reqwest::StatusCode::NOT_MODIFIED {
+        on_progress(ScanProgress {
+            source_id: -1,
+            total: 1,
+            current: 1,
+            current_item: "LaunchBox database is up to date.".to_string(),
+        });
+        return Ok(DownloadOutcome::UpToDate);
+    }
     if !resp.status().is_success() {
-        return Err(AppError::Other(format!(
-            "Failed to download LaunchBox DB: {}",
-            resp.status()
-        )));
+        return Err(crate::http::status_error("LaunchBox", &resp));
     }
 
+    let header = |name: reqwest::header::HeaderName| {
+        resp.headers()
+            .get(name)
+            .and_then(|v| v.to_str().ok())
+            .map(str::to_string)
+    };
+    let etag = header(reqwest::header::ETAG);
+    let last_modified = header(reqwest::header::LAST_MODIFIED);
+
     let total_bytes = resp.content_length().unwrap_or(0);
     let mut downloaded: u64 = 0;
 
@@ -127,19 +196,24 @@ pub async fn download_and_extract(
     });
 
     let zip_path = cache_dir.join("Metadata.zip");
+    let part_path = cache_dir.join("Metadata.zip.part");
+    let mut hasher = Md5::new();
     {
-        let mut file = tokio::fs::File::create(&zip_path).await?;
+        let mut file = tokio::fs::File::create(&part_path).await?;
         let mut stream = resp.bytes_stream();
 
         while let Some(chunk) = stream.next().await {
             if cancel.is_cancelled() {
-                return Ok(());
+                drop(file);
+                let _ = tokio::fs::remove_file(&part_path).await;
+                return Err(AppError::Cancelled("LaunchBox download".to_string()));
             }
             let chunk = chunk?;
             #[allow(clippy::cast_possible_truncation)]
             {
                 downloaded += chunk.len() as u64;
             }
+            hasher.update(&chunk);
             file.write_all(&chunk).await?;
             on_progress(ScanProgress {
                 source_id: -1,
@@ -150,6 +224,28 @@ pub async fn download_and_extract(
         }
         file.flush().await?;
     }
+    tokio::fs::rename(&part_path, &zip_path).await?;
+    let content_md5 = format!("{:x}", hasher.finalize());
+
+    if previous
+        .as_ref()
+        .is_some_and(|s| s.content_md5.as_deref() == Some(content_md5.as_str()))
+    {
+        let _ = tokio::fs::remove_file(&zip_path).await;
+        db.execute(Statement::from_sql_and_values(
+            DatabaseBackend::Sqlite,
+            "UPDATE launchbox_import_state SET etag = ?, last_modified = ? WHERE id = 1",
+            [etag.into(), last_modified.into()],
+        ))
+        .await?;
+        on_progress(ScanProgress {
+            source_id: -1,
+            total: 1,
+            current: 1,
+            current_item: "LaunchBox database is up to date.".to_string(),
+        });
+        return Ok(DownloadOutcome::UpToDate);
+    }
 
     on_progress(ScanProgress {
         source_id: -1,
@@ -197,6 +293,24 @@ pub async fn download_and_extract(
         log::warn!("Failed to remove LaunchBox zip file: {e}");
     }
 
+    // A fresh file starts a fresh import
+    db.execute(Statement::from_sql_and_values(
+        DatabaseBackend::Sqlite,
+        "INSERT INTO launchbox_import_state (id, etag, last_modified, content_md5, status)
+         VALUES (1, ?, ?, ?, ?)
+         ON CONFLICT(id) DO UPDATE SET
+            etag = excluded.etag,
+            last_modified = excluded.last_modified,
+            content_md5 = excluded.content_md5,
+            status = excluded.status,
+            games_imported = 0,
+            images_imported = 0,
+            alt_names_imported = 0,
+            updated_at = strftime('%Y-%m-%dT%H:%M:%fZ', 'now')",
+        [etag.into(), last_modified.into(), content_md5.into(), STATUS_IMPORTING.into()],
+    ))
+    .await?;
+
     on_progress(ScanProgress {
         source_id: -1,
         total: 1,
@@ -204,30 +318,54 @@ pub async fn download_and_extract(
         current_item: "LaunchBox database ready.".to_string(),
     });
 
-    Ok(())
+    Ok(DownloadOutcome::Extracted)
 }
 
-/// Parse `Metadata.xml` and INSERT all games/images into `SQLite` tables.
-/// This replaces the old in-memory index approach.
+/// Parse `Metadata.xml` and INSERT all games/images/alternate names into `SQLite` tables.
+/// This replaces the old in-memory index approach. Progress is recorded per
+/// batch, so an import that was cancelled carries on from the last batch.
 pub async fn import_to_db(
     db: &DatabaseConnection,
     on_progress: impl Fn(ScanProgress) + Send + 'static,
+    cancel: tokio_util::sync::CancellationToken,
 ) -> AppResult<()> {
     let xml_path = metadata_xml_path();
     if !xml_path.exists() {
         return Err(AppError::Other("Metadata.xml not found. Download the LaunchBox database first.".to_string()));
     }
 
-    on_progress(ScanProgress {
-        source_id: -1,
-        total: 1,
-        current: 0,
-        current_item: "Clearing old LaunchBox data...".to_string(),
-    });
+    let resume = import_state(db)
+        .await?
+        .filter(|s| s.status == STATUS_IMPORTING)
+        .map(|s| (s.games_imported, s.images_imported, s.alt_names_imported))
+        .filter(|&done| done != (0, 0, 0));
 
-    // Clear existing data
-    launchbox_images::Entity::delete_many().exec(db).await?;
-    launchbox_games::Entity::delete_many().exec(db).await?;
+    if resume.is_none() {
+        on_progress(ScanProgress {
+            source_id: -1,
+            total: 1,
+            current: 0,
+            current_item: "Clearing old LaunchBox data...".to_string(),
+        });
+
+        // Clear existing data
+        launchbox_images::Entity::delete_many().exec(db).await?;
+        launchbox_alt_names::Entity::delete_many().exec(db).await?;
+        launchbox_games::Entity::delete_many().exec(db).await?;
+        db.execute(Statement::from_sql_and_values(
+            DatabaseBackend::Sqlite,
+            "INSERT INTO launchbox_import_state (id, status) VALUES (1, ?)
+             ON CONFLICT(id) DO UPDATE SET
+                status = excluded.status,
+                games_imported = 0,
+                images_imported = 0,
+                alt_names_imported = 0,
+                updated_at = strftime('%Y-%m-%dT%H:%M:%fZ', 'now')",
+            [STATUS_IMPORTING.into()],
+        ))
+        .await?;
+    }
+    let (games_done, images_done, alt_names_done) = resume.unwrap_or_default();
 
     on_progress(ScanProgress {
         source_id: -1,
@@ -236,10 +374,10 @@ pub async fn import_to_db(
         current_item: "Parsing Metadata.xml...".to_string(),
     });
 
-    // Parse XML in a blocking task and collect games + images
+    // Parse XML in a blocking task and collect games + images + alternate names
     let xml_path_clone = xml_path.clone();
-    let (games, images) = tokio::task::spawn_blocking(move || -> AppResult<(Vec<GameRecord>, Vec<ImageRecord>)> {
-        enum Section { None, Game, GameImage }
+    let (games, images, alt_names) = tokio::task::spawn_blocking(move || -> AppResult<(Vec<GameRecord>, Vec<ImageRecord>, Vec<AltNameRecord>)> {
+        enum Section { None, Game, GameImage, AltName }
 
         let file = std::fs::File::open(&xml_path_clone)?;
         let reader_buf = std::io::BufReader::with_capacity(256 * 1024, file);
@@ -248,6 +386,7 @@ pub async fn import_to_db(
 
         let mut games: Vec<GameRecord> = Vec::new();
         let mut images: Vec<ImageRecord> = Vec::new();
+        let mut alt_names: Vec<AltNameRecord> = Vec::new();
         let mut buf = Vec::with_capacity(4096);
 
         let mut section = Section::None;
@@ -268,6 +407,11 @@ pub async fn import_to_db(
         let mut i_file_name = String::new();
         let mut i_type = String::new();
 
+        // Alternate name fields
+        let mut a_db_id = String::new();
+        let mut a_name = String::new();
+        let mut a_region: Option<String> = None;
+
         let mut current_element = String::new();
 
         loop {
@@ -293,6 +437,12 @@ pub async fn import_to_db(
                             i_file_name.clear();
                             i_type.clear();
                         }
+                        "GameAlternateName" => {
+                            section = Section::AltName;
+                            a_db_id.clear();
+                            a_name.clear();
+                            a_region = None;
+                        }
                         _ => {}
                     }
                     current_element = tag;
@@ -325,6 +475,12 @@ pub async fn import_to_db(
                             "Type" => i_type = text,
                             _ => {}
                         },
+                        Section::AltName => match current_element.as_str() {
+                            "DatabaseID" => a_db_id = text,
+                            "AlternateName" => a_name = text,
+                            "Region" => a_region = Some(text),
+                            _ => {}
+                        },
                         Section::None => {}
                     }
                 }
@@ -359,6 +515,18 @@ pub async fn import_to_db(
                             }
                             section = Section::None;
                         }
+                        "GameAlternateName" => {
+                            if !a_db_id.is_empty() && !a_name.is_empty() {
+                                let name_normalized = normalize_for_match(&a_name);
+                                alt_names.push(AltNameRecord {
+                                    database_id: std::mem::take(&mut a_db_id),
+                                    name: std::mem::take(&mut a_name),
+                                    name_normalized,
+                                    region: a_region.take(),
+                                });
+                            }
+                            section = Section::None;
+                        }
                         _ => {}
                     }
                     current_element.clear();
@@ -372,7 +540,7 @@ pub async fn import_to_db(
             buf.clear();
         }
 
-        Ok((games, images))
+        Ok((games, images, alt_names))
     })
     .await
     .map_err(|e| AppError::Other(format!("Task join error: {e}")))?
@@ -390,9 +558,16 @@ pub async fn import_to_db(
         current_item: format!("Importing {total_games} games..."),
     });
 
+    let cancelled = || AppError::Cancelled("LaunchBox import".to_string());
+    let skip = |done: i64, len: usize| usize::try_from(done).unwrap_or(0).min(len);
+
     // Batch insert games using multi-row VALUES for performance
-    let mut count: u64 = 0;
-    for chunk in games.chunks(500) {
+    #[allow(clippy::cast_possible_truncation)]
+    let mut count = skip(games_done, games.len()) as u64;
+    for chunk in games[skip(games_done, games.len())..].chunks(500) {
+        if cancel.is_cancelled() {
+            return Err(cancelled());
+        }
         let mut query = String::from(
             "INSERT INTO launchbox_games (database_id, name, name_normalized, platform, overview, developer, publisher, genres, release_date, community_rating) VALUES ",
         );
@@ -413,7 +588,7 @@ pub async fn import_to_db(
                 game.community_rating.into(),
             ]);
         }
-        db.execute(Statement::from_sql_and_values(DatabaseBackend::Sqlite, &query, values)).await?;
+        insert_batch(db, &query, values, "games_imported", chunk.len()).await?;
         #[allow(clippy::cast_possible_truncation)]
         {
             count += chunk.len() as u64;
@@ -434,8 +609,14 @@ pub async fn import_to_db(
     });
 
     // Batch insert images using multi-row VALUES for performance
-    count = 0;
-    for chunk in images.chunks(1000) {
+    #[allow(clippy::cast_possible_truncation)]
+    {
+        count = skip(images_done, images.len()) as u64;
+    }
+    for chunk in images[skip(images_done, images.len())..].chunks(1000) {
+        if cancel.is_cancelled() {
+            return Err(cancelled());
+        }
         let mut query = String::from(
             "INSERT INTO launchbox_images (database_id, file_name, image_type) VALUES ",
         );
@@ -449,7 +630,7 @@ pub async fn import_to_db(
                 img.image_type.clone().into(),
             ]);
         }
-        db.execute(Statement::from_sql_and_values(DatabaseBackend::Sqlite, &query, values)).await?;
+        insert_batch(db, &query, values, "images_imported", chunk.len()).await?;
         #[allow(clippy::cast_possible_truncation)]
         {
             count += chunk.len() as u64;
@@ -462,6 +643,43 @@ pub async fn import_to_db(
         });
     }
 
+    on_progress(ScanProgress {
+        source_id: -1,
+        total: total_games + total_images,
+        current: total_games + total_images,
+        current_item: format!("Importing {} alternate names...", alt_names.len()),
+    });
+
+    for chunk in alt_names[skip(alt_names_done, alt_names.len())..].chunks(1000) {
+        if cancel.is_cancelled() {
+            return Err(cancelled());
+        }
+        let mut query = String::from(
+            "INSERT INTO launchbox_alt_names (database_id, name, name_normalized, region) VALUES ",
+        );
+        let mut values: Vec<sea_orm::Value> = Vec::with_capacity(chunk.len() * 4);
+        for (i, alt) in chunk.iter().enumerate() {
+            if i > 0 { query.push(','); }
+            query.push_str("(?, ?, ?, ?)");
+            values.extend_from_slice(&[
+                alt.database_id.clone().into(),
+                alt.name.clone().into(),
+                alt.name_normalized.clone().into(),
+                alt.region.clone().into(),
+            ]);
+        }
+        insert_batch(db, &query, values, "alt_names_imported", chunk.len()).await?;
+    }
+
+    db.execute(Statement::from_sql_and_values(
+        DatabaseBackend::Sqlite,
+        "UPDATE launchbox_import_state
+         SET status = ?, updated_at = strftime('%Y-%m-%dT%H:%M:%fZ', 'now')
+         WHERE id = 1",
+        [STATUS_COMPLETE.into()],
+    ))
+    .await?;
+
     // Clean up Metadata.xml after import
     if let Err(e) = tokio::fs::remove_file(&xml_path).await {
         log::warn!("Failed to remove Metadata.xml after import: {e}");
@@ -477,6 +695,32 @@ pub async fn import_to_db(
     Ok(())
 }
 
+/// Insert one batch and advance the matching import counter in the same
+/// transaction, so a cancelled import knows exactly where to resume.
+async fn insert_batch(
+    db: &DatabaseConnection,
+    query: &str,
+    values: Vec<sea_orm::Value>,
+    counter: &str,
+    rows: usize,
+) -> AppResult<()> {
+    let txn = db.begin().await?;
+    txn.execute(Statement::from_sql_and_values(DatabaseBackend::Sqlite, query, values))
+        .await?;
+    txn.execute(Statement::from_sql_and_values(
+        DatabaseBackend::Sqlite,
+        format!(
+            "UPDATE launchbox_import_state
+             SET {counter} = {counter} + ?, updated_at = strftime('%Y-%m-%dT%H:%M:%fZ', 'now')
+             WHERE id = 1"
+        ),
+        [i64::try_from(rows).unwrap_or(i64::MAX).into()],
+    ))
+    .await?;
+    txn.commit().await?;
+    Ok(())
+}
+
 /// Check if `launchbox_games` table has data.
 pub async fn has_imported_db(db: &DatabaseConnection) -> bool {
     launchbox_games::Entity::find()
@@ -522,13 +766,99 @@ pub async fn find_by_name(
         }
     }
 
-    None
+    // Try regional/alternate titles (e.g. a Japanese ROM of a game listed
+    // under its US name)
+    let model = launchbox_games::Entity::find()
+        .from_raw_sql(Statement::from_sql_and_values(
+            DatabaseBackend::Sqlite,
+            "SELECT g.* FROM launchbox_alt_names a
+             JOIN launchbox_games g ON g.database_id = a.database_id
+             WHERE a.name_normalized IN (?, ?) AND g.platform = ?
+             ORDER BY g.id
+             LIMIT 1",
+            [normalized.clone().into(), no_dash.clone().into(), lb_platform.into()],
+        ))
+        .one(db)
+        .await
+        .ok()?;
+
+    if let Some(m) = model {
+        return Some(model_to_row(m));
+    }
+
+    find_fuzzy(db, &no_dash, lb_platform).await
+}
+
+/// Best candidate by trigram similarity among games whose normalized name
+/// starts with the same word (ignoring a leading "the"), if any clears
+/// `FUZZY_MATCH_THRESHOLD`.
+async fn find_fuzzy(
+    db: &DatabaseConnection,
+    normalized: &str,
+    lb_platform: &str,
+) -> Option<LaunchBoxRow> {
+    let key = strip_leading_article(normalized);
+    let first_word = key.split(' ').next().filter(|w| !w.is_empty())?;
+
+    // Range scans so the name_normalized index is used
+    let starts_with = |prefix: String| {
+        Condition::all()
+            .add(launchbox_games::Column::NameNormalized.gte(prefix.clone()))
+            .add(launchbox_games::Column::NameNormalized.lt(format!("{prefix}\u{10FFFF}")))
+    };
+    let candidates = launchbox_games::Entity::find()
+        .filter(launchbox_games::Column::Platform.eq(lb_platform))
+        .filter(
+            Condition::any()
+                .add(starts_with(first_word.to_string()))
+                .add(starts_with(format!("the {first_word}"))),
+        )
+        .limit(FUZZY_CANDIDATE_LIMIT)
+        .all(db)
+        .await
+        .ok()?;
+
+    let wanted = trigrams(key);
+    candidates
+        .into_iter()
+        .map(|m| {
+            let score = similarity(&wanted, &trigrams(strip_leading_article(&m.name_normalized)));
+            (m, score)
+        })
+        .filter(|(_, score)| *score >= FUZZY_MATCH_THRESHOLD)
+        .max_by(|(_, a), (_, b)| a.total_cmp(b))
+        .map(|(m, score)| LaunchBoxRow {
+            confidence: score,
+            ..model_to_row(m)
+        })
+}
+
+fn strip_leading_article(normalized: &str) -> &str {
+    normalized.strip_prefix("the ").unwrap_or(normalized)
+}
+
+/// Character trigrams of a normalized name, padded so word edges count.
+fn trigrams(name: &str) -> HashSet<[char; 3]> {
+    let padded: Vec<char> = format!("  {name} ").chars().collect();
+    padded.windows(3).map(|w| [w[0], w[1], w[2]]).collect()
+}
+
+/// Jaccard similarity of two trigram sets.
+#[allow(clippy::cast_precision_loss)]
+fn similarity(a: &HashSet<[char; 3]>, b: &HashSet<[char; 3]>) -> f64 {
+    let union = a.union(b).count();
+    if union == 0 {
+        return 0.0;
+    }
+    a.intersection(b).count() as f64 / union as f64
 }
 
 /// Convert a `launchbox_games::Model` to a `LaunchBoxRow`.
 fn model_to_row(m: launchbox_games::Model) -> LaunchBoxRow {
     LaunchBoxRow {
         database_id: m.database_id,
+        name: m.name,
+        confidence: 1.0,
         overview: m.overview,
         developer: m.developer,
         publisher: m.publisher,
@@ -538,6 +868,133 @@ fn model_to_row(m: launchbox_games::Model) -> LaunchBoxRow {
     }
 }
 
+/// Whether the user rejected `database_id` as the match for a ROM.
+pub async fn is_rejected(db: &DatabaseConnection, rom_id: i64, database_id: &str) -> bool {
+    db.query_one(Statement::from_sql_and_values(
+        DatabaseBackend::Sqlite,
+        "SELECT 1 FROM launchbox_matches WHERE rom_id = ? AND database_id = ? AND rejected = 1",
+        [rom_id.into(), database_id.into()],
+    ))
+    .await
+    .ok()
+    .flatten()
+    .is_some()
+}
+
+/// Remember how a ROM was matched: fuzzy matches are kept for review, an
+/// exact match replaces any earlier unreviewed fuzzy one.
+pub async fn record_match(db: &DatabaseConnection, rom_id: i64, game: &LaunchBoxRow) {
+    let stmt = if game.confidence < 1.0 {
+        Statement::from_sql_and_values(
+            DatabaseBackend::Sqlite,
+            "INSERT INTO launchbox_matches (rom_id, database_id, matched_name, confidence)
+             VALUES (?, ?, ?, ?)
+             ON CONFLICT(rom_id) DO UPDATE SET
+                database_id = excluded.database_id,
+                matched_name = excluded.matched_name,
+                confidence = excluded.confidence,
+                created_at = strftime('%Y-%m-%dT%H:%M:%fZ', 'now')
+             WHERE launchbox_matches.rejected = 0",
+            [
+                rom_id.into(),
+                game.database_id.clone().into(),
+                game.name.clone().into(),
+                game.confidence.into(),
+            ],
+        )
+    } else {
+        Statement::from_sql_and_values(
+            DatabaseBackend::Sqlite,
+            "DELETE FROM launchbox_matches WHERE rom_id = ? AND rejected = 0",
+            [rom_id.into()],
+        )
+    };
+    if let Err(e) = db.execute(stmt).await {
+        log::warn!("Failed to record LaunchBox match for rom {rom_id}: {e}");
+    }
+}
+
+/// Fuzzy matches, least confident first.
+pub async fn fuzzy_matches(
+    db: &DatabaseConnection,
+    include_rejected: bool,
+) -> AppResult<Vec<LaunchBoxFuzzyMatch>> {
+    #[derive(Debug, FromQueryResult)]
+    struct MatchRow {
+        rom_id: i64,
+        rom_name: String,
+        platform_slug: String,
+        database_id: String,
+        matched_name: String,
+        confidence: f64,
+        rejected: bool,
+    }
+
+    let rows = MatchRow::find_by_statement(Statement::from_sql_and_values(
+        DatabaseBackend::Sqlite,
+        "SELECT m.rom_id, r.name AS rom_name, p.slug AS platform_slug, m.database_id,
+                m.matched_name, m.confidence, m.rejected
+         FROM launchbox_matches m
+         JOIN roms r ON r.id = m.rom_id
+         JOIN platforms p ON p.id = r.platform_id
+         WHERE ? OR m.rejected = 0
+         ORDER BY m.confidence, r.name",
+        [include_rejected.into()],
+    ))
+    .all(db)
+    .await?;
+
+    Ok(rows
+        .into_iter()
+        .map(|r| LaunchBoxFuzzyMatch {
+            rom_id: r.rom_id,
+            rom_name: r.rom_name,
+            platform_slug: r.platform_slug,
+            database_id: r.database_id,
+            matched_name: r.matched_name,
+            confidence: r.confidence,
+            rejected: r.rejected,
+        })
+        .collect())
+}
+
+/// Reject a ROM's fuzzy match: keep it from being used again and remove the
+/// metadata and artwork it contributed.
+pub async fn reject_match(db: &DatabaseConnection, rom_id: i64) -> AppResult<()> {
+    let txn = db.begin().await?;
+    txn.execute(Statement::from_sql_and_values(
+        DatabaseBackend::Sqlite,
+        "UPDATE launchbox_matches SET rejected = 1 WHERE rom_id = ?",
+        [rom_id.into()],
+    ))
+    .await?;
+    // Only clear fields that still hold the LaunchBox values
+    txn.execute(Statement::from_sql_and_values(
+        DatabaseBackend::Sqlite,
+        "UPDATE metadata SET
+            description = CASE WHEN description = g.overview THEN NULL ELSE description END,
+            developer = CASE WHEN developer = g.developer THEN NULL ELSE developer END,
+            publisher = CASE WHEN publisher = g.publisher THEN NULL ELSE publisher END,
+            genres = CASE WHEN genres = g.genres THEN '[]' ELSE genres END,
+            release_date = CASE WHEN release_date = g.release_date THEN NULL ELSE release_date END,
+            rating = CASE WHEN rating = g.community_rating THEN NULL ELSE rating END
+         FROM (SELECT lg.* FROM launchbox_games lg
+               JOIN launchbox_matches m ON m.database_id = lg.database_id
+               WHERE m.rom_id = ? LIMIT 1) AS g
+         WHERE metadata.rom_id = ?",
+        [rom_id.into(), rom_id.into()],
+    ))
+    .await?;
+    txn.execute(Statement::from_sql_and_values(
+        DatabaseBackend::Sqlite,
+        "DELETE FROM artwork WHERE rom_id = ? AND provider = 'launchbox'",
+        [rom_id.into()],
+    ))
+    .await?;
+    txn.commit().await?;
+    Ok(())
+}
+
 /// Get the best cover image URL for a `LaunchBox` `database_id`.
 pub async fn get_image_url(db: &DatabaseConnection, database_id: &str) -> Option<String> {
     let result = db
@@ -555,8 +1012,6 @@ pub async fn get_image_url(db: &DatabaseConnection, database_id: &str) -> Option
 
 /// Get screenshot image URLs for a `LaunchBox` `database_id`.
 pub async fn get_screenshot_urls(db: &DatabaseConnection, database_id: &str) -> Vec<String> {
-    use sea_orm::QuerySelect;
-
     let models = launchbox_images::Entity::find()
         .filter(launchbox_images::Column::DatabaseId.eq(database_id))
         .filter(launchbox_images::Column::ImageType.contains("Screenshot"))
@@ -589,3 +1044,10 @@ struct ImageRecord {
     file_name: String,
     image_type: String,
 }
+
+struct AltNameRecord {
+    database_id: String,
+    name: String,
+    name_normalized: String,
+    region: Option<String>,
+}
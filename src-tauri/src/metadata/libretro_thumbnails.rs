@@ -1,7 +1,32 @@
 use std::fmt::Write;
+use std::sync::LazyLock;
 
+use sea_orm::{
+    ConnectionTrait, DatabaseBackend, DatabaseConnection, EntityTrait, Statement,
+    TransactionTrait,
+};
+
+use crate::entity::libretro_thumbnail_dirs;
+use crate::error::{AppError, AppResult};
+use crate::http::{self, Provider};
+use crate::metadata::misses;
+use crate::models::MetadataProvider;
 use crate::platform_registry;
 
+/// Boxart/cover thumbnails.
+pub const BOXARTS: &str = "Named_Boxarts";
+/// In-game snapshots.
+pub const SNAPS: &str = "Named_Snaps";
+/// Title screens.
+pub const TITLES: &str = "Named_Titles";
+
+/// Re-download a directory listing once it is older than this.
+const INDEX_MAX_AGE_DAYS: i64 = 30;
+
+/// Only one listing download at a time; the next caller reuses its result.
+static INDEX_LOCK: LazyLock<tokio::sync::Mutex<()>> =
+    LazyLock::new(|| tokio::sync::Mutex::new(()));
+
 /// Sanitize a game name for use in a libretro thumbnail URL.
 /// Matches `RetroArch`'s character replacement: `&*/:`\"`<>?\|` -> `_`
 fn sanitize_name(name: &str) -> String {
@@ -30,30 +55,185 @@ fn encode_uri_component(s: &str) -> String {
     result
 }
 
-/// Build a libretro thumbnail URL for the given platform, game name, and category.
-/// Returns `None` if the platform is not in the slug map.
-fn build_url(platform_slug: &str, game_name: &str, category: &str) -> Option<String> {
-    let system = platform_registry::libretro_dir(platform_slug)?;
+fn dir_url(system: &str, category: &str) -> String {
+    format!(
+        "https://thumbnails.libretro.com/{}/{category}/",
+        encode_uri_component(system)
+    )
+}
+
+/// URLs of the `categories` thumbnails libretro has for a game, looked up in
+/// the cached directory listings. Falls back to a HEAD request per URL when a
+/// listing can't be downloaded, remembering the 404s.
+pub async fn available_urls(
+    db: &DatabaseConnection,
+    http_client: &reqwest::Client,
+    platform_slug: &str,
+    game_name: &str,
+    categories: &[&str],
+) -> Vec<String> {
+    let Some(system) = platform_registry::libretro_dir(platform_slug) else {
+        return Vec::new();
+    };
     let sanitized = sanitize_name(game_name);
-    Some(format!(
-        "https://thumbnails.libretro.com/{}/{}/{}.png",
-        encode_uri_component(system),
-        category,
-        encode_uri_component(&sanitized)
+
+    let mut urls = Vec::new();
+    for &category in categories {
+        let url = format!(
+            "{}{}.png",
+            dir_url(system, category),
+            encode_uri_component(&sanitized)
+        );
+        let available = if ensure_index(db, http_client, system, category).await {
+            is_indexed(db, system, category, &sanitized).await
+        } else if misses::is_known(db, MetadataProvider::Libretro, &url).await {
+            false
+        } else {
+            match http::fetch_text(Provider::Libretro, http_client.head(&url)).await {
+                Ok(f) if f.status == reqwest::StatusCode::NOT_FOUND => {
+                    misses::record(db, MetadataProvider::Libretro, &url).await;
+                    false
+                }
+                Ok(f) => f.status.is_success(),
+                Err(_) => false,
+            }
+        };
+        if available {
+            urls.push(url);
+        }
+    }
+    urls
+}
+
+async fn is_indexed(db: &DatabaseConnection, system: &str, category: &str, name: &str) -> bool {
+    db.query_one(Statement::from_sql_and_values(
+        DatabaseBackend::Sqlite,
+        "SELECT 1 FROM libretro_thumbnails WHERE system = ? AND category = ? AND name = ?",
+        [system.into(), category.into(), name.into()],
     ))
+    .await
+    .ok()
+    .flatten()
+    .is_some()
 }
 
-/// Build a libretro boxart/cover thumbnail URL.
-pub fn build_thumbnail_url(platform_slug: &str, game_name: &str) -> Option<String> {
-    build_url(platform_slug, game_name, "Named_Boxarts")
+/// Make sure a fresh listing of `system/category` is cached. Returns whether
+/// any listing (possibly stale, if refreshing failed) is available.
+async fn ensure_index(
+    db: &DatabaseConnection,
+    http_client: &reqwest::Client,
+    system: &str,
+    category: &str,
+) -> bool {
+    let cutoff = (chrono::Utc::now() - chrono::Duration::days(INDEX_MAX_AGE_DAYS))
+        .format("%Y-%m-%dT%H:%M:%S")
+        .to_string();
+    let cached = || async move {
+        libretro_thumbnail_dirs::Entity::find_by_id((system.to_string(), category.to_string()))
+            .one(db)
+            .await
+            .ok()
+            .flatten()
+    };
+
+    if cached().await.is_some_and(|d| d.fetched_at > cutoff) {
+        return true;
+    }
+    // Offline, an outdated listing beats none
+    if crate::http::is_offline() {
+        return cached().await.is_some();
+    }
+    let _guard = INDEX_LOCK.lock().await;
+    // Another caller may have refreshed it while we waited
+    let existing = cached().await;
+    if existing.as_ref().is_some_and(|d| d.fetched_at > cutoff) {
+        return true;
+    }
+
+    match refresh_index(db, http_client, system, category).await {
+        Ok(count) => {
+            log::info!("Indexed {count} libretro thumbnails for {system}/{category}");
+            true
+        }
+        Err(e) => {
+            log::warn!("Failed to index libretro thumbnails for {system}/{category}: {e}");
+            existing.is_some()
+        }
+    }
 }
 
-/// Build a libretro in-game snapshot URL.
-pub fn build_snapshot_url(platform_slug: &str, game_name: &str) -> Option<String> {
-    build_url(platform_slug, game_name, "Named_Snaps")
+/// Download a directory listing and replace the cached names with it.
+async fn refresh_index(
+    db: &DatabaseConnection,
+    http_client: &reqwest::Client,
+    system: &str,
+    category: &str,
+) -> AppResult<usize> {
+    let fetched = http::fetch_text(Provider::Libretro, http_client.get(dir_url(system, category)))
+        .await
+        .map_err(AppError::Other)?;
+    if !fetched.status.is_success() {
+        return Err(AppError::Other(format!(
+            "Directory listing returned {}",
+            fetched.status
+        )));
+    }
+    let names = parse_listing(&fetched.body);
+
+    let txn = db.begin().await?;
+    txn.execute(Statement::from_sql_and_values(
+        DatabaseBackend::Sqlite,
+        "DELETE FROM libretro_thumbnails WHERE system = ? AND category = ?",
+        [system.into(), category.into()],
+    ))
+    .await?;
+    for chunk in names.chunks(300) {
+        let placeholders = vec!["(?, ?, ?)"; chunk.len()].join(", ");
+        let values: Vec<sea_orm::Value> = chunk
+            .iter()
+            .flat_map(|name| {
+                [
+                    sea_orm::Value::from(system),
+                    sea_orm::Value::from(category),
+                    sea_orm::Value::from(name.as_str()),
+                ]
+            })
+            .collect();
+        txn.execute(Statement::from_sql_and_values(
+            DatabaseBackend::Sqlite,
+            format!(
+                "INSERT OR IGNORE INTO libretro_thumbnails (system, category, name) VALUES {placeholders}"
+            ),
+            values,
+        ))
+        .await?;
+    }
+    #[allow(clippy::cast_possible_wrap)]
+    let entry_count = names.len() as i64;
+    txn.execute(Statement::from_sql_and_values(
+        DatabaseBackend::Sqlite,
+        "INSERT INTO libretro_thumbnail_dirs (system, category, entry_count) VALUES (?, ?, ?)
+         ON CONFLICT(system, category) DO UPDATE SET
+            entry_count = excluded.entry_count,
+            fetched_at = strftime('%Y-%m-%dT%H:%M:%fZ', 'now')",
+        [system.into(), category.into(), entry_count.into()],
+    ))
+    .await?;
+    txn.commit().await?;
+    Ok(names.len())
 }
 
-/// Build a libretro title screen URL.
-pub fn build_title_url(platform_slug: &str, game_name: &str) -> Option<String> {
-    build_url(platform_slug, game_name, "Named_Titles")
+/// Thumbnail names (without `.png`) linked from an autoindex HTML page.
+fn parse_listing(html: &str) -> Vec<String> {
+    html.split("href=\"")
+        .skip(1)
+        .filter_map(|rest| rest.split('"').next())
+        .filter_map(|href| href.strip_suffix(".png"))
+        .filter(|href| !href.contains('/'))
+        .filter_map(|href| {
+            urlencoding::decode(&href.replace("&amp;", "&"))
+                .ok()
+                .map(std::borrow::Cow::into_owned)
+        })
+        .collect()
 }
@@ -0,0 +1,101 @@
+use sea_orm::{ConnectionTrait, DatabaseBackend, DatabaseConnection, Statement};
+
+use crate::error::AppResult;
+use crate::models::MetadataProvider;
+
+impl MetadataProvider {
+    fn as_str(self) -> &'static str {
+        match self {
+            Self::Hasheous => "hasheous",
+            Self::Igdb => "igdb",
+            Self::Libretro => "libretro",
+            Self::Screenscraper => "screenscraper",
+        }
+    }
+
+    /// How long a miss is trusted before the provider is asked again. Catalogs
+    /// that grow by hand-submitted entries are rechecked sooner.
+    pub(crate) fn miss_ttl_days(self) -> i64 {
+        match self {
+            Self::Hasheous => 30,
+            Self::Igdb => 30,
+            Self::Libretro => 14,
+            Self::Screenscraper => 60,
+        }
+    }
+}
+
+fn cutoff(provider: MetadataProvider) -> String {
+    (chrono::Utc::now() - chrono::Duration::days(provider.miss_ttl_days()))
+        .format("%Y-%m-%dT%H:%M:%S")
+        .to_string()
+}
+
+/// Whether `provider` had nothing for `key` within its TTL.
+pub async fn is_known(db: &DatabaseConnection, provider: MetadataProvider, key: &str) -> bool {
+    db.query_one(Statement::from_sql_and_values(
+        DatabaseBackend::Sqlite,
+        "SELECT 1 FROM provider_misses WHERE provider = ? AND lookup_key = ? AND missed_at > ?",
+        [
+            provider.as_str().into(),
+            key.into(),
+            cutoff(provider).into(),
+        ],
+    ))
+    .await
+    .ok()
+    .flatten()
+    .is_some()
+}
+
+/// Remember that `provider` had nothing for `key`.
+pub async fn record(db: &DatabaseConnection, provider: MetadataProvider, key: &str) {
+    if let Err(e) = db
+        .execute(Statement::from_sql_and_values(
+            DatabaseBackend::Sqlite,
+            "INSERT INTO provider_misses (provider, lookup_key) VALUES (?, ?)
+             ON CONFLICT(provider, lookup_key) DO UPDATE SET
+               missed_at = strftime('%Y-%m-%dT%H:%M:%fZ', 'now')",
+            [provider.as_str().into(), key.into()],
+        ))
+        .await
+    {
+        log::warn!("Failed to cache {} miss for {key}: {e}", provider.as_str());
+    }
+}
+
+/// Forget a miss once the provider has the lookup after all.
+pub async fn forget(db: &DatabaseConnection, provider: MetadataProvider, key: &str) {
+    let _ = db
+        .execute(Statement::from_sql_and_values(
+            DatabaseBackend::Sqlite,
+            "DELETE FROM provider_misses WHERE provider = ? AND lookup_key = ?",
+            [provider.as_str().into(), key.into()],
+        ))
+        .await;
+}
+
+/// Drop every cached miss of `provider`, or of all providers, so the next
+/// enrichment run asks again. ScreenScraper's misses live in its own cache.
+/// Returns the number of entries dropped.
+pub async fn clear(db: &DatabaseConnection, provider: Option<MetadataProvider>) -> AppResult<u64> {
+    let name = provider.map(MetadataProvider::as_str);
+    let mut cleared = db
+        .execute(Statement::from_sql_and_values(
+            DatabaseBackend::Sqlite,
+            "DELETE FROM provider_misses WHERE ? IS NULL OR provider = ?",
+            [name.into(), name.into()],
+        ))
+        .await?
+        .rows_affected();
+    if matches!(provider, None | Some(MetadataProvider::Screenscraper)) {
+        cleared += db
+            .execute(Statement::from_string(
+                DatabaseBackend::Sqlite,
+                "DELETE FROM screenscraper_cache WHERE COALESCE(raw_response, '') = ''",
+            ))
+            .await?
+            .rows_affected();
+    }
+    Ok(cleared)
+}
@@ -1,19 +1,86 @@
+pub mod age_rating;
 pub mod dat;
+pub mod dat_report;
+pub mod edits;
 pub mod hasheous;
+pub mod homebrew;
 pub mod igdb;
 pub mod launchbox;
 pub mod libretro_thumbnails;
+pub mod misses;
+pub mod phash;
 pub mod screenscraper;
+pub mod translate;
 
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet, VecDeque};
 use std::path::PathBuf;
+use std::sync::{LazyLock, Mutex, PoisonError};
+use std::time::Duration;
 
 use md5::{Digest, Md5};
 use sea_orm::{ConnectionTrait, DatabaseBackend, DatabaseConnection, FromQueryResult, Statement};
 use tokio_util::sync::CancellationToken;
 
 use crate::error::{AppError, AppResult};
-use crate::models::ScanProgress;
+use crate::events::LibraryEvents;
+use crate::models::{
+    ArtworkChains, EnrichmentOptions, MetadataProvider, ScanProgress, SsMediaPreferences,
+};
+use crate::operation_log::{self, StepTimings};
+
+/// ROM IDs the frontend wants enriched ahead of the rest of a running batch,
+/// e.g. the covers currently on screen. Managed as Tauri state.
+#[derive(Default)]
+pub struct PriorityLane(std::sync::Mutex<VecDeque<i64>>);
+
+impl PriorityLane {
+    /// Replace the lane with the ROM IDs that are visible right now.
+    pub fn set(&self, rom_ids: Vec<i64>) {
+        *self.0.lock().unwrap_or_else(std::sync::PoisonError::into_inner) = rom_ids.into();
+    }
+
+    /// Take the next prioritized ID that is still waiting in this run. IDs
+    /// that belong to another platform's run are left for that run.
+    fn pop_pending(&self, is_pending: impl Fn(i64) -> bool) -> Option<i64> {
+        let mut lane = self.0.lock().unwrap_or_else(std::sync::PoisonError::into_inner);
+        let pos = lane.iter().position(|&id| is_pending(id))?;
+        lane.remove(pos)
+    }
+}
+
+/// ROMs an enrichment run is working on right now. Runs for different
+/// platforms go in parallel; a ROM two runs both pick up is done once.
+static IN_PROGRESS: LazyLock<Mutex<HashSet<i64>>> = LazyLock::new(Mutex::default);
+
+/// Exclusive use of a ROM's metadata rows while it's enriched, released on
+/// drop.
+struct RomClaim(i64);
+
+impl RomClaim {
+    fn try_claim(rom_id: i64) -> Option<Self> {
+        let mut claimed = IN_PROGRESS.lock().unwrap_or_else(PoisonError::into_inner);
+        claimed.insert(rom_id).then_some(Self(rom_id))
+    }
+
+    /// Wait for a run that's enriching the ROM to finish with it.
+    async fn claim(rom_id: i64) -> Self {
+        loop {
+            if let Some(claim) = Self::try_claim(rom_id) {
+                return claim;
+            }
+            tokio::time::sleep(Duration::from_millis(200)).await;
+        }
+    }
+}
+
+impl Drop for RomClaim {
+    fn drop(&mut self) {
+        IN_PROGRESS
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .remove(&self.0);
+    }
+}
 
 #[derive(Debug, FromQueryResult)]
 struct RomRow {
@@ -22,9 +89,15 @@ struct RomRow {
     platform_slug: String,
     has_cover: i64,
     hash_md5: Option<String>,
+    hash_sha1: Option<String>,
+    hash_crc32: Option<String>,
+    headerless_md5: Option<String>,
+    headerless_sha1: Option<String>,
+    headerless_crc32: Option<String>,
     source_type: Option<crate::entity::sources::SourceType>,
     source_rom_id: Option<String>,
     screenscraper_id: Option<i64>,
+    homebrew_kind: Option<crate::entity::rom_homebrew::HomebrewKind>,
 }
 
 /// Helper: look up igdb_game_id from hasheous_cache for a given rom_id.
@@ -41,6 +114,16 @@ async fn query_hasheous_igdb_id(db: &DatabaseConnection, rom_id: i64) -> Option<
         .and_then(|m| m.igdb_game_id)
 }
 
+/// The ROM's file on disk: the local path, or the download cache for remote sources.
+async fn rom_file_path(db: &DatabaseConnection, rom: &RomRow) -> Option<PathBuf> {
+    let file_path = if rom.source_type == Some(crate::entity::sources::SourceType::Local) {
+        rom.source_rom_id.as_ref().map(PathBuf::from)?
+    } else {
+        crate::rom_cache::cached_rom_path(db, rom.id).await.ok().flatten()?
+    };
+    file_path.exists().then_some(file_path)
+}
+
 /// Compute MD5 for a ROM file if not already stored.
 /// For local ROMs, reads from `source_rom_id` path.
 /// For downloaded ROMs, reads from the download cache.
@@ -52,32 +135,7 @@ async fn compute_md5_if_needed(db: &DatabaseConnection, rom: &RomRow) -> Option<
         }
     }
 
-    // Determine file path
-    let file_path = if rom.source_type == Some(crate::entity::sources::SourceType::Local) {
-        rom.source_rom_id.as_ref().map(PathBuf::from)?
-    } else {
-        // For remote sources, check download cache
-        let cache_dir = directories::ProjectDirs::from("com", "romm-buddy", "romm-buddy")
-            .map(|p| p.cache_dir().join("rom_cache"))?;
-        // We need the file_name from the roms table
-        let file_name = db.query_one(Statement::from_sql_and_values(
-            DatabaseBackend::Sqlite,
-            "SELECT file_name FROM roms WHERE id = ?",
-            [rom.id.into()],
-        ))
-        .await
-        .ok()?
-        .and_then(|r| r.try_get::<String>("", "file_name").ok())?;
-        let path = cache_dir.join(&file_name);
-        if !path.exists() {
-            return None;
-        }
-        path
-    };
-
-    if !file_path.exists() {
-        return None;
-    }
+    let file_path = rom_file_path(db, rom).await?;
 
     // Compute MD5 in a blocking task
     let rom_id = rom.id;
@@ -105,35 +163,141 @@ async fn compute_md5_if_needed(db: &DatabaseConnection, rom: &RomRow) -> Option<
     Some(hash)
 }
 
+/// Hashes of a ROM without its header, on platforms whose dumps may carry
+/// one. Computed and stored when missing.
+async fn compute_headerless_if_needed(
+    db: &DatabaseConnection,
+    rom: &RomRow,
+) -> Option<crate::hash::RomHashes> {
+    if !crate::hash::may_have_header(&rom.platform_slug) {
+        return None;
+    }
+    if let Some(stored) = stored_headerless(rom) {
+        return Some(stored);
+    }
+
+    let file_path = rom_file_path(db, rom).await?;
+    let slug = rom.platform_slug.clone();
+    let hashes = tokio::task::spawn_blocking(move || {
+        crate::hash::compute_dump_hashes_with_progress(&file_path, &slug, |_, _| {})
+    })
+    .await
+    .ok()?
+    .ok()?
+    .headerless?;
+
+    if let Err(e) = db
+        .execute(Statement::from_sql_and_values(
+            DatabaseBackend::Sqlite,
+            "UPDATE roms SET headerless_crc32 = ?, headerless_md5 = ?, headerless_sha1 = ? WHERE id = ?",
+            [
+                hashes.crc32.clone().into(),
+                hashes.md5.clone().into(),
+                hashes.sha1.clone().into(),
+                rom.id.into(),
+            ],
+        ))
+        .await
+    {
+        log::warn!("Failed to store headerless hashes for rom {}: {e}", rom.id);
+    }
+
+    Some(hashes)
+}
+
+fn stored_headerless(rom: &RomRow) -> Option<crate::hash::RomHashes> {
+    Some(crate::hash::RomHashes {
+        crc32: rom.headerless_crc32.clone()?,
+        md5: rom.headerless_md5.clone()?,
+        sha1: rom.headerless_sha1.clone()?,
+    })
+}
+
 const UNENRICHED_ROM_SELECT: &str = "SELECT r.id, r.name, p.slug as platform_slug,
         (SELECT COUNT(*) FROM artwork WHERE rom_id = r.id AND art_type = 'cover') as has_cover,
-        r.hash_md5,
+        r.hash_md5, r.hash_sha1, r.hash_crc32,
+        r.headerless_md5, r.headerless_sha1, r.headerless_crc32,
         (SELECT s2.source_type FROM source_roms sr2 JOIN sources s2 ON s2.id = sr2.source_id WHERE sr2.rom_id = r.id LIMIT 1) as source_type,
         (SELECT sr3.source_rom_id FROM source_roms sr3 JOIN sources s3 ON s3.id = sr3.source_id WHERE sr3.rom_id = r.id LIMIT 1) as source_rom_id,
-        p.screenscraper_id
+        p.screenscraper_id,
+        hb.kind as homebrew_kind
  FROM roms r
  JOIN platforms p ON p.id = r.platform_id
  LEFT JOIN metadata m ON m.rom_id = r.id
- LEFT JOIN hasheous_cache hc ON hc.rom_id = r.id";
+ LEFT JOIN hasheous_cache hc ON hc.rom_id = r.id
+ LEFT JOIN rom_homebrew hb ON hb.rom_id = r.id";
+
+/// Which ROMs a batch enrichment run covers.
+pub enum RomSelection<'a> {
+    /// ROMs that need enrichment, optionally filtered by platform and/or
+    /// search term.
+    Unenriched {
+        platform_id: Option<i64>,
+        search: Option<&'a str>,
+    },
+    /// ROMs whose metadata was fetched more than `days` ago, oldest first.
+    /// Pinned metadata is skipped.
+    Stale { days: u32 },
+}
+
+/// Query the ROMs of a batch run.
+async fn fetch_roms_to_enrich(
+    db: &DatabaseConnection,
+    selection: &RomSelection<'_>,
+    steps: &EnrichmentOptions,
+) -> AppResult<Vec<RomRow>> {
+    match *selection {
+        RomSelection::Unenriched { platform_id, search } => {
+            fetch_unenriched_roms(db, platform_id, search, steps).await
+        }
+        RomSelection::Stale { days } => {
+            let stmt = Statement::from_sql_and_values(
+                DatabaseBackend::Sqlite,
+                format!(
+                    "{UNENRICHED_ROM_SELECT}
+                     WHERE m.metadata_fetched_at < strftime('%Y-%m-%dT%H:%M:%fZ', 'now', ?)
+                       AND m.pinned = 0
+                       AND p.archived = 0
+                     ORDER BY m.metadata_fetched_at"
+                ),
+                [format!("-{days} days").into()],
+            );
+            Ok(RomRow::find_by_statement(stmt).all(db).await?)
+        }
+    }
+}
 
 /// Query ROMs that need enrichment, optionally filtered by platform and/or
-/// search term (FTS match).
+/// search term (FTS match). A forced refresh takes every matching ROM; a
+/// covers-only run takes the ROMs without a cover. Homebrew and hacks are
+/// only retried once marked or when the homebrew index changes, since the
+/// other providers never know them.
 async fn fetch_unenriched_roms(
     db: &DatabaseConnection,
     platform_id: Option<i64>,
     search: Option<&str>,
+    steps: &EnrichmentOptions,
 ) -> AppResult<Vec<RomRow>> {
-    let search_query = search
-        .filter(|s| !s.trim().is_empty())
-        .map(|s| format!("{}*", s.replace('"', "")));
+    let search_query = search.and_then(crate::search::fts_query);
 
     let has_search = search_query.is_some();
 
     let mut conditions = Vec::new();
-    conditions.push("(has_cover = 0 OR m.metadata_fetched_at IS NULL OR hc.id IS NULL)".to_string());
+    if steps.covers_only && !steps.force_refresh {
+        conditions.push(
+            "has_cover = 0 AND (hb.rom_id IS NULL OR m.metadata_fetched_at IS NULL)".to_string(),
+        );
+    } else if !steps.force_refresh {
+        conditions.push(
+            "(m.metadata_fetched_at IS NULL OR (hb.rom_id IS NULL AND (has_cover = 0 OR hc.id IS NULL)))"
+                .to_string(),
+        );
+    }
 
     if platform_id.is_some() {
         conditions.push("r.platform_id = ?".to_string());
+    } else {
+        conditions.push("p.archived = 0".to_string());
     }
 
     let fts_join = if has_search {
@@ -166,8 +330,27 @@ struct EnrichContext<'a> {
     http_client: &'a reqwest::Client,
     igdb_client: Option<&'a igdb::IgdbClient>,
     ss_creds: Option<&'a screenscraper::SsUserCredentials>,
+    ss_prefs: &'a SsMediaPreferences,
+    artwork_chains: &'a ArtworkChains,
+    translator: Option<&'a translate::Translator>,
     has_launchbox: bool,
-    last_ss_request: tokio::sync::Mutex<std::time::Instant>,
+    /// Which steps of the pipeline run.
+    steps: &'a EnrichmentOptions,
+    /// Offline mode was on when the run started: only the LaunchBox database
+    /// and what earlier runs cached are used.
+    offline: bool,
+    /// Drop artwork that is a perceptual duplicate of an image already stored for the ROM.
+    dedup_artwork: bool,
+    events: &'a LibraryEvents,
+    /// Time spent per step, recorded in `operation_log` when the run ends.
+    timings: &'a StepTimings,
+}
+
+impl EnrichContext<'_> {
+    /// The client to hash new artwork with, when duplicates are dropped.
+    fn dedup_client(&self) -> Option<&reqwest::Client> {
+        self.dedup_artwork.then_some(self.http_client)
+    }
 }
 
 /// Options that differ between batch and single-ROM enrichment.
@@ -178,12 +361,79 @@ struct EnrichOptions {
     force_refresh: bool,
 }
 
-/// Insert artwork with dedup (ON CONFLICT DO NOTHING).
-async fn insert_artwork(db: &DatabaseConnection, rom_id: i64, art_type: &str, url: &str) {
+/// Whether the ROM's stored description is exactly `text`.
+async fn description_is(db: &DatabaseConnection, rom_id: i64, text: &str) -> bool {
+    db.query_one(Statement::from_sql_and_values(
+        DatabaseBackend::Sqlite,
+        "SELECT 1 FROM metadata WHERE rom_id = ? AND description = ?",
+        [rom_id.into(), text.into()],
+    ))
+    .await
+    .ok()
+    .flatten()
+    .is_some()
+}
+
+/// Insert artwork with dedup (ON CONFLICT DO NOTHING), recording which provider it came from.
+async fn insert_artwork(
+    db: &DatabaseConnection,
+    dedup: Option<&reqwest::Client>,
+    rom_id: i64,
+    art_type: &str,
+    url: &str,
+    provider: &str,
+) {
+    insert_artwork_row(db, dedup, rom_id, art_type, url, None, provider).await;
+}
+
+/// Insert artwork tagged with the region it was selected for.
+async fn insert_regional_artwork(
+    db: &DatabaseConnection,
+    dedup: Option<&reqwest::Client>,
+    rom_id: i64,
+    art_type: &str,
+    url: &str,
+    region: Option<&str>,
+) {
+    insert_artwork_row(db, dedup, rom_id, art_type, url, region, "screenscraper").await;
+}
+
+/// Insert an artwork row. With a `dedup` client the image is hashed first and
+/// skipped when it looks the same as artwork of that type the ROM already has.
+async fn insert_artwork_row(
+    db: &DatabaseConnection,
+    dedup: Option<&reqwest::Client>,
+    rom_id: i64,
+    art_type: &str,
+    url: &str,
+    region: Option<&str>,
+    provider: &str,
+) {
+    let mut hash = None;
+    if let Some(client) = dedup {
+        // Already stored: no need to download it again
+        if has_artwork_url(db, rom_id, art_type, url).await {
+            return;
+        }
+        hash = phash::fetch_dhash(client, url).await;
+        if let Some(h) = hash {
+            if has_similar_artwork(db, client, rom_id, art_type, h).await {
+                log::info!("Skipping duplicate artwork {url} for rom {rom_id}");
+                return;
+            }
+        }
+    }
     if let Err(e) = db.execute(Statement::from_sql_and_values(
         DatabaseBackend::Sqlite,
-        "INSERT INTO artwork (rom_id, art_type, url) VALUES (?, ?, ?) ON CONFLICT(rom_id, art_type, url) DO NOTHING",
-        [rom_id.into(), art_type.into(), url.into()],
+        "INSERT INTO artwork (rom_id, art_type, url, region, provider, phash) VALUES (?, ?, ?, ?, ?, ?) ON CONFLICT(rom_id, art_type, url) DO NOTHING",
+        [
+            rom_id.into(),
+            art_type.into(),
+            url.into(),
+            region.into(),
+            provider.into(),
+            hash.map(phash::format).into(),
+        ],
     ))
     .await
     {
@@ -191,6 +441,200 @@ async fn insert_artwork(db: &DatabaseConnection, rom_id: i64, art_type: &str, ur
     }
 }
 
+/// Whether the ROM has `art_type` artwork at exactly `url`.
+async fn has_artwork_url(db: &DatabaseConnection, rom_id: i64, art_type: &str, url: &str) -> bool {
+    db.query_one(Statement::from_sql_and_values(
+        DatabaseBackend::Sqlite,
+        "SELECT 1 FROM artwork WHERE rom_id = ? AND art_type = ? AND url = ?",
+        [rom_id.into(), art_type.into(), url.into()],
+    ))
+    .await
+    .ok()
+    .flatten()
+    .is_some()
+}
+
+#[derive(Debug, FromQueryResult)]
+struct HashedArtRow {
+    id: i64,
+    art_type: String,
+    url: String,
+    local_path: Option<String>,
+    phash: Option<String>,
+}
+
+/// The perceptual hash of a stored artwork row, computed and saved the first
+/// time it's needed.
+async fn artwork_row_hash(
+    db: &DatabaseConnection,
+    http_client: &reqwest::Client,
+    row: &HashedArtRow,
+) -> Option<u64> {
+    if let Some(h) = row.phash.as_deref().and_then(phash::parse) {
+        return Some(h);
+    }
+    let h = phash::fetch_dhash(http_client, &row.url).await?;
+    let _ = db.execute(Statement::from_sql_and_values(
+        DatabaseBackend::Sqlite,
+        "UPDATE artwork SET phash = ? WHERE id = ?",
+        [phash::format(h).into(), row.id.into()],
+    ))
+    .await;
+    Some(h)
+}
+
+/// Whether the ROM already has `art_type` artwork that looks the same as the
+/// image hashed to `hash`.
+async fn has_similar_artwork(
+    db: &DatabaseConnection,
+    http_client: &reqwest::Client,
+    rom_id: i64,
+    art_type: &str,
+    hash: u64,
+) -> bool {
+    let rows = HashedArtRow::find_by_statement(Statement::from_sql_and_values(
+        DatabaseBackend::Sqlite,
+        "SELECT id, art_type, url, local_path, phash FROM artwork
+         WHERE rom_id = ? AND art_type = ? AND url IS NOT NULL",
+        [rom_id.into(), art_type.into()],
+    ))
+    .all(db)
+    .await
+    .unwrap_or_default();
+    for row in &rows {
+        if artwork_row_hash(db, http_client, row)
+            .await
+            .is_some_and(|h| phash::is_duplicate(h, hash))
+        {
+            return true;
+        }
+    }
+    false
+}
+
+/// Artwork each provider can offer a ROM, gathered by the lookup steps.
+struct ArtworkSources<'a> {
+    igdb: Option<&'a igdb::IgdbGameData>,
+    launchbox_id: Option<&'a str>,
+    ss_media: &'a [screenscraper::SsMedia],
+    /// Name to build libretro-thumbnails URLs from.
+    libretro_name: &'a str,
+}
+
+/// Changes whenever a ROM's artwork rows are inserted or deleted.
+async fn artwork_fingerprint(db: &DatabaseConnection, rom_id: i64) -> (i64, i64) {
+    db.query_one(Statement::from_sql_and_values(
+        DatabaseBackend::Sqlite,
+        "SELECT COUNT(*) as cnt, COALESCE(MAX(id), 0) as max_id FROM artwork WHERE rom_id = ?",
+        [rom_id.into()],
+    ))
+    .await
+    .ok()
+    .flatten()
+    .and_then(|r| Some((r.try_get("", "cnt").ok()?, r.try_get("", "max_id").ok()?)))
+    .unwrap_or_default()
+}
+
+async fn has_artwork(db: &DatabaseConnection, rom_id: i64, art_type: &str) -> bool {
+    db.query_one(Statement::from_sql_and_values(
+        DatabaseBackend::Sqlite,
+        "SELECT COUNT(*) as cnt FROM artwork WHERE rom_id = ? AND art_type = ?",
+        [rom_id.into(), art_type.into()],
+    ))
+    .await
+    .ok()
+    .flatten()
+    .and_then(|r| r.try_get::<i64>("", "cnt").ok())
+    .unwrap_or(0)
+        > 0
+}
+
+/// Insert `art_type` artwork from the first provider in `chain` that has any:
+/// a single cover, or every screenshot that provider offers.
+async fn apply_artwork_chain(
+    ctx: &EnrichContext<'_>,
+    rom: &RomRow,
+    art_type: &str,
+    chain: &[String],
+    sources: &ArtworkSources<'_>,
+) {
+    for provider in chain {
+        let found = if provider == "screenscraper" {
+            let available = screenscraper::select_media(sources.ss_media, ctx.ss_prefs)
+                .iter()
+                .any(|m| m.media_type == art_type);
+            if available {
+                apply_screenscraper_artwork(
+                    ctx.db,
+                    ctx.dedup_client(),
+                    rom.id,
+                    sources.ss_media,
+                    ctx.ss_prefs,
+                    &[art_type],
+                )
+                .await;
+            }
+            available
+        } else {
+            let mut urls = provider_artwork(ctx, rom, provider, art_type, sources).await;
+            if art_type == "cover" {
+                urls.truncate(1);
+            }
+            for url in &urls {
+                insert_artwork(ctx.db, ctx.dedup_client(), rom.id, art_type, url, provider).await;
+            }
+            !urls.is_empty()
+        };
+        if found {
+            return;
+        }
+    }
+}
+
+/// Artwork URLs `provider` has for a ROM (ScreenScraper is handled by the caller).
+async fn provider_artwork(
+    ctx: &EnrichContext<'_>,
+    rom: &RomRow,
+    provider: &str,
+    art_type: &str,
+    sources: &ArtworkSources<'_>,
+) -> Vec<String> {
+    let name = sources.libretro_name;
+    match (provider, art_type) {
+        ("igdb", "cover") => sources.igdb.and_then(igdb::IgdbGameData::cover_url).into_iter().collect(),
+        ("igdb", "screenshot") => sources.igdb.map(igdb::IgdbGameData::screenshot_urls).unwrap_or_default(),
+        ("launchbox", "cover") => match sources.launchbox_id {
+            Some(id) => launchbox::get_image_url(ctx.db, id).await.into_iter().collect(),
+            None => Vec::new(),
+        },
+        ("launchbox", "screenshot") => match sources.launchbox_id {
+            Some(id) => launchbox::get_screenshot_urls(ctx.db, id).await,
+            None => Vec::new(),
+        },
+        ("libretro", "cover") => {
+            libretro_thumbnails::available_urls(
+                ctx.db,
+                ctx.http_client,
+                &rom.platform_slug,
+                name,
+                &[libretro_thumbnails::BOXARTS],
+            )
+            .await
+        }
+        ("libretro", "screenshot") => {
+            libretro_thumbnails::available_urls(
+                ctx.db,
+                ctx.http_client,
+                &rom.platform_slug,
+                name,
+                &[libretro_thumbnails::SNAPS, libretro_thumbnails::TITLES],
+            )
+            .await
+        }
+        _ => Vec::new(),
+    }
+}
+
 /// Unified per-ROM enrichment pipeline used by both `enrich_roms` and `enrich_single_rom`.
 async fn enrich_one_rom(
     ctx: &EnrichContext<'_>,
@@ -198,47 +642,94 @@ async fn enrich_one_rom(
     opts: &EnrichOptions,
 ) -> AppResult<()> {
     let db = ctx.db;
+    let artwork_before = artwork_fingerprint(db, rom.id).await;
+
+    let steps = ctx.steps;
 
     // Step 1: Compute hash if missing
-    let md5 = compute_md5_if_needed(db, rom).await;
-
-    // Step 2: Hasheous lookup
-    let hasheous_result = if opts.force_refresh {
-        // Single-ROM re-enrich: always fetch fresh from API
-        if let Some(ref hash) = md5 {
-            if let Some(result) = hasheous::lookup_by_md5(ctx.http_client, hash).await {
-                hasheous::save_to_cache(db, rom.id, &result).await;
-                Some(result)
-            } else {
-                None
-            }
-        } else {
-            None
-        }
+    let (md5, headerless) = if steps.compute_hashes {
+        ctx.timings
+            .time("enrich.hash", async {
+                (
+                    compute_md5_if_needed(db, rom).await,
+                    compute_headerless_if_needed(db, rom).await,
+                )
+            })
+            .await
     } else {
-        // Batch: check cache first, then API
-        let cached = hasheous::get_cached(db, rom.id).await;
-        match cached {
-            Some(c) => Some(c),
-            None => {
-                if let Some(ref hash) = md5 {
-                    if let Some(result) = hasheous::lookup_by_md5(ctx.http_client, hash).await {
-                        hasheous::save_to_cache(db, rom.id, &result).await;
-                        Some(result)
-                    } else {
-                        None
+        (
+            rom.hash_md5.clone().filter(|h| !h.is_empty()),
+            stored_headerless(rom),
+        )
+    };
+    let headerless = headerless.filter(|h| md5.as_deref() != Some(h.md5.as_str()));
+
+    // Homebrew and hacks aren't in the commercial databases; only the
+    // homebrew index is asked
+    if rom.homebrew_kind.is_some() {
+        return enrich_homebrew_rom(ctx, rom, md5.as_deref(), artwork_before).await;
+    }
+
+    // Step 2: Hasheous lookup, by every hash we have (DAT verification often
+    // fills SHA1/CRC32 even when MD5 couldn't be computed)
+    let hashes = hasheous::HashQuery {
+        md5: md5.as_deref(),
+        sha1: rom.hash_sha1.as_deref(),
+        crc32: rom.hash_crc32.as_deref(),
+    };
+    // Headered dumps are catalogued by their headerless data; try that first
+    let headerless_hashes = headerless.as_ref().map(|h| hasheous::HashQuery {
+        md5: Some(&h.md5),
+        sha1: Some(&h.sha1),
+        crc32: Some(&h.crc32),
+    });
+    // Single-ROM re-enrich always fetches fresh from the API; batches check the cache first
+    let cached = if opts.force_refresh {
+        None
+    } else {
+        hasheous::get_cached(db, rom.id).await
+    };
+    // Hashes Hasheous didn't know are remembered for a while
+    let miss_key = hashes.cache_key();
+    let known_miss = match &miss_key {
+        Some(key) if !opts.force_refresh => {
+            misses::is_known(db, MetadataProvider::Hasheous, key).await
+        }
+        _ => false,
+    };
+    let hasheous_result = match cached {
+        Some(c) => Some(c),
+        None if !steps.hasheous || known_miss || ctx.offline => None,
+        None => {
+            let mut result = None;
+            let mut failed = false;
+            for query in headerless_hashes.into_iter().chain([hashes]) {
+                let lookup = hasheous::lookup_by_hashes(ctx.http_client, query);
+                match ctx.timings.time("enrich.hasheous", lookup).await {
+                    hasheous::Lookup::Found(found) => {
+                        result = Some(found);
+                        break;
                     }
-                } else {
-                    None
+                    hasheous::Lookup::NotFound => {}
+                    hasheous::Lookup::Failed => failed = true,
+                }
+            }
+            if let Some(result) = &result {
+                hasheous::save_to_cache(db, rom.id, result).await;
+                if let Some(key) = &miss_key {
+                    misses::forget(db, MetadataProvider::Hasheous, key).await;
                 }
+            } else if let Some(key) = miss_key.as_deref().filter(|_| !failed) {
+                misses::record(db, MetadataProvider::Hasheous, key).await;
             }
+            result
         }
     };
 
     let hasheous_name = hasheous_result.as_ref().map(|r| r.name.as_str());
 
     // Upsert metadata from Hasheous
-    if let Some(ref result) = hasheous_result {
+    if let Some(result) = hasheous_result.as_ref().filter(|_| !steps.covers_only) {
         let genres_json =
             serde_json::to_string(&result.genres).unwrap_or_else(|_| "[]".to_string());
         if let Err(e) = db.execute(Statement::from_sql_and_values(
@@ -266,14 +757,18 @@ async fn enrich_one_rom(
     }
 
     // Step 3: IGDB enrichment
-    if let Some(client) = ctx.igdb_client {
+    let igdb_data = if let Some(client) = ctx.igdb_client {
         let igdb_data = if let Some(ref prefetched) = opts.igdb_prefetch {
             Some(prefetched.clone())
         } else {
             // Try hasheous IGDB ID first, then name search
             let igdb_game_id = query_hasheous_igdb_id(db, rom.id).await;
             if let Some(igdb_id) = igdb_game_id {
-                match client.fetch_games_by_ids(&[igdb_id]).await {
+                match ctx
+                    .timings
+                    .time("enrich.igdb", client.fetch_games_by_ids(&[igdb_id]))
+                    .await
+                {
                     Ok(games) => games.into_iter().next(),
                     Err(e) => {
                         log::warn!("IGDB fetch failed for igdb_id {igdb_id}: {e}");
@@ -282,30 +777,65 @@ async fn enrich_one_rom(
                 }
             } else {
                 let search_name = hasheous_name.unwrap_or(&rom.name);
-                match client.search_game(search_name).await {
-                    Ok(result) => result,
-                    Err(e) => {
-                        log::warn!("IGDB search failed for rom {}: {e}", rom.id);
-                        None
+                let miss_key = search_name.trim().to_lowercase();
+                if !opts.force_refresh
+                    && misses::is_known(db, MetadataProvider::Igdb, &miss_key).await
+                {
+                    None
+                } else {
+                    match ctx
+                        .timings
+                        .time("enrich.igdb", client.search_game(search_name))
+                        .await
+                    {
+                        Ok(Some(game)) => {
+                            misses::forget(db, MetadataProvider::Igdb, &miss_key).await;
+                            Some(game)
+                        }
+                        Ok(None) => {
+                            misses::record(db, MetadataProvider::Igdb, &miss_key).await;
+                            None
+                        }
+                        Err(e) => {
+                            log::warn!("IGDB search failed for rom {}: {e}", rom.id);
+                            None
+                        }
                     }
                 }
             }
         };
 
-        if let Some(ref game) = igdb_data {
+        if let Some(game) = igdb_data.as_ref().filter(|_| !steps.covers_only) {
             apply_igdb_data(db, rom.id, game).await;
         }
-    }
+        igdb_data
+    } else {
+        None
+    };
 
-    // Step 4: LaunchBox lookup
-    let lb_game = if ctx.has_launchbox {
+    // Step 4: LaunchBox lookup, skipping a match the user rejected
+    let lb_game = if ctx.has_launchbox && steps.launchbox {
         let lookup_name = hasheous_name.unwrap_or(&rom.name);
-        launchbox::find_by_name(db, lookup_name, &rom.platform_slug).await
+        match ctx
+            .timings
+            .time(
+                "enrich.launchbox",
+                launchbox::find_by_name(db, lookup_name, &rom.platform_slug),
+            )
+            .await
+        {
+            Some(game) if launchbox::is_rejected(db, rom.id, &game.database_id).await => None,
+            Some(game) => {
+                launchbox::record_match(db, rom.id, &game).await;
+                Some(game)
+            }
+            None => None,
+        }
     } else {
         None
     };
 
-    if let Some(ref lb_game) = lb_game {
+    if let Some(lb_game) = lb_game.as_ref().filter(|_| !steps.covers_only) {
         if let Err(e) = db.execute(Statement::from_sql_and_values(
             DatabaseBackend::Sqlite,
             "INSERT INTO metadata (rom_id, description, developer, publisher, genres, release_date, rating, metadata_fetched_at)
@@ -332,16 +862,12 @@ async fn enrich_one_rom(
         {
             log::warn!("Failed to upsert LaunchBox metadata for rom {}: {e}", rom.id);
         }
-
-        if rom.has_cover == 0 {
-            if let Some(url) = launchbox::get_image_url(db, &lb_game.database_id).await {
-                insert_artwork(db, rom.id, "cover", &url).await;
-            }
-        }
     }
 
     // Step 5: ScreenScraper enrichment
-    if let Some(ss_system_id) = rom.screenscraper_id {
+    let mut ss_media = Vec::new();
+    let mut ss_synopsis = None;
+    if let Some(ss_system_id) = rom.screenscraper_id.filter(|_| steps.screenscraper) {
         let should_lookup = if opts.force_refresh {
             // Clear cache on re-enrich
             let _ = db.execute(Statement::from_sql_and_values(
@@ -352,30 +878,33 @@ async fn enrich_one_rom(
             .await;
             true
         } else {
-            !screenscraper::is_cached(db, rom.id).await
+            !ctx.offline && !screenscraper::is_cached(db, rom.id).await
         };
 
         if should_lookup {
-            match screenscraper::lookup_game(
+            let lookup = screenscraper::lookup_game(
                 ctx.http_client,
                 ctx.ss_creds,
                 md5.as_deref(),
                 &rom.name,
                 ss_system_id,
-                &ctx.last_ss_request,
-            )
-            .await
-            {
+                ctx.ss_prefs,
+            );
+            match ctx.timings.time("enrich.screenscraper", lookup).await {
                 Ok(Some(ss_data)) => {
+                    // Keep every regional media item so preferences can be re-applied offline
                     screenscraper::save_to_cache(
                         db,
                         rom.id,
                         ss_data.game_id,
-                        &serde_json::to_string(&ss_data.game_id).unwrap_or_default(),
+                        &serde_json::to_string(&ss_data.media).unwrap_or_default(),
                     )
                     .await;
-                    apply_screenscraper_metadata(db, rom.id, &ss_data).await;
-                    apply_screenscraper_artwork(db, rom.id, &ss_data.media).await;
+                    if !steps.covers_only {
+                        apply_screenscraper_metadata(db, rom.id, &ss_data).await;
+                        ss_synopsis = ss_data.synopsis.clone().zip(ss_data.synopsis_language.clone());
+                    }
+                    ss_media = ss_data.media;
                 }
                 Ok(None) => {
                     screenscraper::save_to_cache(db, rom.id, None, "").await;
@@ -384,109 +913,178 @@ async fn enrich_one_rom(
                     log::warn!("ScreenScraper lookup failed for rom {}: {e}", rom.id);
                 }
             }
+        } else {
+            ss_media = screenscraper::cached_media(db, rom.id).await;
         }
     }
 
-    // Step 6: libretro thumbnail (if still no cover)
-    let current_has_cover = if rom.has_cover == 0 {
-        let result = db.query_one(Statement::from_sql_and_values(
-            DatabaseBackend::Sqlite,
-            "SELECT COUNT(*) as cnt FROM artwork WHERE rom_id = ? AND art_type = 'cover'",
-            [rom.id.into()],
-        ))
-        .await;
-        result
-            .ok()
-            .flatten()
-            .and_then(|r| r.try_get::<i64>("", "cnt").ok())
-            .unwrap_or(0)
-            > 0
-    } else {
-        true
-    };
+    // Hand edits win over whatever the providers returned
+    if let Err(e) = edits::reapply(db, rom.id).await {
+        log::warn!("Failed to restore edited metadata for rom {}: {e}", rom.id);
+    }
 
-    if !current_has_cover {
-        let name = hasheous_name.unwrap_or(&rom.name);
-        if let Some(url) = libretro_thumbnails::build_thumbnail_url(&rom.platform_slug, name) {
-            let exists = ctx.http_client
-                .head(&url)
-                .send()
-                .await
-                .is_ok_and(|r| r.status().is_success());
-            if exists {
-                insert_artwork(db, rom.id, "cover", &url).await;
+    // Step 5b: Translate a synopsis ScreenScraper only has in another language,
+    // if it became the description
+    if let (Some(translator), Some((synopsis, language))) = (ctx.translator, &ss_synopsis) {
+        if steps.translate
+            && !translator.is_target(language)
+            && description_is(db, rom.id, synopsis).await
+        {
+            let translate =
+                translator.translate_description(db, ctx.http_client, rom.id, Some(language));
+            if let Err(e) = ctx.timings.time("enrich.translate", translate).await {
+                log::warn!("Failed to translate description for rom {}: {e}", rom.id);
             }
         }
     }
 
-    // Step 7: Screenshot art — collect from all sources (libretro + LaunchBox)
+    // Step 6: Artwork, taken from providers in the user's order per art type
     if opts.force_refresh {
-        // Clear existing screenshots on re-enrich so we get fresh data
+        // Re-enrich picks artwork afresh; covers that came with a ROMM sync stay
+        let sql = if steps.covers_only {
+            "DELETE FROM artwork WHERE rom_id = ?
+               AND art_type = 'cover' AND COALESCE(provider, '') != 'romm'"
+        } else {
+            "DELETE FROM artwork WHERE rom_id = ?
+               AND (art_type = 'screenshot' OR (art_type = 'cover' AND COALESCE(provider, '') != 'romm'))"
+        };
         let _ = db.execute(Statement::from_sql_and_values(
             DatabaseBackend::Sqlite,
-            "DELETE FROM artwork WHERE rom_id = ? AND art_type = 'screenshot'",
+            sql,
             [rom.id.into()],
         ))
         .await;
     }
 
-    let snap_name = hasheous_name.unwrap_or(&rom.name);
-
-    // libretro Named_Snaps + Named_Titles — fire HEAD requests concurrently
-    let snap_url = libretro_thumbnails::build_snapshot_url(&rom.platform_slug, snap_name);
-    let title_url = libretro_thumbnails::build_title_url(&rom.platform_slug, snap_name);
+    let sources = ArtworkSources {
+        igdb: igdb_data.as_ref(),
+        launchbox_id: lb_game.as_ref().map(|g| g.database_id.as_str()),
+        ss_media: &ss_media,
+        libretro_name: hasheous_name.unwrap_or(&rom.name),
+    };
 
-    let snap_future = async {
-        if let Some(url) = &snap_url {
-            ctx.http_client.head(url).send().await.is_ok_and(|r| r.status().is_success())
-        } else {
-            false
+    let may_lack_cover = rom.has_cover == 0 || opts.force_refresh;
+    if may_lack_cover && !has_artwork(db, rom.id, "cover").await {
+        let chain = apply_artwork_chain(ctx, rom, "cover", &ctx.artwork_chains.cover, &sources);
+        ctx.timings.time("enrich.artwork", chain).await;
+    }
+    if steps.covers_only {
+        if ctx.dedup_artwork {
+            dedup_rom_artwork(db, ctx.http_client, rom.id).await;
         }
-    };
-    let title_future = async {
-        if let Some(url) = &title_url {
-            ctx.http_client.head(url).send().await.is_ok_and(|r| r.status().is_success())
-        } else {
-            false
+        if artwork_fingerprint(db, rom.id).await != artwork_before {
+            ctx.events.artwork_updated(vec![rom.id]);
         }
-    };
+        return Ok(());
+    }
+    if !has_artwork(db, rom.id, "screenshot").await {
+        let chain = apply_artwork_chain(
+            ctx,
+            rom,
+            "screenshot",
+            &ctx.artwork_chains.screenshot,
+            &sources,
+        );
+        ctx.timings.time("enrich.artwork", chain).await;
+    }
 
-    let (snap_exists, title_exists) = tokio::join!(snap_future, title_future);
+    // Step 7: ScreenScraper fanart has no competing provider
+    apply_screenscraper_artwork(
+        db,
+        ctx.dedup_client(),
+        rom.id,
+        &ss_media,
+        ctx.ss_prefs,
+        &["fanart"],
+    )
+    .await;
 
-    if snap_exists {
-        if let Some(url) = &snap_url {
-            insert_artwork(db, rom.id, "screenshot", url).await;
+    // Step 7b: Cache the ScreenScraper manual unless the ROM already has one
+    if let Some(manual) =
+        screenscraper::select_manual(&ss_media, ctx.ss_prefs).filter(|_| !ctx.offline)
+    {
+        if let Err(e) =
+            crate::manuals::fetch_from_screenscraper(db, ctx.http_client, rom.id, &manual.url).await
+        {
+            log::warn!("Failed to download the manual for rom {}: {e}", rom.id);
         }
     }
-    if title_exists {
-        if let Some(url) = &title_url {
-            insert_artwork(db, rom.id, "screenshot", url).await;
-        }
+
+    // Step 8: Drop the same image served by several providers, stored before dedup was on
+    if ctx.dedup_artwork {
+        dedup_rom_artwork(db, ctx.http_client, rom.id).await;
+    }
+
+    mark_enriched(db, rom.id).await;
+
+    ctx.events.metadata_updated(vec![rom.id]);
+    if artwork_fingerprint(db, rom.id).await != artwork_before {
+        ctx.events.artwork_updated(vec![rom.id]);
     }
 
-    // LaunchBox screenshots
-    if let Some(ref lb_game) = lb_game {
-        let urls = launchbox::get_screenshot_urls(db, &lb_game.database_id).await;
-        for url in urls {
-            insert_artwork(db, rom.id, "screenshot", &url).await;
+    Ok(())
+}
+
+/// Enrichment of a ROM marked as homebrew or a hack: metadata and cover
+/// from the homebrew index, if it has the ROM. Marked as enriched either
+/// way so later runs don't retry it.
+async fn enrich_homebrew_rom(
+    ctx: &EnrichContext<'_>,
+    rom: &RomRow,
+    md5: Option<&str>,
+    artwork_before: (i64, i64),
+) -> AppResult<()> {
+    let db = ctx.db;
+    let hashes = hasheous::HashQuery {
+        md5,
+        sha1: rom.hash_sha1.as_deref(),
+        crc32: rom.hash_crc32.as_deref(),
+    };
+    let entry = ctx
+        .timings
+        .time(
+            "enrich.homebrew",
+            homebrew::find(db, hashes, &rom.name, &rom.platform_slug),
+        )
+        .await;
+
+    if let Some(entry) = &entry {
+        if !ctx.steps.covers_only {
+            homebrew::apply(db, rom.id, entry).await;
+            if let Err(e) = edits::reapply(db, rom.id).await {
+                log::warn!("Failed to restore edited metadata for rom {}: {e}", rom.id);
+            }
         }
+        if let Some(url) = &entry.cover_url {
+            if !has_artwork(db, rom.id, "cover").await {
+                insert_artwork(db, ctx.dedup_client(), rom.id, "cover", url, "homebrew").await;
+            }
+        }
+    }
+
+    mark_enriched(db, rom.id).await;
+
+    ctx.events.metadata_updated(vec![rom.id]);
+    if artwork_fingerprint(db, rom.id).await != artwork_before {
+        ctx.events.artwork_updated(vec![rom.id]);
     }
+    Ok(())
+}
 
-    // Mark as enriched
+/// Record that a ROM went through enrichment, keeping the first time.
+async fn mark_enriched(db: &DatabaseConnection, rom_id: i64) {
     if let Err(e) = db.execute(Statement::from_sql_and_values(
         DatabaseBackend::Sqlite,
         "INSERT INTO metadata (rom_id, metadata_fetched_at)
          VALUES (?, strftime('%Y-%m-%dT%H:%M:%fZ', 'now'))
          ON CONFLICT(rom_id) DO UPDATE SET
            metadata_fetched_at = COALESCE(metadata.metadata_fetched_at, strftime('%Y-%m-%dT%H:%M:%fZ', 'now'))",
-        [rom.id.into()],
+        [rom_id.into()],
     ))
     .await
     {
-        log::warn!("Failed to mark rom {} as enriched: {e}", rom.id);
+        log::warn!("Failed to mark rom {rom_id} as enriched: {e}");
     }
-
-    Ok(())
 }
 
 /// Enrich ROMs with metadata using the hash-first pipeline:
@@ -494,18 +1092,28 @@ async fn enrich_one_rom(
 /// 2. Hasheous API lookup (cached)
 /// 3. IGDB enrichment (if client provided)
 /// 4. `LaunchBox` SQL lookup using verified name
-/// 5. ScreenScraper enrichment
-/// 6. libretro-thumbnails cover art + screenshots
+/// 5. ScreenScraper enrichment, translating a synopsis in another language
+/// 6. Covers and screenshots from the first provider in each `artwork_chains` list
+/// 7. ScreenScraper fanart and manual
+///
+/// `steps` turns individual steps off, limits the run to covers, or forces a
+/// refresh of ROMs that were already enriched.
 pub async fn enrich_roms(
-    platform_id: Option<i64>,
-    search: Option<&str>,
+    selection: RomSelection<'_>,
     db: &DatabaseConnection,
     on_progress: impl Fn(ScanProgress) + Send,
     cancel: CancellationToken,
     igdb_client: Option<&igdb::IgdbClient>,
     ss_creds: Option<&screenscraper::SsUserCredentials>,
+    ss_prefs: &SsMediaPreferences,
+    artwork_chains: &ArtworkChains,
+    dedup_artwork: bool,
+    priority: &PriorityLane,
+    events: &LibraryEvents,
+    translator: Option<&translate::Translator>,
+    steps: &EnrichmentOptions,
 ) -> AppResult<()> {
-    let roms = fetch_unenriched_roms(db, platform_id, search).await?;
+    let roms = fetch_roms_to_enrich(db, &selection, steps).await?;
 
     #[allow(clippy::cast_possible_truncation)]
     let total = roms.len() as u64;
@@ -514,32 +1122,48 @@ pub async fn enrich_roms(
             source_id: -1,
             total: 0,
             current: 0,
-            current_item: "All ROMs already enriched.".to_string(),
+            current_item: match selection {
+                RomSelection::Unenriched { .. } => "All ROMs already enriched.",
+                RomSelection::Stale { .. } => "No stale metadata to refresh.",
+            }
+            .to_string(),
         });
         return Ok(());
     }
+    let started = std::time::Instant::now();
 
-    let http_client = reqwest::Client::builder()
+    let http_client = crate::http::client_builder()
         .user_agent("romm-buddy/0.1")
         .timeout(std::time::Duration::from_secs(30))
         .build()
         .unwrap_or_default();
 
     let has_launchbox = launchbox::has_imported_db(db).await;
+    let timings = StepTimings::default();
+    let offline = crate::http::is_offline();
 
     let ctx = EnrichContext {
         db,
         http_client: &http_client,
-        igdb_client,
+        igdb_client: igdb_client.filter(|_| steps.igdb && !offline),
         ss_creds,
+        ss_prefs,
+        artwork_chains,
+        translator: translator.filter(|_| !offline),
         has_launchbox,
-        last_ss_request: tokio::sync::Mutex::new(std::time::Instant::now() - std::time::Duration::from_secs(2)),
+        steps,
+        offline,
+        // Hashing artwork means downloading it
+        dedup_artwork: dedup_artwork && !offline,
+        events,
+        timings: &timings,
     };
 
     // IGDB batch optimization: pre-collect all IGDB IDs from hasheous_cache,
-    // batch-fetch in chunks of 10, build a HashMap for O(1) lookup during the loop
+    // batch-fetch in chunks of 10, build a HashMap for O(1) lookup during the loop.
+    // A forced refresh drops those cached IDs, so it looks each ROM up afresh.
     let mut igdb_batch: HashMap<i64, igdb::IgdbGameData> = HashMap::new();
-    if let Some(client) = igdb_client {
+    if let Some(client) = ctx.igdb_client.filter(|_| !steps.force_refresh) {
         let mut igdb_ids: std::collections::HashSet<i64> = std::collections::HashSet::new();
         for rom in &roms {
             if let Some(igdb_id) = query_hasheous_igdb_id(db, rom.id).await {
@@ -550,9 +1174,12 @@ pub async fn enrich_roms(
         let all_igdb_ids: Vec<i64> = igdb_ids.into_iter().collect();
         for chunk in all_igdb_ids.chunks(10) {
             if cancel.is_cancelled() {
-                return Ok(());
+                break;
             }
-            match client.fetch_games_by_ids(chunk).await {
+            match timings
+                .time("enrich.igdb", client.fetch_games_by_ids(chunk))
+                .await
+            {
                 Ok(games) => {
                     for game in games {
                         igdb_batch.insert(game.id, game);
@@ -565,11 +1192,30 @@ pub async fn enrich_roms(
         }
     }
 
-    for (i, rom) in roms.iter().enumerate() {
+    // Visible ROMs jump the queue; everything else is enriched in query order
+    let index: HashMap<i64, usize> = roms.iter().enumerate().map(|(i, r)| (r.id, i)).collect();
+    let mut done = vec![false; roms.len()];
+    let mut cursor = 0;
+    let mut enriched = 0;
+    let mut result = Ok(());
+
+    for i in 0..roms.len() {
         if cancel.is_cancelled() {
-            return Ok(());
+            break;
         }
 
+        let idx = match priority.pop_pending(|id| index.get(&id).is_some_and(|&j| !done[j])) {
+            Some(id) => index[&id],
+            None => {
+                while done[cursor] {
+                    cursor += 1;
+                }
+                cursor
+            }
+        };
+        done[idx] = true;
+        let rom = &roms[idx];
+
         #[allow(clippy::cast_possible_truncation)]
         let current = (i + 1) as u64;
         on_progress(ScanProgress {
@@ -579,29 +1225,52 @@ pub async fn enrich_roms(
             current_item: rom.name.clone(),
         });
 
+        // Another run already has this ROM and will finish it
+        let Some(_claim) = RomClaim::try_claim(rom.id) else {
+            continue;
+        };
+
         // Look up pre-fetched IGDB data for this ROM
         let igdb_prefetch = query_hasheous_igdb_id(db, rom.id)
             .await
             .and_then(|igdb_id| igdb_batch.get(&igdb_id).cloned());
 
+        // Offline, the caches are all there is to enrich from
+        let force_refresh = steps.force_refresh && !offline;
+        if force_refresh {
+            clear_hasheous_cache(db, rom.id).await;
+        }
+
         let opts = EnrichOptions {
             igdb_prefetch,
-            force_refresh: false,
+            force_refresh,
         };
 
-        enrich_one_rom(&ctx, rom, &opts).await?;
+        result = enrich_one_rom(&ctx, rom, &opts).await;
+        if result.is_err() {
+            break;
+        }
+        enriched += 1;
     }
 
-    Ok(())
+    timings.flush(db, None).await;
+    operation_log::record(db, "enrich", None, started, enriched, result.as_ref().err()).await;
+    result
 }
 
-/// Enrich a single ROM by ID — runs the full enrichment pipeline.
+/// Enrich a single ROM by ID — runs the enabled steps of the pipeline.
 /// Clears existing caches first so fresh data is fetched.
 pub async fn enrich_single_rom(
     rom_id: i64,
     db: &DatabaseConnection,
     igdb_client: Option<&igdb::IgdbClient>,
     ss_creds: Option<&screenscraper::SsUserCredentials>,
+    ss_prefs: &SsMediaPreferences,
+    artwork_chains: &ArtworkChains,
+    dedup_artwork: bool,
+    events: &LibraryEvents,
+    translator: Option<&translate::Translator>,
+    steps: &EnrichmentOptions,
 ) -> AppResult<()> {
     let rom = RomRow::find_by_statement(Statement::from_sql_and_values(
         DatabaseBackend::Sqlite,
@@ -610,39 +1279,112 @@ pub async fn enrich_single_rom(
     ))
     .one(db)
     .await?
-    .ok_or_else(|| AppError::Other(format!("ROM {rom_id} not found")))?;
+    .ok_or_else(|| AppError::NotFound(format!("ROM {rom_id}")))?;
 
-    // Clear existing hasheous cache so we re-fetch
-    let _ = db.execute(Statement::from_sql_and_values(
-        DatabaseBackend::Sqlite,
-        "DELETE FROM hasheous_cache WHERE rom_id = ?",
-        [rom_id.into()],
-    ))
-    .await;
+    let _claim = RomClaim::claim(rom_id).await;
+
+    // Clear existing hasheous cache so we re-fetch, unless offline
+    let offline = crate::http::is_offline();
+    if !offline {
+        clear_hasheous_cache(db, rom_id).await;
+    }
 
-    let http_client = reqwest::Client::builder()
+    let http_client = crate::http::client_builder()
         .user_agent("romm-buddy/0.1")
         .timeout(std::time::Duration::from_secs(30))
         .build()
         .unwrap_or_default();
 
     let has_launchbox = launchbox::has_imported_db(db).await;
+    let timings = StepTimings::default();
 
     let ctx = EnrichContext {
         db,
         http_client: &http_client,
-        igdb_client,
+        igdb_client: igdb_client.filter(|_| steps.igdb && !offline),
         ss_creds,
+        ss_prefs,
+        artwork_chains,
+        translator: translator.filter(|_| !offline),
         has_launchbox,
-        last_ss_request: tokio::sync::Mutex::new(std::time::Instant::now() - std::time::Duration::from_secs(2)),
+        steps,
+        offline,
+        // Hashing artwork means downloading it
+        dedup_artwork: dedup_artwork && !offline,
+        events,
+        timings: &timings,
     };
 
     let opts = EnrichOptions {
         igdb_prefetch: None,
-        force_refresh: true,
+        force_refresh: !offline,
     };
 
-    enrich_one_rom(&ctx, &rom, &opts).await
+    let detail = format!("rom {rom_id}");
+    let result = operation_log::timed(
+        db,
+        "enrich",
+        Some(&detail),
+        enrich_one_rom(&ctx, &rom, &opts),
+    )
+    .await;
+    timings.flush(db, Some(&detail)).await;
+    result
+}
+
+async fn clear_hasheous_cache(db: &DatabaseConnection, rom_id: i64) {
+    let _ = db.execute(Statement::from_sql_and_values(
+        DatabaseBackend::Sqlite,
+        "DELETE FROM hasheous_cache WHERE rom_id = ?",
+        [rom_id.into()],
+    ))
+    .await;
+}
+
+/// Perceptual-hash every artwork item of a ROM and delete later rows that
+/// look the same as an earlier one of the same type, along with any copy of
+/// them on disk. New artwork is checked as it's inserted, so this only finds
+/// duplicates stored while dedup was off.
+async fn dedup_rom_artwork(db: &DatabaseConnection, http_client: &reqwest::Client, rom_id: i64) {
+    let rows = match HashedArtRow::find_by_statement(Statement::from_sql_and_values(
+        DatabaseBackend::Sqlite,
+        "SELECT id, art_type, url, local_path, phash FROM artwork
+         WHERE rom_id = ? AND url IS NOT NULL AND art_type != 'user_screenshot'
+         ORDER BY id",
+        [rom_id.into()],
+    ))
+    .all(db)
+    .await
+    {
+        Ok(rows) => rows,
+        Err(e) => {
+            log::warn!("Failed to load artwork for dedup of rom {rom_id}: {e}");
+            return;
+        }
+    };
+
+    let mut kept: HashMap<String, Vec<u64>> = HashMap::new();
+    for row in rows {
+        let Some(hash) = artwork_row_hash(db, http_client, &row).await else {
+            continue;
+        };
+
+        let seen = kept.entry(row.art_type).or_default();
+        if seen.iter().any(|&k| phash::is_duplicate(k, hash)) {
+            log::info!("Dropping duplicate artwork {} for rom {rom_id}", row.url);
+            let deleted = db.execute(Statement::from_sql_and_values(
+                DatabaseBackend::Sqlite,
+                "DELETE FROM artwork WHERE id = ?",
+                [row.id.into()],
+            ))
+            .await;
+            if let (Ok(_), Some(path)) = (deleted, &row.local_path) {
+                let _ = tokio::fs::remove_file(path).await;
+            }
+        } else {
+            seen.push(hash);
+        }
+    }
 }
 
 /// Apply IGDB game data to database: insert into igdb_cache, update metadata, save artwork.
@@ -743,14 +1485,10 @@ async fn apply_igdb_data(db: &DatabaseConnection, rom_id: i64, game: &igdb::Igdb
         log::warn!("Failed to upsert IGDB metadata for rom {rom_id}: {e}");
     }
 
-    // Save IGDB cover art
-    if let Some(url) = game.cover_url() {
-        insert_artwork(db, rom_id, "cover", &url).await;
-    }
-
-    // Save IGDB screenshots
-    for url in game.screenshot_urls() {
-        insert_artwork(db, rom_id, "screenshot", &url).await;
+    let ratings =
+        age_rating::AgeRatings::from_igdb(game.age_ratings.as_deref().unwrap_or_default());
+    if let Err(e) = age_rating::store(db, rom_id, &ratings, true).await {
+        log::warn!("Failed to store IGDB age ratings for rom {rom_id}: {e}");
     }
 }
 
@@ -795,15 +1533,105 @@ async fn apply_screenscraper_metadata(
     {
         log::warn!("Failed to upsert ScreenScraper metadata for rom {rom_id}: {e}");
     }
+
+    if let Err(e) = age_rating::store(db, rom_id, &data.age_ratings, false).await {
+        log::warn!("Failed to store ScreenScraper age ratings for rom {rom_id}: {e}");
+    }
+
+    // The ROM's own language list beats what its file name implies
+    if !data.languages.is_empty() {
+        let languages = serde_json::to_string(&data.languages).unwrap_or_else(|_| "[]".to_string());
+        if let Err(e) = db
+            .execute(Statement::from_sql_and_values(
+                DatabaseBackend::Sqlite,
+                "UPDATE roms SET languages = ? WHERE id = ?",
+                [languages.into(), rom_id.into()],
+            ))
+            .await
+        {
+            log::warn!("Failed to store ScreenScraper languages for rom {rom_id}: {e}");
+        }
+    }
 }
 
-/// Apply ScreenScraper artwork (always append with ON CONFLICT DO NOTHING).
+/// Apply ScreenScraper artwork of `art_types` for the preferred region (ON CONFLICT DO NOTHING).
 async fn apply_screenscraper_artwork(
     db: &DatabaseConnection,
+    dedup: Option<&reqwest::Client>,
     rom_id: i64,
     media: &[screenscraper::SsMedia],
+    prefs: &SsMediaPreferences,
+    art_types: &[&str],
 ) {
-    for item in media {
-        insert_artwork(db, rom_id, &item.media_type, &item.url).await;
+    for item in screenscraper::select_media(media, prefs) {
+        if art_types.contains(&item.media_type.as_str()) {
+            insert_regional_artwork(
+                db,
+                dedup,
+                rom_id,
+                &item.media_type,
+                &item.url,
+                item.region.as_deref(),
+            )
+            .await;
+        }
+    }
+}
+
+/// Re-select regional ScreenScraper artwork from cached media after the
+/// region preferences change, without re-querying the API. Returns ROMs updated.
+pub async fn reselect_screenscraper_artwork(
+    db: &DatabaseConnection,
+    prefs: &SsMediaPreferences,
+    cancel: &CancellationToken,
+    events: &LibraryEvents,
+) -> AppResult<u64> {
+    #[derive(Debug, FromQueryResult)]
+    struct CachedMediaRow {
+        rom_id: i64,
+        raw_response: String,
+    }
+
+    let rows = CachedMediaRow::find_by_statement(Statement::from_string(
+        DatabaseBackend::Sqlite,
+        "SELECT rom_id, raw_response FROM screenscraper_cache WHERE raw_response LIKE '[%'",
+    ))
+    .all(db)
+    .await?;
+
+    let mut updated = 0u64;
+    for row in rows {
+        if cancel.is_cancelled() {
+            break;
+        }
+        let Ok(media) = serde_json::from_str::<Vec<screenscraper::SsMedia>>(&row.raw_response) else {
+            continue;
+        };
+        // Only replace art types ScreenScraper won in the provider chain
+        let art_types: Vec<String> = db
+            .query_all(Statement::from_sql_and_values(
+                DatabaseBackend::Sqlite,
+                "SELECT DISTINCT art_type FROM artwork
+                 WHERE rom_id = ? AND (provider = 'screenscraper' OR region IS NOT NULL)",
+                [row.rom_id.into()],
+            ))
+            .await?
+            .into_iter()
+            .filter_map(|r| r.try_get::<String>("", "art_type").ok())
+            .collect();
+        if art_types.is_empty() {
+            continue;
+        }
+        db.execute(Statement::from_sql_and_values(
+            DatabaseBackend::Sqlite,
+            "DELETE FROM artwork WHERE rom_id = ? AND (provider = 'screenscraper' OR region IS NOT NULL)",
+            [row.rom_id.into()],
+        ))
+        .await?;
+        let art_types: Vec<&str> = art_types.iter().map(String::as_str).collect();
+        apply_screenscraper_artwork(db, None, row.rom_id, &media, prefs, &art_types).await;
+        events.artwork_updated(vec![row.rom_id]);
+        updated += 1;
     }
+    Ok(updated)
 }
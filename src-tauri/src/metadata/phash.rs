@@ -0,0 +1,55 @@
+use image::imageops::FilterType;
+
+/// Max Hamming distance between two hashes that are still considered the same image.
+const DUPLICATE_THRESHOLD: u32 = 6;
+
+/// Compute a 64-bit difference hash (dHash) of an encoded image.
+/// Robust to rescaling and recompression, so the same cover served by two
+/// providers at different sizes hashes (nearly) identically.
+pub fn dhash(bytes: &[u8]) -> Option<u64> {
+    let img = image::load_from_memory(bytes).ok()?;
+    let small = img.resize_exact(9, 8, FilterType::Triangle).to_luma8();
+
+    let mut hash = 0u64;
+    for y in 0..8 {
+        for x in 0..8 {
+            let left = small.get_pixel(x, y)[0];
+            let right = small.get_pixel(x + 1, y)[0];
+            hash = (hash << 1) | u64::from(left > right);
+        }
+    }
+    Some(hash)
+}
+
+/// Download an image and compute its dHash. `None` if it can't be fetched or decoded.
+pub async fn fetch_dhash(client: &reqwest::Client, url: &str) -> Option<u64> {
+    let bytes = client
+        .get(url)
+        .send()
+        .await
+        .ok()?
+        .error_for_status()
+        .ok()?
+        .bytes()
+        .await
+        .ok()?;
+    tokio::task::spawn_blocking(move || dhash(&bytes))
+        .await
+        .ok()
+        .flatten()
+}
+
+/// Whether two hashes are close enough to be the same picture.
+pub fn is_duplicate(a: u64, b: u64) -> bool {
+    (a ^ b).count_ones() <= DUPLICATE_THRESHOLD
+}
+
+/// Hex representation stored in `artwork.phash`.
+pub fn format(hash: u64) -> String {
+    format!("{hash:016x}")
+}
+
+/// Parse a hash stored by [`format`].
+pub fn parse(s: &str) -> Option<u64> {
+    u64::from_str_radix(s, 16).ok()
+}
@@ -1,9 +1,9 @@
 use reqwest::Client;
 use sea_orm::DatabaseConnection;
-use std::time::{Duration, Instant};
-use tokio::sync::Mutex;
 
 use crate::error::{AppError, AppResult};
+use crate::http::{self, Provider};
+use crate::models::SsMediaPreferences;
 
 // ---------------------------------------------------------------------------
 // Developer credentials (identify this app to ScreenScraper)
@@ -33,36 +33,69 @@ pub struct SsGameData {
     pub game_id: Option<i64>,
     pub name: Option<String>,
     pub synopsis: Option<String>,
+    /// Language code of `synopsis`, which may not be a preferred one.
+    pub synopsis_language: Option<String>,
     pub developer: Option<String>,
     pub publisher: Option<String>,
     pub genre: Option<String>,
     pub release_date: Option<String>,
     pub rating: Option<f64>,
+    /// ISO 639-1 codes of the languages the matched ROM is in.
+    pub languages: Vec<String>,
+    pub age_ratings: super::age_rating::AgeRatings,
     pub media: Vec<SsMedia>,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
 pub struct SsMedia {
-    pub media_type: String, // cover, screenshot, fanart
+    pub media_type: String, // cover, screenshot, fanart, manual
     pub url: String,
+    pub region: Option<String>,
 }
 
 // ---------------------------------------------------------------------------
 // Cache helpers
 // ---------------------------------------------------------------------------
 
+/// Whether a lookup is cached: a match, or a miss younger than the
+/// ScreenScraper miss TTL.
 pub async fn is_cached(db: &DatabaseConnection, rom_id: i64) -> bool {
     use crate::entity::screenscraper_cache::{self, Column};
-    use sea_orm::{ColumnTrait, EntityTrait, PaginatorTrait, QueryFilter};
+    use sea_orm::{ColumnTrait, Condition, EntityTrait, PaginatorTrait, QueryFilter};
 
+    let ttl = crate::models::MetadataProvider::Screenscraper.miss_ttl_days();
+    let cutoff = (chrono::Utc::now() - chrono::Duration::days(ttl))
+        .format("%Y-%m-%dT%H:%M:%S")
+        .to_string();
     screenscraper_cache::Entity::find()
         .filter(Column::RomId.eq(rom_id))
+        .filter(
+            Condition::any()
+                .add(Column::RawResponse.ne(""))
+                .add(Column::FetchedAt.gt(cutoff)),
+        )
         .count(db)
         .await
         .unwrap_or(0)
         > 0
 }
 
+/// Media list cached by an earlier lookup; empty if none or a miss was cached.
+pub async fn cached_media(db: &DatabaseConnection, rom_id: i64) -> Vec<SsMedia> {
+    use crate::entity::screenscraper_cache::{self, Column};
+    use sea_orm::{ColumnTrait, EntityTrait, QueryFilter};
+
+    screenscraper_cache::Entity::find()
+        .filter(Column::RomId.eq(rom_id))
+        .one(db)
+        .await
+        .ok()
+        .flatten()
+        .and_then(|row| row.raw_response)
+        .and_then(|raw| serde_json::from_str(&raw).ok())
+        .unwrap_or_default()
+}
+
 pub async fn save_to_cache(
     db: &DatabaseConnection,
     rom_id: i64,
@@ -102,19 +135,8 @@ pub async fn lookup_game(
     md5: Option<&str>,
     rom_name: &str,
     system_id: i64,
-    last_request: &Mutex<Instant>,
+    prefs: &SsMediaPreferences,
 ) -> AppResult<Option<SsGameData>> {
-    // Rate limit: 1 request per second (timestamp-based, skip if enough time elapsed)
-    {
-        let mut last = last_request.lock().await;
-        let elapsed = last.elapsed();
-        let min_interval = Duration::from_secs(1);
-        if elapsed < min_interval {
-            tokio::time::sleep(min_interval - elapsed).await;
-        }
-        *last = Instant::now();
-    }
-
     let mut params: Vec<(&str, String)> = vec![
         ("devid", DEV_ID.to_string()),
         ("devpassword", DEV_PASSWORD.to_string()),
@@ -137,36 +159,34 @@ pub async fn lookup_game(
         }
     }
 
-    let resp = client
+    // Rate limited to 1 request per second
+    let request = client
         .get("https://api.screenscraper.fr/api2/jeuInfos.php")
-        .query(&params)
-        .send()
+        .query(&params);
+    let http::Fetched { status, body } = http::fetch_text(Provider::ScreenScraper, request)
         .await
         .map_err(|e| AppError::Other(format!("ScreenScraper request failed: {e}")))?;
 
-    if !resp.status().is_success() {
-        let status = resp.status();
+    if !status.is_success() {
         // 404 / 430 = game not found, not an error
         if status.as_u16() == 404 || status.as_u16() == 430 {
             return Ok(None);
         }
-        let body = resp.text().await.unwrap_or_default();
+        if matches!(status.as_u16(), 401 | 403 | 429) {
+            return Err(AppError::from_status("ScreenScraper", status));
+        }
         return Err(AppError::Other(format!(
             "ScreenScraper API returned {status}: {body}"
         )));
     }
 
-    let body = resp.text().await.map_err(|e| {
-        AppError::Other(format!("Failed to read ScreenScraper response: {e}"))
-    })?;
-
     // ScreenScraper returns plain text errors even with 200 status
     if body.starts_with("Erreur") || body.starts_with("API closed") {
         log::warn!("ScreenScraper returned error text: {}", &body[..body.len().min(200)]);
         return Ok(None);
     }
 
-    let parsed = parse_response(&body);
+    let parsed = parse_response(&body, prefs);
     Ok(parsed)
 }
 
@@ -175,7 +195,7 @@ pub async fn lookup_game(
 // ---------------------------------------------------------------------------
 
 /// Parse the deeply nested ScreenScraper JSON response.
-fn parse_response(body: &str) -> Option<SsGameData> {
+fn parse_response(body: &str, prefs: &SsMediaPreferences) -> Option<SsGameData> {
     let root: serde_json::Value = serde_json::from_str(body).ok()?;
     let jeu = root.get("response")?.get("jeu")?;
 
@@ -183,11 +203,16 @@ fn parse_response(body: &str) -> Option<SsGameData> {
         v.as_i64().or_else(|| v.as_str().and_then(|s| s.parse().ok()))
     });
 
-    let name = extract_regional_text(jeu.get("noms")?, &["us", "wor", "eu", "jp"]);
+    let regions: Vec<&str> = prefs.regions.iter().map(String::as_str).collect();
+    let languages: Vec<&str> = prefs.languages.iter().map(String::as_str).collect();
+
+    let name = extract_regional_text(jeu.get("noms")?, &regions);
 
-    let synopsis = jeu
+    let (synopsis, synopsis_language) = jeu
         .get("synopsis")
-        .and_then(|arr| extract_lang_text(arr, &["en", "us"]));
+        .and_then(|arr| extract_tagged_lang_text(arr, &languages))
+        .unzip();
+    let synopsis_language = synopsis_language.flatten();
 
     let developer = jeu
         .get("developpeur")
@@ -207,7 +232,7 @@ fn parse_response(body: &str) -> Option<SsGameData> {
                 .iter()
                 .filter_map(|g| {
                     g.get("noms").and_then(|noms| {
-                        extract_regional_text(noms, &["en", "us"])
+                        extract_lang_text(noms, &languages)
                     })
                 })
                 .collect();
@@ -223,10 +248,10 @@ fn parse_response(body: &str) -> Option<SsGameData> {
 
     let release_date = jeu.get("dates").and_then(|dates| {
         if let Some(arr) = dates.as_array() {
-            // Prefer US/world region
-            for region in &["us", "wor", "eu"] {
+            // Prefer the configured region order
+            for region in &regions {
                 for item in arr {
-                    if item.get("region").and_then(|r| r.as_str()) == Some(region) {
+                    if item.get("region").and_then(|r| r.as_str()) == Some(*region) {
                         if let Some(text) = item.get("text").and_then(|t| t.as_str()) {
                             return Some(text.to_string());
                         }
@@ -253,6 +278,13 @@ fn parse_response(body: &str) -> Option<SsGameData> {
             if r <= 20.0 { r * 5.0 } else { r }
         });
 
+    let rom_languages = extract_rom_languages(jeu);
+
+    let age_ratings = jeu
+        .get("classifications")
+        .map(super::age_rating::AgeRatings::from_screenscraper)
+        .unwrap_or_default();
+
     // Parse media
     let mut media = Vec::new();
     if let Some(medias) = jeu.get("medias").and_then(|m| m.as_array()) {
@@ -267,6 +299,7 @@ fn parse_response(body: &str) -> Option<SsGameData> {
                 "box-2D" | "box-2D-front" => Some("cover"),
                 "ss" | "sstitle" => Some("screenshot"),
                 "fanart" => Some("fanart"),
+                "manuel" => Some("manual"),
                 _ => None,
             };
 
@@ -274,6 +307,7 @@ fn parse_response(body: &str) -> Option<SsGameData> {
                 media.push(SsMedia {
                     media_type: art_type.to_string(),
                     url: url.to_string(),
+                    region: m.get("region").and_then(|r| r.as_str()).map(String::from),
                 });
             }
         }
@@ -283,15 +317,55 @@ fn parse_response(body: &str) -> Option<SsGameData> {
         game_id,
         name,
         synopsis,
+        synopsis_language,
         developer,
         publisher,
         genre,
         release_date,
         rating,
+        languages: rom_languages,
+        age_ratings,
         media,
     })
 }
 
+/// Position of a media item's region in the preferences; unlisted regions last.
+fn region_rank(m: &SsMedia, prefs: &SsMediaPreferences) -> usize {
+    m.region
+        .as_deref()
+        .and_then(|r| prefs.regions.iter().position(|p| p == r))
+        .unwrap_or(prefs.regions.len())
+}
+
+/// Pick the media to store: for each art type, only items from the best-ranked
+/// region present (a single cover, but every screenshot of that region).
+pub fn select_media<'a>(media: &'a [SsMedia], prefs: &SsMediaPreferences) -> Vec<&'a SsMedia> {
+    let rank = |m: &SsMedia| region_rank(m, prefs);
+
+    let mut selected = Vec::new();
+    for art_type in ["cover", "screenshot", "fanart"] {
+        let of_type: Vec<&SsMedia> = media.iter().filter(|m| m.media_type == art_type).collect();
+        let Some(best) = of_type.iter().map(|m| rank(m)).min() else {
+            continue;
+        };
+        let mut best_items = of_type.into_iter().filter(|m| rank(m) == best);
+        if art_type == "cover" {
+            selected.extend(best_items.next());
+        } else {
+            selected.extend(best_items);
+        }
+    }
+    selected
+}
+
+/// The manual (PDF) of the best-ranked region, if ScreenScraper has one.
+pub fn select_manual<'a>(media: &'a [SsMedia], prefs: &SsMediaPreferences) -> Option<&'a SsMedia> {
+    media
+        .iter()
+        .filter(|m| m.media_type == "manual")
+        .min_by_key(|m| region_rank(m, prefs))
+}
+
 /// Extract text from a ScreenScraper regional array, preferring the given regions.
 fn extract_regional_text(value: &serde_json::Value, preferred: &[&str]) -> Option<String> {
     let arr = value.as_array()?;
@@ -316,51 +390,111 @@ fn extract_regional_text(value: &serde_json::Value, preferred: &[&str]) -> Optio
 
 /// Extract text from a ScreenScraper language-keyed array (synopsis etc).
 fn extract_lang_text(value: &serde_json::Value, preferred: &[&str]) -> Option<String> {
+    extract_tagged_lang_text(value, preferred).map(|(text, _)| text)
+}
+
+/// Like [`extract_lang_text`], also returning the language of the text.
+fn extract_tagged_lang_text(
+    value: &serde_json::Value,
+    preferred: &[&str],
+) -> Option<(String, Option<String>)> {
     // Could be array of objects with "langue" key, or an object with lang keys
     if let Some(arr) = value.as_array() {
+        let text_of = |item: &serde_json::Value| {
+            let text = item.get("text").and_then(|t| t.as_str()).filter(|s| !s.is_empty())?;
+            let lang = item.get("langue").and_then(|l| l.as_str()).map(String::from);
+            Some((text.to_string(), lang))
+        };
         for lang in preferred {
             for item in arr {
                 if item.get("langue").and_then(|l| l.as_str()) == Some(lang) {
-                    if let Some(text) = item.get("text").and_then(|t| t.as_str()) {
-                        if !text.is_empty() {
-                            return Some(text.to_string());
-                        }
+                    if let Some(found) = text_of(item) {
+                        return Some(found);
                     }
                 }
             }
         }
         // Fallback: first
-        arr.first()
-            .and_then(|d| d.get("text"))
-            .and_then(|t| t.as_str())
-            .filter(|s| !s.is_empty())
-            .map(String::from)
+        arr.first().and_then(text_of)
     } else if let Some(obj) = value.as_object() {
         for lang in preferred {
             if let Some(text) = obj.get(*lang).and_then(|t| t.as_str()) {
                 if !text.is_empty() {
-                    return Some(text.to_string());
+                    return Some((text.to_string(), Some((*lang).to_string())));
                 }
             }
         }
-        obj.values()
+        obj.iter()
             .next()
-            .and_then(|t| t.as_str())
-            .filter(|s| !s.is_empty())
-            .map(String::from)
+            .and_then(|(lang, t)| Some((t.as_str()?, lang)))
+            .filter(|(s, _)| !s.is_empty())
+            .map(|(text, lang)| (text.to_string(), Some(lang.clone())))
     } else {
         None
     }
 }
 
+/// Languages of the matched ROM (`rom.langues.langues_shortname`), or of
+/// every ROM listed for the game when none was matched by hash.
+fn extract_rom_languages(jeu: &serde_json::Value) -> Vec<String> {
+    let of_rom = |rom: &serde_json::Value| -> Vec<String> {
+        // Either `{"langues_shortname": ["en", "fr"]}` or `"romlangues": "en,fr"`
+        let codes: Vec<&str> = match rom.get("langues").and_then(|l| l.get("langues_shortname")) {
+            Some(arr) => arr
+                .as_array()
+                .map(|a| a.iter().filter_map(|c| c.as_str()).collect())
+                .unwrap_or_default(),
+            None => rom
+                .get("romlangues")
+                .and_then(|l| l.as_str())
+                .map(|l| l.split(',').collect())
+                .unwrap_or_default(),
+        };
+        codes.into_iter().filter_map(normalize_language).collect()
+    };
+
+    let mut languages = jeu.get("rom").map(of_rom).unwrap_or_default();
+    if languages.is_empty() {
+        let roms = jeu.get("roms").and_then(|r| r.as_array());
+        for rom in roms.into_iter().flatten() {
+            languages.extend(of_rom(rom));
+        }
+    }
+    let mut seen = std::collections::HashSet::new();
+    languages.retain(|l| seen.insert(l.clone()));
+    languages
+}
+
+/// ScreenScraper language short name to ISO 639-1 (`jp` -> `ja`, `cz` -> `cs`).
+fn normalize_language(code: &str) -> Option<String> {
+    let code = code.trim().to_ascii_lowercase();
+    let code = match code.as_str() {
+        "jp" => "ja".to_string(),
+        "cz" => "cs".to_string(),
+        "dk" => "da".to_string(),
+        "se" => "sv".to_string(),
+        "gr" => "el".to_string(),
+        "kr" => "ko".to_string(),
+        "cn" => "zh".to_string(),
+        _ => code,
+    };
+    (code.len() == 2 && code.chars().all(|c| c.is_ascii_lowercase())).then_some(code)
+}
+
 // ---------------------------------------------------------------------------
 // Connection test
 // ---------------------------------------------------------------------------
 
-pub async fn test_connection(
-    client: &Client,
-    user_creds: &SsUserCredentials,
-) -> AppResult<crate::models::SsTestResult> {
+/// Requests the account has made today against its daily allowance.
+#[derive(Debug, Clone, Copy)]
+pub struct SsQuota {
+    pub requests_today: u64,
+    pub max_requests_per_day: Option<u64>,
+}
+
+/// Look up the user's account, failing with [`AppError::Auth`] when
+/// ScreenScraper doesn't accept the credentials.
+pub async fn user_quota(client: &Client, user_creds: &SsUserCredentials) -> AppResult<SsQuota> {
     let params: Vec<(&str, &str)> = vec![
         ("devid", DEV_ID),
         ("devpassword", DEV_PASSWORD),
@@ -370,40 +504,53 @@ pub async fn test_connection(
         ("sspassword", &user_creds.password),
     ];
 
-    let resp = client
+    let request = client
         .get("https://api.screenscraper.fr/api2/ssuserInfos.php")
-        .query(&params)
-        .send()
-        .await;
-
-    match resp {
-        Ok(r) => {
-            if r.status().is_success() {
-                let body = r.text().await.unwrap_or_default();
-                if body.starts_with("Erreur") || body.contains("\"ssuser\":null") {
-                    Ok(crate::models::SsTestResult {
-                        success: false,
-                        message: "Invalid credentials".to_string(),
-                    })
-                } else {
-                    Ok(crate::models::SsTestResult {
-                        success: true,
-                        message: format!("Connected as {}", user_creds.username),
-                    })
-                }
-            } else {
-                let status = r.status();
-                let body = r.text().await.unwrap_or_default();
-                log::warn!("ScreenScraper test_connection failed: status={status}, body={body}");
-                Ok(crate::models::SsTestResult {
-                    success: false,
-                    message: format!("API returned status {status}: {}", body.chars().take(200).collect::<String>()),
-                })
-            }
-        }
-        Err(e) => Ok(crate::models::SsTestResult {
+        .query(&params);
+    let resp = http::send(Provider::ScreenScraper, request).await?;
+    if !resp.status().is_success() {
+        let err = http::status_error("ScreenScraper", &resp);
+        let body = resp.text().await.unwrap_or_default();
+        log::warn!("ScreenScraper user lookup failed: {err}, body={body}");
+        return Err(err);
+    }
+    let body = resp.text().await?;
+    if body.starts_with("Erreur") {
+        return Err(AppError::Auth("Invalid credentials".to_string()));
+    }
+    let json: serde_json::Value = serde_json::from_str(&body)
+        .map_err(|e| AppError::Other(format!("Invalid ScreenScraper response: {e}")))?;
+    let user = &json["response"]["ssuser"];
+    if !user.is_object() {
+        return Err(AppError::Auth("Invalid credentials".to_string()));
+    }
+    // Numbers come back as strings
+    let number = |key: &str| match &user[key] {
+        serde_json::Value::String(s) => s.parse().ok(),
+        v => v.as_u64(),
+    };
+    Ok(SsQuota {
+        requests_today: number("requeststoday").unwrap_or(0),
+        max_requests_per_day: number("maxrequestsperday").filter(|&max| max > 0),
+    })
+}
+
+pub async fn test_connection(
+    client: &Client,
+    user_creds: &SsUserCredentials,
+) -> AppResult<crate::models::SsTestResult> {
+    Ok(match user_quota(client, user_creds).await {
+        Ok(_) => crate::models::SsTestResult {
+            success: true,
+            message: format!("Connected as {}", user_creds.username),
+        },
+        Err(AppError::Network(e)) => crate::models::SsTestResult {
             success: false,
             message: format!("Connection failed: {e}"),
-        }),
-    }
+        },
+        Err(e) => crate::models::SsTestResult {
+            success: false,
+            message: e.to_string(),
+        },
+    })
 }
@@ -0,0 +1,257 @@
+use md5::{Digest, Md5};
+use sea_orm::{ConnectionTrait, DatabaseBackend, DatabaseConnection, FromQueryResult, Statement};
+
+use crate::error::{AppError, AppResult};
+use crate::http::{self, Provider};
+use crate::models::{LocalizedDescription, TranslationService, TranslationSettings};
+
+/// A configured translation service and the language it translates into.
+pub struct Translator {
+    settings: TranslationSettings,
+    target: String,
+}
+
+struct Translation {
+    text: String,
+    detected_source: Option<String>,
+}
+
+#[derive(Debug, FromQueryResult)]
+struct I18nRow {
+    rom_id: i64,
+    language: String,
+    description: String,
+    source_language: Option<String>,
+    source_md5: String,
+    machine_translated: bool,
+    provider: Option<String>,
+    created_at: String,
+}
+
+impl From<I18nRow> for LocalizedDescription {
+    fn from(row: I18nRow) -> Self {
+        Self {
+            rom_id: row.rom_id,
+            language: row.language,
+            description: row.description,
+            source_language: row.source_language,
+            machine_translated: row.machine_translated,
+            provider: row.provider,
+            created_at: row.created_at,
+        }
+    }
+}
+
+/// `pt-BR` -> `pt`.
+fn base_language(code: &str) -> String {
+    code.split(['-', '_']).next().unwrap_or(code).to_lowercase()
+}
+
+fn service_name(service: TranslationService) -> &'static str {
+    match service {
+        TranslationService::DeepL => "deepl",
+        TranslationService::LibreTranslate => "libretranslate",
+    }
+}
+
+impl Translator {
+    /// None when translation is turned off or has no endpoint. The target is
+    /// the configured language, else the UI language, else English.
+    pub fn new(settings: TranslationSettings, ui_language: Option<&str>) -> Option<Self> {
+        if !settings.enabled || settings.endpoint.trim().is_empty() {
+            return None;
+        }
+        let target = settings
+            .target_language
+            .as_deref()
+            .or(ui_language)
+            .filter(|l| !l.is_empty())
+            .map_or_else(|| "en".to_string(), base_language);
+        Some(Self { settings, target })
+    }
+
+    pub fn target(&self) -> &str {
+        &self.target
+    }
+
+    /// Whether text in `language` is already in the target language.
+    pub fn is_target(&self, language: &str) -> bool {
+        base_language(language) == self.target
+    }
+
+    async fn translate(
+        &self,
+        client: &reqwest::Client,
+        text: &str,
+        source: Option<&str>,
+    ) -> AppResult<Translation> {
+        let endpoint = self.settings.endpoint.trim().trim_end_matches('/');
+        let api_key = self.settings.api_key.as_deref().filter(|k| !k.is_empty());
+
+        let request = match self.settings.service {
+            TranslationService::DeepL => {
+                let mut body = serde_json::json!({
+                    "text": [text],
+                    "target_lang": self.target.to_uppercase(),
+                });
+                if let Some(source) = source {
+                    body["source_lang"] = serde_json::json!(base_language(source).to_uppercase());
+                }
+                let request = client.post(format!("{endpoint}/v2/translate")).json(&body);
+                match api_key {
+                    Some(key) => request.header("Authorization", format!("DeepL-Auth-Key {key}")),
+                    None => request,
+                }
+            }
+            TranslationService::LibreTranslate => {
+                let mut body = serde_json::json!({
+                    "q": text,
+                    "source": source.map_or_else(|| "auto".to_string(), base_language),
+                    "target": self.target,
+                    "format": "text",
+                });
+                if let Some(key) = api_key {
+                    body["api_key"] = serde_json::json!(key);
+                }
+                client.post(format!("{endpoint}/translate")).json(&body)
+            }
+        };
+
+        let resp = http::send(Provider::Translation, request).await?;
+        let status = resp.status();
+        if status == reqwest::StatusCode::UNAUTHORIZED || status == reqwest::StatusCode::FORBIDDEN {
+            return Err(AppError::Auth(
+                "Translation service rejected the API key".to_string(),
+            ));
+        }
+        if !status.is_success() {
+            return Err(AppError::Other(format!(
+                "Translation service returned {status}"
+            )));
+        }
+        let v: serde_json::Value = resp.json().await?;
+
+        let (text, detected) = match self.settings.service {
+            TranslationService::DeepL => {
+                let first = v.get("translations").and_then(|t| t.get(0));
+                (
+                    first.and_then(|t| t.get("text")),
+                    first.and_then(|t| t.get("detected_source_language")),
+                )
+            }
+            TranslationService::LibreTranslate => (
+                v.get("translatedText"),
+                v.get("detectedLanguage").and_then(|d| d.get("language")),
+            ),
+        };
+        let text = text
+            .and_then(serde_json::Value::as_str)
+            .filter(|t| !t.is_empty())
+            .ok_or_else(|| AppError::Other("Translation service returned no text".to_string()))?;
+
+        Ok(Translation {
+            text: text.to_string(),
+            detected_source: detected
+                .and_then(serde_json::Value::as_str)
+                .map(base_language)
+                .or_else(|| source.map(base_language)),
+        })
+    }
+
+    /// Translate a ROM's description into the target language and cache it.
+    /// Returns the cached copy while the description is unchanged, and None
+    /// when there is no description or it is already in the target language.
+    pub async fn translate_description(
+        &self,
+        db: &DatabaseConnection,
+        client: &reqwest::Client,
+        rom_id: i64,
+        source_language: Option<&str>,
+    ) -> AppResult<Option<LocalizedDescription>> {
+        if source_language.is_some_and(|l| self.is_target(l)) {
+            return Ok(None);
+        }
+        let description: Option<String> = db
+            .query_one(Statement::from_sql_and_values(
+                DatabaseBackend::Sqlite,
+                "SELECT description FROM metadata WHERE rom_id = ?",
+                [rom_id.into()],
+            ))
+            .await?
+            .map(|row| row.try_get("", "description"))
+            .transpose()?
+            .flatten();
+        let Some(description) = description.filter(|d| !d.trim().is_empty()) else {
+            return Ok(None);
+        };
+        let source_md5 = format!("{:x}", Md5::digest(description.as_bytes()));
+
+        if let Some(row) = cached_row(db, rom_id, &self.target).await? {
+            if row.source_md5 == source_md5 {
+                return Ok(Some(row.into()));
+            }
+        }
+
+        let translation = self
+            .translate(client, &description, source_language)
+            .await?;
+        if translation
+            .detected_source
+            .as_deref()
+            .is_some_and(|l| self.is_target(l))
+        {
+            return Ok(None);
+        }
+
+        db.execute(Statement::from_sql_and_values(
+            DatabaseBackend::Sqlite,
+            "INSERT INTO metadata_i18n (rom_id, language, description, source_language, source_md5, machine_translated, provider)
+             VALUES (?, ?, ?, ?, ?, 1, ?)
+             ON CONFLICT(rom_id, language) DO UPDATE SET
+               description = excluded.description,
+               source_language = excluded.source_language,
+               source_md5 = excluded.source_md5,
+               machine_translated = 1,
+               provider = excluded.provider,
+               created_at = strftime('%Y-%m-%dT%H:%M:%fZ', 'now')",
+            [
+                rom_id.into(),
+                self.target.clone().into(),
+                translation.text.into(),
+                translation.detected_source.into(),
+                source_md5.into(),
+                service_name(self.settings.service).into(),
+            ],
+        ))
+        .await?;
+
+        Ok(cached_row(db, rom_id, &self.target).await?.map(Into::into))
+    }
+}
+
+async fn cached_row(
+    db: &DatabaseConnection,
+    rom_id: i64,
+    language: &str,
+) -> AppResult<Option<I18nRow>> {
+    Ok(I18nRow::find_by_statement(Statement::from_sql_and_values(
+        DatabaseBackend::Sqlite,
+        "SELECT rom_id, language, description, source_language, source_md5,
+                machine_translated, provider, created_at
+         FROM metadata_i18n WHERE rom_id = ? AND language = ?",
+        [rom_id.into(), language.into()],
+    ))
+    .one(db)
+    .await?)
+}
+
+/// The cached description of a ROM in `language`, if any.
+pub async fn cached(
+    db: &DatabaseConnection,
+    rom_id: i64,
+    language: &str,
+) -> AppResult<Option<LocalizedDescription>> {
+    Ok(cached_row(db, rom_id, &base_language(language))
+        .await?
+        .map(Into::into))
+}
@@ -0,0 +1,511 @@
+use std::collections::HashMap;
+use std::io::ErrorKind;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use futures_util::StreamExt;
+use sea_orm::{
+    ColumnTrait, DatabaseBackend, DatabaseConnection, EntityTrait, FromQueryResult, QueryFilter,
+    Statement,
+};
+use tokio::io::{AsyncBufReadExt, AsyncWrite, AsyncWriteExt, BufReader};
+use tokio::net::TcpStream;
+use tokio_util::sync::CancellationToken;
+
+use crate::entity::sources::{self, SourceType};
+use crate::error::{AppError, AppResult};
+use crate::models::{DeviceExportReport, MisterConfig, ScanProgress};
+use crate::platform_registry;
+use crate::sources::local_sync;
+use crate::sources::romm::RommClient;
+
+/// Where the MiSTer keeps its SD card; games go in `games/<core folder>/`.
+const MISTER_ROOT: &str = "/media/fat";
+const FTP_PORT: u16 = 21;
+const REMOTE_DEFAULT_PORT: u16 = 8182;
+const TIMEOUT: Duration = Duration::from_secs(15);
+
+#[derive(Debug, FromQueryResult)]
+struct MisterRow {
+    name: String,
+    file_name: String,
+    file_size: Option<i64>,
+    slug: String,
+    source_id: i64,
+    source_rom_id: String,
+    source_type: SourceType,
+}
+
+/// A minimal FTP client: enough of RFC 959 to create folders and upload
+/// files in passive mode, which is all MiSTer's FTP server is used for.
+struct Ftp {
+    host: String,
+    ctrl: BufReader<TcpStream>,
+}
+
+impl Ftp {
+    async fn connect(host: &str, user: &str, password: &str) -> AppResult<Self> {
+        let stream = tokio::time::timeout(TIMEOUT, TcpStream::connect((host, FTP_PORT)))
+            .await
+            .map_err(|_| AppError::Other(format!("Timed out connecting to {host}")))??;
+        let mut ftp = Self {
+            host: host.to_string(),
+            ctrl: BufReader::new(stream),
+        };
+        ftp.expect(None, &[220]).await?;
+        let (code, _) = ftp.command(&format!("USER {user}")).await?;
+        if code == 331 {
+            ftp.expect(Some(&format!("PASS {password}")), &[230])
+                .await
+                .map_err(|_| AppError::Auth("MiSTer FTP login failed".to_string()))?;
+        } else if code != 230 {
+            return Err(AppError::Auth("MiSTer FTP login failed".to_string()));
+        }
+        ftp.expect(Some("TYPE I"), &[200]).await?;
+        Ok(ftp)
+    }
+
+    /// Read one reply, following multi-line replies (`123-...` up to `123 ...`).
+    async fn reply(&mut self) -> AppResult<(u32, String)> {
+        let mut line = String::new();
+        if self.ctrl.read_line(&mut line).await? == 0 {
+            return Err(std::io::Error::new(
+                ErrorKind::UnexpectedEof,
+                "MiSTer closed the FTP connection",
+            )
+            .into());
+        }
+        let code: u32 = line
+            .get(..3)
+            .and_then(|c| c.parse().ok())
+            .ok_or_else(|| AppError::Other(format!("Unexpected FTP reply: {}", line.trim())))?;
+        if line.as_bytes().get(3) == Some(&b'-') {
+            let end = format!("{code} ");
+            loop {
+                line.clear();
+                if self.ctrl.read_line(&mut line).await? == 0 || line.starts_with(&end) {
+                    break;
+                }
+            }
+        }
+        Ok((code, line.trim().to_string()))
+    }
+
+    async fn command(&mut self, cmd: &str) -> AppResult<(u32, String)> {
+        self.ctrl
+            .get_mut()
+            .write_all(format!("{cmd}\r\n").as_bytes())
+            .await?;
+        tokio::time::timeout(TIMEOUT, self.reply())
+            .await
+            .map_err(|_| std::io::Error::new(ErrorKind::TimedOut, "MiSTer stopped responding"))?
+    }
+
+    /// Send `cmd` (or only read the pending reply) and fail unless the
+    /// reply code is one of `codes`.
+    async fn expect(&mut self, cmd: Option<&str>, codes: &[u32]) -> AppResult<String> {
+        let (code, text) = match cmd {
+            Some(cmd) => self.command(cmd).await?,
+            None => self.reply().await?,
+        };
+        if codes.contains(&code) {
+            Ok(text)
+        } else {
+            Err(AppError::Other(format!("FTP error: {text}")))
+        }
+    }
+
+    async fn size(&mut self, path: &str) -> AppResult<Option<u64>> {
+        let (code, text) = self.command(&format!("SIZE {path}")).await?;
+        Ok((code == 213)
+            .then(|| text.get(4..).and_then(|s| s.trim().parse().ok()))
+            .flatten())
+    }
+
+    /// Create `dir` and its parents; folders that already exist are fine.
+    async fn mkdirs(&mut self, dir: &str) -> AppResult<()> {
+        let mut current = String::new();
+        for part in dir.split('/').filter(|p| !p.is_empty()) {
+            current.push('/');
+            current.push_str(part);
+            self.command(&format!("MKD {current}")).await?;
+        }
+        Ok(())
+    }
+
+    /// Open a passive data connection. The address in the reply is ignored
+    /// in favour of the control host, since MiSTer may report an internal one.
+    async fn data(&mut self) -> AppResult<TcpStream> {
+        let text = self.expect(Some("PASV"), &[227]).await?;
+        let octets: Option<Vec<u8>> = text
+            .split(['(', ')'])
+            .nth(1)
+            .unwrap_or_default()
+            .split(',')
+            .map(|n| n.trim().parse().ok())
+            .collect();
+        let Some([.., hi, lo]) = octets.as_deref() else {
+            return Err(AppError::Other(format!("Unexpected FTP reply: {text}")));
+        };
+        let port = (u16::from(*hi) << 8) | u16::from(*lo);
+        let host = self.host.clone();
+        tokio::time::timeout(TIMEOUT, TcpStream::connect((host.as_str(), port)))
+            .await
+            .map_err(|_| AppError::Other("Timed out opening FTP data connection".to_string()))?
+            .map_err(Into::into)
+    }
+
+    async fn quit(mut self) {
+        let _ = self.command("QUIT").await;
+    }
+}
+
+/// How ROMs reach the MiSTer: its FTP server, or its SD card shared over
+/// SMB and mounted on this computer.
+enum Target {
+    Ftp(Ftp),
+    Share(PathBuf),
+}
+
+impl Target {
+    async fn open(config: &MisterConfig) -> AppResult<Self> {
+        match config.smb_mount.as_deref().filter(|m| !m.is_empty()) {
+            Some(mount) => {
+                let mount = PathBuf::from(mount);
+                if !mount.join("games").is_dir() {
+                    return Err(AppError::Other(format!(
+                        "No games folder in {}; is the MiSTer share mounted?",
+                        mount.display()
+                    )));
+                }
+                Ok(Self::Share(mount))
+            }
+            None => Ok(Self::Ftp(
+                Ftp::connect(&config.host, &config.ftp_user, &config.ftp_password).await?,
+            )),
+        }
+    }
+
+    /// Size of a file at `rel` (relative to the SD card), if it exists.
+    async fn size(&mut self, rel: &str) -> AppResult<Option<u64>> {
+        match self {
+            Self::Ftp(ftp) => ftp.size(&format!("{MISTER_ROOT}/{rel}")).await,
+            Self::Share(mount) => Ok(tokio::fs::metadata(mount.join(rel))
+                .await
+                .ok()
+                .map(|m| m.len())),
+        }
+    }
+
+    /// Upload a file to `rel` through a `.part` file, renamed once complete.
+    async fn upload(
+        &mut self,
+        rel: &str,
+        payload: &Payload<'_>,
+        clients: &HashMap<i64, RommClient>,
+        cancel: &CancellationToken,
+    ) -> AppResult<u64> {
+        let (dir, name) = rel.rsplit_once('/').unwrap_or(("", rel));
+        let part = format!("{dir}/.{name}.part");
+        match self {
+            Self::Ftp(ftp) => {
+                ftp.mkdirs(&format!("{MISTER_ROOT}/{dir}")).await?;
+                let mut data = ftp.data().await?;
+                ftp.expect(Some(&format!("STOR {MISTER_ROOT}/{part}")), &[125, 150])
+                    .await?;
+                let written = pump(payload, clients, &mut data, cancel).await;
+                let _ = data.shutdown().await;
+                drop(data);
+                let written = match (written, ftp.expect(None, &[226, 250]).await) {
+                    (Ok(written), Ok(_)) => written,
+                    (Err(e), _) | (Ok(_), Err(e)) => {
+                        let _ = ftp.command(&format!("DELE {MISTER_ROOT}/{part}")).await;
+                        return Err(e);
+                    }
+                };
+                ftp.expect(Some(&format!("RNFR {MISTER_ROOT}/{part}")), &[350])
+                    .await?;
+                ftp.expect(Some(&format!("RNTO {MISTER_ROOT}/{rel}")), &[250])
+                    .await?;
+                Ok(written)
+            }
+            Self::Share(mount) => {
+                tokio::fs::create_dir_all(mount.join(dir)).await?;
+                let part = mount.join(part);
+                let result = async {
+                    let mut file = tokio::fs::File::create(&part).await?;
+                    let written = pump(payload, clients, &mut file, cancel).await?;
+                    file.flush().await?;
+                    Ok::<u64, AppError>(written)
+                }
+                .await;
+                match result {
+                    Ok(written) => {
+                        tokio::fs::rename(&part, mount.join(rel)).await?;
+                        Ok(written)
+                    }
+                    Err(e) => {
+                        let _ = tokio::fs::remove_file(&part).await;
+                        Err(e)
+                    }
+                }
+            }
+        }
+    }
+
+    async fn close(self) {
+        if let Self::Ftp(ftp) = self {
+            ftp.quit().await;
+        }
+    }
+}
+
+/// What an upload writes to the MiSTer.
+enum Payload<'a> {
+    /// A file on this computer: a local ROM or one of its tracks.
+    File(&'a Path),
+    /// A ROM downloaded from its ROMM source.
+    Romm(&'a MisterRow),
+}
+
+/// Write an upload's bytes into `out`, read from disk or downloaded from ROMM.
+async fn pump<W: AsyncWrite + Unpin>(
+    payload: &Payload<'_>,
+    clients: &HashMap<i64, RommClient>,
+    out: &mut W,
+    cancel: &CancellationToken,
+) -> AppResult<u64> {
+    match *payload {
+        Payload::File(path) => {
+            let mut file = tokio::fs::File::open(path).await?;
+            Ok(tokio::io::copy(&mut file, out).await?)
+        }
+        Payload::Romm(row) => {
+            let client = clients
+                .get(&row.source_id)
+                .ok_or_else(|| AppError::SourceNotFound(row.source_id.to_string()))?;
+            let romm_id: i64 = row
+                .source_rom_id
+                .parse()
+                .map_err(|_| AppError::Other("Invalid source ROM ID".to_string()))?;
+            let resp = client.download_rom(romm_id, &row.file_name).await?;
+            let mut stream = resp.bytes_stream();
+            let mut written: u64 = 0;
+            while let Some(chunk) = stream.next().await {
+                if cancel.is_cancelled() {
+                    return Err(AppError::Cancelled("Export".to_string()));
+                }
+                let chunk = chunk?;
+                out.write_all(&chunk).await?;
+                written += chunk.len() as u64;
+            }
+            Ok(written)
+        }
+    }
+}
+
+async fn mister_rows(db: &DatabaseConnection, rom_ids: &[i64]) -> AppResult<Vec<MisterRow>> {
+    if rom_ids.is_empty() {
+        return Ok(Vec::new());
+    }
+    // One source per ROM, preferring a local copy over a download
+    let placeholders = vec!["?"; rom_ids.len()].join(",");
+    let sql = format!(
+        "SELECT r.name, r.file_name, r.file_size, p.slug,
+                sr.source_id, sr.source_rom_id, s.source_type
+         FROM roms r
+         JOIN platforms p ON p.id = r.platform_id
+         JOIN source_roms sr ON sr.id = (
+             SELECT sr2.id FROM source_roms sr2
+             JOIN sources s2 ON s2.id = sr2.source_id
+             WHERE sr2.rom_id = r.id AND s2.enabled = 1 AND sr2.source_rom_id IS NOT NULL
+             ORDER BY s2.source_type = 'local' DESC, sr2.id
+             LIMIT 1
+         )
+         JOIN sources s ON s.id = sr.source_id
+         WHERE r.id IN ({placeholders})
+         ORDER BY p.slug, r.name"
+    );
+    Ok(MisterRow::find_by_statement(Statement::from_sql_and_values(
+        DatabaseBackend::Sqlite,
+        &sql,
+        rom_ids.iter().map(|&id| id.into()),
+    ))
+    .all(db)
+    .await?)
+}
+
+/// Path of a ROM relative to the MiSTer's SD card, `games/<core folder>/<file>`.
+fn relative_path(slug: &str, file_name: &str) -> Option<String> {
+    platform_registry::mister_folder(slug).map(|folder| format!("games/{folder}/{file_name}"))
+}
+
+/// The tracks or discs a local multi-file ROM at `path` needs, with where
+/// each goes next to the ROM's `rel` and its size.
+fn companion_uploads(path: &Path, rel: &str) -> Vec<(PathBuf, String, u64)> {
+    let dir = rel.rsplit_once('/').map_or("", |(dir, _)| dir);
+    local_sync::companion_files(path)
+        .into_iter()
+        .filter_map(|file| {
+            let size = std::fs::metadata(&file).ok()?.len();
+            let sub = local_sync::companion_relative_path(path, &file)
+                .to_string_lossy()
+                .replace('\\', "/");
+            Some((file, format!("{dir}/{sub}"), size))
+        })
+        .collect()
+}
+
+/// Check the MiSTer can be reached and written to with `config`.
+pub async fn test(config: &MisterConfig) -> AppResult<()> {
+    Target::open(config).await?.close().await;
+    Ok(())
+}
+
+/// Upload the given ROMs into the MiSTer's games folders, named after the
+/// core that runs each platform. ROMs already there with the same size are
+/// skipped; ROMs of platforms without a MiSTer core are reported as failed.
+pub async fn export(
+    db: &DatabaseConnection,
+    config: &MisterConfig,
+    rom_ids: &[i64],
+    on_progress: impl Fn(ScanProgress),
+    cancel: &CancellationToken,
+) -> AppResult<DeviceExportReport> {
+    let rows = mister_rows(db, rom_ids).await?;
+    let clients: HashMap<i64, RommClient> = sources::Entity::find()
+        .filter(sources::Column::SourceType.eq(SourceType::Romm))
+        .all(db)
+        .await?
+        .into_iter()
+        .map(|s| {
+            (
+                s.id,
+                RommClient::from_source(s.url.unwrap_or_default(), &s.credentials),
+            )
+        })
+        .collect();
+
+    let mut target = Target::open(config).await?;
+    let total = rows.len() as u64;
+    let mut report = DeviceExportReport::default();
+
+    for (i, row) in rows.into_iter().enumerate() {
+        if cancel.is_cancelled() {
+            report.cancelled = true;
+            break;
+        }
+        on_progress(ScanProgress {
+            source_id: -1,
+            total,
+            current: i as u64,
+            current_item: row.name.clone(),
+        });
+
+        let Some(rel) = relative_path(&row.slug, &row.file_name) else {
+            log::warn!("No MiSTer core for {} ({})", row.file_name, row.slug);
+            report.failed.push(row.file_name);
+            continue;
+        };
+        let (payload, companions) = match row.source_type {
+            SourceType::Local => {
+                let path = Path::new(&row.source_rom_id);
+                (Payload::File(path), companion_uploads(path, &rel))
+            }
+            SourceType::Romm => (Payload::Romm(&row), Vec::new()),
+        };
+        let mut missing = Vec::new();
+        for (file, file_rel, size) in companions {
+            if target.size(&file_rel).await? != Some(size) {
+                missing.push((file, file_rel));
+            }
+        }
+        let size = row.file_size.and_then(|s| u64::try_from(s).ok());
+        let present = size.is_some() && target.size(&rel).await? == size;
+        if present && missing.is_empty() {
+            report.skipped += 1;
+            continue;
+        }
+
+        // Tracks first, so a cue sheet on the card always has its tracks
+        let result = async {
+            let mut bytes = 0;
+            for (file, file_rel) in &missing {
+                bytes += target
+                    .upload(file_rel, &Payload::File(file), &clients, cancel)
+                    .await?;
+            }
+            if !present {
+                bytes += target.upload(&rel, &payload, &clients, cancel).await?;
+            }
+            Ok::<u64, AppError>(bytes)
+        }
+        .await;
+        match result {
+            Ok(bytes) => {
+                report.copied += 1;
+                report.bytes_written += bytes;
+            }
+            Err(_) if cancel.is_cancelled() => {
+                report.cancelled = true;
+                break;
+            }
+            // A dropped connection fails every remaining ROM, so stop here
+            Err(e) if matches!(target, Target::Ftp(_)) && is_disconnect(&e) => return Err(e),
+            Err(e) => {
+                log::warn!("MiSTer export of {} failed: {e}", row.file_name);
+                report.failed.push(row.file_name);
+            }
+        }
+    }
+
+    target.close().await;
+    Ok(report)
+}
+
+fn is_disconnect(e: &AppError) -> bool {
+    matches!(
+        e,
+        AppError::Io(e) if matches!(
+            e.kind(),
+            ErrorKind::BrokenPipe
+                | ErrorKind::ConnectionReset
+                | ErrorKind::ConnectionAborted
+                | ErrorKind::UnexpectedEof
+                | ErrorKind::TimedOut
+        )
+    )
+}
+
+/// Start a ROM on the MiSTer through MiSTer Remote's API. The ROM must
+/// have been exported there first.
+pub async fn launch(
+    db: &DatabaseConnection,
+    client: &reqwest::Client,
+    config: &MisterConfig,
+    rom_id: i64,
+) -> AppResult<()> {
+    let row = mister_rows(db, &[rom_id])
+        .await?
+        .into_iter()
+        .next()
+        .ok_or_else(|| AppError::NotFound(format!("ROM {rom_id}")))?;
+    let rel = relative_path(&row.slug, &row.file_name)
+        .ok_or_else(|| AppError::Other(format!("No MiSTer core for {}", row.slug)))?;
+
+    let url = format!(
+        "http://{}:{}/api/games/launch",
+        config.host,
+        config.remote_port.unwrap_or(REMOTE_DEFAULT_PORT)
+    );
+    let resp = client
+        .post(&url)
+        .json(&serde_json::json!({ "path": format!("{MISTER_ROOT}/{rel}") }))
+        .send()
+        .await?;
+    if !resp.status().is_success() {
+        return Err(crate::http::status_error("MiSTer Remote", &resp));
+    }
+    log::info!("Launched {rel} on MiSTer {}", config.host);
+    Ok(())
+}
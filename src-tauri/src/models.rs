@@ -1,6 +1,8 @@
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 
+pub use crate::entity::library::CompletionStatus;
+pub use crate::entity::platforms::ArchiveHandling;
 pub use crate::entity::roms::VerificationStatus;
 pub use crate::entity::sources::SourceType;
 
@@ -10,7 +12,12 @@ pub struct Platform {
     pub slug: String,
     pub name: String,
     pub igdb_id: Option<i64>,
+    /// Extensions the user added on top of `default_extensions`.
     pub file_extensions: Vec<String>,
+    /// Extensions the platform registry knows for this platform.
+    pub default_extensions: Vec<String>,
+    pub archived: bool,
+    pub archive_handling: ArchiveHandling,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -25,6 +32,293 @@ pub struct SourceConfig {
     pub updated_at: DateTime<Utc>,
 }
 
+/// A single file move planned by `organize_local_source`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OrganizeMove {
+    pub rom_id: i64,
+    pub from: String,
+    pub to: String,
+    /// Tracks or discs the ROM's cue/gdi/m3u sheet lists, moved along with it.
+    pub companions: Vec<CompanionMove>,
+}
+
+/// A file moved next to a ROM by `organize_local_source`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CompanionMove {
+    pub from: String,
+    pub to: String,
+}
+
+/// Result of organizing a local source into a folder layout.
+/// With `applied = false` this is the dry-run plan; nothing was moved.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OrganizePlan {
+    pub moves: Vec<OrganizeMove>,
+    /// Moves skipped because the destination exists (or failed when applying).
+    pub conflicts: Vec<OrganizeMove>,
+    pub unchanged: u32,
+    pub applied: bool,
+}
+
+/// A ROM file found by `browse_folder`, as it would appear after importing.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BrowsedRom {
+    pub platform_slug: String,
+    pub platform_name: String,
+    pub rom_name: String,
+    pub file_name: String,
+    pub file_path: String,
+    pub file_size: Option<i64>,
+    /// Title from the ROM's internal header, when the format has one.
+    pub header_title: Option<String>,
+    pub header_serial: Option<String>,
+    /// A ROM with the same platform and file name is already in the library.
+    pub in_library: bool,
+}
+
+/// Result of browsing a folder without importing it.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FolderBrowse {
+    pub layout: crate::sources::local_sync::FolderLayout,
+    pub roms: Vec<BrowsedRom>,
+}
+
+/// Outcome of importing RetroArch's content history.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct HistoryImportReport {
+    pub entries: u32,
+    /// Entries that matched a library ROM.
+    pub matched: u32,
+    /// Library rows whose play data moved forward.
+    pub updated: u32,
+}
+
+/// Outcome of `export_to_device`.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct DeviceExportReport {
+    pub copied: u32,
+    /// ROMs already on the device with the same size.
+    pub skipped: u32,
+    pub covers: u32,
+    pub gamelists: u32,
+    /// ES-DE custom collections written.
+    pub collections: u32,
+    /// File names that could not be copied or downloaded.
+    pub failed: Vec<String>,
+    pub bytes_written: u64,
+    pub cancelled: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ActivityKind {
+    GameLaunched,
+    AchievementUnlocked,
+    RomsAdded,
+    CollectionUpdated,
+}
+
+/// An entry of the activity feed.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ActivityEntry {
+    pub id: i64,
+    pub kind: ActivityKind,
+    pub rom_id: Option<i64>,
+    pub collection_id: Option<i64>,
+    pub source_id: Option<i64>,
+    /// The game, collection or source the entry is about.
+    pub title: String,
+    /// Platform of a launched game, the achievement unlocked, or what
+    /// changed in a collection.
+    pub detail: Option<String>,
+    /// ROMs added by a sync; 1 otherwise.
+    pub count: i64,
+    pub cover_url: Option<String>,
+    pub created_at: String,
+}
+
+/// A game in a year's most-played list.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WrappedGame {
+    /// `None` once the ROM has left the library.
+    pub rom_id: Option<i64>,
+    pub name: String,
+    pub platform_name: Option<String>,
+    pub launches: i64,
+    pub cover_url: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WrappedPlatform {
+    pub name: String,
+    pub launches: i64,
+    /// Different games launched on it.
+    pub games: i64,
+}
+
+/// End-of-year summary of the current profile's play.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct YearlyWrapped {
+    pub year: i32,
+    pub launches: i64,
+    pub games_played: i64,
+    /// Most launched first.
+    pub top_games: Vec<WrappedGame>,
+    pub top_platforms: Vec<WrappedPlatform>,
+    /// January first.
+    pub launches_by_month: Vec<i64>,
+    pub roms_added: i64,
+    pub achievements_earned: u32,
+    pub achievement_points: u32,
+    pub games_beaten: i64,
+    pub games_completed: i64,
+}
+
+/// A timed sync, download, hash or enrichment step from `operation_log`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OperationLogEntry {
+    pub id: i64,
+    /// `sync`, `download`, `hash`, `enrich`, or an enrichment step such as
+    /// `enrich.igdb`.
+    pub operation: String,
+    pub detail: Option<String>,
+    pub started_at: String,
+    /// Total across `count` calls for a step.
+    pub duration_ms: i64,
+    pub count: i64,
+    pub success: bool,
+    pub error: Option<String>,
+}
+
+/// The LAN library API. Not synced, since it holds the access token.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ApiServerConfig {
+    pub enabled: bool,
+    pub port: u16,
+    /// Clients send it as a bearer token or a `token` query parameter.
+    pub token: String,
+}
+
+/// Services that can be given their own proxy instead of the global one.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ProxyService {
+    Romm,
+    Igdb,
+    #[serde(rename = "screenscraper")]
+    ScreenScraper,
+    Hasheous,
+    #[serde(rename = "launchbox")]
+    LaunchBox,
+}
+
+/// Proxy for outbound requests. Not synced, since it depends on the
+/// machine's network.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ProxySettings {
+    /// `http://`, `https://`, `socks5://` or `socks5h://` URL, optionally
+    /// with `user:password@`. `None` connects directly (or through the
+    /// system proxy from `HTTPS_PROXY` and friends).
+    pub url: Option<String>,
+    /// Per-service proxy URL; an empty string connects that service
+    /// directly even when there's a global proxy.
+    pub overrides: std::collections::HashMap<ProxyService, String>,
+}
+
+/// A MiSTer FPGA to export ROMs to. Not synced, since it holds credentials.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MisterConfig {
+    /// Host name or IP address on the local network.
+    pub host: String,
+    /// FTP login; MiSTer's default is `root` / `1`.
+    pub ftp_user: String,
+    pub ftp_password: String,
+    /// Where the MiSTer's SMB share is mounted on this computer. When set,
+    /// ROMs are copied there instead of over FTP.
+    pub smb_mount: Option<String>,
+    /// Port MiSTer Remote listens on, for launching games; defaults to 8182.
+    pub remote_port: Option<u16>,
+}
+
+/// Per-source sync filters, stored as JSON in `sources.settings`. Empty
+/// lists and unset sizes don't filter anything.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SourceSettings {
+    /// Only these platform slugs are synced.
+    pub include_platforms: Vec<String>,
+    pub exclude_platforms: Vec<String>,
+    pub min_file_size: Option<i64>,
+    pub max_file_size: Option<i64>,
+    /// Only files with these extensions (without the dot) are synced.
+    pub include_extensions: Vec<String>,
+    pub exclude_extensions: Vec<String>,
+}
+
+impl SourceSettings {
+    pub fn allows_platform(&self, slug: &str) -> bool {
+        let listed = |list: &[String]| list.iter().any(|s| s.eq_ignore_ascii_case(slug));
+        (self.include_platforms.is_empty() || listed(&self.include_platforms))
+            && !listed(&self.exclude_platforms)
+    }
+
+    /// Whether a file passes the extension and size filters. Files of
+    /// unknown size pass the size filters.
+    pub fn allows_file(&self, file_name: &str, size: Option<i64>) -> bool {
+        let ext = std::path::Path::new(file_name)
+            .extension()
+            .and_then(|e| e.to_str())
+            .unwrap_or_default();
+        let listed = |list: &[String]| list.iter().any(|e| e.eq_ignore_ascii_case(ext));
+        (self.include_extensions.is_empty() || listed(&self.include_extensions))
+            && !listed(&self.exclude_extensions)
+            && size.is_none_or(|size| {
+                self.min_file_size.is_none_or(|min| size >= min)
+                    && self.max_file_size.is_none_or(|max| size <= max)
+            })
+    }
+}
+
+/// Result of `sync_source`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SyncSummary {
+    pub source_id: i64,
+    /// ROMs linked to the source after the sync.
+    pub rom_count: i64,
+    /// Duplicate ROMs merged by the post-sync dedup pass.
+    pub merged_duplicates: u64,
+    pub cancelled: bool,
+}
+
+/// Payload of the `library-changed` event.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LibraryChanged {
+    /// Source that was synced; `None` for library-wide changes like a manual dedup.
+    pub source_id: Option<i64>,
+    pub merged_duplicates: u64,
+}
+
+/// Payload of the `library://` ROM events.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LibraryRomsEvent {
+    pub rom_ids: Vec<i64>,
+    /// Source whose sync added the ROMs (`library://rom-added` only).
+    pub source_id: Option<i64>,
+    /// ROMs deleted by a dedup merge into `rom_ids`.
+    pub merged_rom_ids: Vec<i64>,
+}
+
+impl LibraryRomsEvent {
+    pub fn for_roms(rom_ids: Vec<i64>) -> Self {
+        Self {
+            rom_ids,
+            source_id: None,
+            merged_rom_ids: Vec::new(),
+        }
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ScanProgress {
     pub source_id: i64,
@@ -33,6 +327,57 @@ pub struct ScanProgress {
     pub current_item: String,
 }
 
+/// What a background job is doing.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum JobKind {
+    Sync,
+    Enrichment,
+    LaunchBoxImport,
+    Verification,
+    DatImport,
+    Dedup,
+    CacheScan,
+    Cleanup,
+    ArtworkReselect,
+    DeviceExport,
+    Download,
+    Conversion,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum JobState {
+    Running,
+    Completed,
+    Failed,
+    Cancelled,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct JobProgress {
+    pub current: u64,
+    pub total: u64,
+    /// What's being worked on, e.g. a ROM name.
+    pub item: String,
+}
+
+/// A long-running operation, as listed by `list_jobs` and sent with every
+/// `jobs://updated` event.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Job {
+    pub id: u64,
+    pub kind: JobKind,
+    /// The source being synced or the ROM being downloaded.
+    pub target_id: Option<i64>,
+    pub label: String,
+    pub state: JobState,
+    pub progress: Option<JobProgress>,
+    pub error: Option<String>,
+    pub started_at: String,
+    pub finished_at: Option<String>,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct RomWithMeta {
     pub id: i64,
@@ -51,6 +396,10 @@ pub struct RomWithMeta {
     pub genres: Vec<String>,
     pub themes: Vec<String>,
     pub languages: Vec<String>,
+    /// Revision from the file name: `1`, `A`, `1.1`.
+    pub revision: Option<String>,
+    /// GoodTools/TOSEC flags from the file name: `verified`, `bad`, `hack`, ...
+    pub dump_flags: Vec<String>,
     pub cover_url: Option<String>,
     pub screenshot_urls: Vec<String>,
     pub source_id: i64,
@@ -63,6 +412,120 @@ pub struct RomWithMeta {
     pub favorite: bool,
     pub verification_status: Option<VerificationStatus>,
     pub dat_game_name: Option<String>,
+    /// Skipped by the stale-metadata refresh.
+    pub metadata_pinned: bool,
+    /// Fields edited by hand, which enrichment leaves alone.
+    pub user_edited: Vec<String>,
+    pub esrb_rating: Option<String>,
+    pub pegi_rating: Option<String>,
+    pub note: Option<String>,
+    pub tags: Vec<String>,
+    pub completion_status: CompletionStatus,
+    pub homebrew_kind: Option<crate::entity::rom_homebrew::HomebrewKind>,
+    /// The same game on other platforms in the library. Only `get_rom` fills this.
+    pub other_platforms: Vec<PlatformCopy>,
+}
+
+/// A copy of a game on another platform.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PlatformCopy {
+    pub rom_id: i64,
+    pub name: String,
+    pub platform_id: i64,
+    pub platform_slug: String,
+    pub platform_name: String,
+}
+
+/// A ROM marked as homebrew or a hack, with what the homebrew index matched.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HomebrewInfo {
+    pub kind: crate::entity::rom_homebrew::HomebrewKind,
+    pub title: Option<String>,
+    pub author: Option<String>,
+    /// The game a hack is made from.
+    pub base_game: Option<String>,
+}
+
+/// How many ROMs have a completion status.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StatusCount {
+    pub status: CompletionStatus,
+    pub rom_count: i64,
+}
+
+/// Someone using this install. Favorites, play stats, completion status and
+/// local collections are kept per profile.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Profile {
+    pub id: i64,
+    pub name: String,
+    pub is_current: bool,
+    pub created_at: String,
+    /// Games rated for older players are hidden; None turns the filter off.
+    pub max_age: Option<i64>,
+    /// Show games without an age rating while the filter is on.
+    pub allow_unrated: bool,
+}
+
+/// A copy of the database written by `backup_database` or at startup.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DatabaseBackup {
+    pub path: String,
+    pub size_bytes: u64,
+    pub created_at: String,
+}
+
+/// Result of `PRAGMA integrity_check`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct IntegrityReport {
+    pub ok: bool,
+    pub problems: Vec<String>,
+}
+
+/// A user tag and how many ROMs carry it.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TagCount {
+    pub tag: String,
+    pub rom_count: i64,
+}
+
+/// What a typeahead suggestion points at.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SuggestionKind {
+    Rom,
+    Franchise,
+    Genre,
+    Platform,
+}
+
+/// A match for `search_suggest`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SearchSuggestion {
+    pub kind: SuggestionKind,
+    pub label: String,
+    /// The ROM or platform id; None for franchises and genres.
+    pub id: Option<i64>,
+    /// Platform name of a ROM.
+    pub detail: Option<String>,
+    /// ROMs in the franchise, genre or platform.
+    pub rom_count: Option<i64>,
+    /// Matched parts of `label` as `[start, end)` UTF-16 offsets.
+    pub highlights: Vec<(usize, usize)>,
+}
+
+/// Hand edit of a ROM's metadata; `None` leaves a field unchanged.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct MetadataEdit {
+    pub name: Option<String>,
+    pub description: Option<String>,
+    pub genres: Option<Vec<String>>,
+    pub release_date: Option<String>,
+    /// 0-10, like the ratings enrichment stores.
+    pub rating: Option<f64>,
+    /// Fields to hand back to enrichment.
+    pub reset: Vec<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -71,6 +534,19 @@ pub struct PlatformWithCount {
     pub slug: String,
     pub name: String,
     pub rom_count: i64,
+    pub archived: bool,
+}
+
+/// A collection with its member count. `source_id` is set for collections
+/// mirrored from a ROMM server.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Collection {
+    pub id: i64,
+    pub name: String,
+    pub description: Option<String>,
+    pub source_id: Option<i64>,
+    pub is_favorite: bool,
+    pub rom_count: i64,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -99,6 +575,17 @@ impl DownloadProgress {
         }
     }
 
+    /// Non-fatal notice; the operation carries on after sending it.
+    pub fn warning(rom_id: i64, status: &str, message: String) -> Self {
+        Self {
+            rom_id,
+            total_bytes: 0,
+            downloaded_bytes: 0,
+            status: status.to_string(),
+            error_message: Some(message),
+        }
+    }
+
     pub fn downloading(rom_id: i64, downloaded: u64, total: u64) -> Self {
         Self {
             rom_id,
@@ -108,10 +595,22 @@ impl DownloadProgress {
             error_message: None,
         }
     }
+
+    pub fn uploading(rom_id: i64, uploaded: u64, total: u64) -> Self {
+        Self {
+            rom_id,
+            total_bytes: total,
+            downloaded_bytes: uploaded,
+            status: "uploading".to_string(),
+            error_message: None,
+        }
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CachedFile {
+    /// Path inside the cache directory; what `clear_cache_files` takes.
+    pub path: String,
     pub file_name: String,
     pub size: u64,
     pub last_played_at: Option<String>,
@@ -123,6 +622,39 @@ pub struct CacheInfo {
     pub files: Vec<CachedFile>,
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CleanupReport {
+    pub orphaned_cache_files: u32,
+    pub partial_downloads: u32,
+    pub temp_hash_files: u32,
+    pub orphaned_db_rows: u64,
+    pub reclaimed_bytes: u64,
+}
+
+/// Compressed disc image formats local ROMs can be converted to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CompressedFormat {
+    /// MAME's compressed hunks, read by most disc-based cores.
+    Chd,
+    /// Dolphin's format for GameCube and Wii discs.
+    Rvz,
+    /// Compressed ISO, for PSP and PS2 emulators that don't read CHD.
+    Cso,
+}
+
+/// Outcome of compressing local disc images.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CompressionReport {
+    pub converted: u32,
+    pub skipped: u32,
+    /// "<file>: <error>" for each ROM that couldn't be converted.
+    pub failed: Vec<String>,
+    /// Size of the converted ROMs before (with their track files) and after.
+    pub original_bytes: u64,
+    pub compressed_bytes: u64,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CoreInfo {
     pub core_name: String,
@@ -130,6 +662,33 @@ pub struct CoreInfo {
     pub display_name: Option<String>,
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CoreRecommendation {
+    pub core_name: String,
+    pub display_name: Option<String>,
+    pub installed: bool,
+    pub core_path: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PlatformCoreSetup {
+    pub platform_id: i64,
+    pub platform_slug: String,
+    pub core_name: String,
+}
+
+/// Outcome of `setup_recommended_cores`.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct CoreSetupReport {
+    pub mapped: Vec<PlatformCoreSetup>,
+    /// Cores downloaded along the way.
+    pub installed: Vec<String>,
+    /// Platforms with no curated core.
+    pub skipped: Vec<String>,
+    /// `"<slug>: <error>"` for platforms whose core failed to install.
+    pub failed: Vec<String>,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CoreMapping {
     pub id: i64,
@@ -145,6 +704,49 @@ pub struct EmulatorDef {
     pub id: String,
     pub name: String,
     pub platforms: Vec<String>,
+    pub custom: bool,
+}
+
+/// Command-line template for a standalone emulator.
+/// Placeholders: `{rom}`, `{savestate}`, `{fullscreen}`; `[...]` marks optional groups.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EmulatorArgTemplate {
+    pub args: String,
+    pub fullscreen_flag: Option<String>,
+    #[serde(default)]
+    pub fullscreen: bool,
+}
+
+/// User-defined emulator stored in settings.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CustomEmulator {
+    pub id: String,
+    pub name: String,
+    pub platforms: Vec<String>,
+    pub template: EmulatorArgTemplate,
+}
+
+/// Shell commands run around a launch. Placeholders: `{rom}`, `{rom_name}`,
+/// `{platform}` (slug) and `{emulator}`, also exported to the hook as the
+/// `ROM`, `ROM_NAME`, `PLATFORM` and `EMULATOR` environment variables.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct LaunchHook {
+    /// Runs before the emulator starts; the launch waits for it.
+    pub pre_launch: Option<String>,
+    /// Runs after the emulator exits.
+    pub post_exit: Option<String>,
+    /// Seconds before a hook is killed; defaults to 30.
+    pub timeout_secs: Option<u64>,
+}
+
+/// Launch hooks for every platform, overridable per platform slug. A
+/// platform hook replaces the global one field by field.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct LaunchHooks {
+    pub global: LaunchHook,
+    pub platforms: std::collections::HashMap<String, LaunchHook>,
 }
 
 #[derive(Debug, Clone)]
@@ -160,6 +762,51 @@ pub struct LibraryPage {
     pub total: i64,
 }
 
+/// Where to fetch a ROM outside the launch flow.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RomDownloadUrl {
+    /// ROMM content URL, or a `file://` URL for local sources.
+    pub url: String,
+    pub file_name: String,
+    /// `Authorization` header value for ROMM; short-lived (an access token).
+    pub authorization: Option<String>,
+    /// Ready-to-paste command that downloads the file.
+    pub curl_command: String,
+}
+
+/// Minimal ROM row for the controller-driven launch grid.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LaunchGridRow {
+    pub id: i64,
+    pub name: String,
+    /// Locally cached cover thumbnail, once it has been fetched.
+    pub cover_path: Option<String>,
+    /// Remote cover to fall back on while `cover_path` is still missing.
+    pub cover_url: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LaunchGridPage {
+    pub rows: Vec<LaunchGridRow>,
+    pub total: i64,
+}
+
+/// One batch of `stream_library_roms` results, starting at `offset`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LibraryBatch {
+    pub roms: Vec<RomWithMeta>,
+    pub offset: i64,
+    pub total: i64,
+    /// Set on the final batch (which may be empty).
+    pub done: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LibraryPageLimits {
+    pub default_limit: i64,
+    pub max_limit: i64,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct RaCredentials {
     pub username: String,
@@ -209,12 +856,269 @@ pub struct SsCredentials {
     pub password: String,
 }
 
+/// Machine translation service for descriptions.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TranslationService {
+    #[default]
+    DeepL,
+    LibreTranslate,
+}
+
+/// Translation settings. Not synced, since they hold an API key.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct TranslationSettings {
+    pub enabled: bool,
+    pub service: TranslationService,
+    /// Base URL, e.g. `https://api-free.deepl.com` or a self-hosted LibreTranslate.
+    pub endpoint: String,
+    pub api_key: Option<String>,
+    /// Language to translate into (`en`, `de`, ...); defaults to the UI language.
+    pub target_language: Option<String>,
+}
+
+impl Default for TranslationSettings {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            service: TranslationService::DeepL,
+            endpoint: "https://api-free.deepl.com".to_string(),
+            api_key: None,
+            target_language: None,
+        }
+    }
+}
+
+/// A ROM description in another language, from `metadata_i18n`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LocalizedDescription {
+    pub rom_id: i64,
+    pub language: String,
+    pub description: String,
+    pub source_language: Option<String>,
+    pub machine_translated: bool,
+    pub provider: Option<String>,
+    pub created_at: String,
+}
+
+/// One artwork item with its provenance, for the artwork management UI.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RomArtwork {
+    pub id: i64,
+    pub art_type: String,
+    pub url: Option<String>,
+    pub provider: Option<String>,
+    pub region: Option<String>,
+    pub created_at: String,
+}
+
+/// Region/language preference order for ScreenScraper media and text.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SsMediaPreferences {
+    pub regions: Vec<String>,
+    pub languages: Vec<String>,
+}
+
+impl Default for SsMediaPreferences {
+    fn default() -> Self {
+        Self {
+            regions: ["us", "wor", "eu", "ss", "jp"].map(String::from).to_vec(),
+            languages: vec!["en".to_string()],
+        }
+    }
+}
+
+/// Artwork providers that enrichment can take images from.
+pub const ARTWORK_PROVIDERS: &[&str] = &["igdb", "screenscraper", "launchbox", "libretro"];
+
+/// Provider order per art type. Enrichment takes covers (one image) and
+/// screenshots (every image) from the first provider in the list that has any;
+/// providers left out are never used for that type.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ArtworkChains {
+    pub cover: Vec<String>,
+    pub screenshot: Vec<String>,
+}
+
+impl Default for ArtworkChains {
+    fn default() -> Self {
+        Self {
+            cover: ["igdb", "screenscraper", "launchbox", "libretro"].map(String::from).to_vec(),
+            screenshot: ["igdb", "screenscraper", "libretro", "launchbox"].map(String::from).to_vec(),
+        }
+    }
+}
+
+/// Which enrichment steps run. `fetch_metadata` falls back to the saved
+/// default when the caller passes none.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct EnrichmentOptions {
+    /// Hash ROM files that have no MD5 yet (reads every file once).
+    pub compute_hashes: bool,
+    pub hasheous: bool,
+    pub igdb: bool,
+    pub launchbox: bool,
+    pub screenscraper: bool,
+    /// Only fill in missing covers: no descriptions, screenshots or fanart.
+    pub covers_only: bool,
+    /// Re-enrich ROMs that already have metadata, bypassing provider caches.
+    pub force_refresh: bool,
+    /// Machine-translate descriptions only available in another language,
+    /// when a translation service is configured.
+    pub translate: bool,
+}
+
+impl Default for EnrichmentOptions {
+    fn default() -> Self {
+        Self {
+            compute_hashes: true,
+            hasheous: true,
+            igdb: true,
+            launchbox: true,
+            screenscraper: true,
+            covers_only: false,
+            force_refresh: false,
+            translate: true,
+        }
+    }
+}
+
+/// Metadata providers whose misses are cached.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MetadataProvider {
+    Hasheous,
+    Igdb,
+    Libretro,
+    Screenscraper,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SsTestResult {
     pub success: bool,
     pub message: String,
 }
 
+/// Services checked by `get_provider_status`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum StatusProvider {
+    Romm,
+    Igdb,
+    Screenscraper,
+    Retroachievements,
+    Hasheous,
+    LaunchboxImages,
+}
+
+/// Whether a service answers and accepts the configured credentials.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProviderStatus {
+    pub provider: StatusProvider,
+    /// The source's name for ROMM servers, else the service's.
+    pub name: String,
+    pub source_id: Option<i64>,
+    /// Credentials are set. Services that need none are always configured.
+    pub configured: bool,
+    pub reachable: bool,
+    /// `None` for services without credentials, or when this couldn't be told.
+    pub authenticated: Option<bool>,
+    /// Requests left today, where the service reports it.
+    pub requests_remaining: Option<u64>,
+    pub requests_limit: Option<u64>,
+    pub latency_ms: Option<u64>,
+    /// Why a check failed.
+    pub message: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BiosStatus {
+    /// Present and matches a known hash.
+    Ok,
+    /// Present, but no reference hash exists to check it against.
+    Unverified,
+    /// Present with an unexpected hash (bad or wrong-region dump).
+    Mismatch,
+    Missing,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BiosFileStatus {
+    pub platform_slug: String,
+    pub file_name: String,
+    pub description: String,
+    pub required: bool,
+    pub group: Option<String>,
+    pub status: BiosStatus,
+    pub path: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BiosReadiness {
+    pub platform_slug: String,
+    pub ready: bool,
+    /// Unmet requirements; alternatives are joined with " / ".
+    pub missing: Vec<String>,
+    pub files: Vec<BiosFileStatus>,
+}
+
+/// What `preflight_launch` found.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum LaunchWarningKind {
+    /// The emulator can't open the archive the game is packed in.
+    UnsupportedArchive,
+    BiosMissing,
+    /// The core doesn't list the game's file type.
+    UnsupportedExtension,
+    /// The emulator or core was updated after the save state was made.
+    SaveStateOutdated,
+}
+
+/// A known pitfall for a launch; the user can still launch anyway.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LaunchWarning {
+    pub kind: LaunchWarningKind,
+    pub message: String,
+}
+
+/// Setup checklist for one platform, aggregated from existing settings and data.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PlatformSetupStatus {
+    pub platform_id: i64,
+    pub platform_slug: String,
+    pub core_mapped: bool,
+    pub core_name: Option<String>,
+    /// `"retroarch"` or a standalone emulator id; `None` when nothing is mapped.
+    pub emulator_type: Option<String>,
+    /// RetroArch or the mapped standalone emulator has a known executable.
+    pub emulator_path_set: bool,
+    /// The platform has BIOS files in the registry.
+    pub bios_required: bool,
+    /// `None` when no BIOS is needed or no BIOS directory is known.
+    pub bios: Option<BiosReadiness>,
+    pub dat_imported: bool,
+    pub ra_supported: bool,
+    pub ra_configured: bool,
+    pub rom_count: i64,
+    pub roms_with_cover: i64,
+    /// Percentage of ROMs with a cover, 0-100.
+    pub cover_coverage: f64,
+}
+
+/// Firmware a libretro core declares in its `.info` file.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BiosHint {
+    /// Location relative to the system directory.
+    pub path: String,
+    pub description: String,
+    pub optional: bool,
+    pub md5: Option<String>,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "snake_case")]
 pub enum SaveType {
@@ -222,15 +1126,44 @@ pub enum SaveType {
     SaveState,
 }
 
+/// What a save file holds.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum SaveFormat {
+    /// Cartridge battery RAM, EEPROM or flash.
+    Sram,
+    /// A console memory card image, holding saves of any number of games.
+    MemoryCard,
+    SaveState,
+}
+
+/// A game's save on a memory card image.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MemoryCardSave {
+    /// File name on a PS1 card, directory name on a PS2 one.
+    pub name: String,
+    /// The game's product code, e.g. `SLUS-01234`.
+    pub product_code: Option<String>,
+    pub title: Option<String>,
+    pub size_bytes: u64,
+    pub modified_at: Option<String>,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SaveFileInfo {
     pub file_name: String,
     pub file_path: String,
     pub save_type: SaveType,
+    pub format: SaveFormat,
+    /// Id of the emulator that wrote the file (`retroarch`, `pcsx2`, ...),
+    /// when its name or location gives it away.
+    pub emulator: Option<String>,
     pub size_bytes: u64,
     pub modified_at: String,
     pub slot: Option<u32>,
     pub screenshot_path: Option<String>,
+    /// The saves on a memory card image; empty for other formats.
+    pub card_saves: Vec<MemoryCardSave>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -238,3 +1171,166 @@ pub struct SavePathOverride {
     pub save_dir: Option<String>,
     pub state_dir: Option<String>,
 }
+
+/// File written by `export_settings`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SettingsExport {
+    pub version: u32,
+    pub exported_at: String,
+    pub settings: std::collections::BTreeMap<String, serde_json::Value>,
+}
+
+/// A LaunchBox game matched to a ROM by fuzzy name similarity.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LaunchBoxFuzzyMatch {
+    pub rom_id: i64,
+    pub rom_name: String,
+    pub platform_slug: String,
+    pub database_id: String,
+    pub matched_name: String,
+    /// Trigram similarity, 0..1.
+    pub confidence: f64,
+    pub rejected: bool,
+}
+
+/// A collection or the favorites, written by `export_list` for sharing.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SharedList {
+    pub version: u32,
+    pub name: String,
+    #[serde(default)]
+    pub description: Option<String>,
+    pub exported_at: String,
+    pub entries: Vec<SharedListEntry>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SharedListEntry {
+    pub name: String,
+    pub platform_slug: String,
+    pub platform_name: String,
+    #[serde(default)]
+    pub regions: Vec<String>,
+    #[serde(default)]
+    pub file_name: String,
+    #[serde(default)]
+    pub crc32: Option<String>,
+    #[serde(default)]
+    pub md5: Option<String>,
+    #[serde(default)]
+    pub sha1: Option<String>,
+}
+
+/// Result of `import_list`: the new collection and the entries with no match.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ListImportReport {
+    pub collection_id: i64,
+    pub name: String,
+    pub total: usize,
+    pub matched: usize,
+    pub missing: Vec<SharedListEntry>,
+}
+
+/// A cache file `migrate_cache_layout` deleted instead of re-keying.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DiscardedCacheFile {
+    pub path: String,
+    pub reason: String,
+    pub size: u64,
+}
+
+/// Result of moving the ROM and cover caches to hash-keyed names.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct CacheMigrationReport {
+    pub roms_rekeyed: u32,
+    pub covers_rekeyed: u32,
+    pub discarded: Vec<DiscardedCacheFile>,
+    pub reclaimed_bytes: u64,
+}
+
+/// A ROM download that failed verification and was thrown away.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CorruptDownload {
+    pub id: i64,
+    pub rom_id: i64,
+    pub rom_name: String,
+    pub file_name: String,
+    /// Which try of the download this was, from 1.
+    pub attempt: u32,
+    pub reason: String,
+    pub expected_md5: Option<String>,
+    pub actual_md5: Option<String>,
+    pub expected_size: Option<u64>,
+    pub actual_size: u64,
+    pub created_at: String,
+}
+
+/// One cheat from a ROM's libretro `.cht` file.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Cheat {
+    /// Position in the file (`cheatN_...`).
+    pub index: u32,
+    pub description: String,
+    pub code: Option<String>,
+    pub enabled: bool,
+}
+
+/// Where save backups are uploaded. Not synced, since it holds credentials.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "provider", rename_all = "snake_case")]
+pub enum CloudBackupConfig {
+    /// AWS S3 or a compatible service (Cloudflare R2, Backblaze B2, MinIO...).
+    S3 {
+        /// e.g. `https://s3.eu-west-1.amazonaws.com`
+        endpoint: String,
+        region: String,
+        bucket: String,
+        access_key_id: String,
+        secret_access_key: String,
+        /// Folder inside the bucket; defaults to `romm-buddy`.
+        prefix: Option<String>,
+        /// Address the bucket as `<endpoint>/<bucket>` instead of
+        /// `<bucket>.<host>`; most self-hosted services need this.
+        #[serde(default)]
+        path_style: bool,
+    },
+}
+
+/// A save file changed both here and in the cloud since the last sync.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CloudConflict {
+    pub key: String,
+    pub local_path: String,
+    pub local_modified_at: Option<String>,
+    pub remote_modified_at: String,
+}
+
+/// Result of `backup_saves_to_cloud` or `restore_saves_from_cloud`.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct CloudSyncReport {
+    /// Files uploaded (backup) or downloaded (restore).
+    pub transferred: usize,
+    pub unchanged: usize,
+    /// Left alone; pass `overwrite` to let this side win.
+    pub conflicts: Vec<CloudConflict>,
+}
+
+/// Per-game RetroArch settings, written as override files named after the
+/// game's content so RetroArch picks them up when it loads that game.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct RetroArchGameOverrides {
+    /// Path of a `.slangp`, `.glslp` or `.cgp` shader preset.
+    pub shader_preset: Option<String>,
+    /// `core` for the core's own ratio, or a fixed ratio such as `4:3`.
+    pub aspect_ratio: Option<String>,
+    /// Frames to run ahead; 0 turns run-ahead off for this game.
+    pub run_ahead_frames: Option<u32>,
+    /// Core options (`.opt`), e.g. `snes9x_overclock_superfx` -> `"150%"`.
+    pub core_options: std::collections::BTreeMap<String, String>,
+    /// Input remaps (`.rmp`), e.g. `input_player1_btn_a` -> `"8"`.
+    pub remaps: std::collections::BTreeMap<String, String>,
+    /// `.ips`, `.bps` or `.ups` patch applied in memory at launch
+    /// (soft-patching), leaving the ROM file untouched.
+    pub soft_patch: Option<String>,
+}
@@ -0,0 +1,345 @@
+/// What a ROM file name says about the dump, following the No-Intro
+/// (`Name (USA, Europe) (En,Fr) (Rev 1)`), GoodTools (`Name (U) [!]`) and
+/// TOSEC (`Name v1.1 (1990)(Publisher)(US)(en)[cr]`) conventions.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ParsedName {
+    /// No-Intro region names: `USA`, `Europe`, `Japan`, ...
+    pub regions: Vec<String>,
+    /// ISO 639-1 codes. Taken from the region when the name has none.
+    pub languages: Vec<String>,
+    /// `1`, `A`, `1.1`.
+    pub revision: Option<String>,
+    /// `verified`, `bad`, `hack`, `translation`, `beta`, ...
+    pub flags: Vec<String>,
+}
+
+/// No-Intro region names, with the languages a ROM for the region is in
+/// when the name doesn't list them.
+const REGIONS: &[(&str, Option<&str>)] = &[
+    ("USA", Some("en")),
+    ("Europe", None),
+    ("Japan", Some("ja")),
+    ("World", None),
+    ("Asia", None),
+    ("Australia", Some("en")),
+    ("Brazil", Some("pt")),
+    ("Canada", None),
+    ("China", Some("zh")),
+    ("France", Some("fr")),
+    ("Germany", Some("de")),
+    ("Hong Kong", Some("zh")),
+    ("Italy", Some("it")),
+    ("Korea", Some("ko")),
+    ("Netherlands", Some("nl")),
+    ("Portugal", Some("pt")),
+    ("Russia", Some("ru")),
+    ("Scandinavia", None),
+    ("Spain", Some("es")),
+    ("Sweden", Some("sv")),
+    ("Taiwan", Some("zh")),
+    ("UK", Some("en")),
+];
+
+/// GoodTools single-letter and short country codes.
+const GOODTOOLS_REGIONS: &[(&str, &str)] = &[
+    ("U", "USA"),
+    ("E", "Europe"),
+    ("J", "Japan"),
+    ("W", "World"),
+    ("A", "Australia"),
+    ("B", "Brazil"),
+    ("C", "China"),
+    ("Ch", "China"),
+    ("F", "France"),
+    ("G", "Germany"),
+    ("HK", "Hong Kong"),
+    ("I", "Italy"),
+    ("K", "Korea"),
+    ("Nl", "Netherlands"),
+    ("R", "Russia"),
+    ("S", "Spain"),
+    ("Sw", "Sweden"),
+    ("As", "Asia"),
+];
+
+/// TOSEC (ISO 3166) country codes.
+const TOSEC_REGIONS: &[(&str, &str)] = &[
+    ("US", "USA"),
+    ("EU", "Europe"),
+    ("JP", "Japan"),
+    ("GB", "UK"),
+    ("AU", "Australia"),
+    ("BR", "Brazil"),
+    ("CA", "Canada"),
+    ("CN", "China"),
+    ("DE", "Germany"),
+    ("ES", "Spain"),
+    ("FR", "France"),
+    ("HK", "Hong Kong"),
+    ("IT", "Italy"),
+    ("KR", "Korea"),
+    ("NL", "Netherlands"),
+    ("PT", "Portugal"),
+    ("RU", "Russia"),
+    ("SE", "Sweden"),
+    ("TW", "Taiwan"),
+];
+
+const LANGUAGES: &[&str] = &[
+    "ar", "ca", "cs", "da", "de", "el", "en", "es", "fi", "fr", "he", "hu", "it", "ja", "ko", "nl",
+    "no", "pl", "pt", "ru", "sv", "tr", "zh",
+];
+
+/// Three-letter languages of GoodTools translations (`[T+Eng]`).
+const TRANSLATION_LANGUAGES: &[(&str, &str)] = &[
+    ("Eng", "en"),
+    ("Fre", "fr"),
+    ("Fra", "fr"),
+    ("Ger", "de"),
+    ("Deu", "de"),
+    ("Spa", "es"),
+    ("Ita", "it"),
+    ("Por", "pt"),
+    ("Bra", "pt"),
+    ("Rus", "ru"),
+    ("Chi", "zh"),
+    ("Kor", "ko"),
+    ("Pol", "pl"),
+    ("Swe", "sv"),
+    ("Dut", "nl"),
+    ("Gre", "el"),
+    ("Jap", "ja"),
+];
+
+/// Development and release status tags in parentheses.
+const STATUS_FLAGS: &[(&str, &str)] = &[
+    ("beta", "beta"),
+    ("proto", "proto"),
+    ("prototype", "proto"),
+    ("demo", "demo"),
+    ("sample", "sample"),
+    ("unl", "unlicensed"),
+    ("pirate", "pirate"),
+    ("hack", "hack"),
+];
+
+fn push_unique(list: &mut Vec<String>, value: &str) {
+    if !list.iter().any(|v| v == value) {
+        list.push(value.to_string());
+    }
+}
+
+/// Parse the tags of a ROM file name (with or without its extension).
+pub fn parse(file_name: &str) -> ParsedName {
+    let stem = std::path::Path::new(file_name)
+        .file_stem()
+        .and_then(|s| s.to_str())
+        .filter(|_| has_extension(file_name))
+        .unwrap_or(file_name);
+
+    let mut parsed = ParsedName::default();
+    let title_end = stem.find(['(', '[']).unwrap_or(stem.len());
+
+    // TOSEC puts the version in the title: `Name v1.1 (1990)...`
+    if let Some(version) = stem[..title_end]
+        .split_whitespace()
+        .filter_map(|word| word.strip_prefix(['v', 'V']))
+        .find(|v| {
+            v.starts_with(|c: char| c.is_ascii_digit())
+                && v.chars().all(|c| c.is_ascii_digit() || c == '.')
+        })
+    {
+        parsed.revision = Some(version.to_string());
+    }
+
+    for (open, close) in [('(', ')'), ('[', ']')] {
+        let mut rest = &stem[title_end..];
+        while let Some(start) = rest.find(open) {
+            let Some(len) = rest[start + 1..].find(close) else {
+                break;
+            };
+            let tag = rest[start + 1..start + 1 + len].trim();
+            if open == '(' {
+                parse_paren_tag(tag, &mut parsed);
+            } else {
+                parse_bracket_tag(tag, &mut parsed);
+            }
+            rest = &rest[start + len + 2..];
+        }
+    }
+
+    if parsed.languages.is_empty() {
+        let implied: Vec<&str> = parsed
+            .regions
+            .iter()
+            .filter_map(|r| REGIONS.iter().find(|(name, _)| *name == r.as_str())?.1)
+            .collect();
+        for language in implied {
+            push_unique(&mut parsed.languages, language);
+        }
+    }
+    parsed
+}
+
+/// Whether `file_name` ends in an extension rather than a tag, so
+/// `Name (Rev 1.1)` isn't cut at the dot.
+fn has_extension(file_name: &str) -> bool {
+    file_name
+        .rsplit_once('.')
+        .is_some_and(|(_, ext)| !ext.is_empty() && ext.len() <= 4 && !ext.contains([')', ']', ' ']))
+}
+
+fn parse_paren_tag(tag: &str, parsed: &mut ParsedName) {
+    let parts: Vec<&str> = tag.split([',', '-']).map(str::trim).collect();
+
+    // No-Intro regions: `USA, Europe`
+    if parts
+        .iter()
+        .all(|p| REGIONS.iter().any(|(name, _)| name == p))
+    {
+        for part in parts {
+            push_unique(&mut parsed.regions, part);
+        }
+        return;
+    }
+    // TOSEC countries: `US-EU`
+    let tosec: Option<Vec<&str>> = parts
+        .iter()
+        .map(|p| {
+            TOSEC_REGIONS
+                .iter()
+                .find(|(code, _)| code == p)
+                .map(|(_, name)| *name)
+        })
+        .collect();
+    if let Some(names) = tosec {
+        for name in names {
+            push_unique(&mut parsed.regions, name);
+        }
+        return;
+    }
+    // GoodTools: `U`, `JU`, `UE`, `HK`
+    if let Some(names) = goodtools_regions(tag) {
+        for name in names {
+            push_unique(&mut parsed.regions, name);
+        }
+        return;
+    }
+    // Languages: No-Intro `En,Fr,De`, TOSEC `en-fr`
+    let languages: Option<Vec<String>> = parts
+        .iter()
+        .map(|p| {
+            let code = p.get(..2)?.to_ascii_lowercase();
+            let is_language = p.len() == 2 && LANGUAGES.contains(&code.as_str());
+            is_language.then_some(code)
+        })
+        .collect();
+    if let Some(languages) = languages {
+        for language in languages {
+            push_unique(&mut parsed.languages, &language);
+        }
+        return;
+    }
+
+    let lower = tag.to_ascii_lowercase();
+    // `Rev 1`, `Rev A`, `REV01`, GoodTools `V1.1` and `PRG1`
+    let revision = lower
+        .strip_prefix("rev")
+        .or_else(|| lower.strip_prefix("prg"))
+        .map(str::trim)
+        .filter(|r| !r.is_empty() && r.chars().all(|c| c.is_ascii_alphanumeric() || c == '.'))
+        .or_else(|| {
+            lower
+                .strip_prefix('v')
+                .filter(|v| v.starts_with(|c: char| c.is_ascii_digit()))
+                .filter(|v| v.chars().all(|c| c.is_ascii_digit() || c == '.'))
+        });
+    if let Some(revision) = revision {
+        parsed.revision = Some(revision.to_ascii_uppercase());
+        return;
+    }
+
+    // `Beta`, `Beta 2`, `Proto`, `Unl`
+    let word = lower.split_whitespace().next().unwrap_or_default();
+    if let Some((_, flag)) = STATUS_FLAGS.iter().find(|(tag, _)| *tag == word) {
+        push_unique(&mut parsed.flags, flag);
+    }
+}
+
+/// GoodTools codes, alone (`U`, `HK`) or run together (`JUE`).
+fn goodtools_regions(tag: &str) -> Option<Vec<&'static str>> {
+    if let Some((_, name)) = GOODTOOLS_REGIONS.iter().find(|(code, _)| *code == tag) {
+        return Some(vec![*name]);
+    }
+    if tag.len() < 2 || !tag.chars().all(|c| matches!(c, 'U' | 'E' | 'J')) {
+        return None;
+    }
+    tag.chars()
+        .map(|c| {
+            GOODTOOLS_REGIONS
+                .iter()
+                .find(|(code, _)| code.len() == 1 && code.starts_with(c))
+                .map(|(_, name)| *name)
+        })
+        .collect()
+}
+
+/// GoodTools and TOSEC dump flags: `[!]`, `[b1]`, `[h]`, `[T+Eng]`,
+/// `[cr]`, `[tr fr]`, ...
+fn parse_bracket_tag(tag: &str, parsed: &mut ParsedName) {
+    // GoodTools translation: `T+Eng1.02_Author`, `T-Spa`
+    if let Some(rest) = tag.strip_prefix("T+").or_else(|| tag.strip_prefix("T-")) {
+        push_unique(&mut parsed.flags, "translation");
+        if let Some((_, code)) = TRANSLATION_LANGUAGES
+            .iter()
+            .find(|(name, _)| rest.starts_with(name))
+        {
+            push_unique(&mut parsed.languages, code);
+        }
+        return;
+    }
+
+    let lower = tag.to_ascii_lowercase();
+    let (word, arg) = lower.split_once(' ').unwrap_or((lower.as_str(), ""));
+    // TOSEC translation: `tr fr`
+    if word == "tr" {
+        push_unique(&mut parsed.flags, "translation");
+        if let Some(code) = arg.get(..2).filter(|c| LANGUAGES.contains(c)) {
+            push_unique(&mut parsed.languages, code);
+        }
+        return;
+    }
+
+    let numbered = |prefix: &str| {
+        word.strip_prefix(prefix)
+            .is_some_and(|n| n.chars().all(|c| c.is_ascii_digit() || c == '_'))
+    };
+    let flag = if word == "!" {
+        "verified"
+    } else if word == "cr" {
+        "cracked"
+    } else if numbered("b") {
+        "bad"
+    } else if numbered("a") {
+        "alternate"
+    } else if word.starts_with('h') {
+        "hack"
+    } else if numbered("f") {
+        "fixed"
+    } else if numbered("o") {
+        "overdump"
+    } else if numbered("p") {
+        "pirate"
+    } else if numbered("t") {
+        "trainer"
+    } else if word == "m" {
+        "modified"
+    } else if word == "u" {
+        "underdump"
+    } else if word == "v" {
+        "virus"
+    } else {
+        return;
+    };
+    push_unique(&mut parsed.flags, flag);
+}
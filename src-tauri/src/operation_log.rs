@@ -0,0 +1,181 @@
+use std::collections::BTreeMap;
+use std::future::Future;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use sea_orm::{ConnectionTrait, DatabaseBackend, DatabaseConnection, FromQueryResult, Statement};
+
+use crate::error::{AppError, AppResult};
+use crate::models::OperationLogEntry;
+
+/// Rows kept; older ones are dropped as new ones come in.
+const MAX_ROWS: i64 = 10_000;
+
+const DEFAULT_HISTORY_LIMIT: i64 = 200;
+
+fn millis(elapsed: Duration) -> i64 {
+    i64::try_from(elapsed.as_millis()).unwrap_or(i64::MAX)
+}
+
+async fn insert(
+    db: &DatabaseConnection,
+    operation: &str,
+    detail: Option<&str>,
+    elapsed: Duration,
+    count: u64,
+    error: Option<String>,
+) {
+    let started_at = chrono::Utc::now()
+        - chrono::Duration::from_std(elapsed).unwrap_or_else(|_| chrono::Duration::zero());
+    let result = db
+        .execute(Statement::from_sql_and_values(
+            DatabaseBackend::Sqlite,
+            "INSERT INTO operation_log (operation, detail, started_at, duration_ms, count, success, error)
+             VALUES (?, ?, ?, ?, ?, ?, ?)",
+            [
+                operation.into(),
+                detail.map(str::to_string).into(),
+                started_at
+                    .format("%Y-%m-%dT%H:%M:%S%.3fZ")
+                    .to_string()
+                    .into(),
+                millis(elapsed).into(),
+                i64::try_from(count).unwrap_or(i64::MAX).into(),
+                error.is_none().into(),
+                error.into(),
+            ],
+        ))
+        .await;
+    if let Err(e) = result {
+        log::warn!("Failed to record {operation} timing: {e}");
+        return;
+    }
+    let _ = db
+        .execute(Statement::from_sql_and_values(
+            DatabaseBackend::Sqlite,
+            "DELETE FROM operation_log WHERE id <= (SELECT MAX(id) FROM operation_log) - ?",
+            [MAX_ROWS.into()],
+        ))
+        .await;
+}
+
+/// Record an operation that began at `started` and just finished.
+/// Failing to record is only logged.
+pub async fn record(
+    db: &DatabaseConnection,
+    operation: &str,
+    detail: Option<&str>,
+    started: Instant,
+    count: u64,
+    error: Option<&AppError>,
+) {
+    insert(
+        db,
+        operation,
+        detail,
+        started.elapsed(),
+        count,
+        error.map(ToString::to_string),
+    )
+    .await;
+}
+
+/// Run `fut` and record how long it took and whether it failed.
+pub async fn timed<T>(
+    db: &DatabaseConnection,
+    operation: &str,
+    detail: Option<&str>,
+    fut: impl Future<Output = AppResult<T>>,
+) -> AppResult<T> {
+    let started = Instant::now();
+    let result = fut.await;
+    record(db, operation, detail, started, 1, result.as_ref().err()).await;
+    result
+}
+
+/// Time spent in each step of a batch (enriching hundreds of ROMs), kept in
+/// memory and written as one row per step when the batch ends.
+#[derive(Default)]
+pub struct StepTimings(Mutex<BTreeMap<&'static str, (u64, Duration)>>);
+
+impl StepTimings {
+    /// Run `fut`, adding its duration to `step`.
+    pub async fn time<T>(&self, step: &'static str, fut: impl Future<Output = T>) -> T {
+        let started = Instant::now();
+        let output = fut.await;
+        self.add(step, started.elapsed());
+        output
+    }
+
+    pub fn add(&self, step: &'static str, elapsed: Duration) {
+        if let Ok(mut steps) = self.0.lock() {
+            let entry = steps.entry(step).or_default();
+            entry.0 += 1;
+            entry.1 += elapsed;
+        }
+    }
+
+    /// Write a row per step and start over.
+    pub async fn flush(&self, db: &DatabaseConnection, detail: Option<&str>) {
+        let steps = self
+            .0
+            .lock()
+            .map(|mut steps| std::mem::take(&mut *steps))
+            .unwrap_or_default();
+        for (step, (count, elapsed)) in steps {
+            insert(db, step, detail, elapsed, count, None).await;
+        }
+    }
+}
+
+/// Most recent operations first. `operation` also matches its steps, so
+/// `enrich` returns the `enrich.igdb`, `enrich.screenscraper`, ... rows too.
+pub async fn history(
+    db: &DatabaseConnection,
+    operation: Option<&str>,
+    limit: Option<i64>,
+) -> AppResult<Vec<OperationLogEntry>> {
+    #[derive(Debug, FromQueryResult)]
+    struct Row {
+        id: i64,
+        operation: String,
+        detail: Option<String>,
+        started_at: String,
+        duration_ms: i64,
+        count: i64,
+        success: bool,
+        error: Option<String>,
+    }
+
+    let limit = limit.unwrap_or(DEFAULT_HISTORY_LIMIT).clamp(1, MAX_ROWS);
+    let rows = Row::find_by_statement(Statement::from_sql_and_values(
+        DatabaseBackend::Sqlite,
+        "SELECT id, operation, detail, started_at, duration_ms, count, success, error
+         FROM operation_log
+         WHERE ? IS NULL OR operation = ? OR operation LIKE ? || '.%'
+         ORDER BY id DESC
+         LIMIT ?",
+        [
+            operation.map(str::to_string).into(),
+            operation.map(str::to_string).into(),
+            operation.map(str::to_string).into(),
+            limit.into(),
+        ],
+    ))
+    .all(db)
+    .await?;
+
+    Ok(rows
+        .into_iter()
+        .map(|r| OperationLogEntry {
+            id: r.id,
+            operation: r.operation,
+            detail: r.detail,
+            started_at: r.started_at,
+            duration_ms: r.duration_ms,
+            count: r.count,
+            success: r.success,
+            error: r.error,
+        })
+        .collect())
+}
@@ -6,12 +6,17 @@ pub struct PlatformDef {
     pub slug: &'static str,
     pub display_name: &'static str,
     pub folder_aliases: &'static [&'static str],
+    /// ROM file extensions, lowercase and without the dot. Archives in
+    /// `ARCHIVE_EXTENSIONS` are accepted for every platform on top of these.
+    pub extensions: &'static [&'static str],
     pub romm_aliases: &'static [&'static str],
     pub dat_aliases: &'static [&'static str],
     pub ra_console_id: Option<u32>,
     pub ss_id: Option<u32>,
     pub libretro_dir: Option<&'static str>,
     pub launchbox_name: Option<&'static str>,
+    /// Folder under `games/` on a MiSTer FPGA, named after the core that runs the platform.
+    pub mister_folder: Option<&'static str>,
 }
 
 /// Central platform registry — single source of truth for all platform data.
@@ -21,936 +26,1104 @@ pub const PLATFORMS: &[PlatformDef] = &[
         slug: "gb",
         display_name: "Game Boy",
         folder_aliases: &["gb"],
+        extensions: &["gb", "sgb"],
         romm_aliases: &["game-boy"],
         dat_aliases: &["Nintendo - Game Boy"],
         ra_console_id: Some(4),
         ss_id: Some(9),
         libretro_dir: Some("Nintendo - Game Boy"),
         launchbox_name: Some("Nintendo Game Boy"),
+        mister_folder: Some("GAMEBOY"),
     },
     PlatformDef {
         slug: "gbc",
         display_name: "Game Boy Color",
         folder_aliases: &["gbc"],
+        extensions: &["gbc", "gb"],
         romm_aliases: &["game-boy-color"],
         dat_aliases: &["Nintendo - Game Boy Color"],
         ra_console_id: Some(6),
         ss_id: Some(10),
         libretro_dir: Some("Nintendo - Game Boy Color"),
         launchbox_name: Some("Nintendo Game Boy Color"),
+        mister_folder: Some("GAMEBOY"),
     },
     PlatformDef {
         slug: "gba",
         display_name: "Game Boy Advance",
         folder_aliases: &["gba"],
+        extensions: &["gba"],
         romm_aliases: &["game-boy-advance"],
         dat_aliases: &["Nintendo - Game Boy Advance"],
         ra_console_id: Some(5),
         ss_id: Some(12),
         libretro_dir: Some("Nintendo - Game Boy Advance"),
         launchbox_name: Some("Nintendo Game Boy Advance"),
+        mister_folder: Some("GBA"),
     },
     PlatformDef {
         slug: "nes",
         display_name: "NES / Famicom",
         folder_aliases: &["nes", "fc", "famicom"],
+        extensions: &["nes", "unf", "unif"],
         romm_aliases: &["nintendo-entertainment-system", "famicom"],
         dat_aliases: &["Nintendo - Nintendo Entertainment System"],
         ra_console_id: Some(7),
         ss_id: Some(3),
         libretro_dir: Some("Nintendo - Nintendo Entertainment System"),
         launchbox_name: Some("Nintendo Entertainment System"),
+        mister_folder: Some("NES"),
     },
     PlatformDef {
         slug: "fds",
         display_name: "Famicom Disk System",
         folder_aliases: &["fds"],
+        extensions: &["fds"],
         romm_aliases: &[],
         dat_aliases: &["Nintendo - Famicom Disk System"],
         ra_console_id: None,
         ss_id: Some(106),
         libretro_dir: None,
         launchbox_name: None,
+        mister_folder: Some("NES"),
     },
     PlatformDef {
         slug: "snes",
         display_name: "SNES / Super Famicom",
         folder_aliases: &["snes", "sfc"],
+        extensions: &["sfc", "smc", "swc", "fig"],
         romm_aliases: &["super-nintendo", "super-famicom", "super-nintendo-entertainment-system", "sfam"],
         dat_aliases: &["Nintendo - Super Nintendo Entertainment System"],
         ra_console_id: Some(3),
         ss_id: Some(4),
         libretro_dir: Some("Nintendo - Super Nintendo Entertainment System"),
         launchbox_name: Some("Super Nintendo Entertainment System"),
+        mister_folder: Some("SNES"),
     },
     PlatformDef {
         slug: "n64",
         display_name: "Nintendo 64",
         folder_aliases: &["n64"],
+        extensions: &["n64", "z64", "v64"],
         romm_aliases: &["nintendo-64"],
         dat_aliases: &["Nintendo - Nintendo 64"],
         ra_console_id: Some(2),
         ss_id: Some(14),
         libretro_dir: Some("Nintendo - Nintendo 64"),
         launchbox_name: Some("Nintendo 64"),
+        mister_folder: Some("N64"),
     },
     PlatformDef {
         slug: "nds",
         display_name: "Nintendo DS",
         folder_aliases: &["nds"],
+        extensions: &["nds"],
         romm_aliases: &["nintendo-ds"],
         dat_aliases: &["Nintendo - Nintendo DS"],
         ra_console_id: Some(18),
         ss_id: Some(15),
         libretro_dir: Some("Nintendo - Nintendo DS"),
         launchbox_name: Some("Nintendo DS"),
+        mister_folder: None,
     },
     PlatformDef {
         slug: "3ds",
         display_name: "Nintendo 3DS",
         folder_aliases: &["3ds"],
+        extensions: &["3ds", "cia", "cci", "cxi", "3dsx"],
         romm_aliases: &[],
         dat_aliases: &[],
         ra_console_id: None,
         ss_id: Some(17),
         libretro_dir: None,
         launchbox_name: None,
+        mister_folder: None,
     },
     PlatformDef {
         slug: "gamecube",
         display_name: "GameCube",
         folder_aliases: &["gamecube", "gc"],
+        extensions: &["iso", "gcm", "rvz", "ciso", "gcz", "m3u"],
         romm_aliases: &["ngc"],
         dat_aliases: &["Nintendo - GameCube"],
         ra_console_id: None,
         ss_id: Some(13),
         libretro_dir: Some("Nintendo - GameCube"),
         launchbox_name: Some("Nintendo GameCube"),
+        mister_folder: None,
     },
     PlatformDef {
         slug: "wii",
         display_name: "Wii",
         folder_aliases: &["wii"],
+        extensions: &["iso", "wbfs", "rvz", "wia", "ciso", "gcz", "wad", "m3u"],
         romm_aliases: &[],
         dat_aliases: &["Nintendo - Wii"],
         ra_console_id: None,
         ss_id: Some(16),
         libretro_dir: Some("Nintendo - Wii"),
         launchbox_name: Some("Nintendo Wii"),
+        mister_folder: None,
     },
     PlatformDef {
         slug: "wiiu",
         display_name: "Wii U",
         folder_aliases: &["wiiu"],
+        extensions: &["wud", "wux", "wua", "rpx"],
         romm_aliases: &["wii-u"],
         dat_aliases: &[],
         ra_console_id: None,
         ss_id: Some(18),
         libretro_dir: Some("Nintendo - Wii U"),
         launchbox_name: Some("Nintendo Wii U"),
+        mister_folder: None,
     },
     PlatformDef {
         slug: "switch",
         display_name: "Nintendo Switch",
         folder_aliases: &["switch"],
+        extensions: &["xci", "nsp", "nro"],
         romm_aliases: &[],
         dat_aliases: &[],
         ra_console_id: None,
         ss_id: Some(225),
         libretro_dir: None,
         launchbox_name: Some("Nintendo Switch"),
+        mister_folder: None,
     },
     PlatformDef {
         slug: "switch2",
         display_name: "Nintendo Switch 2",
         folder_aliases: &["switch2"],
+        extensions: &["xci", "nsp"],
         romm_aliases: &["switch-2"],
         dat_aliases: &[],
         ra_console_id: None,
         ss_id: Some(296),
         libretro_dir: None,
         launchbox_name: None,
+        mister_folder: None,
     },
     PlatformDef {
         slug: "dsi",
         display_name: "Nintendo DSi",
         folder_aliases: &["dsi"],
+        extensions: &["nds", "dsi"],
         romm_aliases: &["nintendo-dsi"],
         dat_aliases: &[],
         ra_console_id: None,
         ss_id: Some(15),
         libretro_dir: None,
         launchbox_name: Some("Nintendo DSi"),
+        mister_folder: None,
     },
     PlatformDef {
         slug: "n3ds",
         display_name: "New Nintendo 3DS",
         folder_aliases: &["n3ds", "new3ds"],
+        extensions: &["3ds", "cia", "cci", "cxi", "3dsx"],
         romm_aliases: &["new-nintendo-3ds"],
         dat_aliases: &[],
         ra_console_id: None,
         ss_id: Some(17),
         libretro_dir: None,
         launchbox_name: None,
+        mister_folder: None,
     },
     PlatformDef {
         slug: "vb",
         display_name: "Virtual Boy",
         folder_aliases: &["virtualboy", "vb"],
+        extensions: &["vb", "vboy"],
         romm_aliases: &["virtual-boy", "virtualboy"],
         dat_aliases: &["Nintendo - Virtual Boy"],
         ra_console_id: Some(28),
         ss_id: Some(11),
         libretro_dir: Some("Nintendo - Virtual Boy"),
         launchbox_name: Some("Nintendo Virtual Boy"),
+        mister_folder: None,
     },
     PlatformDef {
         slug: "pokemini",
         display_name: "Pokemon Mini",
         folder_aliases: &["pokemini"],
+        extensions: &["min"],
         romm_aliases: &["pokemon-mini"],
         dat_aliases: &["Nintendo - Pokemon Mini"],
         ra_console_id: None,
         ss_id: Some(211),
         libretro_dir: None,
         launchbox_name: None,
+        mister_folder: Some("PokemonMini"),
     },
     PlatformDef {
         slug: "sufami",
         display_name: "Sufami Turbo",
         folder_aliases: &["sufami"],
+        extensions: &["st"],
         romm_aliases: &["sufami-turbo"],
         dat_aliases: &[],
         ra_console_id: None,
         ss_id: Some(108),
         libretro_dir: None,
         launchbox_name: None,
+        mister_folder: Some("SNES"),
     },
     // ── Sony ──
     PlatformDef {
         slug: "psx",
         display_name: "PlayStation",
         folder_aliases: &["psx", "ps", "ps1"],
+        extensions: &["cue", "bin", "img", "ccd", "chd", "pbp", "iso", "ecm", "m3u"],
         romm_aliases: &["ps", "playstation", "ps1"],
         dat_aliases: &["Sony - PlayStation"],
         ra_console_id: Some(12),
         ss_id: Some(57),
         libretro_dir: Some("Sony - PlayStation"),
         launchbox_name: Some("Sony Playstation"),
+        mister_folder: Some("PSX"),
     },
     PlatformDef {
         slug: "ps2",
         display_name: "PlayStation 2",
         folder_aliases: &["ps2"],
+        extensions: &["iso", "chd", "cso", "zso", "cue", "bin", "m3u"],
         romm_aliases: &["playstation-2"],
         dat_aliases: &["Sony - PlayStation 2"],
         ra_console_id: Some(21),
         ss_id: Some(58),
         libretro_dir: Some("Sony - PlayStation 2"),
         launchbox_name: Some("Sony Playstation 2"),
+        mister_folder: None,
     },
     PlatformDef {
         slug: "psp",
         display_name: "PlayStation Portable",
         folder_aliases: &["psp"],
+        extensions: &["iso", "cso", "pbp", "chd"],
         romm_aliases: &["playstation-portable"],
         dat_aliases: &["Sony - PlayStation Portable"],
         ra_console_id: Some(41),
         ss_id: Some(61),
         libretro_dir: Some("Sony - PlayStation Portable"),
         launchbox_name: Some("Sony PSP"),
+        mister_folder: None,
     },
     PlatformDef {
         slug: "ps3",
         display_name: "PlayStation 3",
         folder_aliases: &["ps3"],
+        extensions: &["iso", "pkg"],
         romm_aliases: &["playstation-3"],
         dat_aliases: &[],
         ra_console_id: None,
         ss_id: Some(59),
         libretro_dir: Some("Sony - PlayStation 3"),
         launchbox_name: Some("Sony Playstation 3"),
+        mister_folder: None,
     },
     PlatformDef {
         slug: "ps4",
         display_name: "PlayStation 4",
         folder_aliases: &["ps4"],
+        extensions: &["pkg"],
         romm_aliases: &["playstation-4"],
         dat_aliases: &[],
         ra_console_id: None,
         ss_id: Some(60),
         libretro_dir: None,
         launchbox_name: Some("Sony Playstation 4"),
+        mister_folder: None,
     },
     PlatformDef {
         slug: "ps5",
         display_name: "PlayStation 5",
         folder_aliases: &["ps5"],
+        extensions: &["pkg"],
         romm_aliases: &["playstation-5"],
         dat_aliases: &[],
         ra_console_id: None,
         ss_id: Some(284),
         libretro_dir: None,
         launchbox_name: Some("Sony Playstation 5"),
+        mister_folder: None,
     },
     PlatformDef {
         slug: "psvita",
         display_name: "PlayStation Vita",
         folder_aliases: &["psvita", "vita"],
+        extensions: &["vpk"],
         romm_aliases: &["playstation-vita"],
         dat_aliases: &[],
         ra_console_id: None,
         ss_id: Some(62),
         libretro_dir: None,
         launchbox_name: Some("Sony PlayStation Vita"),
+        mister_folder: None,
     },
     // ── Microsoft ──
     PlatformDef {
         slug: "xbox",
         display_name: "Xbox",
         folder_aliases: &["xbox"],
+        extensions: &["iso", "xiso"],
         romm_aliases: &[],
         dat_aliases: &[],
         ra_console_id: None,
         ss_id: Some(32),
         libretro_dir: None,
         launchbox_name: Some("Microsoft Xbox"),
+        mister_folder: None,
     },
     PlatformDef {
         slug: "xbox360",
         display_name: "Xbox 360",
         folder_aliases: &["xbox360"],
+        extensions: &["iso", "xex"],
         romm_aliases: &["xbox-360"],
         dat_aliases: &[],
         ra_console_id: None,
         ss_id: Some(33),
         libretro_dir: None,
         launchbox_name: Some("Microsoft Xbox 360"),
+        mister_folder: None,
     },
     PlatformDef {
         slug: "xboxone",
         display_name: "Xbox One",
         folder_aliases: &["xboxone"],
+        extensions: &["xvc"],
         romm_aliases: &["xbox-one"],
         dat_aliases: &[],
         ra_console_id: None,
         ss_id: Some(34),
         libretro_dir: None,
         launchbox_name: Some("Microsoft Xbox One"),
+        mister_folder: None,
     },
     PlatformDef {
         slug: "xboxseriesx",
         display_name: "Xbox Series X/S",
         folder_aliases: &["xboxseriesx"],
+        extensions: &["xvc"],
         romm_aliases: &["series-x-s"],
         dat_aliases: &[],
         ra_console_id: None,
         ss_id: None,
         libretro_dir: None,
         launchbox_name: None,
+        mister_folder: None,
     },
     // ── Sega ──
     PlatformDef {
         slug: "genesis",
         display_name: "Sega Genesis / Mega Drive",
         folder_aliases: &["genesis", "megadrive", "md"],
+        extensions: &["md", "gen", "smd", "bin"],
         romm_aliases: &["megadrive", "mega-drive", "sega-genesis", "mega-drive-slash-genesis"],
         dat_aliases: &["Sega - Mega Drive - Genesis"],
         ra_console_id: Some(1),
         ss_id: Some(1),
         libretro_dir: Some("Sega - Mega Drive - Genesis"),
         launchbox_name: Some("Sega Genesis"),
+        mister_folder: Some("MegaDrive"),
     },
     PlatformDef {
         slug: "segacd",
         display_name: "Sega CD",
         folder_aliases: &["segacd"],
+        extensions: &["cue", "bin", "chd", "iso", "img", "ccd", "m3u"],
         romm_aliases: &["sega-cd"],
         dat_aliases: &["Sega - Mega-CD - Sega CD"],
         ra_console_id: Some(9),
         ss_id: Some(20),
         libretro_dir: Some("Sega - Mega-CD - Sega CD"),
         launchbox_name: Some("Sega CD"),
+        mister_folder: Some("MegaCD"),
     },
     PlatformDef {
         slug: "saturn",
         display_name: "Sega Saturn",
         folder_aliases: &["saturn"],
+        extensions: &["cue", "bin", "chd", "iso", "img", "ccd", "m3u", "mds"],
         romm_aliases: &["sega-saturn"],
         dat_aliases: &["Sega - Saturn"],
         ra_console_id: Some(39),
         ss_id: Some(22),
         libretro_dir: Some("Sega - Saturn"),
         launchbox_name: Some("Sega Saturn"),
+        mister_folder: Some("Saturn"),
     },
     PlatformDef {
         slug: "dreamcast",
         display_name: "Dreamcast",
         folder_aliases: &["dreamcast", "dc"],
+        extensions: &["gdi", "cdi", "chd", "cue", "bin", "m3u"],
         romm_aliases: &["sega-dreamcast", "dc"],
         dat_aliases: &["Sega - Dreamcast"],
         ra_console_id: Some(40),
         ss_id: Some(23),
         libretro_dir: Some("Sega - Dreamcast"),
         launchbox_name: Some("Sega Dreamcast"),
+        mister_folder: None,
     },
     PlatformDef {
         slug: "gamegear",
         display_name: "Game Gear",
         folder_aliases: &["gamegear", "gg"],
+        extensions: &["gg"],
         romm_aliases: &["game-gear"],
         dat_aliases: &["Sega - Game Gear"],
         ra_console_id: Some(15),
         ss_id: Some(21),
         libretro_dir: Some("Sega - Game Gear"),
         launchbox_name: Some("Sega Game Gear"),
+        mister_folder: Some("GameGear"),
     },
     PlatformDef {
         slug: "mastersystem",
         display_name: "Master System",
         folder_aliases: &["mastersystem", "ms", "sms"],
+        extensions: &["sms"],
         romm_aliases: &["master-system", "sega-master-system", "sms"],
         dat_aliases: &["Sega - Master System - Mark III"],
         ra_console_id: Some(11),
         ss_id: Some(2),
         libretro_dir: Some("Sega - Master System - Mark III"),
         launchbox_name: Some("Sega Master System"),
+        mister_folder: Some("SMS"),
     },
     PlatformDef {
         slug: "sg1000",
         display_name: "SG-1000",
         folder_aliases: &["sg-1000", "sg1000", "sg"],
+        extensions: &["sg"],
         romm_aliases: &[],
         dat_aliases: &["Sega - SG-1000"],
         ra_console_id: Some(33),
         ss_id: Some(109),
         libretro_dir: None,
         launchbox_name: None,
+        mister_folder: Some("SG1000"),
     },
     PlatformDef {
         slug: "sega32",
         display_name: "Sega 32X",
         folder_aliases: &["sega32", "32x"],
+        extensions: &["32x", "bin"],
         romm_aliases: &[],
         dat_aliases: &[],
         ra_console_id: Some(10),
         ss_id: Some(19),
         libretro_dir: None,
         launchbox_name: None,
+        mister_folder: Some("S32X"),
     },
     // ── Capcom Arcade ──
     PlatformDef {
         slug: "cps1",
         display_name: "Capcom Play System",
         folder_aliases: &["cps1"],
+        extensions: &[],
         romm_aliases: &[],
         dat_aliases: &[],
         ra_console_id: None,
         ss_id: Some(6),
         libretro_dir: None,
         launchbox_name: None,
+        mister_folder: None,
     },
     PlatformDef {
         slug: "cps2",
         display_name: "Capcom Play System 2",
         folder_aliases: &["cps2"],
+        extensions: &[],
         romm_aliases: &[],
         dat_aliases: &[],
         ra_console_id: None,
         ss_id: Some(7),
         libretro_dir: None,
         launchbox_name: None,
+        mister_folder: None,
     },
     PlatformDef {
         slug: "cps3",
         display_name: "Capcom Play System 3",
         folder_aliases: &["cps3"],
+        extensions: &["chd"],
         romm_aliases: &[],
         dat_aliases: &[],
         ra_console_id: None,
         ss_id: Some(8),
         libretro_dir: None,
         launchbox_name: None,
+        mister_folder: None,
     },
     // ── SNK / Arcade ──
     PlatformDef {
         slug: "neogeo",
         display_name: "Neo Geo",
         folder_aliases: &["neogeo"],
+        extensions: &[],
         romm_aliases: &["neo-geo-aes", "neogeoaes", "neo-geo-mvs", "neogeomvs"],
         dat_aliases: &["SNK - Neo Geo"],
         ra_console_id: Some(14),
         ss_id: None,
         libretro_dir: Some("SNK - Neo Geo"),
         launchbox_name: Some("SNK Neo Geo AES"),
+        mister_folder: Some("NEOGEO"),
     },
     PlatformDef {
         slug: "arcade",
         display_name: "Arcade",
         folder_aliases: &["arcade", "mame", "fbneo", "fba"],
+        extensions: &["chd"],
         romm_aliases: &[],
         dat_aliases: &[],
         ra_console_id: Some(27),
         ss_id: Some(75),
         libretro_dir: Some("MAME"),
         launchbox_name: Some("Arcade"),
+        mister_folder: None,
     },
     PlatformDef {
         slug: "ngp",
         display_name: "Neo Geo Pocket",
         folder_aliases: &["ngp"],
+        extensions: &["ngp"],
         romm_aliases: &["neo-geo-pocket"],
         dat_aliases: &["SNK - Neo Geo Pocket"],
         ra_console_id: Some(14),
         ss_id: Some(25),
         libretro_dir: Some("SNK - Neo Geo Pocket"),
         launchbox_name: Some("SNK Neo Geo Pocket"),
+        mister_folder: Some("NGP"),
     },
     PlatformDef {
         slug: "ngpc",
         display_name: "Neo Geo Pocket Color",
         folder_aliases: &["ngpc"],
+        extensions: &["ngc", "ngpc", "ngp"],
         romm_aliases: &["neo-geo-pocket-color"],
         dat_aliases: &["SNK - Neo Geo Pocket Color"],
         ra_console_id: Some(14),
         ss_id: Some(82),
         libretro_dir: Some("SNK - Neo Geo Pocket Color"),
         launchbox_name: Some("SNK Neo Geo Pocket Color"),
+        mister_folder: Some("NGP"),
     },
     PlatformDef {
         slug: "neocd",
         display_name: "Neo Geo CD",
         folder_aliases: &["neocd"],
+        extensions: &["cue", "bin", "chd", "iso", "img", "ccd", "m3u"],
         romm_aliases: &["neo-geo-cd"],
         dat_aliases: &["SNK - Neo Geo CD"],
         ra_console_id: None,
         ss_id: Some(70),
         libretro_dir: None,
         launchbox_name: None,
+        mister_folder: None,
     },
     // ── NEC ──
     PlatformDef {
         slug: "pce",
         display_name: "TurboGrafx-16 / PC Engine",
         folder_aliases: &["pcengine", "pce", "tg16"],
+        extensions: &["pce"],
         romm_aliases: &["turbografx-16", "tg16", "pc-engine"],
         dat_aliases: &["NEC - PC Engine - TurboGrafx-16"],
         ra_console_id: Some(8),
         ss_id: Some(31),
         libretro_dir: Some("NEC - PC Engine - TurboGrafx 16"),
         launchbox_name: Some("NEC TurboGrafx-16"),
+        mister_folder: Some("TGFX16"),
     },
     PlatformDef {
         slug: "pcecd",
         display_name: "TurboGrafx-CD",
         folder_aliases: &["pcenginecd", "pcecd", "tgcd"],
+        extensions: &["cue", "bin", "chd", "iso", "img", "ccd", "m3u"],
         romm_aliases: &["turbografx-cd", "tg-cd", "pc-engine-cd"],
         dat_aliases: &["NEC - PC Engine CD - TurboGrafx-CD"],
         ra_console_id: Some(76),
         ss_id: Some(114),
         libretro_dir: Some("NEC - PC Engine CD - TurboGrafx-CD"),
         launchbox_name: Some("NEC TurboGrafx-CD"),
+        mister_folder: Some("TGFX16-CD"),
     },
     PlatformDef {
         slug: "sgfx",
         display_name: "SuperGrafx",
         folder_aliases: &["supergrafx", "sgfx"],
+        extensions: &["sgx", "pce"],
         romm_aliases: &["supergrafx"],
         dat_aliases: &["NEC - PC Engine SuperGrafx"],
         ra_console_id: None,
         ss_id: Some(105),
         libretro_dir: None,
         launchbox_name: None,
+        mister_folder: Some("TGFX16"),
     },
     PlatformDef {
         slug: "pcfx",
         display_name: "PC-FX",
         folder_aliases: &["pcfx"],
+        extensions: &["cue", "bin", "chd", "iso", "img", "ccd", "m3u"],
         romm_aliases: &["pc-fx"],
         dat_aliases: &["NEC - PC-FX"],
         ra_console_id: None,
         ss_id: Some(72),
         libretro_dir: None,
         launchbox_name: None,
+        mister_folder: None,
     },
     // ── Atari ──
     PlatformDef {
         slug: "atari2600",
         display_name: "Atari 2600",
         folder_aliases: &["atari2600", "atari", "a26"],
+        extensions: &["a26", "bin"],
         romm_aliases: &[],
         dat_aliases: &["Atari - 2600"],
         ra_console_id: Some(25),
         ss_id: Some(26),
         libretro_dir: None,
         launchbox_name: None,
+        mister_folder: Some("Atari2600"),
     },
     PlatformDef {
         slug: "atari5200",
         display_name: "Atari 5200",
         folder_aliases: &["atari5200"],
+        extensions: &["a52", "car", "bin"],
         romm_aliases: &[],
         dat_aliases: &["Atari - 5200"],
         ra_console_id: None,
         ss_id: Some(40),
         libretro_dir: None,
         launchbox_name: None,
+        mister_folder: Some("ATARI5200"),
     },
     PlatformDef {
         slug: "atari7800",
         display_name: "Atari 7800",
         folder_aliases: &["atari7800", "a78"],
+        extensions: &["a78", "bin"],
         romm_aliases: &[],
         dat_aliases: &["Atari - 7800"],
         ra_console_id: Some(51),
         ss_id: Some(41),
         libretro_dir: None,
         launchbox_name: None,
+        mister_folder: Some("ATARI7800"),
     },
     PlatformDef {
         slug: "lynx",
         display_name: "Atari Lynx",
         folder_aliases: &["lynx"],
+        extensions: &["lnx", "lyx", "o"],
         romm_aliases: &["atari-lynx"],
         dat_aliases: &["Atari - Lynx"],
         ra_console_id: Some(13),
         ss_id: Some(28),
         libretro_dir: Some("Atari - Lynx"),
         launchbox_name: Some("Atari Lynx"),
+        mister_folder: Some("AtariLynx"),
     },
     PlatformDef {
         slug: "atarist",
         display_name: "Atari ST",
         folder_aliases: &["atarist"],
+        extensions: &["st", "msa", "stx", "dim", "ipf"],
         romm_aliases: &["atari-st"],
         dat_aliases: &[],
         ra_console_id: None,
         ss_id: Some(42),
         libretro_dir: None,
         launchbox_name: None,
+        mister_folder: Some("AtariST"),
     },
     PlatformDef {
         slug: "jaguar",
         display_name: "Atari Jaguar",
         folder_aliases: &["jaguar"],
+        extensions: &["j64", "jag", "rom", "abs", "cof", "bin"],
         romm_aliases: &[],
         dat_aliases: &[],
         ra_console_id: Some(17),
         ss_id: Some(27),
         libretro_dir: None,
         launchbox_name: None,
+        mister_folder: None,
     },
     PlatformDef {
         slug: "atari8bit",
         display_name: "Atari 8-bit",
         folder_aliases: &["atari8bit", "atari800"],
+        extensions: &["atr", "atx", "xfd", "xex", "car", "cas", "com"],
         romm_aliases: &["atari800"],
         dat_aliases: &[],
         ra_console_id: None,
         ss_id: Some(43),
         libretro_dir: None,
         launchbox_name: None,
+        mister_folder: Some("ATARI800"),
     },
     // ── Bandai ──
     PlatformDef {
         slug: "ws",
         display_name: "WonderSwan",
         folder_aliases: &["wonderswan", "ws"],
+        extensions: &["ws"],
         romm_aliases: &["wonderswan"],
         dat_aliases: &["Bandai - WonderSwan"],
         ra_console_id: Some(53),
         ss_id: Some(45),
         libretro_dir: Some("Bandai - WonderSwan"),
         launchbox_name: Some("WonderSwan"),
+        mister_folder: Some("WonderSwan"),
     },
     PlatformDef {
         slug: "wsc",
         display_name: "WonderSwan Color",
         folder_aliases: &["wonderswancolor", "wsc"],
+        extensions: &["wsc"],
         romm_aliases: &["wonderswan-color"],
         dat_aliases: &["Bandai - WonderSwan Color"],
         ra_console_id: Some(53),
         ss_id: Some(46),
         libretro_dir: Some("Bandai - WonderSwan Color"),
         launchbox_name: Some("WonderSwan Color"),
+        mister_folder: Some("WonderSwan"),
     },
     // ── Other consoles ──
     PlatformDef {
         slug: "colecovision",
         display_name: "ColecoVision",
         folder_aliases: &["coleco", "colecovision", "col"],
+        extensions: &["col", "rom", "bin"],
         romm_aliases: &[],
         dat_aliases: &["Coleco - ColecoVision"],
         ra_console_id: Some(44),
         ss_id: Some(48),
         libretro_dir: Some("Coleco - ColecoVision"),
         launchbox_name: Some("ColecoVision"),
+        mister_folder: Some("Coleco"),
     },
     PlatformDef {
         slug: "intellivision",
         display_name: "Intellivision",
         folder_aliases: &["intellivision", "int"],
+        extensions: &["int", "rom", "bin"],
         romm_aliases: &[],
         dat_aliases: &["Mattel - Intellivision"],
         ra_console_id: Some(45),
         ss_id: Some(115),
         libretro_dir: None,
         launchbox_name: None,
+        mister_folder: Some("Intellivision"),
     },
     PlatformDef {
         slug: "vectrex",
         display_name: "Vectrex",
         folder_aliases: &["vectrex"],
+        extensions: &["vec", "gam", "bin"],
         romm_aliases: &[],
         dat_aliases: &["GCE - Vectrex"],
         ra_console_id: None,
         ss_id: Some(102),
         libretro_dir: None,
         launchbox_name: None,
+        mister_folder: Some("VECTREX"),
     },
     PlatformDef {
         slug: "channelf",
         display_name: "Channel F",
         folder_aliases: &["channelf"],
+        extensions: &["chf", "bin"],
         romm_aliases: &["fairchild-channel-f"],
         dat_aliases: &[],
         ra_console_id: None,
         ss_id: Some(80),
         libretro_dir: None,
         launchbox_name: None,
+        mister_folder: Some("ChannelF"),
     },
     PlatformDef {
         slug: "3do",
         display_name: "3DO Interactive Multiplayer",
         folder_aliases: &["3do"],
+        extensions: &["iso", "cue", "bin", "chd"],
         romm_aliases: &[],
         dat_aliases: &["Panasonic - 3DO Interactive Multiplayer"],
         ra_console_id: Some(43),
         ss_id: Some(29),
         libretro_dir: None,
         launchbox_name: None,
+        mister_folder: None,
     },
     PlatformDef {
         slug: "cdi",
         display_name: "Philips CD-i",
         folder_aliases: &["cdi"],
+        extensions: &["chd", "cue", "bin", "iso"],
         romm_aliases: &["philips-cd-i"],
         dat_aliases: &["Philips - CD-i"],
         ra_console_id: None,
         ss_id: Some(133),
         libretro_dir: None,
         launchbox_name: None,
+        mister_folder: Some("CD-i"),
     },
     PlatformDef {
         slug: "odyssey2",
         display_name: "Odyssey 2 / Videopac",
         folder_aliases: &["odyssey2"],
+        extensions: &["bin"],
         romm_aliases: &["odyssey-2"],
         dat_aliases: &[],
         ra_console_id: Some(23),
         ss_id: Some(104),
         libretro_dir: None,
         launchbox_name: None,
+        mister_folder: Some("ODYSSEY2"),
     },
     PlatformDef {
         slug: "megaduck",
         display_name: "Mega Duck",
         folder_aliases: &["megaduck"],
+        extensions: &["bin"],
         romm_aliases: &["mega-duck-slash-cougar-boy"],
         dat_aliases: &[],
         ra_console_id: None,
         ss_id: None,
         libretro_dir: None,
         launchbox_name: None,
+        mister_folder: Some("MegaDuck"),
     },
     PlatformDef {
         slug: "supervision",
         display_name: "Watara Supervision",
         folder_aliases: &["supervision"],
+        extensions: &["sv", "bin"],
         romm_aliases: &[],
         dat_aliases: &[],
         ra_console_id: None,
         ss_id: Some(207),
         libretro_dir: None,
         launchbox_name: None,
+        mister_folder: Some("SuperVision"),
     },
     // ── Computers ──
     PlatformDef {
         slug: "win",
         display_name: "PC (Windows)",
         folder_aliases: &["win", "windows"],
+        extensions: &["exe", "lnk", "bat"],
         romm_aliases: &[],
         dat_aliases: &[],
         ra_console_id: None,
         ss_id: Some(138),
         libretro_dir: None,
         launchbox_name: None,
+        mister_folder: None,
     },
     PlatformDef {
         slug: "msx",
         display_name: "MSX",
         folder_aliases: &["msx"],
+        extensions: &["rom", "mx1", "mx2", "dsk", "cas"],
         romm_aliases: &[],
         dat_aliases: &[],
         ra_console_id: None,
         ss_id: Some(113),
         libretro_dir: None,
         launchbox_name: None,
+        mister_folder: Some("MSX"),
     },
     PlatformDef {
         slug: "msx2",
         display_name: "MSX2",
         folder_aliases: &["msx2"],
+        extensions: &["rom", "mx1", "mx2", "dsk", "cas"],
         romm_aliases: &[],
         dat_aliases: &[],
         ra_console_id: None,
         ss_id: Some(116),
         libretro_dir: None,
         launchbox_name: None,
+        mister_folder: None,
     },
     PlatformDef {
         slug: "dos",
         display_name: "DOS",
         folder_aliases: &["dos"],
+        extensions: &["exe", "com", "bat", "dosz", "conf"],
         romm_aliases: &["ms-dos", "msdos"],
         dat_aliases: &[],
         ra_console_id: None,
         ss_id: Some(135),
         libretro_dir: None,
         launchbox_name: None,
+        mister_folder: None,
     },
     PlatformDef {
         slug: "cpc",
         display_name: "Amstrad CPC",
         folder_aliases: &["amstradcpc", "cpc"],
+        extensions: &["dsk", "sna", "cdt", "cpr"],
         romm_aliases: &["acpc", "amstrad-cpc"],
         dat_aliases: &[],
         ra_console_id: None,
         ss_id: Some(65),
         libretro_dir: None,
         launchbox_name: None,
+        mister_folder: Some("Amstrad"),
     },
     PlatformDef {
         slug: "zxspectrum",
         display_name: "ZX Spectrum",
         folder_aliases: &["zxspectrum"],
+        extensions: &["tzx", "tap", "z80", "sna", "dsk", "scl", "trd"],
         romm_aliases: &["zx-spectrum", "zxspectrum", "zxs"],
         dat_aliases: &[],
         ra_console_id: None,
         ss_id: Some(76),
         libretro_dir: None,
         launchbox_name: None,
+        mister_folder: Some("Spectrum"),
     },
     PlatformDef {
         slug: "c64",
         display_name: "Commodore 64",
         folder_aliases: &["c64"],
+        extensions: &["d64", "d71", "d81", "g64", "t64", "prg", "crt", "tap", "nib"],
         romm_aliases: &["commodore-64"],
         dat_aliases: &[],
         ra_console_id: None,
         ss_id: Some(66),
         libretro_dir: None,
         launchbox_name: None,
+        mister_folder: Some("C64"),
     },
     PlatformDef {
         slug: "amiga",
         display_name: "Amiga",
         folder_aliases: &["amiga"],
+        extensions: &["adf", "adz", "dms", "ipf", "hdf", "hdz", "lha", "uae", "m3u"],
         romm_aliases: &[],
         dat_aliases: &[],
         ra_console_id: None,
         ss_id: Some(64),
         libretro_dir: None,
         launchbox_name: None,
+        mister_folder: Some("Amiga"),
     },
     PlatformDef {
         slug: "scummvm",
         display_name: "ScummVM",
         folder_aliases: &["scummvm"],
+        extensions: &["scummvm", "svm"],
         romm_aliases: &[],
         dat_aliases: &[],
         ra_console_id: None,
         ss_id: Some(123),
         libretro_dir: None,
         launchbox_name: None,
+        mister_folder: None,
     },
     PlatformDef {
         slug: "vic20",
         display_name: "VIC-20",
         folder_aliases: &["vic20"],
+        extensions: &["prg", "crt", "d64", "tap", "20", "40", "60", "a0", "b0"],
         romm_aliases: &["vic-20"],
         dat_aliases: &[],
         ra_console_id: None,
         ss_id: Some(73),
         libretro_dir: None,
         launchbox_name: None,
+        mister_folder: Some("VIC20"),
     },
     PlatformDef {
         slug: "x68000",
         display_name: "Sharp X68000",
         folder_aliases: &["x68000"],
+        extensions: &["dim", "xdf", "hdf", "hdm", "2hd", "d88", "m3u"],
         romm_aliases: &["sharp-x68000"],
         dat_aliases: &[],
         ra_console_id: None,
         ss_id: Some(79),
         libretro_dir: None,
         launchbox_name: None,
+        mister_folder: Some("X68000"),
     },
     PlatformDef {
         slug: "pc98",
         display_name: "PC-9800 Series",
         folder_aliases: &["pc98"],
+        extensions: &["d98", "d88", "fdi", "fdd", "hdi", "hdm", "nhd", "thd"],
         romm_aliases: &["pc-9800-series"],
         dat_aliases: &[],
         ra_console_id: None,
         ss_id: Some(208),
         libretro_dir: None,
         launchbox_name: None,
+        mister_folder: None,
     },
     PlatformDef {
         slug: "trs80",
         display_name: "TRS-80",
         folder_aliases: &["trs80"],
+        extensions: &["cas", "dsk", "cmd"],
         romm_aliases: &["trs-80"],
         dat_aliases: &[],
         ra_console_id: None,
         ss_id: None,
         libretro_dir: None,
         launchbox_name: None,
+        mister_folder: None,
     },
     PlatformDef {
         slug: "ti99",
         display_name: "TI-99",
         folder_aliases: &["ti99"],
+        extensions: &["ctg", "rpk", "bin"],
         romm_aliases: &["ti-99"],
         dat_aliases: &[],
         ra_console_id: None,
         ss_id: Some(205),
         libretro_dir: None,
         launchbox_name: None,
+        mister_folder: Some("TI-99_4A"),
     },
     // ── Fantasy Consoles ──
     PlatformDef {
         slug: "tic80",
         display_name: "TIC-80",
         folder_aliases: &["tic80", "tic-80"],
+        extensions: &["tic"],
         romm_aliases: &["tic-80"],
         dat_aliases: &[],
         ra_console_id: None,
         ss_id: Some(222),
         libretro_dir: None,
         launchbox_name: None,
+        mister_folder: None,
     },
     PlatformDef {
         slug: "pico8",
         display_name: "PICO-8",
         folder_aliases: &["pico8", "pico-8"],
+        extensions: &["p8", "png"],
         romm_aliases: &["pico"],
         dat_aliases: &[],
         ra_console_id: None,
         ss_id: Some(234),
         libretro_dir: None,
         launchbox_name: None,
+        mister_folder: None,
     },
 
     // ── Medium Priority - Nintendo ──
@@ -958,23 +1131,27 @@ pub const PLATFORMS: &[PlatformDef] = &[
         slug: "64dd",
         display_name: "Nintendo 64DD",
         folder_aliases: &["64dd"],
+        extensions: &["ndd"],
         romm_aliases: &["64dd"],
         dat_aliases: &[],
         ra_console_id: None,
         ss_id: Some(122),
         libretro_dir: None,
         launchbox_name: None,
+        mister_folder: None,
     },
     PlatformDef {
         slug: "satellaview",
         display_name: "Satellaview",
         folder_aliases: &["satellaview", "bs"],
+        extensions: &["bs", "sfc"],
         romm_aliases: &["satellaview"],
         dat_aliases: &["Nintendo - Satellaview"],
         ra_console_id: None,
         ss_id: Some(107),
         libretro_dir: None,
         launchbox_name: None,
+        mister_folder: Some("SNES"),
     },
 
     // ── Medium Priority - Sony ──
@@ -982,12 +1159,14 @@ pub const PLATFORMS: &[PlatformDef] = &[
         slug: "pspminis",
         display_name: "PSP Minis",
         folder_aliases: &["pspminis"],
+        extensions: &["pbp", "iso", "cso"],
         romm_aliases: &["psp-minis"],
         dat_aliases: &[],
         ra_console_id: None,
         ss_id: Some(172),
         libretro_dir: None,
         launchbox_name: None,
+        mister_folder: None,
     },
 
     // ── Medium Priority - Sega ──
@@ -995,34 +1174,40 @@ pub const PLATFORMS: &[PlatformDef] = &[
         slug: "segacd32",
         display_name: "Sega CD 32X",
         folder_aliases: &["segacd32", "segacd32x"],
+        extensions: &["cue", "bin", "chd", "iso", "img", "ccd", "m3u"],
         romm_aliases: &["segacd32"],
         dat_aliases: &[],
         ra_console_id: None,
         ss_id: None,
         libretro_dir: None,
         launchbox_name: None,
+        mister_folder: None,
     },
     PlatformDef {
         slug: "segapico",
         display_name: "Sega Pico",
         folder_aliases: &["segapico", "pico"],
+        extensions: &["md", "bin"],
         romm_aliases: &["sega-pico"],
         dat_aliases: &[],
         ra_console_id: None,
         ss_id: Some(250),
         libretro_dir: None,
         launchbox_name: None,
+        mister_folder: None,
     },
     PlatformDef {
         slug: "sc3000",
         display_name: "Sega SC-3000",
         folder_aliases: &["sc3000", "sc-3000"],
+        extensions: &["sc", "sg", "bin"],
         romm_aliases: &["sc3000"],
         dat_aliases: &[],
         ra_console_id: None,
         ss_id: None,
         libretro_dir: None,
         launchbox_name: None,
+        mister_folder: None,
     },
 
     // ── Medium Priority - Other ──
@@ -1030,34 +1215,40 @@ pub const PLATFORMS: &[PlatformDef] = &[
         slug: "odyssey",
         display_name: "Magnavox Odyssey",
         folder_aliases: &["odyssey"],
+        extensions: &["bin"],
         romm_aliases: &["odyssey"],
         dat_aliases: &[],
         ra_console_id: None,
         ss_id: None,
         libretro_dir: None,
         launchbox_name: None,
+        mister_folder: None,
     },
     PlatformDef {
         slug: "zx81",
         display_name: "ZX81",
         folder_aliases: &["zx81"],
+        extensions: &["p", "81", "tzx"],
         romm_aliases: &["zx81"],
         dat_aliases: &[],
         ra_console_id: None,
         ss_id: Some(77),
         libretro_dir: None,
         launchbox_name: None,
+        mister_folder: Some("ZX81"),
     },
     PlatformDef {
         slug: "openbor",
         display_name: "OpenBOR",
         folder_aliases: &["openbor"],
+        extensions: &["pak"],
         romm_aliases: &["openbor"],
         dat_aliases: &[],
         ra_console_id: None,
         ss_id: Some(214),
         libretro_dir: None,
         launchbox_name: None,
+        mister_folder: None,
     },
 
     // ── Lower Priority - Commodore ──
@@ -1065,78 +1256,92 @@ pub const PLATFORMS: &[PlatformDef] = &[
         slug: "amigacd32",
         display_name: "Amiga CD32",
         folder_aliases: &["amigacd32"],
+        extensions: &["cue", "bin", "chd", "iso", "img", "ccd", "m3u"],
         romm_aliases: &["amiga-cd32"],
         dat_aliases: &[],
         ra_console_id: None,
         ss_id: Some(130),
         libretro_dir: None,
         launchbox_name: None,
+        mister_folder: None,
     },
     PlatformDef {
         slug: "amigacd",
         display_name: "Amiga CD",
         folder_aliases: &["amigacd"],
+        extensions: &["cue", "bin", "chd", "iso", "img", "ccd", "m3u"],
         romm_aliases: &["amiga-cd"],
         dat_aliases: &[],
         ra_console_id: None,
         ss_id: Some(134),
         libretro_dir: None,
         launchbox_name: None,
+        mister_folder: None,
     },
     PlatformDef {
         slug: "cdtv",
         display_name: "Commodore CDTV",
         folder_aliases: &["cdtv"],
+        extensions: &["cue", "bin", "chd", "iso", "img", "ccd", "m3u"],
         romm_aliases: &["commodore-cdtv"],
         dat_aliases: &[],
         ra_console_id: None,
         ss_id: Some(129),
         libretro_dir: None,
         launchbox_name: None,
+        mister_folder: None,
     },
     PlatformDef {
         slug: "c128",
         display_name: "Commodore 128",
         folder_aliases: &["c128"],
+        extensions: &["d64", "d71", "d81", "prg", "crt", "tap"],
         romm_aliases: &["c128"],
         dat_aliases: &[],
         ra_console_id: None,
         ss_id: Some(66),
         libretro_dir: None,
         launchbox_name: None,
+        mister_folder: None,
     },
     PlatformDef {
         slug: "c16",
         display_name: "Commodore 16",
         folder_aliases: &["c16"],
+        extensions: &["prg", "d64", "tap", "crt"],
         romm_aliases: &["c16"],
         dat_aliases: &[],
         ra_console_id: None,
         ss_id: Some(99),
         libretro_dir: None,
         launchbox_name: None,
+        mister_folder: Some("C16"),
     },
     PlatformDef {
         slug: "cplus4",
         display_name: "Commodore Plus/4",
         folder_aliases: &["cplus4", "plus4"],
+        extensions: &["prg", "d64", "tap", "crt"],
         romm_aliases: &["c-plus-4"],
         dat_aliases: &[],
         ra_console_id: None,
         ss_id: Some(99),
         libretro_dir: None,
         launchbox_name: None,
+        mister_folder: Some("C16"),
     },
     PlatformDef {
         slug: "cpet",
         display_name: "Commodore PET",
         folder_aliases: &["cpet", "pet"],
+        extensions: &["prg", "d64", "tap"],
         romm_aliases: &["cpet"],
         dat_aliases: &[],
         ra_console_id: None,
         ss_id: Some(240),
         libretro_dir: None,
         launchbox_name: None,
+        mister_folder: None,
     },
 
     // ── Lower Priority - Apple ──
@@ -1144,23 +1349,27 @@ pub const PLATFORMS: &[PlatformDef] = &[
         slug: "appleii",
         display_name: "Apple II",
         folder_aliases: &["appleii", "apple2"],
+        extensions: &["dsk", "do", "po", "nib", "woz", "2mg"],
         romm_aliases: &["appleii"],
         dat_aliases: &[],
         ra_console_id: Some(38),
         ss_id: Some(86),
         libretro_dir: None,
         launchbox_name: None,
+        mister_folder: Some("Apple-II"),
     },
     PlatformDef {
         slug: "appleiigs",
         display_name: "Apple IIGS",
         folder_aliases: &["appleiigs"],
+        extensions: &["2mg", "po", "woz", "dsk"],
         romm_aliases: &["apple-iigs"],
         dat_aliases: &[],
         ra_console_id: None,
         ss_id: Some(51),
         libretro_dir: None,
         launchbox_name: None,
+        mister_folder: None,
     },
 
     // ── Lower Priority - Atari ──
@@ -1168,34 +1377,40 @@ pub const PLATFORMS: &[PlatformDef] = &[
         slug: "jaguarcd",
         display_name: "Atari Jaguar CD",
         folder_aliases: &["jaguarcd"],
+        extensions: &["cue", "bin", "chd", "iso", "img", "ccd", "m3u"],
         romm_aliases: &["atari-jaguar-cd"],
         dat_aliases: &[],
         ra_console_id: Some(77),
         ss_id: Some(171),
         libretro_dir: None,
         launchbox_name: None,
+        mister_folder: None,
     },
     PlatformDef {
         slug: "atarixegs",
         display_name: "Atari XEGS",
         folder_aliases: &["atarixegs", "xegs"],
+        extensions: &["atr", "atx", "xfd", "xex", "car", "cas", "com"],
         romm_aliases: &["atari-xegs"],
         dat_aliases: &[],
         ra_console_id: None,
         ss_id: Some(43),
         libretro_dir: None,
         launchbox_name: None,
+        mister_folder: None,
     },
     PlatformDef {
         slug: "atarivcs",
         display_name: "Atari VCS (2020)",
         folder_aliases: &["atarivcs"],
+        extensions: &["a26", "bin"],
         romm_aliases: &["atari-vcs"],
         dat_aliases: &[],
         ra_console_id: None,
         ss_id: None,
         libretro_dir: None,
         launchbox_name: None,
+        mister_folder: None,
     },
 
     // ── Lower Priority - British Computers ──
@@ -1203,111 +1418,131 @@ pub const PLATFORMS: &[PlatformDef] = &[
         slug: "bbcmicro",
         display_name: "BBC Micro",
         folder_aliases: &["bbcmicro"],
+        extensions: &["ssd", "dsd", "uef"],
         romm_aliases: &["bbcmicro"],
         dat_aliases: &[],
         ra_console_id: None,
         ss_id: Some(37),
         libretro_dir: None,
         launchbox_name: None,
+        mister_folder: Some("BBCMicro"),
     },
     PlatformDef {
         slug: "samcoupe",
         display_name: "SAM Coupe",
         folder_aliases: &["samcoupe"],
+        extensions: &["dsk", "mgt", "sad"],
         romm_aliases: &["sam-coupe"],
         dat_aliases: &[],
         ra_console_id: None,
         ss_id: Some(213),
         libretro_dir: None,
         launchbox_name: None,
+        mister_folder: Some("SAMCOUPE"),
     },
     PlatformDef {
         slug: "sinclairql",
         display_name: "Sinclair QL",
         folder_aliases: &["sinclairql"],
+        extensions: &["mdv", "win"],
         romm_aliases: &["sinclair-ql"],
         dat_aliases: &[],
         ra_console_id: None,
         ss_id: None,
         libretro_dir: None,
         launchbox_name: None,
+        mister_folder: None,
     },
     PlatformDef {
         slug: "zxsnext",
         display_name: "ZX Spectrum Next",
         folder_aliases: &["zxsnext"],
+        extensions: &["nex", "tap", "sna"],
         romm_aliases: &["zx-spectrum-next"],
         dat_aliases: &[],
         ra_console_id: None,
         ss_id: None,
         libretro_dir: None,
         launchbox_name: None,
+        mister_folder: None,
     },
     PlatformDef {
         slug: "zx80",
         display_name: "ZX80",
         folder_aliases: &["zx80"],
+        extensions: &["o", "80"],
         romm_aliases: &["zx80"],
         dat_aliases: &[],
         ra_console_id: None,
         ss_id: None,
         libretro_dir: None,
         launchbox_name: None,
+        mister_folder: None,
     },
     PlatformDef {
         slug: "archimedes",
         display_name: "Acorn Archimedes",
         folder_aliases: &["archimedes"],
+        extensions: &["adf", "hdf", "jfd"],
         romm_aliases: &["acorn-archimedes"],
         dat_aliases: &[],
         ra_console_id: None,
         ss_id: Some(84),
         libretro_dir: None,
         launchbox_name: None,
+        mister_folder: Some("Archie"),
     },
     PlatformDef {
         slug: "electron",
         display_name: "Acorn Electron",
         folder_aliases: &["electron"],
+        extensions: &["uef", "ssd", "dsd"],
         romm_aliases: &["acorn-electron"],
         dat_aliases: &[],
         ra_console_id: None,
         ss_id: Some(85),
         libretro_dir: None,
         launchbox_name: None,
+        mister_folder: Some("AcornElectron"),
     },
     PlatformDef {
         slug: "oric",
         display_name: "Oric / Atmos",
         folder_aliases: &["oric"],
+        extensions: &["tap", "dsk"],
         romm_aliases: &["oric"],
         dat_aliases: &[],
         ra_console_id: None,
         ss_id: Some(131),
         libretro_dir: None,
         launchbox_name: None,
+        mister_folder: Some("Oric"),
     },
     PlatformDef {
         slug: "dragon",
         display_name: "Dragon 32/64",
         folder_aliases: &["dragon", "dragon32", "dragon64"],
+        extensions: &["cas", "dsk", "vdk", "ccc"],
         romm_aliases: &["dragon-32-slash-64"],
         dat_aliases: &[],
         ra_console_id: None,
         ss_id: Some(91),
         libretro_dir: None,
         launchbox_name: None,
+        mister_folder: None,
     },
     PlatformDef {
         slug: "thomson",
         display_name: "Thomson MO5/TO",
         folder_aliases: &["thomson", "mo5"],
+        extensions: &["fd", "k7", "m5", "m7", "rom", "sap"],
         romm_aliases: &["thomson-mo5"],
         dat_aliases: &[],
         ra_console_id: None,
         ss_id: Some(141),
         libretro_dir: None,
         launchbox_name: None,
+        mister_folder: None,
     },
 
     // ── Lower Priority - MSX variants ──
@@ -1315,23 +1550,27 @@ pub const PLATFORMS: &[PlatformDef] = &[
         slug: "msxturbo",
         display_name: "MSX turbo R",
         folder_aliases: &["msxturbo"],
+        extensions: &["rom", "dsk", "cas"],
         romm_aliases: &["msx-turbo"],
         dat_aliases: &[],
         ra_console_id: None,
         ss_id: Some(118),
         libretro_dir: None,
         launchbox_name: None,
+        mister_folder: None,
     },
     PlatformDef {
         slug: "msx2plus",
         display_name: "MSX2+",
         folder_aliases: &["msx2plus", "msx2+"],
+        extensions: &["rom", "mx2", "dsk", "cas"],
         romm_aliases: &["msx2plus"],
         dat_aliases: &[],
         ra_console_id: None,
         ss_id: Some(117),
         libretro_dir: None,
         launchbox_name: None,
+        mister_folder: None,
     },
 
     // ── Lower Priority - Japanese Computers ──
@@ -1339,67 +1578,79 @@ pub const PLATFORMS: &[PlatformDef] = &[
         slug: "pc8800",
         display_name: "NEC PC-8801",
         folder_aliases: &["pc8800", "pc88", "pc8801"],
+        extensions: &["d88", "cmt", "t88"],
         romm_aliases: &["pc-8800-series"],
         dat_aliases: &[],
         ra_console_id: Some(47),
         ss_id: Some(221),
         libretro_dir: None,
         launchbox_name: None,
+        mister_folder: None,
     },
     PlatformDef {
         slug: "pc6000",
         display_name: "NEC PC-6001",
         folder_aliases: &["pc6000", "pc6001"],
+        extensions: &["cas", "p6", "d88"],
         romm_aliases: &["nec-pc-6000-series"],
         dat_aliases: &[],
         ra_console_id: None,
         ss_id: None,
         libretro_dir: None,
         launchbox_name: None,
+        mister_folder: None,
     },
     PlatformDef {
         slug: "fmtowns",
         display_name: "FM Towns",
         folder_aliases: &["fmtowns"],
+        extensions: &["cue", "bin", "chd", "iso", "img", "ccd", "m3u", "d88"],
         romm_aliases: &["fm-towns"],
         dat_aliases: &[],
         ra_console_id: None,
         ss_id: Some(253),
         libretro_dir: None,
         launchbox_name: None,
+        mister_folder: None,
     },
     PlatformDef {
         slug: "fm7",
         display_name: "Fujitsu FM-7",
         folder_aliases: &["fm7"],
+        extensions: &["d77", "t77", "d88"],
         romm_aliases: &["fm-7"],
         dat_aliases: &[],
         ra_console_id: None,
         ss_id: Some(97),
         libretro_dir: None,
         launchbox_name: None,
+        mister_folder: None,
     },
     PlatformDef {
         slug: "x1",
         display_name: "Sharp X1",
         folder_aliases: &["x1"],
+        extensions: &["2d", "d88", "tap"],
         romm_aliases: &["x1"],
         dat_aliases: &[],
         ra_console_id: None,
         ss_id: Some(220),
         libretro_dir: None,
         launchbox_name: None,
+        mister_folder: None,
     },
     PlatformDef {
         slug: "sharpmz",
         display_name: "Sharp MZ-2200",
         folder_aliases: &["sharpmz", "mz2200"],
+        extensions: &["mzf", "m12", "d88"],
         romm_aliases: &["sharp-mz-2200"],
         dat_aliases: &[],
         ra_console_id: None,
         ss_id: None,
         libretro_dir: None,
         launchbox_name: None,
+        mister_folder: None,
     },
 
     // ── Lower Priority - Other Computers ──
@@ -1407,45 +1658,53 @@ pub const PLATFORMS: &[PlatformDef] = &[
         slug: "amstradpcw",
         display_name: "Amstrad PCW",
         folder_aliases: &["amstradpcw", "pcw"],
+        extensions: &["dsk"],
         romm_aliases: &["amstrad-pcw"],
         dat_aliases: &[],
         ra_console_id: None,
         ss_id: None,
         libretro_dir: None,
         launchbox_name: None,
+        mister_folder: None,
     },
     PlatformDef {
         slug: "gx4000",
         display_name: "Amstrad GX4000",
         folder_aliases: &["gx4000"],
+        extensions: &["cpr"],
         romm_aliases: &["amstrad-gx4000"],
         dat_aliases: &[],
         ra_console_id: None,
         ss_id: Some(87),
         libretro_dir: None,
         launchbox_name: None,
+        mister_folder: None,
     },
     PlatformDef {
         slug: "cpm",
         display_name: "CP/M",
         folder_aliases: &["cpm"],
+        extensions: &["dsk", "com"],
         romm_aliases: &["cpm"],
         dat_aliases: &[],
         ra_console_id: None,
         ss_id: None,
         libretro_dir: None,
         launchbox_name: None,
+        mister_folder: None,
     },
     PlatformDef {
         slug: "trs80coco",
         display_name: "TRS-80 Color Computer",
         folder_aliases: &["trs80coco", "coco"],
+        extensions: &["ccc", "cas", "dsk", "rom"],
         romm_aliases: &["trs-80-color-computer"],
         dat_aliases: &[],
         ra_console_id: None,
         ss_id: None,
         libretro_dir: None,
         launchbox_name: None,
+        mister_folder: Some("CoCo2"),
     },
 
     // ── Arcade Sub-Systems ──
@@ -1453,122 +1712,144 @@ pub const PLATFORMS: &[PlatformDef] = &[
         slug: "model1",
         display_name: "Sega Model 1",
         folder_aliases: &["model1"],
+        extensions: &[],
         romm_aliases: &["model1"],
         dat_aliases: &[],
         ra_console_id: None,
         ss_id: None,
         libretro_dir: None,
         launchbox_name: None,
+        mister_folder: None,
     },
     PlatformDef {
         slug: "model2",
         display_name: "Sega Model 2",
         folder_aliases: &["model2"],
+        extensions: &[],
         romm_aliases: &["model2"],
         dat_aliases: &[],
         ra_console_id: None,
         ss_id: Some(54),
         libretro_dir: None,
         launchbox_name: None,
+        mister_folder: None,
     },
     PlatformDef {
         slug: "model3",
         display_name: "Sega Model 3",
         folder_aliases: &["model3"],
+        extensions: &[],
         romm_aliases: &["model3"],
         dat_aliases: &[],
         ra_console_id: None,
         ss_id: Some(55),
         libretro_dir: None,
         launchbox_name: None,
+        mister_folder: None,
     },
     PlatformDef {
         slug: "stv",
         display_name: "Sega ST-V",
         folder_aliases: &["stv"],
+        extensions: &[],
         romm_aliases: &["stv"],
         dat_aliases: &[],
         ra_console_id: None,
         ss_id: Some(69),
         libretro_dir: None,
         launchbox_name: None,
+        mister_folder: None,
     },
     PlatformDef {
         slug: "system16",
         display_name: "Sega System 16",
         folder_aliases: &["system16"],
+        extensions: &[],
         romm_aliases: &["system16"],
         dat_aliases: &[],
         ra_console_id: None,
         ss_id: None,
         libretro_dir: None,
         launchbox_name: None,
+        mister_folder: None,
     },
     PlatformDef {
         slug: "system32",
         display_name: "Sega System 32",
         folder_aliases: &["system32"],
+        extensions: &[],
         romm_aliases: &["system32"],
         dat_aliases: &[],
         ra_console_id: None,
         ss_id: None,
         libretro_dir: None,
         launchbox_name: None,
+        mister_folder: None,
     },
     PlatformDef {
         slug: "hikaru",
         display_name: "Sega Hikaru",
         folder_aliases: &["hikaru"],
+        extensions: &["chd"],
         romm_aliases: &["hikaru"],
         dat_aliases: &[],
         ra_console_id: None,
         ss_id: Some(258),
         libretro_dir: None,
         launchbox_name: None,
+        mister_folder: None,
     },
     PlatformDef {
         slug: "hyperneogeo64",
         display_name: "Hyper Neo Geo 64",
         folder_aliases: &["hyperneogeo64"],
+        extensions: &["chd"],
         romm_aliases: &["hyper-neo-geo-64"],
         dat_aliases: &[],
         ra_console_id: None,
         ss_id: None,
         libretro_dir: None,
         launchbox_name: None,
+        mister_folder: None,
     },
     PlatformDef {
         slug: "typex",
         display_name: "Taito Type X",
         folder_aliases: &["typex"],
+        extensions: &["exe"],
         romm_aliases: &["type-x"],
         dat_aliases: &[],
         ra_console_id: None,
         ss_id: Some(112),
         libretro_dir: None,
         launchbox_name: None,
+        mister_folder: None,
     },
     PlatformDef {
         slug: "naomi",
         display_name: "Sega NAOMI",
         folder_aliases: &["naomi"],
+        extensions: &["chd", "lst", "dat"],
         romm_aliases: &["naomi"],
         dat_aliases: &[],
         ra_console_id: None,
         ss_id: None,
         libretro_dir: None,
         launchbox_name: None,
+        mister_folder: None,
     },
     PlatformDef {
         slug: "zinc",
         display_name: "ZiNc",
         folder_aliases: &["zinc"],
+        extensions: &[],
         romm_aliases: &["zinc"],
         dat_aliases: &[],
         ra_console_id: None,
         ss_id: None,
         libretro_dir: None,
         launchbox_name: None,
+        mister_folder: None,
     },
 
     // ── Obscure / Niche ──
@@ -1576,364 +1857,430 @@ pub const PLATFORMS: &[PlatformDef] = &[
         slug: "colecoadam",
         display_name: "Coleco Adam",
         folder_aliases: &["colecoadam", "adam"],
+        extensions: &["ddp", "dsk", "rom"],
         romm_aliases: &["colecoadam"],
         dat_aliases: &[],
         ra_console_id: None,
         ss_id: None,
         libretro_dir: None,
         launchbox_name: None,
+        mister_folder: None,
     },
     PlatformDef {
         slug: "creativision",
         display_name: "CreatiVision",
         folder_aliases: &["creativision"],
+        extensions: &["bin", "rom"],
         romm_aliases: &["creativision"],
         dat_aliases: &[],
         ra_console_id: None,
         ss_id: None,
         libretro_dir: None,
         launchbox_name: None,
+        mister_folder: Some("CreatiVision"),
     },
     PlatformDef {
         slug: "arcadia2001",
         display_name: "Arcadia 2001",
         folder_aliases: &["arcadia2001"],
+        extensions: &["bin"],
         romm_aliases: &["arcadia-2001"],
         dat_aliases: &[],
         ra_console_id: None,
         ss_id: None,
         libretro_dir: None,
         launchbox_name: None,
+        mister_folder: Some("Arcadia"),
     },
     PlatformDef {
         slug: "arduboy",
         display_name: "Arduboy",
         folder_aliases: &["arduboy"],
+        extensions: &["hex", "arduboy"],
         romm_aliases: &["arduboy"],
         dat_aliases: &[],
         ra_console_id: None,
         ss_id: None,
         libretro_dir: None,
         launchbox_name: None,
+        mister_folder: None,
     },
     PlatformDef {
         slug: "astrocade",
         display_name: "Bally Astrocade",
         folder_aliases: &["astrocade"],
+        extensions: &["bin"],
         romm_aliases: &["astrocade"],
         dat_aliases: &[],
         ra_console_id: None,
         ss_id: None,
         libretro_dir: None,
         launchbox_name: None,
+        mister_folder: Some("Astrocade"),
     },
     PlatformDef {
         slug: "casioloopy",
         display_name: "Casio Loopy",
         folder_aliases: &["casioloopy"],
+        extensions: &["bin"],
         romm_aliases: &["casio-loopy"],
         dat_aliases: &[],
         ra_console_id: None,
         ss_id: None,
         libretro_dir: None,
         launchbox_name: None,
+        mister_folder: None,
     },
     PlatformDef {
         slug: "casiopv1000",
         display_name: "Casio PV-1000",
         folder_aliases: &["casiopv1000"],
+        extensions: &["bin"],
         romm_aliases: &["casio-pv-1000"],
         dat_aliases: &[],
         ra_console_id: None,
         ss_id: None,
         libretro_dir: None,
         launchbox_name: None,
+        mister_folder: Some("Casio_PV-1000"),
     },
     PlatformDef {
         slug: "epochcv",
         display_name: "Epoch Cassette Vision",
         folder_aliases: &["epochcv"],
+        extensions: &["bin"],
         romm_aliases: &["epoch-cassette-vision"],
         dat_aliases: &[],
         ra_console_id: None,
         ss_id: None,
         libretro_dir: None,
         launchbox_name: None,
+        mister_folder: None,
     },
     PlatformDef {
         slug: "epochscv",
         display_name: "Epoch Super Cassette Vision",
         folder_aliases: &["epochscv"],
+        extensions: &["bin"],
         romm_aliases: &["epoch-super-cassette-vision"],
         dat_aliases: &[],
         ra_console_id: None,
         ss_id: None,
         libretro_dir: None,
         launchbox_name: None,
+        mister_folder: None,
     },
     PlatformDef {
         slug: "evercade",
         display_name: "Evercade",
         folder_aliases: &["evercade"],
+        extensions: &["bin"],
         romm_aliases: &["evercade"],
         dat_aliases: &[],
         ra_console_id: None,
         ss_id: None,
         libretro_dir: None,
         launchbox_name: None,
+        mister_folder: None,
     },
     PlatformDef {
         slug: "gandw",
         display_name: "Game & Watch",
         folder_aliases: &["gandw", "gameandwatch"],
+        extensions: &["mgw"],
         romm_aliases: &["g-and-w"],
         dat_aliases: &[],
         ra_console_id: None,
         ss_id: None,
         libretro_dir: None,
         launchbox_name: None,
+        mister_folder: None,
     },
     PlatformDef {
         slug: "gamate",
         display_name: "Gamate",
         folder_aliases: &["gamate"],
+        extensions: &["bin"],
         romm_aliases: &["gamate"],
         dat_aliases: &[],
         ra_console_id: None,
         ss_id: None,
         libretro_dir: None,
         launchbox_name: None,
+        mister_folder: Some("Gamate"),
     },
     PlatformDef {
         slug: "gamecom",
         display_name: "Game.com",
         folder_aliases: &["gamecom"],
+        extensions: &["tgc", "bin"],
         romm_aliases: &["game-dot-com"],
         dat_aliases: &[],
         ra_console_id: None,
         ss_id: None,
         libretro_dir: None,
         launchbox_name: None,
+        mister_folder: None,
     },
     PlatformDef {
         slug: "gizmondo",
         display_name: "Gizmondo",
         folder_aliases: &["gizmondo"],
+        extensions: &["exe"],
         romm_aliases: &["gizmondo"],
         dat_aliases: &[],
         ra_console_id: None,
         ss_id: None,
         libretro_dir: None,
         launchbox_name: None,
+        mister_folder: None,
     },
     PlatformDef {
         slug: "gp32",
         display_name: "GP32",
         folder_aliases: &["gp32"],
+        extensions: &["fxe"],
         romm_aliases: &["gp32"],
         dat_aliases: &[],
         ra_console_id: None,
         ss_id: None,
         libretro_dir: None,
         launchbox_name: None,
+        mister_folder: None,
     },
     PlatformDef {
         slug: "gp2x",
         display_name: "GP2X",
         folder_aliases: &["gp2x"],
+        extensions: &["gpe"],
         romm_aliases: &["gp2x"],
         dat_aliases: &[],
         ra_console_id: None,
         ss_id: None,
         libretro_dir: None,
         launchbox_name: None,
+        mister_folder: None,
     },
     PlatformDef {
         slug: "intertonvc4000",
         display_name: "Interton VC 4000",
         folder_aliases: &["intertonvc4000", "vc4000"],
+        extensions: &["bin"],
         romm_aliases: &["interton-vc-4000"],
         dat_aliases: &[],
         ra_console_id: None,
         ss_id: None,
         libretro_dir: None,
         launchbox_name: None,
+        mister_folder: Some("VC4000"),
     },
     PlatformDef {
         slug: "laseractive",
         display_name: "LaserActive",
         folder_aliases: &["laseractive"],
+        extensions: &["chd", "cue", "bin", "iso"],
         romm_aliases: &["laseractive"],
         dat_aliases: &[],
         ra_console_id: None,
         ss_id: None,
         libretro_dir: None,
         launchbox_name: None,
+        mister_folder: None,
     },
     PlatformDef {
         slug: "microvision",
         display_name: "Microvision",
         folder_aliases: &["microvision"],
+        extensions: &["bin"],
         romm_aliases: &["microvision"],
         dat_aliases: &[],
         ra_console_id: None,
         ss_id: None,
         libretro_dir: None,
         launchbox_name: None,
+        mister_folder: None,
     },
     PlatformDef {
         slug: "multivision",
         display_name: "Multivision",
         folder_aliases: &["multivision"],
+        extensions: &["sg", "bin"],
         romm_aliases: &["multivision"],
         dat_aliases: &[],
         ra_console_id: None,
         ss_id: None,
         libretro_dir: None,
         launchbox_name: None,
+        mister_folder: None,
     },
     PlatformDef {
         slug: "nuon",
         display_name: "Nuon",
         folder_aliases: &["nuon"],
+        extensions: &["cd", "iso"],
         romm_aliases: &["nuon"],
         dat_aliases: &[],
         ra_console_id: None,
         ss_id: None,
         libretro_dir: None,
         launchbox_name: None,
+        mister_folder: None,
     },
     PlatformDef {
         slug: "ouya",
         display_name: "Ouya",
         folder_aliases: &["ouya"],
+        extensions: &["apk"],
         romm_aliases: &["ouya"],
         dat_aliases: &[],
         ra_console_id: None,
         ss_id: None,
         libretro_dir: None,
         launchbox_name: None,
+        mister_folder: None,
     },
     PlatformDef {
         slug: "playdate",
         display_name: "Playdate",
         folder_aliases: &["playdate"],
+        extensions: &["pdx"],
         romm_aliases: &["playdate"],
         dat_aliases: &[],
         ra_console_id: None,
         ss_id: None,
         libretro_dir: None,
         launchbox_name: None,
+        mister_folder: None,
     },
     PlatformDef {
         slug: "playdia",
         display_name: "Playdia",
         folder_aliases: &["playdia"],
+        extensions: &["chd", "cue", "bin", "iso"],
         romm_aliases: &["playdia"],
         dat_aliases: &[],
         ra_console_id: None,
         ss_id: None,
         libretro_dir: None,
         launchbox_name: None,
+        mister_folder: None,
     },
     PlatformDef {
         slug: "pocketstation",
         display_name: "PocketStation",
         folder_aliases: &["pocketstation"],
+        extensions: &["gme", "mcr", "mcd"],
         romm_aliases: &["pocketstation"],
         dat_aliases: &[],
         ra_console_id: None,
         ss_id: None,
         libretro_dir: None,
         launchbox_name: None,
+        mister_folder: None,
     },
     PlatformDef {
         slug: "pokitto",
         display_name: "Pokitto",
         folder_aliases: &["pokitto"],
+        extensions: &["bin", "pop"],
         romm_aliases: &["pokitto"],
         dat_aliases: &[],
         ra_console_id: None,
         ss_id: None,
         libretro_dir: None,
         launchbox_name: None,
+        mister_folder: None,
     },
     PlatformDef {
         slug: "superacan",
         display_name: "Super A'Can",
         folder_aliases: &["superacan"],
+        extensions: &["bin"],
         romm_aliases: &["super-acan"],
         dat_aliases: &[],
         ra_console_id: None,
         ss_id: None,
         libretro_dir: None,
         launchbox_name: None,
+        mister_folder: None,
     },
     PlatformDef {
         slug: "swancrystal",
         display_name: "SwanCrystal",
         folder_aliases: &["swancrystal"],
+        extensions: &["wsc"],
         romm_aliases: &["swancrystal"],
         dat_aliases: &[],
         ra_console_id: None,
         ss_id: None,
         libretro_dir: None,
         launchbox_name: None,
+        mister_folder: None,
     },
     PlatformDef {
         slug: "uzebox",
         display_name: "Uzebox",
         folder_aliases: &["uzebox"],
+        extensions: &["uze"],
         romm_aliases: &["uzebox"],
         dat_aliases: &[],
         ra_console_id: None,
         ss_id: None,
         libretro_dir: None,
         launchbox_name: None,
+        mister_folder: None,
     },
     PlatformDef {
         slug: "vsmile",
         display_name: "V.Smile",
         folder_aliases: &["vsmile"],
+        extensions: &["bin"],
         romm_aliases: &["vsmile"],
         dat_aliases: &[],
         ra_console_id: None,
         ss_id: None,
         libretro_dir: None,
         launchbox_name: None,
+        mister_folder: None,
     },
     PlatformDef {
         slug: "videopacg7400",
         display_name: "Videopac G7400",
         folder_aliases: &["videopacg7400", "g7400"],
+        extensions: &["bin"],
         romm_aliases: &["videopac-g7400"],
         dat_aliases: &[],
         ra_console_id: None,
         ss_id: None,
         libretro_dir: None,
         launchbox_name: None,
+        mister_folder: None,
     },
     PlatformDef {
         slug: "wasm4",
         display_name: "WASM-4",
         folder_aliases: &["wasm4", "wasm-4"],
+        extensions: &["wasm"],
         romm_aliases: &["wasm-4"],
         dat_aliases: &[],
         ra_console_id: None,
         ss_id: None,
         libretro_dir: None,
         launchbox_name: None,
+        mister_folder: None,
     },
     PlatformDef {
         slug: "xavixport",
         display_name: "XaviXPORT",
         folder_aliases: &["xavixport"],
+        extensions: &["bin"],
         romm_aliases: &["xavixport"],
         dat_aliases: &[],
         ra_console_id: None,
         ss_id: None,
         libretro_dir: None,
         launchbox_name: None,
+        mister_folder: None,
     },
 ];
 
@@ -1995,6 +2342,10 @@ static SS_MAP: LazyLock<HashMap<&'static str, u32>> = LazyLock::new(|| {
         .collect()
 });
 
+/// Canonical slug → ROM file extensions.
+static EXTENSIONS_MAP: LazyLock<HashMap<&'static str, &'static [&'static str]>> =
+    LazyLock::new(|| PLATFORMS.iter().map(|p| (p.slug, p.extensions)).collect());
+
 /// Canonical slug → libretro thumbnail directory name.
 static LIBRETRO_MAP: LazyLock<HashMap<&'static str, &'static str>> = LazyLock::new(|| {
     PLATFORMS
@@ -2011,6 +2362,97 @@ static LAUNCHBOX_MAP: LazyLock<HashMap<&'static str, &'static str>> = LazyLock::
         .collect()
 });
 
+/// Canonical slug → MiSTer games folder.
+static MISTER_MAP: LazyLock<HashMap<&'static str, &'static str>> = LazyLock::new(|| {
+    PLATFORMS
+        .iter()
+        .filter_map(|p| p.mister_folder.map(|f| (p.slug, f)))
+        .collect()
+});
+
+/// Locale → (slug → display name), shipped with the app. Only names that
+/// differ from the English `display_name` are listed.
+static LOCALIZED_NAMES: LazyLock<HashMap<String, HashMap<String, String>>> = LazyLock::new(|| {
+    serde_json::from_str(include_str!("platform_names.json")).unwrap_or_else(|e| {
+        log::warn!("Failed to parse bundled platform names: {e}");
+        HashMap::new()
+    })
+});
+
+/// Canonical slug → libretro cores to recommend, best first. Core names are
+/// buildbot file stems as returned by `detect_cores`.
+const RECOMMENDED_CORES: &[(&str, &[&str])] = &[
+    // Nintendo
+    ("gb", &["gambatte_libretro", "sameboy_libretro"]),
+    ("gbc", &["gambatte_libretro", "sameboy_libretro"]),
+    ("gba", &["mgba_libretro", "vbam_libretro"]),
+    ("nes", &["mesen_libretro", "fceumm_libretro", "nestopia_libretro"]),
+    ("fds", &["mesen_libretro", "fceumm_libretro"]),
+    ("snes", &["snes9x_libretro", "bsnes_libretro"]),
+    ("n64", &["mupen64plus_next_libretro", "parallel_n64_libretro"]),
+    ("nds", &["melondsds_libretro", "melonds_libretro", "desmume_libretro"]),
+    ("gamecube", &["dolphin_libretro"]),
+    ("wii", &["dolphin_libretro"]),
+    ("vb", &["mednafen_vb_libretro"]),
+    ("pokemini", &["pokemini_libretro"]),
+    // Sony
+    ("psx", &["swanstation_libretro", "mednafen_psx_hw_libretro", "pcsx_rearmed_libretro"]),
+    ("psp", &["ppsspp_libretro"]),
+    // Sega
+    ("genesis", &["genesis_plus_gx_libretro", "picodrive_libretro"]),
+    ("segacd", &["genesis_plus_gx_libretro", "picodrive_libretro"]),
+    ("sega32", &["picodrive_libretro"]),
+    ("mastersystem", &["genesis_plus_gx_libretro", "gearsystem_libretro"]),
+    ("gamegear", &["genesis_plus_gx_libretro", "gearsystem_libretro"]),
+    ("sg1000", &["genesis_plus_gx_libretro", "gearsystem_libretro"]),
+    ("saturn", &["mednafen_saturn_libretro", "yabasanshiro_libretro"]),
+    ("dreamcast", &["flycast_libretro"]),
+    // Arcade / SNK
+    ("arcade", &["fbneo_libretro", "mame2003_plus_libretro"]),
+    ("neogeo", &["fbneo_libretro"]),
+    ("cps1", &["fbneo_libretro"]),
+    ("cps2", &["fbneo_libretro"]),
+    ("cps3", &["fbneo_libretro"]),
+    ("neocd", &["neocd_libretro"]),
+    ("ngp", &["mednafen_ngp_libretro"]),
+    ("ngpc", &["mednafen_ngp_libretro"]),
+    // NEC
+    ("pce", &["mednafen_pce_fast_libretro", "mednafen_pce_libretro"]),
+    ("pcecd", &["mednafen_pce_fast_libretro", "mednafen_pce_libretro"]),
+    ("sgfx", &["mednafen_supergrafx_libretro"]),
+    ("pcfx", &["mednafen_pcfx_libretro"]),
+    // Atari
+    ("atari2600", &["stella_libretro"]),
+    ("atari5200", &["a5200_libretro"]),
+    ("atari7800", &["prosystem_libretro"]),
+    ("lynx", &["handy_libretro", "mednafen_lynx_libretro"]),
+    ("jaguar", &["virtualjaguar_libretro"]),
+    ("atarist", &["hatari_libretro"]),
+    // Other consoles and handhelds
+    ("ws", &["mednafen_wswan_libretro"]),
+    ("wsc", &["mednafen_wswan_libretro"]),
+    ("colecovision", &["gearcoleco_libretro", "bluemsx_libretro"]),
+    ("intellivision", &["freeintv_libretro"]),
+    ("vectrex", &["vecx_libretro"]),
+    ("channelf", &["freechaf_libretro"]),
+    ("odyssey2", &["o2em_libretro"]),
+    ("3do", &["opera_libretro"]),
+    ("megaduck", &["sameduck_libretro"]),
+    ("supervision", &["potator_libretro"]),
+    // Computers
+    ("dos", &["dosbox_pure_libretro"]),
+    ("scummvm", &["scummvm_libretro"]),
+    ("c64", &["vice_x64sc_libretro", "vice_x64_libretro"]),
+    ("amiga", &["puae_libretro"]),
+    ("msx", &["bluemsx_libretro", "fmsx_libretro"]),
+    ("msx2", &["bluemsx_libretro", "fmsx_libretro"]),
+    ("zxspectrum", &["fuse_libretro"]),
+    ("cpc", &["cap32_libretro"]),
+    ("x68000", &["px68k_libretro"]),
+    ("pc98", &["np2kai_libretro"]),
+    ("tic80", &["tic80_libretro"]),
+];
+
 // ── Public convenience functions ──
 
 /// Resolve a folder name to a canonical platform slug.
@@ -2054,6 +2496,14 @@ pub fn ss_id(slug: &str) -> Option<u32> {
     SS_MAP.get(slug).copied()
 }
 
+/// Archive formats ROMs of any platform may be packed in.
+pub const ARCHIVE_EXTENSIONS: &[&str] = &["zip", "7z"];
+
+/// Get the ROM file extensions of a canonical platform slug, without archives.
+pub fn extensions(slug: &str) -> &'static [&'static str] {
+    EXTENSIONS_MAP.get(slug).copied().unwrap_or_default()
+}
+
 /// Get the libretro thumbnail directory name for a canonical platform slug.
 pub fn libretro_dir(slug: &str) -> Option<&'static str> {
     LIBRETRO_MAP.get(slug).copied()
@@ -2063,3 +2513,35 @@ pub fn libretro_dir(slug: &str) -> Option<&'static str> {
 pub fn launchbox_name(slug: &str) -> Option<&'static str> {
     LAUNCHBOX_MAP.get(slug).copied()
 }
+
+/// Get the MiSTer games folder for a canonical platform slug.
+pub fn mister_folder(slug: &str) -> Option<&'static str> {
+    MISTER_MAP.get(slug).copied()
+}
+
+/// Get the shipped display name for a platform in the given locale (e.g. `ja`,
+/// `pt-BR`). Falls back from a regional locale to its base language.
+pub fn localized_display_name(slug: &str, locale: &str) -> Option<&'static str> {
+    let locale = locale.to_lowercase().replace('_', "-");
+    let base = locale.split('-').next().unwrap_or_default();
+    [locale.as_str(), base]
+        .into_iter()
+        .find_map(|l| LOCALIZED_NAMES.get(l).and_then(|names| names.get(slug)))
+        .map(String::as_str)
+}
+
+/// Recommended libretro cores for a canonical platform slug, best first.
+pub fn recommended_cores(slug: &str) -> &'static [&'static str] {
+    RECOMMENDED_CORES
+        .iter()
+        .find(|(s, _)| *s == slug)
+        .map(|&(_, cores)| cores)
+        .unwrap_or_default()
+}
+
+/// Whether `core_name` is one of the cores recommended for any platform.
+pub fn is_recommended_core(core_name: &str) -> bool {
+    RECOMMENDED_CORES
+        .iter()
+        .any(|(_, cores)| cores.contains(&core_name))
+}
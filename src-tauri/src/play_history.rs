@@ -0,0 +1,216 @@
+use std::path::{Path, PathBuf};
+
+use sea_orm::{ConnectionTrait, DatabaseBackend, DatabaseConnection, FromQueryResult, Statement};
+use serde::Deserialize;
+
+use crate::error::{AppError, AppResult};
+use crate::models::HistoryImportReport;
+use crate::saves;
+
+/// One entry of a RetroArch JSON playlist (`.lpl`).
+#[derive(Debug, Deserialize)]
+struct PlaylistItem {
+    #[serde(default)]
+    path: String,
+    #[serde(default)]
+    core_name: String,
+    /// `"1234ABCD|crc"` once RetroArch has scanned the file, else `"DETECT"`.
+    #[serde(default)]
+    crc32: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct Playlist {
+    #[serde(default)]
+    items: Vec<PlaylistItem>,
+}
+
+/// Per-content runtime log (`playlists/logs/<core>/<content>.lrtl`).
+#[derive(Debug, Deserialize)]
+struct RuntimeLog {
+    /// Local time, `YYYY-MM-DD HH:MM:SS`.
+    last_played: Option<String>,
+}
+
+/// Locate RetroArch's content history playlist: `content_history_path` from
+/// retroarch.cfg, then the default locations of old and new RetroArch versions.
+pub fn find_content_history() -> Option<PathBuf> {
+    let cfg = saves::retroarch_config_path();
+    if let Some(configured) = cfg
+        .as_deref()
+        .and_then(|c| saves::read_retroarch_cfg_value(c, "content_history_path"))
+        .map(|v| PathBuf::from(saves::expand_tilde(&v)))
+        .filter(|p| p.is_file())
+    {
+        return Some(configured);
+    }
+
+    let ra_dir = dirs::home_dir()?.join("Library/Application Support/RetroArch");
+    [
+        playlist_dir().map(|d| d.join("builtin/content_history.lpl")),
+        Some(ra_dir.join("playlists/builtin/content_history.lpl")),
+        Some(ra_dir.join("content_history.lpl")),
+    ]
+    .into_iter()
+    .flatten()
+    .find(|p| p.is_file())
+}
+
+fn playlist_dir() -> Option<PathBuf> {
+    saves::retroarch_config_path()
+        .and_then(|cfg| saves::read_retroarch_cfg_value(&cfg, "playlist_directory"))
+        .filter(|v| v != "default")
+        .map(|v| PathBuf::from(saves::expand_tilde(&v)))
+        .or_else(|| {
+            dirs::home_dir().map(|h| h.join("Library/Application Support/RetroArch/playlists"))
+        })
+}
+
+/// Backfill `library.last_played_at`/`play_count` from a RetroArch history
+/// playlist. Entries are matched to ROMs by path (local sources), CRC32, then
+/// file name (cached ROMM downloads). Existing play data is only ever moved
+/// forward, so importing twice is harmless.
+pub async fn import_content_history(
+    db: &DatabaseConnection,
+    lpl_path: &Path,
+) -> AppResult<HistoryImportReport> {
+    let contents = tokio::fs::read_to_string(lpl_path).await?;
+    let playlist: Playlist = serde_json::from_str(&contents).map_err(|e| {
+        AppError::Other(format!(
+            "Unsupported playlist format (RetroArch 1.7.6+ JSON playlists only): {e}"
+        ))
+    })?;
+
+    // History is newest first but carries no timestamps. Entries without a
+    // runtime log get the playlist's mtime, minus a minute per position so
+    // the original order survives.
+    let modified: chrono::DateTime<chrono::Utc> = tokio::fs::metadata(lpl_path)
+        .await?
+        .modified()
+        .map_or_else(|_| chrono::Utc::now(), Into::into);
+    let logs_dir = playlist_dir().map(|d| d.join("logs"));
+
+    let mut report = HistoryImportReport {
+        entries: u32::try_from(playlist.items.len()).unwrap_or(u32::MAX),
+        ..HistoryImportReport::default()
+    };
+    let mut seen = std::collections::HashSet::new();
+
+    for (position, item) in playlist.items.iter().enumerate() {
+        let Some((rom_id, source_id)) = match_rom(db, item).await? else {
+            continue;
+        };
+        // Only the newest entry for a ROM matters
+        if !seen.insert(rom_id) {
+            continue;
+        }
+        report.matched += 1;
+
+        let last_played = logs_dir
+            .as_deref()
+            .and_then(|dir| runtime_last_played(dir, item))
+            .unwrap_or_else(|| {
+                modified - chrono::Duration::minutes(i64::try_from(position).unwrap_or(0))
+            })
+            .format("%Y-%m-%dT%H:%M:%S%.3fZ")
+            .to_string();
+
+        let result = db
+            .execute(Statement::from_sql_and_values(
+                DatabaseBackend::Sqlite,
+                "INSERT INTO library (profile_id, rom_id, source_id, play_count, last_played_at)
+                 VALUES ((SELECT id FROM current_profile), ?, ?, 1, ?)
+                 ON CONFLICT(profile_id, rom_id, source_id) DO UPDATE SET
+                    play_count = MAX(play_count, 1),
+                    last_played_at = excluded.last_played_at,
+                    updated_at = strftime('%Y-%m-%dT%H:%M:%fZ', 'now')
+                 WHERE last_played_at IS NULL OR last_played_at < excluded.last_played_at",
+                [rom_id.into(), source_id.into(), last_played.into()],
+            ))
+            .await?;
+        if result.rows_affected() > 0 {
+            report.updated += 1;
+        }
+    }
+
+    Ok(report)
+}
+
+/// Find the library ROM (and a source it belongs to) for a history entry.
+async fn match_rom(
+    db: &DatabaseConnection,
+    item: &PlaylistItem,
+) -> AppResult<Option<(i64, i64)>> {
+    #[derive(Debug, FromQueryResult)]
+    struct MatchRow {
+        rom_id: i64,
+        source_id: i64,
+    }
+
+    // Archive members are addressed as "/path/game.zip#game.sfc"
+    let path = item.path.split('#').next().unwrap_or_default();
+    if path.is_empty() {
+        return Ok(None);
+    }
+    let file_name = Path::new(path)
+        .file_name()
+        .map(|n| n.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    let crc = item
+        .crc32
+        .split_once('|')
+        .map(|(crc, _)| crc.to_uppercase())
+        .filter(|crc| crc.len() == 8 && crc != "00000000");
+
+    let candidates: [(&str, Option<String>); 3] = [
+        (
+            "SELECT rom_id, source_id FROM source_roms WHERE source_rom_id = ? LIMIT 1",
+            Some(path.to_string()),
+        ),
+        (
+            "SELECT r.id AS rom_id, sr.source_id FROM roms r
+             JOIN source_roms sr ON sr.rom_id = r.id
+             WHERE UPPER(r.hash_crc32) = ? LIMIT 1",
+            crc,
+        ),
+        (
+            "SELECT r.id AS rom_id, sr.source_id FROM roms r
+             JOIN source_roms sr ON sr.rom_id = r.id
+             WHERE r.file_name = ? LIMIT 1",
+            Some(file_name).filter(|n| !n.is_empty()),
+        ),
+    ];
+
+    for (sql, value) in candidates {
+        let Some(value) = value else { continue };
+        if let Some(row) = MatchRow::find_by_statement(Statement::from_sql_and_values(
+            DatabaseBackend::Sqlite,
+            sql,
+            [value.into()],
+        ))
+        .one(db)
+        .await?
+        {
+            return Ok(Some((row.rom_id, row.source_id)));
+        }
+    }
+    Ok(None)
+}
+
+/// `last_played` from the entry's runtime log, converted from local time.
+fn runtime_last_played(
+    logs_dir: &Path,
+    item: &PlaylistItem,
+) -> Option<chrono::DateTime<chrono::Utc>> {
+    let path = item.path.split('#').next_back()?;
+    let stem = Path::new(path).file_stem()?.to_string_lossy();
+    let log_path = logs_dir.join(&item.core_name).join(format!("{stem}.lrtl"));
+    let log: RuntimeLog = serde_json::from_str(&std::fs::read_to_string(log_path).ok()?).ok()?;
+    let naive =
+        chrono::NaiveDateTime::parse_from_str(log.last_played.as_deref()?, "%Y-%m-%d %H:%M:%S")
+            .ok()?;
+    naive
+        .and_local_timezone(chrono::Local)
+        .earliest()
+        .map(|dt| dt.with_timezone(&chrono::Utc))
+}
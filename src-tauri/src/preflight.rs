@@ -0,0 +1,187 @@
+use std::path::{Path, PathBuf};
+
+use crate::models::{LaunchWarning, LaunchWarningKind};
+
+const ARCHIVE_EXTENSIONS: &[&str] = &["zip", "7z", "rar"];
+/// Archives RetroArch unpacks itself before handing the content to a core.
+const RETROARCH_EXTRACTS: &[&str] = &["zip", "7z"];
+
+/// What a libretro core's `.info` file says it can load.
+pub struct CoreSupport {
+    extensions: Vec<String>,
+    /// The core wants archives as they are instead of extracted by RetroArch.
+    block_extract: bool,
+}
+
+impl CoreSupport {
+    /// Parse `supported_extensions = "sfc|smc|zip"` and `block_extract` from a core `.info` file.
+    pub fn parse(info: &str) -> Self {
+        let mut extensions = Vec::new();
+        let mut block_extract = false;
+        for line in info.lines() {
+            let Some((key, value)) = line.split_once('=') else {
+                continue;
+            };
+            let value = value.trim().trim_matches('"');
+            match key.trim() {
+                "supported_extensions" => {
+                    extensions = value
+                        .split('|')
+                        .map(|e| e.trim().to_lowercase())
+                        .filter(|e| !e.is_empty())
+                        .collect();
+                }
+                "block_extract" => block_extract = value == "true",
+                _ => {}
+            }
+        }
+        Self {
+            extensions,
+            block_extract,
+        }
+    }
+
+    fn supports(&self, ext: &str) -> bool {
+        self.extensions.iter().any(|e| e == ext)
+    }
+}
+
+/// How the configured emulator opens content.
+pub enum Launcher {
+    /// `core` is None when the core's `.info` file isn't installed.
+    RetroArch { core: Option<CoreSupport> },
+    /// `archives` lists the archive formats the emulator reads; None for
+    /// custom emulators, which aren't checked.
+    Standalone {
+        name: String,
+        archives: Option<&'static [&'static str]>,
+    },
+}
+
+/// A launch about to happen.
+pub struct LaunchCheck {
+    pub file_name: String,
+    /// The local file or cached download, when it is already on disk.
+    pub rom_path: Option<PathBuf>,
+    /// Libretro core or standalone emulator binary.
+    pub emulator_path: PathBuf,
+    pub launcher: Launcher,
+    pub save_state: Option<PathBuf>,
+}
+
+fn lower_extension(name: &str) -> Option<String> {
+    Path::new(name)
+        .extension()
+        .and_then(|e| e.to_str())
+        .map(str::to_lowercase)
+}
+
+/// Extensions of the files inside a zip archive.
+fn zip_entry_extensions(path: &Path) -> Option<Vec<String>> {
+    let file = std::fs::File::open(path).ok()?;
+    let archive = zip::ZipArchive::new(file).ok()?;
+    Some(archive.file_names().filter_map(lower_extension).collect())
+}
+
+fn core_name(path: &Path) -> String {
+    path.file_stem().and_then(|s| s.to_str()).map_or_else(
+        || "The core".to_string(),
+        |s| s.trim_end_matches("_libretro").to_string(),
+    )
+}
+
+/// Check for archive formats, file types and save states the emulator is
+/// known to choke on. Reads the archive and file times, so call it from a
+/// blocking context.
+pub fn check(launch: &LaunchCheck) -> Vec<LaunchWarning> {
+    let mut warnings = Vec::new();
+    let warn = |warnings: &mut Vec<LaunchWarning>, kind, message: String| {
+        warnings.push(LaunchWarning { kind, message });
+    };
+
+    if let Some(ext) = lower_extension(&launch.file_name) {
+        let is_archive = ARCHIVE_EXTENSIONS.contains(&ext.as_str());
+        match &launch.launcher {
+            Launcher::RetroArch { core } => {
+                let core_name = core_name(&launch.emulator_path);
+                let core_takes_it = core.as_ref().is_some_and(|c| c.supports(&ext));
+                if is_archive && !core_takes_it {
+                    if !RETROARCH_EXTRACTS.contains(&ext.as_str()) {
+                        warn(
+                            &mut warnings,
+                            LaunchWarningKind::UnsupportedArchive,
+                            format!(
+                                "RetroArch can't open .{ext} archives. Extract the game first."
+                            ),
+                        );
+                    } else if core.as_ref().is_some_and(|c| c.block_extract) {
+                        warn(
+                            &mut warnings,
+                            LaunchWarningKind::UnsupportedArchive,
+                            format!("{core_name} loads archives itself and doesn't accept .{ext}."),
+                        );
+                    } else if let Some(core) = core {
+                        let inner = launch
+                            .rom_path
+                            .as_deref()
+                            .filter(|_| ext == "zip")
+                            .and_then(zip_entry_extensions);
+                        if let Some(inner) = inner.filter(|i| !i.is_empty()) {
+                            if !inner.iter().any(|e| core.supports(e)) {
+                                warn(
+                                    &mut warnings,
+                                    LaunchWarningKind::UnsupportedExtension,
+                                    format!(
+                                        "{core_name} can't load any file in the archive ({}).",
+                                        inner.join(", ")
+                                    ),
+                                );
+                            }
+                        }
+                    }
+                } else if let Some(core) = core.as_ref().filter(|c| !c.extensions.is_empty()) {
+                    if !is_archive && !core.supports(&ext) {
+                        warn(
+                            &mut warnings,
+                            LaunchWarningKind::UnsupportedExtension,
+                            format!(
+                                "{core_name} doesn't support .{ext} files (supports {}).",
+                                core.extensions.join(", ")
+                            ),
+                        );
+                    }
+                }
+            }
+            Launcher::Standalone { name, archives } => {
+                if is_archive && archives.is_some_and(|a| !a.contains(&ext.as_str())) {
+                    warn(
+                        &mut warnings,
+                        LaunchWarningKind::UnsupportedArchive,
+                        format!("{name} can't open .{ext} archives. Extract the game first."),
+                    );
+                }
+            }
+        }
+    }
+
+    if let Some(state) = &launch.save_state {
+        let modified = |p: &Path| std::fs::metadata(p).and_then(|m| m.modified()).ok();
+        if let (Some(state_time), Some(emulator_time)) =
+            (modified(state), modified(&launch.emulator_path))
+        {
+            if emulator_time > state_time {
+                let updated: chrono::DateTime<chrono::Utc> = emulator_time.into();
+                warn(
+                    &mut warnings,
+                    LaunchWarningKind::SaveStateOutdated,
+                    format!(
+                        "The save state predates the emulator update on {} and may not load.",
+                        updated.format("%Y-%m-%d")
+                    ),
+                );
+            }
+        }
+    }
+
+    warnings
+}
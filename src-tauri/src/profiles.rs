@@ -0,0 +1,272 @@
+use sea_orm::{
+    ConnectionTrait, DatabaseBackend, DatabaseConnection, FromQueryResult, Statement,
+    TransactionTrait,
+};
+use sha2::{Digest, Sha256};
+
+use crate::error::{AppError, AppResult};
+use crate::models::Profile;
+
+/// The profile existing single-user data was moved into. It can't be deleted.
+pub const DEFAULT_PROFILE_ID: i64 = 1;
+
+/// Settings-table key holding the active profile. Queries read it through the
+/// `current_profile` and `profile_library` views.
+const CURRENT_PROFILE_KEY: &str = "current_profile";
+
+/// Settings-table key holding the parent PIN as `<salt>:<SHA-256 of salt and PIN>`.
+const PARENT_PIN_KEY: &str = "parent_pin";
+
+#[derive(Debug, FromQueryResult)]
+struct ProfileRow {
+    id: i64,
+    name: String,
+    is_current: bool,
+    created_at: String,
+    max_age: Option<i64>,
+    allow_unrated: bool,
+}
+
+impl From<ProfileRow> for Profile {
+    fn from(row: ProfileRow) -> Self {
+        Self {
+            id: row.id,
+            name: row.name,
+            is_current: row.is_current,
+            created_at: row.created_at,
+            max_age: row.max_age,
+            allow_unrated: row.allow_unrated,
+        }
+    }
+}
+
+pub async fn list(db: &DatabaseConnection) -> AppResult<Vec<Profile>> {
+    Ok(ProfileRow::find_by_statement(Statement::from_string(
+        DatabaseBackend::Sqlite,
+        "SELECT p.id, p.name, p.id = (SELECT id FROM current_profile) AS is_current,
+                p.created_at, p.max_age, p.allow_unrated
+         FROM profiles p
+         ORDER BY p.id",
+    ))
+    .all(db)
+    .await?
+    .into_iter()
+    .map(Into::into)
+    .collect())
+}
+
+async fn find(db: &impl ConnectionTrait, id: i64) -> AppResult<Profile> {
+    ProfileRow::find_by_statement(Statement::from_sql_and_values(
+        DatabaseBackend::Sqlite,
+        "SELECT p.id, p.name, p.id = (SELECT id FROM current_profile) AS is_current,
+                p.created_at, p.max_age, p.allow_unrated
+         FROM profiles p
+         WHERE p.id = ?",
+        [id.into()],
+    ))
+    .one(db)
+    .await?
+    .map(Into::into)
+    .ok_or_else(|| AppError::NotFound(format!("Profile {id}")))
+}
+
+pub async fn create(db: &DatabaseConnection, name: &str) -> AppResult<Profile> {
+    let name = name.trim();
+    if name.is_empty() {
+        return Err(AppError::Other("Profile name is required".to_string()));
+    }
+    let taken = db
+        .query_one(Statement::from_sql_and_values(
+            DatabaseBackend::Sqlite,
+            "SELECT 1 FROM profiles WHERE name = ?",
+            [name.into()],
+        ))
+        .await?
+        .is_some();
+    if taken {
+        return Err(AppError::Other(format!(
+            "A profile named {name} already exists"
+        )));
+    }
+    let id = db
+        .execute(Statement::from_sql_and_values(
+            DatabaseBackend::Sqlite,
+            "INSERT INTO profiles (name) VALUES (?)",
+            [name.into()],
+        ))
+        .await?
+        .last_insert_id();
+    let id = i64::try_from(id).map_err(|_| AppError::Other("Invalid profile id".to_string()))?;
+    find(db, id).await
+}
+
+async fn set_current(db: &impl ConnectionTrait, id: i64) -> AppResult<()> {
+    db.execute(Statement::from_sql_and_values(
+        DatabaseBackend::Sqlite,
+        "INSERT INTO settings (key, value) VALUES (?, ?)
+         ON CONFLICT(key) DO UPDATE SET
+            value = excluded.value,
+            updated_at = strftime('%Y-%m-%dT%H:%M:%fZ', 'now')",
+        [CURRENT_PROFILE_KEY.into(), id.to_string().into()],
+    ))
+    .await?;
+    Ok(())
+}
+
+/// Whether the active profile has a content filter.
+async fn current_is_filtered(db: &impl ConnectionTrait) -> AppResult<bool> {
+    Ok(db
+        .query_one(Statement::from_string(
+            DatabaseBackend::Sqlite,
+            "SELECT 1 FROM profiles
+             WHERE id = (SELECT id FROM current_profile) AND max_age IS NOT NULL",
+        ))
+        .await?
+        .is_some())
+}
+
+async fn stored_pin(db: &impl ConnectionTrait) -> AppResult<Option<String>> {
+    Ok(db
+        .query_one(Statement::from_sql_and_values(
+            DatabaseBackend::Sqlite,
+            "SELECT value FROM settings WHERE key = ?",
+            [PARENT_PIN_KEY.into()],
+        ))
+        .await?
+        .and_then(|row| row.try_get::<String>("", "value").ok()))
+}
+
+fn hash_pin(salt: &str, pin: &str) -> String {
+    format!("{:x}", Sha256::digest(format!("{salt}:{pin}").as_bytes()))
+}
+
+/// Fail unless `pin` is the parent PIN. Without one set there's nothing to check.
+async fn require_pin(db: &impl ConnectionTrait, pin: Option<&str>) -> AppResult<()> {
+    let Some(stored) = stored_pin(db).await? else {
+        return Ok(());
+    };
+    let (salt, hash) = stored.split_once(':').unwrap_or_default();
+    if pin.is_some_and(|pin| hash_pin(salt, pin) == hash) {
+        Ok(())
+    } else {
+        Err(AppError::Auth("Wrong parent PIN".to_string()))
+    }
+}
+
+pub async fn has_parent_pin(db: &DatabaseConnection) -> AppResult<bool> {
+    Ok(stored_pin(db).await?.is_some())
+}
+
+/// Set, change or (with `new_pin` of None) remove the parent PIN, which
+/// guards content filters. Changing or removing it takes the `current_pin`;
+/// the first one can't be set from a filtered profile.
+pub async fn set_parent_pin(
+    db: &DatabaseConnection,
+    current_pin: Option<&str>,
+    new_pin: Option<&str>,
+) -> AppResult<()> {
+    if stored_pin(db).await?.is_some() {
+        require_pin(db, current_pin).await?;
+    } else if current_is_filtered(db).await? {
+        return Err(AppError::Other(
+            "Switch to a profile without a content filter to set a parent PIN".to_string(),
+        ));
+    }
+    let Some(pin) = new_pin else {
+        db.execute(Statement::from_sql_and_values(
+            DatabaseBackend::Sqlite,
+            "DELETE FROM settings WHERE key = ?",
+            [PARENT_PIN_KEY.into()],
+        ))
+        .await?;
+        return Ok(());
+    };
+    if pin.len() < 4 || !pin.bytes().all(|b| b.is_ascii_digit()) {
+        return Err(AppError::Other(
+            "The parent PIN must be at least 4 digits".to_string(),
+        ));
+    }
+    let salt = uuid::Uuid::new_v4().simple().to_string();
+    db.execute(Statement::from_sql_and_values(
+        DatabaseBackend::Sqlite,
+        "INSERT INTO settings (key, value) VALUES (?, ?)
+         ON CONFLICT(key) DO UPDATE SET
+            value = excluded.value,
+            updated_at = strftime('%Y-%m-%dT%H:%M:%fZ', 'now')",
+        [
+            PARENT_PIN_KEY.into(),
+            format!("{salt}:{}", hash_pin(&salt, pin)).into(),
+        ],
+    ))
+    .await?;
+    Ok(())
+}
+
+/// Make `id` the active profile; favorites, play stats, statuses and local
+/// collections read and write its rows from now on. Leaving a profile with a
+/// content filter takes the parent PIN.
+pub async fn switch(db: &DatabaseConnection, id: i64, pin: Option<&str>) -> AppResult<Profile> {
+    let target = find(db, id).await?;
+    if !target.is_current && current_is_filtered(db).await? {
+        require_pin(db, pin).await?;
+    }
+    set_current(db, id).await?;
+    find(db, id).await
+}
+
+/// Delete a profile and everything scoped to it. Deleting the active profile
+/// switches back to the default one. Takes the parent PIN while the active
+/// profile has a content filter.
+pub async fn delete(db: &DatabaseConnection, id: i64, pin: Option<&str>) -> AppResult<()> {
+    if id == DEFAULT_PROFILE_ID {
+        return Err(AppError::Other(
+            "The default profile can't be deleted".to_string(),
+        ));
+    }
+    if current_is_filtered(db).await? {
+        require_pin(db, pin).await?;
+    }
+    let txn = db.begin().await?;
+    if find(&txn, id).await?.is_current {
+        set_current(&txn, DEFAULT_PROFILE_ID).await?;
+    }
+    // library rows and local collections go with it (ON DELETE CASCADE)
+    txn.execute(Statement::from_sql_and_values(
+        DatabaseBackend::Sqlite,
+        "DELETE FROM profiles WHERE id = ?",
+        [id.into()],
+    ))
+    .await?;
+    txn.commit().await?;
+    Ok(())
+}
+
+/// Set a profile's content filter. `max_age` of None shows everything.
+/// Takes the parent PIN, which has to be set before any filter is.
+pub async fn set_content_filter(
+    db: &DatabaseConnection,
+    id: i64,
+    max_age: Option<i64>,
+    allow_unrated: bool,
+    pin: Option<&str>,
+) -> AppResult<Profile> {
+    if max_age.is_some_and(|a| !(0..=18).contains(&a)) {
+        return Err(AppError::Other(
+            "Maximum age must be between 0 and 18".to_string(),
+        ));
+    }
+    if max_age.is_some() && stored_pin(db).await?.is_none() {
+        return Err(AppError::Other(
+            "Set a parent PIN before adding a content filter".to_string(),
+        ));
+    }
+    require_pin(db, pin).await?;
+    find(db, id).await?;
+    db.execute(Statement::from_sql_and_values(
+        DatabaseBackend::Sqlite,
+        "UPDATE profiles SET max_age = ?, allow_unrated = ? WHERE id = ?",
+        [max_age.into(), allow_unrated.into(), id.into()],
+    ))
+    .await?;
+    find(db, id).await
+}
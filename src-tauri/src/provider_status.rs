@@ -0,0 +1,236 @@
+use std::future::Future;
+use std::time::{Duration, Instant};
+
+use sea_orm::{ColumnTrait, DatabaseConnection, EntityTrait, QueryFilter};
+
+use crate::commands::{read_igdb_client_from_store, read_ss_creds_from_store};
+use crate::entity::sources::{self, SourceType};
+use crate::error::{AppError, AppResult};
+use crate::models::{ProviderStatus, StatusProvider};
+use crate::settings::SettingsRead;
+use crate::sources::romm::RommClient;
+
+/// How long a service gets to answer before it's reported unreachable.
+const PING_TIMEOUT: Duration = Duration::from_secs(15);
+
+/// Requests left and the allowance, where a service reports them.
+#[derive(Debug, Default)]
+struct Limits {
+    remaining: Option<u64>,
+    limit: Option<u64>,
+}
+
+impl ProviderStatus {
+    fn new(provider: StatusProvider, name: &str) -> Self {
+        Self {
+            provider,
+            name: name.to_string(),
+            source_id: None,
+            configured: true,
+            reachable: false,
+            authenticated: None,
+            requests_remaining: None,
+            requests_limit: None,
+            latency_ms: None,
+            message: None,
+        }
+    }
+
+    fn unconfigured(provider: StatusProvider, name: &str) -> Self {
+        Self {
+            configured: false,
+            message: Some("Not configured".to_string()),
+            ..Self::new(provider, name)
+        }
+    }
+}
+
+/// Run `check` and fill in `status` from how it went. With `has_credentials`
+/// a success also means they were accepted.
+async fn ping(
+    mut status: ProviderStatus,
+    has_credentials: bool,
+    check: impl Future<Output = AppResult<Limits>>,
+) -> ProviderStatus {
+    let started = Instant::now();
+    let result = tokio::time::timeout(PING_TIMEOUT, check)
+        .await
+        .unwrap_or_else(|_| Err(AppError::Other("Timed out".to_string())));
+    status.latency_ms = u64::try_from(started.elapsed().as_millis()).ok();
+
+    match result {
+        Ok(limits) => {
+            status.reachable = true;
+            status.authenticated = has_credentials.then_some(true);
+            status.requests_remaining = limits.remaining;
+            status.requests_limit = limits.limit;
+        }
+        Err(AppError::Auth(message)) => {
+            status.reachable = true;
+            status.authenticated = Some(false);
+            status.message = Some(message);
+        }
+        Err(e @ AppError::RateLimited { .. }) => {
+            status.reachable = true;
+            status.requests_remaining = Some(0);
+            status.message = Some(e.to_string());
+        }
+        Err(e) => {
+            status.latency_ms = None;
+            status.message = Some(e.to_string());
+        }
+    }
+    status
+}
+
+/// `X-RateLimit-*` headers, which some services send with every response.
+fn header_limits(resp: &reqwest::Response) -> Limits {
+    let header = |name: &str| -> Option<u64> {
+        resp.headers().get(name)?.to_str().ok()?.trim().parse().ok()
+    };
+    Limits {
+        remaining: header("x-ratelimit-remaining"),
+        limit: header("x-ratelimit-limit"),
+    }
+}
+
+/// A service that needs no credentials is up if it answers at all; only
+/// server errors and rate limiting count against it.
+async fn ping_url(client: &reqwest::Client, service: &str, url: &str) -> AppResult<Limits> {
+    crate::http::ensure_online(service)?;
+    let resp = client.head(url).send().await?;
+    let status = resp.status();
+    if status.is_server_error() || status == reqwest::StatusCode::TOO_MANY_REQUESTS {
+        return Err(crate::http::status_error(service, &resp));
+    }
+    Ok(header_limits(&resp))
+}
+
+async fn romm_statuses(db: &DatabaseConnection) -> AppResult<Vec<ProviderStatus>> {
+    let romm_sources = sources::Entity::find()
+        .filter(sources::Column::SourceType.eq(SourceType::Romm))
+        .all(db)
+        .await?;
+    if romm_sources.is_empty() {
+        return Ok(vec![ProviderStatus::unconfigured(
+            StatusProvider::Romm,
+            "ROMM",
+        )]);
+    }
+
+    let checks = romm_sources.into_iter().map(|source| {
+        let status = ProviderStatus {
+            source_id: Some(source.id),
+            ..ProviderStatus::new(StatusProvider::Romm, &source.name)
+        };
+        async move {
+            let Some(url) = source.url.filter(|u| !u.is_empty()) else {
+                return ProviderStatus {
+                    configured: false,
+                    message: Some("No server URL".to_string()),
+                    ..status
+                };
+            };
+            let client = RommClient::from_source(url, &source.credentials);
+            ping(status, true, async {
+                client.check_auth().await?;
+                Ok(Limits::default())
+            })
+            .await
+        }
+    });
+    Ok(futures_util::future::join_all(checks).await)
+}
+
+/// Check every service the app talks to at once, with the lightest request
+/// each one offers: a login or token check where credentials are involved,
+/// a HEAD request otherwise. Each ROMM source gets its own entry.
+pub async fn check_all(
+    app: &impl SettingsRead,
+    db: &DatabaseConnection,
+) -> AppResult<Vec<ProviderStatus>> {
+    let client = crate::http::client_builder()
+        .user_agent("romm-buddy/0.1")
+        .timeout(PING_TIMEOUT)
+        .build()?;
+
+    let igdb = async {
+        let status = ProviderStatus::new(StatusProvider::Igdb, "IGDB");
+        match read_igdb_client_from_store(app) {
+            Some(igdb) => {
+                ping(status, true, async {
+                    igdb.check_auth().await?;
+                    Ok(Limits::default())
+                })
+                .await
+            }
+            None => ProviderStatus::unconfigured(StatusProvider::Igdb, "IGDB"),
+        }
+    };
+
+    let screenscraper = async {
+        let status = ProviderStatus::new(StatusProvider::Screenscraper, "ScreenScraper");
+        match read_ss_creds_from_store(app) {
+            Some(creds) => {
+                ping(status, true, async {
+                    let quota = crate::metadata::screenscraper::user_quota(&client, &creds).await?;
+                    Ok(Limits {
+                        remaining: quota
+                            .max_requests_per_day
+                            .map(|max| max.saturating_sub(quota.requests_today)),
+                        limit: quota.max_requests_per_day,
+                    })
+                })
+                .await
+            }
+            None => ProviderStatus::unconfigured(StatusProvider::Screenscraper, "ScreenScraper"),
+        }
+    };
+
+    let retroachievements = async {
+        let status = ProviderStatus::new(StatusProvider::Retroachievements, "RetroAchievements");
+        let setting = |key: &str| {
+            app.setting(key)
+                .and_then(|v| v.as_str().map(str::to_string))
+                .filter(|s| !s.is_empty())
+        };
+        match (
+            setting("retroachievements_username"),
+            setting("retroachievements_api_key"),
+        ) {
+            (Some(username), Some(api_key)) => {
+                ping(status, true, async {
+                    crate::retroachievements::check_auth(&client, &username, &api_key).await?;
+                    Ok(Limits::default())
+                })
+                .await
+            }
+            _ => {
+                ProviderStatus::unconfigured(StatusProvider::Retroachievements, "RetroAchievements")
+            }
+        }
+    };
+
+    let hasheous = ping(
+        ProviderStatus::new(StatusProvider::Hasheous, "Hasheous"),
+        false,
+        ping_url(&client, "Hasheous", "https://hasheous.org/"),
+    );
+    let launchbox = ping(
+        ProviderStatus::new(StatusProvider::LaunchboxImages, "LaunchBox images"),
+        false,
+        ping_url(&client, "LaunchBox", "https://images.launchbox-app.com/"),
+    );
+
+    let (romm, igdb, screenscraper, retroachievements, hasheous, launchbox) = tokio::join!(
+        romm_statuses(db),
+        igdb,
+        screenscraper,
+        retroachievements,
+        hasheous,
+        launchbox,
+    );
+    let mut statuses = romm?;
+    statuses.extend([igdb, screenscraper, retroachievements, hasheous, launchbox]);
+    Ok(statuses)
+}
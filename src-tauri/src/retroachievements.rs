@@ -1,4 +1,5 @@
 use reqwest::Client;
+use sea_orm::DatabaseConnection;
 use serde_json::Value;
 
 use crate::error::{AppError, AppResult};
@@ -138,43 +139,121 @@ pub async fn fetch_game_achievements(
     })
 }
 
-pub async fn test_connection(client: &Client, username: &str, api_key: &str) -> RaTestResult {
+/// Check a username and API key against RA, failing with [`AppError::Auth`]
+/// when RA doesn't accept them.
+pub async fn check_auth(client: &Client, username: &str, api_key: &str) -> AppResult<()> {
+    crate::http::ensure_online("RetroAchievements")?;
     let url = format!(
         "{RA_API_BASE}/API_GetUserSummary.php?z={username}&y={api_key}&u={username}",
     );
 
-    match client.get(&url).send().await {
-        Ok(resp) => {
-            if resp.status().is_success() {
-                match resp.json::<Value>().await {
-                    Ok(body) => {
-                        if body.get("UserPic").is_some() {
-                            RaTestResult {
-                                success: true,
-                                message: format!("Connected as {username}"),
-                            }
-                        } else {
-                            RaTestResult {
-                                success: false,
-                                message: "Invalid API key or username".to_string(),
-                            }
-                        }
-                    }
-                    Err(_) => RaTestResult {
-                        success: false,
-                        message: "Invalid response from RetroAchievements API".to_string(),
-                    },
-                }
-            } else {
-                RaTestResult {
-                    success: false,
-                    message: format!("API returned status {}", resp.status()),
-                }
-            }
-        }
-        Err(e) => RaTestResult {
+    let resp = client.get(&url).send().await?;
+    if !resp.status().is_success() {
+        return Err(crate::http::status_error("RetroAchievements", &resp));
+    }
+    let body: Value = resp
+        .json()
+        .await
+        .map_err(|_| AppError::Other("Invalid response from RetroAchievements API".to_string()))?;
+    if body.get("UserPic").is_none() {
+        return Err(AppError::Auth("Invalid API key or username".to_string()));
+    }
+    Ok(())
+}
+
+pub async fn test_connection(client: &Client, username: &str, api_key: &str) -> RaTestResult {
+    match check_auth(client, username, api_key).await {
+        Ok(()) => RaTestResult {
+            success: true,
+            message: format!("Connected as {username}"),
+        },
+        Err(AppError::Network(e)) => RaTestResult {
             success: false,
             message: format!("Connection failed: {e}"),
         },
+        Err(e) => RaTestResult {
+            success: false,
+            message: e.to_string(),
+        },
+    }
+}
+
+/// How long cached achievement data is served without hitting the RA API.
+pub const CACHE_TTL_HOURS: i64 = 12;
+
+/// Cached achievement data for a ROM, with whether it is still within the TTL.
+pub struct CachedAchievements {
+    pub ra_game_id: String,
+    pub data: AchievementData,
+    pub fresh: bool,
+}
+
+/// Load cached achievement data for a ROM, ignoring entries cached for another RA user.
+pub async fn get_cached(
+    db: &DatabaseConnection,
+    rom_id: i64,
+    username: &str,
+) -> Option<CachedAchievements> {
+    use crate::entity::achievements_cache::{self, Column};
+    use sea_orm::{ColumnTrait, EntityTrait, QueryFilter};
+
+    let row = achievements_cache::Entity::find()
+        .filter(Column::RomId.eq(rom_id))
+        .one(db)
+        .await
+        .ok()
+        .flatten()?;
+    if row.ra_username != username {
+        return None;
+    }
+    let data: AchievementData = match serde_json::from_str(&row.data) {
+        Ok(d) => d,
+        Err(e) => {
+            log::warn!("[RA] Discarding unreadable achievements cache for rom {rom_id}: {e}");
+            return None;
+        }
+    };
+    let fresh = chrono::DateTime::parse_from_rfc3339(&row.fetched_at)
+        .map(|t| chrono::Utc::now().signed_duration_since(t) < chrono::Duration::hours(CACHE_TTL_HOURS))
+        .unwrap_or(false);
+
+    Some(CachedAchievements {
+        ra_game_id: row.ra_game_id,
+        data,
+        fresh,
+    })
+}
+
+pub async fn save_to_cache(
+    db: &DatabaseConnection,
+    rom_id: i64,
+    ra_game_id: &str,
+    username: &str,
+    data: &AchievementData,
+) {
+    use sea_orm::{ConnectionTrait, DatabaseBackend, Statement};
+
+    let json = match serde_json::to_string(data) {
+        Ok(j) => j,
+        Err(e) => {
+            log::warn!("[RA] Failed to serialize achievements for rom {rom_id}: {e}");
+            return;
+        }
+    };
+    if let Err(e) = db
+        .execute(Statement::from_sql_and_values(
+            DatabaseBackend::Sqlite,
+            "INSERT INTO achievements_cache (rom_id, ra_game_id, ra_username, data)
+             VALUES (?, ?, ?, ?)
+             ON CONFLICT(rom_id) DO UPDATE SET
+               ra_game_id = excluded.ra_game_id,
+               ra_username = excluded.ra_username,
+               data = excluded.data,
+               fetched_at = strftime('%Y-%m-%dT%H:%M:%fZ', 'now')",
+            [rom_id.into(), ra_game_id.into(), username.into(), json.into()],
+        ))
+        .await
+    {
+        log::warn!("[RA] Failed to cache achievements for rom {rom_id}: {e}");
     }
 }
@@ -0,0 +1,207 @@
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+use sea_orm::{ConnectionTrait, DatabaseBackend, DatabaseConnection, Statement};
+
+use crate::error::{AppError, AppResult};
+use crate::models::RetroArchGameOverrides;
+use crate::saves;
+
+/// RetroArch's fixed aspect ratios, by their `aspect_ratio_index`. Only the
+/// leading entries, whose indexes have stayed the same across releases.
+const ASPECT_RATIOS: [&str; 9] = [
+    "4:3", "16:9", "16:10", "16:15", "21:9", "1:1", "2:1", "3:2", "3:4",
+];
+
+const SHADER_EXTENSIONS: [&str; 3] = ["slangp", "glslp", "cgp"];
+
+/// Patch formats RetroArch can soft-patch, each with its own launch flag.
+const PATCH_EXTENSIONS: [&str; 3] = ["ips", "bps", "ups"];
+
+/// A folder set in retroarch.cfg, else its default under Application Support.
+fn retroarch_dir(key: &str, default: &str) -> AppResult<PathBuf> {
+    saves::retroarch_config_path()
+        .and_then(|cfg| saves::read_retroarch_cfg_value(&cfg, key))
+        .filter(|dir| dir != "default")
+        .map(|dir| PathBuf::from(saves::expand_tilde(&dir)))
+        .or_else(|| {
+            dirs::home_dir().map(|h| {
+                h.join("Library/Application Support/RetroArch")
+                    .join(default)
+            })
+        })
+        .ok_or_else(|| AppError::NotFound("RetroArch folder".to_string()))
+}
+
+fn render_cfg(entries: &BTreeMap<String, String>) -> String {
+    entries
+        .iter()
+        .map(|(key, value)| format!("{key} = \"{value}\"\n"))
+        .collect()
+}
+
+/// Write `entries` to `path`, or remove the file when there are none so
+/// clearing an override falls back to the core's settings.
+async fn write_or_remove(path: &Path, entries: &BTreeMap<String, String>) -> AppResult<()> {
+    if entries.is_empty() {
+        if path.exists() {
+            tokio::fs::remove_file(path).await?;
+        }
+        return Ok(());
+    }
+    if let Some(parent) = path.parent() {
+        tokio::fs::create_dir_all(parent).await?;
+    }
+    tokio::fs::write(path, render_cfg(entries)).await?;
+    Ok(())
+}
+
+/// The retroarch.cfg keys for the video and run-ahead overrides.
+fn config_entries(overrides: &RetroArchGameOverrides) -> AppResult<BTreeMap<String, String>> {
+    let mut entries = BTreeMap::new();
+    match overrides.aspect_ratio.as_deref() {
+        None => {}
+        Some("core") => {
+            entries.insert("video_aspect_ratio_auto".to_string(), "true".to_string());
+        }
+        Some(ratio) => {
+            let index = ASPECT_RATIOS
+                .iter()
+                .position(|r| *r == ratio)
+                .ok_or_else(|| AppError::Other(format!("Unsupported aspect ratio: {ratio}")))?;
+            entries.insert("video_aspect_ratio_auto".to_string(), "false".to_string());
+            entries.insert("aspect_ratio_index".to_string(), index.to_string());
+        }
+    }
+    if let Some(frames) = overrides.run_ahead_frames {
+        entries.insert("run_ahead_enabled".to_string(), (frames > 0).to_string());
+        if frames > 0 {
+            entries.insert("run_ahead_frames".to_string(), frames.to_string());
+        }
+    }
+    Ok(entries)
+}
+
+/// Write a game's overrides where RetroArch looks for them, all named after
+/// the content (the ROM file name without extension):
+/// - `config/<core>/<content>.cfg`: aspect ratio and run-ahead
+/// - `config/<core>/<content>.opt`: core options
+/// - `config/<core>/<content>.<slangp|glslp|cgp>`: shader preset
+/// - `config/remaps/<core>/<content>.rmp`: input remaps
+///
+/// Files for settings left empty are removed. The overrides are also stored
+/// so they can be edited again.
+pub async fn apply(
+    db: &DatabaseConnection,
+    rom_id: i64,
+    core_name: &str,
+    content_name: &str,
+    overrides: &RetroArchGameOverrides,
+) -> AppResult<()> {
+    let config = config_entries(overrides)?;
+    let shader = match overrides.shader_preset.as_deref().filter(|p| !p.is_empty()) {
+        Some(preset) => {
+            let preset = Path::new(preset);
+            let ext = preset
+                .extension()
+                .and_then(|e| e.to_str())
+                .map(str::to_lowercase)
+                .filter(|e| SHADER_EXTENSIONS.contains(&e.as_str()))
+                .ok_or_else(|| {
+                    AppError::Other(
+                        "Shader preset must be a .slangp, .glslp or .cgp file".to_string(),
+                    )
+                })?;
+            if !preset.is_file() {
+                return Err(AppError::Other(format!(
+                    "Shader preset not found: {}",
+                    preset.display()
+                )));
+            }
+            Some((ext, preset.to_path_buf()))
+        }
+        None => None,
+    };
+
+    if let Some(patch) = overrides.soft_patch.as_deref() {
+        soft_patch_args(patch)?;
+    }
+
+    let core_dir = retroarch_dir("rgui_config_directory", "config")?.join(core_name);
+    let remap_dir = retroarch_dir("input_remapping_directory", "config/remaps")?.join(core_name);
+
+    write_or_remove(&core_dir.join(format!("{content_name}.cfg")), &config).await?;
+    write_or_remove(
+        &core_dir.join(format!("{content_name}.opt")),
+        &overrides.core_options,
+    )
+    .await?;
+    write_or_remove(
+        &remap_dir.join(format!("{content_name}.rmp")),
+        &overrides.remaps,
+    )
+    .await?;
+
+    // A game preset that references the chosen one, replacing any preset
+    // of another shader type that would otherwise win
+    for ext in SHADER_EXTENSIONS {
+        let path = core_dir.join(format!("{content_name}.{ext}"));
+        if path.exists() {
+            tokio::fs::remove_file(&path).await?;
+        }
+    }
+    if let Some((ext, preset)) = shader {
+        tokio::fs::create_dir_all(&core_dir).await?;
+        tokio::fs::write(
+            core_dir.join(format!("{content_name}.{ext}")),
+            format!("#reference \"{}\"\n", preset.display()),
+        )
+        .await?;
+    }
+
+    db.execute(Statement::from_sql_and_values(
+        DatabaseBackend::Sqlite,
+        "INSERT INTO retroarch_overrides (rom_id, options) VALUES (?, ?)
+         ON CONFLICT(rom_id) DO UPDATE SET
+           options = excluded.options,
+           updated_at = strftime('%Y-%m-%dT%H:%M:%fZ', 'now')",
+        [
+            rom_id.into(),
+            serde_json::json!(overrides).to_string().into(),
+        ],
+    ))
+    .await?;
+    log::info!("Wrote RetroArch overrides for {content_name} ({core_name})");
+    Ok(())
+}
+
+/// RetroArch's launch arguments for a soft patch, e.g. `--bps <patch>`.
+pub fn soft_patch_args(patch: &str) -> AppResult<[String; 2]> {
+    let path = Path::new(patch);
+    let ext = path
+        .extension()
+        .and_then(|e| e.to_str())
+        .map(str::to_lowercase)
+        .filter(|e| PATCH_EXTENSIONS.contains(&e.as_str()))
+        .ok_or_else(|| AppError::Other("Patch must be an .ips, .bps or .ups file".to_string()))?;
+    if !path.is_file() {
+        return Err(AppError::NotFound(format!("Patch {patch}")));
+    }
+    Ok([format!("--{ext}"), patch.to_string()])
+}
+
+/// The overrides last set for a ROM; empty when there are none.
+pub async fn get(db: &DatabaseConnection, rom_id: i64) -> AppResult<RetroArchGameOverrides> {
+    let row = db
+        .query_one(Statement::from_sql_and_values(
+            DatabaseBackend::Sqlite,
+            "SELECT options FROM retroarch_overrides WHERE rom_id = ?",
+            [rom_id.into()],
+        ))
+        .await?;
+    let Some(row) = row else {
+        return Ok(RetroArchGameOverrides::default());
+    };
+    let options: String = row.try_get("", "options")?;
+    serde_json::from_str(&options).map_err(|e| AppError::Other(e.to_string()))
+}
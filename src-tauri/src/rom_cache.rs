@@ -0,0 +1,668 @@
+use std::path::{Path, PathBuf};
+
+use sea_orm::{ConnectionTrait, DatabaseBackend, DatabaseConnection, FromQueryResult, Statement};
+
+use crate::commands::rom_cache_dir;
+use crate::error::{AppError, AppResult};
+use crate::models::{CacheMigrationReport, CorruptDownload, DiscardedCacheFile};
+
+/// A file in the ROM cache directory.
+pub struct CacheEntry {
+    pub path: PathBuf,
+    /// Path relative to the cache directory, `<hash>/<file_name>` for keyed
+    /// entries or just the file name for ones downloaded before keying.
+    pub relative_path: String,
+    pub file_name: String,
+    pub hash_md5: Option<String>,
+    pub size: u64,
+}
+
+/// Whether a cache subdirectory name is a content hash (lowercase MD5).
+pub fn is_cache_key(name: &str) -> bool {
+    name.len() == 32
+        && name
+            .bytes()
+            .all(|b| b.is_ascii_digit() || (b'a'..=b'f').contains(&b))
+}
+
+fn is_partial(name: &str) -> bool {
+    name.starts_with('.') && name.ends_with(".part")
+}
+
+/// Temp path to download a ROM into before [`store`] moves it into place.
+pub fn part_path(rom_id: i64, file_name: &str) -> PathBuf {
+    rom_cache_dir().join(format!(".{rom_id}.{file_name}.part"))
+}
+
+fn is_save_file(path: &Path) -> bool {
+    path.extension()
+        .is_some_and(|ext| crate::saves::classify_extension(&ext.to_string_lossy()).is_some())
+}
+
+/// The ROM in a keyed directory, preferring `file_name`. Emulators may have
+/// written saves next to it, so those are skipped.
+fn file_in_key_dir(dir: &Path, file_name: &str) -> Option<PathBuf> {
+    let preferred = dir.join(file_name);
+    if preferred.is_file() {
+        return Some(preferred);
+    }
+    std::fs::read_dir(dir)
+        .ok()?
+        .flatten()
+        .map(|e| e.path())
+        .find(|p| {
+            p.is_file()
+                && !is_save_file(p)
+                && !is_partial(&p.file_name().unwrap_or_default().to_string_lossy())
+        })
+}
+
+/// Cached copy of a ROM, if there is one. Looks up the ROM's MD5 in the cache
+/// index, then on disk (another machine sharing the directory may have put it
+/// there), then falls back to an unkeyed file of the same name.
+pub async fn cached_rom_path(db: &DatabaseConnection, rom_id: i64) -> AppResult<Option<PathBuf>> {
+    let Some(row) = db
+        .query_one(Statement::from_sql_and_values(
+            DatabaseBackend::Sqlite,
+            "SELECT file_name, hash_md5 FROM roms WHERE id = ?",
+            [rom_id.into()],
+        ))
+        .await?
+    else {
+        return Ok(None);
+    };
+    let file_name: String = row.try_get("", "file_name")?;
+    let hash_md5: Option<String> = row.try_get("", "hash_md5")?;
+
+    let dir = rom_cache_dir();
+    if let Some(hash) = hash_md5
+        .map(|h| h.to_lowercase())
+        .filter(|h| is_cache_key(h))
+    {
+        let indexed = db
+            .query_one(Statement::from_sql_and_values(
+                DatabaseBackend::Sqlite,
+                "SELECT file_name FROM rom_cache_index WHERE hash_md5 = ?",
+                [hash.clone().into()],
+            ))
+            .await?
+            .and_then(|row| row.try_get::<String>("", "file_name").ok())
+            .map(|name| dir.join(&hash).join(name))
+            .filter(|p| p.is_file());
+        let found = match indexed {
+            Some(path) => Some(path),
+            None => file_in_key_dir(&dir.join(&hash), &file_name),
+        };
+        if let Some(path) = found {
+            index(db, &hash, &path).await?;
+            return Ok(Some(path));
+        }
+    }
+
+    let legacy = dir.join(&file_name);
+    Ok(legacy.is_file().then_some(legacy))
+}
+
+/// Times a ROM is downloaded before giving up on content that keeps failing
+/// verification.
+pub const DOWNLOAD_ATTEMPTS: u32 = 3;
+
+const DEFAULT_CORRUPT_LIMIT: i64 = 200;
+
+/// What [`store`] did with a finished download.
+pub enum Stored {
+    /// Verified and moved into the cache here.
+    Cached(PathBuf),
+    /// Failed verification, so it was deleted and recorded instead.
+    Corrupt {
+        reason: String,
+        actual_md5: Option<String>,
+    },
+}
+
+/// Verify a finished download and move it into `<hash>/<file_name>`, indexed.
+/// It must have as many bytes as the server announced (`announced_size`, the
+/// response's Content-Length) and match the ROM's MD5 when the library knows
+/// it; otherwise it's deleted and recorded for the corrupted-files report.
+/// The ROM's MD5 is computed (and saved) if the library doesn't know it yet.
+/// If another copy of the same content is already cached, the download is
+/// discarded in favour of it.
+pub async fn store(
+    db: &DatabaseConnection,
+    rom_id: i64,
+    part: &Path,
+    file_name: &str,
+    announced_size: Option<u64>,
+    attempt: u32,
+) -> AppResult<Stored> {
+    let known: Option<String> = db
+        .query_one(Statement::from_sql_and_values(
+            DatabaseBackend::Sqlite,
+            "SELECT hash_md5 FROM roms WHERE id = ?",
+            [rom_id.into()],
+        ))
+        .await?
+        .and_then(|row| row.try_get::<Option<String>>("", "hash_md5").ok())
+        .flatten()
+        .map(|h| h.to_lowercase())
+        .filter(|h| is_cache_key(h));
+    let actual_size = tokio::fs::metadata(part).await?.len();
+
+    let checked = match announced_size.filter(|&size| size != actual_size) {
+        Some(size) => Err((format!("Got {actual_size} of {size} bytes"), None)),
+        None => {
+            let path = part.to_path_buf();
+            let name = file_name.to_string();
+            let expected = known.clone();
+            tokio::task::spawn_blocking(move || check_md5(&path, &name, expected.as_deref()))
+                .await
+                .map_err(|e| AppError::Other(format!("Task join error: {e}")))?
+        }
+    };
+
+    let hash = match checked {
+        Ok(hash) => hash,
+        Err((reason, actual_md5)) => {
+            let _ = tokio::fs::remove_file(part).await;
+            log::warn!("Download of ROM {rom_id} ({file_name}) is corrupt: {reason}");
+            db.execute(Statement::from_sql_and_values(
+                DatabaseBackend::Sqlite,
+                "INSERT INTO corrupt_downloads
+                    (rom_id, file_name, attempt, reason, expected_md5, actual_md5,
+                     expected_size, actual_size)
+                 VALUES (?, ?, ?, ?, ?, ?, ?, ?)",
+                [
+                    rom_id.into(),
+                    file_name.into(),
+                    attempt.into(),
+                    reason.clone().into(),
+                    known.into(),
+                    actual_md5.clone().into(),
+                    announced_size
+                        .map(|s| i64::try_from(s).unwrap_or(i64::MAX))
+                        .into(),
+                    i64::try_from(actual_size).unwrap_or(i64::MAX).into(),
+                ],
+            ))
+            .await?;
+            return Ok(Stored::Corrupt { reason, actual_md5 });
+        }
+    };
+
+    if known.is_none() {
+        db.execute(Statement::from_sql_and_values(
+            DatabaseBackend::Sqlite,
+            "UPDATE roms SET hash_md5 = ? WHERE id = ? AND hash_md5 IS NULL",
+            [hash.clone().into(), rom_id.into()],
+        ))
+        .await?;
+    }
+
+    place(db, &hash, part, file_name).await.map(Stored::Cached)
+}
+
+/// The MD5 to key a download by: `expected` if it matches, the download's
+/// own when nothing is expected. A zip matches if either the file or (like
+/// a local scan hashes it) its first entry does. On a mismatch, returns why
+/// with the MD5 that was found.
+///
+/// Must be called from a blocking context (not async).
+fn check_md5(
+    path: &Path,
+    file_name: &str,
+    expected: Option<&str>,
+) -> Result<String, (String, Option<String>)> {
+    let raw = crate::hash::compute_md5(path).map_err(|e| (format!("Unreadable: {e}"), None))?;
+    let Some(expected) = expected else {
+        return Ok(raw);
+    };
+    if raw == expected {
+        return Ok(raw);
+    }
+    let actual = if file_name.to_lowercase().ends_with(".zip") {
+        let inner = crate::hash::compute_md5_as(path, file_name)
+            .map_err(|e| (format!("Not a readable zip: {e}"), Some(raw.clone())))?;
+        if inner == expected {
+            return Ok(inner);
+        }
+        inner
+    } else {
+        raw
+    };
+    Err((
+        "Contents don't match the ROM's known hash".to_string(),
+        Some(actual),
+    ))
+}
+
+/// Downloads that failed verification, newest first.
+pub async fn list_corrupt(
+    db: &DatabaseConnection,
+    limit: Option<i64>,
+) -> AppResult<Vec<CorruptDownload>> {
+    #[derive(Debug, FromQueryResult)]
+    struct Row {
+        id: i64,
+        rom_id: i64,
+        rom_name: String,
+        file_name: String,
+        attempt: i64,
+        reason: String,
+        expected_md5: Option<String>,
+        actual_md5: Option<String>,
+        expected_size: Option<i64>,
+        actual_size: i64,
+        created_at: String,
+    }
+
+    let rows = Row::find_by_statement(Statement::from_sql_and_values(
+        DatabaseBackend::Sqlite,
+        "SELECT c.id, c.rom_id, r.name AS rom_name, c.file_name, c.attempt, c.reason,
+                c.expected_md5, c.actual_md5, c.expected_size, c.actual_size, c.created_at
+         FROM corrupt_downloads c
+         JOIN roms r ON r.id = c.rom_id
+         ORDER BY c.id DESC
+         LIMIT ?",
+        [limit.unwrap_or(DEFAULT_CORRUPT_LIMIT).into()],
+    ))
+    .all(db)
+    .await?;
+    Ok(rows
+        .into_iter()
+        .map(|r| CorruptDownload {
+            id: r.id,
+            rom_id: r.rom_id,
+            rom_name: r.rom_name,
+            file_name: r.file_name,
+            attempt: u32::try_from(r.attempt).unwrap_or(0),
+            reason: r.reason,
+            expected_md5: r.expected_md5,
+            actual_md5: r.actual_md5,
+            expected_size: r.expected_size.and_then(|s| u64::try_from(s).ok()),
+            actual_size: u64::try_from(r.actual_size).unwrap_or(0),
+            created_at: r.created_at,
+        })
+        .collect())
+}
+
+/// Empty the corrupted-files report, returning how many entries it had.
+pub async fn clear_corrupt(db: &DatabaseConnection) -> AppResult<u64> {
+    let result = db
+        .execute(Statement::from_string(
+            DatabaseBackend::Sqlite,
+            "DELETE FROM corrupt_downloads",
+        ))
+        .await?;
+    Ok(result.rows_affected())
+}
+
+/// Replace a cached zip/7z with what's in it, for platforms set to extract
+/// archives, and point the index at the file to launch. Returns that file.
+/// Archives cached before downloads were keyed by hash are left alone.
+pub async fn extract_cached(
+    db: &DatabaseConnection,
+    archive: &Path,
+    platform_slug: &str,
+) -> AppResult<PathBuf> {
+    let Some((key_dir, hash)) = archive.parent().and_then(|dir| {
+        let name = dir.file_name()?.to_str()?;
+        is_cache_key(name).then(|| (dir.to_path_buf(), name.to_string()))
+    }) else {
+        return Ok(archive.to_path_buf());
+    };
+
+    let from = archive.to_path_buf();
+    let slug = platform_slug.to_string();
+    let launch = tokio::task::spawn_blocking(move || -> AppResult<PathBuf> {
+        extract_flat(&from, &key_dir)?;
+        std::fs::remove_file(&from)?;
+        launch_file(&key_dir, &slug)
+            .ok_or_else(|| AppError::Other(format!("{} was empty", from.display())))
+    })
+    .await
+    .map_err(|e| AppError::Other(format!("Task join error: {e}")))??;
+
+    log::info!(
+        "Extracted cached archive {} to {}",
+        archive.display(),
+        launch.display()
+    );
+    index(db, &hash, &launch).await?;
+    Ok(launch)
+}
+
+/// Whether a file is an archive [`extract_cached`] can open.
+pub fn is_extractable(path: &Path) -> bool {
+    path.extension()
+        .and_then(|e| e.to_str())
+        .is_some_and(|e| e.eq_ignore_ascii_case("zip") || e.eq_ignore_ascii_case("7z"))
+}
+
+/// Write every file in a zip/7z straight into `dir`, dropping folders inside
+/// the archive so cue sheets and their tracks stay side by side (and no
+/// entry can land outside `dir`).
+///
+/// Must be called from a blocking context (not async).
+fn extract_flat(archive: &Path, dir: &Path) -> AppResult<()> {
+    let flat = |name: &str| Path::new(name).file_name().map(|n| dir.join(n));
+    let is_zip = archive
+        .extension()
+        .is_some_and(|e| e.eq_ignore_ascii_case("zip"));
+
+    if is_zip {
+        let mut zip = zip::ZipArchive::new(std::fs::File::open(archive)?)
+            .map_err(|e| AppError::Other(format!("Failed to open zip: {e}")))?;
+        for i in 0..zip.len() {
+            let mut entry = zip
+                .by_index(i)
+                .map_err(|e| AppError::Other(format!("Failed to read zip entry: {e}")))?;
+            if entry.is_dir() {
+                continue;
+            }
+            if let Some(out) = flat(entry.name()) {
+                std::io::copy(&mut entry, &mut std::fs::File::create(out)?)?;
+            }
+        }
+        return Ok(());
+    }
+
+    sevenz_rust::decompress_file_with_extract_fn(archive, dir, |entry, reader, _| {
+        if !entry.is_directory() {
+            if let Some(out) = flat(entry.name()) {
+                std::io::copy(reader, &mut std::fs::File::create(out)?)?;
+            }
+        }
+        Ok(true)
+    })
+    .map_err(|e| AppError::Other(format!("Failed to extract 7z: {e}")))
+}
+
+/// The file to launch among extracted ones: a playlist or disc sheet if
+/// there is one, otherwise the largest file with one of the platform's extensions,
+/// otherwise the largest file.
+///
+/// Must be called from a blocking context (not async).
+fn launch_file(dir: &Path, platform_slug: &str) -> Option<PathBuf> {
+    let files: Vec<(PathBuf, String, u64)> = std::fs::read_dir(dir)
+        .ok()?
+        .flatten()
+        .filter_map(|e| {
+            let meta = e.metadata().ok()?;
+            let path = e.path();
+            let ext = path.extension()?.to_str()?.to_ascii_lowercase();
+            (meta.is_file() && !is_save_file(&path)).then_some((path, ext, meta.len()))
+        })
+        .collect();
+    let largest = |pred: &dyn Fn(&str) -> bool| {
+        files
+            .iter()
+            .filter(|(_, ext, _)| pred(ext))
+            .max_by_key(|(_, _, size)| *size)
+            .map(|(path, _, _)| path.clone())
+    };
+    let platform_extensions = crate::platform_registry::extensions(platform_slug);
+
+    ["m3u", "cue", "gdi"]
+        .iter()
+        .find_map(|sheet| largest(&|ext| ext == *sheet))
+        .or_else(|| largest(&|ext| platform_extensions.contains(&ext)))
+        .or_else(|| largest(&|_| true))
+}
+
+/// Move `from` to `<hash>/<file_name>` (or drop it if that content is already
+/// cached) and index it.
+async fn place(
+    db: &DatabaseConnection,
+    hash: &str,
+    from: &Path,
+    file_name: &str,
+) -> AppResult<PathBuf> {
+    let key_dir = rom_cache_dir().join(hash);
+    tokio::fs::create_dir_all(&key_dir).await?;
+    let path = match file_in_key_dir(&key_dir, file_name) {
+        Some(existing) => {
+            let _ = tokio::fs::remove_file(from).await;
+            existing
+        }
+        None => {
+            let dest = key_dir.join(file_name);
+            tokio::fs::rename(from, &dest).await?;
+            dest
+        }
+    };
+    index(db, hash, &path).await?;
+    Ok(path)
+}
+
+/// Move files cached by name (before downloads were keyed by hash) into their
+/// hash folders. Each file is hashed and checked against the MD5 the library
+/// has for it; files no ROM uses, that don't match, or that can't be read are
+/// deleted and reported.
+pub async fn migrate_legacy(
+    db: &DatabaseConnection,
+    report: &mut CacheMigrationReport,
+) -> AppResult<()> {
+    let dir = rom_cache_dir();
+    let legacy: Vec<CacheEntry> = tokio::task::spawn_blocking(move || list_entries(&dir))
+        .await
+        .map_err(|e| AppError::Other(format!("Task join error: {e}")))?
+        .into_iter()
+        .filter(|e| e.hash_md5.is_none())
+        .collect();
+
+    for entry in legacy {
+        let roms: Vec<(i64, Option<String>)> = db
+            .query_all(Statement::from_sql_and_values(
+                DatabaseBackend::Sqlite,
+                "SELECT id, hash_md5 FROM roms WHERE file_name = ?",
+                [entry.file_name.clone().into()],
+            ))
+            .await?
+            .into_iter()
+            .filter_map(|row| {
+                let id: i64 = row.try_get("", "id").ok()?;
+                let hash: Option<String> = row.try_get("", "hash_md5").ok()?;
+                Some((id, hash.filter(|h| !h.is_empty())))
+            })
+            .collect();
+        if roms.is_empty() {
+            // Saves RetroArch wrote next to downloaded content stay put
+            if !is_save_file(&entry.path) {
+                discard(report, entry, "No ROM in the library uses this file");
+            }
+            continue;
+        }
+
+        let path = entry.path.clone();
+        let computed = match tokio::task::spawn_blocking(move || crate::hash::compute_md5(&path))
+            .await
+            .map_err(|e| AppError::Other(format!("Task join error: {e}")))?
+        {
+            Ok(hash) => hash,
+            Err(e) => {
+                discard(report, entry, &format!("Unreadable: {e}"));
+                continue;
+            }
+        };
+
+        let recorded: Vec<&str> = roms.iter().filter_map(|(_, h)| h.as_deref()).collect();
+        if recorded.is_empty() {
+            let [(rom_id, _)] = roms.as_slice() else {
+                discard(
+                    report,
+                    entry,
+                    "Several ROMs share this file name and none has a known hash",
+                );
+                continue;
+            };
+            db.execute(Statement::from_sql_and_values(
+                DatabaseBackend::Sqlite,
+                "UPDATE roms SET hash_md5 = ? WHERE id = ? AND hash_md5 IS NULL",
+                [computed.clone().into(), (*rom_id).into()],
+            ))
+            .await?;
+        } else if !recorded.iter().any(|h| h.eq_ignore_ascii_case(&computed)) {
+            discard(report, entry, "Contents don't match the ROM's known hash");
+            continue;
+        }
+
+        place(db, &computed, &entry.path, &entry.file_name).await?;
+        report.roms_rekeyed += 1;
+    }
+    Ok(())
+}
+
+/// Delete a cache file that couldn't be migrated and note why.
+fn discard(report: &mut CacheMigrationReport, entry: CacheEntry, reason: &str) {
+    match std::fs::remove_file(&entry.path) {
+        Ok(()) => {
+            log::info!("Discarded cached file {}: {reason}", entry.relative_path);
+            report.reclaimed_bytes += entry.size;
+            report.discarded.push(DiscardedCacheFile {
+                path: entry.relative_path,
+                reason: reason.to_string(),
+                size: entry.size,
+            });
+        }
+        Err(e) => log::warn!("Failed to remove cached file {}: {e}", entry.relative_path),
+    }
+}
+
+/// Record (or refresh) the index row for a keyed cache file.
+async fn index(db: &DatabaseConnection, hash: &str, path: &Path) -> AppResult<()> {
+    let file_name = path
+        .file_name()
+        .unwrap_or_default()
+        .to_string_lossy()
+        .into_owned();
+    let size = std::fs::metadata(path).map(|m| m.len()).unwrap_or(0);
+    db.execute(Statement::from_sql_and_values(
+        DatabaseBackend::Sqlite,
+        "INSERT INTO rom_cache_index (hash_md5, file_name, size) VALUES (?, ?, ?)
+         ON CONFLICT(hash_md5) DO UPDATE SET
+            file_name = excluded.file_name,
+            size = excluded.size,
+            last_used_at = strftime('%Y-%m-%dT%H:%M:%fZ', 'now')",
+        [
+            hash.into(),
+            file_name.into(),
+            i64::try_from(size).unwrap_or(i64::MAX).into(),
+        ],
+    ))
+    .await?;
+    Ok(())
+}
+
+/// Every cached ROM, keyed or not, skipping partial downloads.
+///
+/// Must be called from a blocking context (not async).
+pub fn list_entries(cache_dir: &Path) -> Vec<CacheEntry> {
+    let mut entries = Vec::new();
+    let Ok(dir_entries) = std::fs::read_dir(cache_dir) else {
+        return entries;
+    };
+    for entry in dir_entries.flatten() {
+        let path = entry.path();
+        let name = path
+            .file_name()
+            .unwrap_or_default()
+            .to_string_lossy()
+            .into_owned();
+        let Ok(meta) = entry.metadata() else { continue };
+        if meta.is_file() {
+            if !is_partial(&name) {
+                entries.push(CacheEntry {
+                    path,
+                    relative_path: name.clone(),
+                    file_name: name,
+                    hash_md5: None,
+                    size: meta.len(),
+                });
+            }
+        } else if meta.is_dir() && is_cache_key(&name) {
+            let Ok(files) = std::fs::read_dir(&path) else {
+                continue;
+            };
+            for file in files.flatten() {
+                let file_path = file.path();
+                let file_name = file_path
+                    .file_name()
+                    .unwrap_or_default()
+                    .to_string_lossy()
+                    .into_owned();
+                let Ok(file_meta) = file.metadata() else {
+                    continue;
+                };
+                if !file_meta.is_file() || is_partial(&file_name) {
+                    continue;
+                }
+                entries.push(CacheEntry {
+                    path: file_path,
+                    relative_path: format!("{name}/{file_name}"),
+                    file_name,
+                    hash_md5: Some(name.clone()),
+                    size: file_meta.len(),
+                });
+            }
+        }
+    }
+    entries
+}
+
+/// Resolve a `relative_path` from [`CacheEntry`] back to a file, rejecting
+/// anything that would point outside the cache directory.
+pub fn resolve(cache_dir: &Path, relative_path: &str) -> Option<PathBuf> {
+    if relative_path.contains('\\') || relative_path.contains("..") {
+        return None;
+    }
+    match relative_path.split_once('/') {
+        None => Some(cache_dir.join(relative_path)),
+        Some((key, name)) if is_cache_key(key) && !name.is_empty() && !name.contains('/') => {
+            Some(cache_dir.join(key).join(name))
+        }
+        Some(_) => None,
+    }
+}
+
+/// Delete a cached file, dropping its key directory once empty. Index rows
+/// are cleaned up afterwards by [`prune_index`].
+///
+/// Must be called from a blocking context (not async).
+pub fn remove_entry(path: &Path) -> std::io::Result<()> {
+    std::fs::remove_file(path)?;
+    if let Some(parent) = path.parent().filter(|p| {
+        p.file_name()
+            .is_some_and(|n| is_cache_key(&n.to_string_lossy()))
+    }) {
+        // Only succeeds once the directory is empty
+        let _ = std::fs::remove_dir(parent);
+    }
+    Ok(())
+}
+
+/// Drop index rows whose file is gone.
+pub async fn prune_index(db: &DatabaseConnection) -> AppResult<u64> {
+    let dir = rom_cache_dir();
+    let rows = db
+        .query_all(Statement::from_string(
+            DatabaseBackend::Sqlite,
+            "SELECT hash_md5, file_name FROM rom_cache_index",
+        ))
+        .await?;
+    let mut pruned = 0;
+    for row in rows {
+        let hash: String = row.try_get("", "hash_md5")?;
+        let file_name: String = row.try_get("", "file_name")?;
+        if dir.join(&hash).join(&file_name).is_file() {
+            continue;
+        }
+        db.execute(Statement::from_sql_and_values(
+            DatabaseBackend::Sqlite,
+            "DELETE FROM rom_cache_index WHERE hash_md5 = ?",
+            [hash.into()],
+        ))
+        .await?;
+        pruned += 1;
+    }
+    Ok(pruned)
+}
@@ -0,0 +1,492 @@
+use std::collections::{BTreeMap, HashMap};
+use std::path::{Path, PathBuf};
+
+use hmac::{Hmac, Mac};
+use sea_orm::{ConnectionTrait, DatabaseBackend, DatabaseConnection, FromQueryResult, Statement};
+use serde::{Deserialize, Serialize};
+use sha1::Sha1;
+use sha2::{Digest, Sha256};
+
+use crate::error::{AppError, AppResult};
+use crate::models::{CloudBackupConfig, CloudConflict, CloudSyncReport, SavePathOverride};
+
+const DEFAULT_PREFIX: &str = "romm-buddy";
+const MANIFEST_NAME: &str = "manifest.json";
+
+/// Storage a save backup can be written to.
+#[async_trait::async_trait]
+pub trait CloudProvider: Send + Sync {
+    /// The object at `key`, or None when there isn't one.
+    async fn get(&self, key: &str) -> AppResult<Option<Vec<u8>>>;
+    async fn put(&self, key: &str, body: Vec<u8>) -> AppResult<()>;
+}
+
+/// The provider for a stored configuration.
+pub fn provider(config: &CloudBackupConfig) -> AppResult<Box<dyn CloudProvider>> {
+    crate::http::ensure_online("cloud storage")?;
+    match config {
+        CloudBackupConfig::S3 {
+            endpoint,
+            region,
+            bucket,
+            access_key_id,
+            secret_access_key,
+            prefix,
+            path_style,
+        } => Ok(Box::new(S3Provider {
+            client: crate::http::client_builder()
+                .user_agent("romm-buddy/0.1")
+                .timeout(std::time::Duration::from_secs(120))
+                .build()
+                .unwrap_or_default(),
+            endpoint: reqwest::Url::parse(endpoint)
+                .map_err(|e| AppError::Other(format!("Invalid S3 endpoint: {e}")))?,
+            region: region.clone(),
+            bucket: bucket.clone(),
+            access_key_id: access_key_id.clone(),
+            secret_access_key: secret_access_key.clone(),
+            prefix: prefix
+                .as_deref()
+                .map(|p| p.trim_matches('/'))
+                .filter(|p| !p.is_empty())
+                .unwrap_or(DEFAULT_PREFIX)
+                .to_string(),
+            path_style: *path_style,
+        })),
+    }
+}
+
+// ---------- S3 ----------
+
+/// S3-compatible storage, authenticated with AWS Signature Version 4.
+struct S3Provider {
+    client: reqwest::Client,
+    endpoint: reqwest::Url,
+    region: String,
+    bucket: String,
+    access_key_id: String,
+    secret_access_key: String,
+    prefix: String,
+    path_style: bool,
+}
+
+fn hmac_sha256(key: &[u8], data: &str) -> Vec<u8> {
+    let mut mac = Hmac::<Sha256>::new_from_slice(key).expect("HMAC accepts any key length");
+    mac.update(data.as_bytes());
+    mac.finalize().into_bytes().to_vec()
+}
+
+impl S3Provider {
+    fn object_url(&self, key: &str) -> AppResult<reqwest::Url> {
+        // S3 wants every path segment encoded except the unreserved characters
+        let object_path = format!("{}/{key}", self.prefix)
+            .split('/')
+            .map(|segment| urlencoding::encode(segment).into_owned())
+            .collect::<Vec<_>>()
+            .join("/");
+        let mut url = self.endpoint.clone();
+        if self.path_style {
+            let base = url.path().trim_end_matches('/').to_string();
+            url.set_path(&format!("{base}/{}/{object_path}", self.bucket));
+        } else {
+            let host = url
+                .host_str()
+                .map(str::to_string)
+                .ok_or_else(|| AppError::Other("S3 endpoint has no host".to_string()))?;
+            url.set_host(Some(&format!("{}.{host}", self.bucket)))
+                .map_err(|e| AppError::Other(format!("Invalid S3 bucket name: {e}")))?;
+            url.set_path(&format!("/{object_path}"));
+        }
+        Ok(url)
+    }
+
+    /// A request signed with SigV4, with the payload hash in
+    /// `x-amz-content-sha256` as S3 requires.
+    fn signed(
+        &self,
+        method: reqwest::Method,
+        url: reqwest::Url,
+        body: Vec<u8>,
+    ) -> AppResult<reqwest::RequestBuilder> {
+        let now = chrono::Utc::now();
+        let amz_date = now.format("%Y%m%dT%H%M%SZ").to_string();
+        let date = now.format("%Y%m%d").to_string();
+        let payload_hash = format!("{:x}", Sha256::digest(&body));
+        let host = match (url.host_str(), url.port()) {
+            (Some(host), Some(port)) => format!("{host}:{port}"),
+            (Some(host), None) => host.to_string(),
+            (None, _) => return Err(AppError::Other("S3 endpoint has no host".to_string())),
+        };
+
+        let signed_headers = "host;x-amz-content-sha256;x-amz-date";
+        let canonical_request = format!(
+            "{method}\n{}\n{}\nhost:{host}\nx-amz-content-sha256:{payload_hash}\nx-amz-date:{amz_date}\n\n{signed_headers}\n{payload_hash}",
+            url.path(),
+            url.query().unwrap_or_default(),
+        );
+        let scope = format!("{date}/{}/s3/aws4_request", self.region);
+        let string_to_sign = format!(
+            "AWS4-HMAC-SHA256\n{amz_date}\n{scope}\n{:x}",
+            Sha256::digest(canonical_request.as_bytes())
+        );
+        let key = [date.as_str(), &self.region, "s3", "aws4_request"]
+            .iter()
+            .fold(
+                format!("AWS4{}", self.secret_access_key).into_bytes(),
+                |key, part| hmac_sha256(&key, part),
+            );
+        let signature = hmac_sha256(&key, &string_to_sign)
+            .iter()
+            .map(|b| format!("{b:02x}"))
+            .collect::<String>();
+
+        Ok(self
+            .client
+            .request(method, url)
+            .header("x-amz-content-sha256", payload_hash)
+            .header("x-amz-date", amz_date)
+            .header(
+                "authorization",
+                format!(
+                    "AWS4-HMAC-SHA256 Credential={}/{scope}, SignedHeaders={signed_headers}, Signature={signature}",
+                    self.access_key_id
+                ),
+            )
+            .body(body))
+    }
+}
+
+/// The `<Code>` and `<Message>` of an S3 error body, or the status.
+fn s3_error(status: reqwest::StatusCode, body: &str) -> AppError {
+    let field = |tag: &str| {
+        let start = body.find(&format!("<{tag}>"))? + tag.len() + 2;
+        let end = body[start..].find(&format!("</{tag}>"))? + start;
+        Some(body[start..end].to_string())
+    };
+    match (field("Code"), field("Message")) {
+        (Some(code), Some(message)) => AppError::Other(format!("S3 error {code}: {message}")),
+        _ => AppError::Other(format!("S3 request failed: {status}")),
+    }
+}
+
+#[async_trait::async_trait]
+impl CloudProvider for S3Provider {
+    async fn get(&self, key: &str) -> AppResult<Option<Vec<u8>>> {
+        let resp = self
+            .signed(reqwest::Method::GET, self.object_url(key)?, Vec::new())?
+            .send()
+            .await?;
+        let status = resp.status();
+        if status == reqwest::StatusCode::NOT_FOUND {
+            return Ok(None);
+        }
+        if !status.is_success() {
+            return Err(s3_error(status, &resp.text().await.unwrap_or_default()));
+        }
+        Ok(Some(resp.bytes().await?.to_vec()))
+    }
+
+    async fn put(&self, key: &str, body: Vec<u8>) -> AppResult<()> {
+        let resp = self
+            .signed(reqwest::Method::PUT, self.object_url(key)?, body)?
+            .send()
+            .await?;
+        let status = resp.status();
+        if !status.is_success() {
+            return Err(s3_error(status, &resp.text().await.unwrap_or_default()));
+        }
+        Ok(())
+    }
+}
+
+// ---------- Backup and restore ----------
+
+/// What the cloud holds, written after every backup. Reading one file
+/// instead of listing the bucket keeps the sync to plain GETs and PUTs.
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct Manifest {
+    files: BTreeMap<String, ManifestEntry>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct ManifestEntry {
+    sha1: String,
+    size: u64,
+    modified_at: String,
+}
+
+/// A save file on this device and its key in the cloud:
+/// `<emulator>/<saves|states>/<path inside the folder>`.
+struct LocalSave {
+    key: String,
+    path: PathBuf,
+}
+
+/// Check that the provider can be reached with its credentials.
+pub async fn test(provider: &dyn CloudProvider) -> AppResult<()> {
+    load_manifest(provider).await.map(|_| ())
+}
+
+async fn load_manifest(provider: &dyn CloudProvider) -> AppResult<Manifest> {
+    match provider.get(MANIFEST_NAME).await? {
+        Some(bytes) => serde_json::from_slice(&bytes)
+            .map_err(|e| AppError::Other(format!("Unreadable cloud manifest: {e}"))),
+        None => Ok(Manifest::default()),
+    }
+}
+
+/// Each emulator's save and state folders, with the user's overrides applied.
+fn save_roots(
+    overrides: &HashMap<String, SavePathOverride>,
+) -> Vec<(String, &'static str, Vec<String>)> {
+    let mut roots = Vec::new();
+    for (emulator, paths) in super::default_save_paths() {
+        let user = overrides.get(emulator);
+        let saves = user
+            .and_then(|o| o.save_dir.clone())
+            .map_or(paths.save_dirs, |dir| vec![dir]);
+        let states = user
+            .and_then(|o| o.state_dir.clone())
+            .map_or(paths.state_dirs, |dir| vec![dir]);
+        roots.push((emulator.to_string(), "saves", saves));
+        roots.push((emulator.to_string(), "states", states));
+    }
+    roots.sort_by(|a, b| (&a.0, a.1).cmp(&(&b.0, b.1)));
+    roots
+}
+
+/// Save files and states in a folder and one level of subfolders
+/// (RetroArch sorts them per core), like [`super::scan_for_saves`].
+fn scan_root(dir: &Path) -> Vec<PathBuf> {
+    let mut files = Vec::new();
+    for path in std::fs::read_dir(dir)
+        .into_iter()
+        .flatten()
+        .flatten()
+        .map(|e| e.path())
+    {
+        if path.is_dir() {
+            files.extend(
+                std::fs::read_dir(&path)
+                    .into_iter()
+                    .flatten()
+                    .flatten()
+                    .map(|e| e.path())
+                    .filter(|p| p.is_file()),
+            );
+        } else if path.is_file() {
+            files.push(path);
+        }
+    }
+    files.retain(|p| {
+        p.extension()
+            .and_then(|e| e.to_str())
+            .and_then(super::classify_extension)
+            .is_some()
+    });
+    files
+}
+
+fn local_saves(overrides: &HashMap<String, SavePathOverride>) -> Vec<LocalSave> {
+    let mut seen = std::collections::HashSet::new();
+    let mut saves = Vec::new();
+    for (emulator, kind, dirs) in save_roots(overrides) {
+        for dir in dirs {
+            let dir = PathBuf::from(dir);
+            for path in scan_root(&dir) {
+                if !seen.insert(path.clone()) {
+                    continue;
+                }
+                let Ok(relative) = path.strip_prefix(&dir) else {
+                    continue;
+                };
+                let relative = relative
+                    .components()
+                    .map(|c| c.as_os_str().to_string_lossy())
+                    .collect::<Vec<_>>()
+                    .join("/");
+                saves.push(LocalSave {
+                    key: format!("{emulator}/{kind}/{relative}"),
+                    path,
+                });
+            }
+        }
+    }
+    saves
+}
+
+/// Where a cloud key is restored to on this device: the first configured
+/// folder of its emulator and kind.
+fn restore_path(roots: &[(String, &'static str, Vec<String>)], key: &str) -> Option<PathBuf> {
+    let mut parts = key.splitn(3, '/');
+    let (emulator, kind, relative) = (parts.next()?, parts.next()?, parts.next()?);
+    if relative
+        .split('/')
+        .any(|p| p.is_empty() || p == "." || p == "..")
+    {
+        return None;
+    }
+    let (_, _, dirs) = roots.iter().find(|(e, k, _)| e == emulator && *k == kind)?;
+    Some(Path::new(dirs.first()?).join(relative))
+}
+
+fn sha1_hex(bytes: &[u8]) -> String {
+    format!("{:x}", Sha1::digest(bytes))
+}
+
+fn modified_at(path: &Path) -> Option<String> {
+    let modified = std::fs::metadata(path).ok()?.modified().ok()?;
+    Some(chrono::DateTime::<chrono::Utc>::from(modified).to_rfc3339())
+}
+
+/// Hash of every file as of its last backup or restore.
+async fn synced_hashes(db: &DatabaseConnection) -> AppResult<HashMap<String, String>> {
+    #[derive(Debug, FromQueryResult)]
+    struct SyncRow {
+        remote_key: String,
+        sha1: String,
+    }
+
+    Ok(SyncRow::find_by_statement(Statement::from_string(
+        DatabaseBackend::Sqlite,
+        "SELECT remote_key, sha1 FROM cloud_save_sync",
+    ))
+    .all(db)
+    .await?
+    .into_iter()
+    .map(|r| (r.remote_key, r.sha1))
+    .collect())
+}
+
+async fn record_synced(db: &DatabaseConnection, key: &str, sha1: &str) -> AppResult<()> {
+    db.execute(Statement::from_sql_and_values(
+        DatabaseBackend::Sqlite,
+        "INSERT INTO cloud_save_sync (remote_key, sha1) VALUES (?, ?)
+         ON CONFLICT(remote_key) DO UPDATE SET
+           sha1 = excluded.sha1,
+           synced_at = strftime('%Y-%m-%dT%H:%M:%fZ', 'now')",
+        [key.into(), sha1.into()],
+    ))
+    .await?;
+    Ok(())
+}
+
+/// Upload the save files that changed since the last backup. A file whose
+/// cloud copy was replaced from another device since this one last synced
+/// is a conflict and stays as it is, unless `overwrite` is set.
+pub async fn backup(
+    db: &DatabaseConnection,
+    provider: &dyn CloudProvider,
+    overrides: &HashMap<String, SavePathOverride>,
+    overwrite: bool,
+) -> AppResult<CloudSyncReport> {
+    let mut manifest = load_manifest(provider).await?;
+    let synced = synced_hashes(db).await?;
+    let mut report = CloudSyncReport::default();
+    // Recorded as synced only once the manifest is uploaded, so an error part
+    // way through never leaves the database ahead of the manifest
+    let mut uploaded = Vec::new();
+
+    for save in local_saves(overrides) {
+        let bytes = tokio::fs::read(&save.path).await?;
+        let sha1 = sha1_hex(&bytes);
+        let local_modified_at = modified_at(&save.path);
+        if let Some(remote) = manifest.files.get(&save.key) {
+            if remote.sha1 == sha1 {
+                record_synced(db, &save.key, &sha1).await?;
+                report.unchanged += 1;
+                continue;
+            }
+            let remote_unchanged = synced.get(&save.key) == Some(&remote.sha1);
+            if !remote_unchanged && !overwrite {
+                report.conflicts.push(CloudConflict {
+                    key: save.key,
+                    local_path: save.path.to_string_lossy().into_owned(),
+                    local_modified_at,
+                    remote_modified_at: remote.modified_at.clone(),
+                });
+                continue;
+            }
+        }
+
+        let size = bytes.len() as u64;
+        provider.put(&save.key, bytes).await?;
+        uploaded.push((save.key.clone(), sha1.clone()));
+        manifest.files.insert(
+            save.key,
+            ManifestEntry {
+                sha1,
+                size,
+                modified_at: local_modified_at.unwrap_or_else(|| chrono::Utc::now().to_rfc3339()),
+            },
+        );
+        report.transferred += 1;
+    }
+
+    if report.transferred > 0 {
+        let body =
+            serde_json::to_vec_pretty(&manifest).map_err(|e| AppError::Other(e.to_string()))?;
+        provider.put(MANIFEST_NAME, body).await?;
+    }
+    for (key, sha1) in &uploaded {
+        record_synced(db, key, sha1).await?;
+    }
+    Ok(report)
+}
+
+/// Download the cloud copies that differ from the files here. A local file
+/// that changed since it was last synced is a conflict and stays as it is,
+/// unless `overwrite` is set.
+pub async fn restore(
+    db: &DatabaseConnection,
+    provider: &dyn CloudProvider,
+    overrides: &HashMap<String, SavePathOverride>,
+    overwrite: bool,
+) -> AppResult<CloudSyncReport> {
+    let manifest = load_manifest(provider).await?;
+    let synced = synced_hashes(db).await?;
+    let roots = save_roots(overrides);
+    let mut report = CloudSyncReport::default();
+
+    for (key, remote) in manifest.files {
+        let Some(path) = restore_path(&roots, &key) else {
+            log::warn!("No save folder on this device for cloud file {key}");
+            continue;
+        };
+        if let Ok(bytes) = tokio::fs::read(&path).await {
+            let sha1 = sha1_hex(&bytes);
+            if sha1 == remote.sha1 {
+                record_synced(db, &key, &sha1).await?;
+                report.unchanged += 1;
+                continue;
+            }
+            let local_unchanged = synced.get(&key) == Some(&sha1);
+            if !local_unchanged && !overwrite {
+                report.conflicts.push(CloudConflict {
+                    key,
+                    local_modified_at: modified_at(&path),
+                    local_path: path.to_string_lossy().into_owned(),
+                    remote_modified_at: remote.modified_at,
+                });
+                continue;
+            }
+        }
+
+        let bytes = provider
+            .get(&key)
+            .await?
+            .ok_or_else(|| AppError::Other(format!("Cloud file {key} is missing")))?;
+        if sha1_hex(&bytes) != remote.sha1 {
+            return Err(AppError::Other(format!(
+                "Cloud file {key} doesn't match the manifest"
+            )));
+        }
+        if let Some(parent) = path.parent() {
+            tokio::fs::create_dir_all(parent).await?;
+        }
+        tokio::fs::write(&path, &bytes).await?;
+        record_synced(db, &key, &remote.sha1).await?;
+        report.transferred += 1;
+    }
+    Ok(report)
+}
@@ -0,0 +1,308 @@
+//! Listing the game saves on PlayStation memory card images.
+//!
+//! - PS1 (`.mcr`, `.mcd`): raw 128 KiB cards of 16 8 KiB blocks, the first
+//!   holding a directory frame per block
+//! - PS2 (`.ps2`): 8 MiB FAT-style cards as PCSX2 writes them, with or
+//!   without the 16 spare ECC bytes after every 512-byte page
+
+use std::path::Path;
+
+use crate::models::MemoryCardSave;
+
+const PS1_CARD_SIZE: usize = 128 * 1024;
+const PS1_BLOCK_SIZE: usize = 8 * 1024;
+const PS1_FRAME_SIZE: usize = 128;
+
+const PS2_MAGIC: &[u8] = b"Sony PS2 Memory Card Format ";
+const PS2_DIR_ENTRY_SIZE: usize = 512;
+/// Largest card worth reading: PCSX2 also makes 16-64 MiB cards.
+const MAX_CARD_SIZE: u64 = 128 * 1024 * 1024;
+
+/// The saves on the memory card image at `path`, or None when it isn't a
+/// card format that can be read.
+pub fn list_saves(path: &Path, ext: &str) -> Option<Vec<MemoryCardSave>> {
+    if std::fs::metadata(path).ok()?.len() > MAX_CARD_SIZE {
+        return None;
+    }
+    let data = std::fs::read(path).ok()?;
+    match ext.to_lowercase().as_str() {
+        "mcr" | "mcd" => ps1_saves(&data),
+        "ps2" => Ps2Card::parse(&data)?.saves(),
+        _ => None,
+    }
+}
+
+/// `SLUS-01234` out of a save name like `BASLUS-01234GAME`, whose first two
+/// characters are the region.
+fn product_code(name: &str) -> Option<String> {
+    let code = name.get(2..12)?;
+    let valid = name.starts_with('B')
+        && code.as_bytes()[4] == b'-'
+        && code[..4].bytes().all(|b| b.is_ascii_alphabetic())
+        && code[5..].bytes().all(|b| b.is_ascii_digit());
+    valid.then(|| code.to_string())
+}
+
+/// A NUL-terminated ASCII name.
+fn c_string(bytes: &[u8]) -> String {
+    let end = bytes.iter().position(|&b| b == 0).unwrap_or(bytes.len());
+    String::from_utf8_lossy(&bytes[..end]).into_owned()
+}
+
+/// A Shift-JIS title as stored by PlayStation games, which mostly spell even
+/// Latin text with full-width characters.
+fn sjis_title(bytes: &[u8]) -> Option<String> {
+    let end = bytes.iter().position(|&b| b == 0).unwrap_or(bytes.len());
+    let (decoded, _) = encoding_rs::SHIFT_JIS.decode_without_bom_handling(&bytes[..end]);
+    let title: String = decoded
+        .chars()
+        .map(|c| match c {
+            '\u{3000}' => ' ',
+            '\u{FF01}'..='\u{FF5E}' => char::from_u32(c as u32 - 0xFEE0).unwrap_or(c),
+            _ => c,
+        })
+        .collect();
+    let title = title.split_whitespace().collect::<Vec<_>>().join(" ");
+    (!title.is_empty()).then_some(title)
+}
+
+fn u16_at(data: &[u8], offset: usize) -> Option<u16> {
+    Some(u16::from_le_bytes(
+        data.get(offset..offset + 2)?.try_into().ok()?,
+    ))
+}
+
+fn u32_at(data: &[u8], offset: usize) -> Option<u32> {
+    Some(u32::from_le_bytes(
+        data.get(offset..offset + 4)?.try_into().ok()?,
+    ))
+}
+
+fn ps1_saves(data: &[u8]) -> Option<Vec<MemoryCardSave>> {
+    if data.len() != PS1_CARD_SIZE || !data.starts_with(b"MC") {
+        return None;
+    }
+
+    let mut saves = Vec::new();
+    for block in 1..16 {
+        let frame = &data[block * PS1_FRAME_SIZE..(block + 1) * PS1_FRAME_SIZE];
+        // 0x51: in use, first block of a save
+        if u32_at(frame, 0)? != 0x51 {
+            continue;
+        }
+        let name = c_string(&frame[0x0A..0x1F]);
+        let title_frame = &data[block * PS1_BLOCK_SIZE..block * PS1_BLOCK_SIZE + PS1_FRAME_SIZE];
+        let title = title_frame
+            .starts_with(b"SC")
+            .then(|| sjis_title(&title_frame[0x04..0x44]))
+            .flatten();
+        saves.push(MemoryCardSave {
+            product_code: product_code(&name),
+            name,
+            title,
+            size_bytes: u64::from(u32_at(frame, 0x04)?),
+            modified_at: None,
+        });
+    }
+    Some(saves)
+}
+
+/// A PS2 card's superblock, enough of it to walk the file system.
+struct Ps2Card<'a> {
+    data: &'a [u8],
+    page_len: usize,
+    raw_page_len: usize,
+    pages_per_cluster: usize,
+    alloc_offset: u32,
+    rootdir_cluster: u32,
+    ifc_list: Vec<u32>,
+}
+
+/// A directory entry on a PS2 card.
+struct Ps2Entry {
+    mode: u16,
+    length: u32,
+    cluster: u32,
+    modified_at: Option<String>,
+    name: String,
+}
+
+impl Ps2Entry {
+    const EXISTS: u16 = 0x8000;
+    const DIRECTORY: u16 = 0x0020;
+
+    fn parse(bytes: &[u8]) -> Option<Self> {
+        Some(Self {
+            mode: u16_at(bytes, 0x00)?,
+            length: u32_at(bytes, 0x04)?,
+            cluster: u32_at(bytes, 0x10)?,
+            modified_at: ps2_time(bytes.get(0x18..0x20)?),
+            name: c_string(bytes.get(0x40..0x60)?),
+        })
+    }
+}
+
+/// A card timestamp: padding, seconds, minutes, hours, day, month, year.
+/// The console keeps them in Japan time, shown as is.
+fn ps2_time(tod: &[u8]) -> Option<String> {
+    let year = u16_at(tod, 6)?;
+    let (sec, min, hour, day, month) = (tod[1], tod[2], tod[3], tod[4], tod[5]);
+    if year == 0 || !(1..=12).contains(&month) || day == 0 {
+        return None;
+    }
+    Some(format!(
+        "{year:04}-{month:02}-{day:02}T{hour:02}:{min:02}:{sec:02}"
+    ))
+}
+
+impl<'a> Ps2Card<'a> {
+    /// Bound on cluster chains, so a corrupt FAT can't loop forever.
+    const MAX_CHAIN: usize = 1 << 16;
+
+    fn parse(data: &'a [u8]) -> Option<Self> {
+        if !data.starts_with(PS2_MAGIC) {
+            return None;
+        }
+        let page_len = usize::from(u16_at(data, 0x28)?);
+        let pages_per_cluster = usize::from(u16_at(data, 0x2A)?);
+        let clusters_per_card = u32_at(data, 0x30)? as usize;
+        // PCSX2 only writes 512-byte pages in clusters of up to 16; other
+        // geometry is a corrupt superblock, and a huge one would make every
+        // cluster read allocate gigabytes
+        if page_len != 512 || !(1..=16).contains(&pages_per_cluster) {
+            return None;
+        }
+        // Cards dumped with ECC keep 4 spare bytes per 128 bytes of each page
+        let spare_len = page_len / 128 * 4;
+        let pages = clusters_per_card.saturating_mul(pages_per_cluster);
+        let raw_page_len = if data.len() >= pages.saturating_mul(page_len + spare_len) {
+            page_len + spare_len
+        } else {
+            page_len
+        };
+        let ifc_list = (0..32)
+            .map_while(|i| u32_at(data, 0x50 + i * 4))
+            .filter(|&c| c != 0)
+            .collect();
+        Some(Self {
+            data,
+            page_len,
+            raw_page_len,
+            pages_per_cluster,
+            alloc_offset: u32_at(data, 0x34)?,
+            rootdir_cluster: u32_at(data, 0x3C)?,
+            ifc_list,
+        })
+    }
+
+    fn cluster_len(&self) -> usize {
+        self.page_len * self.pages_per_cluster
+    }
+
+    /// The contents of cluster `n`, counted from the start of the card.
+    fn cluster(&self, n: u32) -> Option<Vec<u8>> {
+        let first_page = (n as usize).checked_mul(self.pages_per_cluster)?;
+        let mut bytes = Vec::with_capacity(self.cluster_len());
+        for page in first_page..first_page + self.pages_per_cluster {
+            let start = page.checked_mul(self.raw_page_len)?;
+            bytes.extend_from_slice(self.data.get(start..start + self.page_len)?);
+        }
+        Some(bytes)
+    }
+
+    /// The FAT entry of allocatable cluster `n`, found through the indirect
+    /// FAT clusters the superblock lists.
+    fn fat_entry(&self, n: u32) -> Option<u32> {
+        let per_cluster = self.cluster_len() / 4;
+        let n = n as usize;
+        let fat_index = n / per_cluster;
+        let indirect = self.cluster(*self.ifc_list.get(fat_index / per_cluster)?)?;
+        let fat_cluster = u32_at(&indirect, fat_index % per_cluster * 4)?;
+        u32_at(&self.cluster(fat_cluster)?, n % per_cluster * 4)
+    }
+
+    /// The allocatable clusters of a file or directory starting at `first`.
+    fn chain(&self, first: u32) -> Vec<u32> {
+        const ALLOCATED: u32 = 0x8000_0000;
+        const END: u32 = 0x7FFF_FFFF;
+
+        let mut chain = Vec::new();
+        let mut next = first;
+        while chain.len() < Self::MAX_CHAIN {
+            chain.push(next);
+            match self.fat_entry(next) {
+                Some(entry) if entry & ALLOCATED != 0 && entry & END != END => {
+                    next = entry & END;
+                }
+                _ => break,
+            }
+        }
+        chain
+    }
+
+    /// The first `len` bytes of the file or directory starting at `first`.
+    fn read(&self, first: u32, len: usize) -> Option<Vec<u8>> {
+        let mut bytes = Vec::with_capacity(len);
+        for n in self.chain(first) {
+            if bytes.len() >= len {
+                break;
+            }
+            bytes.extend(self.cluster(n.checked_add(self.alloc_offset)?)?);
+        }
+        bytes.truncate(len);
+        Some(bytes)
+    }
+
+    /// The entries of the directory starting at `first`, its `.` entry
+    /// giving their count.
+    fn entries(&self, first: u32) -> Option<Vec<Ps2Entry>> {
+        let head = Ps2Entry::parse(&self.read(first, PS2_DIR_ENTRY_SIZE)?)?;
+        let count = (head.length as usize).min(Self::MAX_CHAIN);
+        let bytes = self.read(first, count * PS2_DIR_ENTRY_SIZE)?;
+        Some(
+            bytes
+                .chunks_exact(PS2_DIR_ENTRY_SIZE)
+                .filter_map(Ps2Entry::parse)
+                .filter(|e| e.mode & Ps2Entry::EXISTS != 0 && e.name != "." && e.name != "..")
+                .collect(),
+        )
+    }
+
+    /// One save per directory in the root, titled from its `icon.sys`.
+    fn saves(&self) -> Option<Vec<MemoryCardSave>> {
+        let saves = self
+            .entries(self.rootdir_cluster)?
+            .into_iter()
+            .filter(|e| e.mode & Ps2Entry::DIRECTORY != 0)
+            .map(|dir| {
+                let files = self.entries(dir.cluster).unwrap_or_default();
+                let title = files
+                    .iter()
+                    .find(|f| f.name.eq_ignore_ascii_case("icon.sys"))
+                    .and_then(|f| self.icon_sys_title(f));
+                MemoryCardSave {
+                    product_code: product_code(&dir.name),
+                    title,
+                    size_bytes: files.iter().map(|f| u64::from(f.length)).sum(),
+                    modified_at: dir.modified_at,
+                    name: dir.name,
+                }
+            })
+            .collect();
+        Some(saves)
+    }
+
+    /// The title in an `icon.sys`, whose two lines are split at the byte
+    /// offset stored at 0x06.
+    fn icon_sys_title(&self, file: &Ps2Entry) -> Option<String> {
+        let icon = self.read(file.cluster, 0x104)?;
+        if !icon.starts_with(b"PS2D") {
+            return None;
+        }
+        let title = icon.get(0xC0..0x104)?;
+        let split = usize::from(u16_at(&icon, 0x06)?).min(title.len());
+        let lines = [sjis_title(&title[..split]), sjis_title(&title[split..])];
+        let title = lines.into_iter().flatten().collect::<Vec<_>>().join(" ");
+        (!title.is_empty()).then_some(title)
+    }
+}
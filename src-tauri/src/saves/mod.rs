@@ -0,0 +1,936 @@
+pub mod cloud;
+mod memcard;
+
+use std::collections::HashMap;
+use std::io::{BufRead, Read};
+use std::path::{Path, PathBuf};
+
+use chrono::{DateTime, Utc};
+
+use crate::models::{SaveFileInfo, SaveFormat, SaveType};
+
+/// Default save and state directories for a given emulator.
+pub struct EmulatorSavePaths {
+    pub save_dirs: Vec<String>,
+    pub state_dirs: Vec<String>,
+}
+
+/// Folders of the OS that emulators keep their data under.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Base {
+    /// `~/Library/Application Support`, `%APPDATA%` or `~/.local/share`.
+    Data,
+    /// `~/Library/Application Support`, `%LOCALAPPDATA%` or `~/.local/share`.
+    LocalData,
+    /// `~/Library/Application Support`, `%APPDATA%` or `~/.config`.
+    Config,
+    /// The user's Documents folder.
+    Documents,
+    Home,
+    /// The root of the drive Windows is installed on, usually `C:\`.
+    SystemDrive,
+}
+
+impl Base {
+    fn dir(self) -> Option<PathBuf> {
+        match self {
+            Self::Data => dirs::data_dir(),
+            Self::LocalData => dirs::data_local_dir(),
+            Self::Config => dirs::config_dir(),
+            Self::Documents => dirs::document_dir(),
+            Self::Home => dirs::home_dir(),
+            Self::SystemDrive => {
+                let drive = std::env::var("SystemDrive").unwrap_or_else(|_| "C:".to_string());
+                Some(PathBuf::from(format!("{drive}\\")))
+            }
+        }
+    }
+}
+
+/// Where each emulator keeps its data on macOS.
+const MACOS_DATA_DIRS: &[(&str, Base, &str)] = &[
+    ("retroarch", Base::Data, "RetroArch"),
+    ("dolphin", Base::Data, "Dolphin"),
+    ("duckstation", Base::Data, "DuckStation"),
+    ("pcsx2", Base::Data, "PCSX2"),
+    ("mgba", Base::Data, "mGBA"),
+    ("melonds", Base::Data, "melonDS"),
+    ("cemu", Base::Data, "Cemu"),
+    ("xemu", Base::Data, "xemu"),
+    ("rpcs3", Base::Data, "rpcs3"),
+];
+
+/// Where each emulator keeps its data on Windows, most likely first. Older
+/// Dolphin and DuckStation versions used Documents; RetroArch and Cemu are
+/// often unzipped rather than installed, keeping everything next to the exe.
+const WINDOWS_DATA_DIRS: &[(&str, Base, &str)] = &[
+    ("retroarch", Base::Data, "RetroArch"),
+    ("retroarch", Base::SystemDrive, "RetroArch-Win64"),
+    ("retroarch", Base::SystemDrive, "RetroArch"),
+    ("dolphin", Base::Data, "Dolphin Emulator"),
+    ("dolphin", Base::Documents, "Dolphin Emulator"),
+    ("duckstation", Base::LocalData, "DuckStation"),
+    ("duckstation", Base::Documents, "DuckStation"),
+    ("pcsx2", Base::Documents, "PCSX2"),
+    ("mgba", Base::Data, "mGBA"),
+    ("melonds", Base::Data, "melonDS"),
+    ("cemu", Base::Data, "Cemu"),
+    ("cemu", Base::SystemDrive, "Cemu"),
+    ("xemu", Base::Data, "xemu/xemu"),
+    ("rpcs3", Base::SystemDrive, "RPCS3"),
+];
+
+/// Where each emulator keeps its data on Linux, the Flatpak's folder second.
+const LINUX_DATA_DIRS: &[(&str, Base, &str)] = &[
+    ("retroarch", Base::Config, "retroarch"),
+    (
+        "retroarch",
+        Base::Home,
+        ".var/app/org.libretro.RetroArch/config/retroarch",
+    ),
+    ("dolphin", Base::Data, "dolphin-emu"),
+    (
+        "dolphin",
+        Base::Home,
+        ".var/app/org.DolphinEmu.dolphin-emu/data/dolphin-emu",
+    ),
+    ("duckstation", Base::Data, "duckstation"),
+    (
+        "duckstation",
+        Base::Home,
+        ".var/app/org.duckstation.DuckStation/data/duckstation",
+    ),
+    ("pcsx2", Base::Config, "PCSX2"),
+    ("pcsx2", Base::Home, ".var/app/net.pcsx2.PCSX2/config/PCSX2"),
+    ("mgba", Base::Config, "mgba"),
+    ("melonds", Base::Config, "melonDS"),
+    ("cemu", Base::Data, "Cemu"),
+    ("xemu", Base::Data, "xemu/xemu"),
+    ("rpcs3", Base::Config, "rpcs3"),
+];
+
+/// Save and state folders inside an emulator's data folder, the same on
+/// every OS.
+const SAVE_SUBDIRS: &[(&str, &[&str], &[&str])] = &[
+    ("dolphin", &["GC", "Wii"], &["StateSaves"]),
+    ("duckstation", &["memcards"], &["savestates"]),
+    ("pcsx2", &["memcards"], &["sstates"]),
+    ("melonds", &["saves"], &["states"]),
+    ("cemu", &["mlc01/usr/save"], &[]),
+    ("xemu", &[""], &[]),
+    (
+        "rpcs3",
+        &["dev_hdd0/home/00000001/savedata"],
+        &["savestates"],
+    ),
+];
+
+/// `base` with a `/`-separated relative path appended one component at a
+/// time, so Windows paths don't end up with mixed separators.
+fn join_relative(base: &Path, relative: &str) -> PathBuf {
+    relative
+        .split('/')
+        .filter(|c| !c.is_empty())
+        .fold(base.to_path_buf(), |path, c| path.join(c))
+}
+
+/// The data folder table of the OS this runs on.
+fn data_dir_table() -> &'static [(&'static str, Base, &'static str)] {
+    if cfg!(target_os = "macos") {
+        MACOS_DATA_DIRS
+    } else if cfg!(target_os = "windows") {
+        WINDOWS_DATA_DIRS
+    } else {
+        LINUX_DATA_DIRS
+    }
+}
+
+/// The entries of `table` for `emulator`, most likely first.
+fn table_entries<'a>(
+    table: &'a [(&'a str, Base, &'a str)],
+    emulator: &'a str,
+) -> impl Iterator<Item = (Base, &'a str)> + 'a {
+    table
+        .iter()
+        .filter(move |(id, _, _)| *id == emulator)
+        .map(|&(_, base, relative)| (base, relative))
+}
+
+/// Every folder `emulator` may keep its data in on this OS.
+fn data_dir_candidates(emulator: &str) -> Vec<PathBuf> {
+    table_entries(data_dir_table(), emulator)
+        .filter_map(|(base, relative)| Some(join_relative(&base.dir()?, relative)))
+        .collect()
+}
+
+/// The folders `emulator` keeps its data in: those of its candidates that
+/// exist, or the most likely one when none does yet.
+fn data_dirs(emulator: &str) -> Vec<PathBuf> {
+    existing_or_first(data_dir_candidates(emulator))
+}
+
+/// Those of `candidates` that exist, or the first when none does.
+fn existing_or_first(candidates: Vec<PathBuf>) -> Vec<PathBuf> {
+    let existing: Vec<PathBuf> = candidates.iter().filter(|p| p.is_dir()).cloned().collect();
+    if existing.is_empty() {
+        candidates.into_iter().take(1).collect()
+    } else {
+        existing
+    }
+}
+
+/// A path in a form that compares equal to other spellings of the same
+/// folder. Windows paths are case-insensitive and take either separator.
+pub fn path_key(path: &str) -> String {
+    path_key_for(path, cfg!(target_os = "windows"))
+}
+
+fn path_key_for(path: &str, windows: bool) -> String {
+    if windows {
+        path.replace('/', "\\")
+            .trim_end_matches('\\')
+            .to_lowercase()
+    } else {
+        path.trim_end_matches('/').to_string()
+    }
+}
+
+/// Add `dir` to `dirs` unless it's already there under another spelling.
+pub fn push_dir(dirs: &mut Vec<String>, dir: String) {
+    let key = path_key(&dir);
+    if !dirs.iter().any(|d| path_key(d) == key) {
+        dirs.push(dir);
+    }
+}
+
+/// Read RetroArch's retroarch.cfg to get the actual configured save/state directories.
+/// Falls back to the `saves` and `states` folders next to the config (or in
+/// RetroArch's usual folder, without one) for anything not set.
+fn read_retroarch_config_dirs() -> (Vec<String>, Vec<String>) {
+    let cfg = retroarch_config_path();
+    let root = cfg
+        .as_deref()
+        .and_then(Path::parent)
+        .map(|dir| {
+            if dir.ends_with("config") {
+                dir.parent().unwrap_or(dir)
+            } else {
+                dir
+            }
+        })
+        .map(Path::to_path_buf)
+        .or_else(|| data_dirs("retroarch").into_iter().next())
+        .unwrap_or_default();
+
+    let default_saves = root.join("saves").to_string_lossy().into_owned();
+    let default_states = root.join("states").to_string_lossy().into_owned();
+
+    // Try to read RetroArch config
+    let Some(cfg) = cfg else {
+        return (vec![default_saves], vec![default_states]);
+    };
+
+    let file = match std::fs::File::open(&cfg) {
+        Ok(f) => f,
+        Err(_) => return (vec![default_saves], vec![default_states]),
+    };
+
+    let mut save_dir = None;
+    let mut state_dir = None;
+    let reader = std::io::BufReader::new(file);
+
+    for line in reader.lines().map_while(Result::ok) {
+        let line = line.trim().to_string();
+        if let Some(val) = parse_retroarch_cfg_value(&line, "savefile_directory") {
+            save_dir = Some(resolve_retroarch_dir(&val, &root));
+        } else if let Some(val) = parse_retroarch_cfg_value(&line, "savestate_directory") {
+            state_dir = Some(resolve_retroarch_dir(&val, &root));
+        }
+        if save_dir.is_some() && state_dir.is_some() {
+            break;
+        }
+    }
+
+    (
+        vec![save_dir.unwrap_or(default_saves)],
+        vec![state_dir.unwrap_or(default_states)],
+    )
+}
+
+/// Read mGBA's config.ini in `root` for the configured save game and save
+/// state directories. Falls back to folders in `root` for anything not set.
+fn read_mgba_config_dirs(root: &Path) -> (String, String) {
+    let mut save_dir = root.join("saves").to_string_lossy().into_owned();
+    let mut state_dir = root.join("states").to_string_lossy().into_owned();
+
+    let Ok(contents) = std::fs::read_to_string(root.join("config.ini")) else {
+        return (save_dir, state_dir);
+    };
+    for line in contents.lines() {
+        let Some((key, value)) = line.split_once('=') else {
+            continue;
+        };
+        let value = value.trim();
+        if value.is_empty() {
+            continue;
+        }
+        match key.trim() {
+            "savegamePath" => save_dir = expand_tilde(value),
+            "savestatePath" => state_dir = expand_tilde(value),
+            _ => {}
+        }
+    }
+    (save_dir, state_dir)
+}
+
+/// Locate RetroArch's retroarch.cfg, if it exists.
+pub fn retroarch_config_path() -> Option<PathBuf> {
+    data_dir_candidates("retroarch")
+        .into_iter()
+        .flat_map(|dir| {
+            [
+                dir.join("config").join("retroarch.cfg"),
+                dir.join("retroarch.cfg"),
+            ]
+        })
+        .find(|p| p.exists())
+}
+
+/// Read a single setting from a retroarch.cfg file.
+pub fn read_retroarch_cfg_value(cfg: &Path, key: &str) -> Option<String> {
+    let file = std::fs::File::open(cfg).ok()?;
+    std::io::BufReader::new(file)
+        .lines()
+        .map_while(Result::ok)
+        .find_map(|line| parse_retroarch_cfg_value(&line, key))
+}
+
+/// Set `key = "value"` entries in a retroarch.cfg file, rewriting existing
+/// lines in place and appending any keys that aren't present yet.
+pub fn patch_retroarch_cfg(cfg: &Path, updates: &[(&str, &str)]) -> std::io::Result<()> {
+    let contents = std::fs::read_to_string(cfg)?;
+    let mut pending: Vec<(&str, &str)> = updates.to_vec();
+    let mut out = String::with_capacity(contents.len() + 128);
+
+    for line in contents.lines() {
+        let key = line.split('=').next().unwrap_or_default().trim();
+        if let Some(idx) = pending.iter().position(|(k, _)| *k == key) {
+            let (k, v) = pending.remove(idx);
+            out.push_str(&format!("{k} = \"{v}\"\n"));
+        } else {
+            out.push_str(line);
+            out.push('\n');
+        }
+    }
+    for (k, v) in pending {
+        out.push_str(&format!("{k} = \"{v}\"\n"));
+    }
+
+    // Write to a sibling temp file and rename so RetroArch never sees a truncated config
+    let tmp = cfg.with_extension("cfg.tmp");
+    std::fs::write(&tmp, out)?;
+    std::fs::rename(&tmp, cfg)
+}
+
+/// Parse a key = "value" line from retroarch.cfg
+fn parse_retroarch_cfg_value(line: &str, key: &str) -> Option<String> {
+    let trimmed = line.trim();
+    if !trimmed.starts_with(key) {
+        return None;
+    }
+    let rest = trimmed[key.len()..].trim();
+    let rest = rest.strip_prefix('=')?;
+    let rest = rest.trim();
+    let rest = rest.trim_matches('"');
+    if rest.is_empty() {
+        return None;
+    }
+    Some(rest.to_string())
+}
+
+/// Expand ~ to home directory
+pub fn expand_tilde(path: &str) -> String {
+    if let Some(stripped) = path.strip_prefix("~/").or_else(|| path.strip_prefix("~\\")) {
+        if let Some(home) = dirs::home_dir() {
+            return home.join(stripped).to_string_lossy().into_owned();
+        }
+    }
+    path.to_string()
+}
+
+/// A directory from retroarch.cfg. RetroArch writes `:\saves` (or `:/saves`)
+/// for a folder next to itself, taken here as next to its config, which is
+/// where portable Windows installs keep it.
+fn resolve_retroarch_dir(value: &str, root: &Path) -> String {
+    match value
+        .strip_prefix(":\\")
+        .or_else(|| value.strip_prefix(":/"))
+    {
+        Some(relative) => join_relative(root, &relative.replace('\\', "/"))
+            .to_string_lossy()
+            .into_owned(),
+        None => expand_tilde(value),
+    }
+}
+
+/// Returns a map of emulator id -> default save/state paths on this OS.
+pub fn default_save_paths() -> HashMap<&'static str, EmulatorSavePaths> {
+    let mut map = HashMap::new();
+
+    // RetroArch: read actual config to get real directories
+    let (ra_saves, ra_states) = read_retroarch_config_dirs();
+    map.insert(
+        "retroarch",
+        EmulatorSavePaths {
+            save_dirs: ra_saves,
+            state_dirs: ra_states,
+        },
+    );
+
+    // mGBA: honour savegamePath/savestatePath from its config (.ss0-.ss9 states)
+    if let Some(root) = data_dirs("mgba").into_iter().next() {
+        let (mgba_saves, mgba_states) = read_mgba_config_dirs(&root);
+        map.insert(
+            "mgba",
+            EmulatorSavePaths {
+                save_dirs: vec![mgba_saves],
+                state_dirs: vec![mgba_states],
+            },
+        );
+    }
+
+    for &(emulator, saves, states) in SAVE_SUBDIRS {
+        let roots = data_dirs(emulator);
+        if roots.is_empty() {
+            continue;
+        }
+        let under_roots = |subdirs: &[&str]| -> Vec<String> {
+            roots
+                .iter()
+                .flat_map(|root| subdirs.iter().map(move |sub| join_relative(root, sub)))
+                .map(|p| p.to_string_lossy().into_owned())
+                .collect()
+        };
+        map.insert(
+            emulator,
+            EmulatorSavePaths {
+                save_dirs: under_roots(saves),
+                state_dirs: under_roots(states),
+            },
+        );
+    }
+
+    map
+}
+
+/// Classify a file extension as either a save file or save state.
+pub fn classify_extension(ext: &str) -> Option<SaveType> {
+    let ext_lower = ext.to_lowercase();
+
+    // Save file extensions
+    match ext_lower.as_str() {
+        "sav" | "srm" | "eep" | "fla" | "mcr" | "mcd" | "ps2" | "bin" => {
+            return Some(SaveType::SaveFile);
+        }
+        _ => {}
+    }
+
+    // Save state extensions
+    match ext_lower.as_str() {
+        "state" | "undo" | "oops" | "p2s" => {
+            return Some(SaveType::SaveState);
+        }
+        _ => {}
+    }
+
+    // state0-state99
+    if let Some(rest) = ext_lower.strip_prefix("state") {
+        if let Ok(n) = rest.parse::<u32>() {
+            if n <= 99 {
+                return Some(SaveType::SaveState);
+            }
+        }
+    }
+
+    // ss0-ss9 (mGBA)
+    if let Some(rest) = ext_lower.strip_prefix("ss") {
+        if let Ok(n) = rest.parse::<u32>() {
+            if n <= 9 {
+                return Some(SaveType::SaveState);
+            }
+        }
+    }
+
+    // s01-s99 (Dolphin)
+    if let Some(rest) = ext_lower.strip_prefix('s') {
+        if let Ok(n) = rest.parse::<u32>() {
+            if (1..=99).contains(&n) {
+                return Some(SaveType::SaveState);
+            }
+        }
+    }
+
+    None
+}
+
+/// Extract a slot number from a save state extension (e.g., "state3" -> 3, "state" -> 0, "ss1" -> 1, "s01" -> 1).
+pub fn extract_slot(ext: &str) -> Option<u32> {
+    let ext_lower = ext.to_lowercase();
+
+    // state / state0-state99 (RetroArch)
+    if let Some(rest) = ext_lower.strip_prefix("state") {
+        if rest.is_empty() {
+            return Some(0);
+        }
+        if let Ok(n) = rest.parse::<u32>() {
+            return Some(n);
+        }
+    }
+
+    // ss / ss0-ss9 (mGBA)
+    if let Some(rest) = ext_lower.strip_prefix("ss") {
+        if rest.is_empty() {
+            return Some(0);
+        }
+        if let Ok(n) = rest.parse::<u32>() {
+            return Some(n);
+        }
+    }
+
+    // s01-s99 (Dolphin)
+    if let Some(rest) = ext_lower.strip_prefix('s') {
+        if let Ok(n) = rest.parse::<u32>() {
+            return Some(n);
+        }
+    }
+
+    // p2s (PCSX2) — single format, no slot number
+    if ext_lower == "p2s" {
+        return Some(0);
+    }
+
+    None
+}
+
+/// What a save holds, from its type and extension.
+fn save_format(ext: &str, save_type: &SaveType) -> SaveFormat {
+    match save_type {
+        SaveType::SaveState => SaveFormat::SaveState,
+        SaveType::SaveFile => match ext.to_lowercase().as_str() {
+            "mcr" | "mcd" | "ps2" => SaveFormat::MemoryCard,
+            _ => SaveFormat::Sram,
+        },
+    }
+}
+
+/// Folder names (lowercased) that give away the emulator a save belongs to.
+const EMULATOR_FOLDERS: &[(&str, &str)] = &[
+    ("retroarch", "retroarch"),
+    ("retroarch-win64", "retroarch"),
+    ("dolphin", "dolphin"),
+    ("dolphin emulator", "dolphin"),
+    ("dolphin-emu", "dolphin"),
+    ("duckstation", "duckstation"),
+    ("pcsx2", "pcsx2"),
+    ("mgba", "mgba"),
+    ("melonds", "melonds"),
+    ("cemu", "cemu"),
+    ("xemu", "xemu"),
+    ("rpcs3", "rpcs3"),
+];
+
+/// The emulator that wrote a save: from an extension only it uses, else
+/// from the nearest of its folders in the save's path. Saves kept next to
+/// the ROM in a format several emulators share stay unknown.
+fn identify_emulator(path: &Path, ext: &str) -> Option<&'static str> {
+    let ext = ext.to_lowercase();
+    let numbered = |prefix: &str| {
+        ext.strip_prefix(prefix)
+            .is_some_and(|n| n.parse::<u32>().is_ok())
+    };
+    let by_ext = match ext.as_str() {
+        "srm" | "state" | "undo" | "oops" => Some("retroarch"),
+        "p2s" | "ps2" => Some("pcsx2"),
+        _ if numbered("state") => Some("retroarch"),
+        _ if numbered("ss") => Some("mgba"),
+        _ if numbered("s") => Some("dolphin"),
+        _ => None,
+    };
+    by_ext.or_else(|| {
+        path.parent()?.components().rev().find_map(|c| {
+            let name = c.as_os_str().to_str()?.to_lowercase();
+            EMULATOR_FOLDERS
+                .iter()
+                .find(|(folder, _)| *folder == name)
+                .map(|(_, emulator)| *emulator)
+        })
+    })
+}
+
+const PNG_SIGNATURE: &[u8; 8] = b"\x89PNG\r\n\x1a\n";
+
+/// Where screenshots pulled out of save states are cached.
+fn state_thumbnail_dir() -> PathBuf {
+    directories::ProjectDirs::from("com", "romm-buddy", "romm-buddy").map_or_else(
+        || PathBuf::from("state_thumbnails"),
+        |p| p.cache_dir().join("state_thumbnails"),
+    )
+}
+
+/// A screenshot stored inside a save state:
+/// - mGBA writes states as PNG screenshots carrying the state in an extra
+///   chunk, so the state file itself is the image
+/// - PCSX2 `.p2s` states are zips with a `Screenshot.png` entry, extracted
+///   into the thumbnail cache keyed by path and modification time
+fn embedded_screenshot(path: &Path, ext: &str, modified_at: &str) -> Option<PathBuf> {
+    let mut magic = [0u8; 8];
+    std::fs::File::open(path).ok()?.read_exact(&mut magic).ok()?;
+    if &magic == PNG_SIGNATURE {
+        return Some(path.to_path_buf());
+    }
+    if !ext.eq_ignore_ascii_case("p2s") {
+        return None;
+    }
+
+    let key = crc32fast::hash(format!("{}|{modified_at}", path.display()).as_bytes());
+    let cached = state_thumbnail_dir().join(format!("{key:08x}.png"));
+    if cached.is_file() {
+        return Some(cached);
+    }
+    let mut archive = zip::ZipArchive::new(std::fs::File::open(path).ok()?).ok()?;
+    let mut entry = archive.by_name("Screenshot.png").ok()?;
+    let mut bytes = Vec::new();
+    entry.read_to_end(&mut bytes).ok()?;
+    std::fs::create_dir_all(cached.parent()?).ok()?;
+    std::fs::write(&cached, bytes).ok()?;
+    Some(cached)
+}
+
+/// Build a [`SaveFileInfo`] for a file on disk, picking up a same-named
+/// screenshot (RetroArch's `.state1.png` thumbnails) or one embedded in a
+/// state, and listing the saves on a memory card.
+fn build_save_info(path: &Path, save_type: SaveType, slot: Option<u32>) -> Option<SaveFileInfo> {
+    let file_name = path.file_name()?.to_str()?.to_string();
+    let metadata = std::fs::metadata(path).ok()?;
+
+    let modified_at = metadata
+        .modified()
+        .ok()
+        .map(|t| {
+            let dt: DateTime<Utc> = t.into();
+            dt.to_rfc3339()
+        })
+        .unwrap_or_default();
+
+    // Look for a screenshot with the same base name
+    let ext = path.extension().and_then(|e| e.to_str()).unwrap_or_default();
+    let screenshot_path = {
+        let ss_png = path.with_extension(format!("{ext}.png"));
+        let ss_plain = path.with_extension("png");
+        if ss_png.is_file() {
+            Some(ss_png.to_string_lossy().into_owned())
+        } else if ss_plain.is_file() {
+            Some(ss_plain.to_string_lossy().into_owned())
+        } else if matches!(save_type, SaveType::SaveState) {
+            embedded_screenshot(path, ext, &modified_at).map(|p| p.to_string_lossy().into_owned())
+        } else {
+            None
+        }
+    };
+
+    let format = save_format(ext, &save_type);
+    let card_saves = match format {
+        SaveFormat::MemoryCard => memcard::list_saves(path, ext).unwrap_or_default(),
+        _ => Vec::new(),
+    };
+
+    Some(SaveFileInfo {
+        file_name,
+        file_path: path.to_string_lossy().into_owned(),
+        save_type,
+        format,
+        emulator: identify_emulator(path, ext).map(str::to_string),
+        size_bytes: metadata.len(),
+        modified_at,
+        slot,
+        screenshot_path,
+        card_saves,
+    })
+}
+
+/// Scan directories for save files matching the given ROM file name.
+///
+/// Matches files whose stem matches the ROM's file stem (without extension),
+/// ignoring case as Windows and macOS file systems do.
+/// Returns results sorted by `modified_at` descending (newest first).
+pub fn scan_for_saves(
+    rom_file_name: &str,
+    save_dirs: &[String],
+    state_dirs: &[String],
+) -> Vec<SaveFileInfo> {
+    let rom_stem = Path::new(rom_file_name)
+        .file_stem()
+        .and_then(|s| s.to_str())
+        .unwrap_or("")
+        .to_lowercase();
+
+    if rom_stem.is_empty() {
+        return Vec::new();
+    }
+
+    let mut results = Vec::new();
+
+    let scan_dir = |dir_path: &str, results: &mut Vec<SaveFileInfo>| {
+        let dir = Path::new(dir_path);
+        if !dir.is_dir() {
+            return;
+        }
+
+        // Collect all files: top-level + one level of subdirectories
+        // (RetroArch organizes saves in subdirs per core, e.g., states/Stella/)
+        let mut files_to_check = Vec::new();
+        if let Ok(entries) = std::fs::read_dir(dir) {
+            for entry in entries.flatten() {
+                let path = entry.path();
+                if path.is_file() {
+                    files_to_check.push(path);
+                } else if path.is_dir() {
+                    // Scan one level deep into subdirectories
+                    if let Ok(sub_entries) = std::fs::read_dir(&path) {
+                        for sub_entry in sub_entries.flatten() {
+                            let sub_path = sub_entry.path();
+                            if sub_path.is_file() {
+                                files_to_check.push(sub_path);
+                            }
+                        }
+                    }
+                }
+            }
+        }
+
+        for path in &files_to_check {
+            if !path.is_file() {
+                continue;
+            }
+
+            let file_stem = match path.file_stem().and_then(|s| s.to_str()) {
+                Some(s) => s.to_lowercase(),
+                None => continue,
+            };
+
+            // Check if this file's stem matches the ROM stem
+            if file_stem != rom_stem {
+                continue;
+            }
+
+            let ext = match path.extension().and_then(|e| e.to_str()) {
+                Some(e) => e,
+                None => continue,
+            };
+
+            let save_type = match classify_extension(ext) {
+                Some(t) => t,
+                None => continue,
+            };
+
+            if let Some(info) = build_save_info(path, save_type, extract_slot(ext)) {
+                results.push(info);
+            }
+        }
+    };
+
+    // Scan each folder once, however many times and spellings it's listed in
+    let mut dirs = Vec::new();
+    for dir in save_dirs.iter().chain(state_dirs) {
+        push_dir(&mut dirs, dir.clone());
+    }
+    for dir in &dirs {
+        scan_dir(dir, &mut results);
+    }
+
+    // Deduplicate by file_path (same file can be found from multiple scan dirs,
+    // spelled differently on Windows)
+    results.sort_by_cached_key(|r| path_key(&r.file_path));
+    results.dedup_by(|a, b| path_key(&a.file_path) == path_key(&b.file_path));
+
+    // Sort by modified_at descending (newest first)
+    results.sort_by(|a, b| b.modified_at.cmp(&a.modified_at));
+
+    results
+}
+
+/// Find save states for standalone emulators that name them after the game's
+/// disc ID instead of the ROM file (which `scan_for_saves` can't match):
+/// - Dolphin: `StateSaves/<GameID>.s01`-`.s10`
+/// - DuckStation: `savestates/<serial>_<slot>.sav` and `<serial>_resume.sav`
+pub fn scan_emulator_states(
+    emulator: &str,
+    rom_path: Option<&Path>,
+    state_dirs: &[String],
+) -> Vec<SaveFileInfo> {
+    let Some(rom_path) = rom_path else {
+        return Vec::new();
+    };
+
+    let key = match emulator {
+        "dolphin" => crate::hash::gamecube_game_id(rom_path),
+        "duckstation" => crate::hash::psx_serial(rom_path),
+        _ => None,
+    };
+    let Some(key) = key else {
+        return Vec::new();
+    };
+    let key = key.to_lowercase();
+
+    let mut results = Vec::new();
+    for dir in state_dirs {
+        let Ok(entries) = std::fs::read_dir(dir) else {
+            continue;
+        };
+        for path in entries.flatten().map(|e| e.path()).filter(|p| p.is_file()) {
+            let Some(stem) = path.file_stem().and_then(|s| s.to_str()).map(str::to_lowercase) else {
+                continue;
+            };
+            let Some(ext) = path.extension().and_then(|e| e.to_str()) else {
+                continue;
+            };
+
+            let slot = match emulator {
+                "dolphin"
+                    if stem == key && matches!(classify_extension(ext), Some(SaveType::SaveState)) =>
+                {
+                    extract_slot(ext)
+                }
+                "duckstation" if ext.eq_ignore_ascii_case("sav") => {
+                    match stem.strip_prefix(&key).and_then(|r| r.strip_prefix('_')) {
+                        Some("resume") => None,
+                        Some(n) => match n.parse::<u32>() {
+                            Ok(n) => Some(n),
+                            Err(_) => continue,
+                        },
+                        None => continue,
+                    }
+                }
+                _ => continue,
+            };
+
+            if let Some(mut info) = build_save_info(&path, SaveType::SaveState, slot) {
+                info.emulator = Some(emulator.to_string());
+                results.push(info);
+            }
+        }
+    }
+    results
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn join_relative_appends_each_component() {
+        let base = Path::new("root");
+        assert_eq!(
+            join_relative(base, "mlc01/usr//save/"),
+            base.join("mlc01").join("usr").join("save")
+        );
+        assert_eq!(join_relative(base, ""), base);
+    }
+
+    #[test]
+    fn path_key_folds_windows_spellings() {
+        assert_eq!(
+            path_key_for(r"C:\Users\Me\AppData\Roaming\RetroArch\saves\", true),
+            path_key_for("c:/users/me/appdata/roaming/retroarch/saves", true)
+        );
+        assert_ne!(
+            path_key_for("/home/me/Saves", false),
+            path_key_for("/home/me/saves", false)
+        );
+        assert_eq!(path_key_for("/home/me/saves/", false), "/home/me/saves");
+    }
+
+    #[test]
+    fn push_dir_skips_other_spellings() {
+        let mut dirs = vec!["/roms/gba".to_string()];
+        push_dir(&mut dirs, "/roms/gba/".to_string());
+        push_dir(&mut dirs, "/roms/snes".to_string());
+        assert_eq!(dirs, ["/roms/gba", "/roms/snes"]);
+    }
+
+    #[test]
+    fn resolve_retroarch_dir_handles_relative_and_home_forms() {
+        let root = Path::new("RetroArch-Win64");
+        let saves = root.join("saves").to_string_lossy().into_owned();
+        assert_eq!(resolve_retroarch_dir(r":\saves", root), saves);
+        assert_eq!(resolve_retroarch_dir(":/saves", root), saves);
+        assert_eq!(
+            resolve_retroarch_dir(r":\saves\gba", root),
+            root.join("saves").join("gba").to_string_lossy()
+        );
+
+        let home = dirs::home_dir().unwrap();
+        let states = home.join("states").to_string_lossy().into_owned();
+        assert_eq!(resolve_retroarch_dir("~/states", root), states);
+        assert_eq!(resolve_retroarch_dir(r"~\states", root), states);
+        assert_eq!(resolve_retroarch_dir("/srv/saves", root), "/srv/saves");
+    }
+
+    fn entries(
+        table: &'static [(&'static str, Base, &'static str)],
+        emulator: &'static str,
+    ) -> Vec<(Base, &'static str)> {
+        table_entries(table, emulator).collect()
+    }
+
+    #[test]
+    fn data_dir_tables_cover_each_os() {
+        assert_eq!(
+            entries(WINDOWS_DATA_DIRS, "pcsx2"),
+            [(Base::Documents, "PCSX2")]
+        );
+        assert_eq!(
+            entries(WINDOWS_DATA_DIRS, "duckstation"),
+            [
+                (Base::LocalData, "DuckStation"),
+                (Base::Documents, "DuckStation")
+            ]
+        );
+        assert_eq!(
+            entries(WINDOWS_DATA_DIRS, "retroarch"),
+            [
+                (Base::Data, "RetroArch"),
+                (Base::SystemDrive, "RetroArch-Win64"),
+                (Base::SystemDrive, "RetroArch"),
+            ]
+        );
+        assert_eq!(entries(MACOS_DATA_DIRS, "pcsx2"), [(Base::Data, "PCSX2")]);
+        assert_eq!(
+            entries(LINUX_DATA_DIRS, "duckstation")[0],
+            (Base::Data, "duckstation")
+        );
+        assert_eq!(
+            entries(LINUX_DATA_DIRS, "retroarch")[0],
+            (Base::Config, "retroarch")
+        );
+        assert!(entries(LINUX_DATA_DIRS, "nope").is_empty());
+    }
+
+    #[test]
+    fn data_dirs_prefer_existing_folders() {
+        let tmp = std::env::temp_dir().join(format!("saves-test-{}", std::process::id()));
+        let missing = tmp.join("missing");
+        let present = tmp.join("present");
+        std::fs::create_dir_all(&present).unwrap();
+
+        assert_eq!(
+            existing_or_first(vec![missing.clone(), present.clone()]),
+            [present]
+        );
+        assert_eq!(existing_or_first(vec![missing.clone()]), [missing]);
+        assert!(existing_or_first(Vec::new()).is_empty());
+        assert!(data_dirs("nope").is_empty());
+
+        std::fs::remove_dir_all(tmp).unwrap();
+    }
+}
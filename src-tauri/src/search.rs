@@ -0,0 +1,80 @@
+/// Turn free-form search input into an FTS5 `MATCH` expression that can't
+/// fail to parse. Every word becomes a quoted prefix term, so FTS operators
+/// (`NEAR`, `NOT`, `-`, `:`) are matched literally, and all words must match.
+/// Text in double quotes is kept as an exact phrase; an unbalanced quote runs
+/// to the end of the input. Returns `None` when nothing searchable is left.
+pub fn fts_query(input: &str) -> Option<String> {
+    let mut terms = Vec::new();
+    let mut rest = input;
+    while let Some(start) = rest.find('"') {
+        push_words(&rest[..start], &mut terms);
+        let after = &rest[start + 1..];
+        let (phrase, next) = after.split_once('"').unwrap_or((after, ""));
+        if has_token(phrase) {
+            terms.push(quote(phrase));
+        }
+        rest = next;
+    }
+    push_words(rest, &mut terms);
+
+    (!terms.is_empty()).then(|| terms.join(" "))
+}
+
+fn push_words(text: &str, terms: &mut Vec<String>) {
+    for word in text.split_whitespace().filter(|w| has_token(w)) {
+        terms.push(format!("{}*", quote(word)));
+    }
+}
+
+/// Whether the FTS tokenizer would find anything to index in `text`.
+fn has_token(text: &str) -> bool {
+    text.chars().any(char::is_alphanumeric)
+}
+
+fn quote(text: &str) -> String {
+    format!("\"{}\"", text.replace('"', "\"\""))
+}
+
+/// Escape `%`, `_` and `\` for a `LIKE ... ESCAPE '\'` pattern.
+pub fn like_escape(text: &str) -> String {
+    text.replace('\\', r"\\")
+        .replace('%', r"\%")
+        .replace('_', r"\_")
+}
+
+/// Where the words of `input` match the start of a word in `text`, as
+/// `(start, end)` UTF-16 offsets so the frontend can slice the string as is.
+/// Matching ignores case; overlapping words keep the longest match.
+pub fn highlight_ranges(text: &str, input: &str) -> Vec<(usize, usize)> {
+    let words: Vec<Vec<char>> = input
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|w| !w.is_empty())
+        .map(|w| w.chars().collect())
+        .collect();
+    let chars: Vec<char> = text.chars().collect();
+    let mut ranges = Vec::new();
+    let mut offset = 0;
+    let mut skip_until = 0;
+    for (i, &c) in chars.iter().enumerate() {
+        let at_word_start = i == 0 || !chars[i - 1].is_alphanumeric();
+        if i >= skip_until && at_word_start && c.is_alphanumeric() {
+            let matched = words
+                .iter()
+                .filter(|w| {
+                    chars.len() - i >= w.len()
+                        && w.iter()
+                            .zip(&chars[i..])
+                            .all(|(a, b)| a.to_lowercase().eq(b.to_lowercase()))
+                })
+                .map(Vec::len)
+                .max();
+            if let Some(len) = matched {
+                let width: usize = chars[i..i + len].iter().map(|c| c.len_utf16()).sum();
+                ranges.push((offset, offset + width));
+                skip_until = i + len;
+            }
+        }
+        offset += c.len_utf16();
+    }
+    ranges
+}
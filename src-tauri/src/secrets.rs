@@ -0,0 +1,335 @@
+use std::sync::OnceLock;
+
+use sea_orm::{ConnectionTrait, DatabaseBackend, DatabaseConnection, FromQueryResult, Statement};
+use tauri::{AppHandle, Manager};
+use tauri_plugin_store::StoreExt;
+
+use crate::error::{AppError, AppResult};
+
+/// Keychain service the app's entries are filed under.
+const SERVICE: &str = "com.romm-buddy.app";
+
+/// Stored in place of a secret that lives in the keychain, followed by the
+/// keychain account name, e.g. `keychain:igdb_client_secret`.
+const REF_PREFIX: &str = "keychain:";
+
+/// settings.json keys whose values go in the keychain.
+const SECRET_SETTINGS: &[&str] = &[
+    "igdb_client_secret",
+    "retroachievements_api_key",
+    "retroachievements_token",
+    "screenscraper_password",
+];
+
+/// settings.json keys holding a JSON object, with the fields of it that go
+/// in the keychain. `field.*` stands for every value of an object field.
+const SECRET_SETTING_FIELDS: &[(&str, &[&str])] = &[
+    ("api_server", &["token"]),
+    ("cloud_backup", &["secret_access_key"]),
+    ("mister", &["ftp_password"]),
+    // Proxy URLs can carry `user:password@`
+    ("proxy", &["url", "overrides.*"]),
+    ("translation_service", &["api_key"]),
+];
+
+/// Fields of a source's credentials JSON that go in the keychain. The rest
+/// (username, local path) stay in the DB.
+const SECRET_SOURCE_FIELDS: &[&str] = &["password", "api_token", "token", "api_key"];
+
+/// Whether the OS keychain (macOS Keychain, Windows Credential Manager,
+/// Secret Service on Linux) can be used. Checked once; without one, secrets
+/// are stored in plain text as before.
+fn keychain_available() -> bool {
+    static AVAILABLE: OnceLock<bool> = OnceLock::new();
+    *AVAILABLE.get_or_init(|| {
+        let available = keyring::Entry::new(SERVICE, "availability-check").is_ok_and(|entry| {
+            matches!(entry.get_password(), Ok(_) | Err(keyring::Error::NoEntry))
+        });
+        if !available {
+            log::warn!("No OS keychain available; credentials stay in plain text");
+        }
+        available
+    })
+}
+
+fn entry(account: &str) -> Option<keyring::Entry> {
+    if !keychain_available() {
+        return None;
+    }
+    keyring::Entry::new(SERVICE, account)
+        .map_err(|e| log::warn!("Keychain entry {account} unusable: {e}"))
+        .ok()
+}
+
+/// Store `value` under `account`. Returns the reference to save in its
+/// place, or `None` when it has to stay in plain text.
+fn store(account: &str, value: &str) -> Option<String> {
+    let entry = entry(account)?;
+    match entry.set_password(value) {
+        Ok(()) => Some(format!("{REF_PREFIX}{account}")),
+        Err(e) => {
+            log::warn!("Failed to save {account} to the keychain: {e}");
+            None
+        }
+    }
+}
+
+/// The secret a stored value stands for: the value itself unless it's a
+/// keychain reference. A missing keychain entry reads as empty, the same as
+/// credentials that were never entered.
+fn reveal(stored: &str) -> String {
+    let Some(account) = stored.strip_prefix(REF_PREFIX) else {
+        return stored.to_string();
+    };
+    let Some(entry) = entry(account) else {
+        return String::new();
+    };
+    match entry.get_password() {
+        Ok(secret) => secret,
+        Err(keyring::Error::NoEntry) => {
+            log::warn!("Keychain entry {account} is missing");
+            String::new()
+        }
+        Err(e) => {
+            log::warn!("Failed to read {account} from the keychain: {e}");
+            String::new()
+        }
+    }
+}
+
+fn forget(account: &str) {
+    if let Some(entry) = entry(account) {
+        match entry.delete_credential() {
+            Ok(()) | Err(keyring::Error::NoEntry) => {}
+            Err(e) => log::warn!("Failed to remove {account} from the keychain: {e}"),
+        }
+    }
+}
+
+/// The settings.json value to save for `key`: a keychain reference for
+/// secrets (empty ones are removed from the keychain), the value otherwise.
+pub fn seal_setting(key: &str, value: &str) -> serde_json::Value {
+    if !SECRET_SETTINGS.contains(&key) || value.starts_with(REF_PREFIX) {
+        return serde_json::json!(value);
+    }
+    if value.is_empty() {
+        forget(key);
+        return serde_json::json!(value);
+    }
+    serde_json::json!(store(key, value).unwrap_or_else(|| value.to_string()))
+}
+
+/// The settings.json value to save for an object-valued `key`, with its
+/// secret fields moved to the keychain.
+pub fn seal_setting_object(key: &str, value: serde_json::Value) -> serde_json::Value {
+    let Some(&(_, fields)) = SECRET_SETTING_FIELDS.iter().find(|(k, _)| *k == key) else {
+        return value;
+    };
+    let serde_json::Value::Object(mut object) = value else {
+        return value;
+    };
+    for &field in fields {
+        let Some(map_field) = field.strip_suffix(".*") else {
+            seal_fields(&mut object, &[field], |field| format!("{key}-{field}"));
+            continue;
+        };
+        if let Some(serde_json::Value::Object(map)) = object.get_mut(map_field) {
+            let names: Vec<String> = map.keys().cloned().collect();
+            let names: Vec<&str> = names.iter().map(String::as_str).collect();
+            seal_fields(map, &names, |name| format!("{key}-{map_field}-{name}"));
+        }
+    }
+    serde_json::Value::Object(object)
+}
+
+/// Remove the keychain entries `old` (a setting's previous value) refers to
+/// that its new, sealed value no longer does, e.g. a dropped proxy override.
+pub fn forget_replaced(old: Option<&serde_json::Value>, new: &serde_json::Value) {
+    fn refs(value: &serde_json::Value, out: &mut Vec<String>) {
+        match value {
+            serde_json::Value::String(s) if s.starts_with(REF_PREFIX) => out.push(s.clone()),
+            serde_json::Value::Object(object) => object.values().for_each(|v| refs(v, out)),
+            _ => {}
+        }
+    }
+    let (mut before, mut after) = (Vec::new(), Vec::new());
+    if let Some(old) = old {
+        refs(old, &mut before);
+    }
+    refs(new, &mut after);
+    for stored in before.iter().filter(|r| !after.contains(r)) {
+        if let Some(account) = stored.strip_prefix(REF_PREFIX) {
+            forget(account);
+        }
+    }
+}
+
+/// Remove the keychain entries of an object-valued setting that's deleted.
+pub fn forget_setting_object(key: &str) {
+    if let Some(&(_, fields)) = SECRET_SETTING_FIELDS.iter().find(|(k, _)| *k == key) {
+        for field in fields.iter().filter(|f| !f.ends_with(".*")) {
+            forget(&format!("{key}-{field}"));
+        }
+    }
+}
+
+/// A settings.json value with keychain references replaced by the secrets,
+/// whether it is one or holds some in its fields.
+pub fn open_setting(value: serde_json::Value) -> serde_json::Value {
+    match value {
+        serde_json::Value::String(s) if s.starts_with(REF_PREFIX) => serde_json::json!(reveal(&s)),
+        serde_json::Value::Object(mut object) => {
+            open_fields(&mut object);
+            serde_json::Value::Object(object)
+        }
+        _ => value,
+    }
+}
+
+/// Move `fields` of `object` to the keychain under `account(field)`, and
+/// drop the keychain entries of empty ones. Returns whether any moved.
+fn seal_fields(
+    object: &mut serde_json::Map<String, serde_json::Value>,
+    fields: &[&str],
+    account: impl Fn(&str) -> String,
+) -> bool {
+    let mut sealed = false;
+    for &field in fields {
+        let account = account(field);
+        match object.get(field).and_then(|v| v.as_str()) {
+            Some(value) if value.starts_with(REF_PREFIX) => {}
+            Some("") | None => forget(&account),
+            Some(value) => {
+                if let Some(reference) = store(&account, value) {
+                    object.insert(field.to_string(), serde_json::json!(reference));
+                    sealed = true;
+                }
+            }
+        }
+    }
+    sealed
+}
+
+/// Replace the keychain references among `object`'s fields, and those of
+/// the objects in it, by the secrets.
+fn open_fields(object: &mut serde_json::Map<String, serde_json::Value>) {
+    for value in object.values_mut() {
+        if let Some(stored) = value.as_str().filter(|s| s.starts_with(REF_PREFIX)) {
+            *value = serde_json::json!(reveal(stored));
+        } else if let serde_json::Value::Object(inner) = value {
+            open_fields(inner);
+        }
+    }
+}
+
+fn source_account(source_id: i64, field: &str) -> String {
+    format!("source-{source_id}-{field}")
+}
+
+/// A source's credentials JSON with its secret fields moved to the keychain,
+/// ready for the `credentials` column. Unchanged when there's no keychain.
+pub fn seal_source_credentials(source_id: i64, json: &str) -> String {
+    let Ok(serde_json::Value::Object(mut creds)) = serde_json::from_str(json) else {
+        return json.to_string();
+    };
+    let sealed = seal_fields(&mut creds, SECRET_SOURCE_FIELDS, |field| {
+        source_account(source_id, field)
+    });
+    if sealed {
+        serde_json::Value::Object(creds).to_string()
+    } else {
+        json.to_string()
+    }
+}
+
+/// A source's `credentials` column with keychain references replaced by the
+/// secrets, in the JSON shape the source clients read.
+pub fn open_credentials(json: &str) -> String {
+    if !json.contains(REF_PREFIX) {
+        return json.to_string();
+    }
+    let Ok(serde_json::Value::Object(mut creds)) = serde_json::from_str(json) else {
+        return json.to_string();
+    };
+    open_fields(&mut creds);
+    serde_json::Value::Object(creds).to_string()
+}
+
+/// Remove a deleted source's secrets from the keychain.
+pub fn forget_source(source_id: i64) {
+    for &field in SECRET_SOURCE_FIELDS {
+        forget(&source_account(source_id, field));
+    }
+}
+
+/// Startup: move secrets still stored in plain text (from before the
+/// keychain was used, or from when it wasn't available) into the keychain.
+pub async fn migrate(app: &AppHandle) -> AppResult<()> {
+    if !keychain_available() {
+        return Ok(());
+    }
+
+    let store = app
+        .store("settings.json")
+        .map_err(|e| AppError::Other(e.to_string()))?;
+    let mut moved = 0;
+    for &key in SECRET_SETTINGS {
+        let Some(value) = store.get(key) else {
+            continue;
+        };
+        let Some(plain) = value.as_str().filter(|s| !s.is_empty()) else {
+            continue;
+        };
+        let sealed = seal_setting(key, plain);
+        if sealed != value {
+            store.set(key, sealed);
+            moved += 1;
+        }
+    }
+    for &(key, _) in SECRET_SETTING_FIELDS {
+        let Some(value) = store.get(key) else {
+            continue;
+        };
+        let sealed = seal_setting_object(key, value.clone());
+        if sealed != value {
+            store.set(key, sealed);
+            moved += 1;
+        }
+    }
+    if moved > 0 {
+        store.save().map_err(|e| AppError::Other(e.to_string()))?;
+    }
+
+    #[derive(Debug, FromQueryResult)]
+    struct SourceRow {
+        id: i64,
+        credentials: String,
+    }
+
+    let db = app.state::<DatabaseConnection>();
+    let sources = SourceRow::find_by_statement(Statement::from_string(
+        DatabaseBackend::Sqlite,
+        "SELECT id, credentials FROM sources",
+    ))
+    .all(db.inner())
+    .await?;
+    for source in sources {
+        let sealed = seal_source_credentials(source.id, &source.credentials);
+        if sealed == source.credentials {
+            continue;
+        }
+        db.inner()
+            .execute(Statement::from_sql_and_values(
+                DatabaseBackend::Sqlite,
+                "UPDATE sources SET credentials = ? WHERE id = ?",
+                [sealed.into(), source.id.into()],
+            ))
+            .await?;
+        moved += 1;
+    }
+
+    if moved > 0 {
+        log::info!("Moved {moved} stored credentials to the OS keychain");
+    }
+    Ok(())
+}
@@ -0,0 +1,197 @@
+use std::collections::BTreeMap;
+use std::path::Path;
+
+use sea_orm::{ConnectionTrait, DatabaseBackend, DatabaseConnection, EntityTrait, Statement};
+use tauri::{AppHandle, Manager};
+use tauri_plugin_store::StoreExt;
+
+use crate::entity::settings;
+use crate::error::{AppError, AppResult};
+use crate::models::SettingsExport;
+
+/// settings.json keys mirrored into the `settings` table. Paths that only make
+/// sense on this machine (RetroArch, BIOS, emulators, saves) and credentials
+/// stay out.
+pub const SYNCED_KEYS: &[&str] = &[
+    "artwork_phash_dedup",
+    "artwork_provider_order",
+    "auto_dedup_after_sync",
+    "cache_eviction_days",
+    "custom_emulators",
+    "dat_mirror_url",
+    "database_backup_count",
+    "emulator_arg_templates",
+    "enrichment_defaults",
+    "homebrew_index_url",
+    "language",
+    "library_default_page_size",
+    "library_max_page_size",
+    "metadata_stale_days",
+    "platform_name_overrides",
+    "retroachievements_hardcore",
+    "screenscraper_languages",
+    "screenscraper_regions",
+];
+
+const EXPORT_VERSION: u32 = 1;
+
+/// Tauri's bundle identifier, which names the folder settings.json is in.
+const APP_IDENTIFIER: &str = "com.romm-buddy.app";
+
+/// Read access to settings.json: the app's store, or the file itself when
+/// running headless from the command line. Secrets kept in the OS keychain
+/// are read from there.
+pub trait SettingsRead {
+    fn setting(&self, key: &str) -> Option<serde_json::Value>;
+}
+
+impl SettingsRead for AppHandle {
+    fn setting(&self, key: &str) -> Option<serde_json::Value> {
+        self.store("settings.json")
+            .ok()?
+            .get(key)
+            .map(crate::secrets::open_setting)
+    }
+}
+
+/// settings.json read from disk without the app running, with the synced
+/// keys taken from the DB the way `load_from_db` does at startup.
+pub struct SettingsFile(serde_json::Map<String, serde_json::Value>);
+
+impl SettingsFile {
+    pub async fn load(db: &DatabaseConnection) -> AppResult<Self> {
+        let path = dirs::data_dir()
+            .ok_or_else(|| AppError::Other("No data folder on this system".to_string()))?
+            .join(APP_IDENTIFIER)
+            .join("settings.json");
+        let mut values = match std::fs::read_to_string(&path) {
+            Ok(contents) => serde_json::from_str(&contents)
+                .map_err(|e| AppError::Other(format!("Unreadable {}: {e}", path.display())))?,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => serde_json::Map::new(),
+            Err(e) => return Err(e.into()),
+        };
+        for row in settings::Entity::find().all(db).await? {
+            if !SYNCED_KEYS.contains(&row.key.as_str()) {
+                continue;
+            }
+            match serde_json::from_str::<serde_json::Value>(&row.value) {
+                Ok(value) => {
+                    values.insert(row.key, value);
+                }
+                Err(e) => log::warn!("Ignoring unreadable setting {}: {e}", row.key),
+            }
+        }
+        Ok(Self(values))
+    }
+}
+
+impl SettingsRead for SettingsFile {
+    fn setting(&self, key: &str) -> Option<serde_json::Value> {
+        self.0.get(key).cloned().map(crate::secrets::open_setting)
+    }
+}
+
+/// Copy the synced keys from settings.json into the DB. Called after every
+/// write to one of them.
+pub async fn save_to_db(app: &AppHandle) -> AppResult<()> {
+    let store = app
+        .store("settings.json")
+        .map_err(|e| AppError::Other(e.to_string()))?;
+    let db = app.state::<DatabaseConnection>();
+
+    for &key in SYNCED_KEYS {
+        let stmt = match store.get(key) {
+            Some(value) => Statement::from_sql_and_values(
+                DatabaseBackend::Sqlite,
+                "INSERT INTO settings (key, value) VALUES (?, ?)
+                 ON CONFLICT(key) DO UPDATE SET
+                    value = excluded.value,
+                    updated_at = strftime('%Y-%m-%dT%H:%M:%fZ', 'now')
+                 WHERE settings.value != excluded.value",
+                [key.into(), value.to_string().into()],
+            ),
+            None => Statement::from_sql_and_values(
+                DatabaseBackend::Sqlite,
+                "DELETE FROM settings WHERE key = ?",
+                [key.into()],
+            ),
+        };
+        db.inner().execute(stmt).await?;
+    }
+    Ok(())
+}
+
+/// Startup: make settings.json match the DB, so a restored backup brings its
+/// preferences along. The first run after upgrading seeds the DB instead.
+pub async fn load_from_db(app: &AppHandle) -> AppResult<()> {
+    let db = app.state::<DatabaseConnection>();
+    let rows = settings::Entity::find().all(db.inner()).await?;
+    if !rows.iter().any(|row| SYNCED_KEYS.contains(&row.key.as_str())) {
+        return save_to_db(app).await;
+    }
+
+    let store = app
+        .store("settings.json")
+        .map_err(|e| AppError::Other(e.to_string()))?;
+    for row in rows {
+        if !SYNCED_KEYS.contains(&row.key.as_str()) {
+            continue;
+        }
+        match serde_json::from_str::<serde_json::Value>(&row.value) {
+            Ok(value) => store.set(row.key, value),
+            Err(e) => log::warn!("Ignoring unreadable setting {}: {e}", row.key),
+        }
+    }
+    store.save().map_err(|e| AppError::Other(e.to_string()))?;
+    Ok(())
+}
+
+/// Write the synced settings to a JSON file for another install.
+pub async fn export_to_file(app: &AppHandle, path: &Path) -> AppResult<usize> {
+    let db = app.state::<DatabaseConnection>();
+    let settings: BTreeMap<String, serde_json::Value> = settings::Entity::find()
+        .all(db.inner())
+        .await?
+        .into_iter()
+        .filter(|row| SYNCED_KEYS.contains(&row.key.as_str()))
+        .filter_map(|row| Some((row.key, serde_json::from_str(&row.value).ok()?)))
+        .collect();
+
+    let export = SettingsExport {
+        version: EXPORT_VERSION,
+        exported_at: chrono::Utc::now().format("%Y-%m-%dT%H:%M:%S%.3fZ").to_string(),
+        settings,
+    };
+    let json =
+        serde_json::to_string_pretty(&export).map_err(|e| AppError::Other(e.to_string()))?;
+    tokio::fs::write(path, json).await?;
+    Ok(export.settings.len())
+}
+
+/// Apply a settings export. Keys this version doesn't sync are skipped;
+/// settings missing from the file are left alone.
+pub async fn import_from_file(app: &AppHandle, path: &Path) -> AppResult<usize> {
+    let contents = tokio::fs::read_to_string(path).await?;
+    let export: SettingsExport = serde_json::from_str(&contents)
+        .map_err(|e| AppError::Other(format!("Not a settings export: {e}")))?;
+    if export.version > EXPORT_VERSION {
+        return Err(AppError::Other(format!(
+            "Settings export version {} is newer than this app supports",
+            export.version
+        )));
+    }
+
+    let store = app
+        .store("settings.json")
+        .map_err(|e| AppError::Other(e.to_string()))?;
+    let mut imported = 0;
+    for (key, value) in export.settings {
+        if SYNCED_KEYS.contains(&key.as_str()) {
+            store.set(key, value);
+            imported += 1;
+        }
+    }
+    store.save().map_err(|e| AppError::Other(e.to_string()))?;
+    save_to_db(app).await?;
+    Ok(imported)
+}
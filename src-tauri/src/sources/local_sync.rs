@@ -1,4 +1,5 @@
-use std::path::Path;
+use std::collections::HashMap;
+use std::path::{Component, Path};
 
 use sea_orm::{
     ActiveModelTrait, ActiveValue::Set, ColumnTrait, ConnectionTrait, DatabaseBackend,
@@ -8,26 +9,22 @@ use tokio_util::sync::CancellationToken;
 
 use crate::dedup;
 use crate::error::AppResult;
-use crate::models::ScanProgress;
+use crate::models::{
+    BrowsedRom, CompanionMove, FolderBrowse, OrganizeMove, OrganizePlan, ScanProgress,
+    SourceSettings,
+};
 use crate::platform_registry;
 
-/// Known ROM file extensions -- files matching these are indexed.
-const ROM_EXTENSIONS: &[&str] = &[
-    "gb", "gbc", "gba", "nes", "sfc", "smc", "n64", "z64", "v64",
-    "nds", "3ds", "iso", "bin", "cue", "chd", "rvz", "wbfs", "rom",
-    "md", "gen", "smd", "gg", "sms", "pce", "ngp", "ngc",
-    "ws", "wsc", "lnx", "vb", "zip", "7z", "m3u",
-    "a26", "a78", "col", "sg", "int", "jag",
-    "psx", "pbp", "cso", "xci", "nsp",
-];
-
 /// Detected folder layout convention.
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "lowercase")]
 pub enum FolderLayout {
     /// Lowercase slugs: `gb/`, `gba/`, `snes/` -- ES-DE, `RetroPie`, `ArkOS`, `EmuDeck`.
     EsDe,
     /// `roms/` subdirectory containing lowercase slugs -- Batocera, KNULLI.
     Batocera,
+    /// `Emulation/` folder with `roms/` (named like ES-DE) and `tools/` -- `EmuDeck`.
+    EmuDeck,
     /// `ROMS/` + `MUOS/` sibling directories.
     MuOs,
     /// "Name (TAG)/" pattern -- `MinUI`.
@@ -61,6 +58,11 @@ pub fn detect_layout(root: &Path) -> FolderLayout {
         return FolderLayout::MuOs;
     }
 
+    // `EmuDeck`: `roms/` next to the `tools/` folder holding ES-DE's media
+    if entries.iter().any(|n| n == "roms") && entries.iter().any(|n| n == "tools") {
+        return FolderLayout::EmuDeck;
+    }
+
     // Batocera/KNULLI/`ArkOS`: has a `roms/` or `EASYROMS/` subdirectory
     let batocera_dir = if entries.iter().any(|n| n == "roms") {
         Some(root.join("roms"))
@@ -152,12 +154,35 @@ fn resolve_folder_to_slug(folder_name: &str, layout: &FolderLayout) -> Option<St
     }
 }
 
-/// Check if a file has a ROM extension.
-fn is_rom_file(path: &Path) -> bool {
+/// Extensions users added per platform slug, from `platforms.file_extensions`.
+pub type CustomExtensions = HashMap<String, Vec<String>>;
+
+/// Load the user-added extensions of every platform.
+pub async fn custom_extensions(db: &DatabaseConnection) -> AppResult<CustomExtensions> {
+    use crate::entity::platforms;
+
+    Ok(platforms::Entity::find()
+        .all(db)
+        .await?
+        .into_iter()
+        .filter(|p| !p.file_extensions.0.is_empty())
+        .map(|p| (p.slug, p.file_extensions.into_inner()))
+        .collect())
+}
+
+/// Check if a file has a ROM extension of the platform `slug`: one from the
+/// registry, an archive, or one the user added.
+fn is_rom_file(path: &Path, slug: &str, custom: &CustomExtensions) -> bool {
     path.extension()
         .and_then(|e| e.to_str())
         .is_some_and(|ext| {
-            ROM_EXTENSIONS.iter().any(|&known| known.eq_ignore_ascii_case(ext))
+            platform_registry::extensions(slug)
+                .iter()
+                .chain(platform_registry::ARCHIVE_EXTENSIONS)
+                .any(|&known| known.eq_ignore_ascii_case(ext))
+                || custom
+                    .get(slug)
+                    .is_some_and(|list| list.iter().any(|e| e.eq_ignore_ascii_case(ext)))
         })
 }
 
@@ -169,34 +194,26 @@ fn get_roms_root(root: &Path, layout: &FolderLayout) -> std::path::PathBuf {
             if roms.exists() { roms } else { root.join("EASYROMS") }
         }
         FolderLayout::MuOs => root.join("ROMS"),
+        FolderLayout::EmuDeck => root.join("roms"),
         _ => root.to_path_buf(),
     }
 }
 
-/// Count total ROM files for progress reporting.
-fn count_rom_files(roms_root: &Path) -> u64 {
-    let mut count: u64 = 0;
-    if let Ok(dirs) = std::fs::read_dir(roms_root) {
-        for entry in dirs.flatten() {
-            let path = entry.path();
-            if path.is_dir() {
-                if let Ok(files) = std::fs::read_dir(&path) {
-                    #[allow(clippy::cast_possible_truncation)]
-                    {
-                        count += files
-                            .filter_map(std::result::Result::ok)
-                            .filter(|f| is_rom_file(&f.path()))
-                            .count() as u64;
-                    }
-                }
-            }
-        }
+/// ROM root to write into for `layout`. Unlike `get_roms_root`, defaults to
+/// `roms/` when neither Batocera folder exists yet.
+pub(crate) fn target_roms_root(root: &Path, layout: &FolderLayout) -> std::path::PathBuf {
+    if *layout == FolderLayout::Batocera && !root.join("EASYROMS").exists() {
+        root.join("roms")
+    } else {
+        get_roms_root(root, layout)
     }
-    count
 }
 
 /// Test a local path: detect layout and count platforms/ROMs.
-pub fn test_local_path(root: &Path) -> AppResult<(FolderLayout, u32, u64)> {
+pub fn test_local_path(
+    root: &Path,
+    custom: &CustomExtensions,
+) -> AppResult<(FolderLayout, u32, u64)> {
     if !root.exists() || !root.is_dir() {
         return Err(crate::error::AppError::Other(format!(
             "Path does not exist or is not a directory: {}",
@@ -205,36 +222,12 @@ pub fn test_local_path(root: &Path) -> AppResult<(FolderLayout, u32, u64)> {
     }
 
     let layout = detect_layout(root);
-    let roms_root = get_roms_root(root, &layout);
+    let scanned = scan_local_rom_files(root, custom)?;
+    let platforms: std::collections::HashSet<&str> =
+        scanned.iter().map(|r| r.canonical_slug.as_str()).collect();
 
-    let mut platform_count: u32 = 0;
-    let mut rom_count: u64 = 0;
-
-    if let Ok(dirs) = std::fs::read_dir(&roms_root) {
-        for entry in dirs.flatten() {
-            let path = entry.path();
-            if !path.is_dir() {
-                continue;
-            }
-            let folder_name = entry.file_name().to_string_lossy().into_owned();
-            if resolve_folder_to_slug(&folder_name, &layout).is_some() {
-                #[allow(clippy::cast_possible_truncation)]
-                let file_count = std::fs::read_dir(&path)
-                    .map(|rd| {
-                        rd.filter_map(std::result::Result::ok)
-                            .filter(|f| is_rom_file(&f.path()))
-                            .count() as u64
-                    })
-                    .unwrap_or(0);
-                if file_count > 0 {
-                    platform_count += 1;
-                    rom_count += file_count;
-                }
-            }
-        }
-    }
-
-    Ok((layout, platform_count, rom_count))
+    #[allow(clippy::cast_possible_truncation)]
+    Ok((layout, platforms.len() as u32, scanned.len() as u64))
 }
 
 /// Scanned ROM file info collected from the filesystem.
@@ -246,14 +239,76 @@ struct ScannedRomFile {
     file_size: Option<i64>,
 }
 
-/// Scan the filesystem for ROM files, returning structured results.
+/// Folders of artwork and scraper media, which never hold games.
+const SKIPPED_FOLDERS: &[&str] = &["media", "images", "imgs", "videos", "manuals", "downloaded_images"];
+
+/// How many folder levels below a platform folder are searched for games.
+const MAX_SCAN_DEPTH: usize = 4;
+
+/// Files that list the other files of a multi-file game.
+const SHEET_EXTENSIONS: &[&str] = &["cue", "gdi", "m3u"];
+
+fn has_extension(path: &Path, extensions: &[&str]) -> bool {
+    path.extension()
+        .and_then(|e| e.to_str())
+        .is_some_and(|ext| extensions.iter().any(|known| known.eq_ignore_ascii_case(ext)))
+}
+
+fn lower_file_name(path: &Path) -> String {
+    path.file_name()
+        .map(|n| n.to_string_lossy().to_lowercase())
+        .unwrap_or_default()
+}
+
+/// Collect ROM files below `dir`, descending into per-game subfolders.
+/// Hidden entries and artwork folders are skipped, as is a folder named
+/// after an archive next to it (the CHDs of a MAME romset: `sf2.zip` + `sf2/`).
+fn collect_rom_files(
+    dir: &Path,
+    slug: &str,
+    custom: &CustomExtensions,
+    depth: usize,
+    out: &mut Vec<std::path::PathBuf>,
+) {
+    let Ok(read_dir) = std::fs::read_dir(dir) else {
+        log::warn!("Failed to read directory {}", dir.display());
+        return;
+    };
+    let mut entries: Vec<std::path::PathBuf> = read_dir
+        .filter_map(std::result::Result::ok)
+        .map(|e| e.path())
+        .filter(|p| !lower_file_name(p).starts_with('.'))
+        .collect();
+    entries.sort();
+
+    let archive_stems: std::collections::HashSet<String> = entries
+        .iter()
+        .filter(|p| p.is_file() && has_extension(p, platform_registry::ARCHIVE_EXTENSIONS))
+        .filter_map(|p| p.file_stem().map(|s| s.to_string_lossy().to_lowercase()))
+        .collect();
+
+    for path in entries {
+        if path.is_dir() {
+            let name = lower_file_name(&path);
+            if depth < MAX_SCAN_DEPTH
+                && !SKIPPED_FOLDERS.contains(&name.as_str())
+                && !archive_stems.contains(&name)
+            {
+                collect_rom_files(&path, slug, custom, depth + 1, out);
+            }
+        } else if is_rom_file(&path, slug, custom) {
+            out.push(path);
+        }
+    }
+}
+
+/// Scan the filesystem for ROM files, returning structured results. A cue,
+/// gdi or m3u sheet is one entry covering the files it lists, and a game
+/// alone in its own subfolder is named after that folder.
 /// This is a blocking function that should be called from `spawn_blocking`.
-fn scan_local_rom_files(
-    root: &Path,
-) -> AppResult<(Vec<ScannedRomFile>, u64)> {
+fn scan_local_rom_files(root: &Path, custom: &CustomExtensions) -> AppResult<Vec<ScannedRomFile>> {
     let layout = detect_layout(root);
     let roms_root = get_roms_root(root, &layout);
-    let total_roms = count_rom_files(&roms_root);
 
     let mut dirs: Vec<_> = std::fs::read_dir(&roms_root)?
         .filter_map(std::result::Result::ok)
@@ -268,51 +323,123 @@ fn scan_local_rom_files(
         let Some(canonical_slug) = resolve_folder_to_slug(&folder_name, &layout) else {
             continue;
         };
+        let platform_dir = dir_entry.path();
 
-        let mut files: Vec<_> = std::fs::read_dir(dir_entry.path())?
-            .filter_map(std::result::Result::ok)
-            .filter(|e| is_rom_file(&e.path()))
+        let mut files = Vec::new();
+        collect_rom_files(&platform_dir, &canonical_slug, custom, 1, &mut files);
+
+        let sheets: HashMap<std::path::PathBuf, Vec<std::path::PathBuf>> = files
+            .iter()
+            .filter(|f| has_extension(f, SHEET_EXTENSIONS))
+            .map(|f| (f.clone(), companion_files(f)))
             .collect();
-        files.sort_by_key(std::fs::DirEntry::file_name);
+        let covered: std::collections::HashSet<&std::path::PathBuf> =
+            sheets.values().flatten().collect();
+        let files: Vec<std::path::PathBuf> =
+            files.iter().filter(|f| !covered.contains(f)).cloned().collect();
+
+        let mut per_folder: HashMap<&Path, usize> = HashMap::new();
+        for file in &files {
+            if let Some(parent) = file.parent() {
+                *per_folder.entry(parent).or_default() += 1;
+            }
+        }
 
-        for file_entry in files {
-            let file_path = file_entry.path();
-            let file_name = file_entry.file_name().to_string_lossy().into_owned();
-            let rom_name = file_path
-                .file_stem()
-                .map_or_else(|| file_name.clone(), |s| s.to_string_lossy().into_owned());
+        for file_path in &files {
+            let file_name = file_path
+                .file_name()
+                .map(|n| n.to_string_lossy().into_owned())
+                .unwrap_or_default();
+            let game_folder = file_path
+                .parent()
+                .filter(|p| *p != platform_dir && per_folder.get(p) == Some(&1));
+            let rom_name = match game_folder.and_then(Path::file_name) {
+                Some(folder) => folder.to_string_lossy().into_owned(),
+                None => file_path
+                    .file_stem()
+                    .map_or_else(|| file_name.clone(), |s| s.to_string_lossy().into_owned()),
+            };
 
-            #[allow(clippy::cast_possible_wrap)]
-            let file_size = file_entry.metadata().map(|m| m.len() as i64).ok();
+            let size_of = |p: &Path| std::fs::metadata(p).map(|m| m.len()).ok();
+            let file_size = size_of(file_path).map(|size| {
+                size + sheets
+                    .get(file_path)
+                    .map_or(0, |parts| parts.iter().filter_map(|p| size_of(p)).sum())
+            });
 
+            #[allow(clippy::cast_possible_wrap)]
             results.push(ScannedRomFile {
                 canonical_slug: canonical_slug.clone(),
-                file_path,
+                file_path: file_path.clone(),
                 file_name,
                 rom_name,
-                file_size,
+                file_size: file_size.map(|s| s as i64),
             });
         }
     }
 
-    Ok((results, total_roms))
+    Ok(results)
 }
 
-/// Sync a local filesystem source into the database.
+/// Scan a folder the way a local source sync would, without touching the DB.
+/// ROMs are identified by folder and, where possible, their header.
+/// This is a blocking function that should be called from `spawn_blocking`.
+pub fn browse_folder(root: &Path, custom: &CustomExtensions) -> AppResult<FolderBrowse> {
+    if !root.is_dir() {
+        return Err(crate::error::AppError::Other(format!(
+            "Path does not exist or is not a directory: {}",
+            root.display()
+        )));
+    }
+
+    let layout = detect_layout(root);
+    let scanned = scan_local_rom_files(root, custom)?;
+    let roms = scanned
+        .into_iter()
+        .map(|rom| {
+            let header = crate::hash::header_identity(&rom.file_path, &rom.canonical_slug);
+            BrowsedRom {
+                platform_name: platform_registry::display_name(&rom.canonical_slug)
+                    .map_or_else(|| rom.canonical_slug.clone(), str::to_string),
+                rom_name: rom.rom_name,
+                file_name: rom.file_name,
+                file_path: rom.file_path.to_string_lossy().into_owned(),
+                file_size: rom.file_size,
+                header_title: header.as_ref().and_then(|h| h.title.clone()),
+                header_serial: header.and_then(|h| h.serial),
+                in_library: false,
+                platform_slug: rom.canonical_slug,
+            }
+        })
+        .collect();
+
+    Ok(FolderBrowse { layout, roms })
+}
+
+/// Sync a local filesystem source into the database. Files the source's
+/// filters exclude are skipped.
 pub async fn sync_local_to_db(
     source_id: i64,
     root: &Path,
+    settings: &SourceSettings,
     db: &DatabaseConnection,
     on_progress: impl Fn(ScanProgress) + Send,
     cancel: CancellationToken,
 ) -> AppResult<()> {
     // Scan the filesystem in a blocking task to avoid stalling the async runtime
     let root_owned = root.to_path_buf();
-    let (scanned_files, total_roms) = tokio::task::spawn_blocking(move || {
-        scan_local_rom_files(&root_owned)
+    let custom = custom_extensions(db).await?;
+    let mut scanned_files = tokio::task::spawn_blocking(move || {
+        scan_local_rom_files(&root_owned, &custom)
     })
     .await
     .map_err(|e| crate::error::AppError::Other(format!("Task join error: {e}")))??;
+    scanned_files.retain(|f| {
+        settings.allows_platform(&f.canonical_slug)
+            && settings.allows_file(&f.file_name, f.file_size)
+    });
+    #[allow(clippy::cast_possible_truncation)]
+    let total_roms = scanned_files.len() as u64;
 
     // Cache platform IDs to avoid repeated lookups
     let mut platform_cache: std::collections::HashMap<String, i64> = std::collections::HashMap::new();
@@ -348,6 +475,8 @@ pub async fn sync_local_to_db(
                     screenscraper_id: Set(platform_registry::ss_id(&scanned.canonical_slug).map(|id| id as i64)),
                     file_extensions: Set(crate::entity::json_vec::JsonVec::default()),
                     folder_aliases: Set(crate::entity::json_vec::JsonVec::default()),
+                    archived: Set(false),
+                    archive_handling: Set(platforms::ArchiveHandling::Keep),
                     created_at: Set(chrono::Utc::now().format("%Y-%m-%dT%H:%M:%S%.3fZ").to_string()),
                     updated_at: Set(chrono::Utc::now().format("%Y-%m-%dT%H:%M:%S%.3fZ").to_string()),
                 }.insert(db).await?;
@@ -367,19 +496,40 @@ pub async fn sync_local_to_db(
         });
 
         let abs_path = scanned.file_path.to_string_lossy().into_owned();
-        let _rom_id = dedup::upsert_rom_deduped(
+        let parsed = crate::naming::parse(&scanned.file_name);
+        let regions = serde_json::to_string(&parsed.regions).unwrap_or_else(|_| "[]".into());
+        let rom_id = dedup::upsert_rom_deduped(
             db,
             local_platform_id,
             &scanned.rom_name,
             &scanned.file_name,
             scanned.file_size,
-            "[]",
+            &regions,
             None,
             source_id,
             Some(&abs_path),
             None,
         )
         .await?;
+
+        // Languages from the name only fill a gap; scrapers know better
+        let languages = serde_json::to_string(&parsed.languages).unwrap_or_else(|_| "[]".into());
+        let flags = serde_json::to_string(&parsed.flags).unwrap_or_else(|_| "[]".into());
+        db.execute(Statement::from_sql_and_values(
+            DatabaseBackend::Sqlite,
+            "UPDATE roms SET
+                languages = CASE WHEN languages = '[]' THEN ? ELSE languages END,
+                revision = COALESCE(?, revision),
+                dump_flags = ?
+             WHERE id = ?",
+            [
+                languages.into(),
+                parsed.revision.into(),
+                flags.into(),
+                rom_id.into(),
+            ],
+        ))
+        .await?;
     }
 
     // Update source last_synced_at
@@ -391,3 +541,269 @@ pub async fn sync_local_to_db(
 
     Ok(())
 }
+
+/// Preferred short folder tags (`OnionOS` folders, `MinUI` suffixes) where they
+/// differ from the first registry alias.
+const SHORT_TAGS: &[(&str, &str)] = &[
+    ("nes", "fc"),
+    ("snes", "sfc"),
+    ("genesis", "md"),
+    ("psx", "ps"),
+    ("mastersystem", "ms"),
+    ("gamegear", "gg"),
+    ("pce", "pce"),
+    ("gamecube", "gc"),
+];
+
+/// ES-DE system folder names where they differ from the first registry alias.
+const ES_DE_FOLDERS: &[(&str, &str)] = &[("gamecube", "gc")];
+
+/// Folder a platform's ROMs live in for `layout`, relative to the layout's ROM root.
+/// Only names that resolve back to `slug` on the next sync are used.
+pub(crate) fn layout_folder_name(slug: &str, layout: &FolderLayout) -> Option<String> {
+    let def = platform_registry::PLATFORMS.iter().find(|p| p.slug == slug)?;
+    let first = *def.folder_aliases.first()?;
+    let preferred = |table: &[(&str, &'static str)]| {
+        table
+            .iter()
+            .find(|(s, _)| *s == slug)
+            .map(|(_, name)| *name)
+            .filter(|name| def.folder_aliases.contains(name))
+            .unwrap_or(first)
+    };
+
+    Some(match layout {
+        FolderLayout::EsDe | FolderLayout::EmuDeck => preferred(ES_DE_FOLDERS).to_string(),
+        FolderLayout::Batocera | FolderLayout::MuOs | FolderLayout::Unknown => first.to_string(),
+        FolderLayout::OnionOs => preferred(SHORT_TAGS).to_uppercase(),
+        FolderLayout::MinUi => format!("{} ({})", def.display_name, preferred(SHORT_TAGS).to_uppercase()),
+    })
+}
+
+/// Plan (and unless `dry_run`, perform) moving every ROM file of a local source
+/// into the folder structure of `layout`, along with the tracks of its sheet and
+/// the game's own subfolder. Files already in place are left alone; moves whose
+/// destination already exists are reported as conflicts.
+pub async fn organize_local_source(
+    db: &DatabaseConnection,
+    source_id: i64,
+    root: &Path,
+    layout: &FolderLayout,
+    dry_run: bool,
+) -> AppResult<OrganizePlan> {
+    use sea_orm::FromQueryResult;
+
+    #[derive(Debug, FromQueryResult)]
+    struct SourceRomRow {
+        rom_id: i64,
+        path: String,
+        slug: String,
+    }
+
+    let rows = SourceRomRow::find_by_statement(Statement::from_sql_and_values(
+        DatabaseBackend::Sqlite,
+        "SELECT sr.rom_id, sr.source_rom_id AS path, p.slug
+         FROM source_roms sr
+         JOIN roms r ON r.id = sr.rom_id
+         JOIN platforms p ON p.id = r.platform_id
+         WHERE sr.source_id = ? AND sr.source_rom_id IS NOT NULL
+         ORDER BY sr.source_rom_id",
+        [source_id.into()],
+    ))
+    .all(db)
+    .await?;
+
+    let current_roms_root = get_roms_root(root, &detect_layout(root));
+    let roms_root = target_roms_root(root, layout);
+    let mut plan = OrganizePlan {
+        moves: Vec::new(),
+        conflicts: Vec::new(),
+        unchanged: 0,
+        applied: !dry_run,
+    };
+    let mut targets = std::collections::HashSet::new();
+
+    for row in rows {
+        let from = std::path::PathBuf::from(&row.path);
+        let Some(in_folder) = path_in_platform_folder(&current_roms_root, &from)
+            .or_else(|| from.file_name().map(std::path::PathBuf::from))
+        else {
+            continue;
+        };
+        let Some(folder) = layout_folder_name(&row.slug, layout) else {
+            continue;
+        };
+        let to = roms_root.join(folder).join(in_folder);
+        if to == from {
+            plan.unchanged += 1;
+            continue;
+        }
+        let to_dir = to.parent().unwrap_or(&roms_root);
+        let companions: Vec<(std::path::PathBuf, std::path::PathBuf)> = companion_files(&from)
+            .into_iter()
+            .map(|file| {
+                let dest = to_dir.join(companion_relative_path(&from, &file));
+                (file, dest)
+            })
+            .collect();
+        let conflict = std::iter::once(&to)
+            .chain(companions.iter().map(|(_, dest)| dest))
+            .any(|dest| dest.exists() || !targets.insert(dest.clone()));
+        let mv = OrganizeMove {
+            rom_id: row.rom_id,
+            from: row.path,
+            to: to.to_string_lossy().into_owned(),
+            companions: companions
+                .iter()
+                .map(|(file, dest)| CompanionMove {
+                    from: file.to_string_lossy().into_owned(),
+                    to: dest.to_string_lossy().into_owned(),
+                })
+                .collect(),
+        };
+        if conflict {
+            plan.conflicts.push(mv);
+        } else {
+            plan.moves.push(mv);
+        }
+    }
+
+    if dry_run || plan.moves.is_empty() {
+        return Ok(plan);
+    }
+
+    let pending = std::mem::take(&mut plan.moves);
+    let (done, failed) = tokio::task::spawn_blocking(move || {
+        let mut done = Vec::new();
+        let mut failed = Vec::new();
+        for mv in pending {
+            // Tracks first, so the sheet never sits somewhere without them
+            let moved = mv
+                .companions
+                .iter()
+                .map(|c| (c.from.as_str(), c.to.as_str()))
+                .chain([(mv.from.as_str(), mv.to.as_str())])
+                .try_for_each(|(from, to)| move_file(Path::new(from), Path::new(to)));
+            match moved {
+                Ok(()) => done.push(mv),
+                Err(e) => {
+                    log::warn!("Failed to move {} -> {}: {e}", mv.from, mv.to);
+                    failed.push(mv);
+                }
+            }
+        }
+        (done, failed)
+    })
+    .await
+    .map_err(|e| crate::error::AppError::Other(format!("Task join error: {e}")))?;
+
+    for mv in &done {
+        db.execute(Statement::from_sql_and_values(
+            DatabaseBackend::Sqlite,
+            "UPDATE source_roms SET source_rom_id = ? WHERE source_id = ? AND rom_id = ?",
+            [mv.to.clone().into(), source_id.into(), mv.rom_id.into()],
+        ))
+        .await?;
+    }
+
+    plan.moves = done;
+    plan.conflicts.extend(failed);
+    Ok(plan)
+}
+
+/// `path` relative to its platform folder under `roms_root`: the file name,
+/// preceded by the game's own subfolders when it's nested deeper.
+fn path_in_platform_folder(roms_root: &Path, path: &Path) -> Option<std::path::PathBuf> {
+    let mut parts = path.strip_prefix(roms_root).ok()?.components();
+    parts.next()?;
+    let rest = parts.as_path();
+    (!rest.as_os_str().is_empty()).then(|| rest.to_path_buf())
+}
+
+/// Other files a multi-file ROM needs: the tracks of a `.cue`/`.gdi` sheet or
+/// the discs of an `.m3u` playlist (and their tracks). Empty for single-file
+/// ROMs; only files that exist are returned.
+pub fn companion_files(path: &Path) -> Vec<std::path::PathBuf> {
+    let ext = path
+        .extension()
+        .and_then(|e| e.to_str())
+        .map(str::to_ascii_lowercase)
+        .unwrap_or_default();
+    if !SHEET_EXTENSIONS.contains(&ext.as_str()) {
+        return Vec::new();
+    }
+    let Ok(contents) = std::fs::read_to_string(path) else {
+        return Vec::new();
+    };
+    let dir = path.parent().unwrap_or(Path::new("."));
+
+    let names: Vec<String> = match ext.as_str() {
+        "cue" => contents
+            .lines()
+            .map(str::trim)
+            .filter(|l| l.to_uppercase().starts_with("FILE"))
+            .filter_map(|l| l.split('"').nth(1).map(str::to_string))
+            .collect(),
+        // `<track> <lba> <type> <sector size> <file> <offset>`, file possibly quoted
+        "gdi" => contents
+            .lines()
+            .skip(1)
+            .filter_map(|l| {
+                let l = l.trim();
+                if let Some(quoted) = l.split('"').nth(1) {
+                    Some(quoted.to_string())
+                } else {
+                    l.split_whitespace().nth(4).map(str::to_string)
+                }
+            })
+            .collect(),
+        "m3u" => contents
+            .lines()
+            .map(str::trim)
+            .filter(|l| !l.is_empty() && !l.starts_with('#'))
+            .map(str::to_string)
+            .collect(),
+        _ => return Vec::new(),
+    };
+
+    let mut files = Vec::new();
+    for name in names {
+        let file = dir.join(name);
+        if !file.is_file() || files.contains(&file) {
+            continue;
+        }
+        if ext == "m3u" {
+            files.extend(companion_files(&file));
+        }
+        files.push(file);
+    }
+    files
+}
+
+/// Where a companion `file` of the ROM at `rom` goes, relative to the folder
+/// the ROM is copied or moved to: the same subpath when it sits below the
+/// ROM's folder (an `.m3u`'s disc folders), otherwise just its name.
+pub fn companion_relative_path(rom: &Path, file: &Path) -> std::path::PathBuf {
+    let dir = rom.parent().unwrap_or(Path::new("."));
+    match file.strip_prefix(dir) {
+        Ok(rel) if rel.components().all(|c| matches!(c, Component::Normal(_))) => rel.to_path_buf(),
+        _ => file.file_name().unwrap_or_default().into(),
+    }
+}
+
+/// Move a file, creating the destination folder and falling back to copy +
+/// delete across filesystems. Removes the source folder once it is empty.
+fn move_file(from: &Path, to: &Path) -> std::io::Result<()> {
+    if let Some(parent) = to.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    if std::fs::rename(from, to).is_err() {
+        std::fs::copy(from, to)?;
+        std::fs::remove_file(from)?;
+    }
+    if let Some(parent) = from.parent() {
+        // Only succeeds when the old platform folder is now empty
+        let _ = std::fs::remove_dir(parent);
+    }
+    Ok(())
+}
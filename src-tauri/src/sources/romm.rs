@@ -11,8 +11,10 @@ use std::collections::HashMap;
 
 use crate::dedup;
 use crate::error::{AppError, AppResult};
-use crate::models::{ConnectionTestResult, ScanProgress, TokenPair};
+use crate::http;
+use crate::models::{ConnectionTestResult, ProxyService, ScanProgress, SourceSettings, TokenPair};
 use crate::platform_registry;
+use crate::tls::TlsOptions;
 
 /// ROMM API response types (deserialized from JSON).
 #[derive(Debug, Deserialize)]
@@ -66,6 +68,28 @@ pub struct RommRom {
     pub metadatum: Option<RommMetadatum>,
 }
 
+/// A ROMM collection. The user's favourites are a collection flagged with
+/// `is_favorite` (older servers only name it "Favourites").
+#[derive(Debug, Deserialize)]
+#[allow(dead_code)]
+pub struct RommCollection {
+    pub id: i64,
+    pub name: String,
+    pub description: Option<String>,
+    #[serde(default)]
+    pub rom_ids: Vec<i64>,
+    #[serde(default)]
+    pub is_favorite: bool,
+}
+
+impl RommCollection {
+    fn is_favorites(&self) -> bool {
+        self.is_favorite
+            || self.name.eq_ignore_ascii_case("favourites")
+            || self.name.eq_ignore_ascii_case("favorites")
+    }
+}
+
 #[derive(Debug, Deserialize)]
 pub struct RommMetadatum {
     #[serde(default)]
@@ -77,10 +101,35 @@ pub struct RommClient {
     base_url: String,
     username: String,
     password: String,
+    /// Long-lived API token used instead of username/password. It's sent
+    /// as-is and never refreshed; it works until revoked in ROMM.
+    api_token: Option<String>,
+    scope: &'static str,
     client: Client,
     tokens: RwLock<Option<TokenPair>>,
 }
 
+/// OAuth scopes for browsing and downloading.
+const READ_SCOPE: &str = "me.read roms.read platforms.read assets.read collections.read";
+
+/// Read scopes plus `roms.write`, which viewer accounts don't have.
+const WRITE_SCOPE: &str =
+    "me.read roms.read roms.write platforms.read assets.read collections.read";
+
+/// Read scopes plus `collections.write`, which every ROMM role has.
+const COLLECTIONS_WRITE_SCOPE: &str =
+    "me.read roms.read platforms.read assets.read collections.read collections.write";
+
+/// Uploads can take far longer than the client's default request timeout.
+const UPLOAD_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(6 * 60 * 60);
+const UPLOAD_CHUNK_SIZE: usize = 256 * 1024;
+
+fn http_client(tls: &TlsOptions) -> AppResult<Client> {
+    let builder =
+        http::client_builder_for(ProxyService::Romm).timeout(std::time::Duration::from_secs(30));
+    Ok(tls.apply(builder)?.build()?)
+}
+
 impl RommClient {
     #[allow(clippy::needless_pass_by_value)]
     pub fn new(base_url: String, username: String, password: String) -> Self {
@@ -88,7 +137,9 @@ impl RommClient {
             base_url: base_url.trim_end_matches('/').to_string(),
             username,
             password,
-            client: Client::builder()
+            api_token: None,
+            scope: READ_SCOPE,
+            client: http::client_builder_for(ProxyService::Romm)
                 .timeout(std::time::Duration::from_secs(30))
                 .build()
                 .unwrap_or_default(),
@@ -96,8 +147,76 @@ impl RommClient {
         }
     }
 
-    /// Authenticate with username/password.
+    /// Trust the server's certificate as `tls` says (a custom CA or a pinned
+    /// fingerprint). Fails if the CA file can't be read or the fingerprint
+    /// isn't valid.
+    pub fn with_tls(mut self, tls: &TlsOptions) -> AppResult<Self> {
+        self.client = http_client(tls)?;
+        Ok(self)
+    }
+
+    /// A client that authenticates with an API token instead of a password.
+    pub fn with_api_token(base_url: String, api_token: String) -> Self {
+        Self {
+            api_token: Some(api_token),
+            ..Self::new(base_url, String::new(), String::new())
+        }
+    }
+
+    /// Build a client from a source row's URL and credentials JSON, with
+    /// keychain references resolved. An `api_token` takes precedence over
+    /// `username`/`password`.
+    pub fn from_source(base_url: String, credentials: &str) -> Self {
+        let credentials = crate::secrets::open_credentials(credentials);
+        let creds: HashMap<String, String> = serde_json::from_str(&credentials).unwrap_or_else(|e| {
+            log::warn!("Failed to parse credentials JSON: {e}");
+            HashMap::new()
+        });
+        let mut client = match creds.get("api_token").filter(|t| !t.is_empty()) {
+            Some(token) => Self::with_api_token(base_url, token.clone()),
+            None => {
+                let username = creds.get("username").cloned().unwrap_or_default();
+                let password = creds.get("password").cloned().unwrap_or_default();
+                Self::new(base_url, username, password)
+            }
+        };
+        let tls = TlsOptions::from_credentials(&creds);
+        if !tls.is_default() {
+            // Unusable options leave the certificate checked as usual, so
+            // requests fail rather than trusting the server some other way
+            match http_client(&tls) {
+                Ok(http) => client.client = http,
+                Err(e) => log::warn!("Ignoring TLS options for {}: {e}", client.base_url),
+            }
+        }
+        client
+    }
+
+    /// Request write access (needed for uploads) when authenticating.
+    #[must_use]
+    pub fn with_write_access(mut self) -> Self {
+        self.scope = WRITE_SCOPE;
+        self
+    }
+
+    /// Request permission to edit the user's collections when authenticating.
+    #[must_use]
+    pub fn with_collections_write_access(mut self) -> Self {
+        self.scope = COLLECTIONS_WRITE_SCOPE;
+        self
+    }
+
+    /// Authenticate with username/password. An API token is used as the
+    /// access token directly.
     async fn authenticate(&self) -> AppResult<TokenPair> {
+        if let Some(token) = &self.api_token {
+            return Ok(TokenPair {
+                access_token: token.clone(),
+                refresh_token: String::new(),
+            });
+        }
+        http::ensure_online("ROMM")?;
+
         let url = format!("{}/api/token", self.base_url);
         let resp = self
             .client
@@ -106,7 +225,7 @@ impl RommClient {
                 ("username", self.username.as_str()),
                 ("password", self.password.as_str()),
                 ("grant_type", "password"),
-                ("scope", "me.read roms.read platforms.read assets.read"),
+                ("scope", self.scope),
             ])
             .send()
             .await?;
@@ -128,6 +247,7 @@ impl RommClient {
 
     /// Get a valid access token, refreshing or re-authenticating if needed.
     async fn get_token(&self) -> AppResult<String> {
+        http::ensure_online("ROMM")?;
         {
             let tokens = self.tokens.read().await;
             if let Some(ref tp) = *tokens {
@@ -146,17 +266,33 @@ impl RommClient {
         Ok(access)
     }
 
+    /// Whether a 401 may just mean the access token expired. An API token
+    /// that's refused won't be accepted on a second try.
+    fn can_reauthenticate(&self) -> bool {
+        self.api_token.is_none()
+    }
+
     /// Make an authenticated GET request, retrying once on 401.
     async fn auth_get(&self, url: &str) -> AppResult<reqwest::Response> {
+        self.auth_get_with(url, reqwest::header::HeaderMap::new()).await
+    }
+
+    /// [`Self::auth_get`] with extra request headers.
+    async fn auth_get_with(
+        &self,
+        url: &str,
+        headers: reqwest::header::HeaderMap,
+    ) -> AppResult<reqwest::Response> {
         let token = self.get_token().await?;
         let resp = self
             .client
             .get(url)
+            .headers(headers.clone())
             .bearer_auth(&token)
             .send()
             .await?;
 
-        if resp.status() == reqwest::StatusCode::UNAUTHORIZED {
+        if resp.status() == reqwest::StatusCode::UNAUTHORIZED && self.can_reauthenticate() {
             // Token expired, re-authenticate
             let tp = self.authenticate().await?;
             let new_token = tp.access_token.clone();
@@ -165,6 +301,7 @@ impl RommClient {
             let resp = self
                 .client
                 .get(url)
+                .headers(headers)
                 .bearer_auth(&new_token)
                 .send()
                 .await?;
@@ -174,9 +311,42 @@ impl RommClient {
         }
     }
 
+    /// Make an authenticated form request (POST/PUT), retrying once on 401.
+    async fn auth_form(
+        &self,
+        method: reqwest::Method,
+        url: &str,
+        form: &[(&str, String)],
+    ) -> AppResult<reqwest::Response> {
+        let token = self.get_token().await?;
+        let resp = self
+            .client
+            .request(method.clone(), url)
+            .bearer_auth(&token)
+            .form(form)
+            .send()
+            .await?;
+
+        if resp.status() == reqwest::StatusCode::UNAUTHORIZED && self.can_reauthenticate() {
+            let tp = self.authenticate().await?;
+            let new_token = tp.access_token.clone();
+            *self.tokens.write().await = Some(tp);
+
+            Ok(self
+                .client
+                .request(method, url)
+                .bearer_auth(&new_token)
+                .form(form)
+                .send()
+                .await?)
+        } else {
+            Ok(resp)
+        }
+    }
+
     /// Test connection: authenticate, count platforms and ROMs.
     pub async fn test_connection(&self) -> AppResult<ConnectionTestResult> {
-        self.authenticate().await?;
+        self.check_auth().await?;
 
         let platforms = self.get_platforms().await?;
         #[allow(clippy::cast_possible_truncation)]
@@ -192,15 +362,37 @@ impl RommClient {
         })
     }
 
+    /// Check the credentials without fetching anything else: validate the
+    /// API token, or log in with the username and password.
+    pub async fn check_auth(&self) -> AppResult<()> {
+        if self.api_token.is_some() {
+            self.validate_api_token().await
+        } else {
+            self.authenticate().await.map(drop)
+        }
+    }
+
+    /// Check the API token against the account it belongs to.
+    async fn validate_api_token(&self) -> AppResult<()> {
+        let url = format!("{}/api/users/me", self.base_url);
+        let resp = self.auth_get(&url).await?;
+        match resp.status() {
+            s if s.is_success() => Ok(()),
+            reqwest::StatusCode::UNAUTHORIZED | reqwest::StatusCode::FORBIDDEN => {
+                Err(AppError::Auth(
+                    "ROMM rejected the API token; it may have expired or been revoked".to_string(),
+                ))
+            }
+            _ => Err(http::status_error("ROMM", &resp)),
+        }
+    }
+
     /// Get all platforms from ROMM.
     pub async fn get_platforms(&self) -> AppResult<Vec<RommPlatform>> {
         let url = format!("{}/api/platforms", self.base_url);
         let resp = self.auth_get(&url).await?;
         if !resp.status().is_success() {
-            return Err(AppError::Other(format!(
-                "Failed to get platforms: {}",
-                resp.status()
-            )));
+            return Err(http::status_error("ROMM", &resp));
         }
         let platforms: Vec<RommPlatform> = resp.json().await?;
         Ok(platforms)
@@ -218,10 +410,7 @@ impl RommClient {
         );
         let resp = self.auth_get(&url).await?;
         if !resp.status().is_success() {
-            return Err(AppError::Other(format!(
-                "Failed to get ROMs: {}",
-                resp.status()
-            )));
+            return Err(http::status_error("ROMM", &resp));
         }
         let page: RommPageResponse<RommRom> = resp.json().await.map_err(|e| {
             AppError::Other(format!("Failed to parse ROMs response: {e}"))
@@ -229,15 +418,35 @@ impl RommClient {
         Ok(page)
     }
 
-    /// Sync all ROMs from ROMM into local database.
+    /// Get the current user's collections, including their ROM IDs.
+    pub async fn get_collections(&self) -> AppResult<Vec<RommCollection>> {
+        let url = format!("{}/api/collections", self.base_url);
+        let resp = self.auth_get(&url).await?;
+        if !resp.status().is_success() {
+            return Err(http::status_error("ROMM", &resp));
+        }
+        let collections: Vec<RommCollection> = resp.json().await.map_err(|e| {
+            AppError::Other(format!("Failed to parse collections response: {e}"))
+        })?;
+        Ok(collections)
+    }
+
+    /// Sync all ROMs from ROMM into local database, skipping the platforms
+    /// and files the source's filters exclude.
     pub async fn sync_to_db(
         &self,
         source_id: i64,
+        settings: &SourceSettings,
         db: &DatabaseConnection,
         on_progress: impl Fn(ScanProgress) + Send,
         cancel: CancellationToken,
     ) -> AppResult<()> {
-        let platforms = self.get_platforms().await?;
+        let platforms: Vec<RommPlatform> = self
+            .get_platforms()
+            .await?
+            .into_iter()
+            .filter(|p| settings.allows_platform(&platform_registry::resolve_romm_slug(&p.slug)))
+            .collect();
         #[allow(clippy::cast_sign_loss)]
         let total_roms: u64 = platforms.iter().map(|p| p.rom_count as u64).sum();
         let mut current: u64 = 0;
@@ -285,6 +494,8 @@ impl RommClient {
                     screenscraper_id: Set(platform_registry::ss_id(&canonical_slug).map(|id| id as i64)),
                     file_extensions: Set(crate::entity::json_vec::JsonVec::default()),
                     folder_aliases: Set(crate::entity::json_vec::JsonVec::default()),
+                    archived: Set(false),
+                    archive_handling: Set(platforms::ArchiveHandling::Keep),
                     created_at: Set(chrono::Utc::now().format("%Y-%m-%dT%H:%M:%S%.3fZ").to_string()),
                     updated_at: Set(chrono::Utc::now().format("%Y-%m-%dT%H:%M:%S%.3fZ").to_string()),
                 }.insert(db).await?;
@@ -320,6 +531,9 @@ impl RommClient {
                 };
 
                 current += 1;
+                if !settings.allows_file(&rom.fs_name, rom.fs_size_bytes) {
+                    continue;
+                }
                 let rom_name = rom.name.clone().unwrap_or_else(|| rom.fs_name.clone());
                 on_progress(ScanProgress {
                     source_id,
@@ -379,6 +593,7 @@ impl RommClient {
                     [rom_id.into(), rom.summary.clone().into(), genres_json.into(), release_date.into()],
                 ))
                 .await?;
+                crate::metadata::edits::reapply(db, rom_id).await?;
 
                 // Upsert cover artwork
                 if let Some(ref cover_url) = rom.url_cover {
@@ -389,7 +604,7 @@ impl RommClient {
                     };
                     db.execute(Statement::from_sql_and_values(
                         DatabaseBackend::Sqlite,
-                        "INSERT INTO artwork (rom_id, art_type, url) VALUES (?, 'cover', ?) ON CONFLICT(rom_id, art_type, url) DO NOTHING",
+                        "INSERT INTO artwork (rom_id, art_type, url, provider) VALUES (?, 'cover', ?, 'romm') ON CONFLICT(rom_id, art_type, url) DO NOTHING",
                         [rom_id.into(), full_url.clone().into()],
                     ))
                     .await?;
@@ -403,6 +618,15 @@ impl RommClient {
             }
         }
 
+        // Phase 3: Mirror collections and favourites. Older servers or tokens
+        // without `collections.read` shouldn't fail the whole sync.
+        if cancel.is_cancelled() {
+            return Ok(());
+        }
+        if let Err(e) = self.sync_collections(source_id, db).await {
+            log::warn!("Failed to sync ROMM collections: {e}");
+        }
+
         // Update source last_synced_at
         db.execute(Statement::from_sql_and_values(
             DatabaseBackend::Sqlite,
@@ -413,6 +637,167 @@ impl RommClient {
         Ok(())
     }
 
+    /// Import ROMM collections into `collections`/`collection_roms`, replacing
+    /// the previous copy. ROMs in the ROMM favourites collection are marked as
+    /// favorites locally; local favorites are never cleared here, so use
+    /// `push_favorites` to propagate removals.
+    async fn sync_collections(&self, source_id: i64, db: &DatabaseConnection) -> AppResult<()> {
+        let collections = self.get_collections().await?;
+
+        for collection in &collections {
+            let remote_id = collection.id.to_string();
+            // ROMs in the collection before this sync, if it was already here
+            let before: Option<std::collections::HashSet<i64>> = match db
+                .query_one(Statement::from_sql_and_values(
+                    DatabaseBackend::Sqlite,
+                    "SELECT id FROM collections WHERE source_id = ? AND remote_id = ?",
+                    [source_id.into(), remote_id.clone().into()],
+                ))
+                .await?
+                .and_then(|row| row.try_get::<i64>("", "id").ok())
+            {
+                Some(id) => Some(collection_rom_ids(db, id).await?),
+                None => None,
+            };
+
+            db.execute(Statement::from_sql_and_values(
+                DatabaseBackend::Sqlite,
+                "INSERT INTO collections (name, description, source_id, remote_id, is_favorite)
+                 VALUES (?, ?, ?, ?, ?)
+                 ON CONFLICT(source_id, remote_id) DO UPDATE SET
+                   name = excluded.name,
+                   description = excluded.description,
+                   is_favorite = excluded.is_favorite,
+                   updated_at = strftime('%Y-%m-%dT%H:%M:%fZ', 'now')",
+                [
+                    collection.name.clone().into(),
+                    collection.description.clone().into(),
+                    source_id.into(),
+                    remote_id.clone().into(),
+                    collection.is_favorites().into(),
+                ],
+            ))
+            .await?;
+
+            let collection_id: i64 = db
+                .query_one(Statement::from_sql_and_values(
+                    DatabaseBackend::Sqlite,
+                    "SELECT id FROM collections WHERE source_id = ? AND remote_id = ?",
+                    [source_id.into(), remote_id.into()],
+                ))
+                .await?
+                .and_then(|row| row.try_get("", "id").ok())
+                .ok_or_else(|| AppError::Other("Collection upsert failed".to_string()))?;
+
+            db.execute(Statement::from_sql_and_values(
+                DatabaseBackend::Sqlite,
+                "DELETE FROM collection_roms WHERE collection_id = ?",
+                [collection_id.into()],
+            ))
+            .await?;
+
+            // ROMs from unidentified platforms were skipped, so they simply won't match
+            for romm_rom_id in &collection.rom_ids {
+                db.execute(Statement::from_sql_and_values(
+                    DatabaseBackend::Sqlite,
+                    "INSERT OR IGNORE INTO collection_roms (collection_id, rom_id)
+                     SELECT ?, rom_id FROM source_roms WHERE source_id = ? AND source_rom_id = ?",
+                    [collection_id.into(), source_id.into(), romm_rom_id.to_string().into()],
+                ))
+                .await?;
+            }
+
+            if let Some(before) = before {
+                let after = collection_rom_ids(db, collection_id).await?;
+                let change = crate::activity::describe_change(
+                    after.difference(&before).count(),
+                    before.difference(&after).count(),
+                );
+                if let Some(change) = change {
+                    crate::activity::collection_updated(db, collection_id, &change).await;
+                }
+            }
+
+            if collection.is_favorites() {
+                db.execute(Statement::from_sql_and_values(
+                    DatabaseBackend::Sqlite,
+                    "INSERT INTO library (profile_id, rom_id, source_id, favorite)
+                     SELECT (SELECT id FROM current_profile), rom_id, ?, 1
+                     FROM collection_roms WHERE collection_id = ?
+                     ON CONFLICT(profile_id, rom_id, source_id) DO UPDATE SET
+                       favorite = 1,
+                       updated_at = strftime('%Y-%m-%dT%H:%M:%fZ', 'now')",
+                    [source_id.into(), collection_id.into()],
+                ))
+                .await?;
+            }
+        }
+
+        // Drop collections that were deleted on the server
+        let remote_ids: std::collections::HashSet<String> =
+            collections.iter().map(|c| c.id.to_string()).collect();
+        let local = crate::entity::collections::Entity::find()
+            .filter(crate::entity::collections::Column::SourceId.eq(source_id))
+            .all(db)
+            .await?;
+        for stale in local
+            .into_iter()
+            .filter(|c| c.remote_id.as_ref().is_some_and(|r| !remote_ids.contains(r)))
+        {
+            crate::entity::collections::Entity::delete_by_id(stale.id)
+                .exec(db)
+                .await?;
+        }
+
+        Ok(())
+    }
+
+    /// Replace the contents of the user's ROMM favourites collection with
+    /// `romm_rom_ids`, creating the collection if the server has none yet.
+    pub async fn push_favorites(&self, romm_rom_ids: &[i64]) -> AppResult<()> {
+        let existing = self
+            .get_collections()
+            .await?
+            .into_iter()
+            .find(RommCollection::is_favorites);
+
+        let collection_id = if let Some(c) = existing {
+            c.id
+        } else {
+            let url = format!(
+                "{}/api/collections?is_public=false&is_favorite=true",
+                self.base_url
+            );
+            let resp = self
+                .auth_form(reqwest::Method::POST, &url, &[("name", "Favourites".to_string())])
+                .await?;
+            if !resp.status().is_success() {
+                return Err(http::status_error("ROMM", &resp));
+            }
+            let created: RommCollection = resp.json().await.map_err(|e| {
+                AppError::Other(format!("Failed to parse collection response: {e}"))
+            })?;
+            created.id
+        };
+
+        let rom_ids_json =
+            serde_json::to_string(romm_rom_ids).unwrap_or_else(|_| "[]".to_string());
+        let url = format!("{}/api/collections/{collection_id}", self.base_url);
+        let resp = self
+            .auth_form(reqwest::Method::PUT, &url, &[("rom_ids", rom_ids_json)])
+            .await?;
+        if !resp.status().is_success() {
+            let status = resp.status();
+            return Err(match status {
+                reqwest::StatusCode::FORBIDDEN => AppError::Auth(format!(
+                    "ROMM rejected the favourites update ({status})"
+                )),
+                _ => http::status_error("ROMM", &resp),
+            });
+        }
+        Ok(())
+    }
+
     /// Download a ROM file by its ROMM ID and `file_name`, returning bytes.
     pub async fn download_rom(
         &self,
@@ -426,17 +811,140 @@ impl RommClient {
         );
         let resp = self.auth_get(&url).await?;
         if !resp.status().is_success() {
-            return Err(AppError::Other(format!(
-                "Failed to download ROM: {}",
-                resp.status()
-            )));
+            return Err(http::status_error("ROMM", &resp));
+        }
+        Ok(resp)
+    }
+
+    /// Request part of a ROM file: bytes from `start` up to and including
+    /// `end`, or to the end of the file. Servers that support ranges answer
+    /// 206 Partial Content; any other success status means the whole file
+    /// is coming.
+    pub async fn download_rom_range(
+        &self,
+        romm_rom_id: i64,
+        file_name: &str,
+        start: u64,
+        end: Option<u64>,
+    ) -> AppResult<reqwest::Response> {
+        let url = format!(
+            "{}/api/roms/{romm_rom_id}/content/{}",
+            self.base_url,
+            urlencoding::encode(file_name),
+        );
+        let range = match end {
+            Some(end) => format!("bytes={start}-{end}"),
+            None => format!("bytes={start}-"),
+        };
+        let mut headers = reqwest::header::HeaderMap::new();
+        headers.insert(
+            reqwest::header::RANGE,
+            range
+                .parse()
+                .map_err(|_| AppError::Other(format!("Invalid range {range}")))?,
+        );
+        let resp = self.auth_get_with(&url, headers).await?;
+        if !resp.status().is_success() {
+            return Err(http::status_error("ROMM", &resp));
         }
         Ok(resp)
     }
 
+    /// Content URL of a ROM plus a bearer token for it. The token is a regular
+    /// OAuth access token (or the source's API token), so it stops working
+    /// when ROMM expires or revokes it.
+    pub async fn content_url(&self, romm_rom_id: i64, file_name: &str) -> AppResult<(String, String)> {
+        let url = format!(
+            "{}/api/roms/{romm_rom_id}/content/{}",
+            self.base_url,
+            urlencoding::encode(file_name),
+        );
+        Ok((url, self.get_token().await?))
+    }
+
+    /// ROMM platform ID for one of our canonical platform slugs.
+    pub async fn find_platform_id(&self, slug: &str) -> AppResult<Option<i64>> {
+        Ok(self
+            .get_platforms()
+            .await?
+            .into_iter()
+            .find(|p| platform_registry::resolve_romm_slug(&p.slug) == slug)
+            .map(|p| p.id))
+    }
+
+    /// Upload a file into a ROMM platform, streaming it from disk. ROMM stores
+    /// it in the platform's folder and indexes it on its next scan.
+    /// `on_progress` receives the number of bytes sent so far.
+    pub async fn upload_rom(
+        &self,
+        romm_platform_id: i64,
+        path: &std::path::Path,
+        file_name: &str,
+        on_progress: impl Fn(u64) + Send + Sync + 'static,
+    ) -> AppResult<()> {
+        use tokio::io::AsyncReadExt;
+
+        let file = tokio::fs::File::open(path).await?;
+        let size = file.metadata().await?.len();
+        let body = futures_util::stream::unfold(
+            (file, 0u64, on_progress),
+            |(mut file, sent, on_progress)| async move {
+                let mut buf = vec![0u8; UPLOAD_CHUNK_SIZE];
+                match file.read(&mut buf).await {
+                    Ok(0) => None,
+                    Ok(n) => {
+                        buf.truncate(n);
+                        let sent = sent + n as u64;
+                        on_progress(sent);
+                        Some((Ok(buf), (file, sent, on_progress)))
+                    }
+                    Err(e) => Some((Err(e), (file, sent, on_progress))),
+                }
+            },
+        );
+
+        // The body is a one-shot stream, so there is no retry-on-401 like `auth_get`
+        let token = self.get_token().await?;
+        let resp = self
+            .client
+            .post(format!("{}/api/roms", self.base_url))
+            .bearer_auth(&token)
+            .header("x-upload-platform", romm_platform_id.to_string())
+            .header("x-upload-filename", file_name)
+            .header(reqwest::header::CONTENT_LENGTH, size)
+            .timeout(UPLOAD_TIMEOUT)
+            .body(reqwest::Body::wrap_stream(body))
+            .send()
+            .await?;
+
+        if !resp.status().is_success() {
+            let status = resp.status();
+            let body = resp.text().await.unwrap_or_default();
+            return Err(match status {
+                reqwest::StatusCode::UNAUTHORIZED | reqwest::StatusCode::FORBIDDEN => AppError::Auth(
+                    format!("ROMM rejected the upload ({status}); the account needs upload permission"),
+                ),
+                _ => AppError::Other(format!("Failed to upload ROM ({status}): {body}")),
+            });
+        }
+        Ok(())
+    }
+
+    /// Fetch raw image bytes, authenticating for ROMM API URLs.
+    pub async fn fetch_image(&self, url: &str) -> AppResult<Vec<u8>> {
+        http::ensure_online("ROMM")?;
+        let resp = if url.contains("/api/") {
+            self.auth_get(url).await?
+        } else {
+            self.client.get(url).send().await?
+        };
+        Ok(resp.error_for_status()?.bytes().await?.to_vec())
+    }
+
     /// Proxy an image URL, returning base64-encoded data URL string.
     pub async fn proxy_image(&self, url: &str) -> AppResult<String> {
         use base64::Engine;
+        http::ensure_online("ROMM")?;
         let resp = if url.contains("/api/") {
             // Authenticated ROMM endpoint
             self.auth_get(url).await?
@@ -445,10 +953,7 @@ impl RommClient {
             self.client.get(url).send().await?
         };
         if !resp.status().is_success() {
-            return Err(AppError::Other(format!(
-                "Failed to proxy image: {}",
-                resp.status()
-            )));
+            return Err(http::status_error("ROMM", &resp));
         }
         let content_type = resp
             .headers()
@@ -475,3 +980,20 @@ impl RommClient {
         Ok(format!("data:{content_type};base64,{b64}"))
     }
 }
+
+/// ROMs in a collection.
+async fn collection_rom_ids(
+    db: &DatabaseConnection,
+    collection_id: i64,
+) -> AppResult<std::collections::HashSet<i64>> {
+    Ok(db
+        .query_all(Statement::from_sql_and_values(
+            DatabaseBackend::Sqlite,
+            "SELECT rom_id FROM collection_roms WHERE collection_id = ?",
+            [collection_id.into()],
+        ))
+        .await?
+        .into_iter()
+        .filter_map(|row| row.try_get::<i64>("", "rom_id").ok())
+        .collect())
+}
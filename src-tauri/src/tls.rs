@@ -0,0 +1,197 @@
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex, PoisonError};
+
+use reqwest::ClientBuilder;
+use rustls::client::danger::{HandshakeSignatureValid, ServerCertVerified, ServerCertVerifier};
+use rustls::crypto::{verify_tls12_signature, verify_tls13_signature, WebPkiSupportedAlgorithms};
+use rustls::pki_types::{CertificateDer, ServerName, UnixTime};
+use rustls::{DigitallySignedStruct, SignatureScheme};
+use sha2::{Digest, Sha256};
+
+use crate::error::{AppError, AppResult};
+use crate::models::ProxyService;
+
+/// How to trust a server whose certificate the system doesn't, such as a
+/// ROMM server on the LAN with a self-signed one. Read from the source's
+/// credentials JSON (`ca_cert_path`, `cert_fingerprint`).
+#[derive(Debug, Clone, Default)]
+pub struct TlsOptions {
+    /// PEM (or DER) file with extra CA certificates to trust.
+    pub ca_cert_path: Option<PathBuf>,
+    /// SHA-256 of the server's certificate, in hex. When set, that exact
+    /// certificate is accepted whoever signed it, and nothing else is.
+    pub cert_fingerprint: Option<String>,
+}
+
+impl TlsOptions {
+    pub fn from_credentials(creds: &HashMap<String, String>) -> Self {
+        let field = |key: &str| {
+            creds
+                .get(key)
+                .map(|v| v.trim().to_string())
+                .filter(|v| !v.is_empty())
+        };
+        Self {
+            ca_cert_path: field("ca_cert_path").map(PathBuf::from),
+            cert_fingerprint: field("cert_fingerprint"),
+        }
+    }
+
+    pub fn is_default(&self) -> bool {
+        self.ca_cert_path.is_none() && self.cert_fingerprint.is_none()
+    }
+
+    /// Configure `builder` to trust the server this way.
+    pub fn apply(&self, mut builder: ClientBuilder) -> AppResult<ClientBuilder> {
+        if let Some(fingerprint) = &self.cert_fingerprint {
+            let verifier = CertVerifier::pinned(parse_fingerprint(fingerprint)?);
+            return Ok(builder.use_preconfigured_tls(client_config(verifier)?));
+        }
+        if let Some(path) = &self.ca_cert_path {
+            let bytes = std::fs::read(path).map_err(|e| {
+                AppError::Other(format!("Can't read CA certificate {}: {e}", path.display()))
+            })?;
+            let certs = reqwest::Certificate::from_pem_bundle(&bytes)
+                .ok()
+                .filter(|certs| !certs.is_empty())
+                .map_or_else(
+                    || reqwest::Certificate::from_der(&bytes).map(|c| vec![c]),
+                    Ok,
+                )
+                .map_err(|e| {
+                    AppError::Other(format!("Not a certificate file: {}: {e}", path.display()))
+                })?;
+            for cert in certs {
+                builder = builder.add_root_certificate(cert);
+            }
+        }
+        Ok(builder)
+    }
+}
+
+/// Hex digits with optional `:` or space separators, as browsers and
+/// `openssl x509 -fingerprint -sha256` print them.
+fn parse_fingerprint(fingerprint: &str) -> AppResult<[u8; 32]> {
+    let hex: String = fingerprint
+        .chars()
+        .filter(|c| !matches!(c, ':' | ' '))
+        .collect();
+    let invalid =
+        || AppError::Other("The certificate fingerprint must be a SHA-256 hash".to_string());
+    if hex.len() != 64 || !hex.is_ascii() {
+        return Err(invalid());
+    }
+    let mut bytes = [0u8; 32];
+    for (i, byte) in bytes.iter_mut().enumerate() {
+        *byte = u8::from_str_radix(&hex[i * 2..i * 2 + 2], 16).map_err(|_| invalid())?;
+    }
+    Ok(bytes)
+}
+
+fn format_fingerprint(bytes: &[u8]) -> String {
+    bytes
+        .iter()
+        .map(|b| format!("{b:02X}"))
+        .collect::<Vec<_>>()
+        .join(":")
+}
+
+/// Accepts the one certificate with a pinned fingerprint, or (with no pin)
+/// any certificate, noting its fingerprint so it can be shown to the user.
+#[derive(Debug)]
+struct CertVerifier {
+    pinned: Option<[u8; 32]>,
+    seen: Mutex<Option<[u8; 32]>>,
+    algorithms: WebPkiSupportedAlgorithms,
+}
+
+impl CertVerifier {
+    fn pinned(fingerprint: [u8; 32]) -> Arc<Self> {
+        Arc::new(Self {
+            pinned: Some(fingerprint),
+            ..Self::recording()
+        })
+    }
+
+    fn recording() -> Self {
+        Self {
+            pinned: None,
+            seen: Mutex::new(None),
+            algorithms: rustls::crypto::ring::default_provider().signature_verification_algorithms,
+        }
+    }
+}
+
+impl ServerCertVerifier for CertVerifier {
+    fn verify_server_cert(
+        &self,
+        end_entity: &CertificateDer<'_>,
+        _intermediates: &[CertificateDer<'_>],
+        _server_name: &ServerName<'_>,
+        _ocsp_response: &[u8],
+        _now: UnixTime,
+    ) -> Result<ServerCertVerified, rustls::Error> {
+        let fingerprint: [u8; 32] = Sha256::digest(end_entity.as_ref()).into();
+        *self.seen.lock().unwrap_or_else(PoisonError::into_inner) = Some(fingerprint);
+        match self.pinned {
+            Some(pinned) if pinned != fingerprint => Err(rustls::Error::General(format!(
+                "server certificate fingerprint {} doesn't match the pinned one",
+                format_fingerprint(&fingerprint),
+            ))),
+            _ => Ok(ServerCertVerified::assertion()),
+        }
+    }
+
+    fn verify_tls12_signature(
+        &self,
+        message: &[u8],
+        cert: &CertificateDer<'_>,
+        dss: &DigitallySignedStruct,
+    ) -> Result<HandshakeSignatureValid, rustls::Error> {
+        verify_tls12_signature(message, cert, dss, &self.algorithms)
+    }
+
+    fn verify_tls13_signature(
+        &self,
+        message: &[u8],
+        cert: &CertificateDer<'_>,
+        dss: &DigitallySignedStruct,
+    ) -> Result<HandshakeSignatureValid, rustls::Error> {
+        verify_tls13_signature(message, cert, dss, &self.algorithms)
+    }
+
+    fn supported_verify_schemes(&self) -> Vec<SignatureScheme> {
+        self.algorithms.supported_schemes()
+    }
+}
+
+fn client_config(verifier: Arc<CertVerifier>) -> AppResult<rustls::ClientConfig> {
+    let mut config = rustls::ClientConfig::builder_with_provider(Arc::new(
+        rustls::crypto::ring::default_provider(),
+    ))
+    .with_safe_default_protocol_versions()
+    .map_err(|e| AppError::Other(format!("TLS setup failed: {e}")))?
+    .dangerous()
+    .with_custom_certificate_verifier(verifier)
+    .with_no_client_auth();
+    config.alpn_protocols = vec![b"h2".to_vec(), b"http/1.1".to_vec()];
+    Ok(config)
+}
+
+/// Connect to `url` and return the SHA-256 fingerprint of the certificate
+/// it presents, without checking who signed it, for the user to compare
+/// and pin.
+pub async fn fetch_fingerprint(url: &str) -> AppResult<String> {
+    crate::http::ensure_online(url)?;
+    let verifier = Arc::new(CertVerifier::recording());
+    let client = crate::http::client_builder_for(ProxyService::Romm)
+        .timeout(std::time::Duration::from_secs(15))
+        .use_preconfigured_tls(client_config(verifier.clone())?)
+        .build()?;
+    // Any response (even an error status) means the handshake happened
+    let _ = client.get(url).send().await?;
+    let seen = *verifier.seen.lock().unwrap_or_else(PoisonError::into_inner);
+    seen.map(|f| format_fingerprint(&f))
+        .ok_or_else(|| AppError::Other(format!("{url} didn't present a certificate")))
+}
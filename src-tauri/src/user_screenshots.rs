@@ -0,0 +1,190 @@
+use std::path::{Path, PathBuf};
+
+use sea_orm::{ConnectionTrait, DatabaseBackend, DatabaseConnection, FromQueryResult, Statement};
+
+use crate::error::{AppError, AppResult};
+use crate::saves;
+
+/// Artwork type of screenshots taken in an emulator.
+pub const USER_SCREENSHOT: &str = "user_screenshot";
+
+const IMAGE_EXTENSIONS: [&str; 4] = ["png", "jpg", "jpeg", "bmp"];
+
+/// Folders emulators write screenshots to (macOS defaults, RetroArch's from
+/// its config). Like saves, RetroArch may sort them into per-core or
+/// per-content subfolders and Dolphin into per-game ones.
+fn screenshot_dirs() -> Vec<PathBuf> {
+    let Some(home) = dirs::home_dir() else {
+        return Vec::new();
+    };
+    let app_support = home.join("Library/Application Support");
+    let retroarch = saves::retroarch_config_path()
+        .and_then(|cfg| saves::read_retroarch_cfg_value(&cfg, "screenshot_directory"))
+        .filter(|dir| dir != "default")
+        .map_or_else(
+            || app_support.join("RetroArch/screenshots"),
+            |dir| PathBuf::from(saves::expand_tilde(&dir)),
+        );
+    vec![
+        retroarch,
+        app_support.join("Dolphin/ScreenShots"),
+        app_support.join("DuckStation/screenshots"),
+        app_support.join("PCSX2/snaps"),
+        app_support.join("mGBA/screenshots"),
+        app_support.join("melonDS/screenshots"),
+        app_support.join("rpcs3/screenshots"),
+    ]
+}
+
+fn is_image(path: &Path) -> bool {
+    path.extension()
+        .and_then(|e| e.to_str())
+        .is_some_and(|e| IMAGE_EXTENSIONS.contains(&e.to_lowercase().as_str()))
+}
+
+/// Images in `dir` and one level of subfolders.
+fn list_images(dir: &Path) -> Vec<PathBuf> {
+    let mut images = Vec::new();
+    for path in std::fs::read_dir(dir)
+        .into_iter()
+        .flatten()
+        .flatten()
+        .map(|e| e.path())
+    {
+        if path.is_dir() {
+            images.extend(
+                std::fs::read_dir(&path)
+                    .into_iter()
+                    .flatten()
+                    .flatten()
+                    .map(|e| e.path())
+                    .filter(|p| p.is_file() && is_image(p)),
+            );
+        } else if path.is_file() && is_image(&path) {
+            images.push(path);
+        }
+    }
+    images
+}
+
+/// Emulators name captures after the content followed by a counter or a
+/// timestamp (`Game-230101-120000.png`, `Game-0.png`, `Game 2023-01-01 ...png`).
+/// A file named exactly like the ROM is box art, and `Game Deluxe` another game.
+fn is_capture_of(path: &Path, keys: &[String]) -> bool {
+    let Some(stem) = path.file_stem().and_then(|s| s.to_str()) else {
+        return false;
+    };
+    let stem = stem.to_lowercase();
+    keys.iter().any(|key| {
+        let mut rest = stem.strip_prefix(key.as_str()).unwrap_or_default().chars();
+        rest.next().is_some_and(|c| matches!(c, '-' | '_' | ' '))
+            && rest.next().is_some_and(|c| c.is_ascii_digit())
+    })
+}
+
+/// Import screenshots taken of a ROM in RetroArch or a standalone emulator
+/// as `user_screenshot` artwork, and drop ones whose file was deleted.
+/// Returns how many new screenshots were imported.
+pub async fn scan(
+    db: &DatabaseConnection,
+    rom_id: i64,
+    rom_path: Option<&Path>,
+) -> AppResult<usize> {
+    #[derive(Debug, FromQueryResult)]
+    struct RomRow {
+        name: String,
+        file_name: String,
+    }
+    #[derive(Debug, FromQueryResult)]
+    struct ArtRow {
+        id: i64,
+        local_path: Option<String>,
+    }
+
+    let Some(rom) = RomRow::find_by_statement(Statement::from_sql_and_values(
+        DatabaseBackend::Sqlite,
+        "SELECT name, file_name FROM roms WHERE id = ?",
+        [rom_id.into()],
+    ))
+    .one(db)
+    .await?
+    else {
+        return Ok(0);
+    };
+
+    // Matched by file name, title, and the disc ID Dolphin names captures after
+    let mut keys: Vec<String> = [
+        Path::new(&rom.file_name)
+            .file_stem()
+            .map(|s| s.to_string_lossy().into_owned()),
+        Some(rom.name),
+        rom_path.and_then(crate::hash::gamecube_game_id),
+    ]
+    .into_iter()
+    .flatten()
+    .map(|k| k.to_lowercase())
+    .filter(|k| !k.is_empty())
+    .collect();
+    keys.dedup();
+
+    let mut dirs = screenshot_dirs();
+    // mGBA and some cores write captures next to the ROM
+    if let Some(parent) = rom_path.and_then(Path::parent) {
+        dirs.push(parent.to_path_buf());
+    }
+    let found: Vec<PathBuf> = tokio::task::spawn_blocking(move || {
+        let mut found: Vec<PathBuf> = dirs
+            .iter()
+            .flat_map(|dir| list_images(dir))
+            .filter(|p| is_capture_of(p, &keys))
+            .collect();
+        found.sort();
+        found.dedup();
+        found
+    })
+    .await
+    .map_err(|e| AppError::Other(format!("Task join error: {e}")))?;
+
+    let stored = ArtRow::find_by_statement(Statement::from_sql_and_values(
+        DatabaseBackend::Sqlite,
+        "SELECT id, local_path FROM artwork WHERE rom_id = ? AND art_type = ?",
+        [rom_id.into(), USER_SCREENSHOT.into()],
+    ))
+    .all(db)
+    .await?;
+    for row in &stored {
+        if !row
+            .local_path
+            .as_deref()
+            .is_some_and(|p| Path::new(p).is_file())
+        {
+            db.execute(Statement::from_sql_and_values(
+                DatabaseBackend::Sqlite,
+                "DELETE FROM artwork WHERE id = ?",
+                [row.id.into()],
+            ))
+            .await?;
+        }
+    }
+
+    let mut imported = 0;
+    for path in found {
+        let path = path.to_string_lossy().into_owned();
+        let result = db
+            .execute(Statement::from_sql_and_values(
+                DatabaseBackend::Sqlite,
+                "INSERT INTO artwork (rom_id, art_type, url, local_path, provider)
+                 VALUES (?, ?, ?, ?, 'user')
+                 ON CONFLICT(rom_id, art_type, url) DO NOTHING",
+                [
+                    rom_id.into(),
+                    USER_SCREENSHOT.into(),
+                    format!("file://{path}").into(),
+                    path.into(),
+                ],
+            ))
+            .await?;
+        imported += usize::from(result.rows_affected() > 0);
+    }
+    Ok(imported)
+}
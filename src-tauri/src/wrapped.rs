@@ -0,0 +1,228 @@
+use std::collections::HashSet;
+
+use sea_orm::{ConnectionTrait, DatabaseBackend, DatabaseConnection, FromQueryResult, Statement};
+
+use crate::error::{AppError, AppResult};
+use crate::models::{AchievementData, WrappedGame, WrappedPlatform, YearlyWrapped};
+
+/// Games and platforms listed in the top charts.
+const TOP_COUNT: i64 = 5;
+
+/// A year's summary for the current profile, from the activity feed, the
+/// library and cached achievements. Launches are counted from the feed, so
+/// play before it existed isn't included. `ra_username` limits achievements
+/// to the configured RetroAchievements account.
+pub async fn yearly_wrapped(
+    db: &DatabaseConnection,
+    year: i32,
+    ra_username: Option<&str>,
+) -> AppResult<YearlyWrapped> {
+    if !(1970..=9999).contains(&year) {
+        return Err(AppError::Other(format!("Invalid year {year}")));
+    }
+    let from = format!("{year:04}-01-01");
+    let to = format!("{:04}-01-01", year + 1);
+    let range = || -> [sea_orm::Value; 2] { [from.clone().into(), to.clone().into()] };
+
+    let launches_in_year = "FROM activity
+         WHERE kind = 'game_launched'
+           AND profile_id = (SELECT id FROM current_profile)
+           AND created_at >= ? AND created_at < ?";
+
+    #[derive(Debug, FromQueryResult)]
+    struct Totals {
+        launches: i64,
+        games_played: i64,
+    }
+    let totals = Totals::find_by_statement(Statement::from_sql_and_values(
+        DatabaseBackend::Sqlite,
+        format!(
+            "SELECT COUNT(*) AS launches, COUNT(DISTINCT COALESCE(rom_id, title)) AS games_played
+             {launches_in_year}"
+        ),
+        range(),
+    ))
+    .one(db)
+    .await?
+    .unwrap_or(Totals {
+        launches: 0,
+        games_played: 0,
+    });
+
+    #[derive(Debug, FromQueryResult)]
+    struct GameRow {
+        rom_id: Option<i64>,
+        name: String,
+        platform_name: Option<String>,
+        launches: i64,
+        cover_url: Option<String>,
+    }
+    let mut values = range().to_vec();
+    values.push(TOP_COUNT.into());
+    let top_games = GameRow::find_by_statement(Statement::from_sql_and_values(
+        DatabaseBackend::Sqlite,
+        format!(
+            "SELECT rom_id, title AS name, MAX(detail) AS platform_name, COUNT(*) AS launches,
+                    (SELECT MIN(url) FROM artwork
+                     WHERE rom_id = activity.rom_id AND art_type = 'cover') AS cover_url
+             {launches_in_year}
+             GROUP BY COALESCE(rom_id, title)
+             ORDER BY launches DESC, MAX(id) DESC
+             LIMIT ?"
+        ),
+        values.clone(),
+    ))
+    .all(db)
+    .await?
+    .into_iter()
+    .map(|r| WrappedGame {
+        rom_id: r.rom_id,
+        name: r.name,
+        platform_name: r.platform_name,
+        launches: r.launches,
+        cover_url: r.cover_url,
+    })
+    .collect();
+
+    // Launches store the platform's name as their detail
+    #[derive(Debug, FromQueryResult)]
+    struct PlatformRow {
+        name: String,
+        launches: i64,
+        games: i64,
+    }
+    let top_platforms = PlatformRow::find_by_statement(Statement::from_sql_and_values(
+        DatabaseBackend::Sqlite,
+        format!(
+            "SELECT detail AS name, COUNT(*) AS launches,
+                    COUNT(DISTINCT COALESCE(rom_id, title)) AS games
+             {launches_in_year} AND detail IS NOT NULL
+             GROUP BY detail
+             ORDER BY launches DESC
+             LIMIT ?"
+        ),
+        values,
+    ))
+    .all(db)
+    .await?
+    .into_iter()
+    .map(|r| WrappedPlatform {
+        name: r.name,
+        launches: r.launches,
+        games: r.games,
+    })
+    .collect();
+
+    let mut launches_by_month = vec![0; 12];
+    for row in db
+        .query_all(Statement::from_sql_and_values(
+            DatabaseBackend::Sqlite,
+            format!(
+                "SELECT CAST(substr(created_at, 6, 2) AS INTEGER) AS month, COUNT(*) AS launches
+                 {launches_in_year}
+                 GROUP BY 1"
+            ),
+            range(),
+        ))
+        .await?
+    {
+        let month: i64 = row.try_get("", "month")?;
+        let launches: i64 = row.try_get("", "launches")?;
+        if let Some(slot) = usize::try_from(month - 1)
+            .ok()
+            .and_then(|i| launches_by_month.get_mut(i))
+        {
+            *slot = launches;
+        }
+    }
+
+    let count = |sql: &'static str| async move {
+        let row = db
+            .query_one(Statement::from_sql_and_values(
+                DatabaseBackend::Sqlite,
+                sql,
+                range(),
+            ))
+            .await?;
+        Ok::<i64, AppError>(row.and_then(|r| r.try_get_by_index(0).ok()).unwrap_or(0))
+    };
+    let roms_added =
+        count("SELECT COUNT(*) FROM roms WHERE created_at >= ? AND created_at < ?").await?;
+    // When the status was last touched; a later launch moves it, so this
+    // undercounts games finished early in the year and replayed since
+    let games_beaten = count(
+        "SELECT COUNT(DISTINCT rom_id) FROM profile_library
+         WHERE status = 'beaten' AND updated_at >= ? AND updated_at < ?",
+    )
+    .await?;
+    let games_completed = count(
+        "SELECT COUNT(DISTINCT rom_id) FROM profile_library
+         WHERE status = 'completed' AND updated_at >= ? AND updated_at < ?",
+    )
+    .await?;
+
+    let (achievements_earned, achievement_points) =
+        achievements_in_year(db, year, ra_username).await?;
+
+    Ok(YearlyWrapped {
+        year,
+        launches: totals.launches,
+        games_played: totals.games_played,
+        top_games,
+        top_platforms,
+        launches_by_month,
+        roms_added,
+        achievements_earned,
+        achievement_points,
+        games_beaten,
+        games_completed,
+    })
+}
+
+/// Achievements earned in `year` and their points, from the cached
+/// RetroAchievements data of every ROM. Several ROMs of one RA game count
+/// its achievements once.
+async fn achievements_in_year(
+    db: &DatabaseConnection,
+    year: i32,
+    ra_username: Option<&str>,
+) -> AppResult<(u32, u32)> {
+    #[derive(Debug, FromQueryResult)]
+    struct CacheRow {
+        ra_game_id: String,
+        data: String,
+    }
+    let rows = CacheRow::find_by_statement(Statement::from_sql_and_values(
+        DatabaseBackend::Sqlite,
+        "SELECT ra_game_id, data FROM achievements_cache WHERE ? IS NULL OR ra_username = ?",
+        [
+            ra_username.map(str::to_string).into(),
+            ra_username.map(str::to_string).into(),
+        ],
+    ))
+    .all(db)
+    .await?;
+
+    let prefix = format!("{year:04}-");
+    let mut seen = HashSet::new();
+    let (mut earned, mut points) = (0, 0);
+    for row in rows {
+        let Ok(data) = serde_json::from_str::<AchievementData>(&row.data) else {
+            continue;
+        };
+        for achievement in data.achievements {
+            let this_year = achievement
+                .earned_date
+                .as_deref()
+                .is_some_and(|d| d.starts_with(&prefix));
+            if achievement.earned
+                && this_year
+                && seen.insert((row.ra_game_id.clone(), achievement.id))
+            {
+                earned += 1;
+                points += achievement.points;
+            }
+        }
+    }
+    Ok((earned, points))
+}
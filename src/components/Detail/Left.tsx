@@ -14,7 +14,13 @@ export const LeftPanel = ({ rom }: { rom: RomWithMeta }) => {
 
   const romId = rom?.id;
   const { data: screenshotUrls } = useAsyncInvoke(
-    () => invoke<string[]>("get_rom_screenshots", { romId }),
+    async () => {
+      // Pick up screenshots taken in an emulator since the last visit
+      await invoke<number>("scan_user_screenshots", { romId }).catch((e) =>
+        console.error("Failed to scan screenshots:", e),
+      );
+      return invoke<string[]>("get_rom_screenshots", { romId });
+    },
     [romId],
     { enabled: romId != null },
   );
@@ -1,6 +1,7 @@
 import { useState, useEffect } from "react";
 import { invoke } from "@tauri-apps/api/core";
 import { toast } from "sonner";
+import { errorMessage } from "@/lib/errors";
 
 export default function ScreenshotImg({ path }: { path: string }) {
   const [src, setSrc] = useState<string | null>(null);
@@ -9,7 +10,7 @@ export default function ScreenshotImg({ path }: { path: string }) {
       .then(setSrc)
       .catch((e) => {
         console.error("Failed to load screenshot:", e);
-        toast.error(String(e));
+        toast.error(errorMessage(e));
       });
   }, [path]);
   if (!src) {
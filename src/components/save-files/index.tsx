@@ -9,6 +9,7 @@ import { romSavesAtom } from "../../store/library";
 import SaveFileRow from "./Row";
 import SaveStateRow from "./StateRow";
 import SectionHeading from "@/components/SectionHeading";
+import { errorMessage } from "@/lib/errors";
 
 export function SaveFiles({
   romId,
@@ -28,7 +29,7 @@ export function SaveFiles({
       setRomSaves((prev) => ({ ...prev, [romId]: result.length > 0 }));
     } catch (e) {
       console.error("Failed to load saves:", e);
-      toast.error(String(e));
+      toast.error(errorMessage(e));
     }
   }, [romId, setRomSaves]);
 
@@ -45,7 +46,7 @@ export function SaveFiles({
       setRomSaves((prev) => ({ ...prev, [romId]: saves.length > 1 }));
     } catch (e) {
       console.error("Delete failed:", e);
-      toast.error(String(e));
+      toast.error(errorMessage(e));
     }
   };
 
@@ -62,7 +63,7 @@ export function SaveFiles({
         });
       } catch (e) {
         console.error("Export failed:", e);
-        toast.error(String(e));
+        toast.error(errorMessage(e));
       }
     }
   };
@@ -85,7 +86,7 @@ export function SaveFiles({
         await fetchSaves();
       } catch (e) {
         console.error("Import failed:", e);
-        toast.error(String(e));
+        toast.error(errorMessage(e));
       }
     }
   };
@@ -10,6 +10,7 @@ import { toast } from "sonner";
 import CoreSelect, { type CoreSelectValue, encodeMapping, decodeMapping } from "./CoreSelect";
 import { DEFAULT_CORES } from "../../utils/defaultCores";
 import SectionHeading from "../SectionHeading";
+import { errorMessage } from "@/lib/errors";
 
 interface CoreMappingsProps {
   platforms: PlatformWithCount[];
@@ -78,7 +79,7 @@ export default function CoreMappings({
       }
       onRefresh();
     } catch (e) {
-      toast.error(String(e));
+      toast.error(errorMessage(e));
     }
   };
 
@@ -2,6 +2,7 @@ import { useState, useEffect, useCallback } from "react";
 import { invoke } from "@tauri-apps/api/core";
 import { toast } from "sonner";
 import SectionHeading from "../SectionHeading";
+import { errorMessage } from "@/lib/errors";
 
 type ConnectionStatus = "unchecked" | "ok" | "error" | "testing";
 
@@ -83,7 +84,7 @@ export default function CredentialsSection({
       await invoke(setCommand, buildParams(saveParamMapping));
       toast.success(`${title} credentials saved`);
     } catch (e) {
-      toast.error(String(e));
+      toast.error(errorMessage(e));
     }
   };
 
@@ -101,7 +102,7 @@ export default function CredentialsSection({
       }
     } catch (e) {
       setStatus("error");
-      setStatusMessage(String(e));
+      setStatusMessage(errorMessage(e));
     }
   };
 
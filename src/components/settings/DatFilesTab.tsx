@@ -12,6 +12,7 @@ import { toast } from "sonner";
 import ProgressBar from "../ProgressBar";
 import PlatformDialog from "./PlatformDialog";
 import { platformsAtom } from "../../store/platforms";
+import { errorMessage } from "@/lib/errors";
 
 export default function DatFilesTab() {
   const platforms = useAtomValue(platformsAtom);
@@ -58,7 +59,7 @@ export default function DatFilesTab() {
       toast.success("DAT file imported!");
       loadDatFiles();
     } catch (e) {
-      toast.error(String(e));
+      toast.error(errorMessage(e));
     } finally {
       setImportingDat(false);
       setDatProgress(null);
@@ -86,7 +87,7 @@ export default function DatFilesTab() {
       }
       await doImportDat(selected as string, result.detected_slug);
     } catch (e) {
-      toast.error(String(e));
+      toast.error(errorMessage(e));
     }
   };
 
@@ -104,7 +105,7 @@ export default function DatFilesTab() {
       setDatFiles((prev) => prev.filter((d) => d.id !== id));
       toast.success("DAT file removed");
     } catch (e) {
-      toast.error(String(e));
+      toast.error(errorMessage(e));
     }
   };
 
@@ -122,7 +123,7 @@ export default function DatFilesTab() {
         `Verified ${stats.verified}, Unverified ${stats.unverified}, Bad Dumps ${stats.bad_dump}`,
       );
     } catch (e) {
-      toast.error(String(e));
+      toast.error(errorMessage(e));
     } finally {
       setVerifying(false);
       setVerifyProgress(null);
@@ -4,6 +4,7 @@ import { open } from "@tauri-apps/plugin-dialog";
 import type { EmulatorDef, SavePathOverride } from "../../types";
 import { toast } from "sonner";
 import SaveDirectories from "./SaveDirectories";
+import { errorMessage } from "@/lib/errors";
 
 export default function EmulatorsTab() {
   const [emulators, setEmulators] = useState<EmulatorDef[]>([]);
@@ -70,7 +71,7 @@ export default function EmulatorsTab() {
         }));
         toast.success("Emulator path saved");
       } catch (e) {
-        toast.error(String(e));
+        toast.error(errorMessage(e));
       }
     }
   };
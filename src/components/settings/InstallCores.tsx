@@ -1,9 +1,12 @@
 import { useState } from "react";
 import { invoke } from "@tauri-apps/api/core";
-import type { CoreInfo } from "../../types";
+import { open } from "@tauri-apps/plugin-dialog";
+import type { CoreInfo, ScanProgress } from "../../types";
 import { toast } from "sonner";
 import SectionHeading from "../SectionHeading";
 import SearchInput from "../SearchInput";
+import { errorMessage } from "@/lib/errors";
+import { createProgressChannel } from "@/hooks/useAsyncOperation";
 
 interface InstallCoresProps {
   retroarchPath: string;
@@ -17,6 +20,9 @@ export default function InstallCores({
   const [availableCores, setAvailableCores] = useState<CoreInfo[]>([]);
   const [loadingAvailable, setLoadingAvailable] = useState(false);
   const [installingCore, setInstallingCore] = useState<string | null>(null);
+  const [installProgress, setInstallProgress] = useState<ScanProgress | null>(
+    null,
+  );
   const [coreSearch, setCoreSearch] = useState("");
 
   const handleLoadAvailable = async () => {
@@ -27,7 +33,7 @@ export default function InstallCores({
       });
       setAvailableCores(available);
     } catch (e) {
-      toast.error(String(e));
+      toast.error(errorMessage(e));
     } finally {
       setLoadingAvailable(false);
     }
@@ -35,8 +41,10 @@ export default function InstallCores({
 
   const handleInstallCore = async (coreName: string) => {
     setInstallingCore(coreName);
+    setInstallProgress(null);
     try {
-      await invoke("install_core", { retroarchPath, coreName });
+      const channel = createProgressChannel(setInstallProgress);
+      await invoke("install_core", { retroarchPath, coreName, channel });
       toast.success(`Installed ${coreName}`);
       const detected: CoreInfo[] = await invoke("detect_cores", {
         retroarchPath,
@@ -44,7 +52,42 @@ export default function InstallCores({
       onCoresChanged(detected);
       setAvailableCores((prev) => prev.filter((c) => c.core_name !== coreName));
     } catch (e) {
-      toast.error(String(e));
+      toast.error(errorMessage(e));
+    } finally {
+      setInstallingCore(null);
+    }
+  };
+
+  const handleInstallFromFile = async () => {
+    const selected = await open({
+      directory: false,
+      multiple: false,
+      title: "Select a core or core bundle",
+      filters: [
+        { name: "Libretro Cores", extensions: ["dylib", "so", "dll", "zip"] },
+      ],
+    });
+    if (!selected) return;
+
+    setInstallingCore(selected);
+    try {
+      const installed: CoreInfo[] = await invoke("install_core_from_file", {
+        retroarchPath,
+        path: selected,
+      });
+      toast.success(
+        installed.length === 1
+          ? `Installed ${installed[0].core_name}`
+          : `Installed ${installed.length} cores`,
+      );
+      const detected: CoreInfo[] = await invoke("detect_cores", {
+        retroarchPath,
+      });
+      onCoresChanged(detected);
+      const names = new Set(installed.map((c) => c.core_name));
+      setAvailableCores((prev) => prev.filter((c) => !names.has(c.core_name)));
+    } catch (e) {
+      toast.error(errorMessage(e));
     } finally {
       setInstallingCore(null);
     }
@@ -60,13 +103,22 @@ export default function InstallCores({
       <SectionHeading className="mb-lg">Install Cores</SectionHeading>
       <div className="card">
         {availableCores.length === 0 ? (
-          <button
-            className="btn btn-secondary"
-            onClick={handleLoadAvailable}
-            disabled={loadingAvailable}
-          >
-            {loadingAvailable ? "Loading..." : "Load Available Cores"}
-          </button>
+          <div className="flex items-center gap-md">
+            <button
+              className="btn btn-secondary"
+              onClick={handleLoadAvailable}
+              disabled={loadingAvailable}
+            >
+              {loadingAvailable ? "Loading..." : "Load Available Cores"}
+            </button>
+            <button
+              className="btn btn-secondary"
+              onClick={handleInstallFromFile}
+              disabled={installingCore !== null}
+            >
+              Install From File...
+            </button>
+          </div>
         ) : (
           <>
             <div className="flex items-center gap-lg mb-lg">
@@ -95,7 +147,9 @@ export default function InstallCores({
                     disabled={installingCore !== null}
                   >
                     {installingCore === core.core_name
-                      ? "Installing..."
+                      ? installProgress && installProgress.total > 0
+                        ? `${Math.round((installProgress.current / installProgress.total) * 100)}%`
+                        : "Installing..."
                       : "Install"}
                   </button>
                 </div>
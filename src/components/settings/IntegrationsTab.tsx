@@ -4,6 +4,7 @@ import type { ScanProgress } from "../../types";
 import { toast } from "sonner";
 import ProgressBar from "../ProgressBar";
 import CredentialsSection from "./CredentialsSection";
+import { errorMessage } from "@/lib/errors";
 
 export default function IntegrationsTab() {
   const [updatingMetadataDb, setUpdatingMetadataDb] = useState(false);
@@ -20,7 +21,7 @@ export default function IntegrationsTab() {
       await invoke("update_launchbox_db", { channel });
       toast.success("Metadata database updated!");
     } catch (e) {
-      toast.error(String(e));
+      toast.error(errorMessage(e));
     } finally {
       setUpdatingMetadataDb(false);
       setMetadataDbProgress(null);
@@ -13,6 +13,7 @@ import InstallCores from "./InstallCores";
 import { DEFAULT_CORES } from "../../utils/defaultCores";
 import { platformsAtom } from "../../store/platforms";
 import SectionHeading from "../SectionHeading";
+import { errorMessage } from "@/lib/errors";
 
 export default function RetroArchTab() {
   const [retroarchPath, setRetroarchPath] = useState("");
@@ -111,7 +112,7 @@ export default function RetroArchTab() {
       await loadMappings();
     } catch (e) {
       setPathValid(false);
-      toast.error(String(e));
+      toast.error(errorMessage(e));
     }
   };
 
@@ -2,6 +2,7 @@ import { invoke } from "@tauri-apps/api/core";
 import { open } from "@tauri-apps/plugin-dialog";
 import type { EmulatorDef, SavePathOverride } from "../../types";
 import { toast } from "sonner";
+import { errorMessage } from "@/lib/errors";
 
 interface SaveDirectoriesProps {
   emulators: EmulatorDef[];
@@ -38,7 +39,7 @@ export default function SaveDirectories({
         onSavePathsChange({ ...savePaths, [emulatorId]: updated });
         toast.success("Save directory saved");
       } catch (e) {
-        toast.error(String(e));
+        toast.error(errorMessage(e));
       }
     }
   };
@@ -55,7 +56,7 @@ export default function SaveDirectories({
       onSavePathsChange(next);
       toast.success("Reset to default save directories");
     } catch (e) {
-      toast.error(String(e));
+      toast.error(errorMessage(e));
     }
   };
 
@@ -4,22 +4,27 @@ import type { CacheInfo } from "../../types";
 import { toast } from "sonner";
 import { formatSize, formatDate } from "../../utils/format";
 import SectionHeading from "../SectionHeading";
+import { errorMessage } from "@/lib/errors";
 
 const EVICTION_OPTIONS = [3, 7, 14, 30] as const;
+const SEGMENT_OPTIONS = [1, 4, 6, 8] as const;
 
 export default function StorageTab() {
   const [cacheInfo, setCacheInfo] = useState<CacheInfo | null>(null);
   const [evictionDays, setEvictionDays] = useState(7);
+  const [downloadSegments, setDownloadSegments] = useState(4);
   const [loading, setLoading] = useState(true);
 
   const loadCacheInfo = useCallback(async () => {
     try {
-      const [info, days] = await Promise.all([
+      const [info, days, segments] = await Promise.all([
         invoke<CacheInfo>("get_cache_info"),
         invoke<number>("get_cache_eviction_days"),
+        invoke<number>("get_download_segments"),
       ]);
       setCacheInfo(info);
       setEvictionDays(days);
+      setDownloadSegments(segments);
     } catch (e) {
       console.error("Failed to load cache info:", e);
     } finally {
@@ -37,7 +42,16 @@ export default function StorageTab() {
       await invoke("set_cache_eviction_days", { days });
       toast.success(`Auto-cleanup set to ${days} days`);
     } catch (e) {
-      toast.error(String(e));
+      toast.error(errorMessage(e));
+    }
+  };
+
+  const handleSegmentsChange = async (segments: number) => {
+    setDownloadSegments(segments);
+    try {
+      await invoke("set_download_segments", { segments });
+    } catch (e) {
+      toast.error(errorMessage(e));
     }
   };
 
@@ -47,17 +61,17 @@ export default function StorageTab() {
       toast.success("Cache cleared");
       loadCacheInfo();
     } catch (e) {
-      toast.error(String(e));
+      toast.error(errorMessage(e));
     }
   };
 
-  const handleClearFile = async (fileName: string) => {
+  const handleClearFile = async (path: string) => {
     try {
-      await invoke("clear_cache_files", { fileNames: [fileName] });
+      await invoke("clear_cache_files", { fileNames: [path] });
       toast.success("File removed from cache");
       loadCacheInfo();
     } catch (e) {
-      toast.error(String(e));
+      toast.error(errorMessage(e));
     }
   };
 
@@ -93,6 +107,30 @@ export default function StorageTab() {
         </div>
       </section>
 
+      <section className="mt-3xl">
+        <SectionHeading className="mb-lg">Downloads</SectionHeading>
+        <div className="card">
+          <div className="form-group">
+            <label>Parallel connections for large ROMs</label>
+            <div className="flex gap-md">
+              {SEGMENT_OPTIONS.map((segments) => (
+                <button
+                  key={segments}
+                  className={`px-xl py-sm font-mono text-badge uppercase border ${
+                    downloadSegments === segments
+                      ? "border-accent text-accent bg-accent/10"
+                      : "border-border text-text-muted bg-bg-elevated hover:border-border-light"
+                  }`}
+                  onClick={() => handleSegmentsChange(segments)}
+                >
+                  {segments === 1 ? "Off" : segments}
+                </button>
+              ))}
+            </div>
+          </div>
+        </div>
+      </section>
+
       <section className="mt-3xl">
         <div className="flex items-center justify-between mb-lg">
           <SectionHeading>ROM Cache</SectionHeading>
@@ -126,7 +164,7 @@ export default function StorageTab() {
                 </thead>
                 <tbody>
                   {cacheInfo.files.map((file) => (
-                    <tr key={file.file_name}>
+                    <tr key={file.path}>
                       <td className="p-md px-lg text-body text-text-primary border-b border-border truncate max-w-[300px]">
                         {file.file_name}
                       </td>
@@ -141,7 +179,7 @@ export default function StorageTab() {
                       <td className="p-md px-lg text-body border-b border-border text-right">
                         <button
                           className="btn btn-danger btn-sm"
-                          onClick={() => handleClearFile(file.file_name)}
+                          onClick={() => handleClearFile(file.path)}
                         >
                           Delete
                         </button>
@@ -7,6 +7,7 @@ import { localPathAtom, localSourceAtom } from "@/store/sources";
 import { useAppSync } from "@/App";
 import SourceConnected from "./SourceConnected";
 import { useSourceSave } from "./useSourceSave";
+import { errorMessage } from "@/lib/errors";
 
 interface Props {
   onReload: () => Promise<void>;
@@ -42,7 +43,7 @@ export default function LocalSourceSection({ onReload }: Props) {
       const result: ConnectionTestResult = await invoke("test_local_path", { path });
       setTestResult(result);
     } catch (e) {
-      setTestError(String(e));
+      setTestError(errorMessage(e));
     } finally {
       setTesting(false);
     }
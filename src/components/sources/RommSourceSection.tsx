@@ -3,6 +3,9 @@ import { invoke } from "@tauri-apps/api/core";
 import type { ConnectionTestResult } from "../../types";
 import { useAtomValue } from "jotai";
 import {
+  rommApiTokenAtom,
+  rommCaCertPathAtom,
+  rommCertFingerprintAtom,
   rommNameAtom,
   rommPasswordAtom,
   rommSourceAtom,
@@ -12,6 +15,7 @@ import {
 import { useAppSync } from "@/App";
 import SourceConnected from "./SourceConnected";
 import { useSourceSave } from "./useSourceSave";
+import { errorMessage } from "@/lib/errors";
 
 interface Props {
   onReload: () => Promise<void>;
@@ -24,38 +28,70 @@ export default function RommSourceSection({ onReload }: Props) {
   const initialUrl = useAtomValue(rommUrlAtom);
   const initialUsername = useAtomValue(rommUsernameAtom);
   const initialPassword = useAtomValue(rommPasswordAtom);
+  const initialApiToken = useAtomValue(rommApiTokenAtom);
+  const initialCaCertPath = useAtomValue(rommCaCertPathAtom);
+  const initialCertFingerprint = useAtomValue(rommCertFingerprintAtom);
   const [editing, setEditing] = useState(false);
   const [name, setName] = useState(initialName);
   const [url, setUrl] = useState(initialUrl);
   const [username, setUsername] = useState(initialUsername);
   const [password, setPassword] = useState(initialPassword);
+  const [useToken, setUseToken] = useState(initialApiToken !== "");
+  const [apiToken, setApiToken] = useState(initialApiToken);
+  const [showTls, setShowTls] = useState(initialCaCertPath !== "" || initialCertFingerprint !== "");
+  const [caCertPath, setCaCertPath] = useState(initialCaCertPath);
+  const [certFingerprint, setCertFingerprint] = useState(initialCertFingerprint);
   const [testing, setTesting] = useState(false);
   const [testResult, setTestResult] = useState<ConnectionTestResult | null>(null);
   const [testError, setTestError] = useState<string | null>(null);
 
-  const isFormReady = url.trim() && username.trim() && password.trim();
+  const isFormReady = url.trim() && (useToken ? apiToken.trim() : username.trim() && password.trim());
 
   const handleTest = async () => {
     setTesting(true);
     setTestResult(null);
     setTestError(null);
     try {
-      const result: ConnectionTestResult = await invoke("test_romm_connection", { url, username, password });
+      const result: ConnectionTestResult = await invoke("test_romm_connection", {
+        url,
+        username,
+        password,
+        apiToken: useToken ? apiToken : null,
+        caCertPath: caCertPath || null,
+        certFingerprint: certFingerprint || null,
+      });
       setTestResult(result);
     } catch (e) {
-      setTestError(String(e));
+      setTestError(errorMessage(e));
     } finally {
       setTesting(false);
     }
   };
 
+  const handleFetchFingerprint = async () => {
+    setTestError(null);
+    try {
+      setCertFingerprint(await invoke<string>("get_server_certificate_fingerprint", { url }));
+    } catch (e) {
+      setTestError(errorMessage(e));
+    }
+  };
+
   const handleSave = useSourceSave({
     source,
     editing,
     sourceType: "romm",
     getName: useCallback(() => name || new URL(url).hostname, [name, url]),
     getUrl: useCallback(() => url, [url]),
-    getCredentialsJson: useCallback(() => JSON.stringify({ username, password }), [username, password]),
+    getCredentialsJson: useCallback(
+      () =>
+        JSON.stringify({
+          ...(useToken ? { api_token: apiToken } : { username, password }),
+          ...(caCertPath ? { ca_cert_path: caCertPath } : {}),
+          ...(certFingerprint ? { cert_fingerprint: certFingerprint } : {}),
+        }),
+      [useToken, apiToken, username, password, caCertPath, certFingerprint],
+    ),
     setEditing,
     onReload,
     startSync,
@@ -88,13 +124,58 @@ export default function RommSourceSection({ onReload }: Props) {
             <input type="url" placeholder="http://192.168.1.50:3000" value={url} onChange={(e) => setUrl(e.target.value)} />
           </div>
           <div className="form-group">
-            <label>Username</label>
-            <input type="text" value={username} onChange={(e) => setUsername(e.target.value)} />
-          </div>
-          <div className="form-group">
-            <label>Password</label>
-            <input type="password" value={password} onChange={(e) => setPassword(e.target.value)} />
+            <label>Sign In With</label>
+            <select value={useToken ? "token" : "password"} onChange={(e) => setUseToken(e.target.value === "token")}>
+              <option value="password">Username & password</option>
+              <option value="token">API token (no password stored)</option>
+            </select>
           </div>
+          {useToken ? (
+            <div className="form-group">
+              <label>API Token</label>
+              <input type="password" value={apiToken} onChange={(e) => setApiToken(e.target.value)} />
+            </div>
+          ) : (
+            <>
+              <div className="form-group">
+                <label>Username</label>
+                <input type="text" value={username} onChange={(e) => setUsername(e.target.value)} />
+              </div>
+              <div className="form-group">
+                <label>Password</label>
+                <input type="password" value={password} onChange={(e) => setPassword(e.target.value)} />
+              </div>
+            </>
+          )}
+          {showTls ? (
+            <>
+              <div className="form-group">
+                <label>CA Certificate (optional)</label>
+                <input
+                  type="text"
+                  placeholder="/path/to/ca.pem"
+                  value={caCertPath}
+                  onChange={(e) => setCaCertPath(e.target.value)}
+                />
+              </div>
+              <div className="form-group">
+                <label>Pinned Certificate Fingerprint (SHA-256, optional)</label>
+                <input
+                  type="text"
+                  placeholder="AB:CD:..."
+                  value={certFingerprint}
+                  onChange={(e) => setCertFingerprint(e.target.value)}
+                />
+                <button className="btn btn-secondary mt-sm" onClick={handleFetchFingerprint} disabled={!url.trim()}>
+                  Use the server's current certificate
+                </button>
+              </div>
+            </>
+          ) : (
+            <button className="btn btn-secondary mb-lg" onClick={() => setShowTls(true)}>
+              Self-signed certificate?
+            </button>
+          )}
 
           {testResult && (
             <div className="text-body p-md bg-accent-tint-10 border border-border-accent-tint rounded-none mb-lg">
@@ -5,6 +5,7 @@ import ProgressBar from "../ProgressBar";
 import { useAppSync } from "../../App";
 import { toast } from "sonner";
 import { formatDateTime } from "../../utils/format";
+import { errorMessage } from "@/lib/errors";
 
 interface SourceConnectedProps {
   source: SourceConfig;
@@ -30,7 +31,7 @@ export default function SourceConnected({ source, subtitle, onEdit, onReload }:
       toast.success("Source removed");
       await onReload();
     } catch (e) {
-      toast.error(String(e));
+      toast.error(errorMessage(e));
     }
   }, [source, onReload]);
 
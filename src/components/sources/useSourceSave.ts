@@ -2,6 +2,7 @@ import { useCallback } from "react";
 import { invoke } from "@tauri-apps/api/core";
 import { toast } from "sonner";
 import type { SourceConfig } from "../../types";
+import { errorMessage } from "@/lib/errors";
 
 interface UseSourceSaveOptions {
   source: SourceConfig | null;
@@ -55,7 +56,7 @@ export function useSourceSave({
       if (match) await startSync(match.id);
       await onReload();
     } catch (e) {
-      toast.error(String(e));
+      toast.error(errorMessage(e));
     }
   }, [source, editing, sourceType, getName, getUrl, getCredentialsJson, setEditing, onReload, startSync]);
 }
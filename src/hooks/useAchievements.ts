@@ -1,11 +1,13 @@
-import { useState, useEffect } from "react";
+import { useState, useEffect, useCallback } from "react";
 import { invoke } from "@tauri-apps/api/core";
 import type { AchievementData, RaCredentials } from "../types";
+import { errorMessage } from "@/lib/errors";
 
 export function useAchievements(romId: number | undefined) {
   const [achievements, setAchievements] = useState<AchievementData | null>(null);
   const [loading, setLoading] = useState(false);
   const [error, setError] = useState<string | null>(null);
+  const [refreshKey, setRefreshKey] = useState(0);
 
   useEffect(() => {
     if (!romId) return;
@@ -21,11 +23,14 @@ export function useAchievements(romId: number | undefined) {
           setLoading(false);
           return;
         }
-        const data = await invoke<AchievementData>("get_achievements", { romId });
+        const data = await invoke<AchievementData>("get_achievements", {
+          romId,
+          forceRefresh: refreshKey > 0,
+        });
         if (!cancelled) setAchievements(data);
       } catch (e) {
         if (!cancelled) {
-          const msg = String(e);
+          const msg = errorMessage(e);
           if (!msg.includes("No RetroAchievements game found")) {
             setError(msg);
           }
@@ -38,7 +43,9 @@ export function useAchievements(romId: number | undefined) {
     return () => {
       cancelled = true;
     };
-  }, [romId]);
+  }, [romId, refreshKey]);
 
-  return { achievements, loading, error };
+  const refresh = useCallback(() => setRefreshKey((k) => k + 1), []);
+
+  return { achievements, loading, error, refresh };
 }
@@ -1,4 +1,5 @@
 import { useState, useEffect, type DependencyList } from "react";
+import { errorMessage } from "@/lib/errors";
 
 /**
  * Wraps an async function in a useEffect with automatic cancellation.
@@ -27,7 +28,7 @@ export function useAsyncInvoke<T>(
         const result = await fn();
         if (!cancelled) setData(result);
       } catch (e) {
-        if (!cancelled) setError(String(e));
+        if (!cancelled) setError(errorMessage(e));
       } finally {
         if (!cancelled) setLoading(false);
       }
@@ -2,6 +2,7 @@ import { useState, useCallback, useRef } from "react";
 import { Channel } from "@tauri-apps/api/core";
 import { toast } from "sonner";
 import type { ScanProgress } from "../types";
+import { errorMessage, isAppError, isCancelled } from "@/lib/errors";
 
 export interface AsyncOperationState<TStartArgs extends unknown[], TCancelArgs extends unknown[] = TStartArgs> {
   running: boolean;
@@ -11,7 +12,8 @@ export interface AsyncOperationState<TStartArgs extends unknown[], TCancelArgs e
 }
 
 export function useAsyncOperation<TStartArgs extends unknown[], TCancelArgs extends unknown[] = TStartArgs>(config: {
-  run: (setProgress: (p: ScanProgress) => void, ...args: TStartArgs) => Promise<void>;
+  /** May resolve to a message that replaces `successMessage`. */
+  run: (setProgress: (p: ScanProgress) => void, ...args: TStartArgs) => Promise<string | void>;
   cancel: (...args: TCancelArgs) => Promise<void>;
   successMessage: string;
   errorPrefix: string;
@@ -22,17 +24,29 @@ export function useAsyncOperation<TStartArgs extends unknown[], TCancelArgs exte
   const runningRef = useRef(false);
 
   const start = useCallback(
-    async (...args: TStartArgs) => {
+    async (...args: TStartArgs): Promise<void> => {
       if (runningRef.current) return;
       runningRef.current = true;
       setRunning(true);
       setProgress(null);
       try {
-        await config.run(setProgress, ...args);
-        toast.success(config.successMessage);
+        const message = await config.run(setProgress, ...args);
+        toast.success(message || config.successMessage);
         config.onComplete?.();
       } catch (e) {
-        toast.error(`${config.errorPrefix}: ${e}`);
+        if (isCancelled(e)) {
+          toast.info(errorMessage(e));
+        } else if (isAppError(e) && e.category === "auth") {
+          toast.error(`${config.errorPrefix}: ${e.message}`, {
+            description: "Check the credentials in Settings.",
+          });
+        } else if (isAppError(e) && e.retryable) {
+          toast.error(`${config.errorPrefix}: ${e.message}`, {
+            action: { label: "Retry", onClick: () => void start(...args) },
+          });
+        } else {
+          toast.error(`${config.errorPrefix}: ${errorMessage(e)}`);
+        }
       } finally {
         runningRef.current = false;
         setRunning(false);
@@ -1,4 +1,4 @@
-import { useMemo } from "react";
+import { useMemo, useRef } from "react";
 import { invoke } from "@tauri-apps/api/core";
 import { useAsyncOperation, createProgressChannel } from "./useAsyncOperation";
 import type { ScanProgress } from "../types";
@@ -11,6 +11,7 @@ export interface EnrichState {
 }
 
 export function useEnrichState(onComplete?: () => void): EnrichState {
+  const platformRef = useRef<number | null>(null);
   const config = useMemo(
     () => ({
       run: async (
@@ -18,6 +19,7 @@ export function useEnrichState(onComplete?: () => void): EnrichState {
         platformId: number | null,
         search: string | null,
       ) => {
+        platformRef.current = platformId;
         const hasDb: boolean = await invoke("has_launchbox_db");
         if (!hasDb) {
           const dlChannel = createProgressChannel(setProgress);
@@ -31,7 +33,7 @@ export function useEnrichState(onComplete?: () => void): EnrichState {
         });
       },
       cancel: async () => {
-        await invoke("cancel_metadata");
+        await invoke("cancel_metadata", { platformId: platformRef.current });
       },
       successMessage: "Metadata enrichment complete!",
       errorPrefix: "Metadata enrichment failed",
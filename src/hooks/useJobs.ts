@@ -0,0 +1,33 @@
+import { useState, useEffect, useCallback } from "react";
+import { invoke } from "@tauri-apps/api/core";
+import { listen } from "@tauri-apps/api/event";
+import type { Job } from "../types";
+
+/**
+ * Every background job (syncs, enrichment, imports, exports, downloads),
+ * running ones first, kept up to date from `jobs://updated`.
+ */
+export function useJobs(): { jobs: Job[]; cancelJob: (id: number) => Promise<void> } {
+  const [jobs, setJobs] = useState<Job[]>([]);
+
+  useEffect(() => {
+    invoke<Job[]>("list_jobs").then(setJobs).catch(() => {});
+    const unlisten = listen<Job>("jobs://updated", ({ payload }) => {
+      setJobs((prev) => {
+        const rest = prev.filter((j) => j.id !== payload.id);
+        return [payload, ...rest].sort(
+          (a, b) => Number(b.state === "running") - Number(a.state === "running") || b.id - a.id,
+        );
+      });
+    });
+    return () => {
+      unlisten.then((fn) => fn());
+    };
+  }, []);
+
+  const cancelJob = useCallback(async (id: number) => {
+    await invoke("cancel_job", { jobId: id });
+  }, []);
+
+  return { jobs, cancelJob };
+}
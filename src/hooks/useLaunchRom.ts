@@ -2,27 +2,48 @@ import { useState } from "react";
 import { invoke, Channel } from "@tauri-apps/api/core";
 import type { DownloadProgress } from "../types";
 import { toast } from "sonner";
+import { errorMessage } from "@/lib/errors";
 
 export function useLaunchRom(romId: number, sourceId: number) {
   const [downloading, setDownloading] = useState(false);
   const [downloadProgress, setDownloadProgress] = useState<DownloadProgress | null>(null);
 
-  const launch = async (saveStateSlot?: number | null, saveStatePath?: string) => {
+  const launch = async (
+    saveStateSlot?: number | null,
+    saveStatePath?: string,
+    achievements?: boolean,
+  ) => {
     setDownloading(true);
     setDownloadProgress(null);
     try {
       const channel = new Channel<DownloadProgress>();
-      channel.onmessage = (progress) => setDownloadProgress(progress);
+      channel.onmessage = (progress) => {
+        // BIOS and hook warnings don't stop the launch; surface them and keep going
+        if (progress.status === "bios_missing") {
+          toast.warning(progress.error_message ?? "Missing BIOS files");
+          return;
+        }
+        if (progress.status === "hook_failed") {
+          toast.warning(progress.error_message ?? "Pre-launch hook failed");
+          return;
+        }
+        if (progress.status === "retrying") {
+          toast.warning(progress.error_message ?? "Download was corrupt, retrying");
+          return;
+        }
+        setDownloadProgress(progress);
+      };
       await invoke("download_and_launch", {
         romId,
         sourceId,
         channel,
         saveStateSlot: saveStateSlot ?? null,
         saveStatePath: saveStatePath ?? null,
+        achievements: achievements ?? null,
       });
       toast.success(saveStatePath ? "Game launched from save state!" : "Game launched!");
     } catch (e) {
-      toast.error(String(e));
+      toast.error(errorMessage(e));
     } finally {
       setDownloading(false);
       setDownloadProgress(null);
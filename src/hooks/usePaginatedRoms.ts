@@ -1,7 +1,9 @@
 import { useState, useCallback, useRef, useEffect } from "react";
 import { invoke } from "@tauri-apps/api/core";
+import { listen } from "@tauri-apps/api/event";
 import { toast } from "sonner";
-import type { RomWithMeta, LibraryPage } from "../types";
+import type { RomWithMeta, LibraryPage, LibraryRomsEvent } from "../types";
+import { errorMessage } from "@/lib/errors";
 
 const DEFAULT_PAGE_SIZE = 50;
 
@@ -44,11 +46,12 @@ export function usePaginatedRoms({
         search: search || null,
         offset: 0,
         limit: pageSize,
+        prefetchNextPage: true,
       });
       setRoms(result.roms);
       setTotal(result.total);
     } catch (e) {
-      toast.error(String(e));
+      toast.error(errorMessage(e));
     } finally {
       setLoading(false);
     }
@@ -66,11 +69,12 @@ export function usePaginatedRoms({
         search: search || null,
         offset: newOffset,
         limit: pageSize,
+        prefetchNextPage: true,
       });
       setRoms((prev) => [...prev, ...result.roms]);
       setTotal(result.total);
     } catch (e) {
-      toast.error(String(e));
+      toast.error(errorMessage(e));
       offsetRef.current = newOffset - pageSize;
     } finally {
       setLoadingMore(false);
@@ -88,5 +92,43 @@ export function usePaginatedRoms({
     }
   }, [reload, enabled]);
 
+  // Syncs and dedup merge or remove ROMs; refetch so merged entries disappear
+  useEffect(() => {
+    if (!enabled) return;
+    const unlisten = listen("library-changed", () => {
+      reload();
+    });
+    return () => {
+      unlisten.then((fn) => fn());
+    };
+  }, [reload, enabled]);
+
+  // Enrichment and dedup name the ROMs they touched; refresh just those rows
+  const romsRef = useRef(roms);
+  romsRef.current = roms;
+  useEffect(() => {
+    if (!enabled) return;
+    const refreshRows = async (event: { payload: LibraryRomsEvent }) => {
+      const { rom_ids, merged_rom_ids } = event.payload;
+      const visible = rom_ids.filter((id) => romsRef.current.some((r) => r.id === id));
+      const updated = await Promise.all(
+        visible.map((romId) => invoke<RomWithMeta>("get_rom", { romId }).catch(() => null)),
+      );
+      const byId = new Map(updated.filter((r): r is RomWithMeta => r !== null).map((r) => [r.id, r]));
+      setRoms((prev) =>
+        prev
+          .filter((r) => !merged_rom_ids.includes(r.id))
+          .map((r) => byId.get(r.id) ?? r),
+      );
+    };
+    const unlisteners = [
+      listen<LibraryRomsEvent>("library://metadata-updated", refreshRows),
+      listen<LibraryRomsEvent>("library://artwork-updated", refreshRows),
+    ];
+    return () => {
+      unlisteners.forEach((unlisten) => unlisten.then((fn) => fn()));
+    };
+  }, [enabled]);
+
   return { roms, total, loading, loadingMore, hasMore, loadMore, reload, setRoms };
 }
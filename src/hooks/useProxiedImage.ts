@@ -1,6 +1,7 @@
 import { useState, useEffect } from "react";
 import { invoke } from "@tauri-apps/api/core";
 import { toast } from "sonner";
+import { errorMessage } from "@/lib/errors";
 
 const MAX_CACHE = 200;
 const cache = new Map<string, string>();
@@ -39,7 +40,7 @@ export function useProxiedImage(url: string | null): string | null {
         if (!cancelled) setSrc(dataUrl);
       } catch (e) {
         console.error("Failed to proxy image:", e);
-        toast.error(String(e));
+        toast.error(errorMessage(e));
         if (!cancelled) setSrc(null);
       }
     })();
@@ -0,0 +1,79 @@
+import { useState, useCallback, useRef, useEffect } from "react";
+import { invoke, Channel } from "@tauri-apps/api/core";
+import { toast } from "sonner";
+import type { RomWithMeta, LibraryBatch } from "../types";
+import { errorMessage } from "@/lib/errors";
+
+interface UseStreamedRomsOptions {
+  platformId: number | null;
+  search: string | null;
+  favoritesOnly?: boolean;
+  batchSize?: number;
+  enabled?: boolean;
+}
+
+interface UseStreamedRomsResult {
+  roms: RomWithMeta[];
+  total: number;
+  streaming: boolean;
+  reload: () => Promise<void>;
+}
+
+/**
+ * Loads the whole filtered library, rendering batches as they arrive
+ * instead of waiting for the full result. Prefer usePaginatedRoms for
+ * infinite scrolling; this is for views that need every match.
+ */
+export function useStreamedRoms({
+  platformId,
+  search,
+  favoritesOnly = false,
+  batchSize,
+  enabled = true,
+}: UseStreamedRomsOptions): UseStreamedRomsResult {
+  const [roms, setRoms] = useState<RomWithMeta[]>([]);
+  const [total, setTotal] = useState(0);
+  const [streaming, setStreaming] = useState(false);
+  const runRef = useRef(0);
+
+  const reload = useCallback(async () => {
+    const run = ++runRef.current;
+    setRoms([]);
+    setTotal(0);
+    setStreaming(true);
+    try {
+      const channel = new Channel<LibraryBatch>();
+      channel.onmessage = (batch) => {
+        // Ignore batches from a stream whose filter has since changed
+        if (run !== runRef.current) return;
+        setTotal(batch.total);
+        if (batch.roms.length > 0) {
+          setRoms((prev) => [...prev, ...batch.roms]);
+        }
+      };
+      await invoke("stream_library_roms", {
+        platformId,
+        search: search || null,
+        favoritesOnly,
+        batchSize: batchSize ?? null,
+        channel,
+      });
+    } catch (e) {
+      if (run === runRef.current) toast.error(errorMessage(e));
+    } finally {
+      if (run === runRef.current) setStreaming(false);
+    }
+  }, [platformId, search, favoritesOnly, batchSize]);
+
+  useEffect(() => {
+    if (enabled) {
+      reload();
+    } else {
+      runRef.current++;
+      setRoms([]);
+      setTotal(0);
+    }
+  }, [reload, enabled]);
+
+  return { roms, total, streaming, reload };
+}
@@ -1,7 +1,7 @@
 import { useMemo } from "react";
 import { invoke } from "@tauri-apps/api/core";
 import { useAsyncOperation, createProgressChannel } from "./useAsyncOperation";
-import type { ScanProgress } from "../types";
+import type { ScanProgress, SyncSummary } from "../types";
 
 export interface SyncState {
   syncing: boolean;
@@ -15,7 +15,13 @@ export function useSyncState(onComplete?: () => void): SyncState {
     () => ({
       run: async (setProgress: (p: ScanProgress) => void, sourceId: number) => {
         const channel = createProgressChannel(setProgress);
-        await invoke("sync_source", { sourceId, channel });
+        const summary: SyncSummary = await invoke("sync_source", {
+          sourceId,
+          channel,
+        });
+        if (summary.merged_duplicates > 0) {
+          return `Sync complete! Merged ${summary.merged_duplicates} duplicate${summary.merged_duplicates === 1 ? "" : "s"}.`;
+        }
       },
       cancel: async (sourceId: number) => {
         await invoke("cancel_sync", { sourceId });
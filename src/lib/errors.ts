@@ -0,0 +1,15 @@
+import type { AppError } from "../types";
+
+export function isAppError(e: unknown): e is AppError {
+  return typeof e === "object" && e !== null && "category" in e && "message" in e;
+}
+
+export function errorMessage(e: unknown): string {
+  if (isAppError(e)) return e.message;
+  if (e instanceof Error) return e.message;
+  return String(e);
+}
+
+export function isCancelled(e: unknown): boolean {
+  return isAppError(e) && e.category === "cancelled";
+}
@@ -1,5 +1,6 @@
 import { useEffect, useCallback } from "react";
 import { useNavigate } from "react-router-dom";
+import { invoke } from "@tauri-apps/api/core";
 import { useAtom, useAtomValue } from "jotai";
 import type { RomWithMeta } from "../types";
 import RomGrid from "../components/rom/Grid";
@@ -44,6 +45,15 @@ export default function Library() {
     return () => clearTimeout(timer);
   }, [searchInput, setSearch]);
 
+  // Let the running enrichment job fetch covers for loaded ROMs first
+  useEffect(() => {
+    if (!enriching) return;
+    const romIds = roms.filter((r) => !r.cover_url).map((r) => r.id);
+    if (romIds.length > 0) {
+      invoke("prioritize_roms", { romIds }).catch(() => {});
+    }
+  }, [enriching, roms]);
+
   const handlePlatformSelect = (id: number | null) => {
     setSelectedPlatform(id);
   };
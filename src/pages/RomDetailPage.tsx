@@ -14,6 +14,7 @@ import { LeftPanel } from "@/components/detail/Left";
 import { useLaunchRom } from "../hooks/useLaunchRom";
 import { useAchievements } from "../hooks/useAchievements";
 import { useAsyncInvoke } from "../hooks/useAsyncInvoke";
+import { errorMessage } from "@/lib/errors";
 
 export default function RomDetailPage() {
   const location = useLocation();
@@ -81,7 +82,7 @@ export default function RomDetailPage() {
       setRom(updated);
       toast.success("Metadata refreshed");
     } catch (e) {
-      toast.error(String(e));
+      toast.error(errorMessage(e));
     } finally {
       setEnriching(false);
     }
@@ -7,6 +7,9 @@ export const rommNameAtom = atom("");
 export const rommUrlAtom = atom("");
 export const rommUsernameAtom = atom("");
 export const rommPasswordAtom = atom("");
+export const rommApiTokenAtom = atom("");
+export const rommCaCertPathAtom = atom("");
+export const rommCertFingerprintAtom = atom("");
 export const localSourceAtom = atom<SourceConfig | null>(null);
 export const localPathAtom = atom("");
 
@@ -25,6 +28,9 @@ export const loadSourcesAtom = atom(null, async (_get, set) => {
       const parsed = JSON.parse(creds);
       set(rommUsernameAtom, parsed.username || "");
       set(rommPasswordAtom, parsed.password || "");
+      set(rommApiTokenAtom, parsed.api_token || "");
+      set(rommCaCertPathAtom, parsed.ca_cert_path || "");
+      set(rommCertFingerprintAtom, parsed.cert_fingerprint || "");
     } else {
       set(rommSourceAtom, null);
     }
@@ -15,6 +15,8 @@ export interface RomWithMeta {
   genres: string[];
   themes: string[];
   languages: string[];
+  revision: string | null;
+  dump_flags: string[];
   cover_url: string | null;
   screenshot_urls: string[];
   source_id: number;
@@ -27,6 +29,77 @@ export interface RomWithMeta {
   favorite: boolean;
   verification_status: "verified" | "bad_dump" | null;
   dat_game_name: string | null;
+  metadata_pinned: boolean;
+  user_edited: MetadataField[];
+  esrb_rating: string | null;
+  pegi_rating: string | null;
+  note: string | null;
+  tags: string[];
+  completion_status: CompletionStatus;
+  homebrew_kind: HomebrewKind | null;
+  other_platforms: PlatformCopy[];
+}
+
+export interface PlatformCopy {
+  rom_id: number;
+  name: string;
+  platform_id: number;
+  platform_slug: string;
+  platform_name: string;
+}
+
+export type CompletionStatus = "unplayed" | "playing" | "beaten" | "completed" | "abandoned";
+
+export interface StatusCount {
+  status: CompletionStatus;
+  rom_count: number;
+}
+
+export interface Profile {
+  id: number;
+  name: string;
+  is_current: boolean;
+  created_at: string;
+  max_age: number | null;
+  allow_unrated: boolean;
+}
+
+export interface DatabaseBackup {
+  path: string;
+  size_bytes: number;
+  created_at: string;
+}
+
+export interface IntegrityReport {
+  ok: boolean;
+  problems: string[];
+}
+
+export interface TagCount {
+  tag: string;
+  rom_count: number;
+}
+
+export type SuggestionKind = "rom" | "franchise" | "genre" | "platform";
+
+export interface SearchSuggestion {
+  kind: SuggestionKind;
+  label: string;
+  id: number | null;
+  detail: string | null;
+  rom_count: number | null;
+  highlights: [number, number][];
+}
+
+export type MetadataField = "name" | "description" | "genres" | "release_date" | "rating";
+
+export interface MetadataEdit {
+  name?: string | null;
+  description?: string | null;
+  genres?: string[] | null;
+  release_date?: string | null;
+  rating?: number | null;
+  reset?: MetadataField[];
 }
 
 export interface PlatformWithCount {
@@ -34,6 +107,16 @@ export interface PlatformWithCount {
   slug: string;
   name: string;
   rom_count: number;
+  archived: boolean;
+}
+
+export interface Collection {
+  id: number;
+  name: string;
+  description: string | null;
+  source_id: number | null;
+  is_favorite: boolean;
+  rom_count: number;
 }
 
 export interface SourceConfig {
@@ -47,6 +130,76 @@ export interface SourceConfig {
   updated_at: string;
 }
 
+export interface SourceSettings {
+  include_platforms: string[];
+  exclude_platforms: string[];
+  min_file_size: number | null;
+  max_file_size: number | null;
+  include_extensions: string[];
+  exclude_extensions: string[];
+}
+
+export type FolderLayout =
+  | "esde"
+  | "batocera"
+  | "emudeck"
+  | "muos"
+  | "minui"
+  | "onionos"
+  | "unknown";
+
+export interface BrowsedRom {
+  platform_slug: string;
+  platform_name: string;
+  rom_name: string;
+  file_name: string;
+  file_path: string;
+  file_size: number | null;
+  header_title: string | null;
+  header_serial: string | null;
+  in_library: boolean;
+}
+
+export interface FolderBrowse {
+  layout: FolderLayout;
+  roms: BrowsedRom[];
+}
+
+export interface HistoryImportReport {
+  entries: number;
+  matched: number;
+  updated: number;
+}
+
+export interface DeviceExportReport {
+  copied: number;
+  skipped: number;
+  covers: number;
+  gamelists: number;
+  collections: number;
+  failed: string[];
+  bytes_written: number;
+  cancelled: boolean;
+}
+
+export interface SyncSummary {
+  source_id: number;
+  rom_count: number;
+  merged_duplicates: number;
+  cancelled: boolean;
+}
+
+export interface LibraryChanged {
+  source_id: number | null;
+  merged_duplicates: number;
+}
+
+export interface LibraryRomsEvent {
+  rom_ids: number[];
+  source_id: number | null;
+  merged_rom_ids: number[];
+}
+
 export interface ScanProgress {
   source_id: number;
   total: number;
@@ -58,11 +211,89 @@ export interface DownloadProgress {
   rom_id: number;
   total_bytes: number;
   downloaded_bytes: number;
-  status: "downloading" | "extracting" | "launching" | "done" | "error";
+  status:
+    | "downloading"
+    | "verifying"
+    | "retrying"
+    | "extracting"
+    | "running_hook"
+    | "launching"
+    | "packing"
+    | "uploading"
+    | "bios_missing"
+    | "hook_failed"
+    | "done"
+    | "error";
   error_message?: string;
 }
 
+export type BiosStatus = "ok" | "unverified" | "mismatch" | "missing";
+
+export interface BiosFileStatus {
+  platform_slug: string;
+  file_name: string;
+  description: string;
+  required: boolean;
+  group: string | null;
+  status: BiosStatus;
+  path: string | null;
+}
+
+export interface BiosReadiness {
+  platform_slug: string;
+  ready: boolean;
+  missing: string[];
+  files: BiosFileStatus[];
+}
+
+export type LaunchWarningKind =
+  | "unsupported_archive"
+  | "bios_missing"
+  | "unsupported_extension"
+  | "save_state_outdated";
+
+export interface LaunchWarning {
+  kind: LaunchWarningKind;
+  message: string;
+}
+
+export interface LaunchHook {
+  pre_launch: string | null;
+  post_exit: string | null;
+  timeout_secs: number | null;
+}
+
+export interface LaunchHooks {
+  global: LaunchHook;
+  platforms: Record<string, LaunchHook>;
+}
+
+export interface PlatformSetupStatus {
+  platform_id: number;
+  platform_slug: string;
+  core_mapped: boolean;
+  core_name: string | null;
+  emulator_type: string | null;
+  emulator_path_set: boolean;
+  bios_required: boolean;
+  bios: BiosReadiness | null;
+  dat_imported: boolean;
+  ra_supported: boolean;
+  ra_configured: boolean;
+  rom_count: number;
+  roms_with_cover: number;
+  cover_coverage: number;
+}
+
+export interface BiosHint {
+  path: string;
+  description: string;
+  optional: boolean;
+  md5: string | null;
+}
+
 export interface CachedFile {
+  path: string;
   file_name: string;
   size: number;
   last_played_at: string | null;
@@ -73,12 +304,40 @@ export interface CacheInfo {
   files: CachedFile[];
 }
 
+export interface CleanupReport {
+  orphaned_cache_files: number;
+  partial_downloads: number;
+  temp_hash_files: number;
+  orphaned_db_rows: number;
+  reclaimed_bytes: number;
+}
+
 export interface CoreInfo {
   core_name: string;
   core_path: string;
   display_name: string | null;
 }
 
+export interface CoreRecommendation {
+  core_name: string;
+  display_name: string | null;
+  installed: boolean;
+  core_path: string | null;
+}
+
+export interface PlatformCoreSetup {
+  platform_id: number;
+  platform_slug: string;
+  core_name: string;
+}
+
+export interface CoreSetupReport {
+  mapped: PlatformCoreSetup[];
+  installed: string[];
+  skipped: string[];
+  failed: string[];
+}
+
 export interface CoreMapping {
   id: number;
   platform_id: number;
@@ -92,6 +351,13 @@ export interface EmulatorDef {
   id: string;
   name: string;
   platforms: string[];
+  custom: boolean;
+}
+
+export interface EmulatorArgTemplate {
+  args: string;
+  fullscreen_flag: string | null;
+  fullscreen: boolean;
 }
 
 export interface ConnectionTestResult {
@@ -104,6 +370,37 @@ export interface LibraryPage {
   total: number;
 }
 
+export interface RomDownloadUrl {
+  url: string;
+  file_name: string;
+  authorization: string | null;
+  curl_command: string;
+}
+
+export interface LaunchGridRow {
+  id: number;
+  name: string;
+  cover_path: string | null;
+  cover_url: string | null;
+}
+
+export interface LaunchGridPage {
+  rows: LaunchGridRow[];
+  total: number;
+}
+
+export interface LibraryBatch {
+  roms: RomWithMeta[];
+  offset: number;
+  total: number;
+  done: boolean;
+}
+
+export interface LibraryPageLimits {
+  default_limit: number;
+  max_limit: number;
+}
+
 export interface AchievementData {
   game_title: string;
   num_achievements: number;
@@ -149,6 +446,31 @@ export interface VerificationStats {
   not_checked: number;
 }
 
+export interface DatFetchResult {
+  platform_slug: string;
+  status: "imported" | "unchanged" | "not_found" | "failed";
+  dat_type: string | null;
+  version: string | null;
+  message: string | null;
+}
+
+export interface HaveMissReport {
+  dat_name: string;
+  have: number;
+  miss: number;
+  have_path: string;
+  miss_path: string;
+}
+
+export interface ArcadeSetReport {
+  rom_id: number;
+  set_name: string;
+  dat_name: string;
+  missing: string[];
+  extra: string[];
+  checked_at: string;
+}
+
 export interface RomSource {
   source_id: number;
   source_name: string;
@@ -159,19 +481,352 @@ export interface RomSource {
   hash_md5: string | null;
 }
 
+export type ArtworkProvider = "igdb" | "screenscraper" | "launchbox" | "libretro";
+
+export interface ArtworkChains {
+  cover: ArtworkProvider[];
+  screenshot: ArtworkProvider[];
+}
+
+export interface EnrichmentOptions {
+  compute_hashes: boolean;
+  hasheous: boolean;
+  igdb: boolean;
+  launchbox: boolean;
+  screenscraper: boolean;
+  covers_only: boolean;
+  force_refresh: boolean;
+  translate: boolean;
+}
+
+export type TranslationService = "deepl" | "libretranslate";
+
+export interface TranslationSettings {
+  enabled: boolean;
+  service: TranslationService;
+  endpoint: string;
+  api_key: string | null;
+  target_language: string | null;
+}
+
+export interface LocalizedDescription {
+  rom_id: number;
+  language: string;
+  description: string;
+  source_language: string | null;
+  machine_translated: boolean;
+  provider: string | null;
+  created_at: string;
+}
+
+export interface RomArtwork {
+  id: number;
+  art_type: string;
+  url: string | null;
+  provider: string | null;
+  region: string | null;
+  created_at: string;
+}
+
 export type SaveType = "save_file" | "save_state";
 
+export type SaveFormat = "sram" | "memory_card" | "save_state";
+
+export interface MemoryCardSave {
+  name: string;
+  product_code: string | null;
+  title: string | null;
+  size_bytes: number;
+  modified_at: string | null;
+}
+
 export interface SaveFileInfo {
   file_name: string;
   file_path: string;
   save_type: SaveType;
+  format: SaveFormat;
+  emulator: string | null;
   size_bytes: number;
   modified_at: string;
   slot: number | null;
   screenshot_path: string | null;
+  card_saves: MemoryCardSave[];
 }
 
 export interface SavePathOverride {
   save_dir: string | null;
   state_dir: string | null;
 }
+
+export interface LaunchBoxFuzzyMatch {
+  rom_id: number;
+  rom_name: string;
+  platform_slug: string;
+  database_id: string;
+  matched_name: string;
+  confidence: number;
+  rejected: boolean;
+}
+
+export interface SharedListEntry {
+  name: string;
+  platform_slug: string;
+  platform_name: string;
+  regions: string[];
+  file_name: string;
+  crc32: string | null;
+  md5: string | null;
+  sha1: string | null;
+}
+
+export interface ListImportReport {
+  collection_id: number;
+  name: string;
+  total: number;
+  matched: number;
+  missing: SharedListEntry[];
+}
+
+export interface DiscardedCacheFile {
+  path: string;
+  reason: string;
+  size: number;
+}
+
+export interface CacheMigrationReport {
+  roms_rekeyed: number;
+  covers_rekeyed: number;
+  discarded: DiscardedCacheFile[];
+  reclaimed_bytes: number;
+}
+
+export interface Cheat {
+  index: number;
+  description: string;
+  code: string | null;
+  enabled: boolean;
+}
+
+export interface CloudBackupConfig {
+  provider: "s3";
+  endpoint: string;
+  region: string;
+  bucket: string;
+  access_key_id: string;
+  secret_access_key: string;
+  prefix: string | null;
+  path_style: boolean;
+}
+
+export interface CloudConflict {
+  key: string;
+  local_path: string;
+  local_modified_at: string | null;
+  remote_modified_at: string;
+}
+
+export interface CloudSyncReport {
+  transferred: number;
+  unchanged: number;
+  conflicts: CloudConflict[];
+}
+
+export interface RetroArchGameOverrides {
+  shader_preset: string | null;
+  aspect_ratio: string | null;
+  run_ahead_frames: number | null;
+  core_options: Record<string, string>;
+  remaps: Record<string, string>;
+  soft_patch: string | null;
+}
+
+export interface MisterConfig {
+  host: string;
+  ftp_user: string;
+  ftp_password: string;
+  smb_mount: string | null;
+  remote_port: number | null;
+}
+
+export interface ApiServerConfig {
+  enabled: boolean;
+  port: number;
+  token: string;
+}
+
+export interface OperationLogEntry {
+  id: number;
+  operation: string;
+  detail: string | null;
+  started_at: string;
+  duration_ms: number;
+  count: number;
+  success: boolean;
+  error: string | null;
+}
+
+export type ErrorCategory =
+  | "network"
+  | "auth"
+  | "not_found"
+  | "filesystem"
+  | "rate_limited"
+  | "offline"
+  | "cancelled"
+  | "database"
+  | "other";
+
+export interface AppError {
+  category: ErrorCategory;
+  message: string;
+  retryable: boolean;
+  status?: number;
+  provider?: string;
+  retry_after_secs?: number;
+}
+
+export type JobKind =
+  | "sync"
+  | "enrichment"
+  | "launch_box_import"
+  | "verification"
+  | "dat_import"
+  | "dedup"
+  | "cache_scan"
+  | "cleanup"
+  | "artwork_reselect"
+  | "device_export"
+  | "download"
+  | "conversion";
+
+export type JobState = "running" | "completed" | "failed" | "cancelled";
+
+export interface JobProgress {
+  current: number;
+  total: number;
+  item: string;
+}
+
+export interface Job {
+  id: number;
+  kind: JobKind;
+  target_id: number | null;
+  label: string;
+  state: JobState;
+  progress: JobProgress | null;
+  error: string | null;
+  started_at: string;
+  finished_at: string | null;
+}
+
+export type ProxyService = "romm" | "igdb" | "screenscraper" | "hasheous" | "launchbox";
+
+export interface ProxySettings {
+  url: string | null;
+  overrides: Partial<Record<ProxyService, string>>;
+}
+
+export interface CorruptDownload {
+  id: number;
+  rom_id: number;
+  rom_name: string;
+  file_name: string;
+  attempt: number;
+  reason: string;
+  expected_md5: string | null;
+  actual_md5: string | null;
+  expected_size: number | null;
+  actual_size: number;
+  created_at: string;
+}
+
+export type ArchiveHandling = "keep" | "extract" | "compress";
+
+export type CompressedFormat = "chd" | "rvz" | "cso";
+
+export interface CompressionReport {
+  converted: number;
+  skipped: number;
+  failed: string[];
+  original_bytes: number;
+  compressed_bytes: number;
+}
+
+export type ActivityKind =
+  | "game_launched"
+  | "achievement_unlocked"
+  | "roms_added"
+  | "collection_updated";
+
+export interface ActivityEntry {
+  id: number;
+  kind: ActivityKind;
+  rom_id: number | null;
+  collection_id: number | null;
+  source_id: number | null;
+  title: string;
+  detail: string | null;
+  count: number;
+  cover_url: string | null;
+  created_at: string;
+}
+
+export interface WrappedGame {
+  rom_id: number | null;
+  name: string;
+  platform_name: string | null;
+  launches: number;
+  cover_url: string | null;
+}
+
+export interface WrappedPlatform {
+  name: string;
+  launches: number;
+  games: number;
+}
+
+export interface YearlyWrapped {
+  year: number;
+  launches: number;
+  games_played: number;
+  top_games: WrappedGame[];
+  top_platforms: WrappedPlatform[];
+  launches_by_month: number[];
+  roms_added: number;
+  achievements_earned: number;
+  achievement_points: number;
+  games_beaten: number;
+  games_completed: number;
+}
+
+export type HomebrewKind = "homebrew" | "hack";
+
+export interface HomebrewInfo {
+  kind: HomebrewKind;
+  title: string | null;
+  author: string | null;
+  base_game: string | null;
+}
+
+export type MetadataProvider = "hasheous" | "igdb" | "libretro" | "screenscraper";
+
+export type StatusProvider =
+  | "romm"
+  | "igdb"
+  | "screenscraper"
+  | "retroachievements"
+  | "hasheous"
+  | "launchbox_images";
+
+export interface ProviderStatus {
+  provider: StatusProvider;
+  name: string;
+  source_id: number | null;
+  configured: boolean;
+  reachable: boolean;
+  authenticated: boolean | null;
+  requests_remaining: number | null;
+  requests_limit: number | null;
+  latency_ms: number | null;
+  message: string | null;
+}